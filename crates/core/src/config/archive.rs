@@ -0,0 +1,156 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Hudi timeline archival configurations.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use strum_macros::{EnumIter, IntoStaticStr};
+
+use crate::config::Result;
+use crate::config::error::ConfigError::ParseInt;
+use crate::config::{ConfigParser, HudiConfigValue};
+
+/// Configurations for archiving instants from the active timeline.
+///
+/// Archival is triggered when the number of completed commits on the active timeline
+/// exceeds [HudiArchiveConfig::MaxCommitsToKeep], and it keeps the latest
+/// [HudiArchiveConfig::MinCommitsToKeep] commits active.
+///
+/// **Example**
+///
+/// ```rust
+/// use hudi_core::config::archive::HudiArchiveConfig::{MaxCommitsToKeep, MinCommitsToKeep};
+/// use hudi_core::table::Table as HudiTable;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = [(MinCommitsToKeep, "5"), (MaxCommitsToKeep, "8")];
+/// HudiTable::new_with_options("/tmp/hudi_data", options).await;
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum HudiArchiveConfig {
+    /// Minimum number of latest completed commits to keep on the active timeline.
+    MinCommitsToKeep,
+
+    /// Maximum number of completed commits on the active timeline before archival kicks in.
+    MaxCommitsToKeep,
+
+    /// Number of files in a level of the LSM timeline history to be compacted into
+    /// a single file of the next level. Only applicable to timeline layout version 2.
+    TimelineCompactionBatchSize,
+}
+
+impl AsRef<str> for HudiArchiveConfig {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::MinCommitsToKeep => "hoodie.keep.min.commits",
+            Self::MaxCommitsToKeep => "hoodie.keep.max.commits",
+            Self::TimelineCompactionBatchSize => "hoodie.timeline.compaction.batch.size",
+        }
+    }
+}
+
+impl Display for HudiArchiveConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl ConfigParser for HudiArchiveConfig {
+    type Output = HudiConfigValue;
+
+    fn default_value(&self) -> Option<HudiConfigValue> {
+        match self {
+            Self::MinCommitsToKeep => Some(HudiConfigValue::UInteger(20usize)),
+            Self::MaxCommitsToKeep => Some(HudiConfigValue::UInteger(30usize)),
+            Self::TimelineCompactionBatchSize => Some(HudiConfigValue::UInteger(10usize)),
+        }
+    }
+
+    fn parse_value(&self, configs: &HashMap<String, String>) -> Result<Self::Output> {
+        let get_result = self.resolve_raw_value(configs);
+
+        match self {
+            Self::MinCommitsToKeep | Self::MaxCommitsToKeep | Self::TimelineCompactionBatchSize => {
+                get_result
+                    .and_then(|v| {
+                        usize::from_str(v).map_err(|e| ParseInt(self.key(), v.to_string(), e))
+                    })
+                    .map(HudiConfigValue::UInteger)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::archive::HudiArchiveConfig::{
+        MaxCommitsToKeep, MinCommitsToKeep, TimelineCompactionBatchSize,
+    };
+
+    #[test]
+    fn parse_valid_config_value() {
+        let options = HashMap::from([
+            (MinCommitsToKeep.as_ref().to_string(), "2".to_string()),
+            (MaxCommitsToKeep.as_ref().to_string(), "4".to_string()),
+            (
+                TimelineCompactionBatchSize.as_ref().to_string(),
+                "3".to_string(),
+            ),
+        ]);
+        let actual: usize = MinCommitsToKeep.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 2);
+        let actual: usize = MaxCommitsToKeep.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 4);
+        let actual: usize = TimelineCompactionBatchSize
+            .parse_value(&options)
+            .unwrap()
+            .into();
+        assert_eq!(actual, 3);
+    }
+
+    #[test]
+    fn parse_invalid_config_value() {
+        let options = HashMap::from([(MaxCommitsToKeep.as_ref().to_string(), "x".to_string())]);
+        assert!(matches!(
+            MaxCommitsToKeep.parse_value(&options).unwrap_err(),
+            ParseInt(_, _, _)
+        ));
+        let actual: usize = MaxCommitsToKeep.parse_value_or_default(&options).into();
+        assert_eq!(actual, 30);
+    }
+
+    #[test]
+    fn default_values() {
+        let empty = HashMap::new();
+        let actual: usize = MinCommitsToKeep.parse_value_or_default(&empty).into();
+        assert_eq!(actual, 20);
+        let actual: usize = MaxCommitsToKeep.parse_value_or_default(&empty).into();
+        assert_eq!(actual, 30);
+        let actual: usize = TimelineCompactionBatchSize
+            .parse_value_or_default(&empty)
+            .into();
+        assert_eq!(actual, 10);
+    }
+}
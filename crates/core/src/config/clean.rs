@@ -0,0 +1,242 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Hudi clean configurations.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use strum_macros::{AsRefStr, EnumIter, IntoStaticStr};
+
+use crate::config::Result;
+use crate::config::error::ConfigError;
+use crate::config::error::ConfigError::{InvalidValue, ParseInt};
+use crate::config::{ConfigAlias, ConfigParser, HudiConfigValue};
+
+/// Configurations for cleaning obsolete file slices from Hudi tables.
+///
+/// **Example**
+///
+/// ```rust
+/// use hudi_core::config::clean::HudiCleanConfig::{CleanPolicy, CommitsRetained};
+/// use hudi_core::table::Table as HudiTable;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = [(CleanPolicy, "KEEP_LATEST_COMMITS"), (CommitsRetained, "5")];
+/// HudiTable::new_with_options("/tmp/hudi_data", options).await;
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum HudiCleanConfig {
+    /// The policy to decide which file slices are no longer needed.
+    ///
+    /// See [CleanPolicyValue] for the supported values.
+    CleanPolicy,
+
+    /// Number of latest commits whose file slices are retained,
+    /// used by [CleanPolicyValue::KeepLatestCommits].
+    CommitsRetained,
+
+    /// Number of latest file slices retained per file group,
+    /// used by [CleanPolicyValue::KeepLatestFileVersions].
+    FileVersionsRetained,
+
+    /// Number of hours whose commits' file slices are retained,
+    /// used by [CleanPolicyValue::KeepLatestByHours].
+    HoursRetained,
+}
+
+impl AsRef<str> for HudiCleanConfig {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::CleanPolicy => "hoodie.clean.policy",
+            Self::CommitsRetained => "hoodie.clean.commits.retained",
+            Self::FileVersionsRetained => "hoodie.clean.fileversions.retained",
+            Self::HoursRetained => "hoodie.clean.hours.retained",
+        }
+    }
+}
+
+impl Display for HudiCleanConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl ConfigParser for HudiCleanConfig {
+    type Output = HudiConfigValue;
+
+    fn default_value(&self) -> Option<HudiConfigValue> {
+        match self {
+            Self::CleanPolicy => Some(HudiConfigValue::String(
+                CleanPolicyValue::KeepLatestCommits.as_ref().to_string(),
+            )),
+            Self::CommitsRetained => Some(HudiConfigValue::UInteger(10usize)),
+            Self::FileVersionsRetained => Some(HudiConfigValue::UInteger(3usize)),
+            Self::HoursRetained => Some(HudiConfigValue::UInteger(24usize)),
+        }
+    }
+
+    fn aliases(&self) -> &[ConfigAlias] {
+        match self {
+            Self::CleanPolicy => {
+                const ALIASES: &[ConfigAlias] = &[ConfigAlias::deprecated("hoodie.cleaner.policy")];
+                ALIASES
+            }
+            Self::CommitsRetained => {
+                const ALIASES: &[ConfigAlias] =
+                    &[ConfigAlias::deprecated("hoodie.cleaner.commits.retained")];
+                ALIASES
+            }
+            Self::FileVersionsRetained => {
+                const ALIASES: &[ConfigAlias] = &[ConfigAlias::deprecated(
+                    "hoodie.cleaner.fileversions.retained",
+                )];
+                ALIASES
+            }
+            Self::HoursRetained => {
+                const ALIASES: &[ConfigAlias] =
+                    &[ConfigAlias::deprecated("hoodie.cleaner.hours.retained")];
+                ALIASES
+            }
+        }
+    }
+
+    fn parse_value(&self, configs: &HashMap<String, String>) -> Result<Self::Output> {
+        let get_result = self.resolve_raw_value(configs);
+
+        match self {
+            Self::CleanPolicy => get_result
+                .and_then(CleanPolicyValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::CommitsRetained | Self::FileVersionsRetained | Self::HoursRetained => get_result
+                .and_then(|v| {
+                    usize::from_str(v).map_err(|e| ParseInt(self.key(), v.to_string(), e))
+                })
+                .map(HudiConfigValue::UInteger),
+        }
+    }
+}
+
+/// Config value for [HudiCleanConfig::CleanPolicy].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum CleanPolicyValue {
+    /// Retain file slices needed by the latest N commits, plus the latest slice of each file group.
+    #[strum(serialize = "KEEP_LATEST_COMMITS")]
+    KeepLatestCommits,
+    /// Retain the latest N file slices of each file group.
+    #[strum(serialize = "KEEP_LATEST_FILE_VERSIONS")]
+    KeepLatestFileVersions,
+    /// Retain file slices needed by the commits made within the latest N hours.
+    #[strum(serialize = "KEEP_LATEST_BY_HOURS")]
+    KeepLatestByHours,
+}
+
+impl FromStr for CleanPolicyValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "KEEP_LATEST_COMMITS" => Ok(Self::KeepLatestCommits),
+            "KEEP_LATEST_FILE_VERSIONS" => Ok(Self::KeepLatestFileVersions),
+            "KEEP_LATEST_BY_HOURS" => Ok(Self::KeepLatestByHours),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::clean::HudiCleanConfig::{
+        CleanPolicy, CommitsRetained, FileVersionsRetained, HoursRetained,
+    };
+
+    #[test]
+    fn parse_valid_config_value() {
+        let options = HashMap::from([
+            (
+                CleanPolicy.as_ref().to_string(),
+                "keep_latest_file_versions".to_string(),
+            ),
+            (CommitsRetained.as_ref().to_string(), "5".to_string()),
+            (FileVersionsRetained.as_ref().to_string(), "2".to_string()),
+            (HoursRetained.as_ref().to_string(), "48".to_string()),
+        ]);
+        let policy: String = CleanPolicy.parse_value(&options).unwrap().into();
+        assert_eq!(policy, "KEEP_LATEST_FILE_VERSIONS");
+        let actual: usize = CommitsRetained.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 5);
+        let actual: usize = FileVersionsRetained.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 2);
+        let actual: usize = HoursRetained.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 48);
+    }
+
+    #[test]
+    fn parse_deprecated_alias() {
+        let options = HashMap::from([
+            (
+                "hoodie.cleaner.policy".to_string(),
+                "KEEP_LATEST_BY_HOURS".to_string(),
+            ),
+            (
+                "hoodie.cleaner.commits.retained".to_string(),
+                "7".to_string(),
+            ),
+        ]);
+        let policy: String = CleanPolicy.parse_value(&options).unwrap().into();
+        assert_eq!(policy, "KEEP_LATEST_BY_HOURS");
+        let actual: usize = CommitsRetained.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 7);
+    }
+
+    #[test]
+    fn parse_invalid_config_value() {
+        let options = HashMap::from([
+            (CleanPolicy.as_ref().to_string(), "KEEP_ALL".to_string()),
+            (CommitsRetained.as_ref().to_string(), "-1".to_string()),
+        ]);
+        assert!(matches!(
+            CleanPolicy.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(matches!(
+            CommitsRetained.parse_value(&options).unwrap_err(),
+            ParseInt(_, _, _)
+        ));
+        let actual: usize = CommitsRetained.parse_value_or_default(&options).into();
+        assert_eq!(actual, 10);
+    }
+
+    #[test]
+    fn default_values() {
+        let empty = HashMap::new();
+        let policy: String = CleanPolicy.parse_value_or_default(&empty).into();
+        assert_eq!(policy, "KEEP_LATEST_COMMITS");
+        let actual: usize = CommitsRetained.parse_value_or_default(&empty).into();
+        assert_eq!(actual, 10);
+        let actual: usize = FileVersionsRetained.parse_value_or_default(&empty).into();
+        assert_eq!(actual, 3);
+        let actual: usize = HoursRetained.parse_value_or_default(&empty).into();
+        assert_eq!(actual, 24);
+    }
+}
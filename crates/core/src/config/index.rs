@@ -0,0 +1,253 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Hudi index configurations.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use strum_macros::{AsRefStr, EnumIter, IntoStaticStr};
+
+use crate::config::Result;
+use crate::config::error::ConfigError;
+use crate::config::error::ConfigError::{InvalidValue, ParseInt};
+use crate::config::{ConfigParser, HudiConfigValue};
+
+/// Configurations for the index that maps records to file groups.
+///
+/// **Example**
+///
+/// ```rust
+/// use hudi_core::config::index::HudiIndexConfig::{BucketIndexNumBuckets, IndexType};
+/// use hudi_core::table::Table as HudiTable;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = [(IndexType, "BUCKET"), (BucketIndexNumBuckets, "16")];
+/// HudiTable::new_with_options("/tmp/hudi_data", options).await;
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum HudiIndexConfig {
+    /// The type of the index.
+    ///
+    /// See [IndexTypeValue] for the supported values.
+    IndexType,
+
+    /// The engine of [IndexTypeValue::Bucket].
+    ///
+    /// See [BucketIndexEngineValue] for the supported values.
+    BucketIndexEngine,
+
+    /// Number of buckets, i.e., file groups, per partition for [IndexTypeValue::Bucket].
+    BucketIndexNumBuckets,
+
+    /// Fields whose values are hashed into buckets for [IndexTypeValue::Bucket].
+    ///
+    /// Defaults to the record key fields. Must be a subset of the record key fields.
+    BucketIndexHashField,
+}
+
+impl AsRef<str> for HudiIndexConfig {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::IndexType => "hoodie.index.type",
+            Self::BucketIndexEngine => "hoodie.index.bucket.engine",
+            Self::BucketIndexNumBuckets => "hoodie.bucket.index.num.buckets",
+            Self::BucketIndexHashField => "hoodie.bucket.index.hash.field",
+        }
+    }
+}
+
+impl Display for HudiIndexConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl ConfigParser for HudiIndexConfig {
+    type Output = HudiConfigValue;
+
+    fn default_value(&self) -> Option<HudiConfigValue> {
+        match self {
+            Self::IndexType => Some(HudiConfigValue::String(
+                IndexTypeValue::Simple.as_ref().to_string(),
+            )),
+            Self::BucketIndexEngine => Some(HudiConfigValue::String(
+                BucketIndexEngineValue::Simple.as_ref().to_string(),
+            )),
+            Self::BucketIndexNumBuckets => Some(HudiConfigValue::UInteger(256usize)),
+            Self::BucketIndexHashField => None,
+        }
+    }
+
+    fn parse_value(&self, configs: &HashMap<String, String>) -> Result<Self::Output> {
+        let get_result = self.resolve_raw_value(configs);
+
+        match self {
+            Self::IndexType => get_result
+                .and_then(IndexTypeValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::BucketIndexEngine => get_result
+                .and_then(BucketIndexEngineValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::BucketIndexNumBuckets => get_result
+                .and_then(|v| {
+                    usize::from_str(v).map_err(|e| ParseInt(self.key(), v.to_string(), e))
+                })
+                .and_then(|n| {
+                    if n == 0 {
+                        Err(InvalidValue(format!("{} must be positive", self.as_ref())))
+                    } else {
+                        Ok(HudiConfigValue::UInteger(n))
+                    }
+                }),
+            Self::BucketIndexHashField => get_result.map(|v| {
+                HudiConfigValue::List(v.split(',').map(|s| s.trim().to_string()).collect())
+            }),
+        }
+    }
+}
+
+/// Config value for [HudiIndexConfig::IndexType].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum IndexTypeValue {
+    #[strum(serialize = "BLOOM")]
+    Bloom,
+    #[strum(serialize = "GLOBAL_BLOOM")]
+    GlobalBloom,
+    #[strum(serialize = "SIMPLE")]
+    Simple,
+    #[strum(serialize = "GLOBAL_SIMPLE")]
+    GlobalSimple,
+    /// Records are hashed by their keys into a fixed number of file groups per partition.
+    #[strum(serialize = "BUCKET")]
+    Bucket,
+    #[strum(serialize = "RECORD_INDEX")]
+    RecordIndex,
+    #[strum(serialize = "INMEMORY")]
+    InMemory,
+    #[strum(serialize = "HBASE")]
+    HBase,
+    #[strum(serialize = "FLINK_STATE")]
+    FlinkState,
+}
+
+impl FromStr for IndexTypeValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "BLOOM" => Ok(Self::Bloom),
+            "GLOBAL_BLOOM" => Ok(Self::GlobalBloom),
+            "SIMPLE" => Ok(Self::Simple),
+            "GLOBAL_SIMPLE" => Ok(Self::GlobalSimple),
+            "BUCKET" => Ok(Self::Bucket),
+            "RECORD_INDEX" => Ok(Self::RecordIndex),
+            "INMEMORY" => Ok(Self::InMemory),
+            "HBASE" => Ok(Self::HBase),
+            "FLINK_STATE" => Ok(Self::FlinkState),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+/// Config value for [HudiIndexConfig::BucketIndexEngine].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum BucketIndexEngineValue {
+    /// The number of buckets is fixed.
+    #[strum(serialize = "SIMPLE")]
+    Simple,
+    /// Buckets are resized on a consistent hashing ring.
+    #[strum(serialize = "CONSISTENT_HASHING")]
+    ConsistentHashing,
+}
+
+impl FromStr for BucketIndexEngineValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "SIMPLE" => Ok(Self::Simple),
+            "CONSISTENT_HASHING" => Ok(Self::ConsistentHashing),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::index::HudiIndexConfig::{
+        BucketIndexEngine, BucketIndexHashField, BucketIndexNumBuckets, IndexType,
+    };
+
+    #[test]
+    fn parse_valid_config_value() {
+        let options = HashMap::from([
+            (IndexType.as_ref().to_string(), "bucket".to_string()),
+            (
+                BucketIndexEngine.as_ref().to_string(),
+                "consistent_hashing".to_string(),
+            ),
+            (BucketIndexNumBuckets.as_ref().to_string(), "8".to_string()),
+            (
+                BucketIndexHashField.as_ref().to_string(),
+                "id, name".to_string(),
+            ),
+        ]);
+        let index_type: String = IndexType.parse_value(&options).unwrap().into();
+        assert_eq!(
+            IndexTypeValue::from_str(&index_type).unwrap(),
+            IndexTypeValue::Bucket
+        );
+        let engine: String = BucketIndexEngine.parse_value(&options).unwrap().into();
+        assert_eq!(engine, "CONSISTENT_HASHING");
+        let num_buckets: usize = BucketIndexNumBuckets.parse_value(&options).unwrap().into();
+        assert_eq!(num_buckets, 8);
+        let hash_fields: Vec<String> = BucketIndexHashField.parse_value(&options).unwrap().into();
+        assert_eq!(hash_fields, vec!["id", "name"]);
+    }
+
+    #[test]
+    fn parse_invalid_config_value() {
+        let options = HashMap::from([
+            (IndexType.as_ref().to_string(), "foo".to_string()),
+            (BucketIndexEngine.as_ref().to_string(), "bar".to_string()),
+            (BucketIndexNumBuckets.as_ref().to_string(), "0".to_string()),
+        ]);
+        assert!(matches!(
+            IndexType.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(matches!(
+            BucketIndexEngine.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(matches!(
+            BucketIndexNumBuckets.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        let actual: usize = BucketIndexNumBuckets
+            .parse_value_or_default(&HashMap::new())
+            .into();
+        assert_eq!(actual, 256);
+    }
+}
@@ -0,0 +1,244 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Hudi write concurrency and lock configurations.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use strum_macros::{AsRefStr, EnumIter, IntoStaticStr};
+
+use crate::config::Result;
+use crate::config::error::ConfigError;
+use crate::config::error::ConfigError::{InvalidValue, ParseInt};
+use crate::config::{ConfigParser, HudiConfigValue};
+
+/// Configurations for concurrent writers and the locks guarding their commits.
+///
+/// **Example**
+///
+/// ```rust
+/// use hudi_core::config::lock::HudiLockConfig::{LockProvider, WriteConcurrencyMode};
+/// use hudi_core::table::Table as HudiTable;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = [
+///     (WriteConcurrencyMode, "OPTIMISTIC_CONCURRENCY_CONTROL"),
+///     (LockProvider, "org.apache.hudi.client.transaction.lock.FileSystemBasedLockProvider"),
+/// ];
+/// HudiTable::new_with_options("/tmp/hudi_data", options).await;
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum HudiLockConfig {
+    /// How concurrent writers to the table are coordinated.
+    ///
+    /// See [WriteConcurrencyModeValue] for the supported values.
+    WriteConcurrencyMode,
+
+    /// The lock provider used by [WriteConcurrencyModeValue::OptimisticConcurrencyControl].
+    ///
+    /// See [LockProviderValue] for the supported values.
+    LockProvider,
+
+    /// Maximum time in milliseconds to wait for the lock in each attempt.
+    LockAcquireWaitTimeoutMs,
+
+    /// Number of retries to acquire the lock after the first attempt times out.
+    LockAcquireNumRetries,
+
+    /// Time in milliseconds to wait between the attempts to acquire the lock.
+    LockAcquireRetryWaitTimeMs,
+
+    /// Number of minutes after which a lock held by [LockProviderValue::FileSystem] is
+    /// considered expired and can be taken over. `0` means the lock never expires.
+    FileSystemLockExpireMinutes,
+}
+
+impl AsRef<str> for HudiLockConfig {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::WriteConcurrencyMode => "hoodie.write.concurrency.mode",
+            Self::LockProvider => "hoodie.write.lock.provider",
+            Self::LockAcquireWaitTimeoutMs => "hoodie.write.lock.wait_time_ms",
+            Self::LockAcquireNumRetries => "hoodie.write.lock.num_retries",
+            Self::LockAcquireRetryWaitTimeMs => "hoodie.write.lock.wait_time_ms_between_retry",
+            Self::FileSystemLockExpireMinutes => "hoodie.write.lock.filesystem.expire",
+        }
+    }
+}
+
+impl Display for HudiLockConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl ConfigParser for HudiLockConfig {
+    type Output = HudiConfigValue;
+
+    fn default_value(&self) -> Option<HudiConfigValue> {
+        match self {
+            Self::WriteConcurrencyMode => Some(HudiConfigValue::String(
+                WriteConcurrencyModeValue::SingleWriter.as_ref().to_string(),
+            )),
+            Self::LockProvider => Some(HudiConfigValue::String(
+                LockProviderValue::FileSystem.as_ref().to_string(),
+            )),
+            Self::LockAcquireWaitTimeoutMs => Some(HudiConfigValue::UInteger(60_000usize)),
+            Self::LockAcquireNumRetries => Some(HudiConfigValue::UInteger(15usize)),
+            Self::LockAcquireRetryWaitTimeMs => Some(HudiConfigValue::UInteger(1_000usize)),
+            Self::FileSystemLockExpireMinutes => Some(HudiConfigValue::UInteger(0usize)),
+        }
+    }
+
+    fn parse_value(&self, configs: &HashMap<String, String>) -> Result<Self::Output> {
+        let get_result = self.resolve_raw_value(configs);
+
+        match self {
+            Self::WriteConcurrencyMode => get_result
+                .and_then(WriteConcurrencyModeValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::LockProvider => get_result
+                .and_then(LockProviderValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::LockAcquireWaitTimeoutMs
+            | Self::LockAcquireNumRetries
+            | Self::LockAcquireRetryWaitTimeMs
+            | Self::FileSystemLockExpireMinutes => get_result
+                .and_then(|v| {
+                    usize::from_str(v).map_err(|e| ParseInt(self.key(), v.to_string(), e))
+                })
+                .map(HudiConfigValue::UInteger),
+        }
+    }
+}
+
+/// Config value for [HudiLockConfig::WriteConcurrencyMode].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum WriteConcurrencyModeValue {
+    /// Only one writer commits to the table at a time; commits are not guarded by a lock.
+    #[strum(serialize = "SINGLE_WRITER")]
+    SingleWriter,
+    /// Commits are guarded by a lock and aborted if they conflict with the commits
+    /// completed after the writer started.
+    #[strum(serialize = "OPTIMISTIC_CONCURRENCY_CONTROL")]
+    OptimisticConcurrencyControl,
+}
+
+impl FromStr for WriteConcurrencyModeValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "SINGLE_WRITER" => Ok(Self::SingleWriter),
+            "OPTIMISTIC_CONCURRENCY_CONTROL" => Ok(Self::OptimisticConcurrencyControl),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+/// Config value for [HudiLockConfig::LockProvider], named after the lock provider classes
+/// of Hudi Java.
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum LockProviderValue {
+    /// A lock file under `.hoodie/.aux`, shared by writers across processes.
+    #[strum(serialize = "org.apache.hudi.client.transaction.lock.FileSystemBasedLockProvider")]
+    FileSystem,
+    /// An in-memory lock, shared by writers within the same process.
+    #[strum(serialize = "org.apache.hudi.client.transaction.lock.InProcessLockProvider")]
+    InProcess,
+}
+
+impl FromStr for LockProviderValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "org.apache.hudi.client.transaction.lock.FileSystemBasedLockProvider" => {
+                Ok(Self::FileSystem)
+            }
+            "org.apache.hudi.client.transaction.lock.InProcessLockProvider" => Ok(Self::InProcess),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::lock::HudiLockConfig::{
+        FileSystemLockExpireMinutes, LockAcquireNumRetries, LockProvider, WriteConcurrencyMode,
+    };
+
+    #[test]
+    fn parse_valid_config_value() {
+        let options = HashMap::from([
+            (
+                WriteConcurrencyMode.as_ref().to_string(),
+                "optimistic_concurrency_control".to_string(),
+            ),
+            (
+                LockProvider.as_ref().to_string(),
+                "org.apache.hudi.client.transaction.lock.InProcessLockProvider".to_string(),
+            ),
+            (LockAcquireNumRetries.as_ref().to_string(), "3".to_string()),
+        ]);
+        let mode: String = WriteConcurrencyMode.parse_value(&options).unwrap().into();
+        assert_eq!(mode, "OPTIMISTIC_CONCURRENCY_CONTROL");
+        let provider: String = LockProvider.parse_value(&options).unwrap().into();
+        assert_eq!(
+            LockProviderValue::from_str(&provider).unwrap(),
+            LockProviderValue::InProcess
+        );
+        let actual: usize = LockAcquireNumRetries.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 3);
+    }
+
+    #[test]
+    fn parse_invalid_config_value() {
+        let options = HashMap::from([
+            (WriteConcurrencyMode.as_ref().to_string(), "foo".to_string()),
+            (
+                LockProvider.as_ref().to_string(),
+                "org.apache.hudi.client.transaction.lock.ZookeeperBasedLockProvider".to_string(),
+            ),
+            (
+                FileSystemLockExpireMinutes.as_ref().to_string(),
+                "-1".to_string(),
+            ),
+        ]);
+        assert!(matches!(
+            WriteConcurrencyMode.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(matches!(
+            LockProvider.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(matches!(
+            FileSystemLockExpireMinutes
+                .parse_value(&options)
+                .unwrap_err(),
+            ParseInt(..)
+        ));
+    }
+}
@@ -28,13 +28,18 @@ use ConfigError::NotFound;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+pub mod archive;
+pub mod clean;
 pub mod error;
+pub mod index;
 pub mod internal;
+pub mod lock;
 pub mod plan;
 pub mod read;
 pub mod read_options;
 pub mod table;
 pub mod util;
+pub mod write;
 
 pub use read_options::{QueryType, ReadOptions};
 
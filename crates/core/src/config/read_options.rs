@@ -27,7 +27,7 @@ use crate::config::HudiConfigs;
 use crate::config::error::ConfigError;
 use crate::config::read::HudiReadConfig;
 pub use crate::config::read::QueryType;
-use crate::expr::filter::{Filter, from_str_tuples};
+use crate::expr::filter::{FilterExpr, from_str_tuples};
 
 /// Options for all Hudi read APIs (snapshot, time-travel, incremental, etc).
 ///
@@ -66,7 +66,8 @@ use crate::expr::filter::{Filter, from_str_tuples};
 /// ```
 #[derive(Clone, Debug, Default)]
 pub struct ReadOptions {
-    /// Column filters, parsed and cardinality-validated at construction.
+    /// Column filters, parsed and cardinality-validated at construction, and AND-ed.
+    /// Each may combine filters with `AND`, `OR` and `NOT` (see [`FilterExpr`]).
     /// `field` is any column name (partition or data).
     ///
     /// Filters drive both **pruning** and **row-level filtering**:
@@ -76,9 +77,13 @@ pub struct ReadOptions {
     ///   metadata table, the filter prunes whole files **for snapshot queries only**.
     ///   Incremental file planning currently does partition pruning only — data-column
     ///   filters apply at the row-level mask but do not prune files.
+    /// - When the table uses the simple bucket index, equality and `IN` filters on all the
+    ///   bucket hash fields, or on `_hoodie_record_key`, prune the file groups of the other
+    ///   buckets for both snapshot and incremental queries. Filters combined by `OR` or
+    ///   `NOT` do not prune buckets.
     /// - All filters are applied as a row-level mask after reading, so callers
     ///   always get only rows that match regardless of the planning path.
-    pub filters: Vec<Filter>,
+    pub filters: Vec<FilterExpr>,
 
     /// Column names to project (select). If None, all columns are read.
     pub projection: Option<Vec<String>>,
@@ -171,10 +176,24 @@ impl ReadOptions {
                 o.as_ref().to_string(),
                 v.as_ref().to_string(),
             )
-        }))?;
+        }))?
+        .into_iter()
+        .map(FilterExpr::from)
+        .collect();
         Ok(self)
     }
 
+    /// Sets column filters from [`FilterExpr`]s, e.g., to prune by
+    /// `region = 'us' OR dt = '2024-01-01'`, which `(field, op, value)` tuples
+    /// cannot express.
+    pub fn with_filter_exprs<I>(mut self, filters: I) -> Self
+    where
+        I: IntoIterator<Item = FilterExpr>,
+    {
+        self.filters = filters.into_iter().collect();
+        self
+    }
+
     /// Sets the column projection (which columns to read).
     pub fn with_projection<I, S>(mut self, columns: I) -> Self
     where
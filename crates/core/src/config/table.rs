@@ -145,6 +145,18 @@ pub enum HudiTableConfig {
     /// When creating a metadata table instance, this value should be passed as the
     /// PartitionFields option.
     MetadataTablePartitions,
+
+    /// Enable the `column_stats` partition of the metadata table when it is bootstrapped.
+    ///
+    /// The partition stores the min/max values and null counts of the columns of each data file,
+    /// which query engines use for data skipping.
+    ColumnStatsIndexEnabled,
+
+    /// Enable the `partition_stats` partition of the metadata table when it is bootstrapped.
+    ///
+    /// The partition stores the column stats aggregated per partition, and requires
+    /// [HudiTableConfig::ColumnStatsIndexEnabled].
+    PartitionStatsIndexEnabled,
 }
 
 impl AsRef<str> for HudiTableConfig {
@@ -175,6 +187,8 @@ impl AsRef<str> for HudiTableConfig {
             Self::TimelineHistoryPath => "hoodie.timeline.history.path",
             Self::MetadataTableEnabled => "hoodie.metadata.enable",
             Self::MetadataTablePartitions => "hoodie.table.metadata.partitions",
+            Self::ColumnStatsIndexEnabled => "hoodie.metadata.index.column.stats.enable",
+            Self::PartitionStatsIndexEnabled => "hoodie.metadata.index.partition.stats.enable",
         }
     }
 }
@@ -207,6 +221,8 @@ impl ConfigParser for HudiTableConfig {
             Self::TimelineHistoryPath => Some(HudiConfigValue::String("history".to_string())),
             Self::MetadataTableEnabled => Some(HudiConfigValue::Boolean(false)),
             Self::MetadataTablePartitions => Some(HudiConfigValue::List(vec![])),
+            Self::ColumnStatsIndexEnabled => Some(HudiConfigValue::Boolean(false)),
+            Self::PartitionStatsIndexEnabled => Some(HudiConfigValue::Boolean(false)),
             _ => None,
         }
     }
@@ -299,7 +315,9 @@ impl ConfigParser for HudiTableConfig {
             Self::ArchiveLogFolder => get_result.map(|v| HudiConfigValue::String(v.to_string())),
             Self::TimelinePath => get_result.map(|v| HudiConfigValue::String(v.to_string())),
             Self::TimelineHistoryPath => get_result.map(|v| HudiConfigValue::String(v.to_string())),
-            Self::MetadataTableEnabled => get_result
+            Self::MetadataTableEnabled
+            | Self::ColumnStatsIndexEnabled
+            | Self::PartitionStatsIndexEnabled => get_result
                 .and_then(|v| {
                     bool::from_str(v).map_err(|e| ParseBool(self.key(), v.to_string(), e))
                 })
@@ -316,9 +334,14 @@ impl ConfigParser for HudiTableConfig {
                     let populates_meta_fields: bool = HudiTableConfig::PopulatesMetaFields
                         .parse_value_or_default(configs)
                         .into();
-                    if !populates_meta_fields {
-                        // When populatesMetaFields is false, meta fields such as record key and
-                        // partition path are null, the table is supposed to be append-only.
+                    if !populates_meta_fields
+                        && HudiTableConfig::RecordKeyFields
+                            .parse_value(configs)
+                            .is_err()
+                    {
+                        // When populatesMetaFields is false, the records can only be merged by
+                        // the record keys generated from the record key fields; without them,
+                        // the table is supposed to be append-only.
                         return HudiConfigValue::String(
                             RecordMergeStrategyValue::AppendOnly.as_ref().to_string(),
                         );
@@ -458,6 +481,77 @@ impl FromStr for BaseFileFormatValue {
     }
 }
 
+/// Config value for [HudiTableConfig::KeyGeneratorType].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum KeyGeneratorTypeValue {
+    #[strum(serialize = "SIMPLE")]
+    Simple,
+    #[strum(serialize = "COMPLEX")]
+    Complex,
+    #[strum(serialize = "NON_PARTITION")]
+    NonPartition,
+    #[strum(serialize = "TIMESTAMP")]
+    Timestamp,
+    #[strum(serialize = "CUSTOM")]
+    Custom,
+}
+
+impl KeyGeneratorTypeValue {
+    /// Infer the key generator type from the record key and partition fields,
+    /// the same way as Hudi Java does.
+    pub fn infer(record_key_fields: &[String], partition_fields: &[String]) -> Self {
+        if partition_fields.is_empty() {
+            Self::NonPartition
+        } else if record_key_fields.len() > 1 || partition_fields.len() > 1 {
+            Self::Complex
+        } else {
+            Self::Simple
+        }
+    }
+
+    /// The key generator class persisted as [HudiTableConfig::KeyGeneratorClass]
+    /// for table versions below 8.
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            Self::Simple => "org.apache.hudi.keygen.SimpleKeyGenerator",
+            Self::Complex => "org.apache.hudi.keygen.ComplexKeyGenerator",
+            Self::NonPartition => "org.apache.hudi.keygen.NonpartitionedKeyGenerator",
+            Self::Timestamp => "org.apache.hudi.keygen.TimestampBasedKeyGenerator",
+            Self::Custom => "org.apache.hudi.keygen.CustomKeyGenerator",
+        }
+    }
+
+    /// Resolve the key generator type from [HudiTableConfig::KeyGeneratorClass], including
+    /// the Avro variants of the key generator classes.
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        let class_name = class_name.replace("AvroKeyGenerator", "KeyGenerator");
+        [
+            Self::Simple,
+            Self::Complex,
+            Self::NonPartition,
+            Self::Timestamp,
+            Self::Custom,
+        ]
+        .into_iter()
+        .find(|t| t.class_name() == class_name)
+    }
+}
+
+impl FromStr for KeyGeneratorTypeValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "SIMPLE" => Ok(Self::Simple),
+            "COMPLEX" => Ok(Self::Complex),
+            "NON_PARTITION" => Ok(Self::NonPartition),
+            "TIMESTAMP" => Ok(Self::Timestamp),
+            "CUSTOM" => Ok(Self::Custom),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
 /// Config value for [HudiTableConfig::TimelineTimezone].
 #[derive(Clone, Debug, PartialEq, AsRefStr, Default)]
 pub enum TimelineTimezoneValue {
@@ -529,6 +623,44 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn create_key_generator_type() {
+        assert_eq!(
+            KeyGeneratorTypeValue::from_str("non_partition").unwrap(),
+            KeyGeneratorTypeValue::NonPartition
+        );
+        assert_eq!(KeyGeneratorTypeValue::Timestamp.as_ref(), "TIMESTAMP");
+        assert!(matches!(
+            KeyGeneratorTypeValue::from_str("foo").unwrap_err(),
+            InvalidValue(_)
+        ));
+
+        let fields = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            KeyGeneratorTypeValue::infer(&fields(&["id"]), &[]),
+            KeyGeneratorTypeValue::NonPartition
+        );
+        assert_eq!(
+            KeyGeneratorTypeValue::infer(&fields(&["id"]), &fields(&["region"])),
+            KeyGeneratorTypeValue::Simple
+        );
+
+        assert_eq!(
+            KeyGeneratorTypeValue::from_class_name(
+                "org.apache.hudi.keygen.ComplexAvroKeyGenerator"
+            ),
+            Some(KeyGeneratorTypeValue::Complex)
+        );
+        assert_eq!(
+            KeyGeneratorTypeValue::from_class_name("com.example.MyKeyGenerator"),
+            None
+        );
+        assert_eq!(
+            KeyGeneratorTypeValue::infer(&fields(&["id", "ts"]), &fields(&["region"])),
+            KeyGeneratorTypeValue::Complex
+        );
+    }
+
     #[test]
     fn create_base_file_format() {
         assert_eq!(
@@ -730,6 +862,20 @@ mod tests {
             "Should derive as append-only due to populatesMetaFields=false"
         );
 
+        let hudi_configs = HudiConfigs::new(vec![
+            (HudiTableConfig::PopulatesMetaFields, "false"),
+            (HudiTableConfig::RecordKeyFields, "id"),
+            (HudiTableConfig::OrderingFields, "ts"),
+        ]);
+        let actual: String = hudi_configs
+            .get_or_default(HudiTableConfig::RecordMergeStrategy)
+            .into();
+        assert_eq!(
+            actual,
+            RecordMergeStrategyValue::OverwriteWithLatest.as_ref(),
+            "Should derive as overwrite-with-latest by the generated record keys"
+        );
+
         let hudi_configs = HudiConfigs::new(vec![(HudiTableConfig::PopulatesMetaFields, "true")]);
         let actual: String = hudi_configs
             .get_or_default(HudiTableConfig::RecordMergeStrategy)
@@ -83,6 +83,63 @@ pub fn parse_data_for_options(data: &Bytes, split_chars: &str) -> Result<HashMap
     Ok(options)
 }
 
+/// Formats the given options as the content of a properties file, with entries sorted by keys.
+pub fn format_data_for_options<I, K, V>(options: I) -> Bytes
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut entries: Vec<(String, String)> = options
+        .into_iter()
+        .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+        .collect();
+    entries.sort_unstable();
+    let mut data = String::from("#Properties saved by hudi-rs\n");
+    for (key, value) in entries {
+        data.push_str(&format!("{key}={value}\n"));
+    }
+    Bytes::from(data)
+}
+
+/// Sets the given options in the content of a properties file.
+///
+/// Existing entries are replaced in place and the other lines are kept as is;
+/// new entries are appended to the end.
+pub fn update_data_for_options<'a, I>(data: &Bytes, updates: I) -> Result<Bytes>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut updates: Vec<(&str, &str)> = updates.into_iter().collect();
+    let mut content = String::with_capacity(data.len());
+    for line in BufReader::new(Cursor::new(data)).lines() {
+        let line = line.map_err(|e| ParseLine(format!("Failed to parse config data: {e:?}")))?;
+        let key = line.split(['=', ':']).next().unwrap_or_default().trim();
+        match updates.iter().position(|(k, _)| *k == key) {
+            Some(i) if !line.trim_start().starts_with('#') => {
+                let (k, v) = updates.remove(i);
+                content.push_str(&format!("{k}={v}\n"));
+            }
+            _ => {
+                content.push_str(&line);
+                content.push('\n');
+            }
+        }
+    }
+    for (k, v) in updates {
+        content.push_str(&format!("{k}={v}\n"));
+    }
+    Ok(Bytes::from(content))
+}
+
+/// Computes the value of `hoodie.table.checksum` from the database and table names,
+/// the same way as Hudi Java does.
+pub fn table_checksum(database_name: &str, table_name: &str) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(format!("{database_name}.{table_name}").as_bytes());
+    crc.sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +258,31 @@ mod tests {
         expected.insert("key2".to_string(), "value2".to_string());
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_format_and_update_data_for_options() {
+        let data = format_data_for_options([("k2", "v2"), ("k1", "a:b")]);
+        assert_eq!(
+            data,
+            Bytes::from("#Properties saved by hudi-rs\nk1=a:b\nk2=v2\n")
+        );
+
+        let updated = update_data_for_options(&data, [("k2", "new"), ("k3", "v3")]).unwrap();
+        let parsed = parse_data_for_options(&updated, "=").unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed["k1"], "a:b");
+        assert_eq!(parsed["k2"], "new");
+        assert_eq!(parsed["k3"], "v3");
+        assert!(updated.starts_with(b"#Properties saved by hudi-rs\nk1=a:b\nk2=new\n"));
+    }
+
+    #[test]
+    fn test_table_checksum() {
+        // values from hoodie.properties written by Hudi Java
+        assert_eq!(table_checksum("default", "v6_nonpartitioned"), 1363530505);
+        assert_eq!(
+            table_checksum("", "v9_txns_mor_simple_meta_metadata"),
+            2022389452
+        );
+    }
 }
@@ -0,0 +1,165 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Hudi write configurations.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use strum_macros::{AsRefStr, EnumIter, IntoStaticStr};
+
+use crate::config::Result;
+use crate::config::error::ConfigError;
+use crate::config::error::ConfigError::{InvalidValue, ParseInt};
+use crate::config::{ConfigParser, HudiConfigValue};
+
+/// Configurations for writing records to Hudi tables.
+///
+/// **Example**
+///
+/// ```rust
+/// use hudi_core::config::write::HudiWriteConfig::Operation;
+/// use hudi_core::table::Table as HudiTable;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let options = [(Operation, "insert")];
+/// HudiTable::new_with_options("/tmp/hudi_data", options).await;
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum HudiWriteConfig {
+    /// The operation used by [crate::table::Table::write].
+    ///
+    /// See [WriteOperationValue] for the supported values. If not set, records are upserted to
+    /// tables using the bucket index, and inserted to the others.
+    Operation,
+
+    /// The maximum size in bytes of the records buffered in memory for a write, e.g., by the
+    /// DataFusion sink, which collects the input of a statement to write it as a single commit.
+    /// Writes with larger inputs fail instead of exhausting the memory.
+    MaxBufferedBytes,
+}
+
+impl AsRef<str> for HudiWriteConfig {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Operation => "hoodie.datasource.write.operation",
+            Self::MaxBufferedBytes => "hoodie.write.max.buffered.bytes",
+        }
+    }
+}
+
+impl Display for HudiWriteConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl ConfigParser for HudiWriteConfig {
+    type Output = HudiConfigValue;
+
+    fn default_value(&self) -> Option<HudiConfigValue> {
+        match self {
+            Self::Operation => None,
+            Self::MaxBufferedBytes => Some(HudiConfigValue::UInteger(1024 * 1024 * 1024)),
+        }
+    }
+
+    fn parse_value(&self, configs: &HashMap<String, String>) -> Result<Self::Output> {
+        let get_result = self.resolve_raw_value(configs);
+
+        match self {
+            Self::Operation => get_result
+                .and_then(WriteOperationValue::from_str)
+                .map(|v| HudiConfigValue::String(v.as_ref().to_string())),
+            Self::MaxBufferedBytes => get_result
+                .and_then(|v| {
+                    usize::from_str(v).map_err(|e| ParseInt(self.key(), v.to_string(), e))
+                })
+                .map(HudiConfigValue::UInteger),
+        }
+    }
+}
+
+/// Config value for [HudiWriteConfig::Operation].
+#[derive(Clone, Debug, PartialEq, AsRefStr)]
+pub enum WriteOperationValue {
+    /// Write the records to new file groups.
+    #[strum(serialize = "insert")]
+    Insert,
+    /// Update the existing records and insert the new ones.
+    #[strum(serialize = "upsert")]
+    Upsert,
+}
+
+impl FromStr for WriteOperationValue {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "insert" => Ok(Self::Insert),
+            "upsert" => Ok(Self::Upsert),
+            v => Err(InvalidValue(v.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::write::HudiWriteConfig::{MaxBufferedBytes, Operation};
+
+    #[test]
+    fn parse_valid_config_value() {
+        let options = HashMap::from([(Operation.as_ref().to_string(), "UPSERT".to_string())]);
+        let actual: String = Operation.parse_value(&options).unwrap().into();
+        assert_eq!(
+            WriteOperationValue::from_str(&actual).unwrap(),
+            WriteOperationValue::Upsert
+        );
+    }
+
+    #[test]
+    fn parse_invalid_config_value() {
+        let options = HashMap::from([(Operation.as_ref().to_string(), "bulk_insert".to_string())]);
+        assert!(matches!(
+            Operation.parse_value(&options).unwrap_err(),
+            InvalidValue(_)
+        ));
+        assert!(Operation.default_value().is_none());
+    }
+
+    #[test]
+    fn parse_max_buffered_bytes() {
+        let actual: usize = MaxBufferedBytes
+            .parse_value_or_default(&HashMap::new())
+            .into();
+        assert_eq!(actual, 1024 * 1024 * 1024);
+        let options = HashMap::from([(MaxBufferedBytes.as_ref().to_string(), "1024".to_string())]);
+        let actual: usize = MaxBufferedBytes.parse_value(&options).unwrap().into();
+        assert_eq!(actual, 1024);
+        let options = HashMap::from([(MaxBufferedBytes.as_ref().to_string(), "1GB".to_string())]);
+        assert!(matches!(
+            MaxBufferedBytes.parse_value(&options).unwrap_err(),
+            ParseInt(..)
+        ));
+    }
+}
@@ -51,6 +51,9 @@ pub enum CoreError {
     #[error("{0}")]
     ReadFileSliceError(String),
 
+    #[error("Lock error: {0}")]
+    Lock(String),
+
     #[error("{0}")]
     LogFormatError(String),
 
@@ -92,4 +95,7 @@ pub enum CoreError {
 
     #[error(transparent)]
     Utf8Error(#[from] std::str::Utf8Error),
+
+    #[error("Write conflict: {0}")]
+    WriteConflict(String),
 }
@@ -20,14 +20,15 @@
 use crate::Result;
 use crate::error::CoreError;
 use crate::expr::ExprOperator;
+use arrow::compute::kernels::comparison::{like, nlike};
 use arrow_arith::boolean;
-use arrow_array::{ArrayRef, BooleanArray, Datum, Scalar, StringArray};
+use arrow_array::{ArrayRef, BooleanArray, Datum, RecordBatch, Scalar, StringArray};
 use arrow_cast::{CastOptions, cast_with_options};
 use arrow_ord::cmp::{eq, gt, gt_eq, lt, lt_eq, neq};
 use arrow_schema::{DataType, Field, Schema};
 use std::str::FromStr;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Filter {
     pub field: String,
     pub operator: ExprOperator,
@@ -36,7 +37,14 @@ pub struct Filter {
 
 impl Filter {
     pub fn new(field: String, operator: ExprOperator, values: Vec<String>) -> Result<Self> {
-        if operator.is_multi_value() {
+        if operator.is_null_check() {
+            if !values.is_empty() {
+                return Err(CoreError::Schema(format!(
+                    "{operator} operator requires no value for field '{field}', got {}",
+                    values.len()
+                )));
+            }
+        } else if operator.is_multi_value() {
             if values.is_empty() {
                 return Err(CoreError::Schema(format!(
                     "{operator} operator requires at least one value for field '{field}'"
@@ -90,6 +98,8 @@ impl TryFrom<(&str, &str, &str)> for Filter {
         let operator = ExprOperator::from_str(operator_str)?;
         let values = if operator.is_multi_value() {
             split_in_values(field_value)
+        } else if operator.is_null_check() && field_value.is_empty() {
+            vec![]
         } else {
             vec![field_value.to_string()]
         };
@@ -164,36 +174,177 @@ where
         .collect()
 }
 
-/// Evaluate a slice of [`Filter`]s against a [`RecordBatch`] and return a row-level mask
+/// A boolean expression of filters, e.g., `city = 'sf' OR (fare > 10 AND NOT name LIKE 'a%')`.
+///
+/// The filters are [`Filter`]s by default, and pruners resolve them into other types,
+/// e.g., [`SchemableFilter`]s, with [`FilterExpr::map`]. A slice of expressions is
+/// implicitly AND-ed, as are [`ReadOptions::filters`](crate::config::read_options::ReadOptions::filters).
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr<F = Filter> {
+    Filter(F),
+    /// Matches when all the expressions match, or always when empty.
+    And(Vec<FilterExpr<F>>),
+    /// Matches when any of the expressions matches, or never when empty.
+    Or(Vec<FilterExpr<F>>),
+    Not(Box<FilterExpr<F>>),
+}
+
+impl From<Filter> for FilterExpr {
+    fn from(filter: Filter) -> Self {
+        FilterExpr::Filter(filter)
+    }
+}
+
+impl<F> FilterExpr<F> {
+    /// Returns the filter if the expression is a single filter.
+    pub fn as_filter(&self) -> Option<&F> {
+        match self {
+            FilterExpr::Filter(filter) => Some(filter),
+            _ => None,
+        }
+    }
+
+    /// Returns all the filters in the expression, in depth-first order.
+    pub fn filters(&self) -> Vec<&F> {
+        let mut filters = Vec::new();
+        self.collect_filters(&mut filters);
+        filters
+    }
+
+    fn collect_filters<'a>(&'a self, filters: &mut Vec<&'a F>) {
+        match self {
+            FilterExpr::Filter(filter) => filters.push(filter),
+            FilterExpr::And(exprs) | FilterExpr::Or(exprs) => {
+                exprs.iter().for_each(|e| e.collect_filters(filters))
+            }
+            FilterExpr::Not(expr) => expr.collect_filters(filters),
+        }
+    }
+
+    /// Returns the expression of the same shape with each filter mapped by `f`.
+    pub fn map<G>(&self, f: &mut impl FnMut(&F) -> G) -> FilterExpr<G> {
+        match self {
+            FilterExpr::Filter(filter) => FilterExpr::Filter(f(filter)),
+            FilterExpr::And(exprs) => FilterExpr::And(exprs.iter().map(|e| e.map(f)).collect()),
+            FilterExpr::Or(exprs) => FilterExpr::Or(exprs.iter().map(|e| e.map(f)).collect()),
+            FilterExpr::Not(expr) => FilterExpr::Not(Box::new(expr.map(f))),
+        }
+    }
+
+    /// Returns `false` only if the expression cannot match, given `may_match` which
+    /// returns `false` only if a filter cannot match, e.g., by the min/max statistics
+    /// of a file.
+    ///
+    /// `NOT` cannot be decided this way, so it always may match; use
+    /// [`FilterExpr::to_negation_normal_form`] to push it down to the filters first.
+    pub fn may_match(&self, may_match: &mut impl FnMut(&F) -> bool) -> bool {
+        match self {
+            FilterExpr::Filter(filter) => may_match(filter),
+            FilterExpr::And(exprs) => exprs.iter().all(|e| e.may_match(may_match)),
+            FilterExpr::Or(exprs) => exprs.iter().any(|e| e.may_match(may_match)),
+            FilterExpr::Not(_) => true,
+        }
+    }
+}
+
+impl FilterExpr {
+    /// Returns the negation of the expression without `NOT`, by De Morgan's laws and
+    /// [`Filter::negate`], e.g., `NOT (a = 1 OR b IS NULL)` is `a != 1 AND b IS NOT NULL`.
+    pub fn negate(&self) -> Self {
+        match self {
+            FilterExpr::Filter(filter) => match filter.negate() {
+                Some(negated) => FilterExpr::Filter(negated),
+                None => FilterExpr::Not(Box::new(self.clone())),
+            },
+            FilterExpr::And(exprs) => FilterExpr::Or(exprs.iter().map(Self::negate).collect()),
+            FilterExpr::Or(exprs) => FilterExpr::And(exprs.iter().map(Self::negate).collect()),
+            FilterExpr::Not(expr) => expr.to_negation_normal_form(),
+        }
+    }
+
+    /// Returns the equivalent expression with `NOT` pushed down to the filters.
+    pub fn to_negation_normal_form(&self) -> Self {
+        match self {
+            FilterExpr::Filter(_) => self.clone(),
+            FilterExpr::And(exprs) => {
+                FilterExpr::And(exprs.iter().map(Self::to_negation_normal_form).collect())
+            }
+            FilterExpr::Or(exprs) => {
+                FilterExpr::Or(exprs.iter().map(Self::to_negation_normal_form).collect())
+            }
+            FilterExpr::Not(expr) => expr.negate(),
+        }
+    }
+
+    /// Evaluate the expression against the columns of a [`RecordBatch`], which must
+    /// contain all the fields of the filters.
+    ///
+    /// `AND`, `OR` and `NOT` follow SQL's three-valued logic, so a row whose filter
+    /// evaluates to null is not retained even when negated.
+    pub fn evaluate(&self, batch: &RecordBatch) -> Result<BooleanArray> {
+        let num_rows = batch.num_rows();
+        match self {
+            FilterExpr::Filter(filter) => {
+                let column = batch.column_by_name(&filter.field).ok_or_else(|| {
+                    CoreError::Schema(format!(
+                        "Filter field '{}' not found in batch",
+                        filter.field
+                    ))
+                })?;
+                let schemable =
+                    SchemableFilter::try_from((filter.clone(), batch.schema().as_ref()))?;
+                schemable.apply_comparison(column)
+            }
+            FilterExpr::And(exprs) => {
+                let mut mask = BooleanArray::from(vec![true; num_rows]);
+                for expr in exprs {
+                    mask = boolean::and_kleene(&mask, &expr.evaluate(batch)?)?;
+                }
+                Ok(mask)
+            }
+            FilterExpr::Or(exprs) => {
+                let mut mask = BooleanArray::from(vec![false; num_rows]);
+                for expr in exprs {
+                    mask = boolean::or_kleene(&mask, &expr.evaluate(batch)?)?;
+                }
+                Ok(mask)
+            }
+            FilterExpr::Not(expr) => Ok(boolean::not(&expr.evaluate(batch)?)?),
+        }
+    }
+}
+
+/// Evaluate a slice of [`FilterExpr`]s against a [`RecordBatch`] and return a row-level mask
 /// where `true` indicates the row should be retained.
 ///
-/// This is a low-level primitive: filters whose field is not present in the batch are
+/// This is a low-level primitive: expressions with any field not present in the batch are
 /// skipped silently. Callers that want strict-on-unknown-column behavior must call
 /// [`validate_fields_against_schemas`] before invoking this function (this is what
 /// the file-group reader paths do).
 ///
-/// All applicable filters are evaluated as row-level predicates and ANDed together.
-pub fn filters_to_row_mask(
-    filters: &[Filter],
-    batch: &arrow_array::RecordBatch,
-) -> Result<BooleanArray> {
+/// All applicable expressions are evaluated as row-level predicates and ANDed together.
+pub fn filters_to_row_mask(filters: &[FilterExpr], batch: &RecordBatch) -> Result<BooleanArray> {
     let num_rows = batch.num_rows();
     let mut mask: Option<BooleanArray> = None;
-    for filter in filters {
-        let Some(column) = batch.column_by_name(&filter.field) else {
+    for expr in filters {
+        if expr
+            .filters()
+            .iter()
+            .any(|filter| batch.column_by_name(&filter.field).is_none())
+        {
             continue;
-        };
-        let schemable = SchemableFilter::try_from((filter.clone(), batch.schema().as_ref()))?;
-        let column_mask = schemable.apply_comparison(column)?;
+        }
+        let expr_mask = expr.evaluate(batch)?;
         mask = Some(match mask {
-            Some(prev) => boolean::and(&prev, &column_mask)?,
-            None => column_mask,
+            Some(prev) => boolean::and_kleene(&prev, &expr_mask)?,
+            None => expr_mask,
         });
     }
     Ok(mask.unwrap_or_else(|| BooleanArray::from(vec![true; num_rows])))
 }
 
-/// Error if any [`Filter`] targets a column not present in any of the provided schemas.
+/// Error if any [`Filter`] in the expressions targets a column not present in any of
+/// the provided schemas.
 ///
 /// This is the strict counterpart to [`filters_to_row_mask`], which silently skips
 /// missing columns. Pass one schema for the file-group-reader case (validate against
@@ -201,7 +352,7 @@ pub fn filters_to_row_mask(
 /// table layer, where partition columns may be valid filter targets even when not
 /// physically present in the data schema. Failing fast prevents typos like
 /// `("rder_id", "=", "x")` from becoming silent no-ops.
-pub fn validate_fields_against_schemas<'a, I>(filters: &[Filter], schemas: I) -> Result<()>
+pub fn validate_fields_against_schemas<'a, I>(filters: &[FilterExpr], schemas: I) -> Result<()>
 where
     I: IntoIterator<Item = &'a Schema>,
 {
@@ -210,7 +361,7 @@ where
     for schema in schemas {
         valid.extend(schema.fields().iter().map(|f| f.name().as_str()));
     }
-    for filter in filters {
+    for filter in filters.iter().flat_map(FilterExpr::filters) {
         if !valid.contains(filter.field.as_str()) {
             return Err(CoreError::Schema(format!(
                 "Filter field '{}' not found in schema",
@@ -305,6 +456,40 @@ impl FilterField {
             values: values.into_iter().map(|v| v.into()).collect(),
         }
     }
+
+    pub fn is_null(&self) -> Filter {
+        Filter {
+            field: self.name.clone(),
+            operator: ExprOperator::IsNull,
+            values: vec![],
+        }
+    }
+
+    pub fn is_not_null(&self) -> Filter {
+        Filter {
+            field: self.name.clone(),
+            operator: ExprOperator::IsNotNull,
+            values: vec![],
+        }
+    }
+
+    /// Filter by a SQL `LIKE` pattern, where `%` matches any characters, `_` matches
+    /// a single character, and `\` escapes them.
+    pub fn like(&self, pattern: impl Into<String>) -> Filter {
+        Filter {
+            field: self.name.clone(),
+            operator: ExprOperator::Like,
+            values: vec![pattern.into()],
+        }
+    }
+
+    pub fn not_like(&self, pattern: impl Into<String>) -> Filter {
+        Filter {
+            field: self.name.clone(),
+            operator: ExprOperator::NotLike,
+            values: vec![pattern.into()],
+        }
+    }
 }
 
 pub fn col(name: impl Into<String>) -> FilterField {
@@ -382,6 +567,10 @@ impl SchemableFilter {
                 }
                 Ok(result)
             }
+            ExprOperator::IsNull => boolean::is_null(value.get().0),
+            ExprOperator::IsNotNull => boolean::is_not_null(value.get().0),
+            ExprOperator::Like => like(value, &self.values[0]),
+            ExprOperator::NotLike => nlike(value, &self.values[0]),
         }
         .map_err(|e| e.into())
     }
@@ -412,9 +601,9 @@ mod tests {
             ],
         )
         .unwrap();
-        let filters = vec![
-            Filter::try_from(("string_col", "=", "a"))?,
-            Filter::try_from(("int_col", ">", "1"))?,
+        let filters: Vec<FilterExpr> = vec![
+            Filter::try_from(("string_col", "=", "a"))?.into(),
+            Filter::try_from(("int_col", ">", "1"))?.into(),
         ];
         let mask = filters_to_row_mask(&filters, &batch)?;
         assert_eq!(mask, BooleanArray::from(vec![false, false, true]));
@@ -432,9 +621,9 @@ mod tests {
             ],
         )
         .unwrap();
-        let filters = vec![
-            Filter::try_from(("not_in_batch", "=", "x"))?,
-            Filter::try_from(("string_col", "=", "a"))?,
+        let filters: Vec<FilterExpr> = vec![
+            Filter::try_from(("not_in_batch", "=", "x"))?.into(),
+            Filter::try_from(("string_col", "=", "a"))?.into(),
         ];
         let mask = filters_to_row_mask(&filters, &batch)?;
         assert_eq!(mask, BooleanArray::from(vec![true, false]));
@@ -449,16 +638,16 @@ mod tests {
         assert!(validate_fields_against_schemas(&[], [&schema]).is_ok());
 
         // All-known fields pass.
-        let valid = vec![
-            Filter::try_from(("string_col", "=", "x"))?,
-            Filter::try_from(("int_col", ">", "1"))?,
+        let valid: Vec<FilterExpr> = vec![
+            Filter::try_from(("string_col", "=", "x"))?.into(),
+            Filter::try_from(("int_col", ">", "1"))?.into(),
         ];
         assert!(validate_fields_against_schemas(&valid, [&schema]).is_ok());
 
         // Unknown field errors with a Schema error naming the bad column.
-        let invalid = vec![
-            Filter::try_from(("string_col", "=", "x"))?,
-            Filter::try_from(("typo_col", "=", "y"))?,
+        let invalid: Vec<FilterExpr> = vec![
+            Filter::try_from(("string_col", "=", "x"))?.into(),
+            Filter::try_from(("typo_col", "=", "y"))?.into(),
         ];
         let err = validate_fields_against_schemas(&invalid, [&schema]).unwrap_err();
         assert!(matches!(err, CoreError::Schema(_)));
@@ -469,9 +658,9 @@ mod tests {
         // where partition fields may not be in the data schema but are still valid
         // filter targets.
         let partition_schema = Schema::new(vec![Field::new("city", DataType::Utf8, false)]);
-        let cross_schema_filters = vec![
-            Filter::try_from(("string_col", "=", "x"))?, // in data schema only
-            Filter::try_from(("city", "=", "sf"))?,      // in partition schema only
+        let cross_schema_filters: Vec<FilterExpr> = vec![
+            Filter::try_from(("string_col", "=", "x"))?.into(), // in data schema only
+            Filter::try_from(("city", "=", "sf"))?.into(),      // in partition schema only
         ];
         assert!(
             validate_fields_against_schemas(&cross_schema_filters, [&schema, &partition_schema])
@@ -479,7 +668,7 @@ mod tests {
         );
 
         // A filter on a column missing from BOTH schemas still errors.
-        let still_invalid = vec![Filter::try_from(("nope", "=", "x"))?];
+        let still_invalid: Vec<FilterExpr> = vec![Filter::try_from(("nope", "=", "x"))?.into()];
         let err = validate_fields_against_schemas(&still_invalid, [&schema, &partition_schema])
             .unwrap_err();
         assert!(err.to_string().contains("nope"));
@@ -487,6 +676,121 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_filters_to_row_mask_evaluates_filter_exprs() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("region", DataType::Utf8, true),
+            Field::new("dt", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![
+                    Some("us-west"),
+                    Some("eu"),
+                    None,
+                    Some("eu"),
+                ])) as ArrayRef,
+                Arc::new(StringArray::from(vec![
+                    "2024-01-02",
+                    "2024-01-01",
+                    "2024-01-01",
+                    "2024-01-02",
+                ])) as ArrayRef,
+            ],
+        )
+        .unwrap();
+
+        let region_or_dt = FilterExpr::Or(vec![
+            col("region").like("us%").into(),
+            col("dt").eq("2024-01-01").into(),
+        ]);
+        let mask = filters_to_row_mask(std::slice::from_ref(&region_or_dt), &batch)?;
+        assert_eq!(mask, BooleanArray::from(vec![true, true, true, false]));
+
+        // NOT of a null comparison is null, so the row with null region is not retained.
+        let not_us = FilterExpr::Not(Box::new(col("region").like("us%").into()));
+        let mask = filters_to_row_mask(&[not_us], &batch)?;
+        assert_eq!(
+            mask,
+            BooleanArray::from(vec![Some(false), Some(true), None, Some(true)])
+        );
+
+        let is_null = FilterExpr::from(col("region").is_null());
+        let mask = filters_to_row_mask(&[region_or_dt, is_null], &batch)?;
+        assert_eq!(mask, BooleanArray::from(vec![false, false, true, false]));
+
+        // Expressions with any field missing from the batch are skipped.
+        let missing = FilterExpr::Or(vec![
+            col("dt").eq("2024-01-01").into(),
+            col("not_in_batch").eq("x").into(),
+        ]);
+        let mask = filters_to_row_mask(&[missing], &batch)?;
+        assert_eq!(mask, BooleanArray::from(vec![true; 4]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_expr_negation_normal_form() {
+        let expr = FilterExpr::Not(Box::new(FilterExpr::Or(vec![
+            col("a").eq("1").into(),
+            FilterExpr::And(vec![
+                col("b").is_null().into(),
+                FilterExpr::Not(Box::new(col("c").like("x%").into())),
+            ]),
+        ])));
+        assert_eq!(
+            expr.to_negation_normal_form(),
+            FilterExpr::And(vec![
+                col("a").ne("1").into(),
+                FilterExpr::Or(vec![
+                    col("b").is_not_null().into(),
+                    col("c").like("x%").into(),
+                ]),
+            ])
+        );
+        assert_eq!(
+            expr.filters()
+                .iter()
+                .map(|f| f.field.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert!(expr.as_filter().is_none());
+
+        // `NOT` may always match, while AND / OR combine their expressions.
+        let mut may_match = |filter: &Filter| filter.field != "a";
+        assert!(expr.may_match(&mut may_match));
+        assert!(!expr.to_negation_normal_form().may_match(&mut may_match));
+        assert!(FilterExpr::And(vec![]).may_match(&mut may_match));
+        assert!(!FilterExpr::Or(vec![]).may_match(&mut may_match));
+    }
+
+    #[test]
+    fn test_filter_try_from_tuple_null_check_operators() -> Result<()> {
+        let filter = Filter::try_from(("col", "IS NULL", ""))?;
+        assert_eq!(filter.operator, ExprOperator::IsNull);
+        assert!(filter.values.is_empty());
+        let tuple: (String, String, String) = filter.into();
+        assert_eq!(
+            tuple,
+            ("col".to_string(), "IS NULL".to_string(), String::new())
+        );
+
+        let filter = Filter::try_from(("col", "is not null", ""))?;
+        assert_eq!(filter.operator, ExprOperator::IsNotNull);
+
+        // Null checks take no value.
+        let result = Filter::try_from(("col", "IS NULL", "x"));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("requires no value")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_filters_to_row_mask_empty_returns_all_true() -> Result<()> {
         let schema = Arc::new(create_test_schema());
@@ -37,6 +37,10 @@ pub enum ExprOperator {
     Gte,
     In,
     NotIn,
+    IsNull,
+    IsNotNull,
+    Like,
+    NotLike,
 }
 
 impl Display for ExprOperator {
@@ -51,12 +55,16 @@ impl Display for ExprOperator {
             ExprOperator::Gte => write!(f, ">="),
             ExprOperator::In => write!(f, "IN"),
             ExprOperator::NotIn => write!(f, "NOT IN"),
+            ExprOperator::IsNull => write!(f, "IS NULL"),
+            ExprOperator::IsNotNull => write!(f, "IS NOT NULL"),
+            ExprOperator::Like => write!(f, "LIKE"),
+            ExprOperator::NotLike => write!(f, "NOT LIKE"),
         }
     }
 }
 
 impl ExprOperator {
-    pub const TOKEN_OP_PAIRS: [(&'static str, ExprOperator); 8] = [
+    pub const TOKEN_OP_PAIRS: [(&'static str, ExprOperator); 12] = [
         ("=", ExprOperator::Eq),
         ("!=", ExprOperator::Ne),
         ("<", ExprOperator::Lt),
@@ -65,6 +73,10 @@ impl ExprOperator {
         (">=", ExprOperator::Gte),
         ("IN", ExprOperator::In),
         ("NOT IN", ExprOperator::NotIn),
+        ("IS NULL", ExprOperator::IsNull),
+        ("IS NOT NULL", ExprOperator::IsNotNull),
+        ("LIKE", ExprOperator::Like),
+        ("NOT LIKE", ExprOperator::NotLike),
     ];
 
     /// Returns true if the operator expects multiple values (IN, NOT IN).
@@ -72,6 +84,11 @@ impl ExprOperator {
         matches!(self, ExprOperator::In | ExprOperator::NotIn)
     }
 
+    /// Returns true if the operator expects no value (IS NULL, IS NOT NULL).
+    pub fn is_null_check(&self) -> bool {
+        matches!(self, ExprOperator::IsNull | ExprOperator::IsNotNull)
+    }
+
     /// Negates the operator.
     pub fn negate(&self) -> Option<ExprOperator> {
         match self {
@@ -83,6 +100,10 @@ impl ExprOperator {
             ExprOperator::Gte => Some(ExprOperator::Lt),
             ExprOperator::In => Some(ExprOperator::NotIn),
             ExprOperator::NotIn => Some(ExprOperator::In),
+            ExprOperator::IsNull => Some(ExprOperator::IsNotNull),
+            ExprOperator::IsNotNull => Some(ExprOperator::IsNull),
+            ExprOperator::Like => Some(ExprOperator::NotLike),
+            ExprOperator::NotLike => Some(ExprOperator::Like),
         }
     }
 }
@@ -121,6 +142,19 @@ mod tests {
             ExprOperator::from_str("NOT IN").unwrap(),
             ExprOperator::NotIn
         );
+        assert_eq!(
+            ExprOperator::from_str("is null").unwrap(),
+            ExprOperator::IsNull
+        );
+        assert_eq!(
+            ExprOperator::from_str("IS NOT NULL").unwrap(),
+            ExprOperator::IsNotNull
+        );
+        assert_eq!(ExprOperator::from_str("LIKE").unwrap(), ExprOperator::Like);
+        assert_eq!(
+            ExprOperator::from_str("NOT LIKE").unwrap(),
+            ExprOperator::NotLike
+        );
         assert!(ExprOperator::from_str("??").is_err());
     }
 
@@ -134,5 +168,9 @@ mod tests {
         assert_eq!(ExprOperator::Gte.to_string(), ">=");
         assert_eq!(ExprOperator::In.to_string(), "IN");
         assert_eq!(ExprOperator::NotIn.to_string(), "NOT IN");
+        assert_eq!(ExprOperator::IsNull.to_string(), "IS NULL");
+        assert_eq!(ExprOperator::IsNotNull.to_string(), "IS NOT NULL");
+        assert_eq!(ExprOperator::Like.to_string(), "LIKE");
+        assert_eq!(ExprOperator::NotLike.to_string(), "NOT LIKE");
     }
 }
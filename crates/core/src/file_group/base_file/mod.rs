@@ -19,6 +19,7 @@
 pub mod lance;
 pub mod parquet;
 pub mod reader;
+pub(crate) mod writer;
 
 use crate::Result;
 use crate::error::CoreError;
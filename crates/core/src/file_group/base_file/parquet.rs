@@ -18,21 +18,34 @@
  */
 //! Parquet implementation of [`BaseFileReader`].
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Int32Type, Int64Type};
+use arrow_array::{Array, ArrayRef, Datum};
+use arrow_cast::cast;
+use arrow_schema::{DataType, Schema};
 use futures::StreamExt;
 use futures::future::BoxFuture;
 use object_store::path::Path as ObjPath;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::arrow::arrow_reader::{ArrowReaderOptions, RowSelection, RowSelector};
 use parquet::arrow::async_reader::ParquetObjectReader;
 use parquet::arrow::{ParquetRecordBatchStreamBuilder, parquet_to_arrow_schema};
+use parquet::basic::Type as PhysicalType;
+use parquet::bloom_filter::Sbbf;
 use parquet::file::metadata::ParquetMetaData;
 
 use super::reader::{BaseFileReadOptions, BaseFileReader, BaseFileStream};
-use crate::statistics::StatisticsContainer;
+use crate::expr::ExprOperator;
+use crate::expr::filter::{FilterExpr, SchemableFilter};
+use crate::statistics::{ColumnStatistics, StatisticsContainer, StatsGranularity};
 use crate::storage::Storage;
 use crate::storage::error::{Result, StorageError};
 use crate::storage::file_metadata::FileMetadata;
 use crate::storage::util::join_url_segments;
+use crate::table::file_pruner::FilePruner;
 
 /// Parquet implementation of [`BaseFileReader`].
 ///
@@ -57,18 +70,29 @@ impl ParquetBaseFileReader {
         &self,
         obj_path: ObjPath,
         file_size: u64,
+        reader_options: ArrowReaderOptions,
     ) -> Result<ParquetRecordBatchStreamBuilder<ParquetObjectReader>> {
         let reader = ParquetObjectReader::new(self.storage.object_store.clone(), obj_path)
             .with_file_size(file_size);
-        Ok(ParquetRecordBatchStreamBuilder::new(reader).await?)
+        Ok(ParquetRecordBatchStreamBuilder::new_with_options(reader, reader_options).await?)
     }
 
     async fn open_builder(
         &self,
         relative_path: &str,
+    ) -> Result<ParquetRecordBatchStreamBuilder<ParquetObjectReader>> {
+        self.open_builder_with_options(relative_path, ArrowReaderOptions::new())
+            .await
+    }
+
+    async fn open_builder_with_options(
+        &self,
+        relative_path: &str,
+        reader_options: ArrowReaderOptions,
     ) -> Result<ParquetRecordBatchStreamBuilder<ParquetObjectReader>> {
         let (obj_path, file_size) = self.object_path_and_size(relative_path).await?;
-        self.open_builder_with_size(obj_path, file_size).await
+        self.open_builder_with_size(obj_path, file_size, reader_options)
+            .await
     }
 
     fn apply_options(
@@ -109,6 +133,212 @@ impl ParquetBaseFileReader {
         Ok(builder)
     }
 
+    /// Skips the row groups and pages that cannot match the filters, by the row group
+    /// statistics, the bloom filters and the page indexes.
+    ///
+    /// The rows of the row groups and pages read are not filtered.
+    async fn apply_filters(
+        mut builder: ParquetRecordBatchStreamBuilder<ParquetObjectReader>,
+        filters: &[FilterExpr],
+    ) -> Result<ParquetRecordBatchStreamBuilder<ParquetObjectReader>> {
+        let schema = builder.schema().clone();
+        let metadata = builder.metadata().clone();
+        // Filters on columns not in the file, e.g., dropped partition columns, are
+        // excluded by the pruner.
+        let pruner = match FilePruner::new(filters, &schema, &Schema::empty()) {
+            Ok(pruner) if !pruner.is_empty() => pruner,
+            _ => return Ok(builder),
+        };
+
+        let mut row_groups = Vec::new();
+        for (row_group_idx, row_group) in metadata.row_groups().iter().enumerate() {
+            let stats = StatisticsContainer::from_row_group(row_group, &schema);
+            if pruner.should_include(&stats)
+                && Self::bloom_filters_may_match(&mut builder, row_group_idx, filters, &schema)
+                    .await?
+            {
+                row_groups.push(row_group_idx);
+            }
+        }
+
+        if let Some(selection) = Self::select_pages(&metadata, &row_groups, filters, &schema) {
+            builder = builder.with_row_selection(selection);
+        }
+        Ok(builder.with_row_groups(row_groups))
+    }
+
+    /// Returns `false` if the bloom filters of the row group prove that none of the
+    /// values of an `=` or `IN` filter is in the row group.
+    async fn bloom_filters_may_match(
+        builder: &mut ParquetRecordBatchStreamBuilder<ParquetObjectReader>,
+        row_group_idx: usize,
+        filters: &[FilterExpr],
+        schema: &Schema,
+    ) -> Result<bool> {
+        for expr in filters {
+            let expr = expr.to_negation_normal_form();
+            let Some(filter) = expr.as_filter() else {
+                continue;
+            };
+            if !matches!(filter.operator, ExprOperator::Eq | ExprOperator::In) {
+                continue;
+            }
+            let Ok(filter) = SchemableFilter::try_from((filter.clone(), schema)) else {
+                continue;
+            };
+            // Only top-level columns map to a single leaf column.
+            let Some((column_idx, physical_type)) = builder
+                .parquet_schema()
+                .columns()
+                .iter()
+                .enumerate()
+                .find(|(_, column)| column.path().parts() == [filter.field.name().as_str()])
+                .map(|(idx, column)| (idx, column.physical_type()))
+            else {
+                continue;
+            };
+            let Some(bloom_filter) = builder
+                .get_row_group_column_bloom_filter(row_group_idx, column_idx)
+                .await?
+            else {
+                continue;
+            };
+
+            let may_contain = filter.values.iter().any(|value| {
+                Self::bloom_filter_may_contain(&bloom_filter, physical_type, value.get().0)
+            });
+            if !may_contain {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Checks the value, which is hashed as its plain-encoded physical value, against
+    /// the bloom filter. Types that cannot be hashed this way may always be contained.
+    fn bloom_filter_may_contain(
+        bloom_filter: &Sbbf,
+        physical_type: PhysicalType,
+        value: &dyn Array,
+    ) -> bool {
+        if value.is_empty() || value.is_null(0) {
+            return true;
+        }
+        match (physical_type, value.data_type()) {
+            (
+                PhysicalType::BYTE_ARRAY,
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View,
+            ) => cast(value, &DataType::Utf8)
+                .map(|array| bloom_filter.check(&array.as_string::<i32>().value(0)))
+                .unwrap_or(true),
+            (PhysicalType::INT32, DataType::Int8 | DataType::Int16 | DataType::Int32) => {
+                cast(value, &DataType::Int32)
+                    .map(|array| bloom_filter.check(&array.as_primitive::<Int32Type>().value(0)))
+                    .unwrap_or(true)
+            }
+            (PhysicalType::INT64, DataType::Int64) => {
+                bloom_filter.check(&value.as_primitive::<Int64Type>().value(0))
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns the selection of the rows in the row groups skipping the pages that
+    /// cannot match the filters by the page index statistics, or `None` if the file
+    /// has no page indexes or no page can be skipped.
+    ///
+    /// Pages of different columns do not align, so a filter expression is evaluated
+    /// by page only if all its filters are on the same column.
+    fn select_pages(
+        metadata: &ParquetMetaData,
+        row_groups: &[usize],
+        filters: &[FilterExpr],
+        schema: &Schema,
+    ) -> Option<RowSelection> {
+        if row_groups.is_empty() {
+            return None;
+        }
+        metadata.column_index()?;
+        metadata.offset_index()?;
+
+        let mut selection: Option<RowSelection> = None;
+        for expr in filters {
+            let columns: HashSet<&str> = expr.filters().iter().map(|f| f.field.as_str()).collect();
+            let [column] = columns.into_iter().collect::<Vec<_>>()[..] else {
+                continue;
+            };
+            let Some(column_selection) =
+                Self::select_pages_by_column(metadata, row_groups, expr, column, schema)
+            else {
+                continue;
+            };
+            selection = Some(match selection {
+                Some(selection) => selection.intersection(&column_selection),
+                None => column_selection,
+            });
+        }
+        selection.filter(|selection| selection.skipped_row_count() > 0)
+    }
+
+    /// Returns the selection of the rows in the row groups skipping the pages of the
+    /// column whose statistics prove that the filter expression cannot match.
+    fn select_pages_by_column(
+        metadata: &ParquetMetaData,
+        row_groups: &[usize],
+        expr: &FilterExpr,
+        column: &str,
+        schema: &Schema,
+    ) -> Option<RowSelection> {
+        let column_index = metadata.column_index()?;
+        let offset_index = metadata.offset_index()?;
+        let pruner = FilePruner::new(std::slice::from_ref(expr), schema, &Schema::empty()).ok()?;
+        if pruner.is_empty() {
+            return None;
+        }
+
+        let data_type = schema.field_with_name(column).ok()?.data_type();
+        let converter =
+            StatisticsConverter::try_new(column, schema, metadata.file_metadata().schema_descr())
+                .ok()?;
+        let mins = converter
+            .data_page_mins(column_index, offset_index, row_groups)
+            .ok()?;
+        let maxes = converter
+            .data_page_maxes(column_index, offset_index, row_groups)
+            .ok()?;
+        let null_counts = converter
+            .data_page_null_counts(column_index, offset_index, row_groups)
+            .ok()?;
+        let row_counts = converter
+            .data_page_row_counts(offset_index, metadata.row_groups(), row_groups)
+            .ok()??;
+
+        let page_value =
+            |values: &ArrayRef, page: usize| values.is_valid(page).then(|| values.slice(page, 1));
+        let mut selectors = Vec::with_capacity(row_counts.len());
+        for page in 0..row_counts.len() {
+            let num_rows = row_counts.is_valid(page).then(|| row_counts.value(page))?;
+
+            let mut column_stats = ColumnStatistics::new(column.to_string(), data_type.clone());
+            column_stats.min_value = page_value(&mins, page);
+            column_stats.max_value = page_value(&maxes, page);
+            column_stats.null_count = null_counts
+                .is_valid(page)
+                .then(|| null_counts.value(page) as i64);
+            // A page is evaluated like a row group of its own.
+            let mut stats = StatisticsContainer::new(StatsGranularity::RowGroup);
+            stats.num_rows = Some(num_rows as i64);
+            stats.columns.insert(column.to_string(), column_stats);
+
+            selectors.push(if pruner.should_include(&stats) {
+                RowSelector::select(num_rows as usize)
+            } else {
+                RowSelector::skip(num_rows as usize)
+            });
+        }
+        Some(RowSelection::from(selectors))
+    }
+
     /// Read the raw Parquet footer metadata.
     ///
     /// Exposed for callers that need format-specific details such as row group
@@ -136,7 +366,16 @@ impl BaseFileReader for ParquetBaseFileReader {
         options: BaseFileReadOptions,
     ) -> BoxFuture<'a, Result<BaseFileStream>> {
         Box::pin(async move {
-            let builder = self.open_builder(relative_path).await?;
+            let builder = if options.filters.is_empty() {
+                self.open_builder(relative_path).await?
+            } else {
+                // Page indexes are only needed to skip pages by the filters.
+                let reader_options = ArrowReaderOptions::new().with_page_index(true);
+                let builder = self
+                    .open_builder_with_options(relative_path, reader_options)
+                    .await?;
+                Self::apply_filters(builder, &options.filters).await?
+            };
             let builder = Self::apply_options(builder, &options)?;
             let stream = builder.build()?;
             let schema = stream.schema().clone();
@@ -155,7 +394,9 @@ impl BaseFileReader for ParquetBaseFileReader {
     ) -> BoxFuture<'a, Result<(FileMetadata, StatisticsContainer)>> {
         Box::pin(async move {
             let (obj_path, file_size) = self.object_path_and_size(relative_path).await?;
-            let builder = self.open_builder_with_size(obj_path, file_size).await?;
+            let builder = self
+                .open_builder_with_size(obj_path, file_size, ArrowReaderOptions::new())
+                .await?;
             let parquet_meta = builder.metadata().as_ref();
 
             let name = std::path::Path::new(relative_path)
@@ -199,6 +440,53 @@ mod tests {
         Storage::new_with_base_url(base_url).unwrap()
     }
 
+    /// Writes 100 rows of `id` from 0 to 99 and `key` as a permutation of `k00` to
+    /// `k99` in row groups of 20 rows and pages of 5 rows, with a bloom filter on `key`.
+    fn write_filterable_parquet(dir: &Path) -> Arc<Storage> {
+        use arrow_array::{Int64Array, RecordBatch, StringArray};
+        use arrow_schema::Field;
+        use parquet::arrow::ArrowWriter;
+        use parquet::file::properties::WriterProperties;
+        use parquet::schema::types::ColumnPath;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("key", DataType::Utf8, false),
+        ]));
+        let ids = Int64Array::from_iter_values(0..100);
+        let keys = StringArray::from_iter_values((0..100).map(|i| format!("k{:02}", i * 37 % 100)));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(ids), Arc::new(keys)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(20)
+            .set_data_page_row_count_limit(5)
+            .set_write_batch_size(5)
+            .set_column_bloom_filter_enabled(ColumnPath::from("key"), true)
+            .build();
+        let file = std::fs::File::create(dir.join("filterable.parquet")).unwrap();
+        let mut writer = ArrowWriter::try_new(file, schema, Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let base_url = Url::from_directory_path(canonicalize(dir).unwrap()).unwrap();
+        Storage::new_with_base_url(base_url).unwrap()
+    }
+
+    async fn read_ids(storage: Arc<Storage>, filters: Vec<FilterExpr>) -> Vec<i64> {
+        let reader = ParquetBaseFileReader::new(storage);
+        let options = BaseFileReadOptions::default().with_filters(filters);
+        let batch = reader
+            .read_data("filterable.parquet", options)
+            .await
+            .unwrap();
+        let ids = batch
+            .column_by_name("id")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        ids.values().to_vec()
+    }
+
     #[tokio::test]
     async fn test_read_data_returns_all_rows() {
         let reader = ParquetBaseFileReader::new(test_storage());
@@ -250,6 +538,56 @@ mod tests {
         assert_eq!(batches[0].schema(), eager.schema());
     }
 
+    #[tokio::test]
+    async fn test_read_stream_skips_row_groups_and_pages_by_filters() {
+        use crate::expr::filter::col;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = write_filterable_parquet(dir.path());
+
+        // Row group of ids 80 to 99, then pages of ids 90 to 99
+        let ids = read_ids(storage.clone(), vec![col("id").gte("92").into()]).await;
+        assert_eq!(ids, (90..100).collect::<Vec<_>>());
+
+        // Pages are skipped by the filters on each column, which are AND-ed
+        let ids = read_ids(
+            storage.clone(),
+            vec![col("id").lt("50").into(), col("id").gte("48").into()],
+        )
+        .await;
+        assert_eq!(ids, (45..50).collect::<Vec<_>>());
+
+        // Filters on columns not in the file do not skip any rows
+        let ids = read_ids(storage, vec![col("not_a_column").eq("1").into()]).await;
+        assert_eq!(ids.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_skips_row_groups_by_bloom_filters() {
+        use crate::expr::filter::col;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = write_filterable_parquet(dir.path());
+
+        // `k85` is the key of id 5, while the keys of each row group span from
+        // around `k00` to `k99`, so only the bloom filters can skip row groups.
+        let ids = read_ids(storage.clone(), vec![col("key").eq("k85").into()]).await;
+        assert!(ids.contains(&5));
+        assert!(ids.iter().all(|id| *id < 20), "unexpected ids: {ids:?}");
+
+        let ids = read_ids(
+            storage.clone(),
+            vec![col("key").in_list(["k85", "k68"]).into()],
+        )
+        .await;
+        assert!(ids.contains(&5) && ids.contains(&64));
+        assert!(ids.iter().all(|id| *id < 20 || (60..80).contains(id)));
+
+        // Keys within the min/max statistics but absent from the bloom filters
+        let ids = read_ids(storage, vec![col("key").eq("k5").into()]).await;
+        assert!(ids.is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_metadata_and_stats() {
         let reader = ParquetBaseFileReader::new(test_storage());
@@ -28,6 +28,7 @@ use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 
 use crate::config::table::BaseFileFormatValue;
+use crate::expr::filter::FilterExpr;
 use crate::statistics::StatisticsContainer;
 use crate::storage::Storage;
 use crate::storage::error::{Result, StorageError};
@@ -42,6 +43,10 @@ pub struct BaseFileReadOptions {
     pub projection: Option<Vec<String>>,
     /// Known base-file size in bytes, when the caller already has file metadata.
     pub known_file_size: Option<u64>,
+    /// Filters to skip the data that cannot match, e.g., Parquet row groups and pages.
+    ///
+    /// Readers do not filter the rows by them; the rows read may not match.
+    pub filters: Vec<FilterExpr>,
 }
 
 impl BaseFileReadOptions {
@@ -60,6 +65,12 @@ impl BaseFileReadOptions {
         self
     }
 
+    /// Sets the filters to skip the data that cannot match.
+    pub fn with_filters(mut self, filters: Vec<FilterExpr>) -> Self {
+        self.filters = filters;
+        self
+    }
+
     /// Sets column projection by column names.
     pub fn with_projection<I, S>(mut self, columns: I) -> Self
     where
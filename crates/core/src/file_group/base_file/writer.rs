@@ -0,0 +1,146 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::Result;
+use crate::storage::Storage;
+use arrow::record_batch::RecordBatch;
+use arrow_schema::SchemaRef;
+use bytes::Bytes;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, GzipLevel};
+use parquet::file::metadata::{KeyValue, ParquetMetaData};
+use parquet::file::properties::WriterProperties;
+use std::sync::Arc;
+
+/// The footer metadata key of the minimum record key in a base file.
+pub const FOOTER_MIN_RECORD_KEY: &str = "hoodie_min_record_key";
+
+/// The footer metadata key of the maximum record key in a base file.
+pub const FOOTER_MAX_RECORD_KEY: &str = "hoodie_max_record_key";
+
+/// The properties of the Parquet files written, including the Parquet data blocks of log files.
+pub(crate) fn writer_properties() -> WriterProperties {
+    WriterProperties::builder()
+        .set_compression(Compression::GZIP(GzipLevel::default()))
+        .build()
+}
+
+/// Writer of a Parquet base file.
+///
+/// Records are encoded in memory and written to storage as a whole on
+/// [ParquetBaseFileWriter::finish].
+pub(crate) struct ParquetBaseFileWriter {
+    storage: Arc<Storage>,
+    relative_path: String,
+    writer: ArrowWriter<Vec<u8>>,
+}
+
+impl ParquetBaseFileWriter {
+    pub fn try_new(
+        storage: Arc<Storage>,
+        relative_path: impl Into<String>,
+        schema: SchemaRef,
+    ) -> Result<Self> {
+        let writer = ArrowWriter::try_new(Vec::new(), schema, Some(writer_properties()))?;
+        Ok(Self {
+            storage,
+            relative_path: relative_path.into(),
+            writer,
+        })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.writer.write(batch)?;
+        Ok(())
+    }
+
+    /// Save the range of the record keys in the file to the footer.
+    pub fn append_record_key_range(&mut self, min_record_key: &str, max_record_key: &str) {
+        for (key, value) in [
+            (FOOTER_MIN_RECORD_KEY, min_record_key),
+            (FOOTER_MAX_RECORD_KEY, max_record_key),
+        ] {
+            self.writer
+                .append_key_value_metadata(KeyValue::new(key.to_string(), value.to_string()));
+        }
+    }
+
+    /// Write the file to storage and return its size in bytes and its Parquet metadata.
+    pub async fn finish(mut self) -> Result<(usize, ParquetMetaData)> {
+        let metadata = self.writer.finish()?;
+        let buffer = std::mem::take(self.writer.inner_mut());
+        let size = buffer.len();
+        self.storage
+            .put_file_data(&self.relative_path, Bytes::from(buffer))
+            .await?;
+        Ok((size, metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_group::base_file::parquet::ParquetBaseFileReader;
+    use arrow_array::{Int32Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use url::Url;
+
+    #[tokio::test]
+    async fn test_write_parquet_base_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage =
+            Storage::new_with_base_url(Url::from_directory_path(dir.path()).unwrap()).unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("a"), None])),
+            ],
+        )
+        .unwrap();
+
+        let path = "p1/f1-0_0-0-0_001.parquet";
+        let mut writer = ParquetBaseFileWriter::try_new(storage.clone(), path, schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.append_record_key_range("1", "2");
+        let (size, metadata) = writer.finish().await.unwrap();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(
+            size as u64,
+            std::fs::metadata(dir.path().join(path)).unwrap().len()
+        );
+
+        let reader = ParquetBaseFileReader::new(storage);
+        let metadata = reader.get_parquet_metadata(path).await.unwrap();
+        let footer = metadata.file_metadata().key_value_metadata().unwrap();
+        assert!(
+            footer
+                .iter()
+                .any(|kv| kv.key == FOOTER_MIN_RECORD_KEY && kv.value.as_deref() == Some("1"))
+        );
+        assert!(
+            footer
+                .iter()
+                .any(|kv| kv.key == FOOTER_MAX_RECORD_KEY && kv.value.as_deref() == Some("2"))
+        );
+    }
+}
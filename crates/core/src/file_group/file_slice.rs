@@ -32,10 +32,12 @@ pub struct FileSlice {
     pub base_file: BaseFile,
     pub log_files: BTreeSet<LogFile>,
     pub partition_path: String,
-    /// Column statistics from the base file's Parquet footer.
+    /// Column statistics of the base file.
     ///
-    /// Populated when data-column filters trigger footer-based pruning
-    /// on COW tables or MOR read-optimized mode. `None` otherwise.
+    /// Populated from the base file's Parquet footer when data-column filters
+    /// trigger footer-based pruning on COW tables or MOR read-optimized mode,
+    /// or from the metadata table by [crate::table::Table::load_column_stats].
+    /// `None` otherwise.
     pub base_file_column_stats: Option<StatisticsContainer>,
 }
 
@@ -28,6 +28,7 @@ use crate::file_group::log_file::log_format::LogFormatVersion;
 use crate::file_group::record_batches::RecordBatches;
 use crate::hfile::{HFileReader, HFileRecord};
 use crate::schema::delete::{avro_schema_for_delete_record, avro_schema_for_delete_record_list};
+use apache_avro::types::Value as AvroValue;
 use apache_avro::{Schema as AvroSchema, from_avro_datum};
 use bytes::Bytes;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
@@ -132,6 +133,28 @@ impl Decoder {
         Ok(batches)
     }
 
+    /// Decode the content of an Avro data block into Avro values using the writer schema
+    /// from the block header.
+    pub(crate) fn decode_avro_values(
+        mut reader: impl Read,
+        header: &HashMap<BlockMetadataKey, String>,
+    ) -> Result<Vec<AvroValue>> {
+        Decoder::validate_log_block_version(&mut reader)?;
+
+        let writer_schema = header.get(&BlockMetadataKey::Schema).ok_or_else(|| {
+            CoreError::LogBlockError("Schema not found in block header".to_string())
+        })?;
+        let writer_schema = AvroSchema::parse_str(writer_schema)?;
+
+        let mut record_count_buf = [0u8; 4];
+        reader.read_exact(&mut record_count_buf)?;
+        let record_count = u32::from_be_bytes(record_count_buf);
+
+        AvroDataBlockContentReader::new(reader, &writer_schema, record_count)
+            .map(|r| r.map_err(CoreError::from))
+            .collect()
+    }
+
     fn decode_parquet_record_content(&self, mut reader: impl Read) -> Result<RecordBatches> {
         let mut content_bytes = Vec::new();
         reader.read_to_end(&mut content_bytes)?;
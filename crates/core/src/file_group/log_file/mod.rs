@@ -30,6 +30,7 @@ mod log_block;
 mod log_format;
 pub mod reader;
 pub mod scanner;
+pub(crate) mod writer;
 
 /// Represents a Hudi log file (delta log).
 ///
@@ -29,6 +29,7 @@ use crate::file_group::log_file::log_format::{LogFormatVersion, MAGIC};
 use crate::storage::Storage;
 use crate::storage::reader::StorageReader;
 use crate::timeline::selector::InstantRange;
+use apache_avro::types::Value as AvroValue;
 use std::collections::HashMap;
 use std::io::SeekFrom;
 use std::io::{self, Read, Seek};
@@ -214,6 +215,39 @@ impl<R: Read + Seek> LogFileReader<R> {
         instant_range.not_in_range(instant_time, &self.timezone)
     }
 
+    /// Read the records of all Avro data blocks as Avro values, without converting to Arrow.
+    ///
+    /// Blocks of other types are skipped. This is used for log files whose records are
+    /// models rather than table data, e.g., archived timeline instants.
+    pub(crate) fn read_all_avro_records(&mut self) -> Result<Vec<AvroValue>> {
+        let mut records = Vec::new();
+        while self.read_magic()? {
+            let curr_pos = self
+                .reader
+                .stream_position()
+                .map_err(CoreError::ReadLogFileError)?;
+            let block_length = self.read_block_length()?;
+            let format_version = self.read_log_format_version()?;
+            let block_type = self.read_block_type(&format_version)?;
+            let header = self.read_block_metadata(BlockMetadataType::Header, &format_version)?;
+            if block_type == BlockType::AvroData {
+                let mut content_length_buf = [0u8; 8];
+                self.reader.read_exact(&mut content_length_buf)?;
+                let content_length = u64::from_be_bytes(content_length_buf);
+                let mut content = self.reader.by_ref().take(content_length);
+                records.extend(Decoder::decode_avro_values(&mut content, &header)?);
+            }
+            let target = curr_pos
+                .checked_add(8)
+                .and_then(|v| v.checked_add(block_length))
+                .ok_or_else(|| CoreError::LogFormatError("Block length overflow".to_string()))?;
+            self.reader
+                .seek(SeekFrom::Start(target))
+                .map_err(CoreError::ReadLogFileError)?;
+        }
+        Ok(records)
+    }
+
     fn read_next_block(&mut self, instant_range: &InstantRange) -> Result<Option<LogBlock>> {
         if !self.read_magic()? {
             return Ok(None);
@@ -0,0 +1,443 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::Result;
+use crate::error::CoreError;
+use crate::file_group::base_file::writer::writer_properties;
+use crate::file_group::log_file::log_block::{
+    BlockMetadataKey, BlockType, CommandBlock, LogBlockVersion,
+};
+use crate::file_group::log_file::log_format::{LogFormatVersion, MAGIC};
+use crate::hfile::{CompressionCodec, HFileWriter};
+use crate::schema::delete::avro_schema_for_delete_record_list;
+use crate::storage::Storage;
+use apache_avro::types::Value as AvroValue;
+use apache_avro::{Schema as AvroSchema, to_avro_datum};
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use parquet::arrow::ArrowWriter;
+use parquet::file::metadata::ParquetMetaData;
+use std::sync::Arc;
+
+/// Writer of a log file in [LogFormatVersion::V1].
+///
+/// Blocks are buffered in memory and written to storage as a whole on [LogFileWriter::finish],
+/// as object stores do not support appending to existing files.
+#[derive(Debug)]
+pub(crate) struct LogFileWriter {
+    storage: Arc<Storage>,
+    relative_path: String,
+    buffer: Vec<u8>,
+}
+
+impl LogFileWriter {
+    pub fn new(storage: Arc<Storage>, relative_path: impl Into<String>) -> Self {
+        Self {
+            storage,
+            relative_path: relative_path.into(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Append a block with the given type, header, and encoded content.
+    pub fn append_block(
+        &mut self,
+        block_type: BlockType,
+        header: &[(BlockMetadataKey, String)],
+        content: &[u8],
+    ) {
+        self.buffer
+            .extend_from_slice(&encode_log_block(block_type, header, content));
+    }
+
+    /// Append an Avro data block holding the given records.
+    pub fn append_avro_data_block(
+        &mut self,
+        instant_time: &str,
+        schema: &AvroSchema,
+        records: Vec<AvroValue>,
+    ) -> Result<()> {
+        let content = encode_avro_data_block_content(schema, records)?;
+        let header = [
+            (BlockMetadataKey::InstantTime, instant_time.to_string()),
+            (BlockMetadataKey::Schema, schema.canonical_form()),
+        ];
+        self.append_block(BlockType::AvroData, &header, &content);
+        Ok(())
+    }
+
+    /// Append a Parquet data block holding the records, and return the Parquet metadata of
+    /// the block content.
+    pub fn append_parquet_data_block(
+        &mut self,
+        instant_time: &str,
+        schema: &AvroSchema,
+        records: &RecordBatch,
+    ) -> Result<ParquetMetaData> {
+        let mut writer =
+            ArrowWriter::try_new(Vec::new(), records.schema(), Some(writer_properties()))?;
+        writer.write(records)?;
+        let metadata = writer.finish()?;
+        let content = std::mem::take(writer.inner_mut());
+
+        let header = [
+            (BlockMetadataKey::InstantTime, instant_time.to_string()),
+            (BlockMetadataKey::Schema, schema.canonical_form()),
+        ];
+        self.append_block(BlockType::ParquetData, &header, &content);
+        Ok(metadata)
+    }
+
+    /// Append an HFile data block holding the given key-value records.
+    ///
+    /// The records are sorted by keys; the values are expected to be Avro-serialized
+    /// with the given schema, which is also saved to the file info of the HFile.
+    pub fn append_hfile_data_block(
+        &mut self,
+        instant_time: &str,
+        schema: &AvroSchema,
+        mut records: Vec<(String, Vec<u8>)>,
+    ) -> Result<()> {
+        records.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let schema_str = schema.canonical_form();
+        let mut hfile_writer = HFileWriter::new().with_compression_codec(CompressionCodec::Gzip);
+        for (key, value) in &records {
+            hfile_writer
+                .append(key.as_bytes(), value)
+                .map_err(|e| CoreError::HFile(e.to_string()))?;
+        }
+        hfile_writer.append_avro_schema(schema);
+        let content = hfile_writer
+            .finish()
+            .map_err(|e| CoreError::HFile(e.to_string()))?;
+
+        let header = [
+            (BlockMetadataKey::InstantTime, instant_time.to_string()),
+            (BlockMetadataKey::Schema, schema_str),
+        ];
+        self.append_block(BlockType::HfileData, &header, &content);
+        Ok(())
+    }
+
+    /// Append a delete block holding the given records, each being a `HoodieDeleteRecord` of the
+    /// record key, partition path, and ordering value of a deleted record.
+    pub fn append_delete_block(
+        &mut self,
+        instant_time: &str,
+        delete_records: Vec<AvroValue>,
+    ) -> Result<()> {
+        let content = encode_delete_block_content(delete_records)?;
+        let header = [(BlockMetadataKey::InstantTime, instant_time.to_string())];
+        self.append_block(BlockType::Delete, &header, &content);
+        Ok(())
+    }
+
+    /// Append a command block that rolls back the blocks written by the target instant.
+    pub fn append_rollback_command_block(&mut self, instant_time: &str, target_instant_time: &str) {
+        let header = [
+            (BlockMetadataKey::InstantTime, instant_time.to_string()),
+            (
+                BlockMetadataKey::TargetInstantTime,
+                target_instant_time.to_string(),
+            ),
+            (
+                BlockMetadataKey::CommandBlockType,
+                (CommandBlock::Rollback as u32).to_string(),
+            ),
+        ];
+        self.append_block(BlockType::Command, &header, &[]);
+    }
+
+    /// Write all appended blocks to storage and return the size of the file in bytes.
+    pub async fn finish(self) -> Result<usize> {
+        let size = self.buffer.len();
+        self.storage
+            .put_file_data(&self.relative_path, Bytes::from(self.buffer))
+            .await?;
+        Ok(size)
+    }
+}
+
+/// Encode a log block with [MAGIC] and all the fields of [LogFormatVersion::V1].
+///
+/// Layout:
+/// 1. 6 bytes: [MAGIC]
+/// 2. 8 bytes: length of the block excluding the magic and this field
+/// 3. 4 bytes: [LogFormatVersion]
+/// 4. 4 bytes: [BlockType]
+/// 5. header: number of entries followed by (key ordinal, value length, value) entries
+/// 6. 8 bytes: content length, followed by the content
+/// 7. footer: encoded the same way as the header, always empty
+/// 8. 8 bytes: total length of the block including the magic
+pub(crate) fn encode_log_block(
+    block_type: BlockType,
+    header: &[(BlockMetadataKey, String)],
+    content: &[u8],
+) -> Vec<u8> {
+    let mut block = Vec::with_capacity(content.len() + 128);
+    block.extend_from_slice(&(LogFormatVersion::V1 as u32).to_be_bytes());
+    block.extend_from_slice(&(block_type as u32).to_be_bytes());
+    encode_block_metadata(&mut block, header);
+    block.extend_from_slice(&(content.len() as u64).to_be_bytes());
+    block.extend_from_slice(content);
+    encode_block_metadata(&mut block, &[]);
+
+    let block_length = block.len() as u64 + 8;
+    let total_length = MAGIC.len() as u64 + 8 + block_length;
+    let mut bytes = Vec::with_capacity(total_length as usize);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&block_length.to_be_bytes());
+    bytes.extend_from_slice(&block);
+    bytes.extend_from_slice(&total_length.to_be_bytes());
+    bytes
+}
+
+fn encode_block_metadata(buf: &mut Vec<u8>, metadata: &[(BlockMetadataKey, String)]) {
+    buf.extend_from_slice(&(metadata.len() as u32).to_be_bytes());
+    for (key, value) in metadata {
+        buf.extend_from_slice(&(key.clone() as u32).to_be_bytes());
+        buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        buf.extend_from_slice(value.as_bytes());
+    }
+}
+
+/// Encode the content of an Avro data block in [LogBlockVersion::V3].
+///
+/// Layout: 4 bytes for the block version, 4 bytes for the number of records,
+/// then each record as 4 bytes of its length followed by its Avro binary encoding.
+pub(crate) fn encode_avro_data_block_content(
+    schema: &AvroSchema,
+    records: Vec<AvroValue>,
+) -> Result<Vec<u8>> {
+    let mut content = Vec::new();
+    content.extend_from_slice(&(LogBlockVersion::V3 as u32).to_be_bytes());
+    content.extend_from_slice(&(records.len() as u32).to_be_bytes());
+    for record in records {
+        let datum = to_avro_datum(schema, record)?;
+        content.extend_from_slice(&(datum.len() as u32).to_be_bytes());
+        content.extend_from_slice(&datum);
+    }
+    Ok(content)
+}
+
+/// Encode the content of a delete block in [LogBlockVersion::V3].
+///
+/// Layout: 4 bytes for the block version, 4 bytes for the length of the records, then the
+/// records as a `HoodieDeleteRecordList` in Avro binary encoding.
+pub(crate) fn encode_delete_block_content(delete_records: Vec<AvroValue>) -> Result<Vec<u8>> {
+    let list = AvroValue::Record(vec![(
+        "deleteRecordList".to_string(),
+        AvroValue::Array(delete_records),
+    )]);
+    let datum = to_avro_datum(avro_schema_for_delete_record_list()?, list)?;
+    let mut content = Vec::new();
+    content.extend_from_slice(&(LogBlockVersion::V3 as u32).to_be_bytes());
+    content.extend_from_slice(&(datum.len() as u32).to_be_bytes());
+    content.extend_from_slice(&datum);
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HudiConfigs;
+    use crate::file_group::log_file::reader::LogFileReader;
+    use crate::storage::util::parse_uri;
+    use crate::timeline::selector::InstantRange;
+    use apache_avro::types::Record;
+    use arrow_array::StringArray;
+    use arrow_schema::{DataType, Field, Schema as ArrowSchema};
+
+    #[tokio::test]
+    async fn test_write_and_read_avro_data_blocks() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let base_url = parse_uri(dir.path().to_str().unwrap())?;
+        let storage = Storage::new_with_base_url(base_url)?;
+        let schema = AvroSchema::parse_str(
+            r#"{"type":"record","name":"r","fields":[{"name":"id","type":"string"},{"name":"ts","type":"long"}]}"#,
+        )?;
+
+        let mut writer = LogFileWriter::new(storage.clone(), "dir/.f1_001.log.1_0-0-0");
+        for (instant_time, ids) in [
+            ("20240101000000001", vec!["a", "b"]),
+            ("20240101000000002", vec!["c"]),
+        ] {
+            let records = ids
+                .into_iter()
+                .map(|id| {
+                    let mut record = Record::new(&schema).unwrap();
+                    record.put("id", id);
+                    record.put("ts", 1i64);
+                    record.into()
+                })
+                .collect();
+            writer.append_avro_data_block(instant_time, &schema, records)?;
+        }
+        let size = writer.finish().await?;
+        let data = storage.get_file_data("dir/.f1_001.log.1_0-0-0").await?;
+        assert_eq!(data.len(), size);
+
+        let hudi_configs = Arc::new(HudiConfigs::empty());
+        let mut reader =
+            LogFileReader::new(hudi_configs, storage, "dir/.f1_001.log.1_0-0-0").await?;
+        let blocks = reader.read_all_blocks(&InstantRange::up_to("20240101000000002", "utc"))?;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].instant_time()?, "20240101000000001");
+        assert_eq!(blocks[0].record_batches().unwrap().num_data_rows(), 2);
+        assert_eq!(blocks[1].instant_time()?, "20240101000000002");
+        assert_eq!(blocks[1].record_batches().unwrap().num_data_rows(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_hfile_data_block() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let base_url = parse_uri(dir.path().to_str().unwrap())?;
+        let storage = Storage::new_with_base_url(base_url)?;
+        let schema = AvroSchema::parse_str(
+            r#"{"type":"record","name":"r","fields":[{"name":"v","type":"long"}]}"#,
+        )?;
+
+        let path = "files/.files-0000-0_20240101000000001.log.1_0-0-0";
+        let mut writer = LogFileWriter::new(storage.clone(), path);
+        let records = ["k2", "k1"]
+            .into_iter()
+            .map(|k| (k.to_string(), vec![2u8]))
+            .collect();
+        writer.append_hfile_data_block("20240101000000001", &schema, records)?;
+        writer.finish().await?;
+
+        let hudi_configs = Arc::new(HudiConfigs::empty());
+        let mut reader = LogFileReader::new(hudi_configs, storage, path).await?;
+        let blocks = reader.read_all_blocks(&InstantRange::up_to("20240101000000001", "utc"))?;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].instant_time()?, "20240101000000001");
+        let keys: Vec<&str> = blocks[0]
+            .hfile_records()
+            .unwrap()
+            .iter()
+            .map(|r| r.key_as_str().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["k1", "k2"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_parquet_data_block() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let base_url = parse_uri(dir.path().to_str().unwrap())?;
+        let storage = Storage::new_with_base_url(base_url)?;
+        let schema = AvroSchema::parse_str(
+            r#"{"type":"record","name":"r","fields":[{"name":"id","type":"string"}]}"#,
+        )?;
+        let records = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![Field::new(
+                "id",
+                DataType::Utf8,
+                false,
+            )])),
+            vec![Arc::new(StringArray::from(vec!["a", "b", "c"]))],
+        )?;
+
+        let path = "p1/.f1_20240101000000001.log.1_0-0-0";
+        let mut writer = LogFileWriter::new(storage.clone(), path);
+        let metadata = writer.append_parquet_data_block("20240101000000001", &schema, &records)?;
+        assert_eq!(metadata.file_metadata().num_rows(), 3);
+        writer.finish().await?;
+
+        let hudi_configs = Arc::new(HudiConfigs::empty());
+        let mut reader = LogFileReader::new(hudi_configs, storage, path).await?;
+        let blocks = reader.read_all_blocks(&InstantRange::up_to("20240101000000001", "utc"))?;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].block_type, BlockType::ParquetData);
+        let batches = blocks[0].record_batches().unwrap();
+        assert_eq!(batches.num_data_rows(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_rollback_command_block() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let base_url = parse_uri(dir.path().to_str().unwrap())?;
+        let storage = Storage::new_with_base_url(base_url)?;
+
+        let path = ".f1_001.log.2_0-0-0";
+        let mut writer = LogFileWriter::new(storage.clone(), path);
+        writer.append_rollback_command_block("20240101000000003", "20240101000000002");
+        writer.finish().await?;
+
+        let hudi_configs = Arc::new(HudiConfigs::empty());
+        let mut reader = LogFileReader::new(hudi_configs, storage, path).await?;
+        let blocks = reader.read_all_blocks(&InstantRange::up_to("20240101000000003", "utc"))?;
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].is_rollback_block());
+        assert_eq!(blocks[0].instant_time()?, "20240101000000003");
+        assert_eq!(blocks[0].target_instant_time()?, "20240101000000002");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_delete_block() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let base_url = parse_uri(dir.path().to_str().unwrap())?;
+        let storage = Storage::new_with_base_url(base_url)?;
+
+        let path = ".f1_20240101000000001.log.1_0-0-0";
+        let delete_record = |key: &str, ordering: i64| {
+            AvroValue::Record(vec![
+                (
+                    "recordKey".to_string(),
+                    AvroValue::Union(1, Box::new(AvroValue::String(key.to_string()))),
+                ),
+                (
+                    "partitionPath".to_string(),
+                    AvroValue::Union(1, Box::new(AvroValue::String("p1".to_string()))),
+                ),
+                (
+                    "orderingVal".to_string(),
+                    AvroValue::Union(2, Box::new(AvroValue::Long(ordering))),
+                ),
+            ])
+        };
+        let mut writer = LogFileWriter::new(storage.clone(), path);
+        writer.append_delete_block(
+            "20240101000000001",
+            vec![delete_record("k1", 1), delete_record("k2", 2)],
+        )?;
+        writer.finish().await?;
+
+        let hudi_configs = Arc::new(HudiConfigs::empty());
+        let mut reader = LogFileReader::new(hudi_configs, storage, path).await?;
+        let blocks = reader.read_all_blocks(&InstantRange::up_to("20240101000000001", "utc"))?;
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].is_delete_block());
+        let batches = blocks[0].record_batches().unwrap();
+        assert_eq!(batches.num_delete_rows(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_log_block_lengths() {
+        let bytes = encode_log_block(BlockType::Command, &[], &[]);
+        assert_eq!(&bytes[..6], MAGIC);
+        let block_length = u64::from_be_bytes(bytes[6..14].try_into().unwrap());
+        assert_eq!(block_length as usize, bytes.len() - 14);
+        let total_length = u64::from_be_bytes(bytes[bytes.len() - 8..].try_into().unwrap());
+        assert_eq!(total_length as usize, bytes.len());
+    }
+}
@@ -26,6 +26,7 @@ pub mod file_slice;
 pub mod log_file;
 pub mod reader;
 pub mod record_batches;
+pub(crate) mod virtual_meta_fields;
 
 use crate::Result;
 use crate::error::CoreError;
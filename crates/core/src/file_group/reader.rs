@@ -23,7 +23,7 @@ use crate::config::table::{BaseFileFormatValue, HudiTableConfig};
 use crate::error::CoreError;
 use crate::error::CoreError::ReadFileSliceError;
 use crate::expr::filter::{
-    Filter, SchemableFilter, filters_to_row_mask, validate_fields_against_schemas,
+    Filter, FilterExpr, SchemableFilter, filters_to_row_mask, validate_fields_against_schemas,
 };
 use crate::file_group::base_file::reader::{
     BaseFileReadOptions, BaseFileReader, create_base_file_reader,
@@ -31,23 +31,26 @@ use crate::file_group::base_file::reader::{
 use crate::file_group::file_slice::FileSlice;
 use crate::file_group::log_file::scanner::{LogFileScanner, ScanResult};
 use crate::file_group::record_batches::RecordBatches;
+use crate::file_group::virtual_meta_fields::VirtualMetaFields;
 use crate::hfile::{HFileReader, HFileRecord};
 use crate::merge::record_merger::RecordMerger;
 use crate::metadata::merger::FilesPartitionMerger;
 use crate::metadata::meta_field::MetaField;
-use crate::metadata::table_record::FilesPartitionRecord;
+use crate::metadata::table_record::{FilesPartitionRecord, decode_avro_value};
 use crate::storage::Storage;
 use crate::storage::error::StorageError;
 use crate::table::ReadOptions;
 use crate::table::builder::OptionResolver;
 use crate::timeline::selector::InstantRange;
 use crate::util::arrow::project_batch_by_names;
+use apache_avro::Schema as AvroSchema;
+use apache_avro::types::Value as AvroValue;
 use arrow::compute::and;
 use arrow::compute::filter_record_batch;
 use arrow_array::{BooleanArray, RecordBatch};
 use futures::stream::BoxStream;
 use futures::{StreamExt, TryFutureExt};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -165,6 +168,16 @@ impl FileGroupReader {
             .map_err(|e| ReadFileSliceError(format!("{e}")))
     }
 
+    /// Returns the meta fields to synthesize on read if the table does not populate them.
+    ///
+    /// Metadata tables never have the meta fields.
+    fn virtual_meta_fields(&self) -> Result<Option<VirtualMetaFields>> {
+        if self.is_metadata_table() {
+            return Ok(None);
+        }
+        VirtualMetaFields::from_configs(&self.hudi_configs)
+    }
+
     /// Internal: read base file + apply commit-time filter, no [`ReadOptions`] applied.
     /// Used by the merge path so options aren't applied prematurely before merging
     /// with log files.
@@ -275,6 +288,12 @@ impl FileGroupReader {
             merger.merge_record_batches(all_batches)?
         };
 
+        // The records of the log files belong to the same file slice, so they have the
+        // partition path and the file name of the base file as well.
+        let merged = match self.virtual_meta_fields()? {
+            Some(virtual_meta_fields) => virtual_meta_fields.prepend_to(merged, base_file_path)?,
+            None => merged,
+        };
         apply_eager_options(&options, merged)
     }
 
@@ -409,8 +428,9 @@ impl FileGroupReader {
     /// Supports the following [ReadOptions]:
     /// - `batch_size`: Controls the number of rows per batch
     /// - `projection`: Pushes column selection to the base-file reader level
-    /// - `filters`: Applied as a row-level mask after reading each batch (in addition to
-    ///   any pruning that already happened upstream)
+    /// - `filters`: Passed to the base-file reader to skip row groups and pages that
+    ///   cannot match, and applied as a row-level mask after reading each batch (in
+    ///   addition to any pruning that already happened upstream)
     async fn read_base_file_stream(
         &self,
         relative_path: &str,
@@ -426,6 +446,14 @@ impl FileGroupReader {
         if let Some(size) = known_file_size {
             read_options = read_options.with_known_file_size(size);
         }
+        // The base file is read without merging log files here, so the records that
+        // cannot match the filters can be skipped. The statistics of the meta fields do
+        // not apply if they are synthesized on read.
+        let virtual_meta_fields = self.virtual_meta_fields()?;
+        read_options = read_options.with_filters(match virtual_meta_fields {
+            Some(_) => VirtualMetaFields::filters_without_meta_fields(&options.filters),
+            None => options.filters.clone(),
+        });
 
         // If projection is set, widen the base file read to also include any columns
         // we need post-read but the user didn't request:
@@ -465,7 +493,7 @@ impl FileGroupReader {
         let final_projection = options.projection.clone();
         let read_projection = options.projection.as_ref().map(|proj| {
             let mut combined: Vec<String> = proj.clone();
-            for filter in &options.filters {
+            for filter in options.filters.iter().flat_map(FilterExpr::filters) {
                 let field = filter.field.as_str();
                 if dropped_partition_columns.iter().any(|p| p == field) {
                     continue;
@@ -480,7 +508,11 @@ impl FileGroupReader {
                     combined.push(commit_time);
                 }
             }
-            combined
+            // The meta fields to synthesize are not read, except for the record key fields.
+            match &virtual_meta_fields {
+                Some(virtual_meta_fields) => virtual_meta_fields.read_projection(&combined),
+                None => combined,
+            }
         });
         if let Some(ref cols) = read_projection {
             read_options = read_options.with_projection(cols.clone());
@@ -493,6 +525,7 @@ impl FileGroupReader {
         // Validate once on first batch so typoed filter columns surface as errors
         // rather than silent no-ops in `filters_to_row_mask`.
         let validated = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let virtual_meta_fields = Arc::new(virtual_meta_fields);
 
         let reader = self.reader_for_path(&path)?;
         let base_stream = reader
@@ -506,12 +539,23 @@ impl FileGroupReader {
             let filters = filters.clone();
             let final_projection = final_projection.clone();
             let validated = validated.clone();
+            let virtual_meta_fields = virtual_meta_fields.clone();
+            let path = path.clone();
             async move {
                 match result {
                     Err(e) => Some(Err(ReadFileSliceError(format!(
                         "Failed to read batch: {e:?}"
                     )))),
                     Ok(batch) => {
+                        let batch = match virtual_meta_fields.as_ref() {
+                            Some(virtual_meta_fields) => {
+                                match virtual_meta_fields.prepend_to(batch, &path) {
+                                    Err(e) => return Some(Err(e)),
+                                    Ok(b) => b,
+                                }
+                            }
+                            None => batch,
+                        };
                         if !validated.load(std::sync::atomic::Ordering::Relaxed) {
                             if let Err(e) =
                                 validate_fields_against_schemas(&filters, [batch.schema().as_ref()])
@@ -574,6 +618,63 @@ impl FileGroupReader {
         file_slice: &FileSlice,
         keys: &[&str],
     ) -> Result<HashMap<String, FilesPartitionRecord>> {
+        let mut hfile_keys = keys.to_vec();
+        hfile_keys.sort();
+        let (schema, base_records, log_records) = self
+            .read_metadata_table_hfile_records(file_slice, &hfile_keys)
+            .await?;
+
+        let merger = FilesPartitionMerger::new(schema);
+        merger.merge_for_keys(&base_records, &log_records, &hfile_keys)
+    }
+
+    /// Read records from a file slice of the metadata table column stats partition.
+    ///
+    /// Records of the same key in log files replace those in the base file, so the latest
+    /// record of a key is returned, including the records of deleted stats.
+    ///
+    /// # Arguments
+    /// * `file_slice` - The file slice to read from
+    /// * `keys` - Only read records with these keys. If empty, reads all records.
+    ///
+    /// # Returns
+    /// HashMap of record keys to Avro-deserialized `HoodieMetadataRecord` values.
+    pub(crate) async fn read_metadata_table_column_stats(
+        &self,
+        file_slice: &FileSlice,
+        keys: &[&str],
+    ) -> Result<HashMap<String, AvroValue>> {
+        let mut hfile_keys = keys.to_vec();
+        hfile_keys.sort();
+        let (schema, base_records, log_records) = self
+            .read_metadata_table_hfile_records(file_slice, &hfile_keys)
+            .await?;
+
+        let key_set: HashSet<&str> = hfile_keys.iter().copied().collect();
+        let mut records = HashMap::new();
+        for record in base_records.iter().chain(log_records.iter()) {
+            let Some(key) = record.key_as_str() else {
+                continue;
+            };
+            if !key_set.is_empty() && !key_set.contains(key) {
+                continue;
+            }
+            let value = decode_avro_value(record.value(), &schema)?;
+            records.insert(key.to_string(), value);
+        }
+        Ok(records)
+    }
+
+    /// Read the HFile records of a metadata table file slice.
+    ///
+    /// Returns the Avro schema of the record values, the records of the base file, and the
+    /// records of the log files in chronological order. Base file records are looked up by
+    /// `sorted_keys`, or all collected if it is empty; log file records are not filtered.
+    async fn read_metadata_table_hfile_records(
+        &self,
+        file_slice: &FileSlice,
+        sorted_keys: &[&str],
+    ) -> Result<(AvroSchema, Vec<HFileRecord>, Vec<HFileRecord>)> {
         let base_file_path = file_slice.base_file_relative_path()?;
         let log_file_paths: Vec<String> = if file_slice.has_log_file() {
             file_slice
@@ -601,21 +702,13 @@ impl FileGroupReader {
             .ok_or_else(|| ReadFileSliceError("No Avro schema found in HFile".to_string()))?
             .clone();
 
-        let hfile_keys: Vec<&str> = if keys.is_empty() {
-            vec![]
-        } else {
-            let mut sorted = keys.to_vec();
-            sorted.sort();
-            sorted
-        };
-
-        let base_records: Vec<HFileRecord> = if hfile_keys.is_empty() {
+        let base_records: Vec<HFileRecord> = if sorted_keys.is_empty() {
             hfile_reader.collect_records().map_err(|e| {
                 ReadFileSliceError(format!("Failed to collect HFile records: {e:?}"))
             })?
         } else {
             hfile_reader
-                .lookup_records(&hfile_keys)
+                .lookup_records(sorted_keys)
                 .map_err(|e| ReadFileSliceError(format!("Failed to lookup HFile records: {e:?}")))?
                 .into_iter()
                 .filter_map(|(_, r)| r)
@@ -641,8 +734,7 @@ impl FileGroupReader {
             }
         };
 
-        let merger = FilesPartitionMerger::new(schema);
-        merger.merge_for_keys(&base_records, &log_records, &hfile_keys)
+        Ok((schema, base_records, log_records))
     }
 }
 
@@ -726,7 +818,7 @@ fn apply_commit_time_filter(hudi_configs: &HudiConfigs, batch: RecordBatch) -> R
 ///
 /// Filters whose field is not present in the batch (e.g., partition columns already
 /// pruned upstream) are skipped — see [`crate::expr::filter::filters_to_row_mask`].
-fn apply_filter_mask(filters: &[Filter], batch: RecordBatch) -> Result<RecordBatch> {
+fn apply_filter_mask(filters: &[FilterExpr], batch: RecordBatch) -> Result<RecordBatch> {
     if filters.is_empty() {
         return Ok(batch);
     }
@@ -0,0 +1,277 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Virtual Hudi meta fields for the tables that do not populate them.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray, new_null_array};
+use arrow_schema::{DataType, Schema};
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::table::HudiTableConfig::{PopulatesMetaFields, RecordKeyFields};
+use crate::error::CoreError;
+use crate::expr::filter::FilterExpr;
+use crate::keygen::builtin::BuiltinKeyGenerator;
+use crate::metadata::meta_field::MetaField;
+
+/// Synthesizes the Hudi meta fields of the records read from a file slice, for the tables
+/// with [PopulatesMetaFields] disabled, whose files have the meta fields as nulls or not at all.
+///
+/// - `_hoodie_record_key` is generated from the record key fields by the key generator.
+/// - `_hoodie_partition_path` is the partition path of the file slice.
+/// - `_hoodie_file_name` is the name of the base file of the file slice.
+/// - `_hoodie_commit_time` and `_hoodie_commit_seqno` are nulls, as they cannot be derived.
+///
+/// The records then have the schema of [MetaField::virtual_schema] followed by the data fields.
+#[derive(Clone, Debug)]
+pub(crate) struct VirtualMetaFields {
+    /// The key generator for the record keys, or `None` if the table has no record key
+    /// fields or uses a key generator class that is not built in, where the record keys
+    /// are nulls.
+    key_generator: Option<BuiltinKeyGenerator>,
+    record_key_fields: Vec<String>,
+}
+
+impl VirtualMetaFields {
+    /// Returns `None` if the table populates the meta fields.
+    pub fn from_configs(hudi_configs: &HudiConfigs) -> Result<Option<Self>> {
+        let populates_meta_fields: bool = hudi_configs.get_or_default(PopulatesMetaFields).into();
+        if populates_meta_fields {
+            return Ok(None);
+        }
+
+        let key_generator = match BuiltinKeyGenerator::for_record_keys(hudi_configs) {
+            Ok(key_generator) => Some(key_generator),
+            Err(CoreError::Unsupported(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let record_key_fields: Vec<String> = match key_generator {
+            Some(_) => hudi_configs.get_or_default(RecordKeyFields).into(),
+            None => Vec::new(),
+        };
+        Ok(Some(Self {
+            key_generator,
+            record_key_fields: record_key_fields
+                .into_iter()
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect(),
+        }))
+    }
+
+    /// Returns the columns to read from the files for the projection of the records, i.e.,
+    /// without the meta fields, and with the record key fields if the record key is projected.
+    pub fn read_projection(&self, projection: &[String]) -> Vec<String> {
+        let mut columns: Vec<String> = projection
+            .iter()
+            .filter(|c| MetaField::from_str(c).is_err())
+            .cloned()
+            .collect();
+        if projection
+            .iter()
+            .any(|c| c == MetaField::RecordKey.as_ref())
+        {
+            for field in &self.record_key_fields {
+                if !columns.contains(field) {
+                    columns.push(field.clone());
+                }
+            }
+        }
+        columns
+    }
+
+    /// Returns the filters that do not reference the meta fields, which are the only ones
+    /// that the statistics of the files apply to.
+    pub fn filters_without_meta_fields(filters: &[FilterExpr]) -> Vec<FilterExpr> {
+        filters
+            .iter()
+            .filter(|expr| {
+                expr.filters()
+                    .iter()
+                    .all(|f| MetaField::from_str(&f.field).is_err())
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the records with the meta fields prepended, replacing the ones in the records,
+    /// given the path of the base file relative to the table base path.
+    ///
+    /// The record keys are nulls if any of the record key fields is not in the records, e.g.,
+    /// when they are not read for a projection without the record key.
+    pub fn prepend_to(&self, records: RecordBatch, base_file_path: &str) -> Result<RecordBatch> {
+        let num_rows = records.num_rows();
+        let (partition_path, file_name) = base_file_path
+            .rsplit_once('/')
+            .unwrap_or(("", base_file_path));
+
+        let has_record_key_fields = self
+            .record_key_fields
+            .iter()
+            .all(|field| records.column_by_name(field).is_some());
+        let record_keys: ArrayRef = match &self.key_generator {
+            Some(key_generator) if has_record_key_fields => {
+                Arc::new(StringArray::from(key_generator.get_record_keys(&records)?))
+            }
+            _ => new_null_array(&DataType::Utf8, num_rows),
+        };
+
+        let meta_schema = MetaField::virtual_schema();
+        let mut fields = meta_schema.fields().to_vec();
+        let mut columns: Vec<ArrayRef> = vec![
+            new_null_array(&DataType::Utf8, num_rows),
+            new_null_array(&DataType::Utf8, num_rows),
+            record_keys,
+            Arc::new(StringArray::from(vec![partition_path; num_rows])),
+            Arc::new(StringArray::from(vec![file_name; num_rows])),
+        ];
+        for (field, column) in records.schema().fields().iter().zip(records.columns()) {
+            if MetaField::from_str(field.name()).is_err() {
+                fields.push(field.clone());
+                columns.push(column.clone());
+            }
+        }
+
+        let schema = Schema::new_with_metadata(fields, records.schema().metadata().clone());
+        Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::filter::col;
+    use arrow_array::{Array, Int64Array};
+    use arrow_schema::Field;
+
+    fn virtual_meta_fields() -> VirtualMetaFields {
+        let configs = HudiConfigs::new([
+            ("hoodie.populate.meta.fields", "false"),
+            ("hoodie.table.recordkey.fields", "id"),
+            ("hoodie.table.partition.fields", "city"),
+        ]);
+        VirtualMetaFields::from_configs(&configs).unwrap().unwrap()
+    }
+
+    fn records(with_meta_fields: bool) -> RecordBatch {
+        let mut fields = Vec::new();
+        let mut columns: Vec<ArrayRef> = Vec::new();
+        if with_meta_fields {
+            for field in MetaField::virtual_schema().fields() {
+                fields.push(Arc::new(Field::new(field.name(), DataType::Utf8, true)));
+                columns.push(new_null_array(&DataType::Utf8, 2));
+            }
+        }
+        fields.push(Arc::new(Field::new("id", DataType::Int64, false)));
+        columns.push(Arc::new(Int64Array::from(vec![1, 2])));
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+    }
+
+    fn string_values(records: &RecordBatch, name: &str) -> Vec<Option<String>> {
+        let column = records.column_by_name(name).unwrap();
+        let column = column.as_any().downcast_ref::<StringArray>().unwrap();
+        column.iter().map(|v| v.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn test_from_configs() {
+        let configs = HudiConfigs::new([("hoodie.table.recordkey.fields", "id")]);
+        assert!(VirtualMetaFields::from_configs(&configs).unwrap().is_none());
+
+        let configs = HudiConfigs::new([("hoodie.populate.meta.fields", "false")]);
+        let fields = VirtualMetaFields::from_configs(&configs).unwrap().unwrap();
+        assert!(fields.key_generator.is_none());
+        assert!(fields.record_key_fields.is_empty());
+    }
+
+    #[test]
+    fn test_read_projection() {
+        let fields = virtual_meta_fields();
+        let projection = |columns: &[&str]| {
+            let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+            fields.read_projection(&columns)
+        };
+        assert_eq!(
+            projection(&["_hoodie_file_name", "name", "_hoodie_commit_time"]),
+            vec!["name"]
+        );
+        assert_eq!(
+            projection(&["_hoodie_record_key", "name"]),
+            vec!["name", "id"]
+        );
+        assert_eq!(projection(&["id", "_hoodie_record_key"]), vec!["id"]);
+    }
+
+    #[test]
+    fn test_filters_without_meta_fields() {
+        let filters: Vec<FilterExpr> = vec![
+            col("id").eq("1").into(),
+            col("_hoodie_record_key").eq("1").into(),
+            FilterExpr::Or(vec![
+                col("id").eq("2").into(),
+                col("_hoodie_file_name").is_not_null().into(),
+            ]),
+        ];
+        assert_eq!(
+            VirtualMetaFields::filters_without_meta_fields(&filters),
+            vec![col("id").eq("1").into()]
+        );
+    }
+
+    #[test]
+    fn test_prepend_to_records() {
+        let fields = virtual_meta_fields();
+        for with_meta_fields in [false, true] {
+            let records = fields
+                .prepend_to(records(with_meta_fields), "city=sf/f1_0-1-2_001.parquet")
+                .unwrap();
+            assert_eq!(
+                records.schema().fields()[..5],
+                MetaField::virtual_schema().fields()[..]
+            );
+            assert_eq!(records.num_columns(), 6);
+            assert_eq!(
+                string_values(&records, "_hoodie_record_key"),
+                vec![Some("1".to_string()), Some("2".to_string())]
+            );
+            assert_eq!(
+                string_values(&records, "_hoodie_partition_path"),
+                vec![Some("city=sf".to_string()); 2]
+            );
+            assert_eq!(
+                string_values(&records, "_hoodie_file_name"),
+                vec![Some("f1_0-1-2_001.parquet".to_string()); 2]
+            );
+            assert_eq!(records.column(0).null_count(), 2);
+            assert_eq!(records.column(1).null_count(), 2);
+        }
+
+        // Non-partitioned, and without the record key fields read
+        let records = fields
+            .prepend_to(records(false).project(&[]).unwrap(), "f1_0-1-2_001.parquet")
+            .unwrap();
+        assert_eq!(records.column(2).null_count(), records.num_rows());
+        assert_eq!(
+            string_values(&records, "_hoodie_partition_path"),
+            vec![Some(String::new()); records.num_rows()]
+        );
+    }
+}
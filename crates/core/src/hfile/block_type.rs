@@ -24,7 +24,7 @@ use crate::hfile::error::{HFileError, Result};
 pub const MAGIC_LENGTH: usize = 8;
 
 /// HFile block types with their magic byte sequences.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HFileBlockType {
     /// Data block containing key-value pairs
     Data,
@@ -19,8 +19,10 @@
 //! Compression codec support for HFile blocks.
 
 use crate::hfile::error::{HFileError, Result};
+use flate2::Compression;
 use flate2::read::GzDecoder;
-use std::io::Read;
+use flate2::write::GzEncoder;
+use std::io::{Read, Write};
 
 /// Compression codec IDs used in HFile.
 /// These IDs are stored in the HFile trailer and must not change.
@@ -58,6 +60,21 @@ impl CompressionCodec {
         }
     }
 
+    /// Compress data using this codec.
+    ///
+    /// Only [CompressionCodec::None] and [CompressionCodec::Gzip] are supported for writing.
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            CompressionCodec::None => Ok(data.to_vec()),
+            CompressionCodec::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?)
+            }
+            _ => Err(HFileError::UnsupportedCompression(*self as u32)),
+        }
+    }
+
     /// Decompress data using this codec.
     ///
     /// # Arguments
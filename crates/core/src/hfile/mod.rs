@@ -16,7 +16,7 @@
  * specific language governing permissions and limitations
  * under the License.
  */
-//! HFile reader implementation.
+//! HFile reader and writer implementation.
 //!
 //! HFile is an SSTable based row-oriented file format, optimized for
 //! range scans and point lookups. HFile is used as the base file format
@@ -47,10 +47,13 @@ mod proto;
 mod reader;
 mod record;
 mod trailer;
+mod writer;
 
 pub use block::BlockIndexEntry;
 pub use block_type::HFileBlockType;
+pub use compression::CompressionCodec;
 pub use error::{HFileError, Result};
 pub use key::{Key, KeyValue, Utf8Key};
 pub use reader::{HFileReader, HFileRecordIterator, SeekResult};
 pub use record::HFileRecord;
+pub use writer::{DEFAULT_BLOCK_SIZE, DEFAULT_MAX_INDEX_BLOCK_SIZE, FILE_INFO_SCHEMA, HFileWriter};
@@ -436,10 +436,10 @@ impl HFileReader {
         )?;
         let next_offset = start + block.header.on_disk_size_with_header();
 
-        // Convert to string-keyed map
+        // Convert to string-keyed map; meta block names are stored without a length prefix
         let mut index_map = BTreeMap::new();
         for entry in entries {
-            let key_str = String::from_utf8_lossy(entry.first_key.content()).to_string();
+            let key_str = String::from_utf8_lossy(entry.first_key.bytes()).to_string();
             index_map.insert(key_str, entry);
         }
 
@@ -2330,6 +2330,26 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_get_meta_block() {
+        for filename in [
+            "hudi_1_0_hbase_2_4_9_16KB_NONE_5000.hfile",
+            "hudi_1_0_hbase_2_4_9_16KB_GZ_20000.hfile",
+            "hudi_1_0_hbase_2_4_9_no_entry.hfile",
+        ] {
+            let bytes = read_test_hfile(filename);
+            let reader = HFileReader::new(bytes).expect("Failed to create reader");
+
+            // Hudi Java writes the bloom filter as a meta block
+            let bloom_filter = reader.get_meta_block("bloomFilter").unwrap();
+            assert!(
+                bloom_filter.is_some_and(|b| !b.is_empty()),
+                "{filename} should have a bloomFilter meta block"
+            );
+            assert_eq!(reader.get_meta_block("nonexistent").unwrap(), None);
+        }
+    }
+
     #[test]
     fn test_get_avro_schema_from_metadata_hfile() {
         let bytes = read_metadata_table_hfile();
@@ -0,0 +1,748 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! HFile writer implementation.
+
+use crate::hfile::block::BLOCK_HEADER_SIZE;
+use crate::hfile::block_type::HFileBlockType;
+use crate::hfile::compression::CompressionCodec;
+use crate::hfile::error::{HFileError, Result};
+use crate::hfile::proto::{BytesBytesPair, InfoProto, TrailerProto};
+use crate::hfile::trailer::TRAILER_SIZE;
+use apache_avro::Schema as AvroSchema;
+use prost::Message;
+use std::collections::{BTreeMap, HashMap};
+
+/// Default size of uncompressed data blocks in bytes.
+pub const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Default maximum size of index blocks in bytes, i.e., `hfile.index.block.max.size`.
+pub const DEFAULT_MAX_INDEX_BLOCK_SIZE: usize = 128 * 1024;
+
+/// Minimum number of entries of an index block before the index grows another level,
+/// i.e., `hfile.index.block.min.entries`.
+const MIN_INDEX_NUM_ENTRIES: usize = 16;
+
+/// Number of bytes covered by each checksum.
+const BYTES_PER_CHECKSUM: usize = 16 * 1024;
+
+/// Checksum type id of CRC32C as used by HBase.
+const CHECKSUM_TYPE_CRC32C: u8 = 2;
+
+/// Timestamp written in each key, i.e., `HConstants.LATEST_TIMESTAMP`.
+const KEY_TIMESTAMP: i64 = i64::MAX;
+
+/// Key type written in each key, i.e., `KeyValue.Type.Put`.
+const KEY_TYPE_PUT: u8 = 4;
+
+const PBUF_MAGIC: &[u8; 4] = b"PBUF";
+
+const MAJOR_VERSION: u32 = 3;
+const MINOR_VERSION: u32 = 3;
+
+const COMPARATOR_CLASS_NAME: &str = "org.apache.hadoop.hbase.KeyValue$KVComparator";
+
+/// File info key for the Avro schema of the values, as read by [crate::hfile::HFileReader].
+pub const FILE_INFO_SCHEMA: &str = "schema";
+
+/// An entry of a block index pointing to a data, index, or meta block.
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    offset: u64,
+    /// On-disk size of the block including its header and checksums
+    size: u32,
+    /// First key of the block, or the name of a meta block
+    first_key: Vec<u8>,
+}
+
+/// Writer that produces an HFile (version 3) from key-value pairs appended in sorted order.
+///
+/// The data block index is kept in a single root level as long as it fits into
+/// [DEFAULT_MAX_INDEX_BLOCK_SIZE]; otherwise leaf index blocks are written inline
+/// with the data blocks, and intermediate levels are added until the root level fits.
+/// All blocks are compressed with the configured [CompressionCodec].
+///
+/// The whole file is built in memory and returned by [HFileWriter::finish].
+///
+/// # Example
+/// ```ignore
+/// use hudi_core::hfile::{CompressionCodec, HFileReader, HFileWriter};
+///
+/// let mut writer = HFileWriter::new().with_compression_codec(CompressionCodec::Gzip);
+/// writer.append(b"key1", b"value1")?;
+/// writer.append(b"key2", b"value2")?;
+/// let bytes = writer.finish()?;
+/// let mut reader = HFileReader::new(bytes)?;
+/// ```
+#[derive(Debug)]
+pub struct HFileWriter {
+    block_size: usize,
+    max_index_block_size: usize,
+    compression_codec: CompressionCodec,
+    buffer: Vec<u8>,
+    /// Entries of the current data block being built
+    current_block: Vec<u8>,
+    current_block_first_key: Option<Vec<u8>>,
+    /// Index entries of the written data blocks not yet covered by a leaf index block
+    leaf_index_chunk: Vec<IndexEntry>,
+    /// Index entries of the written leaf index blocks
+    leaf_index_blocks: Vec<IndexEntry>,
+    last_data_block_offset: Option<u64>,
+    /// Offset of the previous block of each type
+    prev_block_offsets: HashMap<HFileBlockType, i64>,
+    meta_blocks: BTreeMap<String, Vec<u8>>,
+    last_key: Option<Vec<u8>>,
+    file_info: BTreeMap<String, Vec<u8>>,
+    entry_count: u64,
+    total_key_length: u64,
+    total_value_length: u64,
+    total_uncompressed_bytes: u64,
+    total_uncompressed_index_size: u64,
+}
+
+impl Default for HFileWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HFileWriter {
+    /// Create a writer with [DEFAULT_BLOCK_SIZE].
+    pub fn new() -> Self {
+        Self::with_block_size(DEFAULT_BLOCK_SIZE)
+    }
+
+    /// Create a writer that starts a new data block once the current one reaches `block_size`.
+    pub fn with_block_size(block_size: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+            max_index_block_size: DEFAULT_MAX_INDEX_BLOCK_SIZE,
+            compression_codec: CompressionCodec::None,
+            buffer: Vec::new(),
+            current_block: Vec::new(),
+            current_block_first_key: None,
+            leaf_index_chunk: Vec::new(),
+            leaf_index_blocks: Vec::new(),
+            last_data_block_offset: None,
+            prev_block_offsets: HashMap::new(),
+            meta_blocks: BTreeMap::new(),
+            last_key: None,
+            file_info: BTreeMap::new(),
+            entry_count: 0,
+            total_key_length: 0,
+            total_value_length: 0,
+            total_uncompressed_bytes: 0,
+            total_uncompressed_index_size: 0,
+        }
+    }
+
+    /// Set the codec to compress the blocks with; defaults to [CompressionCodec::None].
+    pub fn with_compression_codec(mut self, codec: CompressionCodec) -> Self {
+        self.compression_codec = codec;
+        self
+    }
+
+    /// Set the size at which an index block is split; defaults to [DEFAULT_MAX_INDEX_BLOCK_SIZE].
+    pub fn with_max_index_block_size(mut self, max_index_block_size: usize) -> Self {
+        self.max_index_block_size = max_index_block_size.max(1);
+        self
+    }
+
+    /// Append a key-value pair.
+    ///
+    /// Keys must be appended in ascending byte order; duplicate keys are allowed.
+    pub fn append(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        if let Some(last_key) = &self.last_key
+            && key_content(last_key) > key
+        {
+            return Err(HFileError::InvalidFormat(format!(
+                "Keys must be appended in sorted order: {} is after {}",
+                String::from_utf8_lossy(key_content(last_key)),
+                String::from_utf8_lossy(key)
+            )));
+        }
+        let key_bytes = encode_key(key)?;
+
+        self.current_block
+            .extend_from_slice(&(key_bytes.len() as i32).to_be_bytes());
+        self.current_block
+            .extend_from_slice(&(value.len() as i32).to_be_bytes());
+        self.current_block.extend_from_slice(&key_bytes);
+        self.current_block.extend_from_slice(value);
+        // MVCC timestamp as a zero VLong
+        self.current_block.push(0);
+
+        if self.current_block_first_key.is_none() {
+            self.current_block_first_key = Some(key_bytes.clone());
+        }
+        self.entry_count += 1;
+        self.total_key_length += key_bytes.len() as u64;
+        self.total_value_length += value.len() as u64;
+        self.last_key = Some(key_bytes);
+
+        if self.current_block.len() >= self.block_size {
+            self.flush_data_block()?;
+        }
+        Ok(())
+    }
+
+    /// Add an entry to the file info block.
+    pub fn append_file_info(&mut self, key: impl Into<String>, value: impl Into<Vec<u8>>) {
+        self.file_info.insert(key.into(), value.into());
+    }
+
+    /// Add the Avro schema of the values to the file info block under [FILE_INFO_SCHEMA].
+    pub fn append_avro_schema(&mut self, schema: &AvroSchema) {
+        self.append_file_info(FILE_INFO_SCHEMA, schema.canonical_form());
+    }
+
+    /// Add a meta block, e.g., a serialized bloom filter, replacing any block of the same name.
+    pub fn append_meta_block(&mut self, name: impl Into<String>, data: impl Into<Vec<u8>>) {
+        self.meta_blocks.insert(name.into(), data.into());
+    }
+
+    /// Write the meta blocks, the index blocks, the file info block, and the trailer,
+    /// and return the file bytes.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        self.flush_data_block()?;
+
+        // Keep a single-level index if no leaf index block was written inline
+        let (mut root_level, mut num_levels) = if self.leaf_index_blocks.is_empty() {
+            (std::mem::take(&mut self.leaf_index_chunk), 1)
+        } else {
+            self.flush_leaf_index_block()?;
+            (std::mem::take(&mut self.leaf_index_blocks), 2)
+        };
+
+        let mut meta_index = Vec::with_capacity(self.meta_blocks.len());
+        for (name, data) in std::mem::take(&mut self.meta_blocks) {
+            let offset = self.buffer.len() as u64;
+            let size = self.write_block(HFileBlockType::Meta, &data)?;
+            meta_index.push(IndexEntry {
+                offset,
+                size,
+                first_key: name.into_bytes(),
+            });
+        }
+
+        while root_index_size(&root_level) > self.max_index_block_size
+            && root_level.len() > MIN_INDEX_NUM_ENTRIES
+        {
+            root_level = self.write_intermediate_index_level(&root_level)?;
+            num_levels += 1;
+        }
+
+        // Load-on-open section: root data index, meta index, and file info
+        let load_on_open_offset = self.buffer.len() as u64;
+        let root_index = encode_root_index(&root_level);
+        self.total_uncompressed_index_size += root_index.len() as u64;
+        self.write_block(HFileBlockType::RootIndex, &root_index)?;
+
+        self.write_block(HFileBlockType::RootIndex, &encode_root_index(&meta_index))?;
+
+        let file_info_offset = self.buffer.len() as u64;
+        let file_info = self.encode_file_info();
+        self.write_block(HFileBlockType::FileInfo, &file_info)?;
+
+        let trailer = TrailerProto {
+            file_info_offset: Some(file_info_offset),
+            load_on_open_data_offset: Some(load_on_open_offset),
+            uncompressed_data_index_size: Some(self.total_uncompressed_index_size),
+            total_uncompressed_bytes: Some(self.total_uncompressed_bytes),
+            data_index_count: Some(root_level.len() as u32),
+            meta_index_count: Some(meta_index.len() as u32),
+            entry_count: Some(self.entry_count),
+            num_data_index_levels: Some(num_levels),
+            first_data_block_offset: Some(0),
+            last_data_block_offset: Some(self.last_data_block_offset.unwrap_or(0)),
+            comparator_class_name: Some(COMPARATOR_CLASS_NAME.to_string()),
+            compression_codec: Some(self.compression_codec as u32),
+            encryption_key: None,
+        };
+        self.buffer.extend_from_slice(&encode_trailer(&trailer)?);
+        Ok(self.buffer)
+    }
+
+    /// Write the current data block, and write a leaf index block inline
+    /// once the pending index entries reach the maximum index block size.
+    fn flush_data_block(&mut self) -> Result<()> {
+        let Some(first_key) = self.current_block_first_key.take() else {
+            return Ok(());
+        };
+        let data = std::mem::take(&mut self.current_block);
+        let offset = self.buffer.len() as u64;
+        let size = self.write_block(HFileBlockType::Data, &data)?;
+        self.last_data_block_offset = Some(offset);
+        self.leaf_index_chunk.push(IndexEntry {
+            offset,
+            size,
+            first_key,
+        });
+
+        if non_root_index_size(&self.leaf_index_chunk) >= self.max_index_block_size {
+            self.flush_leaf_index_block()?;
+        }
+        Ok(())
+    }
+
+    fn flush_leaf_index_block(&mut self) -> Result<()> {
+        if self.leaf_index_chunk.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.leaf_index_chunk);
+        let entry = self.write_index_block(HFileBlockType::LeafIndex, &chunk)?;
+        self.leaf_index_blocks.push(entry);
+        Ok(())
+    }
+
+    /// Split the entries of an index level into intermediate index blocks,
+    /// and return the index entries of the written blocks as the next level.
+    fn write_intermediate_index_level(&mut self, level: &[IndexEntry]) -> Result<Vec<IndexEntry>> {
+        let mut parent_level = Vec::new();
+        let mut chunk_start = 0;
+        for idx in 0..level.len() {
+            let chunk = &level[chunk_start..=idx];
+            if chunk.len() >= MIN_INDEX_NUM_ENTRIES
+                && root_index_size(chunk) >= self.max_index_block_size
+            {
+                parent_level
+                    .push(self.write_index_block(HFileBlockType::IntermediateIndex, chunk)?);
+                chunk_start = idx + 1;
+            }
+        }
+        if chunk_start < level.len() {
+            let chunk = &level[chunk_start..];
+            parent_level.push(self.write_index_block(HFileBlockType::IntermediateIndex, chunk)?);
+        }
+        Ok(parent_level)
+    }
+
+    /// Write a non-root index block and return the index entry pointing to it.
+    fn write_index_block(
+        &mut self,
+        block_type: HFileBlockType,
+        entries: &[IndexEntry],
+    ) -> Result<IndexEntry> {
+        let data = encode_non_root_index(entries);
+        self.total_uncompressed_index_size += data.len() as u64;
+        let offset = self.buffer.len() as u64;
+        let size = self.write_block(block_type, &data)?;
+        Ok(IndexEntry {
+            offset,
+            size,
+            first_key: entries[0].first_key.clone(),
+        })
+    }
+
+    /// Write a block with its header and checksums, and return its on-disk size.
+    fn write_block(&mut self, block_type: HFileBlockType, data: &[u8]) -> Result<u32> {
+        let offset = self.buffer.len() as i64;
+        let prev_block_offset = self
+            .prev_block_offsets
+            .insert(block_type, offset)
+            .unwrap_or(-1);
+        let compressed = self.compression_codec.compress(data)?;
+        let block = encode_block(block_type, &compressed, data.len(), prev_block_offset);
+        self.total_uncompressed_bytes += (BLOCK_HEADER_SIZE + data.len()) as u64;
+        self.buffer.extend_from_slice(&block);
+        Ok(block.len() as u32)
+    }
+
+    fn encode_file_info(&self) -> Vec<u8> {
+        let mut file_info = self.file_info.clone();
+        let entry_count = self.entry_count.max(1);
+        file_info.insert("KEY_VALUE_VERSION".to_string(), 1i32.to_be_bytes().to_vec());
+        file_info.insert(
+            "MAX_MEMSTORE_TS_KEY".to_string(),
+            0i64.to_be_bytes().to_vec(),
+        );
+        file_info.insert(
+            "hfile.AVG_KEY_LEN".to_string(),
+            ((self.total_key_length / entry_count) as i32)
+                .to_be_bytes()
+                .to_vec(),
+        );
+        file_info.insert(
+            "hfile.AVG_VALUE_LEN".to_string(),
+            ((self.total_value_length / entry_count) as i32)
+                .to_be_bytes()
+                .to_vec(),
+        );
+        file_info.insert(
+            "hfile.CREATE_TIME_TS".to_string(),
+            0i64.to_be_bytes().to_vec(),
+        );
+        if let Some(last_key) = &self.last_key {
+            file_info.insert("hfile.LASTKEY".to_string(), last_key.clone());
+        }
+
+        let info = InfoProto {
+            map_entry: file_info
+                .into_iter()
+                .map(|(first, second)| BytesBytesPair {
+                    first: first.into_bytes(),
+                    second,
+                })
+                .collect(),
+        };
+        let mut bytes = PBUF_MAGIC.to_vec();
+        bytes.extend_from_slice(&info.encode_length_delimited_to_vec());
+        bytes
+    }
+}
+
+/// Encode the key content into the HFile key structure:
+/// 2-byte content length, content, 1-byte column family length (0),
+/// 8-byte timestamp, and 1-byte key type.
+fn encode_key(content: &[u8]) -> Result<Vec<u8>> {
+    let content_length = i16::try_from(content.len())
+        .map_err(|_| HFileError::InvalidFormat(format!("Key too long: {} bytes", content.len())))?;
+    let mut key = Vec::with_capacity(content.len() + 12);
+    key.extend_from_slice(&content_length.to_be_bytes());
+    key.extend_from_slice(content);
+    key.push(0);
+    key.extend_from_slice(&KEY_TIMESTAMP.to_be_bytes());
+    key.push(KEY_TYPE_PUT);
+    Ok(key)
+}
+
+/// Get the content of a key encoded by [encode_key].
+fn key_content(key: &[u8]) -> &[u8] {
+    let length = i16::from_be_bytes([key[0], key[1]]) as usize;
+    &key[2..2 + length]
+}
+
+/// Size of the index entries in the root index format: offset, on-disk size,
+/// VLong key length, and key.
+fn root_index_size(entries: &[IndexEntry]) -> usize {
+    entries
+        .iter()
+        .map(|e| 12 + var_long_size(e.first_key.len() as i64) + e.first_key.len())
+        .sum()
+}
+
+/// Size of the index entries in the non-root index format: entry count, secondary index
+/// of relative entry offsets, and entries of offset, on-disk size, and key.
+fn non_root_index_size(entries: &[IndexEntry]) -> usize {
+    let entries_size: usize = entries.iter().map(|e| 12 + e.first_key.len()).sum();
+    4 + 4 * (entries.len() + 1) + entries_size
+}
+
+fn encode_root_index(entries: &[IndexEntry]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(root_index_size(entries));
+    for entry in entries {
+        buf.extend_from_slice(&(entry.offset as i64).to_be_bytes());
+        buf.extend_from_slice(&(entry.size as i32).to_be_bytes());
+        write_var_long(&mut buf, entry.first_key.len() as i64);
+        buf.extend_from_slice(&entry.first_key);
+    }
+    buf
+}
+
+fn encode_non_root_index(entries: &[IndexEntry]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(non_root_index_size(entries));
+    buf.extend_from_slice(&(entries.len() as i32).to_be_bytes());
+    let mut relative_offset = 0;
+    for entry in entries {
+        buf.extend_from_slice(&(relative_offset as i32).to_be_bytes());
+        relative_offset += 12 + entry.first_key.len();
+    }
+    buf.extend_from_slice(&(relative_offset as i32).to_be_bytes());
+    for entry in entries {
+        buf.extend_from_slice(&(entry.offset as i64).to_be_bytes());
+        buf.extend_from_slice(&(entry.size as i32).to_be_bytes());
+        buf.extend_from_slice(&entry.first_key);
+    }
+    buf
+}
+
+/// Encode a block with its header, (compressed) data, and CRC32C checksums
+/// of every [BYTES_PER_CHECKSUM] bytes of the header and data.
+fn encode_block(
+    block_type: HFileBlockType,
+    data: &[u8],
+    uncompressed_size: usize,
+    prev_block_offset: i64,
+) -> Vec<u8> {
+    let data_size_with_header = BLOCK_HEADER_SIZE + data.len();
+    let checksum_size = data_size_with_header.div_ceil(BYTES_PER_CHECKSUM) * 4;
+
+    let mut block = Vec::with_capacity(data_size_with_header + checksum_size);
+    block.extend_from_slice(block_type.magic());
+    block.extend_from_slice(&((data.len() + checksum_size) as i32).to_be_bytes());
+    block.extend_from_slice(&(uncompressed_size as i32).to_be_bytes());
+    block.extend_from_slice(&prev_block_offset.to_be_bytes());
+    block.push(CHECKSUM_TYPE_CRC32C);
+    block.extend_from_slice(&(BYTES_PER_CHECKSUM as i32).to_be_bytes());
+    block.extend_from_slice(&(data_size_with_header as i32).to_be_bytes());
+    block.extend_from_slice(data);
+
+    let checksums: Vec<u32> = block.chunks(BYTES_PER_CHECKSUM).map(crc32c).collect();
+    for checksum in checksums {
+        block.extend_from_slice(&checksum.to_be_bytes());
+    }
+    block
+}
+
+/// Encode the fixed-size trailer: magic, length-delimited [TrailerProto], zero padding,
+/// and the version as 1-byte minor version followed by 3-byte major version.
+fn encode_trailer(trailer: &TrailerProto) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(TRAILER_SIZE);
+    bytes.extend_from_slice(HFileBlockType::Trailer.magic());
+    bytes.extend_from_slice(&trailer.encode_length_delimited_to_vec());
+    if bytes.len() > TRAILER_SIZE - 4 {
+        return Err(HFileError::InvalidFormat(format!(
+            "Trailer too large: {} bytes",
+            bytes.len()
+        )));
+    }
+    bytes.resize(TRAILER_SIZE - 4, 0);
+    let version = (MINOR_VERSION << 24) | MAJOR_VERSION;
+    bytes.extend_from_slice(&version.to_be_bytes());
+    Ok(bytes)
+}
+
+/// Write a Hadoop VLong encoded integer, the counterpart of
+/// [crate::hfile::block::read_var_long].
+pub(crate) fn write_var_long(buf: &mut Vec<u8>, value: i64) {
+    if (-112..=127).contains(&value) {
+        buf.push(value as i8 as u8);
+        return;
+    }
+    let (mut len, value) = if value < 0 {
+        (-120i64, !value)
+    } else {
+        (-112i64, value)
+    };
+    let mut tmp = value;
+    while tmp != 0 {
+        tmp >>= 8;
+        len -= 1;
+    }
+    buf.push(len as i8 as u8);
+    let num_bytes = if len < -120 {
+        -(len + 120)
+    } else {
+        -(len + 112)
+    };
+    for idx in (0..num_bytes).rev() {
+        buf.push((value >> (idx * 8)) as u8);
+    }
+}
+
+/// Number of bytes written by [write_var_long] for the value.
+fn var_long_size(value: i64) -> usize {
+    if (-112..=127).contains(&value) {
+        return 1;
+    }
+    let value = if value < 0 { !value } else { value };
+    1 + (64 - value.leading_zeros() as usize).div_ceil(8)
+}
+
+/// CRC32C (Castagnoli) checksum.
+fn crc32c(data: &[u8]) -> u32 {
+    const POLY: u32 = 0x82F6_3B78;
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hfile::block::read_var_long;
+    use crate::hfile::trailer::HFileTrailer;
+    use crate::hfile::{HFileReader, SeekResult, Utf8Key};
+
+    #[test]
+    fn test_crc32c() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+    }
+
+    #[test]
+    fn test_var_long_round_trip() {
+        for value in [0i64, 1, 127, 128, 255, 256, 30_000, 1 << 40] {
+            let mut buf = Vec::new();
+            write_var_long(&mut buf, value);
+            assert_eq!(read_var_long(&buf, 0), (value as u64, buf.len()));
+            assert_eq!(var_long_size(value), buf.len());
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_hfile() {
+        let mut writer = HFileWriter::with_block_size(256);
+        for i in 0..100 {
+            writer
+                .append(
+                    format!("key-{i:03}").as_bytes(),
+                    format!("value-{i}").as_bytes(),
+                )
+                .unwrap();
+        }
+        writer.append_file_info(FILE_INFO_SCHEMA, r#""string""#);
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = HFileReader::new(bytes).unwrap();
+        assert_eq!(reader.num_entries(), 100);
+        assert_eq!(
+            reader.get_file_info(FILE_INFO_SCHEMA),
+            Some(r#""string""#.as_bytes())
+        );
+        let records = reader.collect_records().unwrap();
+        assert_eq!(records.len(), 100);
+        assert_eq!(records[42].key_as_str(), Some("key-042"));
+        assert_eq!(records[42].value(), b"value-42");
+
+        assert!(reader.seek_to_first().unwrap());
+        assert_eq!(
+            reader.seek_to(&Utf8Key::new("key-077")).unwrap(),
+            SeekResult::Found
+        );
+        assert_eq!(
+            reader.seek_to(&Utf8Key::new("key-999")).unwrap(),
+            SeekResult::Eof
+        );
+    }
+
+    #[test]
+    fn test_write_empty_hfile() {
+        let bytes = HFileWriter::new().finish().unwrap();
+        let mut reader = HFileReader::new(bytes).unwrap();
+        assert_eq!(reader.num_entries(), 0);
+        assert!(reader.collect_records().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_append_unsorted_keys() {
+        let mut writer = HFileWriter::new();
+        writer.append(b"b", b"").unwrap();
+        assert!(matches!(
+            writer.append(b"a", b"").unwrap_err(),
+            HFileError::InvalidFormat(_)
+        ));
+    }
+
+    fn write_keys(mut writer: HFileWriter, num_keys: usize) -> Vec<u8> {
+        for i in 0..num_keys {
+            writer
+                .append(
+                    format!("key-{i:05}").as_bytes(),
+                    format!("value-{i}").as_bytes(),
+                )
+                .unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_write_and_read_multi_level_index() {
+        let writer = HFileWriter::with_block_size(64).with_max_index_block_size(512);
+        let bytes = write_keys(writer, 5000);
+        let trailer = HFileTrailer::read(&bytes).unwrap();
+        assert!(trailer.num_data_index_levels >= 3);
+
+        let mut reader = HFileReader::new(bytes).unwrap();
+        assert_eq!(reader.num_entries(), 5000);
+        let records = reader.collect_records().unwrap();
+        assert_eq!(records.len(), 5000);
+        assert_eq!(records[0].key_as_str(), Some("key-00000"));
+        assert_eq!(records[4999].key_as_str(), Some("key-04999"));
+        assert_eq!(records[3210].value(), b"value-3210");
+
+        assert!(reader.seek_to_first().unwrap());
+        for key in ["key-00001", "key-01234", "key-04321", "key-04999"] {
+            assert_eq!(
+                reader.seek_to(&Utf8Key::new(key)).unwrap(),
+                SeekResult::Found
+            );
+        }
+        assert_eq!(
+            reader.seek_to(&Utf8Key::new("key-99999")).unwrap(),
+            SeekResult::Eof
+        );
+    }
+
+    #[test]
+    fn test_single_level_index_for_small_files() {
+        let bytes = write_keys(HFileWriter::with_block_size(64), 100);
+        let trailer = HFileTrailer::read(&bytes).unwrap();
+        assert_eq!(trailer.num_data_index_levels, 1);
+        assert!(trailer.data_index_count > 1);
+
+        let mut reader = HFileReader::new(bytes).unwrap();
+        assert_eq!(reader.collect_records().unwrap().len(), 100);
+    }
+
+    #[test]
+    fn test_write_gzip_compressed_hfile_with_meta_blocks() {
+        let schema = AvroSchema::parse_str(r#"{"type":"string"}"#).unwrap();
+        let mut writer = HFileWriter::with_block_size(1024)
+            .with_max_index_block_size(256)
+            .with_compression_codec(CompressionCodec::Gzip);
+        writer.append_avro_schema(&schema);
+        writer.append_meta_block("bloomFilter", b"filter-bytes".to_vec());
+        writer.append_meta_block("another", b"other-bytes".to_vec());
+        for i in 0..2000 {
+            writer
+                .append(format!("key-{i:05}").as_bytes(), &[b'v'; 100])
+                .unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+        assert!(bytes.len() < 2000 * 100);
+        let trailer = HFileTrailer::read(&bytes).unwrap();
+        assert_eq!(trailer.compression_codec, CompressionCodec::Gzip);
+        assert_eq!(trailer.meta_index_count, 2);
+        assert!(trailer.num_data_index_levels > 1);
+
+        let mut reader = HFileReader::new(bytes).unwrap();
+        assert_eq!(reader.get_avro_schema().unwrap(), Some(&schema));
+        assert_eq!(
+            reader.get_meta_block("bloomFilter").unwrap(),
+            Some(b"filter-bytes".to_vec())
+        );
+        assert_eq!(
+            reader.get_meta_block("another").unwrap(),
+            Some(b"other-bytes".to_vec())
+        );
+        assert_eq!(reader.get_meta_block("missing").unwrap(), None);
+        let records = reader.collect_records().unwrap();
+        assert_eq!(records.len(), 2000);
+        assert_eq!(records[1999].key_as_str(), Some("key-01999"));
+        assert_eq!(records[1999].value(), &[b'v'; 100]);
+    }
+
+    #[test]
+    fn test_write_with_unsupported_compression() {
+        let mut writer = HFileWriter::new().with_compression_codec(CompressionCodec::Snappy);
+        writer.append(b"a", b"").unwrap();
+        assert!(matches!(
+            writer.finish().unwrap_err(),
+            HFileError::UnsupportedCompression(3)
+        ));
+    }
+}
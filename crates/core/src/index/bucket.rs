@@ -0,0 +1,444 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! The simple bucket index, which hashes record keys into a fixed number of file groups per
+//! partition, the same way as Hudi Java's `BucketIdentifier`.
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::index::HudiIndexConfig::{
+    BucketIndexEngine, BucketIndexHashField, BucketIndexNumBuckets, IndexType,
+};
+use crate::config::index::{BucketIndexEngineValue, IndexTypeValue};
+use crate::config::table::HudiTableConfig::RecordKeyFields;
+use crate::error::CoreError;
+use crate::expr::ExprOperator;
+use crate::expr::filter::{Filter, FilterExpr, SchemableFilter};
+use crate::keygen::builtin::format_values;
+use crate::metadata::meta_field::MetaField;
+use arrow_schema::Schema;
+use std::collections::{BTreeSet, HashSet};
+use std::str::FromStr;
+
+/// The number of leading characters of a file ID that encode the bucket number.
+const BUCKET_ID_LENGTH: usize = 8;
+
+/// Maximum number of combinations of the hash field values to compute buckets for when
+/// pruning; beyond this, no buckets are pruned.
+const MAX_HASH_KEY_COMBINATIONS: usize = 1024;
+
+/// The simple bucket index of a table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketIndex {
+    num_buckets: u32,
+    /// The hash fields, in the order of the record key fields.
+    hash_fields: Vec<String>,
+}
+
+impl BucketIndex {
+    /// Create the bucket index from the configs, or return `None` if the table is not
+    /// configured to use the simple bucket index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table has no record key fields, or the hash fields are not
+    /// a subset of the record key fields.
+    pub fn from_configs(hudi_configs: &HudiConfigs) -> Result<Option<Self>> {
+        let index_type: String = hudi_configs.get_or_default(IndexType).into();
+        if IndexTypeValue::from_str(&index_type)? != IndexTypeValue::Bucket {
+            return Ok(None);
+        }
+        let engine: String = hudi_configs.get_or_default(BucketIndexEngine).into();
+        if BucketIndexEngineValue::from_str(&engine)? != BucketIndexEngineValue::Simple {
+            return Ok(None);
+        }
+
+        let record_key_fields: Vec<String> = hudi_configs
+            .try_get(RecordKeyFields)?
+            .map(Into::into)
+            .unwrap_or_default();
+        if record_key_fields.is_empty() {
+            return Err(CoreError::Unsupported(
+                "Bucket index requires record key fields".to_string(),
+            ));
+        }
+        let hash_fields: Vec<String> = hudi_configs
+            .try_get(BucketIndexHashField)?
+            .map(Into::into)
+            .unwrap_or_else(|| record_key_fields.clone());
+        if let Some(field) = hash_fields.iter().find(|f| !record_key_fields.contains(f)) {
+            return Err(CoreError::InvalidValue(format!(
+                "Bucket index hash field '{field}' is not a record key field in {record_key_fields:?}"
+            )));
+        }
+
+        let num_buckets: usize = hudi_configs.get_or_default(BucketIndexNumBuckets).into();
+        let num_buckets = u32::try_from(num_buckets).map_err(|_| {
+            CoreError::InvalidValue(format!("Too many buckets for bucket index: {num_buckets}"))
+        })?;
+        Ok(Some(Self {
+            num_buckets,
+            hash_fields: record_key_fields
+                .into_iter()
+                .filter(|f| hash_fields.contains(f))
+                .collect(),
+        }))
+    }
+
+    pub fn num_buckets(&self) -> u32 {
+        self.num_buckets
+    }
+
+    /// Get the bucket of a record by its record key.
+    ///
+    /// The record key is either the value of a single record key field, or the
+    /// `field:value` pairs joined by `,` when generated by the complex key generator.
+    pub fn bucket_id_of_record_key(&self, record_key: &str) -> u32 {
+        let hash_keys: Vec<Option<&str>> = if record_key.contains(':') {
+            record_key
+                .split(',')
+                .filter_map(|kv| match kv.split_once(':') {
+                    None => Some(Some(kv)),
+                    Some((field, value)) => {
+                        if !self.hash_fields.iter().any(|f| f == field) {
+                            None
+                        } else if value == "__null__" {
+                            Some(None)
+                        } else if value == "__empty__" {
+                            Some(Some(""))
+                        } else {
+                            Some(Some(value))
+                        }
+                    }
+                })
+                .collect()
+        } else {
+            vec![Some(record_key)]
+        };
+        self.bucket_id_of_hash_keys(&hash_keys)
+    }
+
+    fn bucket_id_of_hash_keys(&self, hash_keys: &[Option<&str>]) -> u32 {
+        // Same as Java's `List#hashCode` of the hash keys.
+        let hash = hash_keys.iter().fold(1i32, |h, key| {
+            h.wrapping_mul(31)
+                .wrapping_add(key.map_or(0, java_string_hash))
+        });
+        (hash & i32::MAX) as u32 % self.num_buckets
+    }
+
+    /// Get the buckets that may contain the records matching all the filters, or `None` if
+    /// the filters cannot narrow down the buckets.
+    ///
+    /// Buckets are computed from the equality and `IN` filters on either all the hash fields
+    /// or the `_hoodie_record_key` meta field. Filters combined by `OR` or `NOT` are ignored.
+    pub fn get_candidate_buckets(
+        &self,
+        filters: &[FilterExpr],
+        schema: &Schema,
+    ) -> Result<Option<HashSet<u32>>> {
+        let filters: Vec<&Filter> = filters.iter().filter_map(FilterExpr::as_filter).collect();
+        let filters = filters.as_slice();
+        let from_hash_fields = self.get_buckets_from_hash_field_filters(filters, schema)?;
+        let from_record_keys = Self::get_values_of_filters(filters, MetaField::RecordKey.as_ref())
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| self.bucket_id_of_record_key(key))
+                    .collect::<HashSet<_>>()
+            });
+        Ok(match (from_hash_fields, from_record_keys) {
+            (Some(a), Some(b)) => Some(a.intersection(&b).copied().collect()),
+            (a, b) => a.or(b),
+        })
+    }
+
+    fn get_buckets_from_hash_field_filters(
+        &self,
+        filters: &[&Filter],
+        schema: &Schema,
+    ) -> Result<Option<HashSet<u32>>> {
+        let mut values_per_field: Vec<Vec<String>> = Vec::with_capacity(self.hash_fields.len());
+        for field in &self.hash_fields {
+            let Some(values) = Self::get_values_of_filters(filters, field) else {
+                return Ok(None);
+            };
+            let Ok(data_type) = schema.field_with_name(field).map(|f| f.data_type()) else {
+                return Ok(None);
+            };
+            // Normalize the values to the way they are formatted in record keys.
+            let mut normalized = Vec::with_capacity(values.len());
+            for value in &values {
+                let array = SchemableFilter::cast_value(&[value.as_str()], data_type)?.into_inner();
+                match format_values(&array)?.pop().flatten() {
+                    // Hudi Java parses record keys by `,` and `:`, which would not round-trip.
+                    Some(v) if !v.contains([',', ':']) => normalized.push(v),
+                    _ => return Ok(None),
+                }
+            }
+            values_per_field.push(normalized);
+        }
+
+        let num_combinations = values_per_field
+            .iter()
+            .try_fold(1usize, |n, values| n.checked_mul(values.len()));
+        if num_combinations.is_none_or(|n| n > MAX_HASH_KEY_COMBINATIONS) {
+            return Ok(None);
+        }
+
+        let mut combinations: Vec<Vec<Option<&str>>> = vec![Vec::new()];
+        for values in &values_per_field {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|prefix| {
+                    values.iter().map(move |v| {
+                        let mut keys = prefix.clone();
+                        keys.push(Some(v.as_str()));
+                        keys
+                    })
+                })
+                .collect();
+        }
+        Ok(Some(
+            combinations
+                .iter()
+                .map(|keys| self.bucket_id_of_hash_keys(keys))
+                .collect(),
+        ))
+    }
+
+    /// Get the values the field must take to match all the equality and `IN` filters on it,
+    /// or `None` if there are no such filters.
+    fn get_values_of_filters(filters: &[&Filter], field: &str) -> Option<BTreeSet<String>> {
+        filters
+            .iter()
+            .filter(|f| {
+                f.field == field && matches!(f.operator, ExprOperator::Eq | ExprOperator::In)
+            })
+            .map(|f| f.values.iter().cloned().collect::<BTreeSet<_>>())
+            .reduce(|a, b| a.intersection(&b).cloned().collect())
+    }
+}
+
+/// Same as Java's `String#hashCode`, which hashes the UTF-16 code units.
+fn java_string_hash(s: &str) -> i32 {
+    s.encode_utf16()
+        .fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32))
+}
+
+/// Parse the bucket number from the file ID of a file group written with the bucket index.
+///
+/// Returns `None` if the file ID does not start with a bucket number.
+pub fn parse_bucket_id(file_id: &str) -> Option<u32> {
+    let prefix = file_id.get(..BUCKET_ID_LENGTH)?;
+    if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    prefix.parse().ok()
+}
+
+/// Create a new file ID for a file group of the bucket, i.e., a random UUID with its first
+/// characters replaced by the zero-padded bucket number.
+pub fn new_bucket_file_id(bucket_id: u32) -> String {
+    let uuid = uuid::Uuid::new_v4().to_string();
+    format!(
+        "{bucket_id:0width$}{}-0",
+        &uuid[BUCKET_ID_LENGTH..],
+        width = BUCKET_ID_LENGTH
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table::HudiTableConfig::{RecordKeyFields, TableName};
+    use crate::expr::filter::col;
+    use arrow_schema::{DataType, Field};
+
+    fn bucket_index(
+        num_buckets: u32,
+        record_key_fields: &str,
+        hash_fields: Option<&str>,
+    ) -> BucketIndex {
+        let mut options = vec![
+            (TableName.as_ref().to_string(), "t".to_string()),
+            (IndexType.as_ref().to_string(), "BUCKET".to_string()),
+            (
+                BucketIndexNumBuckets.as_ref().to_string(),
+                num_buckets.to_string(),
+            ),
+            (
+                RecordKeyFields.as_ref().to_string(),
+                record_key_fields.to_string(),
+            ),
+        ];
+        if let Some(fields) = hash_fields {
+            options.push((
+                BucketIndexHashField.as_ref().to_string(),
+                fields.to_string(),
+            ));
+        }
+        BucketIndex::from_configs(&HudiConfigs::new(options))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_from_configs() {
+        let configs = HudiConfigs::new([(RecordKeyFields.as_ref(), "id")]);
+        assert!(BucketIndex::from_configs(&configs).unwrap().is_none());
+
+        let configs = HudiConfigs::new([
+            (IndexType.as_ref(), "BUCKET"),
+            (BucketIndexEngine.as_ref(), "CONSISTENT_HASHING"),
+            (RecordKeyFields.as_ref(), "id"),
+        ]);
+        assert!(BucketIndex::from_configs(&configs).unwrap().is_none());
+
+        let configs = HudiConfigs::new([
+            (IndexType.as_ref(), "BUCKET"),
+            (RecordKeyFields.as_ref(), "id,name"),
+            (BucketIndexHashField.as_ref(), "city"),
+        ]);
+        assert!(matches!(
+            BucketIndex::from_configs(&configs).unwrap_err(),
+            CoreError::InvalidValue(_)
+        ));
+
+        let index = bucket_index(256, "id,name", Some("name,id"));
+        assert_eq!(index.num_buckets(), 256);
+        assert_eq!(index.hash_fields, vec!["id", "name"]);
+    }
+
+    #[test]
+    fn test_java_string_hash() {
+        assert_eq!(java_string_hash(""), 0);
+        assert_eq!(java_string_hash("a"), 97);
+        assert_eq!(java_string_hash("id1"), 104054);
+        assert_eq!(java_string_hash("hello world"), 1794106052);
+        assert_eq!(java_string_hash("polygenelubricants"), i32::MIN);
+    }
+
+    #[test]
+    fn test_bucket_id_of_record_key() {
+        let index = bucket_index(256, "id", None);
+        // `List#hashCode` of ["a"] is 31 + 97
+        assert_eq!(index.bucket_id_of_record_key("a"), 128);
+        assert_eq!(index.bucket_id_of_record_key("id1"), 104085 % 256);
+        assert_eq!(index.bucket_id_of_record_key("id:id1"), 104085 % 256);
+
+        let index = bucket_index(8, "id", None);
+        assert_eq!(index.bucket_id_of_record_key("polygenelubricants"), 7);
+
+        let index = bucket_index(256, "id,name", Some("id"));
+        assert_eq!(
+            index.bucket_id_of_record_key("id:id1,name:foo"),
+            index.bucket_id_of_record_key("id1")
+        );
+        let index = bucket_index(256, "id,name", None);
+        // `List#hashCode` of ["id1", null]
+        assert_eq!(
+            index.bucket_id_of_record_key("id:id1,name:__null__"),
+            (104085i32.wrapping_mul(31) & i32::MAX) as u32 % 256
+        );
+    }
+
+    #[test]
+    fn test_bucket_file_id() {
+        let file_id = new_bucket_file_id(42);
+        assert!(file_id.starts_with("00000042-"));
+        assert!(file_id.ends_with("-0"));
+        assert_eq!(file_id.len(), 38);
+        assert_eq!(parse_bucket_id(&file_id), Some(42));
+        assert_eq!(
+            parse_bucket_id("a079bdb3-731c-4894-b855-abfcd6921007-0"),
+            None
+        );
+        assert_eq!(parse_bucket_id("+0000001-0"), None);
+        assert_eq!(parse_bucket_id("0001"), None);
+    }
+
+    #[test]
+    fn test_get_candidate_buckets() {
+        let schema = Schema::new(vec![
+            Field::new(MetaField::RecordKey.as_ref(), DataType::Utf8, false),
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("city", DataType::Utf8, true),
+        ]);
+        let index = bucket_index(16, "id,name", None);
+        let buckets = |filters: &[Filter]| {
+            let filters: Vec<FilterExpr> = filters.iter().cloned().map(FilterExpr::from).collect();
+            index.get_candidate_buckets(&filters, &schema).unwrap()
+        };
+
+        assert_eq!(buckets(&[col("id").eq("1")]), None);
+        assert_eq!(buckets(&[col("id").gt("1"), col("name").eq("a")]), None);
+        assert_eq!(buckets(&[col("city").eq("sf")]), None);
+
+        let expected = index.bucket_id_of_record_key("id:1,name:a");
+        assert_eq!(
+            buckets(&[
+                col("id").eq("001"),
+                col("name").eq("a"),
+                col("city").eq("sf")
+            ]),
+            Some(HashSet::from([expected]))
+        );
+        let expected: HashSet<u32> = ["id:1,name:a", "id:1,name:b", "id:2,name:a", "id:2,name:b"]
+            .into_iter()
+            .map(|k| index.bucket_id_of_record_key(k))
+            .collect();
+        assert_eq!(
+            buckets(&[
+                col("id").in_list(["1", "2"]),
+                col("name").in_list(["a", "b"])
+            ]),
+            Some(expected)
+        );
+        // Values of the same field are intersected.
+        assert_eq!(
+            buckets(&[
+                col("id").in_list(["1", "2"]),
+                col("id").eq("2"),
+                col("name").eq("b")
+            ]),
+            Some(HashSet::from(
+                [index.bucket_id_of_record_key("id:2,name:b")]
+            ))
+        );
+        assert_eq!(buckets(&[col("id").eq("1"), col("name").eq("a:b")]), None);
+
+        let record_key = MetaField::RecordKey.as_ref();
+        assert_eq!(
+            buckets(&[col(record_key).in_list(["id:3,name:c", "id:4,name:d"])]),
+            Some(
+                ["id:3,name:c", "id:4,name:d"]
+                    .into_iter()
+                    .map(|k| index.bucket_id_of_record_key(k))
+                    .collect()
+            )
+        );
+
+        // Filters under OR do not narrow down the buckets.
+        let or = FilterExpr::Or(vec![
+            col(record_key).eq("id:3,name:c").into(),
+            col("city").eq("sf").into(),
+        ]);
+        assert_eq!(index.get_candidate_buckets(&[or], &schema).unwrap(), None);
+    }
+}
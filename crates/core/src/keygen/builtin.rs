@@ -0,0 +1,494 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Generating record keys and partition paths of records to write.
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::table::HudiTableConfig::{
+    IsHiveStylePartitioning, IsPartitionPathUrlencoded, KeyGeneratorClass, KeyGeneratorType,
+    PartitionFields, RecordKeyFields,
+};
+use crate::config::table::KeyGeneratorTypeValue;
+use crate::error::CoreError;
+use arrow::record_batch::RecordBatch;
+use arrow_array::{Array, ArrayRef};
+use arrow_cast::cast;
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::DataType;
+use std::str::FromStr;
+
+/// The partition path value of null or empty partition field values.
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// The record key value of null field values, for [KeyGeneratorTypeValue::Complex].
+const NULL_RECORD_KEY_PLACEHOLDER: &str = "__null__";
+
+/// The record key value of empty field values, for [KeyGeneratorTypeValue::Complex].
+const EMPTY_RECORD_KEY_PLACEHOLDER: &str = "__empty__";
+
+/// Generates record keys and partition paths the same way as the built-in key generators of
+/// Hudi Java, i.e., [KeyGeneratorTypeValue::Simple], [KeyGeneratorTypeValue::Complex], and
+/// [KeyGeneratorTypeValue::NonPartition].
+#[derive(Clone, Debug)]
+pub struct BuiltinKeyGenerator {
+    key_generator_type: KeyGeneratorTypeValue,
+    record_key_fields: Vec<String>,
+    partition_fields: Vec<String>,
+    is_hive_style: bool,
+    is_url_encoded: bool,
+}
+
+impl BuiltinKeyGenerator {
+    /// Create a key generator from the table configs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [CoreError::Unsupported] if the table has no record key fields, or uses a key
+    /// generator other than the built-in ones.
+    pub fn from_configs(hudi_configs: &HudiConfigs) -> Result<Self> {
+        let key_generator = Self::for_record_keys(hudi_configs)?;
+        key_generator.validate_partitioning()?;
+        Ok(key_generator)
+    }
+
+    /// Create a key generator from the table configs for generating record keys only.
+    ///
+    /// Unlike [Self::from_configs], [KeyGeneratorTypeValue::Timestamp] and
+    /// [KeyGeneratorTypeValue::Custom] are supported, as they generate the record keys the same
+    /// way as the other built-in ones, while [Self::get_partition_paths] returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [CoreError::Unsupported] if the table has no record key fields, or uses a key
+    /// generator class other than the built-in ones.
+    pub fn for_record_keys(hudi_configs: &HudiConfigs) -> Result<Self> {
+        let record_key_fields: Vec<String> = hudi_configs
+            .try_get(RecordKeyFields)?
+            .map(Into::into)
+            .unwrap_or_default();
+        let record_key_fields: Vec<String> = record_key_fields
+            .into_iter()
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect();
+        if record_key_fields.is_empty() {
+            return Err(CoreError::Unsupported(
+                "Generating keys for tables without record key fields is not supported".to_string(),
+            ));
+        }
+        let partition_fields: Vec<String> = hudi_configs.get_or_default(PartitionFields).into();
+        let partition_fields: Vec<String> = partition_fields
+            .into_iter()
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect();
+
+        let key_generator_type =
+            Self::resolve_type(hudi_configs, &record_key_fields, &partition_fields)?;
+
+        Ok(Self {
+            key_generator_type,
+            record_key_fields,
+            partition_fields,
+            is_hive_style: hudi_configs.get_or_default(IsHiveStylePartitioning).into(),
+            is_url_encoded: hudi_configs
+                .get_or_default(IsPartitionPathUrlencoded)
+                .into(),
+        })
+    }
+
+    /// Checks that the partition paths can be generated with the key generator.
+    fn validate_partitioning(&self) -> Result<()> {
+        match self.key_generator_type {
+            KeyGeneratorTypeValue::Simple if self.partition_fields.len() > 1 => {
+                Err(CoreError::InvalidValue(format!(
+                    "{} key generator expects one partition field, but got: {:?}",
+                    self.key_generator_type.as_ref(),
+                    self.partition_fields
+                )))
+            }
+            KeyGeneratorTypeValue::Timestamp | KeyGeneratorTypeValue::Custom => {
+                Err(CoreError::Unsupported(format!(
+                    "Generating keys with {} key generator is not supported",
+                    self.key_generator_type.as_ref()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn resolve_type(
+        hudi_configs: &HudiConfigs,
+        record_key_fields: &[String],
+        partition_fields: &[String],
+    ) -> Result<KeyGeneratorTypeValue> {
+        if let Some(v) = hudi_configs.try_get(KeyGeneratorType)? {
+            let s: String = v.into();
+            return Ok(KeyGeneratorTypeValue::from_str(&s)?);
+        }
+        if let Some(v) = hudi_configs.try_get(KeyGeneratorClass)? {
+            let class: String = v.into();
+            return KeyGeneratorTypeValue::from_class_name(&class).ok_or_else(|| {
+                CoreError::Unsupported(format!(
+                    "Generating keys with key generator class {class} is not supported"
+                ))
+            });
+        }
+        Ok(KeyGeneratorTypeValue::infer(
+            record_key_fields,
+            partition_fields,
+        ))
+    }
+
+    pub fn key_generator_type(&self) -> &KeyGeneratorTypeValue {
+        &self.key_generator_type
+    }
+
+    /// Generate the record key of each record in the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record key field is missing from the batch, or a record has no
+    /// value for its record key.
+    pub fn get_record_keys(&self, batch: &RecordBatch) -> Result<Vec<String>> {
+        let columns = self.format_columns(batch, &self.record_key_fields)?;
+        let uses_field_names = self.key_generator_type == KeyGeneratorTypeValue::Complex
+            || self.record_key_fields.len() > 1;
+        (0..batch.num_rows())
+            .map(|row| {
+                if !uses_field_names {
+                    return match &columns[0][row] {
+                        Some(v) if !v.is_empty() => Ok(v.clone()),
+                        _ => Err(CoreError::InvalidValue(format!(
+                            "Record key field '{}' has no value at row {row}",
+                            self.record_key_fields[0]
+                        ))),
+                    };
+                }
+                let mut has_value = false;
+                let parts: Vec<String> = self
+                    .record_key_fields
+                    .iter()
+                    .zip(&columns)
+                    .map(|(field, values)| {
+                        let value = match &values[row] {
+                            None => NULL_RECORD_KEY_PLACEHOLDER,
+                            Some(v) if v.is_empty() => EMPTY_RECORD_KEY_PLACEHOLDER,
+                            Some(v) => {
+                                has_value = true;
+                                v.as_str()
+                            }
+                        };
+                        format!("{field}:{value}")
+                    })
+                    .collect();
+                if has_value {
+                    Ok(parts.join(","))
+                } else {
+                    Err(CoreError::InvalidValue(format!(
+                        "Record key fields {:?} have no value at row {row}",
+                        self.record_key_fields
+                    )))
+                }
+            })
+            .collect()
+    }
+
+    /// Generate the partition path of each record in the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key generator cannot generate partition paths, see
+    /// [Self::for_record_keys], or a partition field is missing from the batch.
+    pub fn get_partition_paths(&self, batch: &RecordBatch) -> Result<Vec<String>> {
+        self.validate_partitioning()?;
+        if self.key_generator_type == KeyGeneratorTypeValue::NonPartition
+            || self.partition_fields.is_empty()
+        {
+            return Ok(vec![String::new(); batch.num_rows()]);
+        }
+        let columns = self.format_columns(batch, &self.partition_fields)?;
+        Ok((0..batch.num_rows())
+            .map(|row| {
+                self.partition_fields
+                    .iter()
+                    .zip(&columns)
+                    .map(|(field, values)| {
+                        let value = match &values[row] {
+                            Some(v) if !v.is_empty() => {
+                                if self.is_url_encoded {
+                                    escape_partition_value(v)
+                                } else {
+                                    v.clone()
+                                }
+                            }
+                            _ => HIVE_DEFAULT_PARTITION.to_string(),
+                        };
+                        if self.is_hive_style {
+                            format!("{field}={value}")
+                        } else {
+                            value
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect())
+    }
+
+    fn format_columns(
+        &self,
+        batch: &RecordBatch,
+        fields: &[String],
+    ) -> Result<Vec<Vec<Option<String>>>> {
+        fields
+            .iter()
+            .map(|field| {
+                let column = batch.column_by_name(field).ok_or_else(|| {
+                    CoreError::Schema(format!("Key field '{field}' is not found in the records"))
+                })?;
+                format_values(column)
+            })
+            .collect()
+    }
+}
+
+/// Format the values as strings, the way Hudi Java does for the Avro values.
+///
+/// Timestamps are formatted as the numbers since epoch, as they are stored in Avro longs.
+pub(crate) fn format_values(array: &ArrayRef) -> Result<Vec<Option<String>>> {
+    let array = match array.data_type() {
+        DataType::Timestamp(_, _) => cast(array, &DataType::Int64)?,
+        _ => array.clone(),
+    };
+    let formatter = ArrayFormatter::try_new(array.as_ref(), &FormatOptions::default())?;
+    Ok((0..array.len())
+        .map(|i| {
+            if array.is_null(i) {
+                None
+            } else {
+                Some(formatter.value(i).to_string())
+            }
+        })
+        .collect())
+}
+
+/// Escape the characters of a partition value that are not allowed in paths, the same way as
+/// Hudi Java's `PartitionPathEncodeUtils`.
+fn escape_partition_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\u{01}'
+                ..='\u{1F}'
+                    | '"'
+                    | '#'
+                    | '%'
+                    | '\''
+                    | '*'
+                    | '/'
+                    | ':'
+                    | '='
+                    | '?'
+                    | '\\'
+                    | '\u{7F}'
+                    | '{'
+                    | '['
+                    | ']'
+                    | '^'
+        ) {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray, TimestampMicrosecondArray};
+    use arrow_schema::{Field, Schema};
+    use std::sync::Arc;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("region", DataType::Utf8, true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("")])),
+                Arc::new(StringArray::from(vec![Some("us/east"), None, Some("eu")])),
+                Arc::new(
+                    TimestampMicrosecondArray::from(vec![1_000_000, 2_000_000, 3_000_000])
+                        .with_timezone("UTC"),
+                ),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_simple_key_generator() {
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id"),
+            ("hoodie.table.partition.fields", "region"),
+        ]);
+        let keygen = BuiltinKeyGenerator::from_configs(&configs).unwrap();
+        assert_eq!(keygen.key_generator_type(), &KeyGeneratorTypeValue::Simple);
+        let batch = test_batch();
+        assert_eq!(keygen.get_record_keys(&batch).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(
+            keygen.get_partition_paths(&batch).unwrap(),
+            vec!["us/east", HIVE_DEFAULT_PARTITION, "eu"]
+        );
+
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "name"),
+            ("hoodie.table.partition.fields", "region"),
+        ]);
+        let keygen = BuiltinKeyGenerator::from_configs(&configs).unwrap();
+        assert!(matches!(
+            keygen.get_record_keys(&batch).unwrap_err(),
+            CoreError::InvalidValue(_)
+        ));
+    }
+
+    #[test]
+    fn test_complex_key_generator_with_hive_style_and_url_encoding() {
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id,name"),
+            ("hoodie.table.partition.fields", "region,ts"),
+            ("hoodie.datasource.write.hive_style_partitioning", "true"),
+            ("hoodie.datasource.write.partitionpath.urlencode", "true"),
+        ]);
+        let keygen = BuiltinKeyGenerator::from_configs(&configs).unwrap();
+        assert_eq!(keygen.key_generator_type(), &KeyGeneratorTypeValue::Complex);
+        let batch = test_batch();
+        assert_eq!(
+            keygen.get_record_keys(&batch).unwrap(),
+            vec!["id:1,name:a", "id:2,name:__null__", "id:3,name:__empty__"]
+        );
+        assert_eq!(
+            keygen.get_partition_paths(&batch).unwrap(),
+            vec![
+                "region=us%2Feast/ts=1000000",
+                "region=__HIVE_DEFAULT_PARTITION__/ts=2000000",
+                "region=eu/ts=3000000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_partitioned_key_generator_from_class() {
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id"),
+            (
+                "hoodie.table.keygenerator.class",
+                "org.apache.hudi.keygen.NonpartitionedKeyGenerator",
+            ),
+        ]);
+        let keygen = BuiltinKeyGenerator::from_configs(&configs).unwrap();
+        assert_eq!(
+            keygen.key_generator_type(),
+            &KeyGeneratorTypeValue::NonPartition
+        );
+        let batch = test_batch();
+        assert_eq!(keygen.get_record_keys(&batch).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(
+            keygen.get_partition_paths(&batch).unwrap(),
+            vec!["", "", ""]
+        );
+    }
+
+    #[test]
+    fn test_record_keys_only_key_generator() {
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id"),
+            ("hoodie.table.partition.fields", "ts"),
+            (
+                "hoodie.table.keygenerator.class",
+                "org.apache.hudi.keygen.TimestampBasedKeyGenerator",
+            ),
+        ]);
+        let keygen = BuiltinKeyGenerator::for_record_keys(&configs).unwrap();
+        assert_eq!(
+            keygen.key_generator_type(),
+            &KeyGeneratorTypeValue::Timestamp
+        );
+        let batch = test_batch();
+        assert_eq!(keygen.get_record_keys(&batch).unwrap(), vec!["1", "2", "3"]);
+        assert!(matches!(
+            keygen.get_partition_paths(&batch).unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id,name"),
+            ("hoodie.table.partition.fields", "region:SIMPLE"),
+            ("hoodie.table.keygenerator.type", "CUSTOM"),
+        ]);
+        let keygen = BuiltinKeyGenerator::for_record_keys(&configs).unwrap();
+        assert_eq!(
+            keygen.get_record_keys(&batch).unwrap(),
+            vec!["id:1,name:a", "id:2,name:__null__", "id:3,name:__empty__"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_key_generators() {
+        let configs = HudiConfigs::new([("hoodie.table.partition.fields", "region")]);
+        assert!(matches!(
+            BuiltinKeyGenerator::from_configs(&configs).unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id"),
+            ("hoodie.table.partition.fields", "ts"),
+            (
+                "hoodie.table.keygenerator.class",
+                "org.apache.hudi.keygen.TimestampBasedKeyGenerator",
+            ),
+        ]);
+        assert!(matches!(
+            BuiltinKeyGenerator::from_configs(&configs).unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+        let configs = HudiConfigs::new([
+            ("hoodie.table.recordkey.fields", "id"),
+            ("hoodie.table.partition.fields", "missing"),
+        ]);
+        let keygen = BuiltinKeyGenerator::from_configs(&configs).unwrap();
+        assert!(matches!(
+            keygen.get_partition_paths(&test_batch()).unwrap_err(),
+            CoreError::Schema(_)
+        ));
+    }
+}
@@ -17,8 +17,10 @@
  * under the License.
  */
 
-//! Key generator implementations for transforming user filters to partition filters.
+//! Key generator implementations for generating record keys and partition paths of records to
+//! write, and for transforming user filters to partition filters.
 
+pub mod builtin;
 pub mod timestamp_based;
 
 use crate::Result;
@@ -552,6 +552,13 @@ impl KeyGeneratorFilterTransformer for TimestampBasedKeyGenerator {
                     values: vec![path],
                 }])
             }
+            ExprOperator::IsNull
+            | ExprOperator::IsNotNull
+            | ExprOperator::Like
+            | ExprOperator::NotLike => {
+                // Not expressible on the formatted partition path; skip pruning
+                Ok(vec![])
+            }
         }
     }
 }
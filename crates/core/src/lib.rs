@@ -52,6 +52,7 @@ pub mod error;
 pub mod expr;
 pub mod file_group;
 pub mod hfile;
+pub mod index;
 pub mod keygen;
 pub mod merge;
 pub mod metadata;
@@ -61,6 +62,7 @@ pub mod statistics;
 pub mod storage;
 pub mod table;
 pub mod timeline;
+pub mod transaction;
 pub mod util;
 
 use error::Result;
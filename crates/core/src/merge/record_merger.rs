@@ -21,9 +21,11 @@ use crate::config::HudiConfigs;
 use crate::config::error::ConfigError;
 use crate::config::error::Result as ConfigResult;
 use crate::config::table::HudiTableConfig::{
-    OrderingFields, PopulatesMetaFields, RecordMergeStrategy,
+    OrderingFields, PopulatesMetaFields, RecordKeyFields, RecordMergeStrategy,
 };
 use crate::file_group::record_batches::RecordBatches;
+use crate::file_group::virtual_meta_fields::VirtualMetaFields;
+use crate::keygen::builtin::BuiltinKeyGenerator;
 use crate::merge::RecordMergeStrategyValue;
 use crate::merge::ordering::{MaxOrderingInfo, process_batch_for_max_orderings};
 use crate::metadata::meta_field::MetaField;
@@ -54,11 +56,17 @@ impl RecordMerger {
         let merge_strategy: String = hudi_configs.get_or_default(RecordMergeStrategy).into();
         let merge_strategy = RecordMergeStrategyValue::from_str(&merge_strategy)?;
 
+        // Without the meta fields, the records can only be merged by the record keys generated
+        // from the record key fields.
         let populate_meta_fields: bool = hudi_configs.get_or_default(PopulatesMetaFields).into();
-        if !populate_meta_fields && merge_strategy != RecordMergeStrategyValue::AppendOnly {
+        if !populate_meta_fields
+            && merge_strategy != RecordMergeStrategyValue::AppendOnly
+            && BuiltinKeyGenerator::for_record_keys(hudi_configs).is_err()
+        {
             return Err(ConfigError::InvalidValue(format!(
-                "When {:?} is false, {:?} must be {:?}.",
+                "When {:?} is false and the record keys cannot be generated from {:?}, {:?} must be {:?}.",
                 PopulatesMetaFields,
+                RecordKeyFields,
                 RecordMergeStrategy,
                 RecordMergeStrategyValue::AppendOnly
             )));
@@ -100,6 +108,14 @@ impl RecordMerger {
                     return Ok(data_batch.clone());
                 }
 
+                // The records of the tables that do not populate the meta fields are merged
+                // by their virtual meta fields, which are dropped from the merged records.
+                let virtual_meta_fields = VirtualMetaFields::from_configs(&self.hudi_configs)?;
+                let data_batch = match &virtual_meta_fields {
+                    Some(virtual_meta_fields) => virtual_meta_fields.prepend_to(data_batch, "")?,
+                    None => data_batch,
+                };
+
                 // Use sorting fields to get sorted indices of the data batch (inserts and updates)
                 let ordering_fields: Vec<String> = self.hudi_configs.get(OrderingFields)?.into();
                 let ordering_field = &ordering_fields[0];
@@ -183,7 +199,19 @@ impl RecordMerger {
                 // then take the records
                 let keep_mask = keep_mask_builder.finish();
                 let keep_indices = arrow::compute::filter(&desc_indices, &keep_mask)?;
-                Ok(take_record_batch(&data_batch, &keep_indices)?)
+                let merged = take_record_batch(&data_batch, &keep_indices)?;
+                match virtual_meta_fields {
+                    Some(_) => {
+                        let indices = self
+                            .schema
+                            .fields()
+                            .iter()
+                            .map(|f| merged.schema().index_of(f.name()))
+                            .collect::<std::result::Result<Vec<_>, _>>()?;
+                        Ok(merged.project(&indices)?)
+                    }
+                    None => Ok(merged),
+                }
             }
         }
     }
@@ -228,9 +256,15 @@ mod tests {
         let configs = create_configs("OVERWRITE_WITH_LATEST", true, None);
         assert!(RecordMerger::validate_configs(&configs).is_err());
 
-        // Invalid: No meta fields with overwrite strategy
+        // Invalid: No meta fields with overwrite strategy and no record key fields
         let configs = create_configs("OVERWRITE_WITH_LATEST", false, Some("ts"));
         assert!(RecordMerger::validate_configs(&configs).is_err());
+
+        // Valid: No meta fields with overwrite strategy, merging by the generated record keys
+        let mut options = configs.as_options();
+        options.insert(RecordKeyFields.as_ref().to_string(), "id".to_string());
+        let configs = HudiConfigs::new(options);
+        assert!(RecordMerger::validate_configs(&configs).is_ok());
     }
 
     fn create_schema(fields: Vec<(&str, DataType, bool)>) -> SchemaRef {
@@ -0,0 +1,105 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Models for instants archived in timeline layout version 1.
+//!
+//! Archived instants are stored as records of Avro data blocks in the archive log files
+//! under `hoodie.archivelog.folder`.
+
+use crate::Result;
+use crate::metadata::clean::{HoodieCleanMetadata, HoodieCleanerPlan};
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::replace_commit::HoodieReplaceCommitMetadata;
+use crate::metadata::rollback::HoodieRollbackMetadata;
+use crate::metadata::savepoint::HoodieSavepointMetadata;
+use apache_avro::schema::AvroSchema;
+use apache_avro::types::Value as AvroValue;
+use apache_avro::{from_value, to_value};
+use apache_avro_derive::AvroSchema as DeriveAvroSchema;
+use serde::{Deserialize, Serialize};
+
+/// An archived instant with its metadata, modeled from HoodieArchivedMetaEntry.avsc.
+///
+/// Only the metadata of the actions supported by the timeline are modeled;
+/// the other fields in the Avro schema are optional and omitted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieArchivedMetaEntry {
+    #[avro(rename = "hoodieCommitMetadata")]
+    pub hoodie_commit_metadata: Option<HoodieCommitMetadata>,
+    #[avro(rename = "hoodieCleanMetadata")]
+    pub hoodie_clean_metadata: Option<HoodieCleanMetadata>,
+    #[avro(rename = "hoodieRollbackMetadata")]
+    pub hoodie_rollback_metadata: Option<HoodieRollbackMetadata>,
+    #[serde(rename = "hoodieSavePointMetadata")]
+    #[avro(rename = "hoodieSavePointMetadata")]
+    pub hoodie_savepoint_metadata: Option<HoodieSavepointMetadata>,
+    #[avro(rename = "commitTime")]
+    pub commit_time: Option<String>,
+    #[avro(rename = "actionType")]
+    pub action_type: Option<String>,
+    pub version: Option<i32>,
+    #[avro(rename = "hoodieCleanerPlan")]
+    pub hoodie_cleaner_plan: Option<HoodieCleanerPlan>,
+    #[avro(rename = "actionState")]
+    pub action_state: Option<String>,
+    #[avro(rename = "hoodieReplaceCommitMetadata")]
+    pub hoodie_replace_commit_metadata: Option<HoodieReplaceCommitMetadata>,
+}
+
+impl HoodieArchivedMetaEntry {
+    /// Convert to an Avro value resolved against [HoodieArchivedMetaEntry::get_schema].
+    pub fn to_avro_value(&self) -> Result<AvroValue> {
+        Ok(to_value(self)?.resolve(&Self::get_schema())?)
+    }
+
+    pub fn from_avro_value(value: &AvroValue) -> Result<Self> {
+        Ok(from_value::<Self>(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_avro_value_round_trip() {
+        let entry = HoodieArchivedMetaEntry {
+            hoodie_commit_metadata: Some(HoodieCommitMetadata {
+                operation_type: Some("insert".to_string()),
+                extra_metadata: Some(HashMap::from([("schema".to_string(), "{}".to_string())])),
+                ..Default::default()
+            }),
+            commit_time: Some("20240101000000000".to_string()),
+            action_type: Some("commit".to_string()),
+            version: Some(1),
+            action_state: Some("COMPLETED".to_string()),
+            ..Default::default()
+        };
+        let value = entry.to_avro_value().unwrap();
+        let decoded = HoodieArchivedMetaEntry::from_avro_value(&value).unwrap();
+        assert_eq!(decoded.commit_time, entry.commit_time);
+        assert_eq!(decoded.action_type, entry.action_type);
+        assert_eq!(decoded.action_state, entry.action_state);
+        let commit_metadata = decoded.hoodie_commit_metadata.unwrap();
+        assert_eq!(commit_metadata.operation_type.as_deref(), Some("insert"));
+        assert!(decoded.hoodie_clean_metadata.is_none());
+    }
+}
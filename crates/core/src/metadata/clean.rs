@@ -0,0 +1,222 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Models for the metadata of `clean` instants.
+//!
+//! Both the cleaner plan (`.clean.requested`) and the clean metadata (`.clean`)
+//! are stored in Avro Object Container format for all timeline layouts.
+
+use crate::Result;
+use crate::metadata::{from_avro_container_bytes, to_avro_container_bytes};
+use apache_avro_derive::AvroSchema as DeriveAvroSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Reference to an [crate::timeline::instant::Instant] in Avro metadata.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieActionInstant {
+    pub timestamp: String,
+    pub action: String,
+    pub state: String,
+}
+
+/// A file to be deleted by a clean.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCleanFileInfo {
+    #[avro(rename = "filePath")]
+    pub file_path: Option<String>,
+    #[avro(rename = "isBootstrapBaseFile")]
+    pub is_bootstrap_base_file: Option<bool>,
+}
+
+/// The plan of a clean, stored in the requested clean instant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCleanerPlan {
+    #[avro(rename = "earliestInstantToRetain")]
+    pub earliest_instant_to_retain: Option<HoodieActionInstant>,
+    #[avro(rename = "lastCompletedCommitTimestamp")]
+    pub last_completed_commit_timestamp: String,
+    pub policy: String,
+    pub version: Option<i32>,
+    #[avro(rename = "filePathsToBeDeletedPerPartition")]
+    pub file_paths_to_be_deleted_per_partition: Option<HashMap<String, Vec<HoodieCleanFileInfo>>>,
+    #[avro(rename = "partitionsToBeDeleted")]
+    pub partitions_to_be_deleted: Option<Vec<String>>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+}
+
+impl HoodieCleanerPlan {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+}
+
+/// The result of cleaning a single partition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCleanPartitionMetadata {
+    #[avro(rename = "partitionPath")]
+    pub partition_path: String,
+    pub policy: String,
+    #[avro(rename = "deletePathPatterns")]
+    pub delete_path_patterns: Vec<String>,
+    #[avro(rename = "successDeleteFiles")]
+    pub success_delete_files: Vec<String>,
+    #[avro(rename = "failedDeleteFiles")]
+    pub failed_delete_files: Vec<String>,
+    #[avro(rename = "isPartitionDeleted")]
+    pub is_partition_deleted: Option<bool>,
+}
+
+/// The metadata of a completed clean instant.
+///
+/// # Example
+/// ```
+/// use hudi_core::metadata::clean::HoodieCleanMetadata;
+/// use apache_avro::schema::AvroSchema;
+///
+/// let schema = HoodieCleanMetadata::get_schema();
+/// println!("Schema: {}", schema.canonical_form());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCleanMetadata {
+    #[avro(rename = "startCleanTime")]
+    pub start_clean_time: String,
+    #[avro(rename = "timeTakenInMillis")]
+    pub time_taken_in_millis: i64,
+    #[avro(rename = "totalFilesDeleted")]
+    pub total_files_deleted: i32,
+    /// The earliest commit whose file slices are retained by the clean.
+    ///
+    /// Empty when the clean policy does not retain by commits.
+    #[avro(rename = "earliestCommitToRetain")]
+    pub earliest_commit_to_retain: String,
+    #[avro(rename = "lastCompletedCommitTimestamp")]
+    pub last_completed_commit_timestamp: String,
+    #[avro(rename = "partitionMetadata")]
+    pub partition_metadata: HashMap<String, HoodieCleanPartitionMetadata>,
+    pub version: Option<i32>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+}
+
+impl HoodieCleanMetadata {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+
+    /// Iterate over relative paths of all successfully deleted files.
+    pub fn iter_deleted_file_paths(&self) -> impl Iterator<Item = String> + '_ {
+        self.partition_metadata.values().flat_map(|p| {
+            p.success_delete_files.iter().map(|f| {
+                if p.partition_path.is_empty() {
+                    f.to_string()
+                } else {
+                    format!("{}/{f}", p.partition_path)
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_clean_metadata() -> HoodieCleanMetadata {
+        let partition_metadata = HoodieCleanPartitionMetadata {
+            partition_path: "p1".to_string(),
+            policy: "KEEP_LATEST_COMMITS".to_string(),
+            delete_path_patterns: vec!["f1_0-1-1_001.parquet".to_string()],
+            success_delete_files: vec!["f1_0-1-1_001.parquet".to_string()],
+            failed_delete_files: vec![],
+            is_partition_deleted: Some(false),
+        };
+        HoodieCleanMetadata {
+            start_clean_time: "20240101000000000".to_string(),
+            time_taken_in_millis: 12,
+            total_files_deleted: 1,
+            earliest_commit_to_retain: "20231231000000000".to_string(),
+            last_completed_commit_timestamp: "20231231120000000".to_string(),
+            partition_metadata: HashMap::from([("p1".to_string(), partition_metadata)]),
+            version: Some(2),
+            extra_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_clean_metadata_avro_round_trip() {
+        let metadata = sample_clean_metadata();
+        let bytes = metadata.to_avro_bytes().unwrap();
+        let decoded = HoodieCleanMetadata::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded, metadata);
+        assert_eq!(
+            decoded.iter_deleted_file_paths().collect::<Vec<_>>(),
+            vec!["p1/f1_0-1-1_001.parquet"]
+        );
+    }
+
+    #[test]
+    fn test_cleaner_plan_avro_round_trip() {
+        let plan = HoodieCleanerPlan {
+            earliest_instant_to_retain: Some(HoodieActionInstant {
+                timestamp: "20231231000000000".to_string(),
+                action: "commit".to_string(),
+                state: "COMPLETED".to_string(),
+            }),
+            last_completed_commit_timestamp: "20231231120000000".to_string(),
+            policy: "KEEP_LATEST_COMMITS".to_string(),
+            version: Some(2),
+            file_paths_to_be_deleted_per_partition: Some(HashMap::from([(
+                String::new(),
+                vec![HoodieCleanFileInfo {
+                    file_path: Some("/tmp/t/f1_0-1-1_001.parquet".to_string()),
+                    is_bootstrap_base_file: Some(false),
+                }],
+            )])),
+            partitions_to_be_deleted: None,
+            extra_metadata: None,
+        };
+        let bytes = plan.to_avro_bytes().unwrap();
+        let decoded = HoodieCleanerPlan::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded, plan);
+    }
+
+    #[test]
+    fn test_from_avro_bytes_returns_error_for_invalid_data() {
+        assert!(HoodieCleanMetadata::from_avro_bytes(b"not avro").is_err());
+    }
+}
@@ -19,6 +19,7 @@
 
 use crate::Result;
 use crate::error::CoreError;
+use crate::metadata::to_avro_container_bytes;
 use apache_avro::Reader as AvroReader;
 use apache_avro::from_value;
 use apache_avro_derive::AvroSchema as DeriveAvroSchema;
@@ -162,6 +163,11 @@ impl HoodieCommitMetadata {
         })
     }
 
+    /// Serialize commit metadata to Avro Object Container bytes (v8+ format)
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+
     /// Convert commit metadata to a JSON Map for compatibility with existing code
     ///
     /// This is useful when the metadata is read from Avro format but needs to be
@@ -416,6 +422,31 @@ mod tests {
         assert!(matches!(result, Err(CoreError::CommitMetadata(_))));
     }
 
+    #[test]
+    fn test_avro_bytes_round_trip() {
+        let stat = HoodieWriteStat {
+            file_id: Some("file-1".to_string()),
+            path: Some("p1/file-1_0-0-0_001.parquet".to_string()),
+            file_size_in_bytes: Some(1024),
+            ..Default::default()
+        };
+        let metadata = HoodieCommitMetadata {
+            version: Some(1),
+            operation_type: Some("UPSERT".to_string()),
+            partition_to_write_stats: Some(HashMap::from([("p1".to_string(), vec![stat])])),
+            ..Default::default()
+        };
+        let bytes = metadata.to_avro_bytes().unwrap();
+        let parsed = HoodieCommitMetadata::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(parsed.operation_type.as_deref(), Some("UPSERT"));
+        let stats = parsed.get_partition_write_stats("p1").unwrap();
+        assert_eq!(stats[0].file_size_in_bytes, Some(1024));
+        assert_eq!(
+            stats[0].path,
+            metadata.get_partition_write_stats("p1").unwrap()[0].path
+        );
+    }
+
     #[test]
     fn test_from_avro_bytes_invalid() {
         // Invalid Avro data that cannot be parsed
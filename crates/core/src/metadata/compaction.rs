@@ -0,0 +1,109 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Models for the plans of `compaction` instants.
+//!
+//! The compaction plan (`.compaction.requested`) is stored in Avro Object
+//! Container format for all timeline layouts.
+
+use crate::Result;
+use crate::metadata::{from_avro_container_bytes, to_avro_container_bytes};
+use apache_avro_derive::AvroSchema as DeriveAvroSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The compaction of a file slice into a new base file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCompactionOperation {
+    #[avro(rename = "baseInstantTime")]
+    pub base_instant_time: Option<String>,
+    #[avro(rename = "deltaFilePaths")]
+    pub delta_file_paths: Option<Vec<String>>,
+    #[avro(rename = "dataFilePath")]
+    pub data_file_path: Option<String>,
+    #[avro(rename = "fileId")]
+    pub file_id: String,
+    #[avro(rename = "partitionPath")]
+    pub partition_path: Option<String>,
+    pub metrics: Option<HashMap<String, f64>>,
+    #[avro(rename = "bootstrapFilePath")]
+    pub bootstrap_file_path: Option<String>,
+}
+
+/// The plan of a compaction, stored in the requested compaction instant.
+///
+/// This is modeled from HoodieCompactionPlan.avsc; the strategy is not modeled.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieCompactionPlan {
+    pub operations: Option<Vec<HoodieCompactionOperation>>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+    pub version: Option<i32>,
+}
+
+impl HoodieCompactionPlan {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+
+    /// Iterate over the file groups to be compacted as `(partition path, file id)`.
+    pub fn iter_file_group_ids(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.operations.iter().flatten().map(|op| {
+            (
+                op.partition_path.as_deref().unwrap_or_default(),
+                op.file_id.as_str(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compaction_plan_avro_round_trip() {
+        let plan = HoodieCompactionPlan {
+            operations: Some(vec![HoodieCompactionOperation {
+                base_instant_time: Some("20240101000000000".to_string()),
+                delta_file_paths: Some(vec![".a-0_20240101000000000.log.1_0-1-1".to_string()]),
+                data_file_path: Some("a-0_0-1-1_20240101000000000.parquet".to_string()),
+                file_id: "a-0".to_string(),
+                partition_path: Some("sf".to_string()),
+                ..Default::default()
+            }]),
+            version: Some(2),
+            ..Default::default()
+        };
+        let decoded =
+            HoodieCompactionPlan::from_avro_bytes(&plan.to_avro_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, plan);
+        assert_eq!(
+            decoded.iter_file_group_ids().collect::<Vec<_>>(),
+            [("sf", "a-0")]
+        );
+    }
+}
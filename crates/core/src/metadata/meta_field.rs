@@ -77,6 +77,13 @@ lazy_static! {
         Field::new(MetaField::PartitionPath.as_ref(), DataType::Utf8, false),
         Field::new(MetaField::FileName.as_ref(), DataType::Utf8, false),
     ]));
+    static ref VIRTUAL_SCHEMA: Arc<Schema> = Arc::new(Schema::new(vec![
+        Field::new(MetaField::CommitTime.as_ref(), DataType::Utf8, true),
+        Field::new(MetaField::CommitSeqno.as_ref(), DataType::Utf8, true),
+        Field::new(MetaField::RecordKey.as_ref(), DataType::Utf8, true),
+        Field::new(MetaField::PartitionPath.as_ref(), DataType::Utf8, false),
+        Field::new(MetaField::FileName.as_ref(), DataType::Utf8, false),
+    ]));
     static ref SCHEMA_WITH_OPERATION: Arc<Schema> = Arc::new(Schema::new(vec![
         Field::new(MetaField::CommitTime.as_ref(), DataType::Utf8, false),
         Field::new(MetaField::CommitSeqno.as_ref(), DataType::Utf8, false),
@@ -97,6 +104,13 @@ impl MetaField {
         SCHEMA.clone()
     }
 
+    /// The schema of the meta fields synthesized on read for the tables that do not populate
+    /// them, where the commit time, the commit sequence number, and the record key may be null
+    /// as they cannot always be derived.
+    pub fn virtual_schema() -> SchemaRef {
+        VIRTUAL_SCHEMA.clone()
+    }
+
     pub fn schema_with_operation() -> SchemaRef {
         SCHEMA_WITH_OPERATION.clone()
     }
@@ -245,6 +259,12 @@ mod tests {
             MetaField::schema().fields[4].name(),
             MetaField::FileName.as_ref()
         );
+        assert_eq!(
+            MetaField::virtual_schema().fields.len(),
+            MetaField::schema().fields.len()
+        );
+        assert!(MetaField::virtual_schema().fields[0].is_nullable());
+        assert!(!MetaField::virtual_schema().fields[4].is_nullable());
         assert_eq!(MetaField::schema_with_operation().fields.len(), 6);
         assert_eq!(
             MetaField::schema_with_operation().fields[5].name(),
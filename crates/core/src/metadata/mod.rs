@@ -16,10 +16,24 @@
  * specific language governing permissions and limitations
  * under the License.
  */
+use crate::Result;
+use crate::error::CoreError;
+use apache_avro::schema::AvroSchema;
+use apache_avro::{Reader as AvroReader, Writer as AvroWriter, from_value};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::Cursor;
+
+pub mod archived;
+pub mod clean;
 pub mod commit;
+pub mod compaction;
 pub mod merger;
 pub mod meta_field;
 pub mod replace_commit;
+pub mod restore;
+pub mod rollback;
+pub mod savepoint;
 pub mod table;
 pub use table::records as table_record;
 
@@ -35,3 +49,22 @@ pub const LAKE_FORMAT_METADATA_DIRS: &[&str; 3] = &[
 
 /// The virtual partition field name used in metadata tables.
 pub const METADATA_TABLE_PARTITION_FIELD: &str = "partition";
+
+/// Serialize a single record into Avro Object Container bytes with the record's schema embedded.
+///
+/// This is the format of timeline instant files written in Avro, e.g., `clean` instants.
+pub(crate) fn to_avro_container_bytes<T: Serialize + AvroSchema>(record: &T) -> Result<Vec<u8>> {
+    let schema = T::get_schema();
+    let mut writer = AvroWriter::new(&schema, Vec::new());
+    writer.append_ser(record)?;
+    Ok(writer.into_inner()?)
+}
+
+/// Deserialize the first record from Avro Object Container bytes.
+pub(crate) fn from_avro_container_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let mut reader = AvroReader::new(Cursor::new(bytes))?;
+    let value = reader
+        .next()
+        .ok_or_else(|| CoreError::InvalidValue("Avro file contains no records".to_string()))??;
+    Ok(from_value::<T>(&value)?)
+}
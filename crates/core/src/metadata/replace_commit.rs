@@ -19,7 +19,8 @@
 
 use crate::Result;
 use crate::error::CoreError;
-use crate::metadata::commit::HoodieWriteStat;
+use crate::metadata::commit::{HoodieCommitMetadata, HoodieWriteStat};
+use crate::metadata::{from_avro_container_bytes, to_avro_container_bytes};
 use apache_avro_derive::AvroSchema as DeriveAvroSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -28,7 +29,7 @@ use std::collections::HashMap;
 /// Represents the metadata for a Hudi Replace Commit
 ///
 /// This is modeled from HoodieReplaceCommitMetadata.avsc.
-#[derive(Debug, Clone, Serialize, Deserialize, DeriveAvroSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, DeriveAvroSchema)]
 #[serde(rename_all = "camelCase")]
 #[avro(namespace = "org.apache.hudi.avro.model")]
 pub struct HoodieReplaceCommitMetadata {
@@ -58,6 +59,11 @@ impl HoodieReplaceCommitMetadata {
             .map_err(|e| CoreError::CommitMetadata(format!("Failed to parse commit metadata: {e}")))
     }
 
+    /// Serialize replace commit metadata to Avro Object Container format bytes
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+
     /// Iterate over all replace file IDs across all partitions
     pub fn iter_replace_file_ids(&self) -> impl Iterator<Item = (&String, &String)> {
         self.partition_to_replace_file_ids
@@ -71,6 +77,135 @@ impl HoodieReplaceCommitMetadata {
     }
 }
 
+impl From<&HoodieCommitMetadata> for HoodieReplaceCommitMetadata {
+    fn from(metadata: &HoodieCommitMetadata) -> Self {
+        Self {
+            version: metadata.version,
+            operation_type: metadata.operation_type.clone(),
+            partition_to_write_stats: metadata.partition_to_write_stats.clone(),
+            compacted: metadata.compacted,
+            extra_metadata: metadata.extra_metadata.clone(),
+            partition_to_replace_file_ids: metadata.partition_to_replace_file_ids.clone(),
+        }
+    }
+}
+
+/// Config key of the columns that clustering sorts the records by, as stored
+/// in the strategy params of a clustering plan.
+pub const CLUSTERING_SORT_COLUMNS_KEY: &str = "hoodie.clustering.plan.strategy.sort.columns";
+
+/// The strategy of a clustering plan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieClusteringStrategy {
+    #[avro(rename = "strategyClassName")]
+    pub strategy_class_name: Option<String>,
+    #[avro(rename = "strategyParams")]
+    pub strategy_params: Option<HashMap<String, String>>,
+    pub version: Option<i32>,
+}
+
+/// A file slice to be rewritten by a clustering.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieSliceInfo {
+    #[avro(rename = "dataFilePath")]
+    pub data_file_path: Option<String>,
+    #[avro(rename = "deltaFilePaths")]
+    pub delta_file_paths: Option<Vec<String>>,
+    #[avro(rename = "fileId")]
+    pub file_id: String,
+    #[avro(rename = "partitionPath")]
+    pub partition_path: String,
+    #[avro(rename = "bootstrapFilePath")]
+    pub bootstrap_file_path: Option<String>,
+    pub version: Option<i32>,
+}
+
+/// A group of file slices to be rewritten together by a clustering; the
+/// metrics are not modeled.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieClusteringGroup {
+    pub slices: Vec<HoodieSliceInfo>,
+    #[avro(rename = "numOutputFileGroups")]
+    pub num_output_file_groups: Option<i32>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+    pub version: Option<i32>,
+}
+
+/// The plan of a clustering.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieClusteringPlan {
+    #[avro(rename = "inputGroups")]
+    pub input_groups: Option<Vec<HoodieClusteringGroup>>,
+    pub strategy: Option<HoodieClusteringStrategy>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+    pub version: Option<i32>,
+    #[avro(rename = "preserveHoodieMetadata")]
+    pub preserve_hoodie_metadata: Option<bool>,
+}
+
+impl HoodieClusteringPlan {
+    /// Iterate over the file groups to be clustered as `(partition path, file id)`.
+    pub fn iter_file_group_ids(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.input_groups
+            .iter()
+            .flatten()
+            .flat_map(|group| &group.slices)
+            .map(|slice| (slice.partition_path.as_str(), slice.file_id.as_str()))
+    }
+
+    /// Returns the columns that the clustering sorts the records by, if any.
+    pub fn sort_columns(&self) -> Option<Vec<String>> {
+        let columns: Vec<String> = self
+            .strategy
+            .as_ref()?
+            .strategy_params
+            .as_ref()?
+            .get(CLUSTERING_SORT_COLUMNS_KEY)?
+            .split(',')
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+        (!columns.is_empty()).then_some(columns)
+    }
+}
+
+/// The requested metadata of a replace commit, stored in the requested
+/// `replacecommit` or `clustering` instant.
+///
+/// This is modeled from HoodieRequestedReplaceMetadata.avsc.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRequestedReplaceMetadata {
+    #[avro(rename = "operationType")]
+    pub operation_type: Option<String>,
+    #[avro(rename = "clusteringPlan")]
+    pub clustering_plan: Option<HoodieClusteringPlan>,
+    #[avro(rename = "extraMetadata")]
+    pub extra_metadata: Option<HashMap<String, String>>,
+    pub version: Option<i32>,
+}
+
+impl HoodieRequestedReplaceMetadata {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +260,77 @@ mod tests {
         let count = metadata.iter_replace_file_ids().count();
         assert_eq!(count, 0);
     }
+
+    #[test]
+    fn test_to_avro_bytes_read_as_commit_metadata() {
+        let commit_metadata = HoodieCommitMetadata {
+            version: Some(1),
+            operation_type: Some("INSERT_OVERWRITE".to_string()),
+            partition_to_replace_file_ids: Some(HashMap::from([(
+                "p1".to_string(),
+                vec!["a-0".to_string()],
+            )])),
+            ..Default::default()
+        };
+        let bytes = HoodieReplaceCommitMetadata::from(&commit_metadata)
+            .to_avro_bytes()
+            .unwrap();
+        let decoded = HoodieCommitMetadata::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded.operation_type.as_deref(), Some("INSERT_OVERWRITE"));
+        assert_eq!(
+            decoded.get_partition_replace_file_ids("p1"),
+            Some(&vec!["a-0".to_string()])
+        );
+    }
+
+    fn clustering_plan_with_params(params: &[(&str, &str)]) -> HoodieClusteringPlan {
+        HoodieClusteringPlan {
+            strategy: Some(HoodieClusteringStrategy {
+                strategy_class_name: Some("SparkSortAndSizeExecutionStrategy".to_string()),
+                strategy_params: Some(
+                    params
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+                version: Some(1),
+            }),
+            version: Some(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clustering_plan_sort_columns() {
+        let plan = clustering_plan_with_params(&[(CLUSTERING_SORT_COLUMNS_KEY, "a, b,")]);
+        assert_eq!(
+            plan.sort_columns(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+
+        let plan = clustering_plan_with_params(&[(CLUSTERING_SORT_COLUMNS_KEY, "")]);
+        assert_eq!(plan.sort_columns(), None);
+        assert_eq!(clustering_plan_with_params(&[]).sort_columns(), None);
+        assert_eq!(HoodieClusteringPlan::default().sort_columns(), None);
+    }
+
+    #[test]
+    fn test_requested_replace_metadata_avro_round_trip() {
+        let metadata = HoodieRequestedReplaceMetadata {
+            operation_type: Some("CLUSTER".to_string()),
+            clustering_plan: Some(clustering_plan_with_params(&[(
+                CLUSTERING_SORT_COLUMNS_KEY,
+                "txn_ts",
+            )])),
+            version: Some(1),
+            ..Default::default()
+        };
+        let bytes = metadata.to_avro_bytes().unwrap();
+        let decoded = HoodieRequestedReplaceMetadata::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded, metadata);
+        assert_eq!(
+            decoded.clustering_plan.unwrap().sort_columns(),
+            Some(vec!["txn_ts".to_string()])
+        );
+    }
 }
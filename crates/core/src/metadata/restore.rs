@@ -0,0 +1,141 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Models for the metadata of `restore` instants.
+//!
+//! Both the restore plan (`.restore.requested`) and the restore metadata (`.restore`)
+//! are stored in Avro Object Container format for all timeline layouts.
+
+use crate::Result;
+use crate::metadata::rollback::{HoodieInstantInfo, HoodieRollbackMetadata};
+use crate::metadata::{from_avro_container_bytes, to_avro_container_bytes};
+use apache_avro_derive::AvroSchema as DeriveAvroSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The plan of a restore, stored in the requested restore instant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRestorePlan {
+    /// The instants to roll back, from the latest to the earliest.
+    #[avro(rename = "instantsToRollback")]
+    pub instants_to_rollback: Vec<HoodieInstantInfo>,
+    pub version: Option<i32>,
+}
+
+impl HoodieRestorePlan {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+}
+
+/// The metadata of a completed restore instant.
+///
+/// # Example
+/// ```
+/// use hudi_core::metadata::restore::HoodieRestoreMetadata;
+/// use apache_avro::schema::AvroSchema;
+///
+/// let schema = HoodieRestoreMetadata::get_schema();
+/// println!("Schema: {}", schema.canonical_form());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRestoreMetadata {
+    #[avro(rename = "startRestoreTime")]
+    pub start_restore_time: String,
+    #[avro(rename = "timeTakenInMillis")]
+    pub time_taken_in_millis: i64,
+    #[avro(rename = "instantsToRollback")]
+    pub instants_to_rollback: Vec<String>,
+    /// The instant time of each rolled back instant to its rollback metadata.
+    #[avro(rename = "hoodieRestoreMetadata")]
+    pub hoodie_restore_metadata: HashMap<String, Vec<HoodieRollbackMetadata>>,
+    pub version: Option<i32>,
+    #[avro(rename = "restoreInstantInfo")]
+    pub restore_instant_info: Vec<HoodieInstantInfo>,
+}
+
+impl HoodieRestoreMetadata {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::rollback::HoodieRollbackPartitionMetadata;
+
+    #[test]
+    fn test_restore_metadata_avro_round_trip() {
+        let instant = HoodieInstantInfo {
+            commit_time: "20240102000000000".to_string(),
+            action: "deltacommit".to_string(),
+        };
+        let rollback_metadata = HoodieRollbackMetadata {
+            start_rollback_time: "20240103000000000".to_string(),
+            total_files_deleted: 1,
+            commits_rollback: vec![instant.commit_time.clone()],
+            partition_metadata: HashMap::from([(
+                "p1".to_string(),
+                HoodieRollbackPartitionMetadata {
+                    partition_path: "p1".to_string(),
+                    success_delete_files: vec!["f1_0-1-1_20240102000000000.parquet".to_string()],
+                    ..Default::default()
+                },
+            )]),
+            version: Some(1),
+            instants_rollback: vec![instant.clone()],
+            ..Default::default()
+        };
+        let metadata = HoodieRestoreMetadata {
+            start_restore_time: "20240103000000000".to_string(),
+            time_taken_in_millis: 20,
+            instants_to_rollback: vec![instant.commit_time.clone()],
+            hoodie_restore_metadata: HashMap::from([(
+                instant.commit_time.clone(),
+                vec![rollback_metadata],
+            )]),
+            version: Some(1),
+            restore_instant_info: vec![instant.clone()],
+        };
+        let bytes = metadata.to_avro_bytes().unwrap();
+        assert_eq!(
+            HoodieRestoreMetadata::from_avro_bytes(&bytes).unwrap(),
+            metadata
+        );
+
+        let plan = HoodieRestorePlan {
+            instants_to_rollback: vec![instant],
+            version: Some(1),
+        };
+        let bytes = plan.to_avro_bytes().unwrap();
+        assert_eq!(HoodieRestorePlan::from_avro_bytes(&bytes).unwrap(), plan);
+    }
+}
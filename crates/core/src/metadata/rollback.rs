@@ -0,0 +1,212 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Models for the metadata of `rollback` instants.
+//!
+//! Both the rollback plan (`.rollback.requested`) and the rollback metadata (`.rollback`)
+//! are stored in Avro Object Container format for all timeline layouts.
+
+use crate::Result;
+use crate::metadata::{from_avro_container_bytes, to_avro_container_bytes};
+use apache_avro_derive::AvroSchema as DeriveAvroSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Reference to the [crate::timeline::instant::Instant] rolled back.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieInstantInfo {
+    #[avro(rename = "commitTime")]
+    pub commit_time: String,
+    pub action: String,
+}
+
+/// The files of a file group to be deleted, or the log blocks to be rolled back, by a rollback.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRollbackRequest {
+    #[avro(rename = "partitionPath")]
+    pub partition_path: String,
+    #[avro(rename = "fileId")]
+    pub file_id: Option<String>,
+    #[avro(rename = "latestBaseInstant")]
+    pub latest_base_instant: Option<String>,
+    #[avro(rename = "filesToBeDeleted")]
+    pub files_to_be_deleted: Vec<String>,
+    /// The log files to append rollback command blocks to, with their sizes.
+    #[avro(rename = "logBlocksToBeDeleted")]
+    pub log_blocks_to_be_deleted: Option<HashMap<String, i64>>,
+}
+
+/// The plan of a rollback, stored in the requested rollback instant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRollbackPlan {
+    #[avro(rename = "instantToRollback")]
+    pub instant_to_rollback: Option<HoodieInstantInfo>,
+    #[serde(rename = "RollbackRequests")]
+    #[avro(rename = "RollbackRequests")]
+    pub rollback_requests: Option<Vec<HoodieRollbackRequest>>,
+    pub version: Option<i32>,
+}
+
+impl HoodieRollbackPlan {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+}
+
+/// The result of rolling back a single partition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRollbackPartitionMetadata {
+    #[avro(rename = "partitionPath")]
+    pub partition_path: String,
+    #[avro(rename = "successDeleteFiles")]
+    pub success_delete_files: Vec<String>,
+    #[avro(rename = "failedDeleteFiles")]
+    pub failed_delete_files: Vec<String>,
+    /// The log files with rollback command blocks appended, with their sizes.
+    #[avro(rename = "rollbackLogFiles")]
+    pub rollback_log_files: Option<HashMap<String, i64>>,
+    /// The log files written by the rolled back instant, with their sizes.
+    #[avro(rename = "logFilesFromFailedCommit")]
+    pub log_files_from_failed_commit: Option<HashMap<String, i64>>,
+}
+
+/// The metadata of a completed rollback instant.
+///
+/// # Example
+/// ```
+/// use hudi_core::metadata::rollback::HoodieRollbackMetadata;
+/// use apache_avro::schema::AvroSchema;
+///
+/// let schema = HoodieRollbackMetadata::get_schema();
+/// println!("Schema: {}", schema.canonical_form());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DeriveAvroSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[avro(namespace = "org.apache.hudi.avro.model")]
+pub struct HoodieRollbackMetadata {
+    #[avro(rename = "startRollbackTime")]
+    pub start_rollback_time: String,
+    #[avro(rename = "timeTakenInMillis")]
+    pub time_taken_in_millis: i64,
+    #[avro(rename = "totalFilesDeleted")]
+    pub total_files_deleted: i32,
+    #[avro(rename = "commitsRollback")]
+    pub commits_rollback: Vec<String>,
+    #[avro(rename = "partitionMetadata")]
+    pub partition_metadata: HashMap<String, HoodieRollbackPartitionMetadata>,
+    pub version: Option<i32>,
+    #[avro(rename = "instantsRollback")]
+    pub instants_rollback: Vec<HoodieInstantInfo>,
+}
+
+impl HoodieRollbackMetadata {
+    pub fn from_avro_bytes(bytes: &[u8]) -> Result<Self> {
+        from_avro_container_bytes(bytes)
+    }
+
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>> {
+        to_avro_container_bytes(self)
+    }
+
+    /// Iterate over relative paths of all successfully deleted files.
+    pub fn iter_deleted_file_paths(&self) -> impl Iterator<Item = String> + '_ {
+        self.partition_metadata.values().flat_map(|p| {
+            p.success_delete_files.iter().map(|f| {
+                if p.partition_path.is_empty() {
+                    f.to_string()
+                } else {
+                    format!("{}/{f}", p.partition_path)
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_avro::schema::AvroSchema;
+
+    #[test]
+    fn test_rollback_metadata_avro_round_trip() {
+        let partition_metadata = HoodieRollbackPartitionMetadata {
+            partition_path: "p1".to_string(),
+            success_delete_files: vec!["f1_0-1-1_002.parquet".to_string()],
+            failed_delete_files: vec![],
+            rollback_log_files: Some(HashMap::from([(".f2_001.log.2_0-0-0".to_string(), 120)])),
+            log_files_from_failed_commit: None,
+        };
+        let metadata = HoodieRollbackMetadata {
+            start_rollback_time: "20240101000000000".to_string(),
+            time_taken_in_millis: 12,
+            total_files_deleted: 1,
+            commits_rollback: vec!["20231231000000000".to_string()],
+            partition_metadata: HashMap::from([("p1".to_string(), partition_metadata)]),
+            version: Some(1),
+            instants_rollback: vec![HoodieInstantInfo {
+                commit_time: "20231231000000000".to_string(),
+                action: "deltacommit".to_string(),
+            }],
+        };
+        let bytes = metadata.to_avro_bytes().unwrap();
+        let decoded = HoodieRollbackMetadata::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded, metadata);
+        assert_eq!(
+            decoded.iter_deleted_file_paths().collect::<Vec<_>>(),
+            vec!["p1/f1_0-1-1_002.parquet"]
+        );
+    }
+
+    #[test]
+    fn test_rollback_plan_avro_round_trip() {
+        let plan = HoodieRollbackPlan {
+            instant_to_rollback: Some(HoodieInstantInfo {
+                commit_time: "20231231000000000".to_string(),
+                action: "commit".to_string(),
+            }),
+            rollback_requests: Some(vec![HoodieRollbackRequest {
+                partition_path: String::new(),
+                file_id: Some("f1".to_string()),
+                latest_base_instant: Some("20231231000000000".to_string()),
+                files_to_be_deleted: vec!["f1_0-1-1_20231231000000000.parquet".to_string()],
+                log_blocks_to_be_deleted: None,
+            }]),
+            version: Some(1),
+        };
+        let bytes = plan.to_avro_bytes().unwrap();
+        let decoded = HoodieRollbackPlan::from_avro_bytes(&bytes).unwrap();
+        assert_eq!(decoded, plan);
+        assert!(
+            HoodieRollbackPlan::get_schema()
+                .canonical_form()
+                .contains("\"RollbackRequests\"")
+        );
+    }
+}
@@ -0,0 +1,1025 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Records of the `column_stats` and `partition_stats` partitions of the metadata table.
+//!
+//! # Record Keys
+//!
+//! Keys are concatenations of base64-encoded hashes, as generated by Hudi Java:
+//! - `column_stats`: `XXHash64(column name) + XXHash64(partition path) + MD5(file name)`
+//! - `partition_stats`: `XXHash64(column name) + XXHash64(partition path)`
+//!
+//! # Record Values
+//!
+//! Values are `HoodieMetadataColumnStats` records in the layout of index version 2: min/max
+//! values are wrapped by their primitive type (e.g., a date is an `IntWrapper`), and the
+//! logical type is kept in `valueType`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use apache_avro::Schema as AvroSchema;
+use apache_avro::schema::{RecordSchema, UnionSchema};
+use apache_avro::to_avro_datum;
+use apache_avro::types::Value as AvroValue;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Decimal128Type, Float32Type, Float64Type, Int32Type, Int64Type, TimestampMillisecondType,
+};
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array,
+    Float64Array, Int32Array, Int64Array, StringArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use md5::{Digest, Md5};
+use parquet::arrow::parquet_to_arrow_schema;
+use parquet::file::metadata::ParquetMetaData;
+use twox_hash::XxHash64;
+
+use crate::Result;
+use crate::error::CoreError;
+use crate::metadata::meta_field::MetaField;
+use crate::metadata::table::records::{
+    FilesPartitionRecord, MetadataPartitionType, MetadataRecordType, get_record_type,
+};
+use crate::statistics::{ColumnStatistics, StatisticsContainer};
+
+/// Seed of the XXHash64 hashes of column names and partition paths.
+///
+/// This is the Java int `0xdabadaba` sign-extended to a long.
+const XXHASH64_SEED: u64 = 0xFFFF_FFFF_DABA_DABA;
+
+/// Max number of data columns to index, same as the default of
+/// `hoodie.metadata.index.column.stats.max.columns.to.index` in Hudi Java.
+const MAX_DATA_COLUMNS_TO_INDEX: usize = 32;
+
+/// Meta fields that are always indexed, when meta fields are populated.
+const INDEXED_META_FIELDS: [MetaField; 3] = [
+    MetaField::CommitTime,
+    MetaField::PartitionPath,
+    MetaField::RecordKey,
+];
+
+/// Ordinals of the value types in `HoodieValueTypeInfo`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+enum ValueType {
+    Null = 1,
+    Boolean = 2,
+    Int = 3,
+    Long = 4,
+    Float = 5,
+    Double = 6,
+    String = 7,
+    Bytes = 8,
+    Decimal = 10,
+    Date = 12,
+    TimestampMillis = 15,
+    TimestampMicros = 16,
+    TimestampNanos = 17,
+    LocalTimestampMillis = 18,
+    LocalTimestampMicros = 19,
+    LocalTimestampNanos = 20,
+}
+
+impl ValueType {
+    /// Get the value type of an indexable Arrow data type.
+    fn from_data_type(data_type: &DataType) -> Option<Self> {
+        let value_type = match data_type {
+            DataType::Boolean => Self::Boolean,
+            DataType::Int8 | DataType::Int16 | DataType::Int32 => Self::Int,
+            DataType::Int64 => Self::Long,
+            DataType::Float32 => Self::Float,
+            DataType::Float64 => Self::Double,
+            DataType::Utf8 | DataType::LargeUtf8 => Self::String,
+            DataType::Binary | DataType::LargeBinary => Self::Bytes,
+            DataType::Decimal128(_, _) => Self::Decimal,
+            DataType::Date32 => Self::Date,
+            DataType::Timestamp(unit, tz) => match (unit, tz.is_some()) {
+                (TimeUnit::Second | TimeUnit::Millisecond, true) => Self::TimestampMillis,
+                (TimeUnit::Microsecond, true) => Self::TimestampMicros,
+                (TimeUnit::Nanosecond, true) => Self::TimestampNanos,
+                (TimeUnit::Second | TimeUnit::Millisecond, false) => Self::LocalTimestampMillis,
+                (TimeUnit::Microsecond, false) => Self::LocalTimestampMicros,
+                (TimeUnit::Nanosecond, false) => Self::LocalTimestampNanos,
+            },
+            _ => return None,
+        };
+        Some(value_type)
+    }
+
+    /// Get the value type of an ordinal in `HoodieValueTypeInfo`.
+    fn from_ordinal(ordinal: i32) -> Option<Self> {
+        [
+            Self::Null,
+            Self::Boolean,
+            Self::Int,
+            Self::Long,
+            Self::Float,
+            Self::Double,
+            Self::String,
+            Self::Bytes,
+            Self::Decimal,
+            Self::Date,
+            Self::TimestampMillis,
+            Self::TimestampMicros,
+            Self::TimestampNanos,
+            Self::LocalTimestampMillis,
+            Self::LocalTimestampMicros,
+            Self::LocalTimestampNanos,
+        ]
+        .into_iter()
+        .find(|t| *t as i32 == ordinal)
+    }
+
+    /// Time unit of the wrapped long values of the timestamp types.
+    fn time_unit(self) -> Option<TimeUnit> {
+        match self {
+            Self::TimestampMillis | Self::LocalTimestampMillis => Some(TimeUnit::Millisecond),
+            Self::TimestampMicros | Self::LocalTimestampMicros => Some(TimeUnit::Microsecond),
+            Self::TimestampNanos | Self::LocalTimestampNanos => Some(TimeUnit::Nanosecond),
+            _ => None,
+        }
+    }
+
+    /// Name of the wrapper record of the min/max values of this type.
+    fn wrapper_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "BooleanWrapper",
+            Self::Int | Self::Date => "IntWrapper",
+            Self::Long
+            | Self::TimestampMillis
+            | Self::TimestampMicros
+            | Self::TimestampNanos
+            | Self::LocalTimestampMillis
+            | Self::LocalTimestampMicros
+            | Self::LocalTimestampNanos => "LongWrapper",
+            Self::Float => "FloatWrapper",
+            Self::Double => "DoubleWrapper",
+            Self::String => "StringWrapper",
+            Self::Bytes | Self::Decimal => "BytesWrapper",
+        }
+    }
+
+    /// Convert a single-element array of min/max value to the value of the wrapper record.
+    fn to_wrapped_value(self, value: &ArrayRef) -> Option<AvroValue> {
+        if value.is_empty() || value.is_null(0) {
+            return None;
+        }
+        let cast = |to_type: &DataType| arrow_cast::cast(value, to_type).ok();
+        let wrapped = match self {
+            Self::Null => return None,
+            Self::Boolean => AvroValue::Boolean(value.as_boolean_opt()?.value(0)),
+            Self::Int | Self::Date => {
+                AvroValue::Int(cast(&DataType::Int32)?.as_primitive::<Int32Type>().value(0))
+            }
+            Self::Long
+            | Self::TimestampMicros
+            | Self::TimestampNanos
+            | Self::LocalTimestampMicros
+            | Self::LocalTimestampNanos => {
+                AvroValue::Long(cast(&DataType::Int64)?.as_primitive::<Int64Type>().value(0))
+            }
+            Self::TimestampMillis | Self::LocalTimestampMillis => {
+                let millis = cast(&DataType::Timestamp(TimeUnit::Millisecond, None))?;
+                AvroValue::Long(millis.as_primitive::<TimestampMillisecondType>().value(0))
+            }
+            Self::Float => AvroValue::Float(
+                cast(&DataType::Float32)?
+                    .as_primitive::<Float32Type>()
+                    .value(0),
+            ),
+            Self::Double => AvroValue::Double(
+                cast(&DataType::Float64)?
+                    .as_primitive::<Float64Type>()
+                    .value(0),
+            ),
+            Self::String => AvroValue::String(
+                cast(&DataType::Utf8)?
+                    .as_string::<i32>()
+                    .value(0)
+                    .to_string(),
+            ),
+            Self::Bytes => AvroValue::Bytes(
+                cast(&DataType::Binary)?
+                    .as_binary::<i32>()
+                    .value(0)
+                    .to_vec(),
+            ),
+            Self::Decimal => AvroValue::Bytes(decimal_to_unscaled_bytes(value)?),
+        };
+        Some(wrapped)
+    }
+}
+
+/// Convert a decimal min/max value to the minimal big-endian two's complement bytes of the
+/// unscaled value, same as Java's `BigInteger.toByteArray()`.
+///
+/// Parquet statistics of decimals are read as Int32, Int64, or fixed-length byte arrays.
+fn decimal_to_unscaled_bytes(value: &ArrayRef) -> Option<Vec<u8>> {
+    let bytes = match value.data_type() {
+        DataType::Int32 => i128::from(value.as_primitive::<Int32Type>().value(0))
+            .to_be_bytes()
+            .to_vec(),
+        DataType::Int64 => i128::from(value.as_primitive::<Int64Type>().value(0))
+            .to_be_bytes()
+            .to_vec(),
+        DataType::Decimal128(_, _) => value
+            .as_primitive::<Decimal128Type>()
+            .value(0)
+            .to_be_bytes()
+            .to_vec(),
+        DataType::Binary => value.as_binary::<i32>().value(0).to_vec(),
+        _ => return None,
+    };
+    let redundant = bytes
+        .windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] & 0x80 == 0) || (w[0] == 0xFF && w[1] & 0x80 != 0))
+        .count();
+    Some(bytes[redundant..].to_vec())
+}
+
+/// Convert the big-endian two's complement bytes of an unscaled decimal value to an `i128`.
+fn unscaled_bytes_to_i128(bytes: &[u8]) -> Option<i128> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buf))
+}
+
+/// Convert the value of a min/max wrapper record to a single-element array of the column type.
+///
+/// Timestamps are read in the unit of the value type, or in the unit of the column type when
+/// the value type is unknown, e.g., for records written without `valueType`.
+fn wrapped_value_to_array(
+    value: &AvroValue,
+    value_type: Option<ValueType>,
+    data_type: &DataType,
+) -> Option<ArrayRef> {
+    let timestamp = |v: i64, unit: Option<TimeUnit>| -> Option<ArrayRef> {
+        let DataType::Timestamp(target_unit, tz) = data_type else {
+            return None;
+        };
+        let array: ArrayRef = match unit.unwrap_or(*target_unit) {
+            TimeUnit::Second => {
+                Arc::new(TimestampSecondArray::from(vec![v]).with_timezone_opt(tz.clone()))
+            }
+            TimeUnit::Millisecond => {
+                Arc::new(TimestampMillisecondArray::from(vec![v]).with_timezone_opt(tz.clone()))
+            }
+            TimeUnit::Microsecond => {
+                Arc::new(TimestampMicrosecondArray::from(vec![v]).with_timezone_opt(tz.clone()))
+            }
+            TimeUnit::Nanosecond => {
+                Arc::new(TimestampNanosecondArray::from(vec![v]).with_timezone_opt(tz.clone()))
+            }
+        };
+        Some(array)
+    };
+    let decimal = |bytes: &[u8]| -> Option<ArrayRef> {
+        let DataType::Decimal128(precision, scale) = data_type else {
+            return None;
+        };
+        let array = Decimal128Array::from(vec![unscaled_bytes_to_i128(bytes)?])
+            .with_precision_and_scale(*precision, *scale)
+            .ok()?;
+        Some(Arc::new(array))
+    };
+    let array: ArrayRef = match value {
+        AvroValue::Boolean(v) => Arc::new(BooleanArray::from(vec![*v])),
+        AvroValue::Int(v) => Arc::new(Int32Array::from(vec![*v])),
+        AvroValue::Date(v) => Arc::new(Date32Array::from(vec![*v])),
+        AvroValue::Long(v) if matches!(data_type, DataType::Timestamp(_, _)) => {
+            timestamp(*v, value_type.and_then(ValueType::time_unit))?
+        }
+        AvroValue::Long(v) => Arc::new(Int64Array::from(vec![*v])),
+        AvroValue::TimestampMillis(v) | AvroValue::LocalTimestampMillis(v) => {
+            timestamp(*v, Some(TimeUnit::Millisecond))?
+        }
+        AvroValue::TimestampMicros(v) | AvroValue::LocalTimestampMicros(v) => {
+            timestamp(*v, Some(TimeUnit::Microsecond))?
+        }
+        AvroValue::TimestampNanos(v) | AvroValue::LocalTimestampNanos(v) => {
+            timestamp(*v, Some(TimeUnit::Nanosecond))?
+        }
+        AvroValue::Float(v) => Arc::new(Float32Array::from(vec![*v])),
+        AvroValue::Double(v) => Arc::new(Float64Array::from(vec![*v])),
+        AvroValue::String(v) => Arc::new(StringArray::from(vec![v.as_str()])),
+        AvroValue::Bytes(v) if matches!(data_type, DataType::Decimal128(_, _)) => decimal(v)?,
+        AvroValue::Bytes(v) => Arc::new(BinaryArray::from_vec(vec![v.as_slice()])),
+        AvroValue::Decimal(v) => decimal(&Vec::<u8>::try_from(v).ok()?)?,
+        _ => return None,
+    };
+    if array.data_type() == data_type {
+        Some(array)
+    } else {
+        arrow_cast::cast(&array, data_type)
+            .ok()
+            .filter(|a| !a.is_null(0))
+    }
+}
+
+/// Get the columns to index from the schema of the table or a data file.
+///
+/// These are the meta fields of commit time, partition path, and record key, followed by up to
+/// 32 top-level data columns of the supported types.
+pub fn columns_to_index(schema: &Schema) -> Vec<&Field> {
+    let meta_fields = INDEXED_META_FIELDS
+        .iter()
+        .filter_map(|f| schema.field_with_name(f.as_ref()).ok());
+    let data_fields = schema
+        .fields()
+        .iter()
+        .filter(|f| MetaField::from_str(f.name()).is_err())
+        .filter(|f| ValueType::from_data_type(f.data_type()).is_some())
+        .take(MAX_DATA_COLUMNS_TO_INDEX)
+        .map(|f| f.as_ref());
+    meta_fields.chain(data_fields).collect()
+}
+
+fn hash_to_base64(value: &str) -> String {
+    BASE64.encode(XxHash64::oneshot(XXHASH64_SEED, value.as_bytes()).to_be_bytes())
+}
+
+/// Get the partition identifier used in the keys, with "." for non-partitioned tables.
+fn partition_identifier(partition_path: &str) -> &str {
+    if partition_path.is_empty() {
+        FilesPartitionRecord::NON_PARTITIONED_NAME
+    } else {
+        partition_path
+    }
+}
+
+/// Get the key of the `column_stats` record of a column of a data file.
+pub fn column_stats_key(partition_path: &str, file_name: &str, column_name: &str) -> String {
+    let file_id = BASE64.encode(Md5::digest(file_name.as_bytes()));
+    format!(
+        "{}{file_id}",
+        partition_stats_key(partition_path, column_name)
+    )
+}
+
+/// Get the key of the `partition_stats` record of a column of a partition.
+pub fn partition_stats_key(partition_path: &str, column_name: &str) -> String {
+    format!(
+        "{}{}",
+        hash_to_base64(column_name),
+        hash_to_base64(partition_identifier(partition_path))
+    )
+}
+
+/// Column statistics of a data file or a partition, stored as `HoodieMetadataColumnStats`.
+#[derive(Clone, Debug)]
+pub struct ColumnStatsRecord {
+    /// Partition path relative to the table base path, "" for non-partitioned tables.
+    pub partition_path: String,
+    /// Name of the data file, or `None` for a record of the `partition_stats` partition.
+    pub file_name: Option<String>,
+    /// Name, type, min/max values, and null count of the column.
+    pub stats: ColumnStatistics,
+    /// Number of values, including nulls.
+    pub value_count: i64,
+    /// Total compressed size of the column in bytes.
+    pub total_size: i64,
+    /// Total uncompressed size of the column in bytes.
+    pub total_uncompressed_size: i64,
+    /// Whether the stats are deleted along with the file or the partition.
+    pub is_deleted: bool,
+    /// Whether the min/max values are exact, as opposed to being loosened by later writes.
+    pub is_tight_bound: bool,
+}
+
+impl ColumnStatsRecord {
+    /// Create a record of each column to index from the footer of a Parquet data file.
+    pub fn from_parquet_metadata(
+        partition_path: &str,
+        file_name: &str,
+        metadata: &ParquetMetaData,
+    ) -> Result<Vec<Self>> {
+        let file_metadata = metadata.file_metadata();
+        let schema = parquet_to_arrow_schema(
+            file_metadata.schema_descr(),
+            file_metadata.key_value_metadata(),
+        )?;
+        let mut container = StatisticsContainer::from_parquet_metadata(metadata, &schema);
+
+        let mut sizes: HashMap<&str, (i64, i64)> = HashMap::new();
+        for column in metadata.row_groups().iter().flat_map(|rg| rg.columns()) {
+            if let [name] = column.column_descr().path().parts() {
+                let size = sizes.entry(name.as_str()).or_default();
+                size.0 += column.compressed_size();
+                size.1 += column.uncompressed_size();
+            }
+        }
+
+        let records = columns_to_index(&schema)
+            .into_iter()
+            .map(|field| {
+                let (total_size, total_uncompressed_size) = sizes
+                    .get(field.name().as_str())
+                    .copied()
+                    .unwrap_or_default();
+                let stats = container.columns.remove(field.name()).unwrap_or_else(|| {
+                    ColumnStatistics::new(field.name().clone(), field.data_type().clone())
+                });
+                Self {
+                    partition_path: partition_path.to_string(),
+                    file_name: Some(file_name.to_string()),
+                    stats,
+                    value_count: file_metadata.num_rows(),
+                    total_size,
+                    total_uncompressed_size,
+                    is_deleted: false,
+                    is_tight_bound: false,
+                }
+            })
+            .collect();
+        Ok(records)
+    }
+
+    /// Create a record that deletes the stats of a column of a data file, or of a partition if
+    /// `file_name` is `None`.
+    pub fn new_deleted(partition_path: &str, file_name: Option<&str>, column: &Field) -> Self {
+        Self {
+            partition_path: partition_path.to_string(),
+            file_name: file_name.map(str::to_string),
+            stats: ColumnStatistics::new(column.name().clone(), column.data_type().clone()),
+            value_count: 0,
+            total_size: 0,
+            total_uncompressed_size: 0,
+            is_deleted: true,
+            is_tight_bound: false,
+        }
+    }
+
+    /// Create the `partition_stats` record of the same column and partition from this record.
+    pub fn to_partition_stats(&self) -> Self {
+        Self {
+            file_name: None,
+            ..self.clone()
+        }
+    }
+
+    /// Merge the stats of the same column of another data file.
+    pub fn merge(&mut self, other: &ColumnStatsRecord) {
+        self.stats.merge(&other.stats);
+        self.value_count += other.value_count;
+        self.total_size += other.total_size;
+        self.total_uncompressed_size += other.total_uncompressed_size;
+        self.is_tight_bound &= other.is_tight_bound;
+    }
+
+    /// Get the metadata table partition of this record.
+    pub fn metadata_partition(&self) -> MetadataPartitionType {
+        if self.file_name.is_some() {
+            MetadataPartitionType::ColumnStats
+        } else {
+            MetadataPartitionType::PartitionStats
+        }
+    }
+
+    /// Get the record type of this record.
+    pub fn record_type(&self) -> MetadataRecordType {
+        if self.file_name.is_some() {
+            MetadataRecordType::ColumnStats
+        } else {
+            MetadataRecordType::PartitionStats
+        }
+    }
+
+    /// Get the record key of this record.
+    pub fn key(&self) -> String {
+        match &self.file_name {
+            Some(file_name) => {
+                column_stats_key(&self.partition_path, file_name, &self.stats.column_name)
+            }
+            None => partition_stats_key(&self.partition_path, &self.stats.column_name),
+        }
+    }
+}
+
+/// Get the record schema of a nullable union field of a record schema.
+fn union_field<'a>(record: &'a RecordSchema, name: &str) -> Result<&'a UnionSchema> {
+    match record.lookup.get(name).map(|&i| &record.fields[i].schema) {
+        Some(AvroSchema::Union(union)) => Ok(union),
+        _ => Err(CoreError::MetadataTable(format!(
+            "Field {name} is not a union in the metadata record schema"
+        ))),
+    }
+}
+
+/// Get the index of the union variant of the named record.
+fn union_variant_index(union: &UnionSchema, name: &str) -> Result<usize> {
+    union
+        .variants()
+        .iter()
+        .position(|s| match s {
+            AvroSchema::Record(r) => r.name.name == name,
+            AvroSchema::Ref { name: n } => n.name == name,
+            _ => false,
+        })
+        .ok_or_else(|| {
+            CoreError::MetadataTable(format!("Union variant {name} not found in the schema"))
+        })
+}
+
+fn nullable(value: Option<AvroValue>) -> AvroValue {
+    match value {
+        Some(v) => AvroValue::Union(1, Box::new(v)),
+        None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+    }
+}
+
+/// Encode a column stats record as an HFile key and an Avro-serialized value.
+///
+/// The value is built with explicit union indexes, as the wrapper records of the min/max
+/// values cannot be told apart by schema resolution.
+pub fn encode_column_stats_record(
+    record: &ColumnStatsRecord,
+    schema: &AvroSchema,
+) -> Result<(String, Vec<u8>)> {
+    let AvroSchema::Record(metadata_schema) = schema else {
+        return Err(CoreError::MetadataTable(
+            "Metadata record schema is not a record".to_string(),
+        ));
+    };
+    let stats_union = union_field(metadata_schema, "ColumnStatsMetadata")?;
+    let stats_index = union_variant_index(stats_union, "HoodieMetadataColumnStats")?;
+    let AvroSchema::Record(stats_schema) = &stats_union.variants()[stats_index] else {
+        return Err(CoreError::MetadataTable(
+            "HoodieMetadataColumnStats is not a record".to_string(),
+        ));
+    };
+
+    let stats = &record.stats;
+    let value_type = ValueType::from_data_type(&stats.data_type).filter(|_| !record.is_deleted);
+    let min_max = |field: &str, value: &Option<ArrayRef>| -> Result<AvroValue> {
+        let wrapped = value
+            .as_ref()
+            .zip(value_type)
+            .and_then(|(v, t)| Some((t.to_wrapped_value(v)?, t)));
+        match wrapped {
+            Some((v, t)) => {
+                let index =
+                    union_variant_index(union_field(stats_schema, field)?, t.wrapper_name())?;
+                let wrapper = AvroValue::Record(vec![("value".to_string(), v)]);
+                Ok(AvroValue::Union(index as u32, Box::new(wrapper)))
+            }
+            None => Ok(nullable(None)),
+        }
+    };
+    let min_value = min_max("minValue", &stats.min_value)?;
+    let max_value = min_max("maxValue", &stats.max_value)?;
+    let has_min_max = !matches!(&min_value, AvroValue::Union(0, _));
+    let type_info = match value_type {
+        Some(t) if has_min_max => {
+            let additional_info = match stats.data_type {
+                DataType::Decimal128(precision, scale) => Some(format!("{precision},{scale}")),
+                _ => None,
+            };
+            (t as i32, additional_info)
+        }
+        _ => (ValueType::Null as i32, None),
+    };
+
+    let file_name = record
+        .file_name
+        .clone()
+        .unwrap_or_else(|| partition_identifier(&record.partition_path).to_string());
+    let mut values: HashMap<&str, AvroValue> = HashMap::from([
+        ("fileName", nullable(Some(AvroValue::String(file_name)))),
+        (
+            "columnName",
+            nullable(Some(AvroValue::String(stats.column_name.clone()))),
+        ),
+        ("minValue", min_value),
+        ("maxValue", max_value),
+        (
+            "valueCount",
+            nullable(Some(AvroValue::Long(record.value_count))),
+        ),
+        ("nullCount", nullable(stats.null_count.map(AvroValue::Long))),
+        (
+            "totalSize",
+            nullable(Some(AvroValue::Long(record.total_size))),
+        ),
+        (
+            "totalUncompressedSize",
+            nullable(Some(AvroValue::Long(record.total_uncompressed_size))),
+        ),
+        ("isDeleted", AvroValue::Boolean(record.is_deleted)),
+        ("isTightBound", AvroValue::Boolean(record.is_tight_bound)),
+        (
+            "valueType",
+            nullable(Some(AvroValue::Record(vec![
+                ("typeOrdinal".to_string(), AvroValue::Int(type_info.0)),
+                (
+                    "additionalInfo".to_string(),
+                    nullable(type_info.1.map(AvroValue::String)),
+                ),
+            ]))),
+        ),
+    ]);
+    let stats_value = AvroValue::Record(
+        stats_schema
+            .fields
+            .iter()
+            .map(|f| {
+                let value = values.remove(f.name.as_str()).unwrap_or(nullable(None));
+                (f.name.clone(), value)
+            })
+            .collect(),
+    );
+
+    let key = record.key();
+    let value = AvroValue::Record(
+        metadata_schema
+            .fields
+            .iter()
+            .map(|f| {
+                let value = match f.name.as_str() {
+                    "key" => AvroValue::String(key.clone()),
+                    "type" => AvroValue::Int(record.record_type() as i32),
+                    "ColumnStatsMetadata" => {
+                        AvroValue::Union(stats_index as u32, Box::new(stats_value.clone()))
+                    }
+                    _ => nullable(None),
+                };
+                (f.name.clone(), value)
+            })
+            .collect(),
+    );
+    let bytes = to_avro_datum(schema, value)
+        .map_err(|e| CoreError::MetadataTable(format!("Avro encode error: {e}")))?;
+    Ok((key, bytes))
+}
+
+/// Get a field of a record, unwrapping unions and skipping nulls.
+fn record_field<'a>(value: &'a AvroValue, name: &str) -> Option<&'a AvroValue> {
+    let unwrap_union = |v: &'a AvroValue| match v {
+        AvroValue::Union(_, inner) => inner.as_ref(),
+        v => v,
+    };
+    let AvroValue::Record(fields) = unwrap_union(value) else {
+        return None;
+    };
+    fields
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| unwrap_union(v))
+        .filter(|v| !matches!(v, AvroValue::Null))
+}
+
+/// Decode a column stats record from an Avro-deserialized metadata record value.
+///
+/// The partition path is not kept in the value, so it is given by the caller, who looks up
+/// the record by its key. The min/max values are converted to `data_type`, the type of the
+/// column in the table schema, and are left unknown if they cannot be converted.
+pub fn decode_column_stats_record(
+    value: &AvroValue,
+    partition_path: &str,
+    data_type: &DataType,
+) -> Result<ColumnStatsRecord> {
+    let stats = record_field(value, "ColumnStatsMetadata").ok_or_else(|| {
+        CoreError::MetadataTable("Missing ColumnStatsMetadata in the record".to_string())
+    })?;
+    let string = |name: &str| match record_field(stats, name) {
+        Some(AvroValue::String(s)) => Some(s.clone()),
+        _ => None,
+    };
+    let long = |name: &str| match record_field(stats, name) {
+        Some(AvroValue::Long(n)) => Some(*n),
+        Some(AvroValue::Int(n)) => Some(i64::from(*n)),
+        _ => None,
+    };
+    let boolean = |name: &str| matches!(record_field(stats, name), Some(AvroValue::Boolean(true)));
+
+    let column_name = string("columnName").ok_or_else(|| {
+        CoreError::MetadataTable("Missing columnName in the column stats record".to_string())
+    })?;
+    let value_type = record_field(stats, "valueType")
+        .and_then(|t| match record_field(t, "typeOrdinal") {
+            Some(AvroValue::Int(ordinal)) => ValueType::from_ordinal(*ordinal),
+            _ => None,
+        })
+        .filter(|t| *t != ValueType::Null);
+    let min_max = |name: &str| {
+        record_field(stats, name)
+            .and_then(|wrapper| record_field(wrapper, "value"))
+            .and_then(|v| wrapped_value_to_array(v, value_type, data_type))
+    };
+    let file_name = match get_record_type(value) {
+        MetadataRecordType::PartitionStats => None,
+        _ => string("fileName"),
+    };
+
+    Ok(ColumnStatsRecord {
+        partition_path: partition_path.to_string(),
+        file_name,
+        stats: ColumnStatistics {
+            column_name,
+            data_type: data_type.clone(),
+            min_value: min_max("minValue"),
+            max_value: min_max("maxValue"),
+            null_count: long("nullCount"),
+        },
+        value_count: long("valueCount").unwrap_or_default(),
+        total_size: long("totalSize").unwrap_or_default(),
+        total_uncompressed_size: long("totalUncompressedSize").unwrap_or_default(),
+        is_deleted: boolean("isDeleted"),
+        is_tight_bound: boolean("isTightBound"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::table::records::{avro_schema_for_metadata_record, decode_avro_value};
+    use arrow_array::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+
+    fn get_field<'a>(value: &'a AvroValue, name: &str) -> &'a AvroValue {
+        let AvroValue::Record(fields) = value else {
+            panic!("Not a record: {value:?}");
+        };
+        let value = &fields.iter().find(|(n, _)| n == name).unwrap().1;
+        match value {
+            AvroValue::Union(_, v) => v,
+            v => v,
+        }
+    }
+
+    fn write_parquet() -> ParquetMetaData {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(MetaField::CommitTime.as_ref(), DataType::Utf8, true),
+            Field::new(MetaField::CommitSeqno.as_ref(), DataType::Utf8, true),
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("date", DataType::Date32, true),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["1", "1", "1"])),
+                Arc::new(StringArray::from(vec!["1_0", "1_1", "1_2"])),
+                Arc::new(Int32Array::from(vec![3, 1, 2])),
+                Arc::new(StringArray::from(vec![Some("b"), None, Some("a")])),
+                Arc::new(Date32Array::from(vec![None::<i32>, None, None])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(-129), Some(0), Some(2500)])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                Arc::new(TimestampMicrosecondArray::from(vec![10, 30, 20]).with_timezone("UTC")),
+            ],
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap()
+    }
+
+    #[test]
+    fn test_record_keys_match_hudi_java() {
+        assert_eq!(
+            partition_stats_key("region=eu", "currency"),
+            "/m6lxu4Dh5w=bOOVnCDQlm8="
+        );
+        assert_eq!(
+            partition_stats_key("region=apac", "merchant_name"),
+            "0FD2vn2+Kvk=OOL0+QGXAHU="
+        );
+        assert_eq!(
+            column_stats_key(
+                "region=us",
+                "3e93c8a9-fda0-4e8a-91dc-b4ade7dab76d-0_1-27-53_20260307162557587.parquet",
+                "currency"
+            ),
+            "/m6lxu4Dh5w=431THTTwO5Q=F01d3NbdD9QcFpSCtykrRA=="
+        );
+        assert_eq!(
+            partition_stats_key("", "id"),
+            format!("{}{}", hash_to_base64("id"), hash_to_base64("."))
+        );
+    }
+
+    #[test]
+    fn test_decimal_to_unscaled_bytes() {
+        let bytes =
+            |v: i64| decimal_to_unscaled_bytes(&(Arc::new(Int64Array::from(vec![v])) as ArrayRef));
+        assert_eq!(bytes(0), Some(vec![0]));
+        assert_eq!(bytes(2500), Some(vec![0x09, 0xC4]));
+        assert_eq!(bytes(128), Some(vec![0x00, 0x80]));
+        assert_eq!(bytes(-129), Some(vec![0xFF, 0x7F]));
+        assert_eq!(bytes(-1), Some(vec![0xFF]));
+        let fixed = BinaryArray::from_vec(vec![&[0x00, 0x00, 0x09, 0xC4]]);
+        assert_eq!(
+            decimal_to_unscaled_bytes(&(Arc::new(fixed) as ArrayRef)),
+            Some(vec![0x09, 0xC4])
+        );
+    }
+
+    #[test]
+    fn test_records_from_parquet_metadata() {
+        let metadata = write_parquet();
+        let records =
+            ColumnStatsRecord::from_parquet_metadata("p1", "f1.parquet", &metadata).unwrap();
+        let names: Vec<&str> = records
+            .iter()
+            .map(|r| r.stats.column_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["_hoodie_commit_time", "id", "name", "date", "amount", "ts"]
+        );
+        for record in &records {
+            assert_eq!(record.value_count, 3);
+            assert!(record.total_size > 0);
+            assert_eq!(
+                record.metadata_partition(),
+                MetadataPartitionType::ColumnStats
+            );
+        }
+        assert_eq!(records[2].stats.null_count, Some(1));
+        assert_eq!(records[3].stats.null_count, Some(3));
+    }
+
+    #[test]
+    fn test_encode_column_stats_record_round_trip() {
+        let schema = avro_schema_for_metadata_record().unwrap();
+        let metadata = write_parquet();
+        let records =
+            ColumnStatsRecord::from_parquet_metadata("p1", "f1.parquet", &metadata).unwrap();
+        let decode = |record: &ColumnStatsRecord| {
+            let (key, bytes) = encode_column_stats_record(record, schema).unwrap();
+            assert_eq!(key, record.key());
+            let value = decode_avro_value(&bytes, schema).unwrap();
+            assert_eq!(
+                get_field(&value, "type"),
+                &AvroValue::Int(record.record_type() as i32)
+            );
+            get_field(&value, "ColumnStatsMetadata").clone()
+        };
+        let min_value =
+            |stats: &AvroValue| get_field(get_field(stats, "minValue"), "value").clone();
+        let max_value =
+            |stats: &AvroValue| get_field(get_field(stats, "maxValue"), "value").clone();
+        let type_ordinal =
+            |stats: &AvroValue| get_field(get_field(stats, "valueType"), "typeOrdinal").clone();
+
+        let id = decode(&records[1]);
+        assert_eq!(
+            get_field(&id, "fileName"),
+            &AvroValue::String("f1.parquet".to_string())
+        );
+        assert_eq!(
+            get_field(&id, "columnName"),
+            &AvroValue::String("id".to_string())
+        );
+        assert_eq!(min_value(&id), AvroValue::Int(1));
+        assert_eq!(max_value(&id), AvroValue::Int(3));
+        assert_eq!(get_field(&id, "valueCount"), &AvroValue::Long(3));
+        assert_eq!(get_field(&id, "nullCount"), &AvroValue::Long(0));
+        assert_eq!(type_ordinal(&id), AvroValue::Int(ValueType::Int as i32));
+
+        let name = decode(&records[2]);
+        assert_eq!(min_value(&name), AvroValue::String("a".to_string()));
+        assert_eq!(max_value(&name), AvroValue::String("b".to_string()));
+        assert_eq!(get_field(&name, "nullCount"), &AvroValue::Long(1));
+
+        let date = decode(&records[3]);
+        assert_eq!(get_field(&date, "minValue"), &AvroValue::Null);
+        assert_eq!(type_ordinal(&date), AvroValue::Int(ValueType::Null as i32));
+
+        let amount = decode(&records[4]);
+        assert_eq!(min_value(&amount), AvroValue::Bytes(vec![0xFF, 0x7F]));
+        assert_eq!(max_value(&amount), AvroValue::Bytes(vec![0x09, 0xC4]));
+        assert_eq!(
+            get_field(get_field(&amount, "valueType"), "additionalInfo"),
+            &AvroValue::String("10,2".to_string())
+        );
+
+        let ts = decode(&records[5]);
+        assert_eq!(min_value(&ts), AvroValue::Long(10));
+        assert_eq!(max_value(&ts), AvroValue::Long(30));
+        assert_eq!(
+            type_ordinal(&ts),
+            AvroValue::Int(ValueType::TimestampMicros as i32)
+        );
+
+        let mut partition_stats = records[1].to_partition_stats();
+        partition_stats.merge(&records[1]);
+        let id = decode(&partition_stats);
+        assert_eq!(
+            get_field(&id, "fileName"),
+            &AvroValue::String("p1".to_string())
+        );
+        assert_eq!(get_field(&id, "valueCount"), &AvroValue::Long(6));
+
+        let field = Field::new("id", DataType::Int32, false);
+        let deleted = decode(&ColumnStatsRecord::new_deleted(
+            "p1",
+            Some("f1.parquet"),
+            &field,
+        ));
+        assert_eq!(get_field(&deleted, "isDeleted"), &AvroValue::Boolean(true));
+        assert_eq!(get_field(&deleted, "minValue"), &AvroValue::Null);
+    }
+
+    #[test]
+    fn test_decode_column_stats_record_round_trip() {
+        let schema = avro_schema_for_metadata_record().unwrap();
+        let metadata = write_parquet();
+        let records =
+            ColumnStatsRecord::from_parquet_metadata("p1", "f1.parquet", &metadata).unwrap();
+        for record in records.iter().chain([&records[1].to_partition_stats()]) {
+            let (_, bytes) = encode_column_stats_record(record, schema).unwrap();
+            let value = decode_avro_value(&bytes, schema).unwrap();
+            let decoded =
+                decode_column_stats_record(&value, "p1", &record.stats.data_type).unwrap();
+            assert_eq!(decoded.file_name, record.file_name);
+            assert_eq!(decoded.stats.null_count, record.stats.null_count);
+            let (_, decoded_bytes) = encode_column_stats_record(&decoded, schema).unwrap();
+            assert_eq!(decoded_bytes, bytes, "{}", record.stats.column_name);
+        }
+
+        let value = decode_avro_value(
+            &encode_column_stats_record(&records[4], schema).unwrap().1,
+            schema,
+        )
+        .unwrap();
+        let amount =
+            decode_column_stats_record(&value, "p1", &DataType::Decimal128(10, 2)).unwrap();
+        let min = amount.stats.min_value.unwrap();
+        assert_eq!(min.as_primitive::<Decimal128Type>().value(0), -129);
+
+        let field = Field::new("id", DataType::Int32, false);
+        let deleted = ColumnStatsRecord::new_deleted("p1", Some("f1.parquet"), &field);
+        let (_, bytes) = encode_column_stats_record(&deleted, schema).unwrap();
+        let value = decode_avro_value(&bytes, schema).unwrap();
+        let decoded = decode_column_stats_record(&value, "p1", &DataType::Int32).unwrap();
+        assert!(decoded.is_deleted);
+        assert_eq!(decoded.stats.min_value, None);
+    }
+
+    #[test]
+    fn test_wrapped_value_to_array() {
+        let ts_type = DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+        let ts = wrapped_value_to_array(
+            &AvroValue::Long(1_500),
+            Some(ValueType::TimestampMillis),
+            &ts_type,
+        )
+        .unwrap();
+        assert_eq!(
+            ts.as_primitive::<arrow_array::types::TimestampMicrosecondType>()
+                .value(0),
+            1_500_000
+        );
+        assert_eq!(ts.data_type(), &ts_type);
+
+        let amount = wrapped_value_to_array(
+            &AvroValue::Bytes(vec![0xFF, 0x7F]),
+            Some(ValueType::Decimal),
+            &DataType::Decimal128(10, 2),
+        )
+        .unwrap();
+        assert_eq!(amount.as_primitive::<Decimal128Type>().value(0), -129);
+
+        let small = wrapped_value_to_array(&AvroValue::Int(7), None, &DataType::Int16).unwrap();
+        assert_eq!(small.data_type(), &DataType::Int16);
+        assert!(
+            wrapped_value_to_array(&AvroValue::String("x".into()), None, &DataType::Int32)
+                .is_none()
+        );
+    }
+}
@@ -21,27 +21,33 @@
 //!
 //! This module provides methods for interacting with Hudi's metadata table,
 //! which stores file listings and other metadata for efficient table operations.
+//! See [writer] for keeping the metadata table in sync on writes.
 
+pub mod column_stats;
 pub mod records;
+pub mod writer;
 
 use std::collections::HashMap;
 
-use arrow_schema::Schema;
+use apache_avro::types::Value as AvroValue;
+use arrow_schema::{Field, Schema};
 
 use crate::Result;
 use crate::config::table::HudiTableConfig::{
     MetadataTableEnabled, MetadataTablePartitions, PartitionFields, TableVersion,
 };
 use crate::error::CoreError;
-use crate::expr::filter::from_str_tuples;
+use crate::expr::filter::{Filter, FilterExpr};
+use crate::file_group::file_slice::FileSlice;
 use crate::metadata::METADATA_TABLE_PARTITION_FIELD;
+use crate::statistics::{ColumnStatistics, StatisticsContainer, StatsGranularity};
 use crate::storage::util::join_url_segments;
 use crate::table::ReadOptions;
 use crate::table::Table;
 use crate::table::file_pruner::FilePruner;
 use crate::table::partition::PartitionPruner;
 
-use records::FilesPartitionRecord;
+use records::{FilesPartitionRecord, MetadataPartitionType};
 
 impl Table {
     /// Check if this table is a metadata table.
@@ -64,6 +70,16 @@ impl Table {
             .into()
     }
 
+    /// Check if the metadata table is enabled with the `column_stats` partition.
+    pub fn has_metadata_table_column_stats(&self) -> bool {
+        let partition_name = MetadataPartitionType::ColumnStats.partition_name();
+        self.is_metadata_table_enabled()
+            && self
+                .get_metadata_table_partitions()
+                .iter()
+                .any(|p| p == partition_name)
+    }
+
     /// Check if the metadata table is enabled.
     ///
     /// Returns `true` if:
@@ -109,7 +125,7 @@ impl Table {
 
     /// Create a metadata table instance for this data table.
     ///
-    /// TODO: support more partitions. Only "files" is used currently.
+    /// TODO: support more partitions. Only "files" and "column_stats" are used currently.
     ///
     /// # Errors
     ///
@@ -132,6 +148,11 @@ impl Table {
             ));
         }
 
+        self.open_metadata_table().await
+    }
+
+    /// Open the metadata table at `.hoodie/metadata` without checking the configured partitions.
+    pub(crate) async fn open_metadata_table(&self) -> Result<Table> {
         let mdt_url = join_url_segments(&self.base_url(), &[".hoodie", "metadata"])?;
         Table::new_with_options(
             mdt_url.as_str(),
@@ -221,13 +242,45 @@ impl Table {
             return Ok(HashMap::new());
         };
 
+        let file_slices = self
+            .get_partition_file_slices(FilesPartitionRecord::PARTITION_NAME, timestamp)
+            .await?;
+        if file_slices.len() != 1 {
+            return Err(CoreError::MetadataTable(format!(
+                "Expected 1 file slice for {} partition, got {}",
+                FilesPartitionRecord::PARTITION_NAME,
+                file_slices.len()
+            )));
+        }
+
+        let file_slice = file_slices.into_iter().next().unwrap();
+        let opts = ReadOptions::new().with_end_timestamp(timestamp);
+        let fg_reader = self.create_file_group_reader_with_options(
+            Some(&opts),
+            std::iter::empty::<(&str, &str)>(),
+        )?;
+
+        fg_reader
+            .read_metadata_table_files_partition(&file_slice, keys)
+            .await
+    }
+
+    /// Get the latest file slices of a partition of the metadata table as of `timestamp`.
+    ///
+    /// # Note
+    /// Must be called on a METADATA table instance.
+    async fn get_partition_file_slices(
+        &self,
+        partition_name: &str,
+        timestamp: &str,
+    ) -> Result<Vec<FileSlice>> {
         let timeline_view = self.timeline.create_view_as_of(timestamp).await?;
 
-        let filters = from_str_tuples([(
+        let filters = [FilterExpr::from(Filter::try_from((
             METADATA_TABLE_PARTITION_FIELD,
             "=",
-            FilesPartitionRecord::PARTITION_NAME,
-        )])?;
+            partition_name,
+        ))?)];
         let partition_schema = self.get_partition_schema().await?;
         let partition_pruner =
             PartitionPruner::new(&filters, &partition_schema, self.hudi_configs.as_ref())?;
@@ -238,8 +291,7 @@ impl Table {
         let table_schema = Schema::empty();
 
         // MDT itself uses HFile base files; no estimator applies here.
-        let file_slices = self
-            .file_system_view
+        self.file_system_view
             .get_file_slices_by_storage_listing(
                 &partition_pruner,
                 &file_pruner,
@@ -247,26 +299,110 @@ impl Table {
                 &timeline_view,
                 None,
             )
-            .await?;
+            .await
+    }
 
-        if file_slices.len() != 1 {
-            return Err(CoreError::MetadataTable(format!(
-                "Expected 1 file slice for {} partition, got {}",
-                FilesPartitionRecord::PARTITION_NAME,
-                file_slices.len()
-            )));
+    /// Read the file-level column stats of data files from the `column_stats` partition of
+    /// metadata table.
+    ///
+    /// # Arguments
+    /// * `files` - The partition paths and file names of the data files.
+    /// * `schema` - The data table schema, giving the types of the stats of indexed columns.
+    ///
+    /// # Returns
+    /// HashMap of file names to their stats. Files without stats are absent, and so are all
+    /// files if the `column_stats` partition is not available. Columns not indexed are left
+    /// unknown in the stats.
+    ///
+    /// # Note
+    /// Must be called on a DATA table, not a METADATA table.
+    pub async fn read_metadata_table_column_stats(
+        &self,
+        files: &[(&str, &str)],
+        schema: &Schema,
+    ) -> Result<HashMap<String, StatisticsContainer>> {
+        if files.is_empty() || !self.has_metadata_table_column_stats() {
+            return Ok(HashMap::new());
         }
 
-        let file_slice = file_slices.into_iter().next().unwrap();
+        let columns = column_stats::columns_to_index(schema);
+        let mut lookup: HashMap<String, (&str, &str, &Field)> = HashMap::new();
+        for (partition_path, file_name) in files {
+            for field in &columns {
+                let key = column_stats::column_stats_key(partition_path, file_name, field.name());
+                lookup.insert(key, (partition_path, file_name, field));
+            }
+        }
+        let keys: Vec<&str> = lookup.keys().map(String::as_str).collect();
+
+        let metadata_table = self.get_or_init_metadata_table().await?;
+        let records = metadata_table.fetch_column_stats_records(&keys).await?;
+
+        let mut stats: HashMap<String, StatisticsContainer> = HashMap::new();
+        for (key, value) in records {
+            let Some((partition_path, file_name, field)) = lookup.get(&key) else {
+                continue;
+            };
+            let record = column_stats::decode_column_stats_record(
+                &value,
+                partition_path,
+                field.data_type(),
+            )?;
+            if record.is_deleted {
+                continue;
+            }
+            let container = stats.entry(file_name.to_string()).or_insert_with(|| {
+                let mut container = StatisticsContainer::new(StatsGranularity::File);
+                container.columns = schema
+                    .fields()
+                    .iter()
+                    .map(|f| {
+                        let column = ColumnStatistics::new(f.name().clone(), f.data_type().clone());
+                        (f.name().clone(), column)
+                    })
+                    .collect();
+                container
+            });
+            container.num_rows = Some(record.value_count);
+            container.columns.insert(field.name().clone(), record.stats);
+        }
+        Ok(stats)
+    }
+
+    /// Fetch records from the `column_stats` partition by their keys.
+    ///
+    /// Keys are looked up in all file groups of the partition, as they are not routed to
+    /// file groups here.
+    ///
+    /// # Note
+    /// Must be called on a METADATA table instance.
+    pub async fn fetch_column_stats_records(
+        &self,
+        keys: &[&str],
+    ) -> Result<HashMap<String, AvroValue>> {
+        let Some(timestamp) = self.timeline.get_latest_commit_timestamp_as_option() else {
+            return Ok(HashMap::new());
+        };
+
+        let file_slices = self
+            .get_partition_file_slices(
+                MetadataPartitionType::ColumnStats.partition_name(),
+                timestamp,
+            )
+            .await?;
         let opts = ReadOptions::new().with_end_timestamp(timestamp);
         let fg_reader = self.create_file_group_reader_with_options(
             Some(&opts),
             std::iter::empty::<(&str, &str)>(),
         )?;
 
-        fg_reader
-            .read_metadata_table_files_partition(&file_slice, keys)
-            .await
+        let results = futures::future::try_join_all(
+            file_slices
+                .iter()
+                .map(|file_slice| fg_reader.read_metadata_table_column_stats(file_slice, keys)),
+        )
+        .await?;
+        Ok(results.into_iter().flatten().collect())
     }
 }
 
@@ -16,10 +16,10 @@
  * specific language governing permissions and limitations
  * under the License.
  */
-//! Metadata table record types for decoding Avro-serialized values.
+//! Metadata table record types for encoding and decoding Avro-serialized values.
 //!
 //! The Hudi metadata table stores records as Avro-serialized `HoodieMetadataRecord`.
-//! This module provides types and functions to decode these records, particularly
+//! This module provides types and functions to encode and decode these records, particularly
 //! for the "files" partition which contains file listings for each partition.
 //!
 //! # Files Partition Structure
@@ -35,10 +35,33 @@
 use crate::Result;
 use crate::error::CoreError;
 use crate::hfile::{HFileReader, HFileRecord};
+use crate::schema::prepend_meta_fields_to_avro_schema_str;
 use apache_avro::Schema as AvroSchema;
+use apache_avro::to_avro_datum;
 use apache_avro::types::Value as AvroValue;
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
+static METADATA_RECORD_AVRO_SCHEMA_STR: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/schemas/HoodieMetadata.avsc"
+));
+
+/// The schema of `HoodieMetadataRecord` with the meta fields prepended, as written by Hudi.
+static METADATA_RECORD_WRITER_SCHEMA: Lazy<Result<AvroSchema>> = Lazy::new(|| {
+    let schema_str = prepend_meta_fields_to_avro_schema_str(METADATA_RECORD_AVRO_SCHEMA_STR)?;
+    parse_avro_schema(&schema_str)
+});
+
+/// Get the Avro schema for writing metadata table records.
+///
+/// The meta fields are prepended to `HoodieMetadataRecord` and always left null.
+pub fn avro_schema_for_metadata_record() -> Result<&'static AvroSchema> {
+    METADATA_RECORD_WRITER_SCHEMA
+        .as_ref()
+        .map_err(|e| CoreError::MetadataTable(e.to_string()))
+}
+
 /// Metadata table partition types.
 ///
 /// These represent the different partitions (directories) within the metadata table,
@@ -65,6 +88,16 @@ impl MetadataPartitionType {
             Self::RecordIndex => "record_index",
         }
     }
+
+    /// Get the prefix of the file ids of the file groups in this partition.
+    pub fn file_id_prefix(&self) -> &'static str {
+        match self {
+            Self::Files => "files-",
+            Self::ColumnStats => "col-stats-",
+            Self::PartitionStats => "partition-stats-",
+            Self::RecordIndex => "record-index-",
+        }
+    }
 }
 
 impl std::fmt::Display for MetadataPartitionType {
@@ -164,6 +197,46 @@ impl FilesPartitionRecord {
     /// The metadata table stores "." for non-partitioned tables, which maps to "" externally.
     pub const NON_PARTITIONED_NAME: &'static str = ".";
 
+    /// Create a [MetadataRecordType::Files] record for a data table partition with the
+    /// added files and their sizes, and the deleted files.
+    ///
+    /// Use "" (empty string) as the partition path for non-partitioned tables.
+    pub fn new_files<A, D>(partition_path: &str, added: A, deleted: D) -> Self
+    where
+        A: IntoIterator<Item = (String, i64)>,
+        D: IntoIterator<Item = String>,
+    {
+        let mut files: HashMap<String, HoodieMetadataFileInfo> = added
+            .into_iter()
+            .map(|(name, size)| (name.clone(), HoodieMetadataFileInfo::new(name, size, false)))
+            .collect();
+        for name in deleted {
+            files.insert(name.clone(), HoodieMetadataFileInfo::new(name, 0, true));
+        }
+        Self {
+            key: partition_path.to_string(),
+            record_type: MetadataRecordType::Files,
+            files,
+        }
+    }
+
+    /// Create the [MetadataRecordType::AllPartitions] record with the added and deleted partitions.
+    ///
+    /// Use "" (empty string) as the partition path for non-partitioned tables.
+    pub fn new_all_partitions<A, D>(added: A, deleted: D) -> Self
+    where
+        A: IntoIterator<Item = String>,
+        D: IntoIterator<Item = String>,
+    {
+        let mut record = Self::new_files(
+            Self::ALL_PARTITIONS_KEY,
+            added.into_iter().map(|p| (p, 0)),
+            deleted,
+        );
+        record.record_type = MetadataRecordType::AllPartitions;
+        record
+    }
+
     /// Check if this is an ALL_PARTITIONS record.
     pub fn is_all_partitions(&self) -> bool {
         self.record_type == MetadataRecordType::AllPartitions
@@ -301,6 +374,55 @@ pub fn decode_files_partition_record_with_schema(
     })
 }
 
+/// Encode a files partition record as an HFile key and an Avro-serialized value.
+///
+/// This is the reverse of [`decode_files_partition_record_with_schema`]: the "" (empty string)
+/// partition of non-partitioned tables is stored as "." in the key and in the AllPartitions
+/// files map.
+pub fn encode_files_partition_record(
+    record: &FilesPartitionRecord,
+    schema: &AvroSchema,
+) -> Result<(String, Vec<u8>)> {
+    let to_stored_name = |name: &str| {
+        if name.is_empty() {
+            FilesPartitionRecord::NON_PARTITIONED_NAME.to_string()
+        } else {
+            name.to_string()
+        }
+    };
+    let key = to_stored_name(&record.key);
+
+    let filesystem_metadata: HashMap<String, AvroValue> = record
+        .files
+        .values()
+        .map(|info| {
+            let name = if record.is_all_partitions() {
+                to_stored_name(&info.name)
+            } else {
+                info.name.clone()
+            };
+            let value = AvroValue::Record(vec![
+                ("size".to_string(), AvroValue::Long(info.size)),
+                ("isDeleted".to_string(), AvroValue::Boolean(info.is_deleted)),
+            ]);
+            (name, value)
+        })
+        .collect();
+
+    let mut avro_record = apache_avro::types::Record::new(schema).ok_or_else(|| {
+        CoreError::MetadataTable("Metadata record schema is not a record".to_string())
+    })?;
+    avro_record.put("key", key.as_str());
+    avro_record.put("type", record.record_type as i32);
+    avro_record.put("filesystemMetadata", AvroValue::Map(filesystem_metadata));
+    let value = AvroValue::from(avro_record)
+        .resolve(schema)
+        .map_err(|e| CoreError::MetadataTable(format!("Avro resolve error: {e}")))?;
+    let bytes = to_avro_datum(schema, value)
+        .map_err(|e| CoreError::MetadataTable(format!("Avro encode error: {e}")))?;
+    Ok((key, bytes))
+}
+
 /// Extract filesystemMetadata from an Avro-decoded HoodieMetadataRecord.
 ///
 /// The filesystemMetadata field is a map where:
@@ -731,6 +853,45 @@ mod tests {
         assert!(partition_names.is_empty());
     }
 
+    #[test]
+    fn test_encode_files_partition_record_round_trip() {
+        let schema = avro_schema_for_metadata_record().unwrap();
+
+        let files = FilesPartitionRecord::new_files(
+            "",
+            [("f1.parquet".to_string(), 100)],
+            ["f0.parquet".to_string()],
+        );
+        let (key, value) = encode_files_partition_record(&files, schema).unwrap();
+        assert_eq!(key, FilesPartitionRecord::NON_PARTITIONED_NAME);
+        let decoded = decode_files_partition_record_with_schema(
+            &HFileRecord::new(key.into_bytes(), value),
+            schema,
+        )
+        .unwrap();
+        assert_eq!(decoded.key, "");
+        assert_eq!(decoded.record_type, MetadataRecordType::Files);
+        assert_eq!(decoded.active_file_names(), vec!["f1.parquet"]);
+        assert_eq!(decoded.total_size(), 100);
+        assert!(decoded.files["f0.parquet"].is_deleted);
+
+        let all_partitions =
+            FilesPartitionRecord::new_all_partitions(["".to_string()], std::iter::empty());
+        let (key, value) = encode_files_partition_record(&all_partitions, schema).unwrap();
+        assert_eq!(key, FilesPartitionRecord::ALL_PARTITIONS_KEY);
+        let avro_value = decode_avro_value(&value, schema).unwrap();
+        assert!(
+            extract_filesystem_metadata(&avro_value)
+                .contains_key(FilesPartitionRecord::NON_PARTITIONED_NAME)
+        );
+        let decoded = decode_files_partition_record_with_schema(
+            &HFileRecord::new(key.into_bytes(), value),
+            schema,
+        )
+        .unwrap();
+        assert_eq!(decoded.partition_names(), vec![""]);
+    }
+
     #[test]
     fn test_parse_avro_schema() {
         // Valid schema
@@ -0,0 +1,1370 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Writing the metadata table.
+//!
+//! - [Table::bootstrap_metadata_table] writes the base HFiles of the `files` partition from a
+//!   full listing of the data table, and of the `column_stats` and `partition_stats` partitions
+//!   from the footers of the Parquet base files, if enabled.
+//! - [Table::update_metadata_table] appends a log file with an HFile data block to each
+//!   affected file group for a data table commit, as a `deltacommit` of the same instant time on
+//!   the metadata table.
+//!
+//! Records of the `column_stats` and `partition_stats` partitions are distributed among the file
+//! groups by the hash of their keys, same as Hudi Java.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+
+use apache_avro::Schema as AvroSchema;
+use arrow_schema::Schema;
+use bytes::Bytes;
+
+use crate::Result;
+use crate::config::table::HudiTableConfig::{
+    ArchiveLogFolder, BaseFileFormat, Checksum, ColumnStatsIndexEnabled, DropsPartitionFields,
+    KeyGeneratorType, MetadataTablePartitions, PartitionStatsIndexEnabled, PopulatesMetaFields,
+    RecordKeyFields, TableName, TableType, TableVersion, TimelineHistoryPath,
+    TimelineLayoutVersion, TimelinePath,
+};
+use crate::config::table::{BaseFileFormatValue, TableTypeValue};
+use crate::config::util::{format_data_for_options, table_checksum, update_data_for_options};
+use crate::error::CoreError;
+use crate::file_group::base_file::BaseFile;
+use crate::file_group::base_file::parquet::ParquetBaseFileReader;
+use crate::file_group::log_file::writer::LogFileWriter;
+use crate::hfile::{CompressionCodec, HFileReader, HFileWriter};
+use crate::metadata::clean::HoodieCleanMetadata;
+use crate::metadata::commit::{HoodieCommitMetadata, HoodieWriteStat};
+use crate::metadata::rollback::HoodieRollbackMetadata;
+use crate::metadata::table::column_stats::{
+    ColumnStatsRecord, columns_to_index, encode_column_stats_record,
+};
+use crate::metadata::table::records::{
+    FilesPartitionRecord, MetadataPartitionType, avro_schema_for_metadata_record,
+    encode_files_partition_record,
+};
+use crate::storage::Storage;
+use crate::table::Table;
+use crate::table::partition::PARTITION_METAFIELD_PREFIX;
+use crate::timeline::instant::Action;
+
+/// The file id of the only file group in the `files` partition.
+pub const FILES_PARTITION_FILE_ID: &str = "files-0000-0";
+
+/// Number of file groups of the `column_stats` partition when bootstrapped, same as the
+/// default of `hoodie.metadata.index.column.stats.file.group.count` in Hudi Java.
+const COLUMN_STATS_FILE_GROUP_COUNT: usize = 2;
+
+/// Number of file groups of the `partition_stats` partition when bootstrapped, same as the
+/// default of `hoodie.metadata.index.partition.stats.file.group.count` in Hudi Java.
+const PARTITION_STATS_FILE_GROUP_COUNT: usize = 1;
+
+/// The instant time to initialize the metadata table of a table without any commit.
+const SOLO_COMMIT_TIMESTAMP: &str = "00000000000000000";
+
+/// The write token of the files written to the metadata table.
+const WRITE_TOKEN: &str = "0-0-0";
+
+/// The partitions of the metadata table that are kept in sync on writes.
+const WRITABLE_PARTITIONS: [MetadataPartitionType; 3] = [
+    MetadataPartitionType::Files,
+    MetadataPartitionType::ColumnStats,
+    MetadataPartitionType::PartitionStats,
+];
+
+/// File info keys of the min and max record keys of metadata table HFiles.
+const FILE_INFO_MIN_RECORD_KEY: &str = "minRecordKey";
+const FILE_INFO_MAX_RECORD_KEY: &str = "maxRecordKey";
+
+/// Encoded records to write, by metadata table partition and file id.
+type FileGroupRecords = BTreeMap<(&'static str, String), Vec<(String, Vec<u8>)>>;
+
+/// Changes to the files of the data table to be synced to the `files` partition.
+///
+/// Partition paths are relative to the table base path, with "" (empty string)
+/// for non-partitioned tables.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FilesPartitionChanges {
+    /// Partition path to the names and sizes of the added files.
+    pub added_files: BTreeMap<String, BTreeMap<String, i64>>,
+    /// Partition path to the names of the deleted files.
+    pub deleted_files: BTreeMap<String, BTreeSet<String>>,
+    /// Paths of the deleted partitions.
+    pub deleted_partitions: BTreeSet<String>,
+}
+
+impl FilesPartitionChanges {
+    /// Collect the files written by a commit from its write stats.
+    pub fn from_commit_metadata(metadata: &HoodieCommitMetadata) -> Self {
+        let mut changes = Self::default();
+        for (partition_path, stat) in metadata.iter_write_stats() {
+            let Some(path) = stat.path.as_deref().filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+            changes
+                .added_files
+                .entry(partition_path.to_string())
+                .or_default()
+                .insert(file_name.to_string(), stat.file_size_in_bytes.unwrap_or(0));
+        }
+        changes
+    }
+
+    /// Collect the files and partitions deleted by a clean.
+    pub fn from_clean_metadata(metadata: &HoodieCleanMetadata) -> Self {
+        let mut changes = Self::default();
+        for (partition_path, partition_metadata) in &metadata.partition_metadata {
+            if !partition_metadata.success_delete_files.is_empty() {
+                changes.deleted_files.insert(
+                    partition_path.to_string(),
+                    partition_metadata
+                        .success_delete_files
+                        .iter()
+                        .cloned()
+                        .collect(),
+                );
+            }
+            if partition_metadata.is_partition_deleted == Some(true) {
+                changes
+                    .deleted_partitions
+                    .insert(partition_path.to_string());
+            }
+        }
+        changes
+    }
+
+    /// Collect the files deleted by a rollback, and the log files with rollback command blocks
+    /// appended by it.
+    pub fn from_rollback_metadata(metadata: &HoodieRollbackMetadata) -> Self {
+        let mut changes = Self::default();
+        for (partition_path, partition_metadata) in &metadata.partition_metadata {
+            if !partition_metadata.success_delete_files.is_empty() {
+                changes.deleted_files.insert(
+                    partition_path.to_string(),
+                    partition_metadata
+                        .success_delete_files
+                        .iter()
+                        .cloned()
+                        .collect(),
+                );
+            }
+            if let Some(log_files) = partition_metadata
+                .rollback_log_files
+                .as_ref()
+                .filter(|f| !f.is_empty())
+            {
+                changes.added_files.insert(
+                    partition_path.to_string(),
+                    log_files.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+                );
+            }
+        }
+        changes
+    }
+
+    /// Merge the changes made after these changes, e.g., by the next rollback of a restore.
+    ///
+    /// A file added by one of the changes and deleted by a later one is only kept as deleted.
+    pub fn merge(&mut self, other: Self) {
+        for (partition_path, deleted) in other.deleted_files {
+            if let Some(added) = self.added_files.get_mut(&partition_path) {
+                added.retain(|name, _| !deleted.contains(name));
+                if added.is_empty() {
+                    self.added_files.remove(&partition_path);
+                }
+            }
+            self.deleted_files
+                .entry(partition_path)
+                .or_default()
+                .extend(deleted);
+        }
+        for (partition_path, added) in other.added_files {
+            if let Some(deleted) = self.deleted_files.get_mut(&partition_path) {
+                deleted.retain(|name| !added.contains_key(name));
+                if deleted.is_empty() {
+                    self.deleted_files.remove(&partition_path);
+                }
+            }
+            self.added_files
+                .entry(partition_path)
+                .or_default()
+                .extend(added);
+        }
+        self.deleted_partitions.extend(other.deleted_partitions);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_files.is_empty()
+            && self.deleted_files.is_empty()
+            && self.deleted_partitions.is_empty()
+    }
+
+    /// Convert the changes to a [FilesPartitionRecord] for each affected partition, plus the
+    /// `__all_partitions__` record if any partition is added or deleted.
+    pub fn to_records(&self) -> Vec<FilesPartitionRecord> {
+        let partitions: BTreeSet<&String> = self
+            .added_files
+            .keys()
+            .chain(self.deleted_files.keys())
+            .collect();
+        let mut records: Vec<FilesPartitionRecord> = partitions
+            .into_iter()
+            .map(|partition_path| {
+                let added = self
+                    .added_files
+                    .get(partition_path)
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, size)| (name.clone(), *size));
+                let deleted = self
+                    .deleted_files
+                    .get(partition_path)
+                    .into_iter()
+                    .flatten()
+                    .cloned();
+                FilesPartitionRecord::new_files(partition_path, added, deleted)
+            })
+            .collect();
+        if !self.added_files.is_empty() || !self.deleted_partitions.is_empty() {
+            records.push(FilesPartitionRecord::new_all_partitions(
+                self.added_files.keys().cloned(),
+                self.deleted_partitions.iter().cloned(),
+            ));
+        }
+        records
+    }
+}
+
+/// Changes of a data table commit to be synced to the metadata table.
+#[derive(Clone, Debug, Default)]
+pub struct MetadataTableChanges {
+    /// Changes to the files, for the `files` partition.
+    pub files: FilesPartitionChanges,
+    /// Column stats of the added data files, for the `column_stats` and `partition_stats`
+    /// partitions.
+    ///
+    /// See [ColumnStatsRecord::from_parquet_metadata].
+    pub column_stats: Vec<ColumnStatsRecord>,
+}
+
+impl MetadataTableChanges {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.column_stats.is_empty()
+    }
+}
+
+impl From<FilesPartitionChanges> for MetadataTableChanges {
+    fn from(files: FilesPartitionChanges) -> Self {
+        Self {
+            files,
+            column_stats: Vec::new(),
+        }
+    }
+}
+
+impl Table {
+    /// Initialize the metadata table from a full listing of the committed files of this table,
+    /// and enable it in `hoodie.properties`.
+    ///
+    /// The `files` partition is always initialized. The `column_stats` and `partition_stats`
+    /// partitions are initialized from the footers of the Parquet base files if
+    /// [ColumnStatsIndexEnabled] and [PartitionStatsIndexEnabled] are set.
+    ///
+    /// The [Table] instance is not refreshed after bootstrapping; create a new instance to
+    /// read through the metadata table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is a metadata table, the table version is below 8, or the
+    /// metadata table already exists. Initializing the `partition_stats` partition is not
+    /// supported if the latest file slices have log files.
+    pub async fn bootstrap_metadata_table(&self) -> Result<()> {
+        if self.is_metadata_table() {
+            return Err(CoreError::MetadataTable(
+                "Cannot bootstrap a metadata table for another metadata table".to_string(),
+            ));
+        }
+        let table_version: isize = self.hudi_configs.get(TableVersion)?.into();
+        if table_version < 8 {
+            return Err(CoreError::Unsupported(format!(
+                "Metadata table is not supported for table version {table_version}."
+            )));
+        }
+        let storage = &self.file_system_view.storage;
+        let mdt_exists = storage
+            .list_files(Some(".hoodie/metadata/.hoodie"))
+            .await?
+            .iter()
+            .any(|f| f.name == "hoodie.properties");
+        if mdt_exists || !self.get_metadata_table_partitions().is_empty() {
+            return Err(CoreError::MetadataTable(
+                "Metadata table already exists".to_string(),
+            ));
+        }
+
+        let column_stats_enabled: bool = self
+            .hudi_configs
+            .get_or_default(ColumnStatsIndexEnabled)
+            .into();
+        let partition_stats_enabled = column_stats_enabled
+            && self
+                .hudi_configs
+                .get_or_default(PartitionStatsIndexEnabled)
+                .into()
+            && !self.get_partition_schema().await?.fields().is_empty();
+
+        let instant_time = match self.timeline.completed_commits.last() {
+            Some(latest) => self
+                .timeline
+                .new_instant_timestamp(Some(&latest.timestamp))?,
+            None => SOLO_COMMIT_TIMESTAMP.to_string(),
+        };
+
+        let parquet_reader = ParquetBaseFileReader::new(storage.clone());
+        let mut changes = FilesPartitionChanges::default();
+        let mut column_stats = Vec::new();
+        let mut latest_column_stats = Vec::new();
+        for (partition_path, file_groups) in self.list_all_file_groups().await? {
+            let files = changes
+                .added_files
+                .entry(partition_path.clone())
+                .or_default();
+            for file_group in &file_groups {
+                let slices = self.committed_file_slices_latest_first(file_group);
+                for (i, slice) in slices.iter().enumerate() {
+                    let base_file = &slice.base_file;
+                    let base_file_name = base_file.file_name();
+                    files.insert(
+                        base_file_name.clone(),
+                        base_file
+                            .file_metadata
+                            .as_ref()
+                            .map_or(0, |m| m.size as i64),
+                    );
+                    let completed_log_files: Vec<_> = slice
+                        .log_files
+                        .iter()
+                        .filter(|l| l.is_completed())
+                        .collect();
+                    for log_file in &completed_log_files {
+                        files.insert(
+                            log_file.file_name(),
+                            log_file.file_metadata.as_ref().map_or(0, |m| m.size as i64),
+                        );
+                    }
+
+                    if !column_stats_enabled {
+                        continue;
+                    }
+                    let is_parquet =
+                        BaseFileFormatValue::Parquet.matches_extension(&base_file_name);
+                    let is_latest = i == 0;
+                    if partition_stats_enabled
+                        && is_latest
+                        && (!is_parquet || !completed_log_files.is_empty())
+                    {
+                        return Err(CoreError::Unsupported(format!(
+                            "Initializing partition stats from file slice {} of {} is not supported yet.",
+                            slice.file_id(),
+                            partition_path
+                        )));
+                    }
+                    if !is_parquet {
+                        continue;
+                    }
+                    let metadata = parquet_reader
+                        .get_parquet_metadata(&slice.base_file_relative_path()?)
+                        .await?;
+                    let records = ColumnStatsRecord::from_parquet_metadata(
+                        &partition_path,
+                        &base_file_name,
+                        &metadata,
+                    )?;
+                    if partition_stats_enabled && is_latest {
+                        latest_column_stats.extend(records.iter().cloned());
+                    }
+                    column_stats.extend(records);
+                }
+            }
+        }
+
+        let schema = avro_schema_for_metadata_record()?;
+        let mut file_group_records = FileGroupRecords::new();
+        file_group_records.insert(
+            (
+                FilesPartitionRecord::PARTITION_NAME,
+                FILES_PARTITION_FILE_ID.to_string(),
+            ),
+            changes
+                .to_records()
+                .iter()
+                .map(|r| encode_files_partition_record(r, schema))
+                .collect::<Result<Vec<_>>>()?,
+        );
+        if column_stats_enabled {
+            init_file_groups(
+                &mut file_group_records,
+                MetadataPartitionType::ColumnStats,
+                COLUMN_STATS_FILE_GROUP_COUNT,
+            );
+            add_column_stats_records(
+                &mut file_group_records,
+                &column_stats,
+                MetadataPartitionType::ColumnStats,
+                COLUMN_STATS_FILE_GROUP_COUNT,
+                schema,
+            )?;
+        }
+        if partition_stats_enabled {
+            init_file_groups(
+                &mut file_group_records,
+                MetadataPartitionType::PartitionStats,
+                PARTITION_STATS_FILE_GROUP_COUNT,
+            );
+            add_column_stats_records(
+                &mut file_group_records,
+                &aggregate_partition_stats(&latest_column_stats, true),
+                MetadataPartitionType::PartitionStats,
+                PARTITION_STATS_FILE_GROUP_COUNT,
+                schema,
+            )?;
+        }
+
+        let mdt_properties = self.metadata_table_properties(table_version);
+        storage
+            .put_file_data(
+                ".hoodie/metadata/.hoodie/hoodie.properties",
+                format_data_for_options(mdt_properties),
+            )
+            .await?;
+        let mdt = self.open_metadata_table().await?;
+        let mdt_storage = mdt.file_system_view.storage.clone();
+
+        let requested = mdt
+            .timeline
+            .create_requested_instant(Action::DeltaCommit, &instant_time, Bytes::new())
+            .await?;
+        let inflight = mdt
+            .timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let mdt_partitions: BTreeSet<&str> = file_group_records.keys().map(|(p, _)| *p).collect();
+        for partition in &mdt_partitions {
+            let partition_metadata =
+                format!("#partition metadata\ncommitTime={instant_time}\npartitionDepth=1\n");
+            mdt_storage
+                .put_file_data(
+                    &format!("{partition}/{PARTITION_METAFIELD_PREFIX}"),
+                    Bytes::from(partition_metadata),
+                )
+                .await?;
+        }
+
+        let mut write_stats = Vec::with_capacity(file_group_records.len());
+        for ((partition, file_id), records) in file_group_records {
+            write_stats.push(
+                write_base_file(
+                    &mdt_storage,
+                    partition,
+                    &file_id,
+                    &instant_time,
+                    schema,
+                    records,
+                )
+                .await?,
+            );
+        }
+        let metadata = metadata_table_commit_metadata("BULK_INSERT_PREPPED", write_stats);
+        mdt.timeline
+            .transition_inflight_to_completed(&inflight, Bytes::from(metadata.to_avro_bytes()?))
+            .await?;
+
+        let properties_path = ".hoodie/hoodie.properties";
+        let properties = storage.get_file_data(properties_path).await?;
+        let partitions = mdt_partitions.into_iter().collect::<Vec<_>>().join(",");
+        let properties = update_data_for_options(
+            &properties,
+            [(MetadataTablePartitions.as_ref(), partitions.as_str())],
+        )?;
+        storage.put_file_data(properties_path, properties).await?;
+        Ok(())
+    }
+
+    /// Sync the changes of a data table commit to the metadata table, as a `deltacommit` with
+    /// the same instant time.
+    ///
+    /// Column stats records of the deleted files and partitions are generated for the columns to
+    /// index of the table schema.
+    ///
+    /// Does nothing if the metadata table is not enabled or there is no change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata table has partitions that are not kept in sync on
+    /// writes, or if the `partition_stats` partition is enabled but some added files have no
+    /// column stats.
+    pub async fn update_metadata_table(
+        &self,
+        instant_time: &str,
+        changes: &MetadataTableChanges,
+    ) -> Result<()> {
+        if !self.is_metadata_table_enabled() || changes.is_empty() {
+            return Ok(());
+        }
+        self.ensure_metadata_table_writable()?;
+        let mdt_partitions = self.get_metadata_table_partitions();
+        let has_partition = |partition: MetadataPartitionType| {
+            mdt_partitions
+                .iter()
+                .any(|p| p == partition.partition_name())
+        };
+
+        let mdt = self.new_metadata_table().await?;
+        let schema = mdt.get_files_partition_avro_schema().await?;
+        let mut file_group_records = FileGroupRecords::new();
+        let files_records = changes
+            .files
+            .to_records()
+            .iter()
+            .map(|r| encode_files_partition_record(r, &schema))
+            .collect::<Result<Vec<_>>>()?;
+        if !files_records.is_empty() {
+            file_group_records.insert(
+                (
+                    FilesPartitionRecord::PARTITION_NAME,
+                    FILES_PARTITION_FILE_ID.to_string(),
+                ),
+                files_records,
+            );
+        }
+
+        let deletes_column_stats =
+            !changes.files.deleted_files.is_empty() || !changes.files.deleted_partitions.is_empty();
+        let table_schema = if deletes_column_stats {
+            Some(self.get_schema_to_index().await?)
+        } else {
+            None
+        };
+        let indexed_columns = table_schema
+            .as_ref()
+            .map(columns_to_index)
+            .unwrap_or_default();
+
+        if has_partition(MetadataPartitionType::ColumnStats) {
+            let mut records = changes.column_stats.clone();
+            for (partition_path, file_names) in &changes.files.deleted_files {
+                for file_name in file_names {
+                    records.extend(indexed_columns.iter().map(|column| {
+                        ColumnStatsRecord::new_deleted(partition_path, Some(file_name), column)
+                    }));
+                }
+            }
+            let partition = MetadataPartitionType::ColumnStats;
+            let num_file_groups = mdt.count_file_groups(partition).await?;
+            add_column_stats_records(
+                &mut file_group_records,
+                &records,
+                partition,
+                num_file_groups,
+                &schema,
+            )?;
+        }
+
+        if has_partition(MetadataPartitionType::PartitionStats) {
+            let files_with_stats: HashSet<(&str, &str)> = changes
+                .column_stats
+                .iter()
+                .filter_map(|r| Some((r.partition_path.as_str(), r.file_name.as_deref()?)))
+                .collect();
+            let files_without_stats: Vec<&str> = changes
+                .files
+                .added_files
+                .iter()
+                .flat_map(|(p, files)| files.keys().map(move |f| (p.as_str(), f.as_str())))
+                .filter(|file| !files_with_stats.contains(file))
+                .map(|(_, f)| f)
+                .collect();
+            if !files_without_stats.is_empty() {
+                return Err(CoreError::Unsupported(format!(
+                    "Updating partition stats without the column stats of files {files_without_stats:?} is not supported."
+                )));
+            }
+
+            let mut records = aggregate_partition_stats(&changes.column_stats, false);
+            for partition_path in &changes.files.deleted_partitions {
+                records.extend(
+                    indexed_columns
+                        .iter()
+                        .map(|column| ColumnStatsRecord::new_deleted(partition_path, None, column)),
+                );
+            }
+            let partition = MetadataPartitionType::PartitionStats;
+            let num_file_groups = mdt.count_file_groups(partition).await?;
+            add_column_stats_records(
+                &mut file_group_records,
+                &records,
+                partition,
+                num_file_groups,
+                &schema,
+            )?;
+        }
+
+        if file_group_records.is_empty() {
+            return Ok(());
+        }
+        let requested = mdt
+            .timeline
+            .create_requested_instant(Action::DeltaCommit, instant_time, Bytes::new())
+            .await?;
+        let inflight = mdt
+            .timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let mut write_stats = Vec::with_capacity(file_group_records.len());
+        for ((partition, file_id), records) in file_group_records {
+            let file_name = format!(".{file_id}_{instant_time}.log.1_{WRITE_TOKEN}");
+            let num_records = records.len();
+            let mut writer = LogFileWriter::new(
+                mdt.file_system_view.storage.clone(),
+                format!("{partition}/{file_name}"),
+            );
+            writer.append_hfile_data_block(instant_time, &schema, records)?;
+            let size = writer.finish().await? as i64;
+            write_stats.push(HoodieWriteStat {
+                log_files: Some(vec![file_name.clone()]),
+                log_version: Some(1),
+                num_update_writes: Some(num_records as i64),
+                ..metadata_table_write_stat(partition, &file_id, &file_name, size, num_records)
+            });
+        }
+        let metadata = metadata_table_commit_metadata("UPSERT_PREPPED", write_stats);
+        mdt.timeline
+            .transition_inflight_to_completed(&inflight, Bytes::from(metadata.to_avro_bytes()?))
+            .await?;
+        Ok(())
+    }
+
+    /// Check that all the configured metadata table partitions are kept in sync on writes.
+    pub(crate) fn ensure_metadata_table_writable(&self) -> Result<()> {
+        let unsupported: Vec<String> = self
+            .get_metadata_table_partitions()
+            .into_iter()
+            .filter(|p| !WRITABLE_PARTITIONS.iter().any(|w| w.partition_name() == p))
+            .collect();
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(CoreError::Unsupported(format!(
+                "Writing to metadata table partitions {unsupported:?} is not supported yet."
+            )))
+        }
+    }
+
+    /// Get the schema whose columns are indexed in the `column_stats` partition.
+    async fn get_schema_to_index(&self) -> Result<Schema> {
+        if self.hudi_configs.get_or_default(PopulatesMetaFields).into() {
+            self.get_schema_with_meta_fields().await
+        } else {
+            self.get_schema().await
+        }
+    }
+
+    /// Count the file groups of a partition of the metadata table.
+    ///
+    /// # Note
+    /// Must be called on a METADATA table instance.
+    async fn count_file_groups(&self, partition: MetadataPartitionType) -> Result<usize> {
+        let partition_name = partition.partition_name();
+        let file_ids: HashSet<String> = self
+            .file_system_view
+            .storage
+            .list_files(Some(partition_name))
+            .await?
+            .into_iter()
+            .filter_map(|f| {
+                let name = f.name.strip_prefix('.').unwrap_or(&f.name);
+                let (file_id, _) = name.split_once('_')?;
+                file_id
+                    .starts_with(partition.file_id_prefix())
+                    .then(|| file_id.to_string())
+            })
+            .collect();
+        if file_ids.is_empty() {
+            return Err(CoreError::MetadataTable(format!(
+                "No file group found in {partition_name} partition"
+            )));
+        }
+        Ok(file_ids.len())
+    }
+
+    /// Get the Avro schema of the records from the latest base file of the `files` partition.
+    ///
+    /// # Note
+    /// Must be called on a METADATA table instance.
+    async fn get_files_partition_avro_schema(&self) -> Result<AvroSchema> {
+        let storage = &self.file_system_view.storage;
+        let latest_base_file = storage
+            .list_files(Some(FilesPartitionRecord::PARTITION_NAME))
+            .await?
+            .into_iter()
+            .filter_map(|f| BaseFile::from_str(&f.name).ok())
+            .filter(|f| f.file_id == FILES_PARTITION_FILE_ID)
+            .max_by(|a, b| a.commit_timestamp.cmp(&b.commit_timestamp))
+            .ok_or_else(|| {
+                CoreError::MetadataTable(format!(
+                    "No base file found in {} partition",
+                    FilesPartitionRecord::PARTITION_NAME
+                ))
+            })?;
+        let path = format!(
+            "{}/{}",
+            FilesPartitionRecord::PARTITION_NAME,
+            latest_base_file.file_name()
+        );
+        let reader = HFileReader::open(storage, &path)
+            .await
+            .map_err(|e| CoreError::HFile(e.to_string()))?;
+        reader
+            .get_avro_schema()
+            .map_err(|e| CoreError::HFile(e.to_string()))?
+            .cloned()
+            .ok_or_else(|| CoreError::MetadataTable(format!("No Avro schema found in {path}")))
+    }
+
+    /// Properties of the metadata table of this table, as written by Hudi Java.
+    fn metadata_table_properties(&self, table_version: isize) -> Vec<(String, String)> {
+        let table_name = format!("{}_metadata", self.table_name());
+        let checksum = table_checksum("", &table_name);
+        [
+            (TableName.as_ref(), table_name),
+            (
+                TableType.as_ref(),
+                TableTypeValue::MergeOnRead.as_ref().to_string(),
+            ),
+            (TableVersion.as_ref(), table_version.to_string()),
+            ("hoodie.table.initial.version", table_version.to_string()),
+            (
+                KeyGeneratorType.as_ref(),
+                "HOODIE_TABLE_METADATA".to_string(),
+            ),
+            (RecordKeyFields.as_ref(), "key".to_string()),
+            (
+                BaseFileFormat.as_ref(),
+                BaseFileFormatValue::HFile.as_ref().to_uppercase(),
+            ),
+            (PopulatesMetaFields.as_ref(), "false".to_string()),
+            (DropsPartitionFields.as_ref(), "false".to_string()),
+            (
+                "hoodie.compaction.payload.class",
+                "org.apache.hudi.metadata.HoodieMetadataPayload".to_string(),
+            ),
+            ("hoodie.record.merge.mode", "CUSTOM".to_string()),
+            (
+                "hoodie.record.merge.strategy.id",
+                "00000000-0000-0000-0000-000000000000".to_string(),
+            ),
+            (TimelineLayoutVersion.as_ref(), "2".to_string()),
+            (TimelinePath.as_ref(), "timeline".to_string()),
+            (TimelineHistoryPath.as_ref(), "history".to_string()),
+            (ArchiveLogFolder.as_ref(), "history".to_string()),
+            (Checksum.as_ref(), checksum.to_string()),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+    }
+}
+
+/// Get the file id of the file group at the index in a metadata table partition.
+fn file_group_id(partition: MetadataPartitionType, index: usize) -> String {
+    format!("{}{index:04}-0", partition.file_id_prefix())
+}
+
+/// Map a record key to the index of a file group, same as
+/// `HoodieTableMetadataUtil.mapRecordKeyToFileGroupIndex` in Hudi Java.
+fn file_group_index(key: &str, num_file_groups: usize) -> usize {
+    // Java's String.hashCode()
+    let hash = key
+        .encode_utf16()
+        .fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32));
+    hash.unsigned_abs() as usize % num_file_groups
+}
+
+/// Add all the file groups of a partition being initialized, including those without records.
+fn init_file_groups(
+    file_group_records: &mut FileGroupRecords,
+    partition: MetadataPartitionType,
+    num_file_groups: usize,
+) {
+    for index in 0..num_file_groups {
+        file_group_records
+            .entry((partition.partition_name(), file_group_id(partition, index)))
+            .or_default();
+    }
+}
+
+/// Encode the column stats records and add them to their file groups.
+fn add_column_stats_records(
+    file_group_records: &mut FileGroupRecords,
+    records: &[ColumnStatsRecord],
+    partition: MetadataPartitionType,
+    num_file_groups: usize,
+    schema: &AvroSchema,
+) -> Result<()> {
+    for record in records {
+        let (key, value) = encode_column_stats_record(record, schema)?;
+        let file_id = file_group_id(partition, file_group_index(&key, num_file_groups));
+        file_group_records
+            .entry((partition.partition_name(), file_id))
+            .or_default()
+            .push((key, value));
+    }
+    Ok(())
+}
+
+/// Aggregate the column stats of data files to the stats of their partitions.
+fn aggregate_partition_stats(
+    records: &[ColumnStatsRecord],
+    is_tight_bound: bool,
+) -> Vec<ColumnStatsRecord> {
+    let mut aggregated: BTreeMap<(&str, &str), ColumnStatsRecord> = BTreeMap::new();
+    for record in records.iter().filter(|r| !r.is_deleted) {
+        aggregated
+            .entry((&record.partition_path, &record.stats.column_name))
+            .and_modify(|r| r.merge(record))
+            .or_insert_with(|| ColumnStatsRecord {
+                is_tight_bound,
+                ..record.to_partition_stats()
+            });
+    }
+    aggregated.into_values().collect()
+}
+
+/// Write the records to a new base HFile of a file group of the metadata table.
+async fn write_base_file(
+    storage: &Storage,
+    partition: &str,
+    file_id: &str,
+    instant_time: &str,
+    schema: &AvroSchema,
+    mut records: Vec<(String, Vec<u8>)>,
+) -> Result<HoodieWriteStat> {
+    records.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    let mut hfile_writer = HFileWriter::new().with_compression_codec(CompressionCodec::Gzip);
+    for (key, value) in &records {
+        hfile_writer
+            .append(key.as_bytes(), value)
+            .map_err(|e| CoreError::HFile(e.to_string()))?;
+    }
+    if let (Some(min), Some(max)) = (records.first(), records.last()) {
+        hfile_writer.append_file_info(FILE_INFO_MIN_RECORD_KEY, min.0.as_bytes());
+        hfile_writer.append_file_info(FILE_INFO_MAX_RECORD_KEY, max.0.as_bytes());
+    }
+    hfile_writer.append_avro_schema(schema);
+    let hfile_bytes = hfile_writer
+        .finish()
+        .map_err(|e| CoreError::HFile(e.to_string()))?;
+
+    let file_name = format!("{file_id}_{WRITE_TOKEN}_{instant_time}.hfile");
+    let size = hfile_bytes.len() as i64;
+    storage
+        .put_file_data(
+            &format!("{partition}/{file_name}"),
+            Bytes::from(hfile_bytes),
+        )
+        .await?;
+    Ok(HoodieWriteStat {
+        base_file: Some(file_name.clone()),
+        num_inserts: Some(records.len() as i64),
+        ..metadata_table_write_stat(partition, file_id, &file_name, size, records.len())
+    })
+}
+
+fn metadata_table_write_stat(
+    partition: &str,
+    file_id: &str,
+    file_name: &str,
+    size: i64,
+    num_records: usize,
+) -> HoodieWriteStat {
+    HoodieWriteStat {
+        file_id: Some(file_id.to_string()),
+        path: Some(format!("{partition}/{file_name}")),
+        partition_path: Some(partition.to_string()),
+        num_writes: Some(num_records as i64),
+        total_write_bytes: Some(size),
+        file_size_in_bytes: Some(size),
+        total_write_errors: Some(0),
+        ..Default::default()
+    }
+}
+
+fn metadata_table_commit_metadata(
+    operation_type: &str,
+    write_stats: Vec<HoodieWriteStat>,
+) -> HoodieCommitMetadata {
+    let mut partition_to_write_stats: HashMap<String, Vec<HoodieWriteStat>> = HashMap::new();
+    for stat in write_stats {
+        partition_to_write_stats
+            .entry(stat.partition_path.clone().unwrap_or_default())
+            .or_default()
+            .push(stat);
+    }
+    HoodieCommitMetadata {
+        version: Some(1),
+        operation_type: Some(operation_type.to_string()),
+        partition_to_write_stats: Some(partition_to_write_stats),
+        compacted: Some(false),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HudiConfigs;
+    use crate::config::clean::HudiCleanConfig;
+    use crate::file_group::log_file::reader::LogFileReader;
+    use crate::metadata::clean::HoodieCleanPartitionMetadata;
+    use crate::metadata::rollback::HoodieRollbackPartitionMetadata;
+    use crate::metadata::table::column_stats::{column_stats_key, partition_stats_key};
+    use crate::storage::util::parse_uri;
+    use crate::table::partition::PartitionPruner;
+    use crate::table::test_util::read_snapshot_sorted;
+    use crate::timeline::selector::InstantRange;
+    use hudi_test::SampleTable;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    async fn read_active_files(table: &Table) -> BTreeMap<String, BTreeSet<String>> {
+        let partition_schema = table.get_partition_schema().await.unwrap();
+        let pruner =
+            PartitionPruner::new(&[], &partition_schema, table.hudi_configs.as_ref()).unwrap();
+        table
+            .read_metadata_table_files_partition(&pruner)
+            .await
+            .unwrap()
+            .into_values()
+            .filter(|r| !r.is_all_partitions())
+            .map(|r| {
+                let files = r.active_file_names().into_iter().map(String::from);
+                (r.key.clone(), files.collect())
+            })
+            .collect()
+    }
+
+    fn listed_files(base_path: &str, partition_path: &str) -> BTreeSet<String> {
+        std::fs::read_dir(Path::new(base_path).join(partition_path))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .filter(|name| !name.starts_with(".hoodie"))
+            .collect()
+    }
+
+    fn mdt_partition_dir(base_path: &str, partition: MetadataPartitionType) -> PathBuf {
+        Path::new(base_path)
+            .join(".hoodie/metadata")
+            .join(partition.partition_name())
+    }
+
+    fn listed_mdt_files(base_path: &str, partition: MetadataPartitionType) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(mdt_partition_dir(base_path, partition))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .filter(|name| !name.starts_with(".hoodie"))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Reads the keys of all base HFiles in a metadata table partition, grouped by file id.
+    fn read_base_file_keys(
+        base_path: &str,
+        partition: MetadataPartitionType,
+    ) -> BTreeMap<String, BTreeSet<String>> {
+        let dir = mdt_partition_dir(base_path, partition);
+        let mut keys_by_file_id = BTreeMap::new();
+        for name in listed_mdt_files(base_path, partition) {
+            if !name.ends_with(".hfile") {
+                continue;
+            }
+            let file_id = name.split('_').next().unwrap().to_string();
+            let mut reader = HFileReader::new(std::fs::read(dir.join(&name)).unwrap()).unwrap();
+            let keys: BTreeSet<String> = reader
+                .collect_records()
+                .unwrap()
+                .iter()
+                .map(|r| r.key_as_str().unwrap().to_string())
+                .collect();
+            keys_by_file_id.insert(file_id, keys);
+        }
+        keys_by_file_id
+    }
+
+    #[test]
+    fn test_file_group_index_matches_hudi_java() {
+        assert_eq!(
+            file_group_index("/m6lxu4Dh5w=431THTTwO5Q=yjhiUJUDXiNZ1xraJO2Dzg==", 2),
+            0
+        );
+        assert_eq!(
+            file_group_index("/m6lxu4Dh5w=431THTTwO5Q=F01d3NbdD9QcFpSCtykrRA==", 2),
+            1
+        );
+        assert_eq!(file_group_index("any-key", 1), 0);
+        assert_eq!(
+            file_group_id(MetadataPartitionType::ColumnStats, 1),
+            "col-stats-0001-0"
+        );
+    }
+
+    #[test]
+    fn test_files_partition_changes_to_records() {
+        let mut changes = FilesPartitionChanges::default();
+        changes
+            .added_files
+            .insert("p1".to_string(), BTreeMap::from([("f1".to_string(), 10)]));
+        changes
+            .deleted_files
+            .insert("p2".to_string(), BTreeSet::from(["f2".to_string()]));
+
+        let records = changes.to_records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].key, "p1");
+        assert!(records[0].has_active_file("f1"));
+        assert_eq!(records[1].key, "p2");
+        assert!(records[1].files["f2"].is_deleted);
+        assert!(records[2].is_all_partitions());
+        assert_eq!(records[2].partition_names(), vec!["p1"]);
+    }
+
+    #[test]
+    fn test_files_partition_changes_from_clean_metadata() {
+        let partition_metadata = HoodieCleanPartitionMetadata {
+            partition_path: "p1".to_string(),
+            success_delete_files: vec!["f1".to_string()],
+            failed_delete_files: vec!["f2".to_string()],
+            ..Default::default()
+        };
+        let metadata = HoodieCleanMetadata {
+            partition_metadata: HashMap::from([("p1".to_string(), partition_metadata)]),
+            ..Default::default()
+        };
+        let changes = FilesPartitionChanges::from_clean_metadata(&metadata);
+        assert_eq!(
+            changes.deleted_files,
+            BTreeMap::from([("p1".to_string(), BTreeSet::from(["f1".to_string()]))])
+        );
+        assert!(changes.added_files.is_empty());
+        assert!(changes.deleted_partitions.is_empty());
+    }
+
+    #[test]
+    fn test_files_partition_changes_from_rollback_metadata() {
+        let partition_metadata = HoodieRollbackPartitionMetadata {
+            partition_path: "p1".to_string(),
+            success_delete_files: vec!["f1".to_string()],
+            failed_delete_files: vec!["f2".to_string()],
+            rollback_log_files: Some(HashMap::from([(".f3_001.log.2_0-0-0".to_string(), 96)])),
+            ..Default::default()
+        };
+        let metadata = HoodieRollbackMetadata {
+            partition_metadata: HashMap::from([("p1".to_string(), partition_metadata)]),
+            ..Default::default()
+        };
+        let changes = FilesPartitionChanges::from_rollback_metadata(&metadata);
+        assert_eq!(
+            changes.deleted_files,
+            BTreeMap::from([("p1".to_string(), BTreeSet::from(["f1".to_string()]))])
+        );
+        assert_eq!(
+            changes.added_files,
+            BTreeMap::from([(
+                "p1".to_string(),
+                BTreeMap::from([(".f3_001.log.2_0-0-0".to_string(), 96)])
+            )])
+        );
+        assert!(changes.deleted_partitions.is_empty());
+    }
+
+    #[test]
+    fn test_files_partition_changes_merge() {
+        let mut changes = FilesPartitionChanges {
+            added_files: BTreeMap::from([(
+                "p1".to_string(),
+                BTreeMap::from([(".f1_001.log.2_0-0-0".to_string(), 96)]),
+            )]),
+            deleted_files: BTreeMap::from([("p1".to_string(), BTreeSet::from(["f2".to_string()]))]),
+            ..Default::default()
+        };
+        changes.merge(FilesPartitionChanges {
+            deleted_files: BTreeMap::from([(
+                "p1".to_string(),
+                BTreeSet::from([".f1_001.log.2_0-0-0".to_string(), "f1".to_string()]),
+            )]),
+            deleted_partitions: BTreeSet::from(["p2".to_string()]),
+            ..Default::default()
+        });
+        assert!(changes.added_files.is_empty());
+        assert_eq!(
+            changes.deleted_files["p1"],
+            BTreeSet::from([
+                ".f1_001.log.2_0-0-0".to_string(),
+                "f1".to_string(),
+                "f2".to_string()
+            ])
+        );
+        assert_eq!(
+            changes.deleted_partitions,
+            BTreeSet::from(["p2".to_string()])
+        );
+
+        changes.merge(FilesPartitionChanges {
+            added_files: BTreeMap::from([(
+                "p1".to_string(),
+                BTreeMap::from([("f2".to_string(), 8)]),
+            )]),
+            ..Default::default()
+        });
+        assert_eq!(
+            changes.added_files,
+            BTreeMap::from([("p1".to_string(), BTreeMap::from([("f2".to_string(), 8)]))])
+        );
+        assert!(!changes.deleted_files["p1"].contains("f2"));
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_metadata_table_v9() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(!table.is_metadata_table_enabled());
+        let expected = read_snapshot_sorted(&table, "txn_id").await;
+
+        table.bootstrap_metadata_table().await.unwrap();
+        assert!(matches!(
+            table.bootstrap_metadata_table().await.unwrap_err(),
+            CoreError::MetadataTable(_)
+        ));
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(table.is_metadata_table_enabled());
+        let active_files = read_active_files(&table).await;
+        assert!(!active_files.is_empty());
+        for (partition_path, files) in &active_files {
+            assert_eq!(files, &listed_files(&base_path, partition_path));
+        }
+        assert_eq!(read_snapshot_sorted(&table, "txn_id").await, expected);
+
+        let mdt = table.new_metadata_table().await.unwrap();
+        assert_eq!(mdt.timeline.completed_commits.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_metadata_table_non_partitioned() {
+        let base_path = SampleTable::V9TxnsNonpartNometa.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        table.bootstrap_metadata_table().await.unwrap();
+
+        let table = Table::new(&base_path).await.unwrap();
+        let active_files = read_active_files(&table).await;
+        assert_eq!(
+            active_files,
+            BTreeMap::from([(String::new(), listed_files(&base_path, ""))])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_metadata_table_unsupported_for_v6() {
+        let base_path = SampleTable::V6Nonpartitioned.path_to_cow();
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(matches!(
+            table.bootstrap_metadata_table().await.unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_clean_updates_metadata_table_files() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        Table::new(&base_path)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+
+        let options = [
+            (
+                HudiCleanConfig::CleanPolicy.as_ref(),
+                "KEEP_LATEST_FILE_VERSIONS",
+            ),
+            (HudiCleanConfig::FileVersionsRetained.as_ref(), "1"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let expected = read_snapshot_sorted(&table, "txn_id").await;
+        let clean_metadata = table.clean().await.unwrap().unwrap();
+        assert!(clean_metadata.total_files_deleted > 0);
+
+        let table = Table::new(&base_path).await.unwrap();
+        let mdt = table.new_metadata_table().await.unwrap();
+        assert_eq!(mdt.timeline.completed_commits.len(), 2);
+        assert_eq!(
+            mdt.timeline.completed_commits[1].timestamp,
+            clean_metadata.start_clean_time
+        );
+        for (partition_path, files) in read_active_files(&table).await {
+            assert_eq!(files, listed_files(&base_path, &partition_path));
+        }
+        assert_eq!(read_snapshot_sorted(&table, "txn_id").await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_metadata_table_with_stats_indexes() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [
+            (ColumnStatsIndexEnabled.as_ref(), "true"),
+            (PartitionStatsIndexEnabled.as_ref(), "true"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let expected = read_snapshot_sorted(&table, "txn_id").await;
+        table.bootstrap_metadata_table().await.unwrap();
+
+        let table = Table::new(&base_path).await.unwrap();
+        let partitions = table.get_metadata_table_partitions();
+        assert_eq!(partitions, ["column_stats", "files", "partition_stats"]);
+
+        let columns: Vec<String> = columns_to_index(&table.get_schema_to_index().await.unwrap())
+            .iter()
+            .map(|f| f.name().to_string())
+            .collect();
+        assert!(columns.contains(&"_hoodie_commit_time".to_string()));
+        assert!(columns.contains(&"txn_id".to_string()));
+
+        let active_files = read_active_files(&table).await;
+        let mut expected_col_stats_keys = BTreeSet::new();
+        let mut expected_partition_stats_keys = BTreeSet::new();
+        for (partition_path, files) in &active_files {
+            for column in &columns {
+                for file_name in files {
+                    expected_col_stats_keys.insert(column_stats_key(
+                        partition_path,
+                        file_name,
+                        column,
+                    ));
+                }
+                expected_partition_stats_keys.insert(partition_stats_key(partition_path, column));
+            }
+        }
+
+        let col_stats_keys = read_base_file_keys(&base_path, MetadataPartitionType::ColumnStats);
+        assert_eq!(
+            col_stats_keys.keys().collect::<Vec<_>>(),
+            ["col-stats-0000-0", "col-stats-0001-0"]
+        );
+        for (idx, keys) in col_stats_keys.values().enumerate() {
+            assert!(keys.iter().all(|k| file_group_index(k, 2) == idx));
+        }
+        let all_col_stats_keys: BTreeSet<String> = col_stats_keys.into_values().flatten().collect();
+        assert_eq!(all_col_stats_keys, expected_col_stats_keys);
+
+        let partition_stats_keys =
+            read_base_file_keys(&base_path, MetadataPartitionType::PartitionStats);
+        assert_eq!(
+            partition_stats_keys,
+            BTreeMap::from([(
+                "partition-stats-0000-0".to_string(),
+                expected_partition_stats_keys
+            )])
+        );
+
+        assert_eq!(read_snapshot_sorted(&table, "txn_id").await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_clean_updates_metadata_table_column_stats() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [(ColumnStatsIndexEnabled.as_ref(), "true")];
+        Table::new_with_options(&base_path, options)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+
+        let options = [
+            (
+                HudiCleanConfig::CleanPolicy.as_ref(),
+                "KEEP_LATEST_FILE_VERSIONS",
+            ),
+            (HudiCleanConfig::FileVersionsRetained.as_ref(), "1"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let clean_metadata = table.clean().await.unwrap().unwrap();
+        let clean_time = clean_metadata.start_clean_time.clone();
+
+        let table = Table::new(&base_path).await.unwrap();
+        let columns: Vec<String> = columns_to_index(&table.get_schema_to_index().await.unwrap())
+            .iter()
+            .map(|f| f.name().to_string())
+            .collect();
+        let mut expected_deleted_keys = BTreeSet::new();
+        for (partition_path, partition_metadata) in &clean_metadata.partition_metadata {
+            for file_path in &partition_metadata.success_delete_files {
+                let file_name = file_path.rsplit('/').next().unwrap();
+                for column in &columns {
+                    expected_deleted_keys.insert(column_stats_key(
+                        partition_path,
+                        file_name,
+                        column,
+                    ));
+                }
+            }
+        }
+        assert!(!expected_deleted_keys.is_empty());
+
+        let partition_dir = mdt_partition_dir(&base_path, MetadataPartitionType::ColumnStats);
+        let storage =
+            Storage::new_with_base_url(parse_uri(partition_dir.to_str().unwrap()).unwrap())
+                .unwrap();
+        let mut deleted_keys = BTreeSet::new();
+        for name in listed_mdt_files(&base_path, MetadataPartitionType::ColumnStats) {
+            if !name.contains(&format!("_{clean_time}.log.")) {
+                continue;
+            }
+            let hudi_configs = Arc::new(HudiConfigs::empty());
+            let mut reader = LogFileReader::new(hudi_configs, storage.clone(), &name)
+                .await
+                .unwrap();
+            let blocks = reader
+                .read_all_blocks(&InstantRange::up_to(&clean_time, "utc"))
+                .unwrap();
+            for record in blocks[0].hfile_records().unwrap() {
+                assert_eq!(
+                    file_group_index(record.key_as_str().unwrap(), COLUMN_STATS_FILE_GROUP_COUNT),
+                    name[1..].starts_with("col-stats-0001") as usize
+                );
+                deleted_keys.insert(record.key_as_str().unwrap().to_string());
+            }
+        }
+        assert_eq!(deleted_keys, expected_deleted_keys);
+    }
+}
@@ -0,0 +1,251 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Conversion of Arrow schemas to Avro schemas, following the conventions of
+//! Spark's `SchemaConverters` used by Hudi Java for [HudiTableConfig::CreateSchema].
+//!
+//! [HudiTableConfig::CreateSchema]: crate::config::table::HudiTableConfig::CreateSchema
+
+use crate::error::{CoreError, Result};
+use apache_avro::Schema as AvroSchema;
+use arrow_schema::{DataType, Field, Fields, Schema, TimeUnit};
+use serde_json::{Value, json};
+
+/// Convert an Arrow schema to the JSON string of an Avro record schema.
+///
+/// Nested records are named after their fields, in the namespace of the enclosing record;
+/// nullable fields become unions of the type and `null`, in that order.
+pub fn arrow_schema_to_avro_schema_str(
+    schema: &Schema,
+    record_name: &str,
+    namespace: &str,
+) -> Result<String> {
+    let record = record_to_avro(schema.fields(), record_name, namespace)?;
+    let avro_schema_str = record.to_string();
+    AvroSchema::parse_str(&avro_schema_str)
+        .map_err(|e| CoreError::Schema(format!("Invalid Avro schema converted from Arrow: {e}")))?;
+    Ok(avro_schema_str)
+}
+
+/// The Avro record name and namespace Hudi Java uses for the schema of a table.
+pub fn avro_record_name_and_namespace(table_name: &str) -> (String, String) {
+    (
+        format!("{table_name}_record"),
+        format!("hoodie.{table_name}"),
+    )
+}
+
+fn record_to_avro(fields: &Fields, record_name: &str, namespace: &str) -> Result<Value> {
+    let child_namespace = full_name(namespace, record_name);
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let field_type = field_to_avro(field, field.name(), &child_namespace)?;
+            Ok(json!({"name": field.name(), "type": field_type}))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut record = json!({"type": "record", "name": record_name, "fields": fields});
+    if !namespace.is_empty() {
+        record["namespace"] = json!(namespace);
+    }
+    Ok(record)
+}
+
+fn field_to_avro(field: &Field, record_name: &str, namespace: &str) -> Result<Value> {
+    let avro_type = data_type_to_avro(field.data_type(), record_name, namespace)?;
+    if field.is_nullable() {
+        Ok(json!([avro_type, "null"]))
+    } else {
+        Ok(avro_type)
+    }
+}
+
+fn data_type_to_avro(data_type: &DataType, record_name: &str, namespace: &str) -> Result<Value> {
+    let avro_type = match data_type {
+        DataType::Boolean => json!("boolean"),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            json!("int")
+        }
+        DataType::Int64 | DataType::UInt32 => json!("long"),
+        DataType::Float16 | DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => json!("string"),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => json!("bytes"),
+        DataType::Decimal128(precision, scale) => json!({
+            "type": "fixed",
+            "name": "fixed",
+            "namespace": full_name(namespace, record_name),
+            "size": min_bytes_for_precision(*precision),
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale,
+        }),
+        DataType::Date32 => json!({"type": "int", "logicalType": "date"}),
+        DataType::Time32(TimeUnit::Millisecond) => {
+            json!({"type": "int", "logicalType": "time-millis"})
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            json!({"type": "long", "logicalType": "time-micros"})
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-millis"})
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-micros"})
+        }
+        DataType::List(item) | DataType::LargeList(item) => {
+            json!({"type": "array", "items": field_to_avro(item, record_name, namespace)?})
+        }
+        DataType::Struct(fields) => record_to_avro(fields, record_name, namespace)?,
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(kv)
+                if kv.len() == 2
+                    && matches!(
+                        kv[0].data_type(),
+                        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                    ) =>
+            {
+                json!({"type": "map", "values": field_to_avro(&kv[1], record_name, namespace)?})
+            }
+            _ => {
+                return Err(CoreError::Schema(format!(
+                    "Only maps with string keys can be converted to Avro: {data_type}"
+                )));
+            }
+        },
+        _ => {
+            return Err(CoreError::Schema(format!(
+                "Unsupported Arrow type for Avro conversion: {data_type}"
+            )));
+        }
+    };
+    Ok(avro_type)
+}
+
+fn full_name(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}.{name}")
+    }
+}
+
+/// Minimum number of bytes of a two's complement integer holding any unscaled value of a
+/// decimal with the given precision.
+fn min_bytes_for_precision(precision: u8) -> usize {
+    let max_value = 10f64.powi(precision as i32);
+    let mut num_bytes = 1;
+    while 2f64.powi(8 * num_bytes as i32 - 1) < max_value {
+        num_bytes += 1;
+    }
+    num_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::to_arrow_schema;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_min_bytes_for_precision() {
+        assert_eq!(min_bytes_for_precision(1), 1);
+        assert_eq!(min_bytes_for_precision(10), 5);
+        assert_eq!(min_bytes_for_precision(18), 8);
+        assert_eq!(min_bytes_for_precision(38), 16);
+    }
+
+    #[test]
+    fn test_convert_matches_hudi_java_create_schema() {
+        let struct_fields = Fields::from(vec![
+            Field::new("field1", DataType::Utf8, true),
+            Field::new("field2", DataType::Int32, true),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("decimalField", DataType::Decimal128(10, 5), true),
+            Field::new("dateField", DataType::Date32, true),
+            Field::new("structField", DataType::Struct(struct_fields), true),
+        ]);
+        let (name, namespace) = avro_record_name_and_namespace("tbl");
+        let avro_schema_str = arrow_schema_to_avro_schema_str(&schema, &name, &namespace).unwrap();
+
+        // From `hoodie.table.create.schema` written by Hudi Java
+        let expected = r#"{"type":"record","name":"tbl_record","namespace":"hoodie.tbl","fields":[{"name":"id","type":["int","null"]},{"name":"decimalField","type":[{"type":"fixed","name":"fixed","namespace":"hoodie.tbl.tbl_record.decimalField","size":5,"logicalType":"decimal","precision":10,"scale":5},"null"]},{"name":"dateField","type":[{"type":"int","logicalType":"date"},"null"]},{"name":"structField","type":[{"type":"record","name":"structField","namespace":"hoodie.tbl.tbl_record","fields":[{"name":"field1","type":["string","null"]},{"name":"field2","type":["int","null"]}]},"null"]}]}"#;
+        let actual: Value = serde_json::from_str(&avro_schema_str).unwrap();
+        assert_eq!(actual, serde_json::from_str::<Value>(expected).unwrap());
+    }
+
+    #[test]
+    fn test_convert_round_trip() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("amount", DataType::Decimal128(18, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+            Field::new(
+                "tags",
+                DataType::List(Arc::new(Field::new("element", DataType::Utf8, false))),
+                true,
+            ),
+        ]);
+        let avro_schema_str = arrow_schema_to_avro_schema_str(&schema, "r", "ns").unwrap();
+        let avro_schema = AvroSchema::parse_str(&avro_schema_str).unwrap();
+        let converted = to_arrow_schema(&avro_schema).unwrap();
+        let types: Vec<_> = converted
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.is_nullable()))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ("id", false),
+                ("name", true),
+                ("amount", true),
+                ("ts", false),
+                ("tags", true)
+            ]
+        );
+        assert_eq!(converted.field(2).data_type(), &DataType::Decimal128(18, 2));
+        assert_eq!(
+            converted.field(3).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+    }
+
+    #[test]
+    fn test_convert_unsupported_type() {
+        let schema = Schema::new(vec![Field::new(
+            "d",
+            DataType::Duration(TimeUnit::Second),
+            true,
+        )]);
+        assert!(matches!(
+            arrow_schema_to_avro_schema_str(&schema, "r", "ns").unwrap_err(),
+            CoreError::Schema(_)
+        ));
+    }
+}
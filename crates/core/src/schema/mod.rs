@@ -22,6 +22,7 @@ use crate::schema::resolver::sanitize_avro_schema_str;
 use arrow_schema::{Schema, SchemaRef};
 use serde_json::Value;
 
+pub mod arrow_to_avro;
 pub mod delete;
 pub mod resolver;
 
@@ -31,6 +32,14 @@ pub fn prepend_meta_fields(schema: SchemaRef) -> Result<Schema> {
         .map_err(CoreError::ArrowError)
 }
 
+/// Prepends the meta fields synthesized on read, see [MetaField::virtual_schema], for the
+/// tables that do not populate them.
+pub fn prepend_virtual_meta_fields(schema: SchemaRef) -> Result<Schema> {
+    let meta_field_schema = MetaField::virtual_schema();
+    Schema::try_merge([meta_field_schema.as_ref().clone(), schema.as_ref().clone()])
+        .map_err(CoreError::ArrowError)
+}
+
 // TODO use this when applicable, like some table config says there is an operation field
 pub fn prepend_meta_fields_with_operation(schema: SchemaRef) -> Result<Schema> {
     let meta_field_schema = MetaField::schema_with_operation();
@@ -97,6 +106,17 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_prepend_virtual_meta_fields() {
+        let schema = Schema::new(vec![Field::new("field1", DataType::Int32, false)]);
+        let new_schema = prepend_virtual_meta_fields(Arc::new(schema)).unwrap();
+        assert_arrow_field_names_eq!(
+            new_schema,
+            [MetaField::field_names(), vec!["field1"]].concat()
+        );
+        assert!(new_schema.field(0).is_nullable());
+    }
+
     #[test]
     fn test_prepend_meta_fields_with_operation() {
         let schema = Schema::new(vec![Field::new("field1", DataType::Int32, false)]);
@@ -23,7 +23,9 @@ use crate::error::{CoreError, Result};
 use crate::file_group::base_file::lance::LanceBaseFileReader;
 use crate::file_group::base_file::parquet::ParquetBaseFileReader;
 use crate::metadata::commit::HoodieCommitMetadata;
-use crate::schema::{prepend_meta_fields, prepend_meta_fields_to_avro_schema_str};
+use crate::schema::{
+    prepend_meta_fields, prepend_meta_fields_to_avro_schema_str, prepend_virtual_meta_fields,
+};
 use crate::storage::Storage;
 use crate::table::Table;
 use apache_avro::schema::Schema as AvroSchema;
@@ -47,9 +49,20 @@ pub async fn resolve_data_schema(table: &Table) -> Result<Schema> {
 }
 
 /// Resolves the [`arrow_schema::Schema`] for a given Hudi table, with Hudi meta fields prepended.
+///
+/// For tables that do not populate the meta fields, the meta fields are the ones synthesized
+/// on read, see [`crate::metadata::meta_field::MetaField::virtual_schema`].
 pub async fn resolve_schema(table: &Table) -> Result<Schema> {
     let data_schema = resolve_data_schema(table).await?;
-    prepend_meta_fields(SchemaRef::new(data_schema))
+    let populates_meta_fields: bool = table
+        .hudi_configs
+        .get_or_default(HudiTableConfig::PopulatesMetaFields)
+        .into();
+    if populates_meta_fields || table.is_metadata_table() {
+        prepend_meta_fields(SchemaRef::new(data_schema))
+    } else {
+        prepend_virtual_meta_fields(SchemaRef::new(data_schema))
+    }
 }
 
 /// Resolves the [`apache_avro::schema::Schema`] as a [`String`] for a given Hudi table.
@@ -22,7 +22,7 @@
 //! column statistics from Parquet files for query pruning at different granularity levels.
 //!
 //! Core types:
-//! - [`ColumnStatistics`]: Per-column statistics (min, max) for range-based pruning
+//! - [`ColumnStatistics`]: Per-column statistics (min, max, null count) for range-based pruning
 //! - [`StatisticsContainer`]: Container for all column statistics at a given granularity
 //!
 //! Min/max values are stored as single-element Arrow arrays (`ArrayRef`), enabling
@@ -96,7 +96,7 @@ impl std::fmt::Display for StatsGranularity {
 
 /// Statistics for a single column at a given granularity.
 ///
-/// Tracks min and max values from Parquet footer statistics for range-based pruning.
+/// Tracks min and max values and the null count from Parquet footer statistics.
 /// Values are stored as single-element Arrow arrays for direct comparison using `arrow_ord::cmp`.
 #[derive(Clone, Debug)]
 pub struct ColumnStatistics {
@@ -108,6 +108,8 @@ pub struct ColumnStatistics {
     pub min_value: Option<ArrayRef>,
     /// Maximum value (stored as a single-element Arrow array)
     pub max_value: Option<ArrayRef>,
+    /// Number of null values, `None` if unknown for any of the aggregated row groups
+    pub null_count: Option<i64>,
 }
 
 impl ColumnStatistics {
@@ -118,6 +120,7 @@ impl ColumnStatistics {
             data_type,
             min_value: None,
             max_value: None,
+            null_count: None,
         }
     }
 
@@ -137,12 +140,13 @@ impl ColumnStatistics {
             data_type: data_type.clone(),
             min_value,
             max_value,
+            null_count: stats.null_count_opt().map(|n| n as i64),
         }
     }
 
     /// Merge with another ColumnStatistics (for aggregation).
     ///
-    /// Takes min of mins, max of maxs.
+    /// Takes min of mins, max of maxs, and sum of null counts.
     /// Used when aggregating row group stats to file-level stats.
     pub fn merge(&mut self, other: &ColumnStatistics) {
         // Merge min values (take the smaller one)
@@ -172,6 +176,8 @@ impl ColumnStatistics {
             (None, Some(b)) => Some(Arc::clone(b)),
             (None, None) => None,
         };
+
+        self.null_count = self.null_count.zip(other.null_count).map(|(a, b)| a + b);
     }
 }
 
@@ -206,6 +212,28 @@ impl StatisticsContainer {
         }
     }
 
+    /// Merge with the stats of other rows (for aggregation).
+    ///
+    /// Sums the row counts and merges the stats of each column. Columns whose stats are
+    /// unknown in either container, i.e., without a null count, become unknown.
+    /// Used when aggregating file-level stats to table-level stats.
+    pub fn merge(&mut self, other: &StatisticsContainer) {
+        self.num_rows = self.num_rows.zip(other.num_rows).map(|(a, b)| a + b);
+        for (col_name, col_stats) in self.columns.iter_mut() {
+            match other.columns.get(col_name) {
+                Some(other_stats)
+                    if col_stats.null_count.is_some() && other_stats.null_count.is_some() =>
+                {
+                    col_stats.merge(other_stats)
+                }
+                _ => {
+                    *col_stats =
+                        ColumnStatistics::new(col_name.clone(), col_stats.data_type.clone())
+                }
+            }
+        }
+    }
+
     /// Create file-level stats by aggregating row group stats from Parquet metadata.
     ///
     /// This iterates through all row groups, extracts stats for each column,
@@ -472,16 +500,19 @@ mod tests {
             data_type: DataType::Int32,
             min_value: Some(int32_array(10)),
             max_value: Some(int32_array(50)),
+            null_count: Some(1),
         };
         let stats2 = ColumnStatistics {
             column_name: "test".to_string(),
             data_type: DataType::Int32,
             min_value: Some(int32_array(5)),
             max_value: Some(int32_array(100)),
+            null_count: Some(2),
         };
         stats1.merge(&stats2);
         assert_eq!(get_int32(stats1.min_value.as_ref().unwrap()), 5);
         assert_eq!(get_int32(stats1.max_value.as_ref().unwrap()), 100);
+        assert_eq!(stats1.null_count, Some(3));
 
         // Test 2: One side has None values - preserves the Some value
         let mut stats3 = ColumnStatistics {
@@ -489,16 +520,55 @@ mod tests {
             data_type: DataType::Int32,
             min_value: Some(int32_array(10)),
             max_value: None,
+            null_count: None,
         };
         let stats4 = ColumnStatistics {
             column_name: "test".to_string(),
             data_type: DataType::Int32,
             min_value: None,
             max_value: Some(int32_array(100)),
+            null_count: None,
         };
         stats3.merge(&stats4);
         assert_eq!(get_int32(stats3.min_value.as_ref().unwrap()), 10);
         assert_eq!(get_int32(stats3.max_value.as_ref().unwrap()), 100);
+        assert_eq!(stats3.null_count, None);
+    }
+
+    #[test]
+    fn test_statistics_container_merge() {
+        let container = |min: i32, max: i32, null_count: Option<i64>| {
+            let mut container = StatisticsContainer::new(StatsGranularity::File);
+            container.num_rows = Some(10);
+            for name in ["a", "b"] {
+                let stats = ColumnStatistics {
+                    column_name: name.to_string(),
+                    data_type: DataType::Int32,
+                    min_value: Some(int32_array(min)),
+                    max_value: Some(int32_array(max)),
+                    null_count: null_count.filter(|_| name == "a"),
+                };
+                container.columns.insert(name.to_string(), stats);
+            }
+            container
+        };
+        let mut merged = container(10, 50, Some(1));
+        merged.merge(&container(5, 20, Some(2)));
+        assert_eq!(merged.num_rows, Some(20));
+        let a = &merged.columns["a"];
+        assert_eq!(get_int32(a.min_value.as_ref().unwrap()), 5);
+        assert_eq!(get_int32(a.max_value.as_ref().unwrap()), 50);
+        assert_eq!(a.null_count, Some(3));
+        // Stats without null counts are unknown
+        let b = &merged.columns["b"];
+        assert!(b.min_value.is_none() && b.max_value.is_none());
+
+        let mut unknown = StatisticsContainer::new(StatsGranularity::File);
+        merged.merge(&unknown);
+        assert_eq!(merged.num_rows, None);
+        assert!(merged.columns["a"].min_value.is_none());
+        unknown.merge(&container(5, 20, Some(2)));
+        assert!(unknown.columns.is_empty());
     }
 
     #[test]
@@ -509,12 +579,14 @@ mod tests {
             data_type: DataType::UInt32,
             min_value: Some(uint32_array(100)),
             max_value: Some(uint32_array(200)),
+            null_count: None,
         };
         let uint32_large = ColumnStatistics {
             column_name: "test".to_string(),
             data_type: DataType::UInt32,
             min_value: Some(uint32_array(3_000_000_000)),
             max_value: Some(uint32_array(4_000_000_000)),
+            null_count: None,
         };
         uint32_stats.merge(&uint32_large);
         // 100 < 3B in unsigned comparison
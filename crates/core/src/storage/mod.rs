@@ -24,8 +24,9 @@ use std::sync::Arc;
 
 use async_recursion::async_recursion;
 use bytes::Bytes;
+use futures::TryStreamExt;
 use object_store::path::Path as ObjPath;
-use object_store::{ObjectStore, parse_url_opts};
+use object_store::{ObjectStore, PutMode, PutOptions, parse_url_opts};
 use url::Url;
 
 use crate::config::HudiConfigs;
@@ -130,6 +131,90 @@ impl Storage {
         Ok(bytes)
     }
 
+    /// Write the given data to the file at the relative path, overwriting any existing file.
+    pub async fn put_file_data(&self, relative_path: &str, data: Bytes) -> Result<()> {
+        let obj_url = join_url_segments(&self.base_url, &[relative_path])?;
+        let obj_path = ObjPath::from_url_path(obj_url.path())?;
+        self.object_store.put(&obj_path, data.into()).await?;
+        Ok(())
+    }
+
+    /// Write the given data to the file at the relative path only if the file does not exist.
+    ///
+    /// Returns `false` without writing if the file already exists.
+    pub async fn put_file_data_if_absent(&self, relative_path: &str, data: Bytes) -> Result<bool> {
+        let obj_url = join_url_segments(&self.base_url, &[relative_path])?;
+        let obj_path = ObjPath::from_url_path(obj_url.path())?;
+        let opts = PutOptions::from(PutMode::Create);
+        match self
+            .object_store
+            .put_opts(&obj_path, data.into(), opts)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::AlreadyExists { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Create the directory at the relative path, including its parents.
+    ///
+    /// Object stores have no directories, so this only takes effect on local file systems.
+    pub async fn create_dir(&self, relative_path: &str) -> Result<()> {
+        if self.base_url.scheme() != "file" {
+            return Ok(());
+        }
+        let dir_url = join_url_segments(&self.base_url, &[relative_path])?;
+        let dir_path = dir_url
+            .to_file_path()
+            .map_err(|_| InvalidPath(format!("Failed to get local path from: {dir_url}")))?;
+        std::fs::create_dir_all(dir_path)?;
+        Ok(())
+    }
+
+    /// Delete the file at the relative path.
+    ///
+    /// Deleting a file that does not exist is not an error.
+    pub async fn delete_file(&self, relative_path: &str) -> Result<()> {
+        let obj_url = join_url_segments(&self.base_url, &[relative_path])?;
+        let obj_path = ObjPath::from_url_path(obj_url.path())?;
+        match self.object_store.delete(&obj_path).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Delete all files under the relative path, and the directory itself on local file systems.
+    ///
+    /// Deleting a directory that does not exist is not an error.
+    pub async fn delete_dir(&self, relative_path: &str) -> Result<()> {
+        let dir_url = join_url_segments(&self.base_url, &[relative_path])?;
+        let dir_path = ObjPath::from_url_path(dir_url.path())?;
+        let locations: Vec<ObjPath> = self
+            .object_store
+            .list(Some(&dir_path))
+            .map_ok(|meta| meta.location)
+            .try_collect()
+            .await?;
+        for location in locations {
+            match self.object_store.delete(&location).await {
+                Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        if self.base_url.scheme() == "file" {
+            let local_path = dir_url
+                .to_file_path()
+                .map_err(|_| InvalidPath(format!("Failed to get local path from: {dir_url}")))?;
+            match std::fs::remove_dir_all(local_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
     pub async fn get_storage_reader(&self, relative_path: &str) -> Result<StorageReader> {
         let obj_url = join_url_segments(&self.base_url, &[relative_path])?;
         let obj_path = ObjPath::from_url_path(obj_url.path())?;
@@ -377,6 +462,80 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn storage_put_and_delete_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base_url = Url::from_directory_path(temp_dir.path()).unwrap();
+        let storage = Storage::new_with_base_url(base_url).unwrap();
+
+        storage
+            .put_file_data("a/b/c.txt", Bytes::from_static(b"hudi"))
+            .await
+            .unwrap();
+        let data = storage.get_file_data("a/b/c.txt").await.unwrap();
+        assert_eq!(data.as_ref(), b"hudi");
+
+        storage.delete_file("a/b/c.txt").await.unwrap();
+        assert!(storage.get_file_data("a/b/c.txt").await.is_err());
+
+        // deleting a non-existent file is a no-op
+        storage.delete_file("a/b/c.txt").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn storage_create_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base_url = Url::from_directory_path(temp_dir.path()).unwrap();
+        let storage = Storage::new_with_base_url(base_url).unwrap();
+
+        storage.create_dir(".hoodie/.aux/.bootstrap").await.unwrap();
+        assert!(temp_dir.path().join(".hoodie/.aux/.bootstrap").is_dir());
+        // creating an existing directory is a no-op
+        storage.create_dir(".hoodie").await.unwrap();
+        assert_eq!(storage.list_dirs(None).await.unwrap(), [".hoodie"]);
+    }
+
+    #[tokio::test]
+    async fn storage_delete_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base_url = Url::from_directory_path(temp_dir.path()).unwrap();
+        let storage = Storage::new_with_base_url(base_url).unwrap();
+
+        for path in ["a/b/c.txt", "a/d.txt", "e.txt"] {
+            storage
+                .put_file_data(path, Bytes::from_static(b"hudi"))
+                .await
+                .unwrap();
+        }
+        storage.create_dir("a/f").await.unwrap();
+        storage.delete_dir("a").await.unwrap();
+        assert!(!temp_dir.path().join("a").exists());
+        assert!(temp_dir.path().join("e.txt").exists());
+
+        // deleting a non-existent directory is a no-op
+        storage.delete_dir("a").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn storage_put_file_data_if_absent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base_url = Url::from_directory_path(temp_dir.path()).unwrap();
+        let storage = Storage::new_with_base_url(base_url).unwrap();
+
+        let written = storage
+            .put_file_data_if_absent(".hoodie/.aux/lock", Bytes::from("a"))
+            .await
+            .unwrap();
+        assert!(written);
+        let written = storage
+            .put_file_data_if_absent(".hoodie/.aux/lock", Bytes::from("b"))
+            .await
+            .unwrap();
+        assert!(!written);
+        let data = storage.get_file_data(".hoodie/.aux/lock").await.unwrap();
+        assert_eq!(data, Bytes::from("a"));
+    }
+
     #[tokio::test]
     async fn storage_get_file_info() {
         let base_url =
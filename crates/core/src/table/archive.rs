@@ -0,0 +1,316 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Archiving instants of a Hudi table beyond the configured window of the active timeline.
+//!
+//! See [HudiArchiveConfig] for the configurations.
+
+use crate::Result;
+use crate::config::archive::HudiArchiveConfig;
+use crate::error::CoreError;
+use crate::table::Table;
+use crate::timeline::instant::{Action, Instant, State};
+use crate::timeline::selector::TimelineSelector;
+use std::collections::HashSet;
+
+impl Table {
+    /// Archive the completed instants beyond the window configured by [HudiArchiveConfig].
+    ///
+    /// Archival is triggered when the number of completed commits exceeds
+    /// [HudiArchiveConfig::MaxCommitsToKeep], and it keeps the latest
+    /// [HudiArchiveConfig::MinCommitsToKeep] commits on the active timeline. Instants are
+    /// never archived past any pending instant, the earliest savepoint, the earliest commit
+    /// retained by the latest clean, or a replacecommit whose replaced file groups have not been
+    /// cleaned yet.
+    ///
+    /// If the metadata table is enabled, instants are not archived past the latest completed
+    /// instant of the metadata table, so the instants not synced to it yet stay active. The
+    /// timeline of the metadata table itself is not archived, which keeps the deltacommits of
+    /// all the active instants of the table on it.
+    ///
+    /// Returns the archived instants in ascending order. The [Table] instance is not
+    /// refreshed after archiving; create a new instance to read the updated timeline.
+    pub async fn archive(&self) -> Result<Vec<Instant>> {
+        let instants = self.plan_archive().await?;
+        self.timeline.archive_instants(&instants).await?;
+        Ok(instants)
+    }
+
+    /// Get the completed instants to be archived without modifying the table.
+    pub(crate) async fn plan_archive(&self) -> Result<Vec<Instant>> {
+        let min_commits: usize = self
+            .hudi_configs
+            .get_or_default(HudiArchiveConfig::MinCommitsToKeep)
+            .into();
+        let max_commits: usize = self
+            .hudi_configs
+            .get_or_default(HudiArchiveConfig::MaxCommitsToKeep)
+            .into();
+        if min_commits == 0 || min_commits >= max_commits {
+            return Err(CoreError::InvalidValue(format!(
+                "{} ({min_commits}) must be positive and less than {} ({max_commits})",
+                HudiArchiveConfig::MinCommitsToKeep.as_ref(),
+                HudiArchiveConfig::MaxCommitsToKeep.as_ref(),
+            )));
+        }
+
+        let commits = &self.timeline.completed_commits;
+        if commits.len() <= max_commits {
+            return Ok(Vec::new());
+        }
+        let mut boundary = commits[commits.len() - min_commits].timestamp.clone();
+
+        let selector = TimelineSelector::actions_in_states(&[], &[], self.hudi_configs.clone())?;
+        let instants = self.timeline.load_instants(&selector, false).await?;
+
+        // Requested and inflight files are kept for completed instants in timeline layout 2
+        let completed: HashSet<&str> = instants
+            .iter()
+            .filter(|i| i.state == State::Completed)
+            .map(|i| i.timestamp.as_str())
+            .collect();
+        if let Some(pending) = instants
+            .iter()
+            .find(|i| !completed.contains(i.timestamp.as_str()))
+        {
+            boundary = boundary.min(pending.timestamp.clone());
+        }
+
+        // Instants since the earliest savepoint are needed to restore the table to it.
+        if let Some(savepoint) = instants
+            .iter()
+            .find(|i| i.state == State::Completed && i.action == Action::Savepoint)
+        {
+            boundary = boundary.min(savepoint.timestamp.clone());
+        }
+
+        let latest_clean = instants
+            .iter()
+            .rev()
+            .find(|i| i.state == State::Completed && i.action == Action::Clean);
+        if let Some(clean) = latest_clean {
+            let metadata = self.timeline.get_clean_metadata(clean).await?;
+            if !metadata.earliest_commit_to_retain.is_empty() {
+                boundary = boundary.min(metadata.earliest_commit_to_retain);
+            }
+        }
+
+        // The file groups replaced by a replacecommit are only deleted by a later clean;
+        // keep the replacecommit active until then.
+        let cleaned_before = latest_clean.map(|c| c.timestamp.as_str()).unwrap_or("");
+        if let Some(replace) = instants.iter().find(|i| {
+            i.state == State::Completed
+                && i.action == Action::ReplaceCommit
+                && i.timestamp.as_str() >= cleaned_before
+        }) {
+            boundary = boundary.min(replace.timestamp.clone());
+        }
+
+        if self.is_metadata_table_enabled() {
+            let metadata_table = self.new_metadata_table().await?;
+            let synced_until = metadata_table
+                .timeline
+                .completed_commits
+                .last()
+                .map(|i| i.timestamp.as_str())
+                .unwrap_or("");
+            if let Some(unsynced) = instants
+                .iter()
+                .find(|i| i.timestamp.as_str() > synced_until)
+            {
+                boundary = boundary.min(unsynced.timestamp.clone());
+            }
+        }
+
+        Ok(instants
+            .into_iter()
+            .filter(|i| i.state == State::Completed && i.timestamp < boundary)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::internal::HudiInternalConfig::TimelineArchivedReadEnabled;
+    use crate::table::test_util::read_snapshot_sorted;
+    use crate::timeline::{DEFAULT_LOADING_ACTIONS, EARLIEST_START_TIMESTAMP};
+    use hudi_test::SampleTable;
+    use std::path::Path;
+
+    async fn load_all_completed_commits(base_path: &str) -> Vec<String> {
+        let table =
+            Table::new_with_options(base_path, [(TimelineArchivedReadEnabled.as_ref(), "true")])
+                .await
+                .unwrap();
+        let selector = TimelineSelector::completed_actions_in_range(
+            DEFAULT_LOADING_ACTIONS,
+            table.hudi_configs.clone(),
+            Some(EARLIEST_START_TIMESTAMP),
+            None,
+        )
+        .unwrap();
+        let mut timestamps: Vec<String> = table
+            .timeline
+            .load_instants(&selector, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|i| i.timestamp)
+            .collect();
+        timestamps.sort_unstable();
+        timestamps
+    }
+
+    #[tokio::test]
+    async fn test_archive_to_lsm_history_v9() {
+        const KEY: &str = "txn_id";
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [
+            (HudiArchiveConfig::MinCommitsToKeep.as_ref(), "2"),
+            (HudiArchiveConfig::MaxCommitsToKeep.as_ref(), "3"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let expected = read_snapshot_sorted(&table, KEY).await;
+        let all_commits = load_all_completed_commits(&base_path).await;
+        assert_eq!(all_commits.len(), 12);
+
+        // bounded by the first replacecommit as there is no clean yet
+        let archived = table.archive().await.unwrap();
+        let archived_timestamps: Vec<&str> =
+            archived.iter().map(|i| i.timestamp.as_str()).collect();
+        assert_eq!(archived_timestamps, all_commits[..7]);
+
+        let timeline_dir = Path::new(&base_path).join(".hoodie/timeline");
+        for entry in std::fs::read_dir(&timeline_dir).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            assert!(
+                archived_timestamps.iter().all(|ts| !name.starts_with(ts)),
+                "{name} should have been archived"
+            );
+        }
+        assert!(timeline_dir.join("history/_version_").exists());
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(table.timeline.completed_commits.len(), 5);
+        assert_eq!(read_snapshot_sorted(&table, KEY).await, expected);
+        assert_eq!(load_all_completed_commits(&base_path).await, all_commits);
+    }
+
+    #[tokio::test]
+    async fn test_archive_with_metadata_table_enabled() {
+        const KEY: &str = "txn_id";
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        Table::new(&base_path)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+        let options = [
+            (HudiArchiveConfig::MinCommitsToKeep.as_ref(), "2"),
+            (HudiArchiveConfig::MaxCommitsToKeep.as_ref(), "3"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        assert!(table.is_metadata_table_enabled());
+        let expected = read_snapshot_sorted(&table, KEY).await;
+        let all_commits = load_all_completed_commits(&base_path).await;
+        let mdt_commits = table
+            .new_metadata_table()
+            .await
+            .unwrap()
+            .timeline
+            .completed_commits;
+
+        let archived = table.archive().await.unwrap();
+        let archived_timestamps: Vec<&str> =
+            archived.iter().map(|i| i.timestamp.as_str()).collect();
+        assert_eq!(archived_timestamps, all_commits[..7]);
+
+        // The metadata table keeps its timeline and still lists the files of the table.
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(table.timeline.completed_commits.len(), 5);
+        let mdt = table.new_metadata_table().await.unwrap();
+        assert_eq!(mdt.timeline.completed_commits, mdt_commits);
+        assert_eq!(read_snapshot_sorted(&table, KEY).await, expected);
+        assert_eq!(load_all_completed_commits(&base_path).await, all_commits);
+    }
+
+    #[tokio::test]
+    async fn test_archive_to_archive_log_v6() {
+        const KEY: &str = "_hoodie_record_key";
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow_fresh();
+        let options = [
+            (HudiArchiveConfig::MinCommitsToKeep.as_ref(), "1"),
+            (HudiArchiveConfig::MaxCommitsToKeep.as_ref(), "2"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let expected = read_snapshot_sorted(&table, KEY).await;
+
+        let archived = table.archive().await.unwrap();
+        let archived_timestamps: Vec<&str> =
+            archived.iter().map(|i| i.timestamp.as_str()).collect();
+        assert_eq!(
+            archived_timestamps,
+            vec!["20240707001301554", "20240707001302376"]
+        );
+        assert!(
+            Path::new(&base_path)
+                .join(".hoodie/archived/.commits_.archive.1_1-0-1")
+                .exists()
+        );
+        assert!(
+            !Path::new(&base_path)
+                .join(".hoodie/20240707001301554.commit.requested")
+                .exists()
+        );
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(table.timeline.completed_commits.len(), 1);
+        assert_eq!(read_snapshot_sorted(&table, KEY).await, expected);
+        assert_eq!(
+            load_all_completed_commits(&base_path).await,
+            vec![
+                "20240707001301554",
+                "20240707001302376",
+                "20240707001303088"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_not_enough_commits() {
+        let base_path = SampleTable::V6Nonpartitioned.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(table.archive().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_archive_invalid_configs() {
+        let base_path = SampleTable::V6Nonpartitioned.path_to_cow();
+        let options = [
+            (HudiArchiveConfig::MinCommitsToKeep.as_ref(), "3"),
+            (HudiArchiveConfig::MaxCommitsToKeep.as_ref(), "3"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        assert!(matches!(
+            table.archive().await.unwrap_err(),
+            CoreError::InvalidValue(_)
+        ));
+    }
+}
@@ -83,6 +83,8 @@ macro_rules! impl_with_options {
     };
 }
 
+pub(crate) use impl_with_options;
+
 impl_with_options!(
     TableBuilder,
     hudi_options,
@@ -190,7 +192,7 @@ impl OptionResolver {
         validate_configs(&hudi_configs)
     }
 
-    fn resolve_user_provided_options(&mut self) {
+    pub(crate) fn resolve_user_provided_options(&mut self) {
         // Insert the base path into hudi options since it is explicitly provided
         self.hudi_options.insert(
             HudiTableConfig::BasePath.as_ref().to_string(),
@@ -213,7 +215,7 @@ impl OptionResolver {
     /// Also supports standard cloud storage env vars like `AWS_ACCESS_KEY_ID`, `GOOGLE_APPLICATION_CREDENTIALS`, `AZURE_STORAGE_ACCOUNT_KEY`, etc.
     ///
     /// [note] All keys will be converted to lowercase.
-    fn resolve_env_vars(&mut self) {
+    pub(crate) fn resolve_env_vars(&mut self) {
         for (env_key, env_value) in std::env::vars() {
             let lower_option_key = if let Some(stripped) = env_key.strip_prefix("HOODIE_ENV_") {
                 Some(stripped.replace("_DOT_", ".").to_ascii_lowercase())
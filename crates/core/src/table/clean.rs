@@ -0,0 +1,651 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Cleaning obsolete file slices from a Hudi table.
+//!
+//! The cleaner lists all file groups from storage, decides which [FileSlice]s are no longer
+//! needed according to [HudiCleanConfig::CleanPolicy], deletes their files, and records a
+//! `clean` instant with [HoodieCleanMetadata] on the [crate::timeline::Timeline].
+//!
+//! File slices with files recorded by savepoints are never cleaned, nor are the file groups
+//! in the plans of pending compactions and clusterings.
+
+use crate::Result;
+use crate::config::clean::CleanPolicyValue;
+use crate::config::clean::HudiCleanConfig;
+use crate::file_group::FileGroup;
+use crate::file_group::file_slice::FileSlice;
+use crate::metadata::clean::{
+    HoodieActionInstant, HoodieCleanFileInfo, HoodieCleanMetadata, HoodieCleanPartitionMetadata,
+    HoodieCleanerPlan,
+};
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::table::writer::FilesPartitionChanges;
+use crate::storage::util::join_url_segments;
+use crate::table::Table;
+use crate::table::listing::FileLister;
+use crate::table::partition::PartitionPruner;
+use crate::timeline::instant::{Action, Instant};
+use crate::timeline::util::format_datetime_in_timezone;
+use crate::timeline::view::TimelineView;
+use bytes::Bytes;
+use chrono::{TimeDelta, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+
+/// The version of [HoodieCleanerPlan] and [HoodieCleanMetadata] written by the cleaner.
+const CLEAN_METADATA_VERSION: i32 = 2;
+
+/// Files to be deleted by a clean, and the commit that bounds the retained file slices.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CleanPlan {
+    pub policy: String,
+    pub earliest_commit_to_retain: Option<Instant>,
+    pub last_completed_commit: Option<Instant>,
+    /// Partition path to the relative paths of the files to be deleted.
+    pub partition_to_files: BTreeMap<String, Vec<String>>,
+}
+
+impl CleanPlan {
+    pub fn is_empty(&self) -> bool {
+        self.partition_to_files
+            .values()
+            .all(|files| files.is_empty())
+    }
+}
+
+impl Table {
+    /// Clean the file slices that are no longer needed according to [HudiCleanConfig].
+    ///
+    /// The obsolete files are deleted from storage and a `clean` instant is recorded on
+    /// the timeline. If the metadata table is enabled, the deleted files are also synced to
+    /// its `files` partition. Returns `None` if there is nothing to clean.
+    ///
+    /// The [Table] instance is not refreshed after cleaning; create a new instance to
+    /// read the updated timeline.
+    pub async fn clean(&self) -> Result<Option<HoodieCleanMetadata>> {
+        if self.is_metadata_table_enabled() {
+            self.ensure_metadata_table_writable()?;
+        }
+
+        let plan = self.plan_clean().await?;
+        if plan.is_empty() {
+            return Ok(None);
+        }
+
+        self.execute_clean(&plan).await.map(Some)
+    }
+
+    /// Compute the files to be deleted without modifying the table.
+    pub(crate) async fn plan_clean(&self) -> Result<CleanPlan> {
+        let policy_str: String = self
+            .hudi_configs
+            .get_or_default(HudiCleanConfig::CleanPolicy)
+            .into();
+        let policy = CleanPolicyValue::from_str(&policy_str)?;
+        let commits = &self.timeline.completed_commits;
+        let mut plan = CleanPlan {
+            policy: policy.as_ref().to_string(),
+            last_completed_commit: commits.last().cloned(),
+            ..Default::default()
+        };
+
+        let earliest_commit_to_retain = match policy {
+            CleanPolicyValue::KeepLatestCommits => {
+                let retained: usize = self
+                    .hudi_configs
+                    .get_or_default(HudiCleanConfig::CommitsRetained)
+                    .into();
+                if commits.len() <= retained {
+                    return Ok(plan);
+                }
+                Some(commits[commits.len() - retained.max(1)].clone())
+            }
+            CleanPolicyValue::KeepLatestByHours => {
+                let hours: usize = self
+                    .hudi_configs
+                    .get_or_default(HudiCleanConfig::HoursRetained)
+                    .into();
+                let cutoff = Utc::now() - TimeDelta::hours(hours as i64);
+                let cutoff = format_datetime_in_timezone(&cutoff, &self.timezone())?;
+                match commits.iter().find(|c| c.timestamp >= cutoff) {
+                    Some(instant) => Some(instant.clone()),
+                    None => return Ok(plan),
+                }
+            }
+            CleanPolicyValue::KeepLatestFileVersions => None,
+        };
+        plan.earliest_commit_to_retain = earliest_commit_to_retain;
+
+        let replaced_file_ids = self.get_replaced_file_ids().await?;
+        let pending_file_groups = self
+            .timeline
+            .get_file_groups_in_pending_compaction_and_clustering()
+            .await?;
+        let savepointed_files = self.get_savepointed_files().await?;
+        let file_versions_retained: usize = self
+            .hudi_configs
+            .get_or_default(HudiCleanConfig::FileVersionsRetained)
+            .into();
+
+        for (partition_path, file_groups) in self.list_all_file_groups().await? {
+            let mut files: Vec<String> = Vec::new();
+            for file_group in &file_groups {
+                let key = (partition_path.clone(), file_group.file_id.clone());
+                // The pending compaction or clustering reads the file slices.
+                if pending_file_groups.contains(&key) {
+                    continue;
+                }
+                let slices = self.committed_file_slices_latest_first(file_group);
+                let slices_to_delete: Vec<&FileSlice> = match replaced_file_ids.get(&key) {
+                    Some(replace_ts) if Self::is_replaced_before(replace_ts, &plan) => slices,
+                    _ => match &plan.earliest_commit_to_retain {
+                        None => slices
+                            .into_iter()
+                            .skip(file_versions_retained.max(1))
+                            .collect(),
+                        Some(earliest) => {
+                            Self::slices_older_than_retained(slices, &earliest.timestamp)
+                        }
+                    },
+                };
+                for slice in slices_to_delete {
+                    let mut slice_files = vec![slice.base_file_relative_path()?];
+                    for log_file in &slice.log_files {
+                        slice_files.push(slice.log_file_relative_path(log_file)?);
+                    }
+                    if slice_files.iter().any(|f| savepointed_files.contains(f)) {
+                        continue;
+                    }
+                    files.extend(slice_files);
+                }
+            }
+            if !files.is_empty() {
+                files.sort_unstable();
+                plan.partition_to_files.insert(partition_path, files);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Whether a file group replaced at `replace_ts` is no longer needed by the plan.
+    fn is_replaced_before(replace_ts: &str, plan: &CleanPlan) -> bool {
+        match &plan.earliest_commit_to_retain {
+            None => true,
+            Some(earliest) => replace_ts < earliest.timestamp.as_str(),
+        }
+    }
+
+    /// Select the slices to delete when retaining by commits.
+    ///
+    /// Slices created at or after `earliest_commit_to_retain` are retained, so is the latest
+    /// slice created before it, as it is still needed to serve queries as of that commit.
+    fn slices_older_than_retained<'a>(
+        slices: Vec<&'a FileSlice>,
+        earliest_commit_to_retain: &str,
+    ) -> Vec<&'a FileSlice> {
+        slices
+            .into_iter()
+            .filter(|s| s.creation_instant_time() < earliest_commit_to_retain)
+            .skip(1)
+            .collect()
+    }
+
+    /// Get the committed [FileSlice]s of the [FileGroup] sorted from the latest to the oldest.
+    ///
+    /// A slice is considered committed if its creation instant is a completed commit on the
+    /// active timeline, or it is before the active timeline starts, i.e., archived.
+    pub(crate) fn committed_file_slices_latest_first<'a>(
+        &self,
+        file_group: &'a FileGroup,
+    ) -> Vec<&'a FileSlice> {
+        let commits = &self.timeline.completed_commits;
+        let completed: HashSet<&str> = commits.iter().map(|c| c.timestamp.as_str()).collect();
+        let timeline_start = self.timeline.earliest_active_timestamp.as_deref();
+        file_group
+            .file_slices
+            .values()
+            .rev()
+            .filter(|s| {
+                let ts = s.creation_instant_time();
+                completed.contains(ts) || timeline_start.is_some_and(|start| ts < start)
+            })
+            .collect()
+    }
+
+    /// Get the replaced file groups as `(partition path, file id)` to the replacing instant time.
+    async fn get_replaced_file_ids(&self) -> Result<HashMap<(String, String), String>> {
+        let mut replaced = HashMap::new();
+        for instant in self
+            .timeline
+            .completed_commits
+            .iter()
+            .filter(|i| i.is_replacecommit())
+        {
+            let metadata = self.timeline.get_instant_metadata(instant).await?;
+            let metadata = HoodieCommitMetadata::from_json_map(&metadata)?;
+            for (partition, file_id) in metadata.iter_replace_file_ids() {
+                replaced.insert(
+                    (partition.to_string(), file_id.to_string()),
+                    instant.timestamp.clone(),
+                );
+            }
+        }
+        Ok(replaced)
+    }
+
+    /// List all file groups of the table from storage, without any pruning.
+    pub(crate) async fn list_all_file_groups(&self) -> Result<Vec<(String, Vec<FileGroup>)>> {
+        let commits = &self.timeline.completed_commits;
+        let as_of_timestamp = commits
+            .last()
+            .map(|c| c.timestamp.clone())
+            .unwrap_or_default();
+        let completion_time_view = TimelineView::new(
+            as_of_timestamp,
+            None,
+            commits,
+            HashSet::new(),
+            &self.hudi_configs,
+        )
+        .with_archived_before(self.timeline.earliest_active_timestamp.clone());
+        let lister = FileLister::new(
+            self.hudi_configs.clone(),
+            self.file_system_view.storage.clone(),
+            PartitionPruner::empty(),
+        );
+        let file_groups = lister
+            .list_file_groups_for_relevant_partitions(&completion_time_view, None)
+            .await?;
+        let mut file_groups: Vec<(String, Vec<FileGroup>)> = file_groups.into_iter().collect();
+        file_groups.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(file_groups)
+    }
+
+    /// Delete the planned files and record the `clean` instant on the timeline.
+    async fn execute_clean(&self, plan: &CleanPlan) -> Result<HoodieCleanMetadata> {
+        let timer = std::time::Instant::now();
+        let timeline = &self.timeline;
+        let latest_clean = timeline
+            .get_completed_cleans(true)
+            .await?
+            .into_iter()
+            .next();
+        let after = [plan.last_completed_commit.as_ref(), latest_clean.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|i| i.timestamp.as_str())
+            .max();
+        let clean_timestamp = timeline.new_instant_timestamp(after)?;
+
+        let cleaner_plan = self.to_cleaner_plan(plan)?;
+        let requested = timeline
+            .create_requested_instant(
+                Action::Clean,
+                &clean_timestamp,
+                Bytes::from(cleaner_plan.to_avro_bytes()?),
+            )
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let storage = &self.file_system_view.storage;
+        let mut partition_metadata = HashMap::new();
+        let mut total_files_deleted = 0;
+        for (partition_path, files) in &plan.partition_to_files {
+            let mut metadata = HoodieCleanPartitionMetadata {
+                partition_path: partition_path.to_string(),
+                policy: plan.policy.clone(),
+                is_partition_deleted: Some(false),
+                ..Default::default()
+            };
+            for file in files {
+                let file_name = file.rsplit('/').next().unwrap_or(file).to_string();
+                metadata.delete_path_patterns.push(file_name.clone());
+                match storage.delete_file(file).await {
+                    Ok(()) => metadata.success_delete_files.push(file_name),
+                    Err(e) => {
+                        log::warn!("Failed to delete file '{file}' during clean: {e}");
+                        metadata.failed_delete_files.push(file_name);
+                    }
+                }
+            }
+            total_files_deleted += metadata.success_delete_files.len() as i32;
+            partition_metadata.insert(partition_path.to_string(), metadata);
+        }
+
+        let clean_metadata = HoodieCleanMetadata {
+            start_clean_time: clean_timestamp,
+            time_taken_in_millis: timer.elapsed().as_millis() as i64,
+            total_files_deleted,
+            earliest_commit_to_retain: plan
+                .earliest_commit_to_retain
+                .as_ref()
+                .map(|i| i.timestamp.clone())
+                .unwrap_or_default(),
+            last_completed_commit_timestamp: plan
+                .last_completed_commit
+                .as_ref()
+                .map(|i| i.timestamp.clone())
+                .unwrap_or_default(),
+            partition_metadata,
+            version: Some(CLEAN_METADATA_VERSION),
+            extra_metadata: None,
+        };
+        self.update_metadata_table(
+            &clean_metadata.start_clean_time,
+            &FilesPartitionChanges::from_clean_metadata(&clean_metadata).into(),
+        )
+        .await?;
+        timeline
+            .transition_inflight_to_completed(
+                &inflight,
+                Bytes::from(clean_metadata.to_avro_bytes()?),
+            )
+            .await?;
+
+        Ok(clean_metadata)
+    }
+
+    fn to_cleaner_plan(&self, plan: &CleanPlan) -> Result<HoodieCleanerPlan> {
+        let base_url = self.base_url();
+        let mut file_paths = HashMap::new();
+        for (partition_path, files) in &plan.partition_to_files {
+            let file_infos = files
+                .iter()
+                .map(|f| {
+                    Ok(HoodieCleanFileInfo {
+                        file_path: Some(join_url_segments(&base_url, &[f])?.to_string()),
+                        is_bootstrap_base_file: Some(false),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            file_paths.insert(partition_path.to_string(), file_infos);
+        }
+        Ok(HoodieCleanerPlan {
+            earliest_instant_to_retain: plan.earliest_commit_to_retain.as_ref().map(|i| {
+                HoodieActionInstant {
+                    timestamp: i.timestamp.clone(),
+                    action: i.action.as_ref().to_string(),
+                    state: "COMPLETED".to_string(),
+                }
+            }),
+            last_completed_commit_timestamp: plan
+                .last_completed_commit
+                .as_ref()
+                .map(|i| i.timestamp.clone())
+                .unwrap_or_default(),
+            policy: plan.policy.clone(),
+            version: Some(CLEAN_METADATA_VERSION),
+            file_paths_to_be_deleted_per_partition: Some(file_paths),
+            partitions_to_be_deleted: None,
+            extra_metadata: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::read::HudiReadConfig;
+    use crate::error::CoreError;
+    use crate::metadata::compaction::{HoodieCompactionOperation, HoodieCompactionPlan};
+    use crate::metadata::replace_commit::{
+        HoodieClusteringGroup, HoodieClusteringPlan, HoodieRequestedReplaceMetadata,
+        HoodieSliceInfo,
+    };
+    use crate::table::ReadOptions;
+    use crate::table::test_util::read_snapshot_sorted;
+    use hudi_test::SampleTable;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn test_clean_keep_latest_file_versions_v6() {
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow_fresh();
+        let options = [
+            (
+                HudiCleanConfig::CleanPolicy.as_ref(),
+                "KEEP_LATEST_FILE_VERSIONS",
+            ),
+            (HudiCleanConfig::FileVersionsRetained.as_ref(), "1"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let expected = read_snapshot_sorted(&table, "_hoodie_record_key").await;
+
+        // all file groups but the one written by the insert overwrite are replaced
+        let plan = table.plan_clean().await.unwrap();
+        assert!(plan.earliest_commit_to_retain.is_none());
+        let files: Vec<&str> = plan
+            .partition_to_files
+            .values()
+            .flatten()
+            .map(|f| f.as_str())
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                "10/4f2685a3-614f-49ca-9b2b-e1cb9fb61f27-0_0-95-135_20240707001302376.parquet",
+                "10/4f2685a3-614f-49ca-9b2b-e1cb9fb61f27-0_1-74-111_20240707001301554.parquet",
+                "20/88163884-fef0-4aab-865d-c72327a8a1d5-0_0-74-110_20240707001301554.parquet",
+                "30/d398fae1-c0e6-4098-8124-f55f7098bdba-0_1-95-136_20240707001302376.parquet",
+            ]
+        );
+
+        let metadata = table.clean().await.unwrap().unwrap();
+        assert_eq!(metadata.total_files_deleted, 4);
+        assert_eq!(metadata.earliest_commit_to_retain, "");
+        assert_eq!(
+            metadata.last_completed_commit_timestamp,
+            "20240707001303088"
+        );
+        for file in files {
+            assert!(!Path::new(&base_path).join(file).exists());
+        }
+
+        let table = Table::new(&base_path).await.unwrap();
+        let cleans = table.timeline.get_completed_cleans(false).await.unwrap();
+        assert_eq!(cleans.len(), 1);
+        assert!(cleans[0].completion_timestamp.is_none());
+        assert!(
+            Path::new(&base_path)
+                .join(format!(".hoodie/{}.clean.requested", cleans[0].timestamp))
+                .exists()
+        );
+        let saved = table.timeline.get_clean_metadata(&cleans[0]).await.unwrap();
+        assert_eq!(saved, metadata);
+        assert_eq!(
+            read_snapshot_sorted(&table, "_hoodie_record_key").await,
+            expected
+        );
+
+        // nothing left to clean
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        assert!(table.clean().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clean_keep_latest_commits_v9() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [(HudiCleanConfig::CommitsRetained.as_ref(), "3")];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let commits = &table.timeline.completed_commits;
+        let earliest_commit_to_retain = commits[commits.len() - 3].timestamp.clone();
+        let as_of_earliest = ReadOptions::new().with_hudi_option(
+            HudiReadConfig::AsOfTimestamp.as_ref(),
+            earliest_commit_to_retain.as_str(),
+        );
+        let expected_as_of_earliest = table.read(&as_of_earliest).await.unwrap();
+        let expected_latest = read_snapshot_sorted(&table, "_hoodie_record_key").await;
+
+        let metadata = table.clean().await.unwrap().unwrap();
+        assert_eq!(
+            metadata.earliest_commit_to_retain,
+            earliest_commit_to_retain
+        );
+        assert!(metadata.total_files_deleted > 0);
+        for path in metadata.iter_deleted_file_paths() {
+            assert!(!Path::new(&base_path).join(&path).exists());
+            let file_commit_ts = path.rsplit('_').next().unwrap();
+            assert!(file_commit_ts < earliest_commit_to_retain.as_str());
+        }
+
+        let table = Table::new(&base_path).await.unwrap();
+        let cleans = table.timeline.get_completed_cleans(false).await.unwrap();
+        assert_eq!(cleans.len(), 1);
+        let completion_ts = cleans[0].completion_timestamp.as_ref().unwrap();
+        assert!(completion_ts > &cleans[0].timestamp);
+        assert_eq!(
+            table.timeline.get_clean_metadata(&cleans[0]).await.unwrap(),
+            metadata
+        );
+        assert_eq!(
+            read_snapshot_sorted(&table, "_hoodie_record_key").await,
+            expected_latest
+        );
+        let actual_as_of_earliest = table.read(&as_of_earliest).await.unwrap();
+        assert_eq!(
+            actual_as_of_earliest
+                .iter()
+                .map(|b| b.num_rows())
+                .sum::<usize>(),
+            expected_as_of_earliest
+                .iter()
+                .map(|b| b.num_rows())
+                .sum::<usize>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clean_skips_file_groups_in_pending_compaction_and_clustering() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [(HudiCleanConfig::CommitsRetained.as_ref(), "3")];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let plan = table.plan_clean().await.unwrap();
+        let mut file_groups: Vec<(String, String)> = plan
+            .partition_to_files
+            .iter()
+            .flat_map(|(partition, files)| {
+                files.iter().map(move |f| {
+                    let file_name = f.rsplit('/').next().unwrap();
+                    let file_id = file_name.split('_').next().unwrap();
+                    (partition.clone(), file_id.to_string())
+                })
+            })
+            .collect();
+        file_groups.dedup();
+        assert!(file_groups.len() >= 2, "{file_groups:?}");
+        let (compacted, clustered) = (&file_groups[0], &file_groups[1]);
+
+        let timeline_dir = Path::new(&base_path).join(".hoodie/timeline");
+        let compaction_plan = HoodieCompactionPlan {
+            operations: Some(vec![HoodieCompactionOperation {
+                file_id: compacted.1.clone(),
+                partition_path: Some(compacted.0.clone()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        std::fs::write(
+            timeline_dir.join("20990101000000000.compaction.requested"),
+            compaction_plan.to_avro_bytes().unwrap(),
+        )
+        .unwrap();
+        let clustering_plan = HoodieRequestedReplaceMetadata {
+            operation_type: Some("CLUSTER".to_string()),
+            clustering_plan: Some(HoodieClusteringPlan {
+                input_groups: Some(vec![HoodieClusteringGroup {
+                    slices: vec![HoodieSliceInfo {
+                        file_id: clustered.1.clone(),
+                        partition_path: clustered.0.clone(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        std::fs::write(
+            timeline_dir.join("20990101000000001.clustering.requested"),
+            clustering_plan.to_avro_bytes().unwrap(),
+        )
+        .unwrap();
+
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        let pending_plan = table.plan_clean().await.unwrap();
+        let pending_files: Vec<&String> =
+            pending_plan.partition_to_files.values().flatten().collect();
+        for (partition, files) in &plan.partition_to_files {
+            for file in files {
+                let skipped = [compacted, clustered].iter().any(|(p, file_id)| {
+                    p == partition
+                        && file
+                            .rsplit('/')
+                            .next()
+                            .unwrap()
+                            .starts_with(file_id.as_str())
+                });
+                assert_eq!(pending_files.contains(&file), !skipped, "{file}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clean_keep_latest_by_hours_retains_recent_commits() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let options = [
+            (
+                HudiCleanConfig::CleanPolicy.as_ref(),
+                "KEEP_LATEST_BY_HOURS",
+            ),
+            (HudiCleanConfig::HoursRetained.as_ref(), "1"),
+        ];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        // all commits of the sample table are older than the retained hours
+        let plan = table.plan_clean().await.unwrap();
+        assert!(plan.is_empty());
+        assert!(table.clean().await.unwrap().is_none());
+        assert!(
+            table
+                .timeline
+                .get_completed_cleans(false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clean_not_enough_commits() {
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow();
+        let table = Table::new(&base_path).await.unwrap();
+        let plan = table.plan_clean().await.unwrap();
+        assert!(plan.is_empty());
+        assert!(plan.earliest_commit_to_retain.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clean_unsupported_with_metadata_table() {
+        let base_path = SampleTable::V9TxnsSimpleOverwrite.path_to_cow();
+        let table = Table::new(&base_path).await.unwrap();
+        let result = table.clean().await;
+        assert!(matches!(result.unwrap_err(), CoreError::Unsupported(_)));
+    }
+}
@@ -0,0 +1,691 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use paste::paste;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow_schema::{Schema, SchemaRef};
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::table::HudiTableConfig::{
+    ArchiveLogFolder, BaseFileFormat, Checksum, CreateSchema, DatabaseName, DropsPartitionFields,
+    IsHiveStylePartitioning, IsPartitionPathUrlencoded, KeyGeneratorClass, KeyGeneratorType,
+    OrderingFields, PartitionFields, PopulatesMetaFields, RecordKeyFields, TableName, TableType,
+    TableVersion, TimelineHistoryPath, TimelineLayoutVersion, TimelinePath, TimelineTimezone,
+};
+use crate::config::table::{
+    BaseFileFormatValue, HudiTableConfig, KeyGeneratorTypeValue, TableTypeValue,
+};
+use crate::config::util::{format_data_for_options, table_checksum};
+use crate::error::CoreError;
+use crate::metadata::meta_field::MetaField;
+use crate::schema::arrow_to_avro::{
+    arrow_schema_to_avro_schema_str, avro_record_name_and_namespace,
+};
+use crate::storage::Storage;
+use crate::table::Table;
+use crate::table::builder::{OptionResolver, TableBuilder, impl_with_options};
+use crate::table::validation::validate_configs;
+
+/// The table version of the tables created by [TableCreator] by default.
+pub const DEFAULT_TABLE_VERSION: isize = 9;
+
+/// User-provided Hudi options that are persisted in `hoodie.properties` when creating a table.
+const PERSISTED_OPTIONS: [HudiTableConfig; 6] = [
+    DatabaseName,
+    DropsPartitionFields,
+    IsHiveStylePartitioning,
+    IsPartitionPathUrlencoded,
+    PopulatesMetaFields,
+    TimelineTimezone,
+];
+
+/// Prefix of the key generator options that are persisted in `hoodie.properties`,
+/// e.g., the ones for [KeyGeneratorTypeValue::Timestamp].
+const KEYGEN_OPTIONS_PREFIX: &str = "hoodie.keygen.";
+
+/// Builder for creating a new Hudi table, i.e., writing its `hoodie.properties` and
+/// the directory layout of its timeline.
+///
+/// # Example
+///
+/// ```ignore
+/// let table = TableCreator::new("/tmp/trips", "trips", schema)
+///     .with_record_key_fields(["id"])
+///     .with_partition_fields(["city"])
+///     .with_ordering_fields(["ts"])
+///     .create()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableCreator {
+    option_resolver: OptionResolver,
+    table_name: String,
+    schema: SchemaRef,
+    table_type: TableTypeValue,
+    record_key_fields: Vec<String>,
+    partition_fields: Vec<String>,
+    ordering_fields: Vec<String>,
+    key_generator_type: Option<KeyGeneratorTypeValue>,
+    base_file_format: BaseFileFormatValue,
+    table_version: isize,
+}
+
+impl_with_options!(
+    TableCreator,
+    hudi_options,
+    hudi_option,
+    storage_options,
+    storage_option,
+    options,
+    option
+);
+
+impl TableCreator {
+    /// Create a table creator for a Copy-on-Write table with the Parquet base file format
+    /// and the table version [DEFAULT_TABLE_VERSION].
+    ///
+    /// The schema is the data schema without the meta fields.
+    pub fn new(base_uri: &str, table_name: &str, schema: SchemaRef) -> Self {
+        Self {
+            option_resolver: OptionResolver::new(base_uri),
+            table_name: table_name.to_string(),
+            schema,
+            table_type: TableTypeValue::CopyOnWrite,
+            record_key_fields: Vec::new(),
+            partition_fields: Vec::new(),
+            ordering_fields: Vec::new(),
+            key_generator_type: None,
+            base_file_format: BaseFileFormatValue::Parquet,
+            table_version: DEFAULT_TABLE_VERSION,
+        }
+    }
+
+    pub fn with_table_type(mut self, table_type: TableTypeValue) -> Self {
+        self.table_type = table_type;
+        self
+    }
+
+    pub fn with_record_key_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.record_key_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the partition fields.
+    ///
+    /// For [KeyGeneratorTypeValue::Custom], each field is in the form of
+    /// `<field>:<SIMPLE|TIMESTAMP>`.
+    pub fn with_partition_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.partition_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_ordering_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ordering_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the key generator type. If not set, it is inferred from the record key and
+    /// partition fields by [KeyGeneratorTypeValue::infer].
+    pub fn with_key_generator_type(mut self, key_generator_type: KeyGeneratorTypeValue) -> Self {
+        self.key_generator_type = Some(key_generator_type);
+        self
+    }
+
+    pub fn with_base_file_format(mut self, base_file_format: BaseFileFormatValue) -> Self {
+        self.base_file_format = base_file_format;
+        self
+    }
+
+    pub fn with_table_version(mut self, table_version: isize) -> Self {
+        self.table_version = table_version;
+        self
+    }
+
+    /// Create the table and return it as a [Table].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table already exists at the base URI, or the table properties
+    /// are invalid, e.g., a field is not found in the schema or the key generator type does not
+    /// match the partition fields.
+    pub async fn create(self) -> Result<Table> {
+        let mut option_resolver = self.option_resolver.clone();
+        option_resolver.resolve_user_provided_options();
+        option_resolver.resolve_env_vars();
+
+        self.validate_fields()?;
+        let properties = self.table_properties(&option_resolver)?;
+
+        let mut configs = properties.clone();
+        configs.push((
+            HudiTableConfig::BasePath.as_ref().to_string(),
+            option_resolver.base_uri.clone(),
+        ));
+        let hudi_configs = HudiConfigs::new(configs);
+        validate_configs(&hudi_configs)?;
+
+        let storage = Storage::new(
+            Arc::new(option_resolver.storage_options.clone()),
+            Arc::new(hudi_configs),
+        )?;
+        let table_exists = storage
+            .list_files(Some(".hoodie"))
+            .await?
+            .iter()
+            .any(|f| f.name == "hoodie.properties");
+        if table_exists {
+            return Err(CoreError::InvalidValue(format!(
+                "Table already exists at {}",
+                option_resolver.base_uri
+            )));
+        }
+
+        for dir in self.layout_dirs() {
+            storage.create_dir(dir).await?;
+        }
+        storage
+            .put_file_data(
+                ".hoodie/hoodie.properties",
+                format_data_for_options(properties),
+            )
+            .await?;
+
+        let OptionResolver {
+            base_uri,
+            hudi_options,
+            storage_options,
+            options,
+        } = self.option_resolver;
+        TableBuilder::from_base_uri(&base_uri)
+            .with_hudi_options(hudi_options)
+            .with_storage_options(storage_options)
+            .with_options(options)
+            .build()
+            .await
+    }
+
+    fn key_generator_type(&self) -> KeyGeneratorTypeValue {
+        self.key_generator_type.clone().unwrap_or_else(|| {
+            KeyGeneratorTypeValue::infer(&self.record_key_fields, &self.partition_fields)
+        })
+    }
+
+    fn validate_fields(&self) -> Result<()> {
+        if self.table_name.is_empty() {
+            return Err(CoreError::InvalidValue(
+                "Table name must not be empty".to_string(),
+            ));
+        }
+
+        let key_generator_type = self.key_generator_type();
+        let mut partition_source_fields = Vec::with_capacity(self.partition_fields.len());
+        for field in &self.partition_fields {
+            let source_field = match key_generator_type {
+                KeyGeneratorTypeValue::Custom => match field.split_once(':') {
+                    Some((name, "SIMPLE" | "TIMESTAMP")) => name,
+                    _ => {
+                        return Err(CoreError::InvalidValue(format!(
+                            "Partition field '{field}' must be in the form of \
+                             <field>:<SIMPLE|TIMESTAMP> for key generator type {}",
+                            key_generator_type.as_ref()
+                        )));
+                    }
+                },
+                _ => field.as_str(),
+            };
+            partition_source_fields.push(source_field);
+        }
+
+        let fields_to_check = [
+            (
+                RecordKeyFields,
+                self.record_key_fields.iter().map(String::as_str).collect(),
+            ),
+            (PartitionFields, partition_source_fields),
+            (
+                OrderingFields,
+                self.ordering_fields
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+        for (config, fields) in fields_to_check {
+            for field in fields {
+                if self.schema.field_with_name(field).is_err() {
+                    return Err(CoreError::InvalidValue(format!(
+                        "Field '{field}' in {} is not found in the schema",
+                        config.as_ref()
+                    )));
+                }
+            }
+        }
+
+        let num_partition_fields = self.partition_fields.len();
+        let is_valid = match key_generator_type {
+            KeyGeneratorTypeValue::NonPartition => num_partition_fields == 0,
+            KeyGeneratorTypeValue::Simple => {
+                num_partition_fields == 1 && self.record_key_fields.len() <= 1
+            }
+            KeyGeneratorTypeValue::Timestamp => num_partition_fields == 1,
+            KeyGeneratorTypeValue::Complex | KeyGeneratorTypeValue::Custom => true,
+        };
+        if !is_valid {
+            return Err(CoreError::InvalidValue(format!(
+                "Key generator type {} does not support {} record key field(s) and {num_partition_fields} partition field(s)",
+                key_generator_type.as_ref(),
+                self.record_key_fields.len(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// The table properties in `hoodie.properties`, as written by Hudi Java for the table version.
+    fn table_properties(&self, option_resolver: &OptionResolver) -> Result<Vec<(String, String)>> {
+        let data_fields = self
+            .schema
+            .fields()
+            .iter()
+            .filter(|f| MetaField::from_str(f.name()).is_err())
+            .cloned()
+            .collect::<Vec<_>>();
+        let (record_name, namespace) = avro_record_name_and_namespace(&self.table_name);
+        let create_schema =
+            arrow_schema_to_avro_schema_str(&Schema::new(data_fields), &record_name, &namespace)?;
+
+        let mut properties = vec![
+            (TableName.as_ref(), self.table_name.clone()),
+            (TableType.as_ref(), self.table_type.as_ref().to_string()),
+            (TableVersion.as_ref(), self.table_version.to_string()),
+            (CreateSchema.as_ref(), create_schema),
+            (
+                BaseFileFormat.as_ref(),
+                self.base_file_format.as_ref().to_uppercase(),
+            ),
+            (ArchiveLogFolder.as_ref(), "archived".to_string()),
+        ];
+        if !self.record_key_fields.is_empty() {
+            properties.push((RecordKeyFields.as_ref(), self.record_key_fields.join(",")));
+        }
+        if !self.partition_fields.is_empty() {
+            properties.push((PartitionFields.as_ref(), self.partition_fields.join(",")));
+        }
+
+        let key_generator_type = self.key_generator_type();
+        if self.table_version >= 8 {
+            properties.push((
+                KeyGeneratorType.as_ref(),
+                key_generator_type.as_ref().to_string(),
+            ));
+        } else {
+            properties.push((
+                KeyGeneratorClass.as_ref(),
+                key_generator_type.class_name().to_string(),
+            ));
+        }
+
+        if !self.ordering_fields.is_empty() {
+            // Table versions below 9 use the deprecated alias for the ordering fields
+            let ordering_fields_key = if self.table_version >= 9 {
+                OrderingFields.as_ref()
+            } else {
+                "hoodie.table.precombine.field"
+            };
+            properties.push((ordering_fields_key, self.ordering_fields.join(",")));
+        }
+
+        if self.table_version >= 8 {
+            let merge_mode = if self.ordering_fields.is_empty() {
+                "COMMIT_TIME_ORDERING"
+            } else {
+                "EVENT_TIME_ORDERING"
+            };
+            properties.extend([
+                (TimelineLayoutVersion.as_ref(), "2".to_string()),
+                (TimelinePath.as_ref(), "timeline".to_string()),
+                (TimelineHistoryPath.as_ref(), "history".to_string()),
+                (
+                    "hoodie.table.initial.version",
+                    self.table_version.to_string(),
+                ),
+                ("hoodie.record.merge.mode", merge_mode.to_string()),
+            ]);
+        } else {
+            properties.push((TimelineLayoutVersion.as_ref(), "1".to_string()));
+        }
+        if self.table_version == 8 {
+            let merge_strategy_id = if self.ordering_fields.is_empty() {
+                "ce9acb64-bde0-424c-9b91-f6ebba25356d"
+            } else {
+                "eeb8d96f-b1e4-49fd-bbf8-28ac514178e5"
+            };
+            properties.extend([
+                (
+                    "hoodie.record.merge.strategy.id",
+                    merge_strategy_id.to_string(),
+                ),
+                (
+                    "hoodie.compaction.payload.class",
+                    "org.apache.hudi.common.model.DefaultHoodieRecordPayload".to_string(),
+                ),
+            ]);
+        }
+        if self.table_version >= 9 {
+            properties.push(("hoodie.table.format", "native".to_string()));
+        }
+
+        let hudi_options = &option_resolver.hudi_options;
+        for config in &PERSISTED_OPTIONS {
+            if let Some(value) = hudi_options.get(config.as_ref()) {
+                properties.push((config.as_ref(), value.clone()));
+            }
+        }
+        for (key, value) in hudi_options {
+            if key.starts_with(KEYGEN_OPTIONS_PREFIX) {
+                properties.push((key.as_str(), value.clone()));
+            }
+        }
+        for config in &[
+            DropsPartitionFields,
+            IsHiveStylePartitioning,
+            IsPartitionPathUrlencoded,
+        ] {
+            if !hudi_options.contains_key(config.as_ref()) {
+                properties.push((config.as_ref(), "false".to_string()));
+            }
+        }
+
+        let database_name = hudi_options
+            .get(DatabaseName.as_ref())
+            .map_or("", String::as_str);
+        let checksum = table_checksum(database_name, &self.table_name);
+        properties.push((Checksum.as_ref(), checksum.to_string()));
+
+        Ok(properties
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect())
+    }
+
+    /// The directories created under `.hoodie/`, as Hudi Java does for the table version.
+    fn layout_dirs(&self) -> Vec<&'static str> {
+        let mut dirs = vec![
+            ".hoodie/.temp",
+            ".hoodie/.aux/.bootstrap/.partitions",
+            ".hoodie/.aux/.bootstrap/.fileids",
+            ".hoodie/.schema",
+        ];
+        if self.table_version >= 8 {
+            dirs.push(".hoodie/timeline/history");
+        } else {
+            dirs.push(".hoodie/archived");
+        }
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::util::parse_data_for_options;
+    use crate::table::ReadOptions;
+    use arrow_schema::{DataType, Field};
+    use bytes::Bytes;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("city", DataType::Utf8, true),
+            Field::new("ts", DataType::Int64, true),
+        ]))
+    }
+
+    fn read_properties(base_path: &Path) -> HashMap<String, String> {
+        let data = std::fs::read(base_path.join(".hoodie/hoodie.properties")).unwrap();
+        parse_data_for_options(&Bytes::from(data), "=").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_v9_table() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_uri, "trips", test_schema())
+            .with_table_type(TableTypeValue::MergeOnRead)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_hudi_option(DatabaseName.as_ref(), "default")
+            .create()
+            .await
+            .unwrap();
+
+        assert_eq!(table.table_name(), "trips");
+        assert!(table.is_mor());
+        assert!(table.get_timeline().completed_commits.is_empty());
+        let schema = table.get_schema().await.unwrap();
+        let field_names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(field_names, ["id", "name", "city", "ts"]);
+        assert!(table.read(&ReadOptions::new()).await.unwrap().is_empty());
+
+        let properties = read_properties(temp_dir.path());
+        assert_eq!(properties[TableVersion.as_ref()], "9");
+        assert_eq!(properties["hoodie.table.initial.version"], "9");
+        assert_eq!(properties[KeyGeneratorType.as_ref()], "SIMPLE");
+        assert_eq!(properties[OrderingFields.as_ref()], "ts");
+        assert_eq!(properties[TimelineLayoutVersion.as_ref()], "2");
+        assert_eq!(
+            properties["hoodie.record.merge.mode"],
+            "EVENT_TIME_ORDERING"
+        );
+        assert_eq!(properties["hoodie.table.format"], "native");
+        assert_eq!(properties[BaseFileFormat.as_ref()], "PARQUET");
+        assert_eq!(
+            properties[Checksum.as_ref()],
+            table_checksum("default", "trips").to_string()
+        );
+        assert!(!properties.contains_key(KeyGeneratorClass.as_ref()));
+        assert!(!properties.contains_key("hoodie.record.merge.strategy.id"));
+        for dir in [
+            ".hoodie/.temp",
+            ".hoodie/.aux/.bootstrap/.partitions",
+            ".hoodie/.aux/.bootstrap/.fileids",
+            ".hoodie/.schema",
+            ".hoodie/timeline/history",
+        ] {
+            assert!(temp_dir.path().join(dir).is_dir(), "{dir} should exist");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_v8_table() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_uri, "trips", test_schema())
+            .with_record_key_fields(["id", "name"])
+            .with_partition_fields(["city"])
+            .with_table_version(8)
+            .create()
+            .await
+            .unwrap();
+        assert!(!table.is_mor());
+
+        let properties = read_properties(temp_dir.path());
+        assert_eq!(properties[TableVersion.as_ref()], "8");
+        assert_eq!(properties[KeyGeneratorType.as_ref()], "COMPLEX");
+        assert_eq!(
+            properties["hoodie.record.merge.mode"],
+            "COMMIT_TIME_ORDERING"
+        );
+        assert_eq!(
+            properties["hoodie.record.merge.strategy.id"],
+            "ce9acb64-bde0-424c-9b91-f6ebba25356d"
+        );
+        assert_eq!(
+            properties[Checksum.as_ref()],
+            table_checksum("", "trips").to_string()
+        );
+        assert!(!properties.contains_key("hoodie.table.format"));
+        assert!(temp_dir.path().join(".hoodie/timeline/history").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_create_v6_table() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_uri, "trips", test_schema())
+            .with_record_key_fields(["id"])
+            .with_ordering_fields(["ts"])
+            .with_table_version(6)
+            .with_hudi_option(IsHiveStylePartitioning.as_ref(), "true")
+            .create()
+            .await
+            .unwrap();
+        assert!(
+            table
+                .get_partition_schema()
+                .await
+                .unwrap()
+                .fields()
+                .is_empty()
+        );
+
+        let properties = read_properties(temp_dir.path());
+        assert_eq!(
+            properties[KeyGeneratorClass.as_ref()],
+            "org.apache.hudi.keygen.NonpartitionedKeyGenerator"
+        );
+        assert_eq!(properties["hoodie.table.precombine.field"], "ts");
+        assert_eq!(properties[TimelineLayoutVersion.as_ref()], "1");
+        assert_eq!(properties[IsHiveStylePartitioning.as_ref()], "true");
+        assert_eq!(properties[IsPartitionPathUrlencoded.as_ref()], "false");
+        assert!(!properties.contains_key(KeyGeneratorType.as_ref()));
+        assert!(!properties.contains_key(PartitionFields.as_ref()));
+        assert!(!properties.contains_key(TimelinePath.as_ref()));
+        assert!(temp_dir.path().join(".hoodie/archived").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_create_table_persists_keygen_options() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        TableCreator::new(base_uri, "trips", test_schema())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["ts:TIMESTAMP"])
+            .with_key_generator_type(KeyGeneratorTypeValue::Custom)
+            .with_hudi_option(
+                "hoodie.keygen.timebased.timestamp.type",
+                "EPOCHMILLISECONDS",
+            )
+            .with_hudi_option("hoodie.keygen.timebased.output.dateformat", "yyyy/MM/dd")
+            .create()
+            .await
+            .unwrap();
+
+        let properties = read_properties(temp_dir.path());
+        assert_eq!(properties[KeyGeneratorType.as_ref()], "CUSTOM");
+        assert_eq!(properties[PartitionFields.as_ref()], "ts:TIMESTAMP");
+        assert_eq!(
+            properties["hoodie.keygen.timebased.timestamp.type"],
+            "EPOCHMILLISECONDS"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_table_fails_if_exists() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        let creator =
+            TableCreator::new(base_uri, "trips", test_schema()).with_record_key_fields(["id"]);
+        creator.clone().create().await.unwrap();
+        let err = creator.create().await.unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_create_table_with_invalid_properties() {
+        let temp_dir = tempdir().unwrap();
+        let base_uri = temp_dir.path().to_str().unwrap();
+        let creator = || TableCreator::new(base_uri, "trips", test_schema());
+
+        let err = creator()
+            .with_record_key_fields(["uuid"])
+            .create()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'uuid'"), "{err}");
+
+        let err = creator()
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_key_generator_type(KeyGeneratorTypeValue::NonPartition)
+            .create()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::InvalidValue(_)), "{err}");
+
+        let err = creator()
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_key_generator_type(KeyGeneratorTypeValue::Custom)
+            .create()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("<field>:<SIMPLE|TIMESTAMP>"),
+            "{err}"
+        );
+
+        let err = creator()
+            .with_record_key_fields(["id"])
+            .with_table_version(7)
+            .create()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::Unsupported(_)), "{err}");
+
+        let err = creator()
+            .with_record_key_fields(["id"])
+            .with_base_file_format(BaseFileFormatValue::HFile)
+            .create()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::Unsupported(_)), "{err}");
+
+        assert!(!temp_dir.path().join(".hoodie").exists());
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Discovery of the Hudi tables stored under a root URI, e.g., of a data lake.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future::try_join_all;
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::table::HudiTableConfig::{DatabaseName, TableName};
+use crate::config::util::parse_data_for_options;
+use crate::storage::Storage;
+use crate::storage::util::{join_url_segments, parse_uri};
+use crate::table::builder::OptionResolver;
+
+/// The path of `hoodie.properties` relative to the base path of a table.
+const PROPERTIES_PATH: &str = ".hoodie/hoodie.properties";
+
+/// A Hudi table found by [discover_tables].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredTable {
+    /// The base URI of the table.
+    pub base_uri: String,
+    /// The [DatabaseName] of the table, which defaults to `default`.
+    pub database_name: String,
+    /// The [TableName] of the table.
+    pub table_name: String,
+}
+
+/// Discover the Hudi tables under `root_uri` by listing its directories for
+/// the ones with `.hoodie/hoodie.properties`, including `root_uri` itself.
+///
+/// The directories of the tables found are not listed further, and neither
+/// are the hidden ones whose names start with `.` or `_`. The directories of
+/// the same depth are listed concurrently.
+///
+/// The `options` are resolved as the storage options of [crate::table::Table],
+/// including the cloud storage env vars. The tables are sorted by their base
+/// URIs.
+pub async fn discover_tables<I, K, V>(root_uri: &str, options: I) -> Result<Vec<DiscoveredTable>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let mut option_resolver = OptionResolver::new_with_options(root_uri, options);
+    option_resolver.resolve_user_provided_options();
+    option_resolver.resolve_env_vars();
+    let storage = Storage::new(
+        Arc::new(option_resolver.storage_options),
+        Arc::new(HudiConfigs::new(option_resolver.hudi_options)),
+    )?;
+    let root_url = parse_uri(root_uri)?;
+
+    let mut tables = Vec::new();
+    let mut dirs = vec![String::new()];
+    while !dirs.is_empty() {
+        let listed = try_join_all(dirs.iter().map(|dir| list_dir(&storage, dir))).await?;
+        let mut subdirs = Vec::new();
+        for (dir, listed) in dirs.iter().zip(listed) {
+            match listed {
+                ListedDir::Table(properties) => {
+                    let configs = HudiConfigs::new(properties);
+                    let database_name: String = configs.get_or_default(DatabaseName).into();
+                    let table_name: String = match configs.try_get(TableName)? {
+                        Some(name) => name.into(),
+                        None => dir.rsplit('/').next().unwrap_or_default().to_string(),
+                    };
+                    tables.push(DiscoveredTable {
+                        base_uri: join_url_segments(&root_url, &[dir])?.to_string(),
+                        database_name,
+                        table_name,
+                    });
+                }
+                ListedDir::Subdirs(names) => subdirs.extend(names.into_iter().map(|name| {
+                    if dir.is_empty() {
+                        name
+                    } else {
+                        format!("{dir}/{name}")
+                    }
+                })),
+            }
+        }
+        dirs = subdirs;
+    }
+    tables.sort_by(|a, b| a.base_uri.cmp(&b.base_uri));
+    Ok(tables)
+}
+
+enum ListedDir {
+    /// The `hoodie.properties` of the table in the directory.
+    Table(HashMap<String, String>),
+    /// The names of the subdirectories to list.
+    Subdirs(Vec<String>),
+}
+
+async fn list_dir(storage: &Storage, dir: &str) -> Result<ListedDir> {
+    let subdir = (!dir.is_empty()).then_some(dir);
+    let names = storage.list_dirs(subdir).await?;
+    if names.iter().any(|name| name == ".hoodie") {
+        let path = match subdir {
+            Some(dir) => format!("{dir}/{PROPERTIES_PATH}"),
+            None => PROPERTIES_PATH.to_string(),
+        };
+        // A `.hoodie` directory without `hoodie.properties` is not a table.
+        if let Ok(data) = storage.get_file_data(&path).await {
+            return Ok(ListedDir::Table(parse_data_for_options(&data, "=")?));
+        }
+    }
+    Ok(ListedDir::Subdirs(
+        names
+            .into_iter()
+            .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::util::empty_options;
+    use crate::table::creator::TableCreator;
+    use arrow_schema::{DataType, Field, Schema};
+
+    async fn create_table(base_path: &str, database_name: Option<&str>, table_name: &str) {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let mut creator = TableCreator::new(base_path, table_name, schema)
+            .with_record_key_fields(["id"])
+            .with_ordering_fields(["id"]);
+        if let Some(database_name) = database_name {
+            creator = creator.with_hudi_option(DatabaseName.as_ref(), database_name);
+        }
+        creator.create().await.unwrap();
+    }
+
+    fn table(base_uri: &str, database_name: &str, table_name: &str) -> DiscoveredTable {
+        DiscoveredTable {
+            base_uri: base_uri.to_string(),
+            database_name: database_name.to_string(),
+            table_name: table_name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discover_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        create_table(&format!("{root}/sales/orders"), Some("sales"), "orders").await;
+        create_table(
+            &format!("{root}/sales/nested/items"),
+            Some("sales"),
+            "items",
+        )
+        .await;
+        create_table(&format!("{root}/trips"), None, "trips").await;
+        // Not discovered: hidden directories, and tables nested in tables.
+        create_table(&format!("{root}/.trash/trips"), None, "old_trips").await;
+        create_table(&format!("{root}/_tmp/trips"), None, "tmp_trips").await;
+        create_table(&format!("{root}/trips/nested"), None, "nested").await;
+        std::fs::create_dir_all(format!("{root}/empty/.hoodie")).unwrap();
+
+        let root_url = parse_uri(root).unwrap();
+        let uri = |path: &str| join_url_segments(&root_url, &[path]).unwrap().to_string();
+        let tables = discover_tables(root, empty_options()).await.unwrap();
+        assert_eq!(
+            tables,
+            vec![
+                table(&uri("sales/nested/items"), "sales", "items"),
+                table(&uri("sales/orders"), "sales", "orders"),
+                table(&uri("trips"), "default", "trips"),
+            ]
+        );
+
+        let tables = discover_tables(&format!("{root}/trips"), empty_options())
+            .await
+            .unwrap();
+        assert_eq!(tables, vec![table(&uri("trips"), "default", "trips")]);
+
+        let tables = discover_tables(&format!("{root}/empty"), empty_options())
+            .await
+            .unwrap();
+        assert!(tables.is_empty());
+    }
+}
@@ -21,12 +21,13 @@
 
 use crate::Result;
 use crate::expr::ExprOperator;
-use crate::expr::filter::{Filter, SchemableFilter};
+use crate::expr::filter::{FilterExpr, SchemableFilter};
 use crate::statistics::{ColumnStatistics, StatisticsContainer};
 
-use arrow_array::{ArrayRef, Datum};
+use arrow_array::{Array, ArrayRef, Datum, StringArray};
+use arrow_cast::cast;
 use arrow_ord::cmp;
-use arrow_schema::Schema;
+use arrow_schema::{DataType, Schema};
 use std::collections::HashSet;
 
 /// A file-level pruner that filters files based on column statistics.
@@ -36,8 +37,9 @@ use std::collections::HashSet;
 /// statistics prove that no rows in the file can match the predicate.
 #[derive(Debug, Clone)]
 pub struct FilePruner {
-    /// Filters that apply to non-partition columns
-    and_filters: Vec<SchemableFilter>,
+    /// Filter expressions in negation normal form, where `None` is a filter on a
+    /// partition column or a column not in the table schema, which cannot prune files.
+    and_filters: Vec<FilterExpr<Option<SchemableFilter>>>,
 }
 
 impl FilePruner {
@@ -50,7 +52,7 @@ impl FilePruner {
     /// * `table_schema` - The table's data schema
     /// * `partition_schema` - The partition schema (filters on these columns are excluded)
     pub fn new(
-        and_filters: &[Filter],
+        and_filters: &[FilterExpr],
         table_schema: &Schema,
         partition_schema: &Schema,
     ) -> Result<Self> {
@@ -61,11 +63,19 @@ impl FilePruner {
             .map(|f| f.name().as_str())
             .collect();
 
-        // Only keep filters on non-partition columns that exist in the table schema
-        let and_filters: Vec<SchemableFilter> = and_filters
+        // Only resolve filters on non-partition columns that exist in the table schema,
+        // after pushing NOT down to the filters so that each can prune on its own
+        let and_filters: Vec<FilterExpr<Option<SchemableFilter>>> = and_filters
             .iter()
-            .filter(|filter| !partition_columns.contains(filter.field.as_str()))
-            .filter_map(|filter| SchemableFilter::try_from((filter.clone(), table_schema)).ok())
+            .map(|expr| {
+                expr.to_negation_normal_form().map(&mut |filter| {
+                    if partition_columns.contains(filter.field.as_str()) {
+                        return None;
+                    }
+                    SchemableFilter::try_from((filter.clone(), table_schema)).ok()
+                })
+            })
+            .filter(|expr| !matches!(expr, FilterExpr::Filter(None)))
             .collect();
 
         Ok(FilePruner { and_filters })
@@ -86,7 +96,9 @@ impl FilePruner {
     /// Returns `true` if the file should be included based on its statistics.
     ///
     /// A file is included if ANY of its rows MIGHT match all the filters.
-    /// A file is excluded (pruned) only if we can prove that NO rows can match.
+    /// A file is excluded (pruned) only if we can prove that NO rows can match,
+    /// i.e., an AND-ed filter expression cannot match, where an `OR` cannot match
+    /// only if none of its expressions can.
     ///
     /// If statistics are missing or incomplete, the file is included (safe default).
     pub fn should_include(&self, stats: &StatisticsContainer) -> bool {
@@ -95,32 +107,49 @@ impl FilePruner {
             return true;
         }
 
-        // All filters must pass (AND semantics)
-        // If any filter definitively excludes the file, return false
-        for filter in &self.and_filters {
-            let col_name = filter.field.name();
+        let mut may_match = |filter: &Option<SchemableFilter>| {
+            let Some(filter) = filter else {
+                return true;
+            };
 
             // Get column statistics. When using StatisticsContainer::from_parquet_metadata(),
             // all schema columns will have an entry. However, stats may come from other sources
             // (e.g., manually constructed), so we handle missing columns defensively.
-            let Some(col_stats) = stats.columns.get(col_name) else {
+            let Some(col_stats) = stats.columns.get(filter.field.name()) else {
                 // No stats for this column, cannot prune - include the file
-                continue;
+                return true;
             };
 
-            // Check if this filter can prune the file
-            if self.can_prune_by_filter(filter, col_stats) {
-                return false; // File can be pruned
-            }
-        }
+            !self.can_prune_by_filter(filter, col_stats, stats.num_rows)
+        };
 
-        true // File should be included
+        // All filter expressions must pass (AND semantics)
+        self.and_filters
+            .iter()
+            .all(|expr| expr.may_match(&mut may_match))
     }
 
     /// Determines if a file can be pruned based on a single filter and column stats.
     ///
     /// Returns `true` if the file can definitely be pruned (no rows can match).
-    fn can_prune_by_filter(&self, filter: &SchemableFilter, col_stats: &ColumnStatistics) -> bool {
+    fn can_prune_by_filter(
+        &self,
+        filter: &SchemableFilter,
+        col_stats: &ColumnStatistics,
+        num_rows: Option<i64>,
+    ) -> bool {
+        match filter.operator {
+            ExprOperator::IsNull => {
+                // Prune if: no nulls
+                return col_stats.null_count == Some(0);
+            }
+            ExprOperator::IsNotNull => {
+                // Prune if: all rows are nulls
+                return col_stats.null_count.is_some() && col_stats.null_count == num_rows;
+            }
+            _ => {}
+        }
+
         // Multi-value operators not yet supported for file-level pruning
         // TODO: support IN/NOT IN by checking if all values are outside the min/max range
         if filter.operator.is_multi_value() {
@@ -161,10 +190,64 @@ impl FilePruner {
                 // Prune if: max < value
                 self.can_prune_gte(&filter_value, max)
             }
+            ExprOperator::Like => {
+                // Prune if: no values start with the literal prefix of the pattern
+                self.can_prune_like(&filter_value, min, max)
+            }
+            // Prune for NOT LIKE would need to prove all values match the pattern
+            ExprOperator::NotLike => false,
             ExprOperator::In | ExprOperator::NotIn => {
                 unreachable!("Multi-value operators are short-circuited above")
             }
+            ExprOperator::IsNull | ExprOperator::IsNotNull => {
+                unreachable!("Null checks are short-circuited above")
+            }
+        }
+    }
+
+    /// Prune for `col LIKE pattern`: prune if max < prefix, or min > prefix without
+    /// starting with it, where prefix is the literal prefix of the pattern before
+    /// any wildcard or escape.
+    fn can_prune_like(
+        &self,
+        pattern: &ArrayRef,
+        min: &Option<ArrayRef>,
+        max: &Option<ArrayRef>,
+    ) -> bool {
+        let Some(pattern) = Self::as_string(pattern) else {
+            return false;
+        };
+        let prefix = pattern.split(['%', '_', '\\']).next().unwrap_or_default();
+        if prefix.is_empty() {
+            return false;
         }
+
+        let max_lt_prefix = max
+            .as_ref()
+            .and_then(Self::as_string)
+            .is_some_and(|max| max.as_str() < prefix);
+        // Values starting with the prefix are less than any value greater than the
+        // prefix that does not start with it.
+        let min_gt_prefix = min
+            .as_ref()
+            .and_then(Self::as_string)
+            .is_some_and(|min| min.as_str() > prefix && !min.starts_with(prefix));
+
+        max_lt_prefix || min_gt_prefix
+    }
+
+    /// Returns the first value of a string array, which compares as bytes like
+    /// the min/max statistics do.
+    fn as_string(array: &ArrayRef) -> Option<String> {
+        if !matches!(
+            array.data_type(),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+        ) {
+            return None;
+        }
+        let array = cast(array, &DataType::Utf8).ok()?;
+        let array = array.as_any().downcast_ref::<StringArray>()?;
+        (!array.is_empty() && array.is_valid(0)).then(|| array.value(0).to_string())
     }
 
     /// Prune for `col = value`: prune if value < min OR value > max
@@ -276,7 +359,8 @@ impl FilePruner {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow_array::{Int64Array, StringArray};
+    use crate::expr::filter::{Filter, col};
+    use arrow_array::Int64Array;
     use arrow_schema::{DataType, Field};
     use std::sync::Arc;
 
@@ -302,6 +386,7 @@ mod tests {
                 data_type: DataType::Int64,
                 min_value: Some(Arc::new(Int64Array::from(vec![min])) as ArrayRef),
                 max_value: Some(Arc::new(Int64Array::from(vec![max])) as ArrayRef),
+                null_count: None,
             },
         );
         stats
@@ -316,6 +401,7 @@ mod tests {
                 data_type: DataType::Utf8,
                 min_value: Some(Arc::new(StringArray::from(vec![min])) as ArrayRef),
                 max_value: Some(Arc::new(StringArray::from(vec![max])) as ArrayRef),
+                null_count: None,
             },
         );
         stats
@@ -336,7 +422,11 @@ mod tests {
         let partition_schema = create_partition_schema();
 
         // Filter on partition column should be excluded
-        let filters = vec![Filter::try_from(("date", "=", "2024-01-01")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![
+            Filter::try_from(("date", "=", "2024-01-01"))
+                .unwrap()
+                .into(),
+        ];
 
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
         assert!(pruner.is_empty()); // Partition column filter should be excluded
@@ -348,7 +438,7 @@ mod tests {
         let partition_schema = create_partition_schema();
 
         // Filter on non-partition column should be kept
-        let filters = vec![Filter::try_from(("id", ">", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", ">", "50")).unwrap().into()];
 
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
         assert!(!pruner.is_empty());
@@ -359,7 +449,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "=", "5")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "=", "5")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id = 5. Should prune (5 < 10).
@@ -372,7 +462,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "=", "200")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "=", "200")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id = 200. Should prune (200 > 100).
@@ -385,7 +475,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id = 50. Should include (10 <= 50 <= 100).
@@ -398,7 +488,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "!=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "!=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=50, max=50. Filter: id != 50. Should prune (all values are 50).
@@ -411,7 +501,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "!=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "!=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id != 50. Should include (has other values).
@@ -424,7 +514,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "<", "10")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "<", "10")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id < 10. Should prune (min >= 10).
@@ -437,7 +527,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "<", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "<", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id < 50. Should include (some values < 50).
@@ -450,7 +540,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "<=", "5")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "<=", "5")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id <= 5. Should prune (min > 5).
@@ -463,7 +553,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", ">", "100")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", ">", "100")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id > 100. Should prune (max <= 100).
@@ -476,7 +566,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", ">", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", ">", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id > 50. Should include (some values > 50).
@@ -489,7 +579,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", ">=", "150")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", ">=", "150")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id >= 150. Should prune (max < 150).
@@ -502,7 +592,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "<=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "<=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id <= 50. Should include (some values <= 50).
@@ -515,7 +605,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", ">=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", ">=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min=10, max=100. Filter: id >= 50. Should include (some values >= 50).
@@ -528,7 +618,8 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("name", "=", "zebra")).unwrap()];
+        let filters: Vec<FilterExpr> =
+            vec![Filter::try_from(("name", "=", "zebra")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats: min="apple", max="banana". Filter: name = "zebra". Should prune.
@@ -546,13 +637,14 @@ mod tests {
         let partition_schema = Schema::empty();
 
         // IN operator should not prune (conservative approach)
-        let filters = vec![
+        let filters: Vec<FilterExpr> = vec![
             Filter::new(
                 "id".to_string(),
                 ExprOperator::In,
                 vec!["5".to_string(), "10".to_string()],
             )
-            .unwrap(),
+            .unwrap()
+            .into(),
         ];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
@@ -561,13 +653,14 @@ mod tests {
         assert!(pruner.should_include(&stats));
 
         // NOT IN operator should also not prune
-        let filters = vec![
+        let filters: Vec<FilterExpr> = vec![
             Filter::new(
                 "id".to_string(),
                 ExprOperator::NotIn,
                 vec!["5".to_string(), "10".to_string()],
             )
-            .unwrap(),
+            .unwrap()
+            .into(),
         ];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
         assert!(pruner.should_include(&stats));
@@ -578,7 +671,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Stats for different column - should include (cannot prune without stats)
@@ -591,7 +684,7 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![Filter::try_from(("id", "=", "50")).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(("id", "=", "50")).unwrap().into()];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
         // Column exists but has no min/max (e.g., Parquet file with statistics disabled)
@@ -603,6 +696,7 @@ mod tests {
                 data_type: DataType::Int64,
                 min_value: None,
                 max_value: None,
+                null_count: None,
             },
         );
 
@@ -615,9 +709,9 @@ mod tests {
         let table_schema = create_test_schema();
         let partition_schema = Schema::empty();
 
-        let filters = vec![
-            Filter::try_from(("id", ">", "0")).unwrap(),
-            Filter::try_from(("id", "<", "5")).unwrap(),
+        let filters: Vec<FilterExpr> = vec![
+            Filter::try_from(("id", ">", "0")).unwrap().into(),
+            Filter::try_from(("id", "<", "5")).unwrap().into(),
         ];
         let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
 
@@ -626,4 +720,96 @@ mod tests {
         let stats = create_stats_with_int_range("id", 10, 100);
         assert!(!pruner.should_include(&stats));
     }
+
+    #[test]
+    fn test_or_prunes_only_when_no_branch_can_match() {
+        let table_schema = create_test_schema();
+        let partition_schema = create_partition_schema();
+        let stats = create_stats_with_int_range("id", 10, 100);
+
+        // id < 5 OR id > 200: neither branch can match.
+        let filters = vec![FilterExpr::Or(vec![
+            col("id").lt("5").into(),
+            col("id").gt("200").into(),
+        ])];
+        let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
+        assert!(!pruner.should_include(&stats));
+
+        // id < 5 OR id = 50: the second branch may match.
+        let filters = vec![FilterExpr::Or(vec![
+            col("id").lt("5").into(),
+            col("id").eq("50").into(),
+        ])];
+        let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
+        assert!(pruner.should_include(&stats));
+
+        // id < 5 OR date = '2024-01-01': partition filters may match any file.
+        let filters = vec![FilterExpr::Or(vec![
+            col("id").lt("5").into(),
+            col("date").eq("2024-01-01").into(),
+        ])];
+        let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
+        assert!(pruner.should_include(&stats));
+
+        // NOT (id >= 5 AND id <= 200) is pushed down to id < 5 OR id > 200.
+        let filters = vec![FilterExpr::Not(Box::new(FilterExpr::And(vec![
+            col("id").gte("5").into(),
+            col("id").lte("200").into(),
+        ])))];
+        let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
+        assert!(!pruner.should_include(&stats));
+    }
+
+    #[test]
+    fn test_null_check_operators() {
+        let table_schema = create_test_schema();
+        let partition_schema = create_partition_schema();
+        let mut stats = create_stats_with_string_range("name", "a", "z");
+        stats.num_rows = Some(10);
+
+        let is_null = vec![FilterExpr::from(col("name").is_null())];
+        let is_null = FilePruner::new(&is_null, &table_schema, &partition_schema).unwrap();
+        let is_not_null = vec![FilterExpr::from(col("name").is_not_null())];
+        let is_not_null = FilePruner::new(&is_not_null, &table_schema, &partition_schema).unwrap();
+
+        // Unknown null count cannot prune.
+        assert!(is_null.should_include(&stats));
+        assert!(is_not_null.should_include(&stats));
+
+        stats.columns.get_mut("name").unwrap().null_count = Some(0);
+        assert!(!is_null.should_include(&stats));
+        assert!(is_not_null.should_include(&stats));
+
+        stats.columns.get_mut("name").unwrap().null_count = Some(10);
+        assert!(is_null.should_include(&stats));
+        assert!(!is_not_null.should_include(&stats));
+    }
+
+    #[test]
+    fn test_like_prunes_by_literal_prefix() {
+        let table_schema = create_test_schema();
+        let partition_schema = create_partition_schema();
+        let stats = create_stats_with_string_range("name", "banana", "cherry");
+
+        let should_include = |pattern: &str| {
+            let filters = vec![FilterExpr::from(col("name").like(pattern))];
+            FilePruner::new(&filters, &table_schema, &partition_schema)
+                .unwrap()
+                .should_include(&stats)
+        };
+        assert!(!should_include("apple%"));
+        assert!(!should_include("d%"));
+        assert!(!should_include("chz_"));
+        assert!(should_include("ba%"));
+        assert!(should_include("b%"));
+        assert!(should_include("cherry%"));
+        assert!(should_include("c%"));
+        // Without a literal prefix, any value may match.
+        assert!(should_include("%zzz"));
+        assert!(should_include("_a%"));
+
+        let filters = vec![FilterExpr::from(col("name").not_like("apple%"))];
+        let pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
+        assert!(pruner.should_include(&stats));
+    }
 }
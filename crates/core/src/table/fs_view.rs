@@ -343,7 +343,7 @@ mod tests {
     use crate::config::HudiConfigs;
     use crate::config::table::BaseFileFormatValue;
     use crate::config::table::HudiTableConfig::{BaseFileFormat, BasePath};
-    use crate::expr::filter::Filter;
+    use crate::expr::filter::{Filter, FilterExpr};
     use crate::file_group::FileGroup;
     use crate::metadata::table::records::{
         FilesPartitionRecord, HoodieMetadataFileInfo, MetadataRecordType,
@@ -433,7 +433,7 @@ mod tests {
         let hudi_table = Table::new(base_url.path()).await.unwrap();
         let table_schema = hudi_table.get_schema().await.unwrap();
         let partition_schema = hudi_table.get_partition_schema().await.unwrap();
-        let filters = vec![Filter::try_from(filter).unwrap()];
+        let filters: Vec<FilterExpr> = vec![Filter::try_from(filter).unwrap().into()];
         let file_pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
         let as_of = hudi_table.timeline.get_latest_commit_timestamp().unwrap();
         (hudi_table, table_schema, file_pruner, as_of)
@@ -646,7 +646,8 @@ mod tests {
         let table = Table::new(table_base_url.path()).await.unwrap();
         let table_schema = table.get_schema().await.unwrap();
         let partition_schema = table.get_partition_schema().await.unwrap();
-        let filters = vec![Filter::try_from(("intField", ">=", "0")).unwrap()];
+        let filters: Vec<FilterExpr> =
+            vec![Filter::try_from(("intField", ">=", "0")).unwrap().into()];
         let file_pruner = FilePruner::new(&filters, &table_schema, &partition_schema).unwrap();
         assert!(!file_pruner.is_empty());
         let as_of = table.timeline.get_latest_commit_timestamp().unwrap();
@@ -700,7 +701,8 @@ mod tests {
             .unwrap();
         let partition_schema = hudi_table.get_partition_schema().await.unwrap();
         let table_schema = hudi_table.get_schema().await.unwrap();
-        let filters = vec![Filter::try_from(("intField", ">=", "0")).unwrap()];
+        let filters: Vec<FilterExpr> =
+            vec![Filter::try_from(("intField", ">=", "0")).unwrap().into()];
         let partition_pruner = PartitionPruner::new(
             &filters,
             &partition_schema,
@@ -746,7 +748,7 @@ mod tests {
         let filter_lt_20 = Filter::try_from(("byteField", "<", "20")).unwrap();
         let filter_eq_300 = Filter::try_from(("shortField", "=", "300")).unwrap();
         let partition_pruner = PartitionPruner::new(
-            &[filter_lt_20, filter_eq_300],
+            &[filter_lt_20.into(), filter_eq_300.into()],
             &partition_schema,
             hudi_table.hudi_configs.as_ref(),
         )
@@ -792,7 +794,8 @@ mod tests {
             .unwrap();
         let partition_schema = hudi_table.get_partition_schema().await.unwrap();
         let table_schema = hudi_table.get_schema().await.unwrap();
-        let partition_filters = vec![Filter::try_from(("byteField", "=", "10")).unwrap()];
+        let partition_filters: Vec<FilterExpr> =
+            vec![Filter::try_from(("byteField", "=", "10")).unwrap().into()];
         let partition_pruner = PartitionPruner::new(
             &partition_filters,
             &partition_schema,
@@ -1065,7 +1068,8 @@ mod tests {
         assert!(initial_partitions > 1);
 
         // Then issue a stricter call that prunes some loaded partitions during collection.
-        let partition_filters = vec![Filter::try_from(("byteField", "=", "10")).unwrap()];
+        let partition_filters: Vec<FilterExpr> =
+            vec![Filter::try_from(("byteField", "=", "10")).unwrap().into()];
         let partition_pruner = PartitionPruner::new(
             &partition_filters,
             &partition_schema,
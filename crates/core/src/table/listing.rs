@@ -158,7 +158,20 @@ impl FileLister {
 
             file_group.add_base_files(base_files)?;
 
-            let log_files = file_id_to_log_files.remove(&file_id).unwrap_or_default();
+            // Log files completed before the earliest base file belong to a file slice without
+            // base file, e.g., of a metadata table partition initialized with log files only,
+            // which is superseded by the compacted base file.
+            let earliest_base_instant = file_group.file_slices.keys().next().cloned();
+            let log_files = file_id_to_log_files
+                .remove(&file_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|log_file| {
+                    match (&log_file.completion_timestamp, &earliest_base_instant) {
+                        (Some(completion), Some(earliest)) => completion >= earliest,
+                        _ => true,
+                    }
+                });
             file_group.add_log_files(log_files)?;
 
             file_groups.push(file_group);
@@ -166,7 +179,9 @@ impl FileLister {
         Ok(file_groups)
     }
 
-    async fn list_relevant_partition_paths(&self) -> Result<Vec<String>> {
+    /// List the paths of the partitions kept by the partition pruner, or
+    /// [EMPTY_PARTITION_PATH] for non-partitioned tables.
+    pub(crate) async fn list_relevant_partition_paths(&self) -> Result<Vec<String>> {
         if !is_table_partitioned(&self.hudi_configs)? {
             return Ok(vec![EMPTY_PARTITION_PATH.to_string()]);
         }
@@ -86,12 +86,23 @@
 //! }
 //! ```
 
+pub mod archive;
 pub mod builder;
+pub mod clean;
+pub mod creator;
+pub mod discovery;
 pub mod file_pruner;
 pub(crate) mod fs_view;
 mod listing;
 pub mod partition;
+pub mod restore;
+pub mod rollback;
+pub mod savepoint;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod upgrade;
 mod validation;
+pub mod write;
 
 pub use crate::config::read_options::{QueryType, ReadOptions};
 
@@ -101,9 +112,11 @@ use crate::config::read::HudiReadConfig;
 use crate::config::table::HudiTableConfig::PartitionFields;
 use crate::config::table::{BaseFileFormatValue, HudiTableConfig, TableTypeValue};
 use crate::error::CoreError;
-use crate::expr::filter::{Filter, validate_fields_against_schemas};
+use crate::expr::filter::{FilterExpr, validate_fields_against_schemas};
 use crate::file_group::file_slice::FileSlice;
 use crate::file_group::reader::FileGroupReader;
+use crate::file_group::virtual_meta_fields::VirtualMetaFields;
+use crate::index::bucket::{BucketIndex, parse_bucket_id};
 use crate::keygen::is_timestamp_based_keygen;
 use crate::metadata::METADATA_TABLE_PARTITION_FIELD;
 use crate::metadata::commit::HoodieCommitMetadata;
@@ -111,6 +124,7 @@ use crate::metadata::meta_field::MetaField;
 use crate::schema::resolver::{
     resolve_avro_schema, resolve_avro_schema_with_meta_fields, resolve_data_schema, resolve_schema,
 };
+use crate::statistics::StatisticsContainer;
 use crate::statistics::estimator::FileStatsEstimator;
 use crate::table::builder::TableBuilder;
 use crate::table::file_pruner::FilePruner;
@@ -464,7 +478,7 @@ impl Table {
     async fn get_file_slices_inner(
         &self,
         timestamp: &str,
-        filters: &[Filter],
+        filters: &[FilterExpr],
         base_file_only: bool,
     ) -> Result<Vec<FileSlice>> {
         let timeline_view = self.timeline.create_view_as_of(timestamp).await?;
@@ -484,8 +498,16 @@ impl Table {
         // File-level stats pruning using base file Parquet footers is only safe
         // when log files cannot introduce records that contradict the base file's
         // min/max stats — i.e., COW tables or MOR read-optimized mode.
+        // The statistics of the meta fields do not apply if they are synthesized on read.
         let file_pruner = if base_file_only {
-            FilePruner::new(filters, &table_schema, &partition_schema)?
+            match VirtualMetaFields::from_configs(&self.hudi_configs)? {
+                Some(_) if !self.is_metadata_table() => FilePruner::new(
+                    &VirtualMetaFields::filters_without_meta_fields(filters),
+                    &table_schema,
+                    &partition_schema,
+                )?,
+                _ => FilePruner::new(filters, &table_schema, &partition_schema)?,
+            }
         } else {
             FilePruner::empty()
         };
@@ -520,6 +542,7 @@ impl Table {
                 estimator,
             )
             .await?;
+        self.retain_candidate_buckets(&mut file_slices, filters, &table_schema)?;
 
         if base_file_only {
             for fs in &mut file_slices {
@@ -533,7 +556,7 @@ impl Table {
         &self,
         start_timestamp: &str,
         end_timestamp: &str,
-        filters: &[Filter],
+        filters: &[FilterExpr],
         base_file_only: bool,
     ) -> Result<Vec<FileSlice>> {
         // Seed the cached estimator from a sample base file at or before
@@ -547,19 +570,17 @@ impl Table {
             .await?;
 
         // Skip schema fetch and pruner construction when there are no filters.
-        let partition_pruner = if filters.is_empty() {
-            None
+        let (partition_pruner, table_schema) = if filters.is_empty() {
+            (None, None)
         } else {
             let partition_schema = self.get_partition_schema().await?;
             // See `get_file_slices_inner` for why validation uses the
             // meta-inclusive schema.
             let table_schema = self.get_schema_with_meta_fields().await?;
             validate_fields_against_schemas(filters, [&table_schema, &partition_schema])?;
-            Some(PartitionPruner::new(
-                filters,
-                &partition_schema,
-                self.hudi_configs.as_ref(),
-            )?)
+            let pruner =
+                PartitionPruner::new(filters, &partition_schema, self.hudi_configs.as_ref())?;
+            (Some(pruner), Some(table_schema))
         };
 
         let mut file_slices: Vec<FileSlice> = Vec::new();
@@ -573,6 +594,9 @@ impl Table {
                 file_slices.push(file_slice.clone());
             }
         }
+        if let Some(table_schema) = table_schema {
+            self.retain_candidate_buckets(&mut file_slices, filters, &table_schema)?;
+        }
 
         if base_file_only {
             for fs in &mut file_slices {
@@ -582,6 +606,31 @@ impl Table {
         Ok(file_slices)
     }
 
+    /// Prune the file slices whose file groups are bucketed by the bucket index and cannot
+    /// contain the records matching the filters.
+    ///
+    /// File groups whose file IDs do not encode a bucket number are kept.
+    fn retain_candidate_buckets(
+        &self,
+        file_slices: &mut Vec<FileSlice>,
+        filters: &[FilterExpr],
+        table_schema: &Schema,
+    ) -> Result<()> {
+        if filters.is_empty() {
+            return Ok(());
+        }
+        let Some(bucket_index) = BucketIndex::from_configs(&self.hudi_configs)? else {
+            return Ok(());
+        };
+        let Some(buckets) = bucket_index.get_candidate_buckets(filters, table_schema)? else {
+            return Ok(());
+        };
+        file_slices.retain(|fs| {
+            parse_bucket_id(fs.file_id()).is_none_or(|bucket| buckets.contains(&bucket))
+        });
+        Ok(())
+    }
+
     /// Create a [FileGroupReader] using the [Table]'s Hudi configs.
     ///
     /// `read_options.hudi_options` override table-level Hudi configs
@@ -746,7 +795,11 @@ impl Table {
         applicable.filters = options
             .filters
             .iter()
-            .filter(|filter| !partition_columns.iter().any(|p| p == &filter.field))
+            .filter(|expr| {
+                expr.filters()
+                    .iter()
+                    .all(|filter| !partition_columns.iter().any(|p| p == &filter.field))
+            })
             .cloned()
             .collect();
         applicable
@@ -952,6 +1005,74 @@ impl Table {
     }
 }
 
+impl Table {
+    /// Load the column stats of the base files of the given file slices from the metadata
+    /// table.
+    ///
+    /// Sets [`FileSlice::base_file_column_stats`] of the slices without stats loaded, e.g.,
+    /// from base file footers during file pruning. Slices whose base files are not found in
+    /// the `column_stats` partition of metadata table are left as is.
+    ///
+    /// The stats only cover the base files; records in log files are not reflected.
+    pub async fn load_column_stats(&self, file_slices: &mut [FileSlice]) -> Result<()> {
+        if !self.has_metadata_table_column_stats() {
+            return Ok(());
+        }
+
+        let files: Vec<(&str, String)> = file_slices
+            .iter()
+            .filter(|fs| fs.base_file_column_stats.is_none())
+            .map(|fs| (fs.partition_path.as_str(), fs.base_file.file_name()))
+            .collect();
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(partition_path, file_name)| (*partition_path, file_name.as_str()))
+            .collect();
+        let schema = self.get_schema_with_meta_fields().await?;
+        let mut stats = self
+            .read_metadata_table_column_stats(&files, &schema)
+            .await?;
+        for fs in file_slices
+            .iter_mut()
+            .filter(|fs| fs.base_file_column_stats.is_none())
+        {
+            fs.base_file_column_stats = stats.remove(&fs.base_file.file_name());
+        }
+        Ok(())
+    }
+
+    /// Compute estimated table-level column stats for scan planning.
+    ///
+    /// Aggregates the column stats of the base files of the file slices to read, loaded by
+    /// [`Table::load_column_stats`]. Returns `None` if the `column_stats` partition of metadata
+    /// table is not available, any base file has no stats, or the query type is incremental.
+    ///
+    /// The stats only cover the base files; records in log files are not reflected.
+    pub async fn compute_table_column_stats(
+        &self,
+        options: &ReadOptions,
+    ) -> Option<StatisticsContainer> {
+        if !matches!(options.query_type(), Ok(QueryType::Snapshot))
+            || !self.has_metadata_table_column_stats()
+        {
+            return None;
+        }
+
+        let mut file_slices = self.get_file_slices(options).await.ok()?;
+        self.load_column_stats(&mut file_slices).await.ok()?;
+        let mut stats = file_slices.into_iter().map(|fs| fs.base_file_column_stats);
+        let mut table_stats = stats.next()??;
+        for file_stats in stats {
+            table_stats.merge(&file_stats?);
+        }
+        Some(table_stats)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -966,10 +1087,12 @@ mod tests {
     };
     use crate::config::util::empty_options;
     use crate::error::CoreError;
+    use crate::expr::filter::Filter;
     use crate::metadata::meta_field::MetaField;
     use crate::storage::Storage;
     use crate::storage::util::join_url_segments;
     use crate::timeline::EARLIEST_START_TIMESTAMP;
+    use arrow_array::cast::AsArray;
     use hudi_test::{SampleTable, assert_arrow_field_names_eq, assert_avro_field_names_eq};
     use serial_test::serial;
     use std::collections::HashSet;
@@ -1887,6 +2010,46 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[tokio::test]
+    async fn hudi_table_get_file_slices_with_or_and_not_filter_exprs() {
+        let base_url = SampleTable::V6ComplexkeygenHivestyle.url_to_cow();
+        let hudi_table = Table::new(base_url.path()).await.unwrap();
+
+        async fn partition_paths(table: &Table, filters: Vec<FilterExpr>) -> HashSet<String> {
+            let options = ReadOptions::new().with_filter_exprs(filters);
+            let mut paths = HashSet::new();
+            for f in table.get_file_slices(&options).await.unwrap() {
+                paths.insert(f.partition_path.clone());
+            }
+            paths
+        }
+
+        let or_filter = FilterExpr::Or(vec![
+            Filter::try_from(("byteField", "=", "10")).unwrap().into(),
+            Filter::try_from(("byteField", "=", "30")).unwrap().into(),
+        ]);
+        let expected = ["byteField=10/shortField=300", "byteField=30/shortField=100"]
+            .map(String::from)
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            partition_paths(&hudi_table, vec![or_filter]).await,
+            expected
+        );
+
+        let not_filter = FilterExpr::Not(Box::new(
+            Filter::try_from(("shortField", "=", "100")).unwrap().into(),
+        ));
+        let expected = ["byteField=10/shortField=300"]
+            .map(String::from)
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            partition_paths(&hudi_table, vec![not_filter]).await,
+            expected
+        );
+    }
+
     #[tokio::test]
     async fn test_get_or_init_estimator_returns_none_for_non_parquet_format() {
         let base_url = SampleTable::V9TxnsSimpleMeta.url_to_cow();
@@ -1950,6 +2113,66 @@ mod tests {
         assert!(bytes > 0);
     }
 
+    #[tokio::test]
+    async fn test_load_column_stats_with_sample_mdt_table() {
+        let base_url = SampleTable::V9TxnsSimpleMeta.url_to_cow();
+        let table = Table::new(base_url.path()).await.unwrap();
+        let mut file_slices = table.get_file_slices(&ReadOptions::new()).await.unwrap();
+        table.load_column_stats(&mut file_slices).await.unwrap();
+
+        let mut total_rows = 0;
+        for fs in &file_slices {
+            let stats = fs.base_file_column_stats.as_ref().unwrap();
+            total_rows += stats.num_rows.unwrap();
+            let region = &stats.columns["region"];
+            let expected = fs.partition_path.strip_prefix("region=").unwrap();
+            let min = region.min_value.as_ref().unwrap();
+            assert_eq!(min.as_string::<i32>().value(0), expected);
+            assert_eq!(region.null_count, Some(0));
+            // Columns not indexed are unknown
+            let seqno = &stats.columns[MetaField::CommitSeqno.as_ref()];
+            assert!(seqno.min_value.is_none() && seqno.null_count.is_none());
+        }
+
+        let table_stats = table
+            .compute_table_column_stats(&ReadOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(table_stats.num_rows, Some(total_rows));
+        let region = &table_stats.columns["region"];
+        let min = region.min_value.as_ref().unwrap();
+        let max = region.max_value.as_ref().unwrap();
+        assert_eq!(min.as_string::<i32>().value(0), "apac");
+        assert_eq!(max.as_string::<i32>().value(0), "us");
+
+        let incremental = ReadOptions::new().with_query_type(QueryType::Incremental);
+        assert!(
+            table
+                .compute_table_column_stats(&incremental)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_column_stats_without_column_stats_partition() {
+        let base_url = SampleTable::V8Nonpartitioned.url_to_cow();
+        let table = Table::new(base_url.path()).await.unwrap();
+        let mut file_slices = table.get_file_slices(&ReadOptions::new()).await.unwrap();
+        table.load_column_stats(&mut file_slices).await.unwrap();
+        assert!(
+            file_slices
+                .iter()
+                .all(|fs| fs.base_file_column_stats.is_none())
+        );
+        assert!(
+            table
+                .compute_table_column_stats(&ReadOptions::new())
+                .await
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_compute_table_stats_returns_none_when_base_file_extension_does_not_match() {
         let base_url = SampleTable::V9TxnsSimpleMeta.url_to_cow();
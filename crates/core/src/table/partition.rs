@@ -20,11 +20,11 @@ use crate::Result;
 use crate::config::HudiConfigs;
 use crate::config::table::HudiTableConfig;
 use crate::error::CoreError::{self, InvalidPartitionPath};
-use crate::expr::filter::{Filter, SchemableFilter};
+use crate::expr::filter::{FilterExpr, SchemableFilter};
 use crate::keygen::KeyGeneratorFilterTransformer;
 use crate::keygen::timestamp_based::TimestampBasedKeyGenerator;
 
-use arrow_array::{ArrayRef, Scalar};
+use arrow_array::{ArrayRef, Scalar, StringArray};
 use arrow_schema::{Field, Schema};
 
 use crate::config::table::HudiTableConfig::{KeyGeneratorClass, KeyGeneratorType, PartitionFields};
@@ -98,22 +98,35 @@ pub struct PartitionPruner {
     is_hive_style: bool,
     is_url_encoded: bool,
     is_partitioned: bool,
-    and_filters: Vec<SchemableFilter>,
+    /// Filter expressions in negation normal form, where `None` is a filter on a
+    /// non-partition column that may match any partition.
+    and_filters: Vec<FilterExpr<Option<SchemableFilter>>>,
 }
 
 impl PartitionPruner {
     pub fn new(
-        and_filters: &[Filter],
+        and_filters: &[FilterExpr],
         partition_schema: &Schema,
         hudi_configs: &HudiConfigs,
     ) -> Result<Self> {
+        // Push NOT down to the filters, so that each filter can be evaluated on its own
+        let and_filters: Vec<FilterExpr> = and_filters
+            .iter()
+            .map(FilterExpr::to_negation_normal_form)
+            .collect();
+
         // Transform filters based on key generator configuration
         let transformed_filters =
-            Self::transform_filters_for_keygen(and_filters, partition_schema, hudi_configs)?;
+            Self::transform_filters_for_keygen(&and_filters, partition_schema, hudi_configs)?;
 
-        let and_filters: Vec<SchemableFilter> = transformed_filters
+        let and_filters: Vec<FilterExpr<Option<SchemableFilter>>> = transformed_filters
             .iter()
-            .filter_map(|filter| SchemableFilter::try_from((filter.clone(), partition_schema)).ok())
+            .map(|expr| {
+                expr.map(&mut |filter| {
+                    SchemableFilter::try_from((filter.clone(), partition_schema)).ok()
+                })
+            })
+            .filter(|expr| !matches!(expr, FilterExpr::Filter(None)))
             .collect();
 
         let schema = Arc::new(partition_schema.clone());
@@ -154,6 +167,34 @@ impl PartitionPruner {
         self.is_partitioned
     }
 
+    /// Returns the schema of the partition fields.
+    pub fn partition_schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Returns the values of the partition fields of the records in the partition,
+    /// parsed from the partition path as single-element arrays, or `None` if the
+    /// partition path fails to parse.
+    ///
+    /// With a single `_hoodie_partition_path` field, e.g., for a timestamp-based
+    /// key generator, the value is the partition path as stored in the records.
+    pub fn partition_values(&self, partition_path: &str) -> Option<HashMap<String, ArrayRef>> {
+        if self.is_partition_path_field() {
+            let value: ArrayRef = Arc::new(StringArray::from(vec![partition_path]));
+            return Some(HashMap::from([(
+                MetaField::PartitionPath.as_ref().to_string(),
+                value,
+            )]));
+        }
+        let segments = self.parse_segments(partition_path).ok()?;
+        Some(
+            segments
+                .into_iter()
+                .map(|(name, value)| (name, value.into_inner()))
+                .collect(),
+        )
+    }
+
     /// Returns `true` if the partition path should be included based on the filters.
     pub fn should_include(&self, partition_path: &str) -> bool {
         let segments = match self.parse_segments(partition_path) {
@@ -161,7 +202,10 @@ impl PartitionPruner {
             Err(_) => return true, // Include the partition regardless of parsing error
         };
 
-        self.and_filters.iter().all(|filter| {
+        let mut may_match = |filter: &Option<SchemableFilter>| {
+            let Some(filter) = filter else {
+                return true; // Include the partition when filtering on a non-partition field
+            };
             match segments.get(filter.field.name()) {
                 Some(segment_value) => {
                     match filter.apply_comparison(segment_value) {
@@ -171,16 +215,19 @@ impl PartitionPruner {
                 }
                 None => true, // Include the partition when filtering field does not match any field in the partition
             }
-        })
+        };
+        self.and_filters
+            .iter()
+            .all(|expr| expr.may_match(&mut may_match))
     }
 
     /// Transforms user filters on data columns to filters on partition path columns
     /// based on the configured key generator.
     fn transform_filters_for_keygen(
-        filters: &[Filter],
+        filters: &[FilterExpr],
         _partition_schema: &Schema,
         hudi_configs: &HudiConfigs,
-    ) -> Result<Vec<Filter>> {
+    ) -> Result<Vec<FilterExpr>> {
         if is_timestamp_based_keygen(hudi_configs)? {
             match TimestampBasedKeyGenerator::from_configs(hudi_configs) {
                 Ok(transformer) => {
@@ -199,17 +246,60 @@ impl PartitionPruner {
     }
 
     fn apply_transformer_to_filters(
-        filters: &[Filter],
+        filters: &[FilterExpr],
         transformer: &dyn KeyGeneratorFilterTransformer,
-    ) -> Result<Vec<Filter>> {
+    ) -> Result<Vec<FilterExpr>> {
         let mut transformed = Vec::new();
-        for filter in filters {
-            let partition_filters = transformer.transform_filter(filter)?;
-            transformed.extend(partition_filters);
+        for expr in filters {
+            match expr {
+                FilterExpr::Filter(filter) => {
+                    let partition_filters = transformer.transform_filter(filter)?;
+                    transformed.extend(partition_filters.into_iter().map(FilterExpr::from));
+                }
+                _ => transformed.push(Self::apply_transformer_to_expr(expr, transformer)?),
+            }
         }
         Ok(transformed)
     }
 
+    /// Transforms the filters of an expression in negation normal form, where the
+    /// transformed filters of each filter are AND-ed in place of it.
+    fn apply_transformer_to_expr(
+        expr: &FilterExpr,
+        transformer: &dyn KeyGeneratorFilterTransformer,
+    ) -> Result<FilterExpr> {
+        let transform_all = |exprs: &[FilterExpr]| -> Result<Vec<FilterExpr>> {
+            exprs
+                .iter()
+                .map(|e| Self::apply_transformer_to_expr(e, transformer))
+                .collect()
+        };
+        Ok(match expr {
+            FilterExpr::Filter(filter) => {
+                let mut partition_filters: Vec<FilterExpr> = transformer
+                    .transform_filter(filter)?
+                    .into_iter()
+                    .map(FilterExpr::from)
+                    .collect();
+                if partition_filters.len() == 1 {
+                    partition_filters.remove(0)
+                } else {
+                    FilterExpr::And(partition_filters)
+                }
+            }
+            FilterExpr::And(exprs) => FilterExpr::And(transform_all(exprs)?),
+            FilterExpr::Or(exprs) => FilterExpr::Or(transform_all(exprs)?),
+            // Transformed filters are implied by, not equivalent to, the original
+            // ones, so they cannot be negated.
+            FilterExpr::Not(_) => FilterExpr::And(vec![]),
+        })
+    }
+
+    fn is_partition_path_field(&self) -> bool {
+        self.schema.fields().len() == 1
+            && self.schema.field(0).name() == MetaField::PartitionPath.as_ref()
+    }
+
     fn parse_segments(&self, partition_path: &str) -> Result<HashMap<String, Scalar<ArrayRef>>> {
         let partition_path = if self.is_url_encoded {
             percent_encoding::percent_decode(partition_path.as_bytes())
@@ -220,9 +310,7 @@ impl PartitionPruner {
         };
 
         // Special case: single _hoodie_partition_path field uses the raw path as-is
-        if self.schema.fields().len() == 1
-            && self.schema.field(0).name() == MetaField::PartitionPath.as_ref()
-        {
+        if self.is_partition_path_field() {
             let scalar = SchemableFilter::cast_value(
                 &[partition_path.as_str()],
                 &arrow_schema::DataType::Utf8,
@@ -277,9 +365,10 @@ mod tests {
         IsHiveStylePartitioning, IsPartitionPathUrlencoded,
     };
     use crate::expr::ExprOperator;
+    use crate::expr::filter::{Filter, col};
 
     use arrow::datatypes::{DataType, Field, Schema};
-    use arrow_array::Date32Array;
+    use arrow_array::{Date32Array, Int32Array};
     use std::str::FromStr;
 
     fn create_test_schema() -> Schema {
@@ -349,7 +438,11 @@ mod tests {
         let filter_gt_date = Filter::try_from(("date", ">", "2023-01-01")).unwrap();
         let filter_eq_a = Filter::try_from(("category", "=", "A")).unwrap();
 
-        let pruner = PartitionPruner::new(&[filter_gt_date, filter_eq_a], &schema, &configs);
+        let pruner = PartitionPruner::new(
+            &[filter_gt_date.into(), filter_eq_a.into()],
+            &schema,
+            &configs,
+        );
         assert!(pruner.is_ok());
 
         let pruner = pruner.unwrap();
@@ -375,7 +468,8 @@ mod tests {
         assert!(pruner_empty.is_empty());
 
         let filter_gt_date = Filter::try_from(("date", ">", "2023-01-01")).unwrap();
-        let pruner_non_empty = PartitionPruner::new(&[filter_gt_date], &schema, &configs).unwrap();
+        let pruner_non_empty =
+            PartitionPruner::new(&[filter_gt_date.into()], &schema, &configs).unwrap();
         assert!(!pruner_non_empty.is_empty());
     }
 
@@ -389,7 +483,11 @@ mod tests {
         let filter_lte_100 = Filter::try_from(("count", "<=", "100")).unwrap();
 
         let pruner = PartitionPruner::new(
-            &[filter_gt_date, filter_eq_a, filter_lte_100],
+            &[
+                filter_gt_date.into(),
+                filter_eq_a.into(),
+                filter_lte_100.into(),
+            ],
             &schema,
             &configs,
         )
@@ -401,6 +499,48 @@ mod tests {
         assert!(!pruner.should_include("date=2023-02-01/category=B/count=10"));
     }
 
+    #[test]
+    fn test_partition_pruner_should_include_with_or_and_not() {
+        let schema = create_test_schema();
+        let configs = create_hudi_configs(true, false);
+
+        // category IN ('A') OR date = '2023-01-01'
+        let category_or_date = FilterExpr::Or(vec![
+            col("category").in_list(["A"]).into(),
+            col("date").eq("2023-01-01").into(),
+        ]);
+        // NOT (count > 100 OR category LIKE 'C%')
+        let not_count_or_category = FilterExpr::Not(Box::new(FilterExpr::Or(vec![
+            col("count").gt("100").into(),
+            col("category").like("C%").into(),
+        ])));
+        let pruner = PartitionPruner::new(
+            &[category_or_date, not_count_or_category],
+            &schema,
+            &configs,
+        )
+        .unwrap();
+
+        assert!(pruner.should_include("date=2023-02-01/category=A/count=10"));
+        assert!(pruner.should_include("date=2023-01-01/category=B/count=10"));
+        assert!(!pruner.should_include("date=2023-02-01/category=B/count=10"));
+        assert!(!pruner.should_include("date=2023-01-01/category=B/count=200"));
+        assert!(!pruner.should_include("date=2023-01-01/category=C1/count=10"));
+
+        // A filter on a non-partition column may match any partition, even under OR.
+        let data_or_category = FilterExpr::Or(vec![
+            col("fare").gt("10").into(),
+            col("category").eq("A").into(),
+        ]);
+        let pruner = PartitionPruner::new(&[data_or_category], &schema, &configs).unwrap();
+        assert!(!pruner.is_empty());
+        assert!(pruner.should_include("date=2023-02-01/category=B/count=10"));
+
+        let is_null = FilterExpr::from(col("category").is_null());
+        let pruner = PartitionPruner::new(&[is_null], &schema, &configs).unwrap();
+        assert!(!pruner.should_include("date=2023-02-01/category=B/count=10"));
+    }
+
     #[test]
     fn test_partition_pruner_parse_segments() {
         let schema = create_test_schema();
@@ -447,6 +587,50 @@ mod tests {
         assert!(matches!(result.unwrap_err(), InvalidPartitionPath(_)));
     }
 
+    #[test]
+    fn test_partition_pruner_partition_values() {
+        let schema = create_test_schema();
+        let configs = create_hudi_configs(true, false);
+        let pruner = PartitionPruner::new(&[], &schema, &configs).unwrap();
+        assert_eq!(pruner.partition_schema(), &schema);
+
+        let values = pruner
+            .partition_values("date=2023-02-01/category=A/count=10")
+            .unwrap();
+        assert_eq!(values.len(), 3);
+        let count = values["count"]
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(count.values(), &[10]);
+        let category = values["category"]
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(category.value(0), "A");
+
+        assert!(
+            pruner
+                .partition_values("date=2023-02-01/category=A")
+                .is_none()
+        );
+
+        // The raw partition path is the value of a single _hoodie_partition_path field.
+        let schema = Schema::new(vec![Field::new(
+            MetaField::PartitionPath.as_ref(),
+            DataType::Utf8,
+            false,
+        )]);
+        let configs = create_hudi_configs(false, true);
+        let pruner = PartitionPruner::new(&[], &schema, &configs).unwrap();
+        let values = pruner.partition_values("2023%2F02%2F01").unwrap();
+        let path = values[MetaField::PartitionPath.as_ref()]
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(path.value(0), "2023%2F02%2F01");
+    }
+
     #[test]
     fn test_partition_filter_try_from_valid() {
         let schema = create_test_schema();
@@ -502,7 +686,12 @@ mod tests {
     fn test_partition_filter_try_from_all_operators() {
         let schema = create_test_schema();
         for (op, op_enum) in ExprOperator::TOKEN_OP_PAIRS {
-            let filter = Filter::new("count".to_string(), op_enum, vec!["5".to_string()]).unwrap();
+            let values = if op_enum.is_null_check() {
+                vec![]
+            } else {
+                vec!["5".to_string()]
+            };
+            let filter = Filter::new("count".to_string(), op_enum, values).unwrap();
             let partition_filter = SchemableFilter::try_from((filter, &schema));
             let filter = partition_filter.unwrap();
             assert_eq!(filter.field.name(), "count");
@@ -541,16 +730,25 @@ mod tests {
         };
 
         let transformed = PartitionPruner::transform_filters_for_keygen(
-            &[user_filter],
+            &[user_filter.into()],
             &partition_schema,
             &configs,
         )
         .unwrap();
 
         assert_eq!(transformed.len(), 1);
-        assert_eq!(transformed[0].field, MetaField::PartitionPath.as_ref());
-        assert_eq!(transformed[0].operator, ExprOperator::Gte);
-        assert_eq!(transformed[0].values[0], "year=2023/month=04/day=15");
+        assert_eq!(
+            transformed[0].as_filter().unwrap().field,
+            MetaField::PartitionPath.as_ref()
+        );
+        assert_eq!(
+            transformed[0].as_filter().unwrap().operator,
+            ExprOperator::Gte
+        );
+        assert_eq!(
+            transformed[0].as_filter().unwrap().values[0],
+            "year=2023/month=04/day=15"
+        );
 
         // Equality filter: UNIX_TIMESTAMP Eq → single path
         let configs = HudiConfigs::new([
@@ -572,15 +770,18 @@ mod tests {
         };
 
         let transformed = PartitionPruner::transform_filters_for_keygen(
-            &[user_filter],
+            &[user_filter.into()],
             &partition_schema,
             &configs,
         )
         .unwrap();
 
         assert_eq!(transformed.len(), 1);
-        assert_eq!(transformed[0].field, MetaField::PartitionPath.as_ref());
-        assert_eq!(transformed[0].values[0], "2024/01/25");
+        assert_eq!(
+            transformed[0].as_filter().unwrap().field,
+            MetaField::PartitionPath.as_ref()
+        );
+        assert_eq!(transformed[0].as_filter().unwrap().values[0], "2024/01/25");
 
         // v8 detection via keygenerator.type=TIMESTAMP (no keygenerator.class)
         let configs = HudiConfigs::new([
@@ -602,14 +803,17 @@ mod tests {
         };
 
         let transformed = PartitionPruner::transform_filters_for_keygen(
-            &[user_filter],
+            &[user_filter.into()],
             &partition_schema,
             &configs,
         )
         .unwrap();
 
         assert_eq!(transformed.len(), 1);
-        assert_eq!(transformed[0].values[0], "year=2023/month=04/day=15");
+        assert_eq!(
+            transformed[0].as_filter().unwrap().values[0],
+            "year=2023/month=04/day=15"
+        );
     }
 
     #[test]
@@ -632,15 +836,18 @@ mod tests {
         };
 
         let transformed = PartitionPruner::transform_filters_for_keygen(
-            std::slice::from_ref(&user_filter),
+            &[user_filter.clone().into()],
             &partition_schema,
             &configs,
         )
         .unwrap();
 
         assert_eq!(transformed.len(), 1);
-        assert_eq!(transformed[0].field, user_filter.field);
-        assert_eq!(transformed[0].values[0], user_filter.values[0]);
+        assert_eq!(transformed[0].as_filter().unwrap().field, user_filter.field);
+        assert_eq!(
+            transformed[0].as_filter().unwrap().values[0],
+            user_filter.values[0]
+        );
     }
 
     #[test]
@@ -673,7 +880,8 @@ mod tests {
             values: vec!["2024-01-15T00:00:00Z".to_string()],
         };
 
-        let pruner = PartitionPruner::new(&[user_filter], &partition_schema, &configs).unwrap();
+        let pruner =
+            PartitionPruner::new(&[user_filter.into()], &partition_schema, &configs).unwrap();
 
         assert!(!pruner.is_empty());
 
@@ -708,7 +916,8 @@ mod tests {
             values: vec!["2024-01-15T00:00:00Z".to_string()],
         };
 
-        let pruner = PartitionPruner::new(&[user_filter], &partition_schema, &configs).unwrap();
+        let pruner =
+            PartitionPruner::new(&[user_filter.into()], &partition_schema, &configs).unwrap();
         assert!(pruner.should_include("2024/01/15"));
         assert!(!pruner.should_include("2024/01/16"));
         assert!(!pruner.should_include("2023/12/31"));
@@ -0,0 +1,350 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Restoring a Hudi table to a savepoint.
+//!
+//! Restoring rolls back all instants of write actions after the savepoint, completed or
+//! pending, from the latest to the earliest, and records a `restore` instant with
+//! [HoodieRestoreMetadata] on the [crate::timeline::Timeline]. The rolled back instants are
+//! removed from the timeline.
+
+use crate::Result;
+use crate::error::CoreError;
+use crate::metadata::restore::{HoodieRestoreMetadata, HoodieRestorePlan};
+use crate::metadata::table::writer::FilesPartitionChanges;
+use crate::table::Table;
+use crate::table::rollback::to_instant_info;
+use crate::timeline::DEFAULT_LOADING_ACTIONS;
+use crate::timeline::instant::{Action, Instant, State};
+use bytes::Bytes;
+use std::collections::{BTreeMap, HashMap};
+
+/// The version of [HoodieRestorePlan] and [HoodieRestoreMetadata] written by the restore.
+const RESTORE_METADATA_VERSION: i32 = 1;
+
+impl Table {
+    /// Restore the table to the savepoint at the given instant time.
+    ///
+    /// The instants of write actions after the savepoint are rolled back, a `restore` instant
+    /// is recorded on the timeline, and the rolled back instants are removed. If the metadata
+    /// table is enabled, the changed files are also synced to its `files` partition. The
+    /// savepoint itself is kept.
+    ///
+    /// The [Table] instance is not refreshed after the restore; create a new instance to
+    /// read the updated timeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no completed savepoint at the instant time, or if there
+    /// are later savepoints, which need to be deleted by [Table::delete_savepoint] first.
+    pub async fn restore_to_savepoint(
+        &self,
+        savepoint_time: &str,
+    ) -> Result<HoodieRestoreMetadata> {
+        if self.is_metadata_table_enabled() {
+            self.ensure_metadata_table_writable()?;
+        }
+
+        let instants = self.load_all_instants().await?;
+        let savepoints: Vec<&Instant> = instants
+            .iter()
+            .filter(|i| i.action == Action::Savepoint && i.state == State::Completed)
+            .collect();
+        if !savepoints.iter().any(|s| s.timestamp == savepoint_time) {
+            return Err(CoreError::Timeline(format!(
+                "Savepoint {savepoint_time} is not found"
+            )));
+        }
+        if let Some(later) = savepoints
+            .iter()
+            .find(|s| s.timestamp.as_str() > savepoint_time)
+        {
+            return Err(CoreError::Timeline(format!(
+                "Savepoint {} is later than {savepoint_time} and needs to be deleted first",
+                later.timestamp
+            )));
+        }
+
+        let timer = std::time::Instant::now();
+        let timeline = &self.timeline;
+        let latest_timestamp = instants.iter().map(|i| i.timestamp.as_str()).max();
+        let restore_timestamp = timeline.new_instant_timestamp(latest_timestamp)?;
+        let to_rollback = Self::writes_after(&instants, savepoint_time);
+
+        let restore_plan = HoodieRestorePlan {
+            instants_to_rollback: to_rollback.iter().map(to_instant_info).collect(),
+            version: Some(RESTORE_METADATA_VERSION),
+        };
+        let requested = timeline
+            .create_requested_instant(
+                Action::Restore,
+                &restore_timestamp,
+                Bytes::from(restore_plan.to_avro_bytes()?),
+            )
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        // Each rollback is planned after the later instants are rolled back, so that the log
+        // files with rollback command blocks appended by them are also found.
+        let mut changes = FilesPartitionChanges::default();
+        let mut rollbacks = HashMap::new();
+        for instant in &to_rollback {
+            let plan = self.plan_rollback(instant.clone()).await?;
+            let rollback_metadata = self.rollback_files(&plan, &restore_timestamp).await?;
+            changes.merge(FilesPartitionChanges::from_rollback_metadata(
+                &rollback_metadata,
+            ));
+            rollbacks.insert(instant.timestamp.clone(), vec![rollback_metadata]);
+        }
+        self.update_metadata_table(&restore_timestamp, &changes.into())
+            .await?;
+        for instant in &to_rollback {
+            timeline.delete_instant(instant).await?;
+        }
+
+        let restore_metadata = HoodieRestoreMetadata {
+            start_restore_time: restore_timestamp,
+            time_taken_in_millis: timer.elapsed().as_millis() as i64,
+            instants_to_rollback: to_rollback.iter().map(|i| i.timestamp.clone()).collect(),
+            hoodie_restore_metadata: rollbacks,
+            version: Some(RESTORE_METADATA_VERSION),
+            restore_instant_info: restore_plan.instants_to_rollback,
+        };
+        timeline
+            .transition_inflight_to_completed(
+                &inflight,
+                Bytes::from(restore_metadata.to_avro_bytes()?),
+            )
+            .await?;
+
+        Ok(restore_metadata)
+    }
+
+    /// Get the instants of write actions after the given timestamp sorted from the latest to
+    /// the earliest, each in its latest state.
+    fn writes_after(instants: &[Instant], timestamp: &str) -> Vec<Instant> {
+        let mut writes: BTreeMap<&str, &Instant> = BTreeMap::new();
+        for instant in instants.iter().filter(|i| {
+            DEFAULT_LOADING_ACTIONS.contains(&i.action) && i.timestamp.as_str() > timestamp
+        }) {
+            writes
+                .entry(instant.timestamp.as_str())
+                .and_modify(|i| *i = (*i).max(instant))
+                .or_insert(instant);
+        }
+        writes.into_values().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table::TableTypeValue;
+    use crate::table::creator::TableCreator;
+    use crate::table::test_util::{data_schema, list_file_names, read_names, records};
+    use std::path::Path;
+
+    const BUCKET_OPTIONS: [(&str, &str); 2] = [
+        ("hoodie.index.type", "BUCKET"),
+        ("hoodie.bucket.index.num.buckets", "2"),
+    ];
+
+    async fn load_table(base_path: &str) -> Table {
+        Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap()
+    }
+
+    async fn test_restore_to_savepoint(table_type: TableTypeValue, table_version: isize) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(table_type.clone())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_table_version(table_version)
+            .with_hudi_options(BUCKET_OPTIONS)
+            .create()
+            .await
+            .unwrap();
+        let table = load_table(base_path).await;
+        table
+            .upsert(&[records(&[(1, "a", "sf", 1), (2, "b", "nyc", 1)])])
+            .await
+            .unwrap();
+        if table_version >= 8 {
+            load_table(base_path)
+                .await
+                .bootstrap_metadata_table()
+                .await
+                .unwrap();
+        }
+        let expected = read_names(base_path).await;
+
+        let table = load_table(base_path).await;
+        assert_eq!(table.is_metadata_table_enabled(), table_version >= 8);
+        let savepoint_time = table.timeline.get_latest_commit_timestamp().unwrap();
+        table.savepoint("ops", "").await.unwrap();
+        let mut later_writes = Vec::new();
+        let result = table
+            .upsert(&[records(&[(1, "a2", "sf", 2), (3, "c", "la", 1)])])
+            .await
+            .unwrap();
+        later_writes.push(result.instant.timestamp);
+        let table = load_table(base_path).await;
+        let result = table
+            .insert_overwrite(&[records(&[(4, "d", "sf", 1)])])
+            .await
+            .unwrap();
+        later_writes.push(result.instant.timestamp);
+
+        // A failed write left pending with a partially written file.
+        let table = load_table(base_path).await;
+        let pending_time = table
+            .timeline
+            .new_instant_timestamp(later_writes.last().map(String::as_str))
+            .unwrap();
+        let action = if table_type == TableTypeValue::MergeOnRead {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        let requested = table
+            .timeline
+            .create_requested_instant(action, &pending_time, Bytes::new())
+            .await
+            .unwrap();
+        table
+            .timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await
+            .unwrap();
+        let partial_file = Path::new(base_path).join(format!(
+            "sf/a80c27f5-2f65-435f-8308-7a4b85f01626-0_0-1-1_{pending_time}.parquet"
+        ));
+        std::fs::write(&partial_file, b"partial").unwrap();
+        later_writes.push(pending_time);
+        assert_ne!(read_names(base_path).await, expected);
+
+        // Later savepoints need to be deleted first.
+        let table = load_table(base_path).await;
+        table.savepoint("ops", "").await.unwrap();
+        assert!(matches!(
+            table
+                .restore_to_savepoint("20200101000000000")
+                .await
+                .unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        assert!(matches!(
+            table
+                .restore_to_savepoint(&savepoint_time)
+                .await
+                .unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        table.delete_savepoint(&later_writes[1]).await.unwrap();
+
+        let metadata = table.restore_to_savepoint(&savepoint_time).await.unwrap();
+        later_writes.reverse();
+        assert_eq!(metadata.instants_to_rollback, later_writes);
+        assert_eq!(
+            metadata
+                .restore_instant_info
+                .iter()
+                .map(|i| i.commit_time.as_str())
+                .collect::<Vec<_>>(),
+            later_writes
+        );
+        assert_eq!(metadata.hoodie_restore_metadata.len(), 3);
+        assert!(!partial_file.exists());
+        assert_eq!(read_names(base_path).await, expected);
+
+        let timeline_dir = if table_version >= 8 {
+            Path::new(base_path).join(".hoodie/timeline")
+        } else {
+            Path::new(base_path).join(".hoodie")
+        };
+        let timeline_files = list_file_names(&timeline_dir);
+        for ts in &later_writes {
+            assert!(timeline_files.iter().all(|name| !name.starts_with(ts)));
+        }
+        let restore_ts = &metadata.start_restore_time;
+        let completed = timeline_files
+            .iter()
+            .find(|name| name.starts_with(restore_ts.as_str()) && name.ends_with(".restore"))
+            .unwrap();
+        let data = std::fs::read(timeline_dir.join(completed)).unwrap();
+        assert_eq!(
+            HoodieRestoreMetadata::from_avro_bytes(&data).unwrap(),
+            metadata
+        );
+        let data =
+            std::fs::read(timeline_dir.join(format!("{restore_ts}.restore.requested"))).unwrap();
+        assert_eq!(
+            HoodieRestorePlan::from_avro_bytes(&data)
+                .unwrap()
+                .instants_to_rollback,
+            metadata.restore_instant_info
+        );
+        for partition in ["sf", "nyc", "la"] {
+            for name in list_file_names(&Path::new(base_path).join(partition)) {
+                assert!(later_writes.iter().all(|ts| !name.contains(ts.as_str())));
+            }
+        }
+
+        // The restored table can be written again.
+        let table = load_table(base_path).await;
+        assert_eq!(table.timeline.completed_commits.len(), 1);
+        table
+            .upsert(&[records(&[(1, "a3", "sf", 3), (5, "e", "la", 1)])])
+            .await
+            .unwrap();
+        assert_eq!(
+            read_names(base_path).await,
+            vec![
+                ("1".to_string(), "a3".to_string()),
+                ("2".to_string(), "b".to_string()),
+                ("5".to_string(), "e".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_savepoint_cow_v9() {
+        test_restore_to_savepoint(TableTypeValue::CopyOnWrite, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_savepoint_cow_v6() {
+        test_restore_to_savepoint(TableTypeValue::CopyOnWrite, 6).await;
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_savepoint_mor_v9() {
+        test_restore_to_savepoint(TableTypeValue::MergeOnRead, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_savepoint_mor_v6() {
+        test_restore_to_savepoint(TableTypeValue::MergeOnRead, 6).await;
+    }
+}
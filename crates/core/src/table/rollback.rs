@@ -0,0 +1,924 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Rolling back failed or inflight writes to a Hudi table.
+//!
+//! A crashed writer leaves its instant requested or inflight on the timeline, together with the
+//! files it has partially written. Rolling back such a pending instant deletes the files written
+//! by it, appends rollback command blocks to the log files it appended to existing file groups,
+//! records a `rollback` instant with [HoodieRollbackMetadata] on the [crate::timeline::Timeline],
+//! and removes the pending instant.
+//!
+//! The written files are found from the markers under `.hoodie/.temp/<instant time>`, or by
+//! listing the partitions if the instant has no markers.
+
+use crate::Result;
+use crate::config::lock::HudiLockConfig::WriteConcurrencyMode;
+use crate::config::lock::WriteConcurrencyModeValue;
+use crate::error::CoreError;
+use crate::file_group::base_file::BaseFile;
+use crate::file_group::log_file::LogFile;
+use crate::file_group::log_file::writer::LogFileWriter;
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::rollback::{
+    HoodieInstantInfo, HoodieRollbackMetadata, HoodieRollbackPartitionMetadata, HoodieRollbackPlan,
+    HoodieRollbackRequest,
+};
+use crate::metadata::table::writer::FilesPartitionChanges;
+use crate::storage::get_leaf_dirs;
+use crate::storage::util::join_url_segments;
+use crate::table::Table;
+use crate::table::listing::FileLister;
+use crate::table::partition::PartitionPruner;
+use crate::timeline::DEFAULT_LOADING_ACTIONS;
+use crate::timeline::instant::{Action, Instant, State};
+use crate::timeline::selector::TimelineSelector;
+use bytes::Bytes;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::str::FromStr;
+
+/// The version of [HoodieRollbackPlan] and [HoodieRollbackMetadata] written by the rollback.
+const ROLLBACK_METADATA_VERSION: i32 = 1;
+
+/// The write token of the log files written for rollback command blocks.
+const ROLLBACK_WRITE_TOKEN: &str = "0-0-0";
+
+/// The directory holding a subdirectory of markers for each instant.
+pub(crate) const MARKER_BASE_DIR: &str = ".hoodie/.temp";
+
+/// The file that tells the markers are written by the timeline server, in `MARKERS<n>` files.
+const MARKER_TYPE_FILE_NAME: &str = "MARKERS.type";
+
+/// Files written by a pending instant, to be rolled back.
+#[derive(Clone, Debug)]
+pub(crate) struct RollbackPlan {
+    pub instant: Instant,
+    /// Partition path to the names of the files created by the instant, to be deleted.
+    pub partition_to_files: BTreeMap<String, BTreeSet<String>>,
+    /// Partition path to the names and sizes of the log files that the instant appended to
+    /// file groups created before it, to be rolled back by command blocks.
+    pub partition_to_appended_log_files: BTreeMap<String, BTreeMap<String, i64>>,
+}
+
+impl RollbackPlan {
+    fn new(instant: Instant) -> Self {
+        Self {
+            instant,
+            partition_to_files: BTreeMap::new(),
+            partition_to_appended_log_files: BTreeMap::new(),
+        }
+    }
+}
+
+impl Table {
+    /// Roll back the pending instant of a write action at the given instant time.
+    ///
+    /// The files written by the instant are deleted or rolled back by log command blocks, a
+    /// `rollback` instant is recorded on the timeline, and the pending instant is removed. If the
+    /// metadata table is enabled, the deleted files are also synced to its `files` partition.
+    ///
+    /// The [Table] instance is not refreshed after the rollback; create a new instance to
+    /// read the updated timeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no pending instant of a write action at the instant time,
+    /// e.g., the instant is completed.
+    pub async fn rollback(&self, instant_time: &str) -> Result<HoodieRollbackMetadata> {
+        if self.is_metadata_table_enabled() {
+            self.ensure_metadata_table_writable()?;
+        }
+
+        let instants = self.load_all_instants().await?;
+        let clustering = self.timeline.get_requested_clustering_timestamps().await?;
+        let Some(pending) = Self::pending_writes(&instants, &clustering)
+            .into_iter()
+            .find(|i| i.timestamp == instant_time)
+        else {
+            let matched = instants
+                .iter()
+                .filter(|i| i.timestamp == instant_time)
+                .max();
+            return Err(match matched {
+                None => CoreError::Timeline(format!("Instant {instant_time} is not found")),
+                Some(i) if i.state == State::Completed => CoreError::Timeline(format!(
+                    "Instant {instant_time} is completed and cannot be rolled back"
+                )),
+                Some(_) if clustering.contains(instant_time) => CoreError::Unsupported(format!(
+                    "Rolling back pending clustering {instant_time} is not supported"
+                )),
+                Some(i) => CoreError::Unsupported(format!(
+                    "Rolling back {} instants is not supported",
+                    i.action.as_ref()
+                )),
+            });
+        };
+
+        let plan = self.plan_rollback(pending).await?;
+        let latest_timestamp = instants.iter().map(|i| i.timestamp.as_str()).max();
+        self.execute_rollback(&plan, latest_timestamp).await
+    }
+
+    /// Roll back all pending instants of write actions, from the latest to the earliest.
+    ///
+    /// This is only supported for [WriteConcurrencyModeValue::SingleWriter], where any pending
+    /// write is a failed one; with concurrent writers, a pending write may still be running.
+    pub async fn rollback_failed_writes(&self) -> Result<Vec<HoodieRollbackMetadata>> {
+        let mode: String = self
+            .hudi_configs
+            .get_or_default(WriteConcurrencyMode)
+            .into();
+        if WriteConcurrencyModeValue::from_str(&mode)? != WriteConcurrencyModeValue::SingleWriter {
+            return Err(CoreError::Unsupported(format!(
+                "Rolling back failed writes is not supported for write concurrency mode {mode}"
+            )));
+        }
+
+        let instants = self.load_all_instants().await?;
+        let clustering = self.timeline.get_requested_clustering_timestamps().await?;
+        let mut rollbacks = Vec::new();
+        for pending in Self::pending_writes(&instants, &clustering) {
+            rollbacks.push(self.rollback(&pending.timestamp).await?);
+        }
+        Ok(rollbacks)
+    }
+
+    /// Load the instants of all actions and states on the active timeline.
+    pub(crate) async fn load_all_instants(&self) -> Result<Vec<Instant>> {
+        let selector = TimelineSelector::actions_in_states(&[], &[], self.hudi_configs.clone())?;
+        self.timeline.load_instants(&selector, false).await
+    }
+
+    /// Get the pending instants of write actions sorted from the latest to the earliest, each in
+    /// its latest state.
+    ///
+    /// Pending clusterings at the `clustering` timestamps are left out as in Hudi Java, since
+    /// they are table services to be retried rather than failed writes.
+    pub(crate) fn pending_writes(
+        instants: &[Instant],
+        clustering: &HashSet<String>,
+    ) -> Vec<Instant> {
+        let completed: HashSet<&str> = instants
+            .iter()
+            .filter(|i| i.state == State::Completed)
+            .map(|i| i.timestamp.as_str())
+            .collect();
+        let mut pending: BTreeMap<&str, &Instant> = BTreeMap::new();
+        for instant in instants.iter().filter(|i| {
+            DEFAULT_LOADING_ACTIONS.contains(&i.action)
+                && !completed.contains(i.timestamp.as_str())
+                && !clustering.contains(&i.timestamp)
+        }) {
+            pending
+                .entry(instant.timestamp.as_str())
+                .and_modify(|i| *i = (*i).max(instant))
+                .or_insert(instant);
+        }
+        pending.into_values().rev().cloned().collect()
+    }
+
+    /// Find the files written by the instant without modifying the table.
+    ///
+    /// The markers of a completed instant have been deleted, so its written files are always
+    /// found by listing.
+    pub(crate) async fn plan_rollback(&self, instant: Instant) -> Result<RollbackPlan> {
+        let mut written = if instant.state == State::Completed {
+            Vec::new()
+        } else {
+            self.list_marked_files(&instant.timestamp).await?
+        };
+        if written.is_empty() {
+            written = self.list_files_written_by(&instant).await?;
+        }
+
+        let mut plan = RollbackPlan::new(instant);
+        let mut appended: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (partition_path, file_name) in written {
+            // A log file named with an earlier instant time belongs to a file group created
+            // before the pending instant, which only appended to it.
+            match LogFile::from_str(&file_name) {
+                Ok(log_file) if log_file.timestamp != plan.instant.timestamp => {
+                    appended
+                        .entry(partition_path)
+                        .or_default()
+                        .insert(file_name);
+                }
+                _ => {
+                    plan.partition_to_files
+                        .entry(partition_path)
+                        .or_default()
+                        .insert(file_name);
+                }
+            }
+        }
+
+        let storage = &self.file_system_view.storage;
+        for (partition_path, file_names) in appended {
+            let log_files: BTreeMap<String, i64> = storage
+                .list_files(Some(&partition_path))
+                .await?
+                .into_iter()
+                .filter(|f| file_names.contains(&f.name))
+                .map(|f| (f.name, f.size as i64))
+                .collect();
+            if !log_files.is_empty() {
+                plan.partition_to_appended_log_files
+                    .insert(partition_path, log_files);
+            }
+        }
+        Ok(plan)
+    }
+
+    /// List the files marked by the instant as `(partition path, file name)`.
+    ///
+    /// Both the markers written directly by the writers and the ones written by the timeline
+    /// server are supported.
+    async fn list_marked_files(&self, instant_time: &str) -> Result<Vec<(String, String)>> {
+        let storage = &self.file_system_view.storage;
+        let marker_dir = format!("{MARKER_BASE_DIR}/{instant_time}");
+        let marker_paths = self.list_marker_paths(instant_time).await?;
+
+        let mut markers = Vec::new();
+        if marker_paths
+            .iter()
+            .any(|p| p.ends_with(&format!("/{MARKER_TYPE_FILE_NAME}")))
+        {
+            for path in marker_paths
+                .iter()
+                .filter(|p| !p.ends_with(&format!("/{MARKER_TYPE_FILE_NAME}")))
+            {
+                let data = storage.get_file_data(path).await?;
+                markers.extend(
+                    String::from_utf8_lossy(&data)
+                        .lines()
+                        .filter(|l| !l.is_empty())
+                        .map(str::to_string),
+                );
+            }
+        } else {
+            markers.extend(marker_paths.iter().filter_map(|p| {
+                p.strip_prefix(&marker_dir)
+                    .map(|m| m.trim_start_matches('/').to_string())
+            }));
+        }
+
+        let mut marked_files: Vec<(String, String)> = markers
+            .iter()
+            .filter_map(|m| m.rsplit_once(".marker."))
+            .map(|(path, _)| match path.rsplit_once('/') {
+                Some((partition_path, file_name)) => {
+                    (partition_path.to_string(), file_name.to_string())
+                }
+                None => (String::new(), path.to_string()),
+            })
+            .collect();
+        marked_files.sort_unstable();
+        marked_files.dedup();
+        Ok(marked_files)
+    }
+
+    /// List the relative paths of the marker files of the instant.
+    pub(crate) async fn list_marker_paths(&self, instant_time: &str) -> Result<Vec<String>> {
+        let storage = &self.file_system_view.storage;
+        let marker_dir = format!("{MARKER_BASE_DIR}/{instant_time}");
+        let mut paths = Vec::new();
+        for dir in get_leaf_dirs(storage, Some(&marker_dir)).await? {
+            for file in storage.list_files(Some(&dir)).await? {
+                paths.push(format!("{dir}/{}", file.name));
+            }
+        }
+        Ok(paths)
+    }
+
+    /// List the files written by the instant from all partitions as
+    /// `(partition path, file name)`.
+    ///
+    /// Files created by the instant are named with its instant time. Log files appended to
+    /// existing file groups are named with the instant time of the file slice instead, which
+    /// are found from the write stats of the completed instant, or the ones saved to the
+    /// inflight instant by table version 6 writers.
+    async fn list_files_written_by(&self, instant: &Instant) -> Result<Vec<(String, String)>> {
+        let storage = &self.file_system_view.storage;
+        let lister = FileLister::new(
+            self.hudi_configs.clone(),
+            storage.clone(),
+            PartitionPruner::empty(),
+        );
+        let mut written = Vec::new();
+        for partition_path in lister.list_relevant_partition_paths().await? {
+            for file in storage.list_files(Some(&partition_path)).await? {
+                let is_written = if let Ok(log_file) = LogFile::from_str(&file.name) {
+                    log_file.timestamp == instant.timestamp
+                } else if let Ok(base_file) = BaseFile::from_str(&file.name) {
+                    base_file.commit_timestamp == instant.timestamp
+                } else {
+                    false
+                };
+                if is_written {
+                    written.push((partition_path.clone(), file.name));
+                }
+            }
+        }
+
+        if instant.state != State::Requested {
+            match self
+                .timeline
+                .get_instant_metadata(instant)
+                .await
+                .and_then(|m| HoodieCommitMetadata::from_json_map(&m))
+            {
+                Ok(metadata) => {
+                    for (partition_path, stat) in metadata.iter_write_stats() {
+                        let Some(path) = stat.path.as_deref() else {
+                            continue;
+                        };
+                        let file_name = path.rsplit('/').next().unwrap_or(path);
+                        if LogFile::from_str(file_name).is_ok() {
+                            written.push((partition_path.to_string(), file_name.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    log::debug!("No write stats found in instant {instant:?}: {e}");
+                }
+            }
+        }
+
+        written.sort_unstable();
+        written.dedup();
+        Ok(written)
+    }
+
+    /// Roll back the planned files and record the `rollback` instant on the timeline.
+    async fn execute_rollback(
+        &self,
+        plan: &RollbackPlan,
+        latest_timestamp: Option<&str>,
+    ) -> Result<HoodieRollbackMetadata> {
+        let timeline = &self.timeline;
+        let rollback_timestamp = timeline.new_instant_timestamp(latest_timestamp)?;
+
+        let rollback_plan = self.to_rollback_plan(plan)?;
+        let requested = timeline
+            .create_requested_instant(
+                Action::Rollback,
+                &rollback_timestamp,
+                Bytes::from(rollback_plan.to_avro_bytes()?),
+            )
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let rollback_metadata = self.rollback_files(plan, &rollback_timestamp).await?;
+        self.update_metadata_table(
+            &rollback_metadata.start_rollback_time,
+            &FilesPartitionChanges::from_rollback_metadata(&rollback_metadata).into(),
+        )
+        .await?;
+        timeline.delete_pending_instant(&plan.instant).await?;
+        timeline
+            .transition_inflight_to_completed(
+                &inflight,
+                Bytes::from(rollback_metadata.to_avro_bytes()?),
+            )
+            .await?;
+
+        Ok(rollback_metadata)
+    }
+
+    /// Delete the files planned to roll back, append rollback command blocks to the appended
+    /// log files, and delete the markers of the rolled back instant.
+    ///
+    /// The timeline and the metadata table are not modified.
+    pub(crate) async fn rollback_files(
+        &self,
+        plan: &RollbackPlan,
+        rollback_timestamp: &str,
+    ) -> Result<HoodieRollbackMetadata> {
+        let timer = std::time::Instant::now();
+        let storage = &self.file_system_view.storage;
+        let mut partition_metadata = BTreeMap::new();
+        let mut total_files_deleted = 0;
+        for (partition_path, file_names) in &plan.partition_to_files {
+            let metadata = partition_metadata
+                .entry(partition_path.to_string())
+                .or_insert_with(|| HoodieRollbackPartitionMetadata {
+                    partition_path: partition_path.to_string(),
+                    ..Default::default()
+                });
+            for file_name in file_names {
+                let path = relative_path(partition_path, file_name);
+                match storage.delete_file(&path).await {
+                    Ok(()) => metadata.success_delete_files.push(file_name.to_string()),
+                    Err(e) => {
+                        log::warn!("Failed to delete file '{path}' during rollback: {e}");
+                        metadata.failed_delete_files.push(file_name.to_string());
+                    }
+                }
+            }
+            total_files_deleted += metadata.success_delete_files.len() as i32;
+        }
+        for (partition_path, log_files) in &plan.partition_to_appended_log_files {
+            let rollback_log_files = self
+                .append_rollback_blocks(
+                    partition_path,
+                    log_files,
+                    rollback_timestamp,
+                    &plan.instant.timestamp,
+                )
+                .await?;
+            let metadata = partition_metadata
+                .entry(partition_path.to_string())
+                .or_insert_with(|| HoodieRollbackPartitionMetadata {
+                    partition_path: partition_path.to_string(),
+                    ..Default::default()
+                });
+            metadata.rollback_log_files = Some(rollback_log_files.into_iter().collect());
+            metadata.log_files_from_failed_commit =
+                Some(log_files.iter().map(|(k, v)| (k.clone(), *v)).collect());
+        }
+
+        let rollback_metadata = HoodieRollbackMetadata {
+            start_rollback_time: rollback_timestamp.to_string(),
+            time_taken_in_millis: timer.elapsed().as_millis() as i64,
+            total_files_deleted,
+            commits_rollback: vec![plan.instant.timestamp.clone()],
+            partition_metadata: partition_metadata.into_iter().collect(),
+            version: Some(ROLLBACK_METADATA_VERSION),
+            instants_rollback: vec![to_instant_info(&plan.instant)],
+        };
+
+        for path in self.list_marker_paths(&plan.instant.timestamp).await? {
+            storage.delete_file(&path).await?;
+        }
+        Ok(rollback_metadata)
+    }
+
+    /// Write a new log file with a rollback command block to each file group of the appended
+    /// log files, and return the names and sizes of the new log files.
+    async fn append_rollback_blocks(
+        &self,
+        partition_path: &str,
+        appended_log_files: &BTreeMap<String, i64>,
+        rollback_timestamp: &str,
+        target_timestamp: &str,
+    ) -> Result<BTreeMap<String, i64>> {
+        let storage = &self.file_system_view.storage;
+        let existing_log_files: Vec<LogFile> = storage
+            .list_files(Some(partition_path))
+            .await?
+            .into_iter()
+            .filter_map(|f| LogFile::from_str(&f.name).ok())
+            .collect();
+        let file_slices: BTreeSet<(String, String, String)> = appended_log_files
+            .keys()
+            .filter_map(|name| LogFile::from_str(name).ok())
+            .map(|l| (l.file_id, l.timestamp, l.extension))
+            .collect();
+
+        let mut rollback_log_files = BTreeMap::new();
+        for (file_id, timestamp, extension) in file_slices {
+            let latest_version = existing_log_files
+                .iter()
+                .filter(|l| {
+                    l.file_id == file_id && l.timestamp == timestamp && l.extension == extension
+                })
+                .map(|l| l.version)
+                .max()
+                .unwrap_or_default();
+            let log_file = LogFile {
+                file_id,
+                timestamp,
+                completion_timestamp: None,
+                extension,
+                version: latest_version + 1,
+                write_token: ROLLBACK_WRITE_TOKEN.to_string(),
+                file_metadata: None,
+            };
+            let file_name = log_file.file_name();
+            let mut writer =
+                LogFileWriter::new(storage.clone(), relative_path(partition_path, &file_name));
+            writer.append_rollback_command_block(rollback_timestamp, target_timestamp);
+            let size = writer.finish().await? as i64;
+            rollback_log_files.insert(file_name, size);
+        }
+        Ok(rollback_log_files)
+    }
+
+    fn to_rollback_plan(&self, plan: &RollbackPlan) -> Result<HoodieRollbackPlan> {
+        let base_url = self.base_url();
+        let full_path = |partition_path: &str, file_name: &str| -> Result<String> {
+            let path = relative_path(partition_path, file_name);
+            Ok(join_url_segments(&base_url, &[&path])?.to_string())
+        };
+
+        let mut requests: BTreeMap<(String, String), HoodieRollbackRequest> = BTreeMap::new();
+        for (partition_path, file_names) in &plan.partition_to_files {
+            for file_name in file_names {
+                let file_id = file_id_of(file_name);
+                requests
+                    .entry((partition_path.to_string(), file_id.clone()))
+                    .or_insert_with(|| HoodieRollbackRequest {
+                        partition_path: partition_path.to_string(),
+                        file_id: Some(file_id),
+                        latest_base_instant: Some(plan.instant.timestamp.clone()),
+                        ..Default::default()
+                    })
+                    .files_to_be_deleted
+                    .push(full_path(partition_path, file_name)?);
+            }
+        }
+        for (partition_path, log_files) in &plan.partition_to_appended_log_files {
+            for (file_name, size) in log_files {
+                let Ok(log_file) = LogFile::from_str(file_name) else {
+                    continue;
+                };
+                requests
+                    .entry((partition_path.to_string(), log_file.file_id.clone()))
+                    .or_insert_with(|| HoodieRollbackRequest {
+                        partition_path: partition_path.to_string(),
+                        file_id: Some(log_file.file_id.clone()),
+                        latest_base_instant: Some(log_file.timestamp.clone()),
+                        ..Default::default()
+                    })
+                    .log_blocks_to_be_deleted
+                    .get_or_insert_default()
+                    .insert(full_path(partition_path, file_name)?, *size);
+            }
+        }
+
+        Ok(HoodieRollbackPlan {
+            instant_to_rollback: Some(to_instant_info(&plan.instant)),
+            rollback_requests: Some(requests.into_values().collect()),
+            version: Some(ROLLBACK_METADATA_VERSION),
+        })
+    }
+}
+
+pub(crate) fn to_instant_info(instant: &Instant) -> HoodieInstantInfo {
+    HoodieInstantInfo {
+        commit_time: instant.timestamp.clone(),
+        action: instant.action.as_ref().to_string(),
+    }
+}
+
+fn relative_path(partition_path: &str, file_name: &str) -> String {
+    if partition_path.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{partition_path}/{file_name}")
+    }
+}
+
+/// Get the file id from the name of a base file or log file, or the name itself if it cannot
+/// be parsed.
+fn file_id_of(file_name: &str) -> String {
+    LogFile::from_str(file_name)
+        .map(|l| l.file_id)
+        .or_else(|_| BaseFile::from_str(file_name).map(|b| b.file_id))
+        .unwrap_or_else(|_| file_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HudiConfigs;
+    use crate::config::table::TableTypeValue;
+    use crate::file_group::log_file::reader::LogFileReader;
+    use crate::metadata::replace_commit::{HoodieClusteringPlan, HoodieRequestedReplaceMetadata};
+    use crate::table::creator::TableCreator;
+    use crate::table::test_util::read_snapshot_sorted;
+    use crate::timeline::selector::InstantRange;
+    use arrow_schema::{DataType, Field, Schema};
+    use hudi_test::SampleTable;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Create a pending instant as a crashed writer leaves it.
+    async fn create_pending_instant(
+        table: &Table,
+        action: Action,
+        after: Option<&str>,
+        inflight: bool,
+    ) -> Instant {
+        let timestamp = table.timeline.new_instant_timestamp(after).unwrap();
+        let requested = table
+            .timeline
+            .create_requested_instant(action, &timestamp, Bytes::new())
+            .await
+            .unwrap();
+        if inflight {
+            table
+                .timeline
+                .transition_requested_to_inflight(&requested, Bytes::new())
+                .await
+                .unwrap()
+        } else {
+            requested
+        }
+    }
+
+    fn write_file(base_path: &str, relative_path: &str, data: &[u8]) {
+        let path = Path::new(base_path).join(relative_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    }
+
+    fn timeline_file_names(timeline_dir: &Path) -> Vec<String> {
+        std::fs::read_dir(timeline_dir)
+            .unwrap()
+            .filter_map(|e| e.unwrap().file_name().into_string().ok())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rollback_inflight_commit_by_listing_v6() {
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        let expected = read_snapshot_sorted(&table, "_hoodie_record_key").await;
+
+        let pending = create_pending_instant(&table, Action::Commit, None, true).await;
+        let ts = &pending.timestamp;
+        let written = [
+            format!("10/4f2685a3-614f-49ca-9b2b-e1cb9fb61f27-0_0-1-1_{ts}.parquet"),
+            format!("30/9fa8c3b4-6f0e-4c8d-9d47-5c1b2a0e3d11-0_1-1-2_{ts}.parquet"),
+        ];
+        for path in &written {
+            write_file(&base_path, path, b"partial");
+        }
+
+        let metadata = table.rollback(ts).await.unwrap();
+        assert_eq!(metadata.total_files_deleted, 2);
+        assert_eq!(metadata.commits_rollback, vec![ts.to_string()]);
+        assert_eq!(
+            metadata.instants_rollback,
+            vec![HoodieInstantInfo {
+                commit_time: ts.to_string(),
+                action: "commit".to_string(),
+            }]
+        );
+        let mut deleted: Vec<String> = metadata.iter_deleted_file_paths().collect();
+        deleted.sort_unstable();
+        assert_eq!(deleted, written);
+        for path in &written {
+            assert!(!Path::new(&base_path).join(path).exists());
+        }
+
+        let timeline_dir = Path::new(&base_path).join(".hoodie");
+        let file_names = timeline_file_names(&timeline_dir);
+        assert!(file_names.iter().all(|name| !name.starts_with(ts.as_str())));
+        let rollback_ts = &metadata.start_rollback_time;
+        assert!(rollback_ts > ts);
+        for suffix in ["rollback.requested", "rollback.inflight", "rollback"] {
+            assert!(file_names.contains(&format!("{rollback_ts}.{suffix}")));
+        }
+        let data = std::fs::read(timeline_dir.join(format!("{rollback_ts}.rollback"))).unwrap();
+        assert_eq!(
+            HoodieRollbackMetadata::from_avro_bytes(&data).unwrap(),
+            metadata
+        );
+        let data =
+            std::fs::read(timeline_dir.join(format!("{rollback_ts}.rollback.requested"))).unwrap();
+        let plan = HoodieRollbackPlan::from_avro_bytes(&data).unwrap();
+        assert_eq!(plan.rollback_requests.unwrap().len(), 2);
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(
+            read_snapshot_sorted(&table, "_hoodie_record_key").await,
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_with_markers_v9() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        Table::new(&base_path)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(table.is_metadata_table_enabled());
+        let expected = read_snapshot_sorted(&table, "_hoodie_record_key").await;
+
+        let pending = create_pending_instant(&table, Action::Commit, None, true).await;
+        let ts = &pending.timestamp;
+        let marked = format!("region=eu/a80c27f5-2f65-435f-8308-7a4b85f01626-0_0-1-1_{ts}.parquet");
+        write_file(&base_path, &marked, b"partial");
+        let marker = format!(".hoodie/.temp/{ts}/{marked}.marker.MERGE");
+        write_file(&base_path, &marker, b"");
+
+        let metadata = table.rollback(ts).await.unwrap();
+        assert_eq!(metadata.total_files_deleted, 1);
+        assert_eq!(
+            metadata.iter_deleted_file_paths().collect::<Vec<_>>(),
+            vec![marked.clone()]
+        );
+        assert!(!Path::new(&base_path).join(&marked).exists());
+        assert!(!Path::new(&base_path).join(&marker).exists());
+
+        let timeline_dir = Path::new(&base_path).join(".hoodie/timeline");
+        let file_names = timeline_file_names(&timeline_dir);
+        assert!(file_names.iter().all(|name| !name.starts_with(ts.as_str())));
+        let rollback_ts = &metadata.start_rollback_time;
+        assert!(file_names.iter().any(
+            |name| name.starts_with(&format!("{rollback_ts}_")) && name.ends_with(".rollback")
+        ));
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(
+            read_snapshot_sorted(&table, "_hoodie_record_key").await,
+            expected
+        );
+        let mdt = table.new_metadata_table().await.unwrap();
+        assert!(
+            mdt.timeline
+                .completed_commits
+                .iter()
+                .any(|i| &i.timestamp == rollback_ts)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_appended_log_files_v6() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base_path = temp_dir.path().to_str().unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("city", DataType::Utf8, true),
+        ]));
+        let table = TableCreator::new(base_path, "trips", schema)
+            .with_table_type(TableTypeValue::MergeOnRead)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_table_version(6)
+            .create()
+            .await
+            .unwrap();
+
+        let base_instant = "20240101000000000";
+        let appended = format!(".f1-0_{base_instant}.log.1_0-1-1");
+        write_file(
+            base_path,
+            &format!("sf/f1-0_0-0-0_{base_instant}.parquet"),
+            b"",
+        );
+        write_file(base_path, &format!("sf/{appended}"), b"appended");
+        let pending = create_pending_instant(&table, Action::DeltaCommit, None, true).await;
+        let ts = &pending.timestamp;
+        let created = format!("f2-0_0-1-1_{ts}.parquet");
+        write_file(base_path, &format!("sf/{created}"), b"partial");
+        let markers = format!("sf/{appended}.marker.APPEND\nsf/{created}.marker.CREATE\n");
+        write_file(
+            base_path,
+            &format!(".hoodie/.temp/{ts}/MARKERS.type"),
+            b"TIMELINE_SERVER_BASED",
+        );
+        write_file(
+            base_path,
+            &format!(".hoodie/.temp/{ts}/MARKERS0"),
+            markers.as_bytes(),
+        );
+
+        let metadata = table.rollback(ts).await.unwrap();
+        assert_eq!(metadata.total_files_deleted, 1);
+        let partition_metadata = &metadata.partition_metadata["sf"];
+        assert_eq!(
+            partition_metadata.success_delete_files,
+            vec![created.clone()]
+        );
+        assert!(!Path::new(base_path).join("sf").join(&created).exists());
+        assert!(Path::new(base_path).join("sf").join(&appended).exists());
+        assert_eq!(
+            partition_metadata.log_files_from_failed_commit,
+            Some([(appended.clone(), 8)].into_iter().collect())
+        );
+
+        let rollback_log_file = format!(".f1-0_{base_instant}.log.2_0-0-0");
+        let rollback_log_files = partition_metadata.rollback_log_files.as_ref().unwrap();
+        assert_eq!(
+            rollback_log_files.keys().collect::<Vec<_>>(),
+            vec![&rollback_log_file]
+        );
+        let mut reader = LogFileReader::new(
+            Arc::new(HudiConfigs::empty()),
+            table.file_system_view.storage.clone(),
+            &format!("sf/{rollback_log_file}"),
+        )
+        .await
+        .unwrap();
+        let blocks = reader
+            .read_all_blocks(&InstantRange::up_to(&metadata.start_rollback_time, "utc"))
+            .unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].is_rollback_block());
+        assert_eq!(blocks[0].target_instant_time().unwrap(), ts);
+        assert!(
+            std::fs::read_dir(Path::new(base_path).join(format!(".hoodie/.temp/{ts}")))
+                .unwrap()
+                .next()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_failed_writes() {
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        let latest_commit = table.timeline.completed_commits.last().unwrap().clone();
+        assert!(matches!(
+            table.rollback(&latest_commit.timestamp).await.unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        assert!(matches!(
+            table.rollback("20200101000000000").await.unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+
+        let first = create_pending_instant(&table, Action::Commit, None, false).await;
+        let second =
+            create_pending_instant(&table, Action::ReplaceCommit, Some(&first.timestamp), true)
+                .await;
+        let rollbacks = table.rollback_failed_writes().await.unwrap();
+        let rolled_back: Vec<&str> = rollbacks
+            .iter()
+            .map(|m| m.commits_rollback[0].as_str())
+            .collect();
+        assert_eq!(rolled_back, vec![&second.timestamp, &first.timestamp]);
+        assert!(rollbacks[0].start_rollback_time < rollbacks[1].start_rollback_time);
+
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(table.rollback_failed_writes().await.unwrap().is_empty());
+        let options = [(
+            WriteConcurrencyMode.as_ref(),
+            "OPTIMISTIC_CONCURRENCY_CONTROL",
+        )];
+        let table = Table::new_with_options(&base_path, options).await.unwrap();
+        assert!(matches!(
+            table.rollback_failed_writes().await.unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_failed_writes_skips_pending_clustering() {
+        let base_path = SampleTable::V6SimplekeygenNonhivestyleOverwritetable.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        let requested = HoodieRequestedReplaceMetadata {
+            operation_type: Some("CLUSTER".to_string()),
+            clustering_plan: Some(HoodieClusteringPlan::default()),
+            ..Default::default()
+        };
+        let clustering_time = table.timeline.new_instant_timestamp(None).unwrap();
+        let clustering = table
+            .timeline
+            .create_requested_instant(
+                Action::ReplaceCommit,
+                &clustering_time,
+                Bytes::from(requested.to_avro_bytes().unwrap()),
+            )
+            .await
+            .unwrap();
+        table
+            .timeline
+            .transition_requested_to_inflight(&clustering, Bytes::new())
+            .await
+            .unwrap();
+        let write =
+            create_pending_instant(&table, Action::Commit, Some(&clustering_time), true).await;
+
+        let rollbacks = table.rollback_failed_writes().await.unwrap();
+        let rolled_back: Vec<&str> = rollbacks
+            .iter()
+            .map(|m| m.commits_rollback[0].as_str())
+            .collect();
+        assert_eq!(rolled_back, vec![&write.timestamp]);
+        assert!(matches!(
+            table.rollback(&clustering_time).await.unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+
+        let timeline_files = timeline_file_names(&Path::new(&base_path).join(".hoodie"));
+        for state in ["requested", "inflight"] {
+            let file_name = format!("{clustering_time}.replacecommit.{state}");
+            assert!(timeline_files.contains(&file_name), "{file_name}");
+        }
+    }
+}
@@ -0,0 +1,295 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Savepointing a Hudi table.
+//!
+//! A savepoint records the files of the latest file slices of each partition as of a completed
+//! commit in a `savepoint` instant with [HoodieSavepointMetadata] on the
+//! [crate::timeline::Timeline]. The cleaner does not delete the savepointed files, and the
+//! archiver does not archive the instants since the earliest savepoint, so that the table can
+//! be restored to the savepoint by [Table::restore_to_savepoint].
+
+use crate::Result;
+use crate::error::CoreError;
+use crate::metadata::savepoint::{HoodieSavepointMetadata, HoodieSavepointPartitionMetadata};
+use crate::table::{ReadOptions, Table};
+use crate::timeline::instant::Action;
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashSet};
+
+/// The version of [HoodieSavepointMetadata] written by the savepoint.
+const SAVEPOINT_METADATA_VERSION: i32 = 1;
+
+impl Table {
+    /// Savepoint the table at the latest completed commit.
+    ///
+    /// See [Table::savepoint_at].
+    pub async fn savepoint(&self, user: &str, comments: &str) -> Result<HoodieSavepointMetadata> {
+        let latest_commit = self.timeline.get_latest_commit_timestamp()?;
+        self.savepoint_at(&latest_commit, user, comments).await
+    }
+
+    /// Savepoint the table at the completed commit of the given instant time.
+    ///
+    /// The files of the latest file slices as of the commit are recorded in a `savepoint`
+    /// instant with the same instant time as the commit.
+    ///
+    /// The [Table] instance is not refreshed after the savepoint; create a new instance to
+    /// read the updated timeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no completed commit at the instant time, if the commit is
+    /// already savepointed, or if the files of the commit may have been cleaned, i.e., the
+    /// commit is before the earliest commit retained by the latest clean.
+    pub async fn savepoint_at(
+        &self,
+        instant_time: &str,
+        user: &str,
+        comments: &str,
+    ) -> Result<HoodieSavepointMetadata> {
+        let timeline = &self.timeline;
+        let Some(commit) = timeline
+            .completed_commits
+            .iter()
+            .find(|c| c.timestamp == instant_time)
+        else {
+            return Err(CoreError::Timeline(format!(
+                "Commit {instant_time} is not found"
+            )));
+        };
+        if timeline
+            .get_completed_savepoints(false)
+            .await?
+            .iter()
+            .any(|s| s.timestamp == instant_time)
+        {
+            return Err(CoreError::Timeline(format!(
+                "Commit {instant_time} is already savepointed"
+            )));
+        }
+        if let Some(clean) = timeline.get_completed_cleans(true).await?.first() {
+            let earliest_retained = timeline
+                .get_clean_metadata(clean)
+                .await?
+                .earliest_commit_to_retain;
+            if instant_time < earliest_retained.as_str() {
+                return Err(CoreError::Timeline(format!(
+                    "Commit {instant_time} is before the earliest commit retained by the latest clean {earliest_retained}"
+                )));
+            }
+        }
+
+        let options = ReadOptions::new().with_as_of_timestamp(instant_time);
+        let mut partition_to_files: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for slice in self.get_file_slices(&options).await? {
+            let files = partition_to_files
+                .entry(slice.partition_path.clone())
+                .or_default();
+            files.push(slice.base_file.file_name());
+            files.extend(slice.log_files.iter().map(|l| l.file_name()));
+        }
+        let metadata = HoodieSavepointMetadata {
+            savepointed_by: user.to_string(),
+            savepointed_at: Utc::now().timestamp_millis(),
+            comments: comments.to_string(),
+            partition_metadata: partition_to_files
+                .into_iter()
+                .map(|(partition_path, mut files)| {
+                    files.sort_unstable();
+                    let partition_metadata = HoodieSavepointPartitionMetadata {
+                        partition_path: partition_path.clone(),
+                        savepoint_data_file: files,
+                    };
+                    (partition_path, partition_metadata)
+                })
+                .collect(),
+            version: Some(SAVEPOINT_METADATA_VERSION),
+        };
+
+        let requested = timeline
+            .create_requested_instant(Action::Savepoint, &commit.timestamp, Bytes::new())
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+        timeline
+            .transition_inflight_to_completed(&inflight, Bytes::from(metadata.to_avro_bytes()?))
+            .await?;
+        Ok(metadata)
+    }
+
+    /// Delete the savepoint at the given instant time, so that its files can be cleaned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no completed savepoint at the instant time.
+    pub async fn delete_savepoint(&self, instant_time: &str) -> Result<()> {
+        let savepoints = self.timeline.get_completed_savepoints(false).await?;
+        let Some(savepoint) = savepoints.iter().find(|s| s.timestamp == instant_time) else {
+            return Err(CoreError::Timeline(format!(
+                "Savepoint {instant_time} is not found"
+            )));
+        };
+        self.timeline.delete_instant(savepoint).await
+    }
+
+    /// Get the relative paths of the files recorded by all completed savepoints.
+    pub(crate) async fn get_savepointed_files(&self) -> Result<HashSet<String>> {
+        let mut files = HashSet::new();
+        for savepoint in self.timeline.get_completed_savepoints(false).await? {
+            let metadata = self.timeline.get_savepoint_metadata(&savepoint).await?;
+            files.extend(metadata.iter_savepointed_file_paths());
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::archive::HudiArchiveConfig::{MaxCommitsToKeep, MinCommitsToKeep};
+    use crate::config::clean::HudiCleanConfig::CommitsRetained;
+    use crate::config::table::TableTypeValue;
+    use crate::table::creator::TableCreator;
+    use crate::table::test_util::{data_schema, records};
+    use std::path::Path;
+
+    async fn load_table(base_path: &str) -> Table {
+        let options = [
+            ("hoodie.index.type", "BUCKET"),
+            ("hoodie.bucket.index.num.buckets", "2"),
+            (CommitsRetained.as_ref(), "1"),
+            (MinCommitsToKeep.as_ref(), "1"),
+            (MaxCommitsToKeep.as_ref(), "2"),
+        ];
+        Table::new_with_options(base_path, options).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_savepointed_files_are_not_cleaned_or_archived() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(TableTypeValue::CopyOnWrite)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .create()
+            .await
+            .unwrap();
+        let mut commits = Vec::new();
+        for ts in 1..=3 {
+            let table = load_table(base_path).await;
+            let result = table
+                .upsert(&[records(&[(1, "a", "sf", ts), (2, "b", "nyc", ts)])])
+                .await
+                .unwrap();
+            commits.push(result.instant.timestamp);
+        }
+
+        let table = load_table(base_path).await;
+        assert!(matches!(
+            table
+                .savepoint_at("20200101000000000", "ops", "")
+                .await
+                .unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        let metadata = table
+            .savepoint_at(&commits[0], "ops", "before backfill")
+            .await
+            .unwrap();
+        assert_eq!(metadata.savepointed_by, "ops");
+        assert_eq!(metadata.comments, "before backfill");
+        let mut savepointed: Vec<String> = metadata.iter_savepointed_file_paths().collect();
+        savepointed.sort_unstable();
+        assert_eq!(savepointed.len(), 2);
+        assert!(savepointed[0].starts_with("nyc/"));
+        assert!(savepointed[1].starts_with("sf/"));
+        assert!(
+            savepointed
+                .iter()
+                .all(|f| f.ends_with(&format!("_{}.parquet", commits[0])))
+        );
+        assert!(matches!(
+            table
+                .savepoint_at(&commits[0], "ops", "")
+                .await
+                .unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+
+        let table = load_table(base_path).await;
+        let savepoints = table
+            .timeline
+            .get_completed_savepoints(false)
+            .await
+            .unwrap();
+        assert_eq!(savepoints.len(), 1);
+        assert_eq!(savepoints[0].timestamp, commits[0]);
+        assert_eq!(
+            table
+                .timeline
+                .get_savepoint_metadata(&savepoints[0])
+                .await
+                .unwrap(),
+            metadata
+        );
+
+        // The file slices of the first commit are out of the retained commits.
+        assert!(table.clean().await.unwrap().is_none());
+        for file in &savepointed {
+            assert!(Path::new(base_path).join(file).exists());
+        }
+        let table = load_table(base_path).await;
+        assert!(table.plan_archive().await.unwrap().is_empty());
+
+        table.delete_savepoint(&commits[0]).await.unwrap();
+        assert!(matches!(
+            table.delete_savepoint(&commits[0]).await.unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        let table = load_table(base_path).await;
+        assert!(
+            table
+                .timeline
+                .get_completed_savepoints(false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let clean_metadata = table.clean().await.unwrap().unwrap();
+        assert_eq!(clean_metadata.total_files_deleted, 2);
+        for file in &savepointed {
+            assert!(!Path::new(base_path).join(file).exists());
+        }
+
+        // The files of the first commit may have been cleaned.
+        let table = load_table(base_path).await;
+        assert!(matches!(
+            table
+                .savepoint_at(&commits[0], "ops", "")
+                .await
+                .unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        assert!(!table.plan_archive().await.unwrap().is_empty());
+    }
+}
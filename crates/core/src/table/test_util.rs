@@ -0,0 +1,95 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Test fixtures shared by the tests of writing to and maintaining Hudi tables.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::compute::{concat_batches, sort_to_indices, take_record_batch};
+use arrow_array::{Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::metadata::meta_field::MetaField;
+use crate::table::{ReadOptions, Table};
+
+/// The schema of the records of [records].
+pub(crate) fn data_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("ts", DataType::Int64, false),
+    ]))
+}
+
+/// Create the records of the `(id, name, city, ts)` rows.
+pub(crate) fn records(rows: &[(i64, &str, &str, i64)]) -> RecordBatch {
+    RecordBatch::try_new(
+        data_schema(),
+        vec![
+            Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.1))),
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.2))),
+            Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.3))),
+        ],
+    )
+    .unwrap()
+}
+
+/// Read the snapshot of the table as a single batch sorted by the `key` column.
+pub(crate) async fn read_snapshot_sorted(table: &Table, key: &str) -> RecordBatch {
+    let batches = table.read(&ReadOptions::new()).await.unwrap();
+    let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+    let indices = sort_to_indices(batch.column_by_name(key).unwrap(), None, None).unwrap();
+    take_record_batch(&batch, &indices).unwrap()
+}
+
+/// Read the sorted `(record key, name)` rows of the table at the base path.
+pub(crate) async fn read_names(base_path: &str) -> Vec<(String, String)> {
+    let table = Table::new(base_path).await.unwrap();
+    let batches = table.read(&ReadOptions::new()).await.unwrap();
+    let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+    let column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .clone()
+    };
+    let (keys, names) = (column(MetaField::RecordKey.as_ref()), column("name"));
+    let mut rows: Vec<(String, String)> = (0..keys.len())
+        .map(|i| (keys.value(i).to_string(), names.value(i).to_string()))
+        .collect();
+    rows.sort_unstable();
+    rows
+}
+
+/// List the sorted names of the files in the directory.
+pub(crate) fn list_file_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap())
+        .filter(|e| e.file_type().unwrap().is_file())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    names.sort_unstable();
+    names
+}
@@ -0,0 +1,771 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Upgrading and downgrading the table version of a Hudi table.
+//!
+//! Table versions 6, 8 and 9 are supported, and the table is migrated through the versions in
+//! between one step at a time. Between table versions 6 and 8, the active timeline is converted
+//! between timeline layouts 1 and 2:
+//!
+//! - The instant files are moved between `.hoodie/` and `.hoodie/timeline/`.
+//! - Completed instants are renamed between `{instant_time}.{action}` and
+//!   `{instant_time}_{completion_time}.{action}`. Timeline layout 1 does not record completion
+//!   times, so the instant time is used as the completion time, which keeps the order of the
+//!   instants that readers of timeline layout 1 rely on.
+//! - The commit metadata of `commit`, `deltacommit` and `replacecommit` instants is rewritten
+//!   between JSON and Avro.
+//! - The requested and inflight instants of clustering are renamed between the `replacecommit`
+//!   and `clustering` actions.
+//!
+//! The metadata table is deleted by any version change, as it is not migrated along with the
+//! data table; it can be bootstrapped again by [Table::bootstrap_metadata_table].
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use bytes::Bytes;
+
+use crate::Result;
+use crate::config::table::HudiTableConfig::{
+    KeyGeneratorClass, KeyGeneratorType, MetadataTablePartitions, OrderingFields, TableVersion,
+    TimelineHistoryPath, TimelineLayoutVersion, TimelinePath,
+};
+use crate::config::table::KeyGeneratorTypeValue;
+use crate::config::util::{format_data_for_options, parse_data_for_options};
+use crate::error::CoreError;
+use crate::metadata::HUDI_METADATA_DIR;
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::replace_commit::HoodieReplaceCommitMetadata;
+use crate::table::Table;
+use crate::timeline::instant::{Action, Instant, State};
+use crate::timeline::loader::TimelineLoader;
+
+/// The table versions that a table can be upgraded or downgraded between.
+pub const SUPPORTED_TABLE_VERSIONS: [isize; 3] = [6, 8, 9];
+
+const PRECOMBINE_FIELD: &str = "hoodie.table.precombine.field";
+const INITIAL_VERSION: &str = "hoodie.table.initial.version";
+const RECORD_MERGE_MODE: &str = "hoodie.record.merge.mode";
+const RECORD_MERGE_STRATEGY_ID: &str = "hoodie.record.merge.strategy.id";
+const PAYLOAD_CLASS: &str = "hoodie.compaction.payload.class";
+const TABLE_FORMAT: &str = "hoodie.table.format";
+const METADATA_TABLE_PARTITIONS_INFLIGHT: &str = "hoodie.table.metadata.partitions.inflight";
+
+const COMMIT_TIME_ORDERING: &str = "COMMIT_TIME_ORDERING";
+const EVENT_TIME_ORDERING: &str = "EVENT_TIME_ORDERING";
+const COMMIT_TIME_MERGE_STRATEGY_ID: &str = "ce9acb64-bde0-424c-9b91-f6ebba25356d";
+const EVENT_TIME_MERGE_STRATEGY_ID: &str = "eeb8d96f-b1e4-49fd-bbf8-28ac514178e5";
+const DEFAULT_PAYLOAD_CLASS: &str = "org.apache.hudi.common.model.DefaultHoodieRecordPayload";
+const OVERWRITE_WITH_LATEST_PAYLOAD_CLASS: &str =
+    "org.apache.hudi.common.model.OverwriteWithLatestAvroPayload";
+
+const CLUSTERING_ACTION: &str = "clustering";
+
+const PROPERTIES_PATH: &str = ".hoodie/hoodie.properties";
+const METADATA_TABLE_DIR: &str = ".hoodie/metadata";
+
+impl Table {
+    /// Upgrade or downgrade the table to the given table version.
+    ///
+    /// `hoodie.properties` is migrated the same way as Hudi Java does for each version step,
+    /// and the active timeline is converted between the timeline layouts if needed. The metadata
+    /// table is deleted. Changing to the current table version does nothing.
+    ///
+    /// The [Table] instance is not refreshed after the change; create a new instance to read
+    /// the table of the new version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is a metadata table, the current or the given table version is
+    /// not one of [SUPPORTED_TABLE_VERSIONS], or there are pending instants, which need to be
+    /// completed or rolled back first. Converting the timeline layout is not supported if the
+    /// table has an archived timeline, and migrating between table versions 8 and 9 is not
+    /// supported for custom payload classes.
+    pub async fn change_table_version(&self, table_version: isize) -> Result<()> {
+        if self.is_metadata_table() {
+            return Err(CoreError::MetadataTable(
+                "Cannot change the table version of a metadata table".to_string(),
+            ));
+        }
+        let current_version: isize = self.hudi_configs.get(TableVersion)?.into();
+        for version in [current_version, table_version] {
+            if !SUPPORTED_TABLE_VERSIONS.contains(&version) {
+                return Err(CoreError::Unsupported(format!(
+                    "Changing the table version from {current_version} to {table_version} is not supported"
+                )));
+            }
+        }
+        if current_version == table_version {
+            return Ok(());
+        }
+        self.ensure_no_pending_instants().await?;
+
+        let storage = &self.file_system_view.storage;
+        let data = storage.get_file_data(PROPERTIES_PATH).await?;
+        let mut properties = parse_data_for_options(&data, "=:")?;
+        migrate_properties(&mut properties, current_version, table_version)?;
+        properties.remove(MetadataTablePartitions.as_ref());
+        properties.remove(METADATA_TABLE_PARTITIONS_INFLIGHT);
+
+        let current_layout: isize = self
+            .hudi_configs
+            .try_get(TimelineLayoutVersion)?
+            .map(|v| v.into())
+            .unwrap_or(if current_version >= 8 { 2 } else { 1 });
+        let replaced_files = if current_layout == 1 && table_version >= 8 {
+            let timeline_path = properties[TimelinePath.as_ref()].clone();
+            let history_path = &properties[TimelineHistoryPath.as_ref()];
+            let timeline_dir = format!("{HUDI_METADATA_DIR}/{timeline_path}");
+            let replaced = self
+                .convert_timeline_layout(HUDI_METADATA_DIR, &timeline_dir)
+                .await?;
+            storage
+                .create_dir(&format!("{timeline_dir}/{history_path}"))
+                .await?;
+            replaced
+        } else if current_layout == 2 && table_version < 8 {
+            let active_loader =
+                TimelineLoader::new_layout_two_active(self.hudi_configs.clone(), storage.clone());
+            let replaced = self
+                .convert_timeline_layout(&active_loader.get_timeline_dir(), HUDI_METADATA_DIR)
+                .await?;
+            let archived_loader =
+                TimelineLoader::new_layout_one_archived(self.hudi_configs.clone(), storage.clone());
+            storage
+                .create_dir(&archived_loader.get_archived_timeline_dir())
+                .await?;
+            replaced
+        } else {
+            Vec::new()
+        };
+
+        // The instant files in the old layout are deleted only after the properties point to
+        // the new layout, so that the timeline stays complete if the change is interrupted.
+        storage
+            .put_file_data(PROPERTIES_PATH, format_data_for_options(&properties))
+            .await?;
+        for path in replaced_files {
+            storage.delete_file(&path).await?;
+        }
+        storage.delete_dir(METADATA_TABLE_DIR).await?;
+        Ok(())
+    }
+
+    async fn ensure_no_pending_instants(&self) -> Result<()> {
+        let instants = self.load_all_instants().await?;
+        let completed: HashSet<(&str, &str)> = instants
+            .iter()
+            .filter(|i| i.state == State::Completed)
+            .map(|i| (i.timestamp.as_str(), i.action.as_ref()))
+            .collect();
+        let pending: BTreeSet<String> = instants
+            .iter()
+            .filter(|i| !completed.contains(&(i.timestamp.as_str(), i.action.as_ref())))
+            .map(|i| format!("{}.{}", i.timestamp, i.action.as_ref()))
+            .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        Err(CoreError::Timeline(format!(
+            "Pending instants {pending:?} need to be completed or rolled back before changing the table version"
+        )))
+    }
+
+    /// Write the instant files of the active timeline in `from_dir` to `to_dir` in the other
+    /// timeline layout, and return the paths of the instant files in `from_dir`.
+    async fn convert_timeline_layout(&self, from_dir: &str, to_dir: &str) -> Result<Vec<String>> {
+        let storage = &self.file_system_view.storage;
+        let archived_loader = if from_dir == HUDI_METADATA_DIR {
+            TimelineLoader::new_layout_one_archived(self.hudi_configs.clone(), storage.clone())
+        } else {
+            TimelineLoader::new_layout_two_archived(self.hudi_configs.clone(), storage.clone())
+        };
+        let archived_dir = archived_loader.get_archived_timeline_dir();
+        if !storage.list_files(Some(&archived_dir)).await?.is_empty() {
+            return Err(CoreError::Unsupported(format!(
+                "Converting the archived timeline in {archived_dir} is not supported"
+            )));
+        }
+
+        let to_layout_two = from_dir == HUDI_METADATA_DIR;
+        let timezone = self.timezone();
+        let mut instant_files = Vec::new();
+        for file in storage.list_files(Some(from_dir)).await? {
+            if !file.name.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+            let data = storage
+                .get_file_data(&format!("{from_dir}/{}", file.name))
+                .await?;
+            instant_files.push((file.name, data));
+        }
+
+        // Timeline layout 2 has the `clustering` action for the pending states of `replacecommit`
+        // instants of clustering.
+        let mut clustering_timestamps = HashSet::new();
+        if to_layout_two {
+            for (file_name, data) in &instant_files {
+                let Ok(instant) = Instant::try_from_file_name_and_timezone(file_name, &timezone)
+                else {
+                    continue;
+                };
+                if instant.is_replacecommit() && instant.state == State::Completed {
+                    let metadata = HoodieCommitMetadata::from_json_bytes(data)?;
+                    let operation_type = metadata.operation_type.unwrap_or_default();
+                    if operation_type.eq_ignore_ascii_case("cluster") {
+                        clustering_timestamps.insert(instant.timestamp);
+                    }
+                }
+            }
+        }
+
+        let mut replaced_files = Vec::with_capacity(instant_files.len());
+        for (file_name, data) in instant_files {
+            let (new_file_name, new_data) =
+                match Instant::try_from_file_name_and_timezone(&file_name, &timezone) {
+                    Ok(instant) => {
+                        let is_clustering = clustering_timestamps.contains(&instant.timestamp);
+                        convert_instant_file(instant, data, to_layout_two, is_clustering)?
+                    }
+                    // Instants of other actions, e.g., compactions, only differ in the file
+                    // names of the completed ones.
+                    Err(_) => (convert_file_name(&file_name, to_layout_two), data),
+                };
+            storage
+                .put_file_data(&format!("{to_dir}/{new_file_name}"), new_data)
+                .await?;
+            replaced_files.push(format!("{from_dir}/{file_name}"));
+        }
+        Ok(replaced_files)
+    }
+}
+
+/// Migrate the table properties from one table version to another, one version step at a time.
+fn migrate_properties(
+    properties: &mut HashMap<String, String>,
+    from_version: isize,
+    to_version: isize,
+) -> Result<()> {
+    let mut version = from_version;
+    while version != to_version {
+        version = match (version, version < to_version) {
+            (6, true) => {
+                upgrade_six_to_eight(properties);
+                8
+            }
+            (8, true) => {
+                upgrade_eight_to_nine(properties)?;
+                9
+            }
+            (9, false) => {
+                downgrade_nine_to_eight(properties);
+                8
+            }
+            (8, false) => {
+                downgrade_eight_to_six(properties);
+                6
+            }
+            _ => {
+                return Err(CoreError::Unsupported(format!(
+                    "Changing the table version from {version} to {to_version} is not supported"
+                )));
+            }
+        };
+        properties.insert(TableVersion.as_ref().to_string(), version.to_string());
+    }
+    Ok(())
+}
+
+fn set(properties: &mut HashMap<String, String>, key: &str, value: &str) {
+    properties.insert(key.to_string(), value.to_string());
+}
+
+fn set_if_absent(properties: &mut HashMap<String, String>, key: &str, value: &str) {
+    properties
+        .entry(key.to_string())
+        .or_insert_with(|| value.to_string());
+}
+
+fn rename(properties: &mut HashMap<String, String>, from_key: &str, to_key: &str) {
+    if let Some(value) = properties.remove(from_key) {
+        properties.insert(to_key.to_string(), value);
+    }
+}
+
+fn upgrade_six_to_eight(properties: &mut HashMap<String, String>) {
+    set(properties, TimelineLayoutVersion.as_ref(), "2");
+    set_if_absent(properties, TimelinePath.as_ref(), "timeline");
+    set_if_absent(properties, TimelineHistoryPath.as_ref(), "history");
+    set_if_absent(properties, INITIAL_VERSION, "6");
+
+    // Custom key generator classes are kept as is.
+    let key_generator_type = properties
+        .get(KeyGeneratorClass.as_ref())
+        .and_then(|class| KeyGeneratorTypeValue::from_class_name(class));
+    if let Some(key_generator_type) = key_generator_type {
+        properties.remove(KeyGeneratorClass.as_ref());
+        set(
+            properties,
+            KeyGeneratorType.as_ref(),
+            key_generator_type.as_ref(),
+        );
+    }
+
+    let (merge_mode, merge_strategy_id) = if properties.contains_key(PRECOMBINE_FIELD) {
+        (EVENT_TIME_ORDERING, EVENT_TIME_MERGE_STRATEGY_ID)
+    } else {
+        (COMMIT_TIME_ORDERING, COMMIT_TIME_MERGE_STRATEGY_ID)
+    };
+    set_if_absent(properties, RECORD_MERGE_MODE, merge_mode);
+    set_if_absent(properties, RECORD_MERGE_STRATEGY_ID, merge_strategy_id);
+    set_if_absent(properties, PAYLOAD_CLASS, DEFAULT_PAYLOAD_CLASS);
+}
+
+fn upgrade_eight_to_nine(properties: &mut HashMap<String, String>) -> Result<()> {
+    if let Some(payload_class) = properties.get(PAYLOAD_CLASS)
+        && payload_class != DEFAULT_PAYLOAD_CLASS
+        && payload_class != OVERWRITE_WITH_LATEST_PAYLOAD_CLASS
+    {
+        return Err(CoreError::Unsupported(format!(
+            "Upgrading the table with payload class {payload_class} to table version 9 is not supported"
+        )));
+    }
+    properties.remove(PAYLOAD_CLASS);
+    properties.remove(RECORD_MERGE_STRATEGY_ID);
+    rename(properties, PRECOMBINE_FIELD, OrderingFields.as_ref());
+    set(properties, TABLE_FORMAT, "native");
+    Ok(())
+}
+
+fn downgrade_nine_to_eight(properties: &mut HashMap<String, String>) {
+    properties.remove(TABLE_FORMAT);
+    rename(properties, OrderingFields.as_ref(), PRECOMBINE_FIELD);
+    let is_commit_time_ordering = match properties.get(RECORD_MERGE_MODE) {
+        Some(merge_mode) => merge_mode == COMMIT_TIME_ORDERING,
+        None => !properties.contains_key(PRECOMBINE_FIELD),
+    };
+    let merge_strategy_id = if is_commit_time_ordering {
+        COMMIT_TIME_MERGE_STRATEGY_ID
+    } else {
+        EVENT_TIME_MERGE_STRATEGY_ID
+    };
+    set_if_absent(properties, RECORD_MERGE_STRATEGY_ID, merge_strategy_id);
+    set_if_absent(properties, PAYLOAD_CLASS, DEFAULT_PAYLOAD_CLASS);
+}
+
+fn downgrade_eight_to_six(properties: &mut HashMap<String, String>) {
+    set(properties, TimelineLayoutVersion.as_ref(), "1");
+    for key in [
+        TimelinePath.as_ref(),
+        TimelineHistoryPath.as_ref(),
+        INITIAL_VERSION,
+        RECORD_MERGE_MODE,
+        RECORD_MERGE_STRATEGY_ID,
+    ] {
+        properties.remove(key);
+    }
+
+    let key_generator_type = properties
+        .get(KeyGeneratorType.as_ref())
+        .and_then(|t| t.parse::<KeyGeneratorTypeValue>().ok());
+    if let Some(key_generator_type) = key_generator_type {
+        properties.remove(KeyGeneratorType.as_ref());
+        set(
+            properties,
+            KeyGeneratorClass.as_ref(),
+            key_generator_type.class_name(),
+        );
+    }
+}
+
+/// Convert the file name of an instant of an action not modeled by [Action] to the other
+/// timeline layout.
+fn convert_file_name(file_name: &str, to_layout_two: bool) -> String {
+    let Some((timestamp, suffix)) = file_name.split_once('.') else {
+        return file_name.to_string();
+    };
+    if to_layout_two {
+        if suffix.contains('.') {
+            file_name.to_string()
+        } else {
+            format!("{timestamp}_{timestamp}.{suffix}")
+        }
+    } else {
+        let timestamp = timestamp
+            .split_once('_')
+            .map_or(timestamp, |(requested, _)| requested);
+        let suffix = match suffix.split_once('.') {
+            Some((CLUSTERING_ACTION, state)) => {
+                format!("{}.{state}", Action::ReplaceCommit.as_ref())
+            }
+            _ => suffix.to_string(),
+        };
+        format!("{timestamp}.{suffix}")
+    }
+}
+
+/// Convert the file name and the content of an instant file to the other timeline layout.
+fn convert_instant_file(
+    instant: Instant,
+    data: Bytes,
+    to_layout_two: bool,
+    is_clustering: bool,
+) -> Result<(String, Bytes)> {
+    let completion_timestamp = if to_layout_two && instant.state == State::Completed {
+        Some(instant.timestamp.clone())
+    } else {
+        None
+    };
+    let instant = Instant {
+        completion_timestamp,
+        ..instant
+    };
+    let file_name = if is_clustering && instant.state != State::Completed {
+        format!(
+            "{}.{CLUSTERING_ACTION}.{}",
+            instant.timestamp,
+            instant.state.as_ref()
+        )
+    } else {
+        instant.file_name()
+    };
+
+    let is_commit_metadata = matches!(
+        instant.action,
+        Action::Commit | Action::DeltaCommit | Action::ReplaceCommit
+    ) && instant.state != State::Requested;
+    if !is_commit_metadata || data.is_empty() {
+        return Ok((file_name, data));
+    }
+
+    let data = if to_layout_two {
+        let metadata = HoodieCommitMetadata::from_json_bytes(&data)?;
+        if instant.is_replacecommit() && instant.state == State::Completed {
+            HoodieReplaceCommitMetadata::from(&metadata).to_avro_bytes()?
+        } else {
+            metadata.to_avro_bytes()?
+        }
+    } else {
+        let metadata = HoodieCommitMetadata::from_avro_bytes(&data)?;
+        serde_json::to_vec(&metadata.to_json_map()?)
+            .map_err(|e| CoreError::CommitMetadata(format!("Failed to serialize to JSON: {e}")))?
+    };
+    Ok((file_name, Bytes::from(data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table::TableTypeValue;
+    use crate::table::creator::TableCreator;
+    use crate::table::test_util::{data_schema, list_file_names, read_snapshot_sorted, records};
+    use hudi_test::{SampleTable, TableFormat};
+    use std::path::Path;
+
+    const BUCKET_OPTIONS: [(&str, &str); 2] = [
+        ("hoodie.index.type", "BUCKET"),
+        ("hoodie.bucket.index.num.buckets", "2"),
+    ];
+
+    const KEY: &str = "_hoodie_record_key";
+
+    fn read_properties(base_path: &str) -> HashMap<String, String> {
+        let data = std::fs::read(Path::new(base_path).join(PROPERTIES_PATH)).unwrap();
+        parse_data_for_options(&Bytes::from(data), "=:").unwrap()
+    }
+
+    async fn change_table_version(base_path: &str, table_version: isize) {
+        Table::new(base_path)
+            .await
+            .unwrap()
+            .change_table_version(table_version)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_change_table_version_of_v6_cow_table() {
+        let base_path = SampleTable::V6ComplexkeygenHivestyle.path_to_cow_fresh();
+        let expected = read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await;
+        let hoodie_dir = Path::new(&base_path).join(HUDI_METADATA_DIR);
+        let commits = ["20240418173213674", "20240418173235694"];
+        let mut num_base_files = Vec::new();
+        for ts in commits {
+            let data = std::fs::read(hoodie_dir.join(format!("{ts}.commit"))).unwrap();
+            let metadata = HoodieCommitMetadata::from_json_bytes(&data).unwrap();
+            num_base_files.push(metadata.iter_base_file_paths().count());
+        }
+
+        change_table_version(&base_path, 9).await;
+        let properties = read_properties(&base_path);
+        assert_eq!(properties[TableVersion.as_ref()], "9");
+        assert_eq!(properties[TimelineLayoutVersion.as_ref()], "2");
+        assert_eq!(properties[INITIAL_VERSION], "6");
+        assert_eq!(properties[KeyGeneratorType.as_ref()], "COMPLEX");
+        assert_eq!(properties[OrderingFields.as_ref()], "longField");
+        assert_eq!(properties[RECORD_MERGE_MODE], EVENT_TIME_ORDERING);
+        assert_eq!(properties[TABLE_FORMAT], "native");
+        for key in [
+            KeyGeneratorClass.as_ref(),
+            PRECOMBINE_FIELD,
+            RECORD_MERGE_STRATEGY_ID,
+            PAYLOAD_CLASS,
+        ] {
+            assert!(!properties.contains_key(key), "{key} should be removed");
+        }
+        assert!(hoodie_dir.join("timeline/history").is_dir());
+        assert_eq!(list_file_names(&hoodie_dir), ["hoodie.properties"]);
+        let timeline_files = list_file_names(&hoodie_dir.join("timeline"));
+        assert_eq!(timeline_files.len(), 6);
+        for (ts, num) in commits.iter().zip(&num_base_files) {
+            assert!(timeline_files.contains(&format!("{ts}.commit.requested")));
+            assert!(timeline_files.contains(&format!("{ts}.inflight")));
+            let data =
+                std::fs::read(hoodie_dir.join(format!("timeline/{ts}_{ts}.commit"))).unwrap();
+            let metadata = HoodieCommitMetadata::from_avro_bytes(&data).unwrap();
+            assert_eq!(metadata.iter_base_file_paths().count(), *num);
+        }
+        let table = Table::new(&base_path).await.unwrap();
+        assert_eq!(
+            table.timeline.completed_commits[0].completion_timestamp,
+            Some(commits[0].to_string())
+        );
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await,
+            expected
+        );
+
+        change_table_version(&base_path, 6).await;
+        let properties = read_properties(&base_path);
+        assert_eq!(properties[TableVersion.as_ref()], "6");
+        assert_eq!(properties[TimelineLayoutVersion.as_ref()], "1");
+        assert_eq!(
+            properties[KeyGeneratorClass.as_ref()],
+            "org.apache.hudi.keygen.ComplexKeyGenerator"
+        );
+        assert_eq!(properties[PRECOMBINE_FIELD], "longField");
+        for key in [
+            KeyGeneratorType.as_ref(),
+            OrderingFields.as_ref(),
+            TimelinePath.as_ref(),
+            RECORD_MERGE_MODE,
+            TABLE_FORMAT,
+        ] {
+            assert!(!properties.contains_key(key), "{key} should be removed");
+        }
+        assert!(list_file_names(&hoodie_dir.join("timeline")).is_empty());
+        for (ts, num) in commits.iter().zip(&num_base_files) {
+            let data = std::fs::read(hoodie_dir.join(format!("{ts}.commit"))).unwrap();
+            let metadata = HoodieCommitMetadata::from_json_bytes(&data).unwrap();
+            assert_eq!(metadata.iter_base_file_paths().count(), *num);
+        }
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await,
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_table_version_of_v9_mor_table() {
+        let base_path = SampleTable::V9TxnsSimpleMeta.path_fresh(TableFormat::MorAvro);
+        let expected = read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await;
+        let hoodie_dir = Path::new(&base_path).join(HUDI_METADATA_DIR);
+
+        change_table_version(&base_path, 6).await;
+        let properties = read_properties(&base_path);
+        assert_eq!(properties[TableVersion.as_ref()], "6");
+        assert!(!properties.contains_key(MetadataTablePartitions.as_ref()));
+        assert!(!properties.contains_key(METADATA_TABLE_PARTITIONS_INFLIGHT));
+        assert!(!hoodie_dir.join("metadata").exists());
+        let timeline_files = list_file_names(&hoodie_dir);
+        for name in [
+            "20260307162618152.commit",
+            "20260307162618152.compaction.requested",
+            "20260307162619269.replacecommit",
+            "20260307162619269.replacecommit.requested",
+            "20260307162619269.replacecommit.inflight",
+            "20260307162621933.indexing",
+        ] {
+            assert!(
+                timeline_files.contains(&name.to_string()),
+                "{name} not found"
+            );
+        }
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await,
+            expected
+        );
+
+        change_table_version(&base_path, 9).await;
+        let timeline_files = list_file_names(&hoodie_dir.join("timeline"));
+        for name in [
+            "20260307162618152_20260307162618152.commit",
+            "20260307162619269.clustering.requested",
+            "20260307162619269_20260307162619269.replacecommit",
+            "20260307162614221.replacecommit.requested",
+            "20260307162621933_20260307162621933.indexing",
+        ] {
+            assert!(
+                timeline_files.contains(&name.to_string()),
+                "{name} not found"
+            );
+        }
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(&base_path).await.unwrap(), KEY).await,
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_table_version_of_created_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let create_table = |base_path: String, table_version: isize| async move {
+            TableCreator::new(&base_path, "trips", data_schema())
+                .with_table_type(TableTypeValue::MergeOnRead)
+                .with_record_key_fields(["id"])
+                .with_partition_fields(["city"])
+                .with_ordering_fields(["ts"])
+                .with_table_version(table_version)
+                .with_hudi_options(BUCKET_OPTIONS)
+                .create()
+                .await
+                .unwrap()
+        };
+        create_table(base_path.to_string(), 6).await;
+        let v6_properties = read_properties(base_path);
+        let load_table = || async {
+            Table::new_with_options(base_path, BUCKET_OPTIONS)
+                .await
+                .unwrap()
+        };
+        load_table()
+            .await
+            .upsert(&[records(&[(1, "a", "sf", 1), (2, "b", "nyc", 1)])])
+            .await
+            .unwrap();
+        load_table()
+            .await
+            .upsert(&[records(&[(1, "a2", "sf", 2)])])
+            .await
+            .unwrap();
+        load_table()
+            .await
+            .insert_overwrite(&[records(&[(3, "c", "la", 1)])])
+            .await
+            .unwrap();
+        let expected = read_snapshot_sorted(&Table::new(base_path).await.unwrap(), KEY).await;
+
+        // The properties are the same as the ones of a table created with the version.
+        change_table_version(base_path, 9).await;
+        let v9_dir = tempfile::tempdir().unwrap();
+        create_table(v9_dir.path().to_str().unwrap().to_string(), 9).await;
+        let mut v9_properties = read_properties(v9_dir.path().to_str().unwrap());
+        v9_properties.insert(INITIAL_VERSION.to_string(), "6".to_string());
+        assert_eq!(read_properties(base_path), v9_properties);
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(base_path).await.unwrap(), KEY).await,
+            expected
+        );
+
+        load_table()
+            .await
+            .upsert(&[records(&[(2, "b2", "nyc", 2)])])
+            .await
+            .unwrap();
+        load_table().await.bootstrap_metadata_table().await.unwrap();
+        assert!(load_table().await.is_metadata_table_enabled());
+        let expected = read_snapshot_sorted(&Table::new(base_path).await.unwrap(), KEY).await;
+
+        change_table_version(base_path, 8).await;
+        let properties = read_properties(base_path);
+        assert_eq!(properties[PRECOMBINE_FIELD], "ts");
+        assert_eq!(
+            properties[RECORD_MERGE_STRATEGY_ID],
+            EVENT_TIME_MERGE_STRATEGY_ID
+        );
+        assert!(!Path::new(base_path).join(METADATA_TABLE_DIR).exists());
+        assert!(!load_table().await.is_metadata_table_enabled());
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(base_path).await.unwrap(), KEY).await,
+            expected
+        );
+
+        change_table_version(base_path, 6).await;
+        let mut properties = read_properties(base_path);
+        assert_eq!(
+            properties.remove(PAYLOAD_CLASS).as_deref(),
+            Some(DEFAULT_PAYLOAD_CLASS)
+        );
+        assert_eq!(properties, v6_properties);
+        assert_eq!(
+            read_snapshot_sorted(&Table::new(base_path).await.unwrap(), KEY).await,
+            expected
+        );
+
+        // The downgraded table can be written again.
+        load_table()
+            .await
+            .upsert(&[records(&[(3, "c2", "la", 2)])])
+            .await
+            .unwrap();
+        assert_eq!(load_table().await.timeline.completed_commits.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_change_table_version_errors() {
+        let base_path = SampleTable::V6NonpartitionedRollback.path_to_mor_parquet_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        for table_version in [5, 7, 10] {
+            assert!(matches!(
+                table.change_table_version(table_version).await.unwrap_err(),
+                CoreError::Unsupported(_)
+            ));
+        }
+        table.change_table_version(6).await.unwrap();
+
+        // A pending instant needs to be completed or rolled back first.
+        let pending_time = table.timeline.new_instant_timestamp(None).unwrap();
+        let requested = table
+            .timeline
+            .create_requested_instant(Action::DeltaCommit, &pending_time, Bytes::new())
+            .await
+            .unwrap();
+        assert!(matches!(
+            table.change_table_version(8).await.unwrap_err(),
+            CoreError::Timeline(_)
+        ));
+        table
+            .timeline
+            .delete_pending_instant(&requested)
+            .await
+            .unwrap();
+
+        // The archived timeline is not converted.
+        let archived_file =
+            Path::new(&base_path).join(".hoodie/archived/.commits_.archive.1_1-0-1");
+        std::fs::write(&archived_file, b"").unwrap();
+        assert!(matches!(
+            table.change_table_version(8).await.unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+        std::fs::remove_file(&archived_file).unwrap();
+
+        table.change_table_version(8).await.unwrap();
+        assert_eq!(read_properties(&base_path)[TableVersion.as_ref()], "8");
+    }
+}
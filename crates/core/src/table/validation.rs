@@ -17,7 +17,11 @@
  * under the License.
  */
 use crate::config::HudiConfigs;
+use crate::config::archive::HudiArchiveConfig;
+use crate::config::clean::HudiCleanConfig;
+use crate::config::index::HudiIndexConfig;
 use crate::config::internal::HudiInternalConfig::SkipConfigValidation;
+use crate::config::lock::HudiLockConfig;
 use crate::config::plan::HudiPlanConfig;
 use crate::config::table::BaseFileFormatValue;
 use crate::config::table::HudiTableConfig;
@@ -43,6 +47,22 @@ pub fn validate_configs(hudi_configs: &HudiConfigs) -> crate::error::Result<()>
         hudi_configs.validate(conf)?
     }
 
+    for conf in HudiCleanConfig::iter() {
+        hudi_configs.validate(conf)?
+    }
+
+    for conf in HudiArchiveConfig::iter() {
+        hudi_configs.validate(conf)?
+    }
+
+    for conf in HudiLockConfig::iter() {
+        hudi_configs.validate(conf)?
+    }
+
+    for conf in HudiIndexConfig::iter() {
+        hudi_configs.validate(conf)?
+    }
+
     // additional validation
     let table_version: isize = hudi_configs.get(TableVersion)?.into();
     if !matches!(table_version, 6 | 8 | 9) {
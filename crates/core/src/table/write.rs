@@ -0,0 +1,2363 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Writing records to a Hudi table.
+//!
+//! Overwriting operations write the records to new file groups and record a `replacecommit`
+//! instant on the [crate::timeline::Timeline], whose `partitionToReplaceFileIds` lists the file
+//! groups replaced by the new ones. The replaced file groups are excluded from reads right away,
+//! and their files are deleted by a later [Table::clean].
+//!
+//! Inserts write the records to new file groups, and record a `commit` or `deltacommit` instant.
+//!
+//! Upserts route the records to file groups by the simple bucket index
+//! ([crate::index::bucket::BucketIndex]), and record a `commit` or `deltacommit` instant.
+//!
+//! Updates and deletes look up the file groups holding the record keys in the latest file slices
+//! of the partitions written to, which works with any index. For copy-on-write tables, the file
+//! groups are rewritten into new base files; for merge-on-read tables, the records are appended
+//! to new log files as data or delete blocks.
+//!
+//! Each written file is tracked by a marker under `.hoodie/.temp/<instant time>`, so that a
+//! failed write can be undone by [Table::rollback].
+
+use crate::Result;
+use crate::config::index::HudiIndexConfig::IndexType;
+use crate::config::index::IndexTypeValue;
+use crate::config::table::BaseFileFormatValue;
+use crate::config::table::HudiTableConfig::{
+    DropsPartitionFields, OrderingFields, PopulatesMetaFields, RecordMergeStrategy, TableVersion,
+};
+use crate::config::write::HudiWriteConfig::Operation;
+use crate::config::write::WriteOperationValue;
+use crate::error::CoreError;
+use crate::file_group::base_file::writer::ParquetBaseFileWriter;
+use crate::file_group::file_slice::FileSlice;
+use crate::file_group::log_file::LogFile;
+use crate::file_group::log_file::writer::LogFileWriter;
+use crate::file_group::record_batches::RecordBatches;
+use crate::index::bucket::{BucketIndex, new_bucket_file_id, parse_bucket_id};
+use crate::keygen::builtin::BuiltinKeyGenerator;
+use crate::merge::RecordMergeStrategyValue;
+use crate::merge::record_merger::RecordMerger;
+use crate::metadata::commit::{HoodieCommitMetadata, HoodieWriteStat};
+use crate::metadata::meta_field::MetaField;
+use crate::metadata::table::column_stats::ColumnStatsRecord;
+use crate::metadata::table::writer::{FilesPartitionChanges, MetadataTableChanges};
+use crate::schema::arrow_to_avro::{
+    arrow_schema_to_avro_schema_str, avro_record_name_and_namespace,
+};
+use crate::table::partition::PARTITION_METAFIELD_PREFIX;
+use crate::table::rollback::MARKER_BASE_DIR;
+use crate::table::{ReadOptions, Table};
+use crate::timeline::instant::{Action, Instant};
+use crate::transaction::{TransactionManager, TransactionStart};
+use apache_avro::Schema as AvroSchema;
+use apache_avro::types::Value as AvroValue;
+use arrow::compute::{cast, concat_batches, take_record_batch};
+use arrow::record_batch::RecordBatch;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int32Type, Int64Type};
+use arrow_array::{Array, ArrayRef, StringArray, UInt32Array};
+use arrow_schema::{DataType, Schema, SchemaRef};
+use bytes::Bytes;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use strum_macros::AsRefStr;
+
+/// The version of [HoodieCommitMetadata] written by the writer.
+const COMMIT_METADATA_VERSION: i32 = 1;
+
+/// The write token of the base files written by the writer.
+const WRITE_TOKEN: &str = "0-0-0";
+
+/// The `prevCommit` of the write stats of new file groups.
+const NULL_COMMIT: &str = "null";
+
+/// The key of the writer schema in the extra metadata of a commit.
+const SCHEMA_METADATA_KEY: &str = "schema";
+
+/// Type of a write operation, recorded as the `operationType` of the commit metadata.
+#[derive(Clone, Debug, PartialEq, Eq, AsRefStr)]
+pub enum WriteOperationType {
+    /// Write the records to new file groups.
+    #[strum(serialize = "INSERT")]
+    Insert,
+    /// Replace the file groups of the partitions written to.
+    #[strum(serialize = "INSERT_OVERWRITE")]
+    InsertOverwrite,
+    /// Replace the file groups of all partitions.
+    #[strum(serialize = "INSERT_OVERWRITE_TABLE")]
+    InsertOverwriteTable,
+    /// Replace the file groups of the given partitions without writing records.
+    #[strum(serialize = "DELETE_PARTITION")]
+    DeletePartition,
+    /// Update the existing records and insert the new ones.
+    #[strum(serialize = "UPSERT")]
+    Upsert,
+    /// Delete the existing records.
+    #[strum(serialize = "DELETE")]
+    Delete,
+}
+
+/// Type of the marker of a written file, named after how the file is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr)]
+enum MarkerType {
+    /// A new base file of a new file group.
+    #[strum(serialize = "CREATE")]
+    Create,
+    /// A new base file merging the records of an existing file group.
+    #[strum(serialize = "MERGE")]
+    Merge,
+    /// A new log file of an existing file group.
+    #[strum(serialize = "APPEND")]
+    Append,
+}
+
+/// The completed instant of a write and its commit metadata.
+#[derive(Clone, Debug)]
+pub struct WriteResult {
+    pub instant: Instant,
+    pub commit_metadata: HoodieCommitMetadata,
+}
+
+/// The row indices and record keys of the records to upsert and to delete in a file group.
+#[derive(Default)]
+struct FileGroupWrite {
+    /// The latest file slice of the file group, or `None` for a new file group.
+    file_slice: Option<FileSlice>,
+    upsert_rows: Vec<u32>,
+    upsert_keys: Vec<String>,
+    delete_rows: Vec<u32>,
+    delete_keys: Vec<String>,
+}
+
+/// The partition path and the bucket of a file group to write to, mapped to the row indices and
+/// record keys of the records to write. The bucket is `None` if the table does not use the bucket
+/// index.
+type FileGroupToRows = BTreeMap<(String, Option<u32>), (Vec<u32>, Vec<String>)>;
+
+/// The partitions whose file groups are replaced by a write.
+enum ReplacedPartitions {
+    /// The partitions written to.
+    Written,
+    /// All partitions of the table.
+    All,
+    /// The given partitions.
+    Given(BTreeSet<String>),
+}
+
+impl Table {
+    /// Overwrite the partitions that the records belong to, i.e., write the records to new file
+    /// groups and replace all existing file groups of those partitions.
+    ///
+    /// The partitions not written to are left as is. The record schema is expected to match
+    /// [Table::get_schema].
+    ///
+    /// The [Table] instance is not refreshed after writing; create a new instance to read the
+    /// written records.
+    ///
+    /// # Errors
+    ///
+    /// If the write fails after it started, the pending `replacecommit` instant is left on the
+    /// timeline, to be rolled back by [Table::rollback_failed_writes].
+    pub async fn insert_overwrite(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        self.write_new_file_groups(
+            WriteOperationType::InsertOverwrite,
+            records,
+            Some(ReplacedPartitions::Written),
+        )
+        .await
+    }
+
+    /// Overwrite the whole table, i.e., write the records to new file groups and replace all
+    /// existing file groups of the table.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn insert_overwrite_table(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        self.write_new_file_groups(
+            WriteOperationType::InsertOverwriteTable,
+            records,
+            Some(ReplacedPartitions::All),
+        )
+        .await
+    }
+
+    /// Delete the given partitions, i.e., replace all their existing file groups without
+    /// writing new ones.
+    ///
+    /// See [Table::insert_overwrite] for the errors.
+    pub async fn delete_partition<I, S>(&self, partition_paths: I) -> Result<WriteResult>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let partitions = partition_paths
+            .into_iter()
+            .map(|p| p.as_ref().trim_matches('/').to_string())
+            .collect();
+        self.write_new_file_groups(
+            WriteOperationType::DeletePartition,
+            &[],
+            Some(ReplacedPartitions::Given(partitions)),
+        )
+        .await
+    }
+
+    /// Write the records with the operation of [crate::config::write::HudiWriteConfig::Operation],
+    /// i.e., [Table::insert] or [Table::upsert]. If the operation is not configured, the records
+    /// are upserted to tables using the bucket index, and inserted to the others.
+    pub async fn write(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        let operation = match self.hudi_configs.try_get(Operation)? {
+            Some(operation) => WriteOperationValue::from_str(&String::from(operation))?,
+            None if BucketIndex::from_configs(&self.hudi_configs)?.is_some() => {
+                WriteOperationValue::Upsert
+            }
+            None => WriteOperationValue::Insert,
+        };
+        match operation {
+            WriteOperationValue::Insert => self.insert(records).await,
+            WriteOperationValue::Upsert => self.upsert(records).await,
+        }
+    }
+
+    /// Insert the records, i.e., write them to new file groups without looking up the existing
+    /// records, so records with the same keys as the existing ones are duplicated.
+    ///
+    /// Tables using the bucket index are not supported, as each bucket must have exactly one file
+    /// group; use [Table::upsert] for them instead.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn insert(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        if BucketIndex::from_configs(&self.hudi_configs)?.is_some() {
+            return Err(CoreError::Unsupported(format!(
+                "Inserting to tables with `{}` being {} is not supported",
+                IndexType.as_ref(),
+                IndexTypeValue::Bucket.as_ref()
+            )));
+        }
+        self.write_new_file_groups(WriteOperationType::Insert, records, None)
+            .await
+    }
+
+    /// Upsert the records, i.e., update the existing records with the same record keys and
+    /// insert the others.
+    ///
+    /// The table must use the simple bucket index, which routes each record to the file group of
+    /// its bucket without looking up the existing records. For copy-on-write tables, the records
+    /// are merged with the existing ones of the file group into a new base file; for
+    /// merge-on-read tables, they are appended to a new log file of the file group. Records of
+    /// buckets without file groups are written to new file groups. The records with the same key
+    /// are merged by the ordering field, the same way as on reads.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn upsert(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        self.ensure_writable()?;
+        let Some(bucket_index) = BucketIndex::from_configs(&self.hudi_configs)? else {
+            return Err(CoreError::Unsupported(format!(
+                "Upserting requires `{}` to be {}",
+                IndexType.as_ref(),
+                IndexTypeValue::Bucket.as_ref()
+            )));
+        };
+        self.ensure_merging_by_ordering("Upserting")?;
+
+        let data_schema = Arc::new(self.get_schema().await?);
+        let records = self.concat_records(&data_schema, records)?;
+        let file_group_to_rows = match &records {
+            Some(batch) => self.group_rows_by_file_group(batch, Some(&bucket_index))?,
+            None => BTreeMap::new(),
+        };
+        let bucket_to_file_slice = self.get_bucket_file_slices().await?;
+
+        let action = if self.is_mor() {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        let timeline = &self.timeline;
+        let start = TransactionManager::from_table(self)?
+            .begin(timeline)
+            .await?;
+        let instant_time = timeline.new_instant_timestamp(start.timestamp())?;
+        let requested = timeline
+            .create_requested_instant(action, &instant_time, Bytes::new())
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let mut partition_to_write_stats: HashMap<String, Vec<HoodieWriteStat>> = HashMap::new();
+        let mut column_stats = Vec::new();
+        if let Some(batch) = &records {
+            for (index, ((partition_path, bucket), (rows, record_keys))) in
+                file_group_to_rows.into_iter().enumerate()
+            {
+                let file_slice =
+                    bucket.and_then(|b| bucket_to_file_slice.get(&(partition_path.clone(), b)));
+                let file_id = match file_slice {
+                    Some(file_slice) => file_slice.file_id().to_string(),
+                    None => new_file_id(bucket),
+                };
+                let file_name = base_file_name(&file_id, &instant_time);
+                let file_group_records = self.combine_records(self.prepare_records(
+                    &take_record_batch(batch, &UInt32Array::from(rows))?,
+                    &instant_time,
+                    index,
+                    &partition_path,
+                    &file_name,
+                    &record_keys,
+                )?)?;
+
+                let (stat, stats) = match file_slice {
+                    None => {
+                        let record_keys = self.get_record_keys(&file_group_records)?;
+                        self.write_base_file(
+                            &instant_time,
+                            &partition_path,
+                            &file_id,
+                            MarkerType::Create,
+                            &file_group_records,
+                            &record_keys,
+                        )
+                        .await?
+                    }
+                    Some(file_slice) if self.is_mor() => {
+                        self.append_log_file(
+                            &instant_time,
+                            file_slice,
+                            Some(&file_group_records),
+                            None,
+                        )
+                        .await?
+                    }
+                    Some(file_slice) => {
+                        self.merge_base_file(
+                            &instant_time,
+                            file_slice,
+                            Some(&file_group_records),
+                            &[],
+                        )
+                        .await?
+                    }
+                };
+                column_stats.extend(stats);
+                partition_to_write_stats
+                    .entry(partition_path)
+                    .or_default()
+                    .push(stat);
+            }
+        }
+
+        let commit_metadata = HoodieCommitMetadata {
+            version: Some(COMMIT_METADATA_VERSION),
+            operation_type: Some(WriteOperationType::Upsert.as_ref().to_string()),
+            partition_to_write_stats: Some(partition_to_write_stats),
+            compacted: Some(false),
+            extra_metadata: Some(HashMap::from([(
+                SCHEMA_METADATA_KEY.to_string(),
+                self.get_writer_avro_schema(&data_schema).await?,
+            )])),
+            ..Default::default()
+        };
+        self.complete_write(
+            &instant_time,
+            &start,
+            &inflight,
+            commit_metadata,
+            column_stats,
+        )
+        .await
+    }
+
+    /// Update the existing records with the same record keys; the records whose keys do not exist
+    /// in the table are ignored.
+    ///
+    /// Unlike [Table::upsert], the table may use any index: the file groups holding the record
+    /// keys are looked up in the latest file slices of the partitions that the records belong
+    /// to. For copy-on-write tables, the records are merged with the existing ones of each file
+    /// group into a new base file; for merge-on-read tables, they are appended to a new log file
+    /// of each file group. The records with the same key are merged by the ordering field, the
+    /// same way as on reads.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn update(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        self.write_existing_file_groups(WriteOperationType::Upsert, records, &[], false)
+            .await
+    }
+
+    /// Delete the existing records with the same record keys; the records whose keys do not exist
+    /// in the table are ignored.
+    ///
+    /// The file groups holding the record keys are looked up the same way as [Table::update].
+    /// For copy-on-write tables, each file group is rewritten into a new base file without the
+    /// deleted records; for merge-on-read tables, the record keys are appended to a new log file
+    /// of each file group in a delete block, with the ordering values of the given records.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn delete(&self, records: &[RecordBatch]) -> Result<WriteResult> {
+        self.write_existing_file_groups(WriteOperationType::Delete, &[], records, false)
+            .await
+    }
+
+    /// Upsert and delete records in a single commit, e.g., for `MERGE INTO` statements.
+    ///
+    /// The records to upsert update the existing records with the same record keys the same way
+    /// as [Table::update], and the records to delete delete them the same way as
+    /// [Table::delete]. The records to upsert whose keys do not exist in the table are inserted:
+    /// to the file groups of their buckets for tables using the bucket index, and to a new file
+    /// group of each partition otherwise. The records to delete whose keys do not exist are
+    /// ignored.
+    ///
+    /// See [Table::insert_overwrite] for the expectations and errors.
+    pub async fn merge(
+        &self,
+        upserts: &[RecordBatch],
+        deletes: &[RecordBatch],
+    ) -> Result<WriteResult> {
+        self.write_existing_file_groups(WriteOperationType::Upsert, upserts, deletes, true)
+            .await
+    }
+
+    /// Upsert and delete the records in the existing file groups holding their record keys. If
+    /// inserting, the records to upsert whose keys are not found are inserted, otherwise they are
+    /// ignored.
+    async fn write_existing_file_groups(
+        &self,
+        operation: WriteOperationType,
+        upserts: &[RecordBatch],
+        deletes: &[RecordBatch],
+        inserting: bool,
+    ) -> Result<WriteResult> {
+        self.ensure_writable()?;
+        let verb = match operation {
+            WriteOperationType::Delete => "Deleting",
+            _ if inserting => "Merging",
+            _ => "Updating",
+        };
+        self.ensure_merging_by_ordering(verb)?;
+
+        let data_schema = Arc::new(self.get_schema().await?);
+        let upserts = self.concat_records(&data_schema, upserts)?;
+        let deletes = self.concat_records(&data_schema, deletes)?;
+        let file_group_writes = self
+            .group_rows_by_existing_file_group(upserts.as_ref(), deletes.as_ref(), inserting)
+            .await?;
+
+        let action = if self.is_mor() {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        let timeline = &self.timeline;
+        let start = TransactionManager::from_table(self)?
+            .begin(timeline)
+            .await?;
+        let instant_time = timeline.new_instant_timestamp(start.timestamp())?;
+        let requested = timeline
+            .create_requested_instant(action, &instant_time, Bytes::new())
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let mut partition_to_write_stats: HashMap<String, Vec<HoodieWriteStat>> = HashMap::new();
+        let mut column_stats = Vec::new();
+        for (index, ((partition_path, file_id), write)) in file_group_writes.into_iter().enumerate()
+        {
+            let file_name = base_file_name(&file_id, &instant_time);
+            let upserted = match &upserts {
+                Some(batch) if !write.upsert_rows.is_empty() => {
+                    Some(self.combine_records(self.prepare_records(
+                        &take_record_batch(batch, &UInt32Array::from(write.upsert_rows))?,
+                        &instant_time,
+                        index,
+                        &partition_path,
+                        &file_name,
+                        &write.upsert_keys,
+                    )?)?)
+                }
+                _ => None,
+            };
+            let deleted = match &deletes {
+                Some(batch) if !write.delete_rows.is_empty() => Some(take_record_batch(
+                    batch,
+                    &UInt32Array::from(write.delete_rows),
+                )?),
+                _ => None,
+            };
+
+            let (stat, stats) = match (&write.file_slice, &upserted) {
+                (None, Some(records)) => {
+                    let record_keys = self.get_record_keys(records)?;
+                    self.write_base_file(
+                        &instant_time,
+                        &partition_path,
+                        &file_id,
+                        MarkerType::Create,
+                        records,
+                        &record_keys,
+                    )
+                    .await?
+                }
+                (None, None) => continue,
+                (Some(file_slice), _) if self.is_mor() => {
+                    self.append_log_file(
+                        &instant_time,
+                        file_slice,
+                        upserted.as_ref(),
+                        deleted.as_ref(),
+                    )
+                    .await?
+                }
+                (Some(file_slice), _) => {
+                    self.merge_base_file(
+                        &instant_time,
+                        file_slice,
+                        upserted.as_ref(),
+                        &write.delete_keys,
+                    )
+                    .await?
+                }
+            };
+            column_stats.extend(stats);
+            partition_to_write_stats
+                .entry(partition_path)
+                .or_default()
+                .push(stat);
+        }
+
+        let commit_metadata = HoodieCommitMetadata {
+            version: Some(COMMIT_METADATA_VERSION),
+            operation_type: Some(operation.as_ref().to_string()),
+            partition_to_write_stats: Some(partition_to_write_stats),
+            compacted: Some(false),
+            extra_metadata: Some(HashMap::from([(
+                SCHEMA_METADATA_KEY.to_string(),
+                self.get_writer_avro_schema(&data_schema).await?,
+            )])),
+            ..Default::default()
+        };
+        self.complete_write(
+            &instant_time,
+            &start,
+            &inflight,
+            commit_metadata,
+            column_stats,
+        )
+        .await
+    }
+
+    /// Write the records to new file groups. If partitions to replace are given, the write is
+    /// recorded as a `replacecommit` replacing their existing file groups.
+    async fn write_new_file_groups(
+        &self,
+        operation: WriteOperationType,
+        records: &[RecordBatch],
+        replaced_partitions: Option<ReplacedPartitions>,
+    ) -> Result<WriteResult> {
+        self.ensure_writable()?;
+        let data_schema = Arc::new(self.get_schema().await?);
+        let records = self.concat_records(&data_schema, records)?;
+        let bucket_index = BucketIndex::from_configs(&self.hudi_configs)?;
+        let file_group_to_rows = match &records {
+            Some(batch) => self.group_rows_by_file_group(batch, bucket_index.as_ref())?,
+            None => BTreeMap::new(),
+        };
+        let partition_to_replace_file_ids = match &replaced_partitions {
+            Some(replaced_partitions) => {
+                let written_partitions =
+                    file_group_to_rows.keys().map(|(p, _)| p.clone()).collect();
+                Some(
+                    self.get_file_ids_to_replace(replaced_partitions, &written_partitions)
+                        .await?,
+                )
+            }
+            None => None,
+        };
+        let action = if partition_to_replace_file_ids.is_some() {
+            Action::ReplaceCommit
+        } else if self.is_mor() {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+
+        let timeline = &self.timeline;
+        let start = TransactionManager::from_table(self)?
+            .begin(timeline)
+            .await?;
+        let instant_time = timeline.new_instant_timestamp(start.timestamp())?;
+        let requested = timeline
+            .create_requested_instant(action, &instant_time, Bytes::new())
+            .await?;
+        let inflight = timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await?;
+
+        let mut partition_to_write_stats: HashMap<String, Vec<HoodieWriteStat>> = HashMap::new();
+        let mut column_stats = Vec::new();
+        if let Some(batch) = &records {
+            for (index, ((partition_path, bucket), (rows, record_keys))) in
+                file_group_to_rows.into_iter().enumerate()
+            {
+                let file_id = new_file_id(bucket);
+                let file_name = base_file_name(&file_id, &instant_time);
+                let file_group_records = self.prepare_records(
+                    &take_record_batch(batch, &UInt32Array::from(rows))?,
+                    &instant_time,
+                    index,
+                    &partition_path,
+                    &file_name,
+                    &record_keys,
+                )?;
+                let (stat, stats) = self
+                    .write_base_file(
+                        &instant_time,
+                        &partition_path,
+                        &file_id,
+                        MarkerType::Create,
+                        &file_group_records,
+                        &record_keys,
+                    )
+                    .await?;
+                column_stats.extend(stats);
+                partition_to_write_stats
+                    .entry(partition_path)
+                    .or_default()
+                    .push(stat);
+            }
+        }
+
+        let commit_metadata = HoodieCommitMetadata {
+            version: Some(COMMIT_METADATA_VERSION),
+            operation_type: Some(operation.as_ref().to_string()),
+            partition_to_write_stats: Some(partition_to_write_stats),
+            partition_to_replace_file_ids,
+            compacted: Some(false),
+            extra_metadata: Some(HashMap::from([(
+                SCHEMA_METADATA_KEY.to_string(),
+                self.get_writer_avro_schema(&data_schema).await?,
+            )])),
+        };
+
+        self.complete_write(
+            &instant_time,
+            &start,
+            &inflight,
+            commit_metadata,
+            column_stats,
+        )
+        .await
+    }
+
+    /// Complete the inflight instant, updating the metadata table right before, and delete the
+    /// markers of the written files.
+    async fn complete_write(
+        &self,
+        instant_time: &str,
+        start: &TransactionStart,
+        inflight: &Instant,
+        commit_metadata: HoodieCommitMetadata,
+        column_stats: Vec<ColumnStatsRecord>,
+    ) -> Result<WriteResult> {
+        let changes = MetadataTableChanges {
+            files: FilesPartitionChanges::from_commit_metadata(&commit_metadata),
+            column_stats,
+        };
+        let instant = TransactionManager::from_table(self)?
+            .commit_with(
+                start,
+                inflight,
+                &commit_metadata,
+                self.update_metadata_table(instant_time, &changes),
+            )
+            .await?;
+
+        let storage = &self.file_system_view.storage;
+        for path in self.list_marker_paths(instant_time).await? {
+            storage.delete_file(&path).await?;
+        }
+
+        Ok(WriteResult {
+            instant,
+            commit_metadata,
+        })
+    }
+
+    fn ensure_writable(&self) -> Result<()> {
+        if self.is_metadata_table_enabled() {
+            self.ensure_metadata_table_writable()?;
+        }
+        match BaseFileFormatValue::from_configs(&self.hudi_configs)? {
+            None | Some(BaseFileFormatValue::Parquet) => {}
+            Some(format) => {
+                return Err(CoreError::Unsupported(format!(
+                    "Writing base files in {} format is not supported",
+                    format.as_ref()
+                )));
+            }
+        }
+        if self
+            .hudi_configs
+            .get_or_default(DropsPartitionFields)
+            .into()
+        {
+            return Err(CoreError::Unsupported(
+                "Writing to tables that drop partition fields is not supported".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that the records with the same key are merged by the ordering field, which updates
+    /// and deletes rely on.
+    fn ensure_merging_by_ordering(&self, verb: &str) -> Result<()> {
+        let merge_strategy: String = self.hudi_configs.get_or_default(RecordMergeStrategy).into();
+        if RecordMergeStrategyValue::from_str(&merge_strategy)?
+            != RecordMergeStrategyValue::OverwriteWithLatest
+        {
+            return Err(CoreError::Unsupported(format!(
+                "{verb} requires `{}` to be {}",
+                RecordMergeStrategy.as_ref(),
+                RecordMergeStrategyValue::OverwriteWithLatest.as_ref()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that the records match the data schema, and concatenate them into one batch.
+    fn concat_records(
+        &self,
+        data_schema: &Schema,
+        records: &[RecordBatch],
+    ) -> Result<Option<RecordBatch>> {
+        let Some(first) = records.first() else {
+            return Ok(None);
+        };
+        for batch in records {
+            let schema = batch.schema();
+            let matches = schema.fields().len() == data_schema.fields().len()
+                && schema
+                    .fields()
+                    .iter()
+                    .zip(data_schema.fields())
+                    .all(|(a, b)| {
+                        a.name() == b.name() && is_compatible_type(a.data_type(), b.data_type())
+                    });
+            if !matches {
+                return Err(CoreError::Schema(format!(
+                    "Records schema {schema:?} does not match the table schema {data_schema:?}"
+                )));
+            }
+        }
+        Ok(Some(concat_batches(&first.schema(), records)?))
+    }
+
+    /// Group the row indices and record keys of the records by the file groups to write to, i.e.,
+    /// by their partition paths, and by their buckets if the table uses the bucket index.
+    fn group_rows_by_file_group(
+        &self,
+        batch: &RecordBatch,
+        bucket_index: Option<&BucketIndex>,
+    ) -> Result<FileGroupToRows> {
+        let key_generator = BuiltinKeyGenerator::from_configs(&self.hudi_configs)?;
+        let record_keys = key_generator.get_record_keys(batch)?;
+        let partition_paths = key_generator.get_partition_paths(batch)?;
+        let mut file_group_to_rows: FileGroupToRows = BTreeMap::new();
+        for (row, (record_key, partition_path)) in
+            record_keys.into_iter().zip(partition_paths).enumerate()
+        {
+            let bucket = bucket_index.map(|index| index.bucket_id_of_record_key(&record_key));
+            let (rows, keys) = file_group_to_rows
+                .entry((partition_path, bucket))
+                .or_default();
+            rows.push(row as u32);
+            keys.push(record_key);
+        }
+        Ok(file_group_to_rows)
+    }
+
+    /// Get the ids of the file groups to replace, by partition paths.
+    async fn get_file_ids_to_replace(
+        &self,
+        replaced_partitions: &ReplacedPartitions,
+        written_partitions: &BTreeSet<String>,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut partition_to_file_ids: HashMap<String, Vec<String>> = HashMap::new();
+        for file_slice in self.get_file_slices(&ReadOptions::new()).await? {
+            let is_replaced = match replaced_partitions {
+                ReplacedPartitions::Written => {
+                    written_partitions.contains(&file_slice.partition_path)
+                }
+                ReplacedPartitions::All => true,
+                ReplacedPartitions::Given(partitions) => {
+                    partitions.contains(&file_slice.partition_path)
+                }
+            };
+            if is_replaced {
+                partition_to_file_ids
+                    .entry(file_slice.partition_path.clone())
+                    .or_default()
+                    .push(file_slice.file_id().to_string());
+            }
+        }
+        for file_ids in partition_to_file_ids.values_mut() {
+            file_ids.sort_unstable();
+        }
+        Ok(partition_to_file_ids)
+    }
+
+    /// Group the row indices and record keys of the records to upsert and to delete by the file
+    /// groups to write to, keyed by the partition paths and file ids.
+    ///
+    /// The records go to the latest file slices holding their record keys. If inserting, the
+    /// records to upsert whose keys are not found go to the file groups of their buckets for
+    /// tables using the bucket index, or to a new file group of each partition otherwise; the
+    /// other records whose keys are not found are left out.
+    async fn group_rows_by_existing_file_group(
+        &self,
+        upserts: Option<&RecordBatch>,
+        deletes: Option<&RecordBatch>,
+        inserting: bool,
+    ) -> Result<BTreeMap<(String, String), FileGroupWrite>> {
+        let key_generator = BuiltinKeyGenerator::from_configs(&self.hudi_configs)?;
+        let keys_and_partitions = |batch: Option<&RecordBatch>| -> Result<Vec<(String, String)>> {
+            match batch {
+                Some(batch) => Ok(key_generator
+                    .get_record_keys(batch)?
+                    .into_iter()
+                    .zip(key_generator.get_partition_paths(batch)?)
+                    .collect()),
+                None => Ok(Vec::new()),
+            }
+        };
+        let upsert_keys = keys_and_partitions(upserts)?;
+        let delete_keys = keys_and_partitions(deletes)?;
+        let partitions: HashSet<&String> = upsert_keys
+            .iter()
+            .chain(&delete_keys)
+            .map(|(_, partition_path)| partition_path)
+            .collect();
+
+        let reader = self.create_file_group_reader_with_options(
+            Some(&ReadOptions::new()),
+            std::iter::empty::<(&str, &str)>(),
+        )?;
+        let mut file_group_writes: BTreeMap<(String, String), FileGroupWrite> = BTreeMap::new();
+        let mut key_to_file_group: HashMap<(String, String), (String, String)> = HashMap::new();
+        for file_slice in self.get_file_slices(&ReadOptions::new()).await? {
+            if !partitions.contains(&file_slice.partition_path) {
+                continue;
+            }
+            let existing = reader
+                .read_file_slice(&file_slice, &ReadOptions::new())
+                .await?;
+            let file_group = (
+                file_slice.partition_path.clone(),
+                file_slice.file_id().to_string(),
+            );
+            for key in key_generator.get_record_keys(&existing)? {
+                key_to_file_group.insert((file_group.0.clone(), key), file_group.clone());
+            }
+            file_group_writes.insert(
+                file_group,
+                FileGroupWrite {
+                    file_slice: Some(file_slice),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let bucket_index = match inserting {
+            true => BucketIndex::from_configs(&self.hudi_configs)?,
+            false => None,
+        };
+        let bucket_to_file_slice = match bucket_index {
+            Some(_) => self.get_bucket_file_slices().await?,
+            None => HashMap::new(),
+        };
+        let mut new_file_ids: HashMap<(String, Option<u32>), String> = HashMap::new();
+        for (row, (record_key, partition_path)) in upsert_keys.into_iter().enumerate() {
+            let file_group =
+                match key_to_file_group.get(&(partition_path.clone(), record_key.clone())) {
+                    Some(file_group) => file_group.clone(),
+                    None if inserting => {
+                        let bucket = bucket_index
+                            .as_ref()
+                            .map(|index| index.bucket_id_of_record_key(&record_key));
+                        let file_id = match bucket
+                            .and_then(|b| bucket_to_file_slice.get(&(partition_path.clone(), b)))
+                        {
+                            Some(file_slice) => file_slice.file_id().to_string(),
+                            None => new_file_ids
+                                .entry((partition_path.clone(), bucket))
+                                .or_insert_with(|| new_file_id(bucket))
+                                .clone(),
+                        };
+                        (partition_path, file_id)
+                    }
+                    None => continue,
+                };
+            let write = file_group_writes.entry(file_group).or_default();
+            write.upsert_rows.push(row as u32);
+            write.upsert_keys.push(record_key);
+        }
+        for (row, (record_key, partition_path)) in delete_keys.into_iter().enumerate() {
+            if let Some(file_group) = key_to_file_group.get(&(partition_path, record_key.clone())) {
+                let write = file_group_writes.entry(file_group.clone()).or_default();
+                write.delete_rows.push(row as u32);
+                write.delete_keys.push(record_key);
+            }
+        }
+        file_group_writes
+            .retain(|_, write| !write.upsert_rows.is_empty() || !write.delete_rows.is_empty());
+        Ok(file_group_writes)
+    }
+
+    /// Get the latest file slices by their partition paths and buckets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file group is not written with the bucket index, or more than one
+    /// file group is written for a bucket.
+    async fn get_bucket_file_slices(&self) -> Result<HashMap<(String, u32), FileSlice>> {
+        let mut bucket_to_file_slice = HashMap::new();
+        for file_slice in self.get_file_slices(&ReadOptions::new()).await? {
+            let Some(bucket) = parse_bucket_id(file_slice.file_id()) else {
+                return Err(CoreError::FileGroup(format!(
+                    "File group {} in partition '{}' is not written with the bucket index",
+                    file_slice.file_id(),
+                    file_slice.partition_path
+                )));
+            };
+            let key = (file_slice.partition_path.clone(), bucket);
+            if let Some(existing) = bucket_to_file_slice.insert(key, file_slice) {
+                return Err(CoreError::FileGroup(format!(
+                    "More than one file group is found for bucket {bucket} in partition '{}'",
+                    existing.partition_path
+                )));
+            }
+        }
+        Ok(bucket_to_file_slice)
+    }
+
+    /// Get the record keys of the records from their record key fields, which works whether or
+    /// not the records have the meta fields.
+    fn get_record_keys(&self, records: &RecordBatch) -> Result<Vec<String>> {
+        BuiltinKeyGenerator::from_configs(&self.hudi_configs)?.get_record_keys(records)
+    }
+
+    /// Merge the records with the same record key, keeping the latest one by the ordering field.
+    fn combine_records(&self, records: RecordBatch) -> Result<RecordBatch> {
+        RecordMerger::new(records.schema(), self.hudi_configs.clone())
+            .merge_record_batches(RecordBatches::new_with_data_batches([records]))
+    }
+
+    /// Merge the records with the existing ones of the file slice of a copy-on-write table,
+    /// leave out the records of the deleted keys, and write them to a new base file of the file
+    /// group.
+    async fn merge_base_file(
+        &self,
+        instant_time: &str,
+        file_slice: &FileSlice,
+        records: Option<&RecordBatch>,
+        deleted_keys: &[String],
+    ) -> Result<(HoodieWriteStat, Vec<ColumnStatsRecord>)> {
+        let reader = self.create_file_group_reader_with_options(
+            Some(&ReadOptions::new()),
+            std::iter::empty::<(&str, &str)>(),
+        )?;
+        let existing = reader
+            .read_file_slice(file_slice, &ReadOptions::new())
+            .await?;
+        // The meta fields synthesized on read are not written if the table does not populate them.
+        let existing = if self.hudi_configs.get_or_default(PopulatesMetaFields).into() {
+            existing
+        } else {
+            remove_meta_fields(&existing)?
+        };
+        let (merged, num_updates, num_inserts) = match records {
+            Some(records) => {
+                let existing_keys: HashSet<String> =
+                    self.get_record_keys(&existing)?.into_iter().collect();
+                let num_updates = self
+                    .get_record_keys(records)?
+                    .iter()
+                    .filter(|key| existing_keys.contains(*key))
+                    .count();
+                let records = cast_to_schema(records, existing.schema())?;
+                let merged = RecordMerger::new(existing.schema(), self.hudi_configs.clone())
+                    .merge_record_batches(RecordBatches::new_with_data_batches([
+                        existing,
+                        records.clone(),
+                    ]))?;
+                (merged, num_updates, records.num_rows() - num_updates)
+            }
+            None => (existing, 0, 0),
+        };
+
+        let deleted_keys: HashSet<&str> = deleted_keys.iter().map(String::as_str).collect();
+        let kept_rows: Vec<u32> = self
+            .get_record_keys(&merged)?
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| !deleted_keys.contains(key.as_str()))
+            .map(|(row, _)| row as u32)
+            .collect();
+        let num_deletes = merged.num_rows() - kept_rows.len();
+        let merged = take_record_batch(&merged, &UInt32Array::from(kept_rows))?;
+        let merged_keys = self.get_record_keys(&merged)?;
+
+        let (mut stat, column_stats) = self
+            .write_base_file(
+                instant_time,
+                &file_slice.partition_path,
+                file_slice.file_id(),
+                MarkerType::Merge,
+                &merged,
+                &merged_keys,
+            )
+            .await?;
+        stat.prev_commit = Some(file_slice.base_file.commit_timestamp.clone());
+        stat.prev_base_file = Some(file_slice.base_file.file_name());
+        stat.num_update_writes = Some(num_updates as i64);
+        stat.num_inserts = Some(num_inserts as i64);
+        stat.num_deletes = Some(num_deletes as i64);
+        Ok((stat, column_stats))
+    }
+
+    /// Append the records to a new log file of the file slice of a merge-on-read table, the
+    /// records to update in a data block and the records to delete in a delete block.
+    ///
+    /// Table version 8 and above name the log file with the instant time, while table version 6
+    /// names it with the instant time of the file slice and the next log version.
+    async fn append_log_file(
+        &self,
+        instant_time: &str,
+        file_slice: &FileSlice,
+        records: Option<&RecordBatch>,
+        deletes: Option<&RecordBatch>,
+    ) -> Result<(HoodieWriteStat, Vec<ColumnStatsRecord>)> {
+        let storage = &self.file_system_view.storage;
+        let partition_path = &file_slice.partition_path;
+        let file_id = file_slice.file_id();
+        let base_timestamp = &file_slice.base_file.commit_timestamp;
+        let table_version: isize = self.hudi_configs.get(TableVersion)?.into();
+        let (timestamp, version) = if table_version >= 8 {
+            (instant_time.to_string(), 1)
+        } else {
+            // Also count the log files of failed writes, which are not in the file slice.
+            let latest_version = storage
+                .list_files(Some(partition_path))
+                .await?
+                .into_iter()
+                .filter_map(|f| LogFile::from_str(&f.name).ok())
+                .filter(|l| l.file_id == file_id && &l.timestamp == base_timestamp)
+                .map(|l| l.version)
+                .max()
+                .unwrap_or_default();
+            (base_timestamp.clone(), latest_version + 1)
+        };
+        let log_file = LogFile {
+            file_id: file_id.to_string(),
+            timestamp,
+            completion_timestamp: None,
+            extension: "log".to_string(),
+            version,
+            write_token: WRITE_TOKEN.to_string(),
+            file_metadata: None,
+        };
+        let file_name = log_file.file_name();
+        let relative_path = relative_path(partition_path, &file_name);
+
+        self.create_marker(instant_time, &relative_path, MarkerType::Append)
+            .await?;
+        let mut writer = LogFileWriter::new(storage.clone(), &relative_path);
+        let mut column_stats = Vec::new();
+        let (mut num_updates, mut num_deletes, mut num_blocks) = (0, 0, 0);
+        if let Some(records) = records {
+            let (name, namespace) = avro_record_name_and_namespace(&self.table_name());
+            let avro_schema = AvroSchema::parse_str(&arrow_schema_to_avro_schema_str(
+                &records.schema(),
+                &name,
+                &namespace,
+            )?)?;
+            let parquet_metadata =
+                writer.append_parquet_data_block(instant_time, &avro_schema, records)?;
+            if self.is_metadata_table_enabled() {
+                column_stats = ColumnStatsRecord::from_parquet_metadata(
+                    partition_path,
+                    &file_name,
+                    &parquet_metadata,
+                )?;
+            }
+            num_updates = records.num_rows() as i64;
+            num_blocks += 1;
+        }
+        if let Some(deletes) = deletes {
+            writer.append_delete_block(
+                instant_time,
+                self.to_delete_records(deletes, partition_path)?,
+            )?;
+            num_deletes = deletes.num_rows() as i64;
+            num_blocks += 1;
+        }
+        let size = writer.finish().await?;
+
+        let stat = HoodieWriteStat {
+            file_id: Some(file_id.to_string()),
+            path: Some(relative_path),
+            base_file: Some(file_slice.base_file.file_name()),
+            log_files: Some(vec![file_name]),
+            prev_commit: Some(base_timestamp.clone()),
+            num_writes: Some(num_updates),
+            num_deletes: Some(num_deletes),
+            num_update_writes: Some(num_updates),
+            num_inserts: Some(0),
+            total_write_bytes: Some(size as i64),
+            total_write_errors: Some(0),
+            partition_path: Some(partition_path.to_string()),
+            total_log_blocks: Some(num_blocks),
+            file_size_in_bytes: Some(size as i64),
+            log_version: Some(version as i32),
+            log_offset: Some(0),
+            ..Default::default()
+        };
+        Ok((stat, column_stats))
+    }
+
+    /// Convert the records to delete into `HoodieDeleteRecord`s of their record keys and ordering
+    /// values.
+    fn to_delete_records(
+        &self,
+        records: &RecordBatch,
+        partition_path: &str,
+    ) -> Result<Vec<AvroValue>> {
+        let record_keys = self.get_record_keys(records)?;
+        let ordering_fields: Vec<String> = self
+            .hudi_configs
+            .try_get(OrderingFields)?
+            .map(Into::into)
+            .unwrap_or_default();
+        let ordering_array = match ordering_fields.first() {
+            Some(field) => Some(records.column_by_name(field).ok_or_else(|| {
+                CoreError::Schema(format!("Records have no ordering field {field}"))
+            })?),
+            None => None,
+        };
+        record_keys
+            .into_iter()
+            .enumerate()
+            .map(|(row, record_key)| {
+                Ok(AvroValue::Record(vec![
+                    (
+                        "recordKey".to_string(),
+                        AvroValue::Union(1, Box::new(AvroValue::String(record_key))),
+                    ),
+                    (
+                        "partitionPath".to_string(),
+                        AvroValue::Union(
+                            1,
+                            Box::new(AvroValue::String(partition_path.to_string())),
+                        ),
+                    ),
+                    (
+                        "orderingVal".to_string(),
+                        match ordering_array {
+                            Some(array) => ordering_value_to_avro(array, row)?,
+                            // Without an ordering field, the ordering values are nulls as in
+                            // Hudi Java.
+                            None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+                        },
+                    ),
+                ]))
+            })
+            .collect()
+    }
+
+    /// Prepend the meta fields to the records to write to a file, if the table populates them.
+    fn prepare_records(
+        &self,
+        records: &RecordBatch,
+        instant_time: &str,
+        index: usize,
+        partition_path: &str,
+        file_name: &str,
+        record_keys: &[String],
+    ) -> Result<RecordBatch> {
+        if self.hudi_configs.get_or_default(PopulatesMetaFields).into() {
+            prepend_meta_fields(
+                records,
+                instant_time,
+                index,
+                partition_path,
+                file_name,
+                record_keys,
+            )
+        } else {
+            Ok(records.clone())
+        }
+    }
+
+    /// Write the records to a new base file of the file group, and return its write stat and the
+    /// column stats of the written file.
+    ///
+    /// All records are counted as inserts in the write stat.
+    async fn write_base_file(
+        &self,
+        instant_time: &str,
+        partition_path: &str,
+        file_id: &str,
+        marker_type: MarkerType,
+        records: &RecordBatch,
+        record_keys: &[String],
+    ) -> Result<(HoodieWriteStat, Vec<ColumnStatsRecord>)> {
+        let storage = &self.file_system_view.storage;
+        let file_name = base_file_name(file_id, instant_time);
+        let relative_path = relative_path(partition_path, &file_name);
+
+        let (min_record_key, max_record_key) =
+            match (record_keys.iter().min(), record_keys.iter().max()) {
+                (Some(min), Some(max)) => (min.as_str(), max.as_str()),
+                _ => ("", ""),
+            };
+        let num_records = records.num_rows();
+
+        self.create_marker(instant_time, &relative_path, marker_type)
+            .await?;
+        if !partition_path.is_empty() {
+            let depth = partition_path.split('/').count();
+            storage
+                .put_file_data_if_absent(
+                    &format!("{partition_path}/{PARTITION_METAFIELD_PREFIX}"),
+                    Bytes::from(format!(
+                        "#partition metadata\ncommitTime={instant_time}\npartitionDepth={depth}\n"
+                    )),
+                )
+                .await?;
+        }
+        let mut writer =
+            ParquetBaseFileWriter::try_new(storage.clone(), &relative_path, records.schema())?;
+        writer.write(records)?;
+        writer.append_record_key_range(min_record_key, max_record_key);
+        let (size, parquet_metadata) = writer.finish().await?;
+
+        let column_stats = if self.is_metadata_table_enabled() {
+            ColumnStatsRecord::from_parquet_metadata(partition_path, &file_name, &parquet_metadata)?
+        } else {
+            Vec::new()
+        };
+        let stat = HoodieWriteStat {
+            file_id: Some(file_id.to_string()),
+            path: Some(relative_path),
+            prev_commit: Some(NULL_COMMIT.to_string()),
+            num_writes: Some(num_records as i64),
+            num_deletes: Some(0),
+            num_update_writes: Some(0),
+            num_inserts: Some(num_records as i64),
+            total_write_bytes: Some(size as i64),
+            total_write_errors: Some(0),
+            partition_path: Some(partition_path.to_string()),
+            file_size_in_bytes: Some(size as i64),
+            ..Default::default()
+        };
+        Ok((stat, column_stats))
+    }
+
+    /// Create the marker of a file written by the instant.
+    async fn create_marker(
+        &self,
+        instant_time: &str,
+        relative_path: &str,
+        marker_type: MarkerType,
+    ) -> Result<()> {
+        self.file_system_view
+            .storage
+            .put_file_data(
+                &format!(
+                    "{MARKER_BASE_DIR}/{instant_time}/{relative_path}.marker.{}",
+                    marker_type.as_ref()
+                ),
+                Bytes::new(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get the Avro schema of the written records, to be saved to the commit metadata.
+    async fn get_writer_avro_schema(&self, data_schema: &Schema) -> Result<String> {
+        match self.get_schema_in_avro_str().await {
+            Err(CoreError::SchemaNotFound(_)) => {
+                let (name, namespace) = avro_record_name_and_namespace(&self.table_name());
+                arrow_schema_to_avro_schema_str(data_schema, &name, &namespace)
+            }
+            result => result,
+        }
+    }
+}
+
+/// Whether the records of a field type can be written as the table field type.
+///
+/// Timestamps of the same unit are compatible regardless of their time zones, as the time zone is
+/// not kept in the table schema.
+fn is_compatible_type(record_type: &DataType, table_type: &DataType) -> bool {
+    match (record_type, table_type) {
+        (DataType::Timestamp(a, _), DataType::Timestamp(b, _)) => a == b,
+        _ => record_type == table_type,
+    }
+}
+
+/// Convert an ordering value to the `orderingVal` union of a `HoodieDeleteRecord`.
+fn ordering_value_to_avro(array: &ArrayRef, row: usize) -> Result<AvroValue> {
+    if array.is_null(row) {
+        return Ok(AvroValue::Union(0, Box::new(AvroValue::Null)));
+    }
+    let (position, value) = match array.data_type() {
+        DataType::Int32 => (
+            1,
+            AvroValue::Int(array.as_primitive::<Int32Type>().value(row)),
+        ),
+        DataType::Int64 => (
+            2,
+            AvroValue::Long(array.as_primitive::<Int64Type>().value(row)),
+        ),
+        DataType::Float32 => (
+            3,
+            AvroValue::Float(array.as_primitive::<Float32Type>().value(row)),
+        ),
+        DataType::Float64 => (
+            4,
+            AvroValue::Double(array.as_primitive::<Float64Type>().value(row)),
+        ),
+        DataType::Utf8 => (
+            6,
+            AvroValue::String(array.as_string::<i32>().value(row).to_string()),
+        ),
+        data_type => {
+            return Err(CoreError::Unsupported(format!(
+                "Deleting with ordering values of type {data_type} is not supported"
+            )));
+        }
+    };
+    Ok(AvroValue::Union(position, Box::new(value)))
+}
+
+/// Remove the Hudi meta fields from the records.
+fn remove_meta_fields(records: &RecordBatch) -> Result<RecordBatch> {
+    let indices: Vec<usize> = records
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| MetaField::from_str(f.name()).is_err())
+        .map(|(i, _)| i)
+        .collect();
+    Ok(records.project(&indices)?)
+}
+
+/// Cast the columns of the records to the types of the fields of the schema, by position.
+fn cast_to_schema(records: &RecordBatch, schema: SchemaRef) -> Result<RecordBatch> {
+    let columns = records
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(column, field)| cast(column, field.data_type()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// Create the id of a new file group, encoding the bucket number if given.
+fn new_file_id(bucket: Option<u32>) -> String {
+    match bucket {
+        Some(bucket) => new_bucket_file_id(bucket),
+        None => format!("{}-0", uuid::Uuid::new_v4()),
+    }
+}
+
+fn base_file_name(file_id: &str, instant_time: &str) -> String {
+    format!("{file_id}_{WRITE_TOKEN}_{instant_time}.parquet")
+}
+
+fn relative_path(partition_path: &str, file_name: &str) -> String {
+    if partition_path.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{partition_path}/{file_name}")
+    }
+}
+
+/// Prepend the Hudi meta fields to the records of a file.
+fn prepend_meta_fields(
+    records: &RecordBatch,
+    instant_time: &str,
+    index: usize,
+    partition_path: &str,
+    file_name: &str,
+    record_keys: &[String],
+) -> Result<RecordBatch> {
+    let num_rows = records.num_rows();
+    let meta_schema = MetaField::schema();
+    let mut fields = meta_schema.fields().to_vec();
+    fields.extend(records.schema().fields().iter().cloned());
+    let schema: SchemaRef = Arc::new(Schema::new_with_metadata(
+        fields,
+        records.schema().metadata().clone(),
+    ));
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![instant_time; num_rows])),
+        Arc::new(StringArray::from_iter_values(
+            (0..num_rows).map(|row| format!("{instant_time}_{index}_{row}")),
+        )),
+        Arc::new(StringArray::from_iter_values(record_keys)),
+        Arc::new(StringArray::from(vec![partition_path; num_rows])),
+        Arc::new(StringArray::from(vec![file_name; num_rows])),
+    ];
+    columns.extend(records.columns().iter().cloned());
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table::TableTypeValue;
+    use crate::file_group::base_file::parquet::ParquetBaseFileReader;
+    use crate::metadata::replace_commit::HoodieReplaceCommitMetadata;
+    use crate::table::creator::TableCreator;
+    use crate::table::test_util::{data_schema, read_names, records};
+    use arrow::compute::concat_batches;
+    use arrow_array::{Array, Int64Array};
+    use arrow_schema::Field;
+    use hudi_test::SampleTable;
+    use std::path::Path;
+
+    async fn read_sorted(base_path: &str) -> Vec<(String, String)> {
+        let table = Table::new(base_path).await.unwrap();
+        let batches = table.read(&ReadOptions::new()).await.unwrap();
+        if batches.is_empty() {
+            return Vec::new();
+        }
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        let keys = batch
+            .column_by_name("_hoodie_record_key")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .clone();
+        let partitions = batch
+            .column_by_name("_hoodie_partition_path")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .clone();
+        let mut rows: Vec<(String, String)> = (0..keys.len())
+            .map(|i| (partitions.value(i).to_string(), keys.value(i).to_string()))
+            .collect();
+        rows.sort_unstable();
+        rows
+    }
+
+    async fn create_table(base_path: &str, table_version: isize) -> Table {
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(TableTypeValue::CopyOnWrite)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_table_version(table_version)
+            .create()
+            .await
+            .unwrap()
+    }
+
+    fn rows(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(p, k)| (p.to_string(), k.to_string()))
+            .collect()
+    }
+
+    async fn test_overwrite_operations(table_version: isize) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, table_version).await;
+
+        let result = table
+            .insert_overwrite(&[records(&[
+                (1, "a", "sf", 1),
+                (2, "b", "nyc", 1),
+                (3, "c", "sf", 1),
+            ])])
+            .await
+            .unwrap();
+        assert!(result.instant.is_replacecommit());
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("INSERT_OVERWRITE"));
+        assert_eq!(metadata.iter_write_stats().count(), 2);
+        assert_eq!(metadata.iter_replace_file_ids().count(), 0);
+        assert!(
+            !Path::new(base_path)
+                .join(format!(".hoodie/.temp/{}/sf", result.instant.timestamp))
+                .join(format!(
+                    "{}.marker.CREATE",
+                    metadata.get_partition_write_stats("sf").unwrap()[0]
+                        .path
+                        .as_deref()
+                        .unwrap()
+                        .trim_start_matches("sf/")
+                ))
+                .exists()
+        );
+        assert!(
+            Path::new(base_path)
+                .join("sf/.hoodie_partition_metadata")
+                .exists()
+        );
+        assert_eq!(
+            read_sorted(base_path).await,
+            rows(&[("nyc", "2"), ("sf", "1"), ("sf", "3")])
+        );
+
+        let table = Table::new(base_path).await.unwrap();
+        let sf_file_id = metadata.get_partition_write_stats("sf").unwrap()[0]
+            .file_id
+            .clone()
+            .unwrap();
+        let result = table
+            .insert_overwrite(&[records(&[(4, "d", "sf", 1), (5, "e", "la", 1)])])
+            .await
+            .unwrap();
+        let metadata = &result.commit_metadata;
+        assert_eq!(
+            metadata.get_partition_replace_file_ids("sf"),
+            Some(&vec![sf_file_id])
+        );
+        assert_eq!(metadata.get_partition_replace_file_ids("nyc"), None);
+        assert_eq!(
+            read_sorted(base_path).await,
+            rows(&[("la", "5"), ("nyc", "2"), ("sf", "4")])
+        );
+
+        let table = Table::new(base_path).await.unwrap();
+        let timeline = table.get_timeline();
+        let completed = timeline.get_completed_replacecommits(true).await.unwrap();
+        assert_eq!(completed[0].timestamp, result.instant.timestamp);
+        let stored = timeline.get_instant_metadata(&completed[0]).await.unwrap();
+        let stored = HoodieReplaceCommitMetadata::from_json_map(&stored).unwrap();
+        assert_eq!(stored.iter_replace_file_ids().count(), 1);
+
+        let result = table.delete_partition(["nyc"]).await.unwrap();
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("DELETE_PARTITION"));
+        assert_eq!(metadata.iter_write_stats().count(), 0);
+        assert_eq!(metadata.get_partitions_with_replacements(), vec!["nyc"]);
+        assert_eq!(
+            read_sorted(base_path).await,
+            rows(&[("la", "5"), ("sf", "4")])
+        );
+
+        let table = Table::new(base_path).await.unwrap();
+        let result = table
+            .insert_overwrite_table(&[records(&[(6, "f", "nyc", 1)])])
+            .await
+            .unwrap();
+        let metadata = &result.commit_metadata;
+        let mut replaced_partitions = metadata.get_partitions_with_replacements();
+        replaced_partitions.sort_unstable();
+        assert_eq!(replaced_partitions, vec!["la", "sf"]);
+        assert_eq!(read_sorted(base_path).await, rows(&[("nyc", "6")]));
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_operations_v9() {
+        test_overwrite_operations(9).await;
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_operations_v6() {
+        test_overwrite_operations(6).await;
+    }
+
+    #[tokio::test]
+    async fn test_insert() {
+        for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+            let dir = tempfile::tempdir().unwrap();
+            let base_path = dir.path().to_str().unwrap();
+            let table = TableCreator::new(base_path, "trips", data_schema())
+                .with_table_type(table_type)
+                .with_record_key_fields(["id"])
+                .with_partition_fields(["city"])
+                .create()
+                .await
+                .unwrap();
+
+            let result = table
+                .insert(&[records(&[(1, "a", "sf", 1), (2, "b", "nyc", 1)])])
+                .await
+                .unwrap();
+            assert!(!result.instant.is_replacecommit());
+            assert_eq!(table.is_mor(), result.instant.action == Action::DeltaCommit);
+            let metadata = &result.commit_metadata;
+            assert_eq!(metadata.operation_type.as_deref(), Some("INSERT"));
+            assert_eq!(metadata.iter_write_stats().count(), 2);
+            assert!(metadata.partition_to_replace_file_ids.is_none());
+
+            let table = Table::new(base_path).await.unwrap();
+            table
+                .insert(&[records(&[(1, "c", "sf", 1), (3, "d", "la", 1)])])
+                .await
+                .unwrap();
+            assert_eq!(
+                read_sorted(base_path).await,
+                rows(&[("la", "3"), ("nyc", "2"), ("sf", "1"), ("sf", "1")])
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, 9).await;
+        let result = table.write(&[records(&[(1, "a", "sf", 1)])]).await.unwrap();
+        assert_eq!(
+            result.commit_metadata.operation_type.as_deref(),
+            Some("INSERT")
+        );
+        let table = Table::new_with_options(base_path, [(Operation.as_ref(), "upsert")])
+            .await
+            .unwrap();
+        assert!(matches!(
+            table
+                .write(&[records(&[(2, "b", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        assert!(matches!(
+            table
+                .insert(&[records(&[(1, "a", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_insert_without_meta_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_path, "trips", data_schema())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_hudi_option(PopulatesMetaFields.as_ref(), "false")
+            .create()
+            .await
+            .unwrap();
+        table
+            .insert(&[records(&[
+                (1, "a", "sf", 1),
+                (2, "b", "nyc", 1),
+                (3, "c", "sf", 1),
+            ])])
+            .await
+            .unwrap();
+
+        // The meta fields are not written, but synthesized on read.
+        let table = Table::new(base_path).await.unwrap();
+        let file_slices = table.get_file_slices(&ReadOptions::new()).await.unwrap();
+        let parquet_reader = ParquetBaseFileReader::new(table.file_system_view.storage.clone());
+        let file_schema = parquet_reader
+            .get_schema(&file_slices[0].base_file_relative_path().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(file_schema.fields(), data_schema().fields());
+        assert_eq!(
+            read_sorted(base_path).await,
+            rows(&[("nyc", "2"), ("sf", "1"), ("sf", "3")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_insert_overwrite_existing_table_with_metadata_table() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        Table::new(&base_path)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+        let table = Table::new(&base_path).await.unwrap();
+        assert!(table.is_metadata_table_enabled());
+        let before = read_sorted(&base_path).await;
+        let partition = before[0].0.clone();
+        let replaced_rows = before.iter().filter(|(p, _)| p == &partition).count();
+
+        let batches = table
+            .read(
+                &ReadOptions::new()
+                    .with_filters([("_hoodie_partition_path", "=", partition.as_str())])
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let data_schema = Arc::new(table.get_schema().await.unwrap());
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        let batch = batch
+            .project(
+                &data_schema
+                    .fields()
+                    .iter()
+                    .map(|f| batch.schema().index_of(f.name()).unwrap())
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+        let batch = batch.slice(0, 1);
+        let result = table.insert_overwrite(&[batch]).await.unwrap();
+        assert_eq!(
+            result.commit_metadata.get_partitions_with_replacements(),
+            vec![partition.clone()]
+        );
+
+        let after = read_sorted(&base_path).await;
+        assert_eq!(after.len(), before.len() - replaced_rows + 1);
+        let table = Table::new(&base_path).await.unwrap();
+        let mdt = table.new_metadata_table().await.unwrap();
+        assert!(
+            mdt.timeline
+                .completed_commits
+                .iter()
+                .any(|i| i.timestamp == result.instant.timestamp)
+        );
+    }
+
+    const BUCKET_OPTIONS: [(&str, &str); 2] = [
+        ("hoodie.index.type", "BUCKET"),
+        ("hoodie.bucket.index.num.buckets", "4"),
+    ];
+
+    async fn test_bucket_index_upserts(table_type: TableTypeValue, table_version: isize) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(table_type.clone())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_table_version(table_version)
+            .with_hudi_options(BUCKET_OPTIONS)
+            .create()
+            .await
+            .unwrap();
+        let bucket_index = BucketIndex::from_configs(&table.hudi_configs)
+            .unwrap()
+            .unwrap();
+
+        let result = table
+            .insert_overwrite(&[records(&[
+                (1, "a", "sf", 1),
+                (2, "b", "nyc", 1),
+                (3, "c", "sf", 1),
+            ])])
+            .await
+            .unwrap();
+        for (_, stat) in result.commit_metadata.iter_write_stats() {
+            assert!(parse_bucket_id(stat.file_id.as_deref().unwrap()).is_some());
+        }
+
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        let result = table
+            .upsert(&[
+                records(&[(1, "a2", "sf", 2), (4, "d", "sf", 1)]),
+                records(&[(1, "a3", "sf", 1), (2, "b2", "nyc", 2)]),
+            ])
+            .await
+            .unwrap();
+        let expected_action = if table_type == TableTypeValue::MergeOnRead {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        assert_eq!(result.instant.action, expected_action);
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("UPSERT"));
+        let mut num_updates = 0;
+        let mut num_inserts = 0;
+        for (partition_path, stat) in metadata.iter_write_stats() {
+            let file_id = stat.file_id.as_deref().unwrap();
+            let keys: Vec<&str> = match partition_path.as_str() {
+                "nyc" => vec!["2"],
+                _ => vec!["1", "4"],
+            };
+            assert!(keys.iter().any(|k| {
+                parse_bucket_id(file_id) == Some(bucket_index.bucket_id_of_record_key(k))
+            }));
+            num_updates += stat.num_update_writes.unwrap();
+            num_inserts += stat.num_inserts.unwrap();
+        }
+        assert_eq!(num_updates + num_inserts, 3);
+        if table_type == TableTypeValue::CopyOnWrite {
+            assert_eq!((num_updates, num_inserts), (2, 1));
+        } else {
+            assert!(
+                metadata
+                    .iter_write_stats()
+                    .any(|(_, s)| s.log_files.is_some())
+            );
+        }
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[("1", "a2"), ("2", "b2"), ("3", "c"), ("4", "d")])
+        );
+
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        table
+            .upsert(&[records(&[(4, "d2", "sf", 2), (5, "e", "la", 1)])])
+            .await
+            .unwrap();
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[
+                ("1", "a2"),
+                ("2", "b2"),
+                ("3", "c"),
+                ("4", "d2"),
+                ("5", "e")
+            ])
+        );
+
+        // Each bucket of a partition has at most one file group.
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        let file_slices = table.get_bucket_file_slices().await.unwrap();
+        assert_eq!(
+            file_slices.len(),
+            table
+                .get_file_slices(&ReadOptions::new())
+                .await
+                .unwrap()
+                .len()
+        );
+
+        // Reads prune the file groups of the other buckets.
+        let options = ReadOptions::new()
+            .with_filters([("city", "=", "sf"), ("id", "=", "4")])
+            .unwrap();
+        let pruned = table.get_file_slices(&options).await.unwrap();
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(
+            parse_bucket_id(pruned[0].file_id()),
+            Some(bucket_index.bucket_id_of_record_key("4"))
+        );
+        let batches = table.read(&options).await.unwrap();
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(table.get_record_keys(&batch).unwrap(), vec!["4"]);
+    }
+
+    #[tokio::test]
+    async fn test_bucket_index_upserts_cow_v9() {
+        test_bucket_index_upserts(TableTypeValue::CopyOnWrite, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_bucket_index_upserts_cow_v6() {
+        test_bucket_index_upserts(TableTypeValue::CopyOnWrite, 6).await;
+    }
+
+    #[tokio::test]
+    async fn test_bucket_index_upserts_mor_v9() {
+        test_bucket_index_upserts(TableTypeValue::MergeOnRead, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_bucket_index_upserts_mor_v6() {
+        test_bucket_index_upserts(TableTypeValue::MergeOnRead, 6).await;
+    }
+
+    async fn test_upserts_without_meta_fields(table_type: TableTypeValue) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(table_type)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_hudi_options(BUCKET_OPTIONS)
+            .with_hudi_option(PopulatesMetaFields.as_ref(), "false")
+            .create()
+            .await
+            .unwrap()
+            .insert_overwrite(&[records(&[(1, "a", "sf", 1), (2, "b", "nyc", 1)])])
+            .await
+            .unwrap();
+
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        table
+            .upsert(&[records(&[(1, "a2", "sf", 2), (3, "c", "sf", 1)])])
+            .await
+            .unwrap();
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        table
+            .update(&[records(&[(2, "b2", "nyc", 2)])])
+            .await
+            .unwrap();
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        table
+            .merge(
+                &[records(&[(3, "c2", "sf", 2), (4, "d", "la", 1)])],
+                &[records(&[(1, "a2", "sf", 3)])],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[("2", "b2"), ("3", "c2"), ("4", "d")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upserts_without_meta_fields_cow() {
+        test_upserts_without_meta_fields(TableTypeValue::CopyOnWrite).await;
+    }
+
+    #[tokio::test]
+    async fn test_upserts_without_meta_fields_mor() {
+        test_upserts_without_meta_fields(TableTypeValue::MergeOnRead).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_existing_table_without_meta_fields() {
+        let base_path = SampleTable::V9TxnsSimpleNometa.path_to_cow_fresh();
+        let table = Table::new(&base_path).await.unwrap();
+        let data_schema = Arc::new(table.get_schema().await.unwrap());
+        let batches = table.read(&ReadOptions::new()).await.unwrap();
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        let batch = batch
+            .project(
+                &data_schema
+                    .fields()
+                    .iter()
+                    .map(|f| batch.schema().index_of(f.name()).unwrap())
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+        let updated = batch.slice(0, 1);
+        let result = table.update(&[updated]).await.unwrap();
+        assert_eq!(
+            result
+                .commit_metadata
+                .iter_write_stats()
+                .map(|(_, s)| s.num_update_writes.unwrap())
+                .sum::<i64>(),
+            1
+        );
+        assert_eq!(read_sorted(&base_path).await.len(), batch.num_rows());
+    }
+
+    #[tokio::test]
+    async fn test_write_conflict_leaves_metadata_table_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let options = [
+            BUCKET_OPTIONS[0],
+            BUCKET_OPTIONS[1],
+            (
+                "hoodie.write.concurrency.mode",
+                "OPTIMISTIC_CONCURRENCY_CONTROL",
+            ),
+        ];
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_hudi_options(options)
+            .create()
+            .await
+            .unwrap()
+            .upsert(&[records(&[(1, "a", "sf", 1)])])
+            .await
+            .unwrap();
+        Table::new(base_path)
+            .await
+            .unwrap()
+            .bootstrap_metadata_table()
+            .await
+            .unwrap();
+
+        // Both writers start from the same timeline and update the same file group.
+        let winner = Table::new_with_options(base_path, options).await.unwrap();
+        let loser = Table::new_with_options(base_path, options).await.unwrap();
+        assert!(loser.is_metadata_table_enabled());
+        winner
+            .upsert(&[records(&[(1, "a2", "sf", 2)])])
+            .await
+            .unwrap();
+        let err = loser
+            .upsert(&[records(&[(1, "a3", "sf", 3)])])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::WriteConflict(_)), "{err}");
+
+        let table = Table::new(base_path).await.unwrap();
+        let completed: HashSet<String> = table
+            .timeline
+            .completed_commits
+            .iter()
+            .map(|i| i.timestamp.clone())
+            .collect();
+        let failed: HashSet<String> = table
+            .timeline
+            .get_active_instants(false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|i| i.timestamp)
+            .filter(|t| !completed.contains(t))
+            .collect();
+        assert_eq!(failed.len(), 1);
+        let mdt = table.new_metadata_table().await.unwrap();
+        let mdt_instants = mdt.timeline.get_active_instants(false).await.unwrap();
+        assert!(
+            mdt_instants.iter().all(|i| !failed.contains(&i.timestamp)),
+            "the metadata table should have no instant of the failed commit {failed:?}"
+        );
+        assert!(
+            mdt.timeline
+                .completed_commits
+                .iter()
+                .any(|i| completed.contains(&i.timestamp))
+        );
+        assert_eq!(read_names(base_path).await, rows(&[("1", "a2")]));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, 9).await;
+        assert!(matches!(
+            table
+                .upsert(&[records(&[(1, "a", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+
+        // Without the ordering field, the records with the same key cannot be merged.
+        let table = Table::new_with_options(base_path, BUCKET_OPTIONS)
+            .await
+            .unwrap();
+        assert!(matches!(
+            table
+                .upsert(&[records(&[(1, "a", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+    }
+
+    async fn test_update_and_delete(table_type: TableTypeValue, table_version: isize) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(table_type.clone())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .with_table_version(table_version)
+            .create()
+            .await
+            .unwrap();
+        table
+            .insert(&[records(&[
+                (1, "a", "sf", 1),
+                (2, "b", "nyc", 1),
+                (3, "c", "sf", 1),
+            ])])
+            .await
+            .unwrap();
+
+        // The record of a key not in the table, or in another partition, is ignored.
+        let table = Table::new(base_path).await.unwrap();
+        let result = table
+            .update(&[records(&[
+                (1, "a2", "sf", 2),
+                (2, "b2", "la", 2),
+                (4, "d", "sf", 2),
+            ])])
+            .await
+            .unwrap();
+        let expected_action = if table_type == TableTypeValue::MergeOnRead {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        assert_eq!(result.instant.action, expected_action);
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("UPSERT"));
+        assert_eq!(metadata.iter_write_stats().count(), 1);
+        let (partition_path, stat) = metadata.iter_write_stats().next().unwrap();
+        assert_eq!(partition_path, "sf");
+        assert_eq!(stat.num_update_writes, Some(1));
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[("1", "a2"), ("2", "b"), ("3", "c")])
+        );
+
+        let table = Table::new(base_path).await.unwrap();
+        let result = table
+            .delete(&[records(&[
+                (1, "a2", "sf", 2),
+                (2, "b", "nyc", 1),
+                (5, "e", "nyc", 1),
+            ])])
+            .await
+            .unwrap();
+        assert_eq!(result.instant.action, expected_action);
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("DELETE"));
+        assert_eq!(metadata.iter_write_stats().count(), 2);
+        for (_, stat) in metadata.iter_write_stats() {
+            assert_eq!(stat.num_deletes, Some(1));
+            assert_eq!(
+                stat.log_files.is_some(),
+                table_type == TableTypeValue::MergeOnRead
+            );
+        }
+        assert_eq!(read_names(base_path).await, rows(&[("3", "c")]));
+
+        // A deleted record can be written again.
+        let table = Table::new(base_path).await.unwrap();
+        table
+            .insert(&[records(&[(1, "a3", "sf", 1)])])
+            .await
+            .unwrap();
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[("1", "a3"), ("3", "c")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_cow_v9() {
+        test_update_and_delete(TableTypeValue::CopyOnWrite, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_cow_v6() {
+        test_update_and_delete(TableTypeValue::CopyOnWrite, 6).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_mor_v9() {
+        test_update_and_delete(TableTypeValue::MergeOnRead, 9).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_mor_v6() {
+        test_update_and_delete(TableTypeValue::MergeOnRead, 6).await;
+    }
+
+    async fn test_merge(table_type: TableTypeValue, options: &[(&str, &str)]) {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_table_type(table_type.clone())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_ordering_fields(["ts"])
+            .create()
+            .await
+            .unwrap();
+        let table = Table::new_with_options(base_path, options.iter().copied())
+            .await
+            .unwrap();
+        let result = table
+            .merge(
+                &[records(&[(1, "a", "sf", 1), (2, "b", "nyc", 1)])],
+                &[records(&[(3, "c", "sf", 1)])],
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.commit_metadata.iter_write_stats().count(), 2);
+        assert_eq!(read_names(base_path).await, rows(&[("1", "a"), ("2", "b")]));
+
+        // Updates, inserts and deletes of the same file groups are written in one commit.
+        let table = Table::new_with_options(base_path, options.iter().copied())
+            .await
+            .unwrap();
+        let result = table
+            .merge(
+                &[records(&[
+                    (1, "a2", "sf", 2),
+                    (3, "c", "sf", 1),
+                    (4, "d", "la", 1),
+                ])],
+                &[records(&[(2, "b", "nyc", 1), (5, "e", "nyc", 1)])],
+            )
+            .await
+            .unwrap();
+        let expected_action = if table_type == TableTypeValue::MergeOnRead {
+            Action::DeltaCommit
+        } else {
+            Action::Commit
+        };
+        assert_eq!(result.instant.action, expected_action);
+        let metadata = &result.commit_metadata;
+        assert_eq!(metadata.operation_type.as_deref(), Some("UPSERT"));
+        let num_deletes: i64 = metadata
+            .iter_write_stats()
+            .map(|(_, stat)| stat.num_deletes.unwrap())
+            .sum();
+        assert_eq!(num_deletes, 1);
+        assert_eq!(
+            read_names(base_path).await,
+            rows(&[("1", "a2"), ("3", "c"), ("4", "d")])
+        );
+
+        let table = Table::new_with_options(base_path, options.iter().copied())
+            .await
+            .unwrap();
+        let file_slices = table.get_file_slices(&ReadOptions::new()).await.unwrap();
+        if options.is_empty() {
+            // The inserts go to a new file group of each partition, i.e., sf has two.
+            assert_eq!(file_slices.len(), 4);
+        } else {
+            // Each bucket of a partition has at most one file group.
+            assert_eq!(
+                table.get_bucket_file_slices().await.unwrap().len(),
+                file_slices.len()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_cow() {
+        test_merge(TableTypeValue::CopyOnWrite, &[]).await;
+    }
+
+    #[tokio::test]
+    async fn test_merge_mor() {
+        test_merge(TableTypeValue::MergeOnRead, &[]).await;
+    }
+
+    #[tokio::test]
+    async fn test_merge_cow_with_bucket_index() {
+        test_merge(TableTypeValue::CopyOnWrite, &BUCKET_OPTIONS).await;
+    }
+
+    #[tokio::test]
+    async fn test_merge_mor_with_bucket_index() {
+        test_merge(TableTypeValue::MergeOnRead, &BUCKET_OPTIONS).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, 9).await;
+        assert!(matches!(
+            table
+                .update(&[records(&[(1, "a", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+        assert!(matches!(
+            table
+                .delete(&[records(&[(1, "a", "sf", 1)])])
+                .await
+                .unwrap_err(),
+            CoreError::Unsupported(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_to_delete_records_with_null_ordering_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, 9).await;
+        let null_ordering_val = AvroValue::Union(0, Box::new(AvroValue::Null));
+
+        // The table has no ordering field.
+        let delete_records = table
+            .to_delete_records(&records(&[(1, "a", "sf", 1)]), "sf")
+            .unwrap();
+        assert_eq!(
+            delete_records,
+            vec![AvroValue::Record(vec![
+                (
+                    "recordKey".to_string(),
+                    AvroValue::Union(1, Box::new(AvroValue::String("1".to_string()))),
+                ),
+                (
+                    "partitionPath".to_string(),
+                    AvroValue::Union(1, Box::new(AvroValue::String("sf".to_string()))),
+                ),
+                ("orderingVal".to_string(), null_ordering_val.clone()),
+            ])]
+        );
+
+        let ordering_array: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None]));
+        assert_eq!(
+            ordering_value_to_avro(&ordering_array, 0).unwrap(),
+            AvroValue::Union(2, Box::new(AvroValue::Long(1)))
+        );
+        assert_eq!(
+            ordering_value_to_avro(&ordering_array, 1).unwrap(),
+            null_ordering_val
+        );
+    }
+
+    #[tokio::test]
+    async fn test_insert_overwrite_schema_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let table = create_table(base_path, 9).await;
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap();
+        assert!(matches!(
+            table.insert_overwrite(&[batch]).await.unwrap_err(),
+            CoreError::Schema(_)
+        ));
+        let table = Table::new(base_path).await.unwrap();
+        assert!(
+            table
+                .get_timeline()
+                .get_completed_replacecommits(false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
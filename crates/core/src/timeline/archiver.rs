@@ -0,0 +1,226 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Moving completed instants from the active timeline to the archived timeline.
+//!
+//! - Timeline layout 1: instants are appended as [HoodieArchivedMetaEntry] records to a new
+//!   archive log file `.commits_.archive.{version}_1-0-1` under `hoodie.archivelog.folder`.
+//! - Timeline layout 2: instants are written as [LSMTimelineInstant]s to a new level-0 file of
+//!   the LSM timeline history, which is then compacted per `hoodie.timeline.compaction.batch.size`.
+//!
+//! The instant files are removed from the active timeline only after the archive is written.
+
+use crate::Result;
+use crate::config::archive::HudiArchiveConfig;
+use crate::error::CoreError;
+use crate::file_group::log_file::writer::LogFileWriter;
+use crate::metadata::archived::HoodieArchivedMetaEntry;
+use crate::metadata::clean::{HoodieCleanMetadata, HoodieCleanerPlan};
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::replace_commit::HoodieReplaceCommitMetadata;
+use crate::metadata::rollback::HoodieRollbackMetadata;
+use crate::metadata::savepoint::HoodieSavepointMetadata;
+use crate::timeline::Timeline;
+use crate::timeline::instant::{Action, Instant, State};
+use crate::timeline::lsm_tree::{LSMTimelineInstant, LSMTree};
+use apache_avro::schema::AvroSchema;
+use bytes::Bytes;
+use std::collections::HashSet;
+
+/// File name prefix of the archive log files in timeline layout 1.
+pub(crate) const ARCHIVE_LOG_FILE_PREFIX: &str = ".commits_.archive";
+
+/// Version of the [HoodieArchivedMetaEntry] records.
+const ARCHIVED_META_ENTRY_VERSION: i32 = 1;
+
+impl Timeline {
+    /// Archive the given completed instants and remove them from the active timeline.
+    pub(crate) async fn archive_instants(&self, instants: &[Instant]) -> Result<()> {
+        if instants.is_empty() {
+            return Ok(());
+        }
+        if let Some(instant) = instants.iter().find(|i| i.state != State::Completed) {
+            return Err(CoreError::Timeline(format!(
+                "Only completed instants can be archived, but got {instant:?}"
+            )));
+        }
+
+        if self.active_loader.tracks_completion_time() {
+            self.archive_to_lsm_history(instants).await?;
+        } else {
+            self.archive_to_archive_log(instants).await?;
+        }
+        self.delete_active_instant_files(instants).await
+    }
+
+    /// Load the content of the requested instant file of the completed instant, if not empty.
+    async fn load_requested_instant_data(&self, instant: &Instant) -> Result<Option<Bytes>> {
+        let requested = Instant {
+            completion_timestamp: None,
+            state: State::Requested,
+            ..instant.clone()
+        };
+        match self.active_loader.load_instant_file_data(&requested).await {
+            Ok(data) if !data.is_empty() => Ok(Some(data)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn archive_to_lsm_history(&self, instants: &[Instant]) -> Result<()> {
+        let mut archived = Vec::with_capacity(instants.len());
+        for instant in instants {
+            let completion_time = instant.completion_timestamp.clone().ok_or_else(|| {
+                CoreError::Timeline(format!("Completion timestamp not found for {instant:?}"))
+            })?;
+            let metadata = self.active_loader.load_instant_file_data(instant).await?;
+            archived.push(LSMTimelineInstant {
+                instant_time: instant.timestamp.clone(),
+                completion_time,
+                action: instant.action.as_ref().to_string(),
+                metadata: (!metadata.is_empty()).then_some(metadata),
+                plan: self.load_requested_instant_data(instant).await?,
+            });
+        }
+
+        let lsm_tree = LSMTree::new(self.storage.clone());
+        lsm_tree.append_instants(&archived).await?;
+        let batch_size: usize = self
+            .hudi_configs
+            .get_or_default(HudiArchiveConfig::TimelineCompactionBatchSize)
+            .into();
+        lsm_tree.compact(batch_size).await
+    }
+
+    async fn to_archived_meta_entry(&self, instant: &Instant) -> Result<HoodieArchivedMetaEntry> {
+        let mut entry = HoodieArchivedMetaEntry {
+            commit_time: Some(instant.timestamp.clone()),
+            action_type: Some(instant.action.as_ref().to_string()),
+            version: Some(ARCHIVED_META_ENTRY_VERSION),
+            action_state: Some("COMPLETED".to_string()),
+            ..Default::default()
+        };
+        match instant.action {
+            Action::Commit | Action::DeltaCommit => {
+                let metadata = self.active_loader.load_instant_metadata(instant).await?;
+                entry.hoodie_commit_metadata =
+                    Some(HoodieCommitMetadata::from_json_map(&metadata)?);
+            }
+            Action::ReplaceCommit => {
+                let metadata = self.active_loader.load_instant_metadata(instant).await?;
+                entry.hoodie_replace_commit_metadata =
+                    Some(HoodieReplaceCommitMetadata::from_json_map(&metadata)?);
+            }
+            Action::Clean => {
+                let metadata = self.active_loader.load_instant_file_data(instant).await?;
+                entry.hoodie_clean_metadata =
+                    Some(HoodieCleanMetadata::from_avro_bytes(&metadata)?);
+                if let Some(plan) = self.load_requested_instant_data(instant).await? {
+                    entry.hoodie_cleaner_plan = Some(HoodieCleanerPlan::from_avro_bytes(&plan)?);
+                }
+            }
+            Action::Rollback => {
+                let metadata = self.active_loader.load_instant_file_data(instant).await?;
+                entry.hoodie_rollback_metadata =
+                    Some(HoodieRollbackMetadata::from_avro_bytes(&metadata)?);
+            }
+            Action::Savepoint => {
+                let metadata = self.active_loader.load_instant_file_data(instant).await?;
+                entry.hoodie_savepoint_metadata =
+                    Some(HoodieSavepointMetadata::from_avro_bytes(&metadata)?);
+            }
+            // The restore metadata is not modeled in the archived entries.
+            Action::Restore => {}
+        }
+        Ok(entry)
+    }
+
+    async fn archive_to_archive_log(&self, instants: &[Instant]) -> Result<()> {
+        let mut records = Vec::with_capacity(instants.len());
+        for instant in instants {
+            records.push(
+                self.to_archived_meta_entry(instant)
+                    .await?
+                    .to_avro_value()?,
+            );
+        }
+
+        let archive_dir = self.active_loader.get_archived_timeline_dir();
+        let latest_version = self
+            .storage
+            .list_files(Some(&archive_dir))
+            .await?
+            .iter()
+            .filter_map(|f| {
+                f.name
+                    .strip_prefix(&format!("{ARCHIVE_LOG_FILE_PREFIX}."))
+                    .and_then(|s| s.split('_').next())
+                    .and_then(|v| v.parse::<u32>().ok())
+            })
+            .max()
+            .unwrap_or(0);
+        let path = format!(
+            "{archive_dir}/{ARCHIVE_LOG_FILE_PREFIX}.{}_1-0-1",
+            latest_version + 1
+        );
+
+        let latest_instant_time = instants
+            .iter()
+            .map(|i| i.timestamp.as_str())
+            .max()
+            .unwrap_or_default();
+        let mut writer = LogFileWriter::new(self.storage.clone(), path);
+        writer.append_avro_data_block(
+            latest_instant_time,
+            &HoodieArchivedMetaEntry::get_schema(),
+            records,
+        )?;
+        writer.finish().await?;
+        Ok(())
+    }
+
+    /// Delete the requested, inflight, and completed files of the instants from the active timeline.
+    ///
+    /// The completed files are deleted last, so that an interrupted archival does not leave
+    /// requested or inflight files behind without their completed files.
+    async fn delete_active_instant_files(&self, instants: &[Instant]) -> Result<()> {
+        let timeline_dir = self.active_loader.get_timeline_dir();
+        let timestamps: HashSet<&str> = instants.iter().map(|i| i.timestamp.as_str()).collect();
+        let completed_file_names: HashSet<String> =
+            instants.iter().map(|i| i.file_name()).collect();
+
+        let (completed, others): (Vec<String>, Vec<String>) = self
+            .storage
+            .list_files(Some(&timeline_dir))
+            .await?
+            .into_iter()
+            .map(|f| f.name)
+            .filter(|name| {
+                name.split(['.', '_'])
+                    .next()
+                    .is_some_and(|ts| timestamps.contains(ts))
+            })
+            .partition(|name| completed_file_names.contains(name));
+
+        for name in others.iter().chain(completed.iter()) {
+            self.storage
+                .delete_file(&format!("{timeline_dir}/{name}"))
+                .await?;
+        }
+        Ok(())
+    }
+}
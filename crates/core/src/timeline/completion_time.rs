@@ -50,7 +50,10 @@ pub trait CompletionTimeView {
     /// Returns `Some(completion_timestamp)` if the request timestamp corresponds
     /// to a completed commit, `None` if the commit is pending/unknown or if
     /// completion time is not tracked.
-    fn get_completion_time(&self, request_timestamp: &str) -> Option<&str>;
+    ///
+    /// For archived commits whose completion timestamps are not loaded,
+    /// the request timestamp itself may be returned.
+    fn get_completion_time<'a>(&'a self, request_timestamp: &'a str) -> Option<&'a str>;
 
     /// Returns true if uncommitted files should be filtered out.
     fn should_filter_uncommitted(&self) -> bool;
@@ -31,6 +31,10 @@ pub enum Action {
     Commit,
     DeltaCommit,
     ReplaceCommit,
+    Clean,
+    Rollback,
+    Savepoint,
+    Restore,
 }
 
 impl FromStr for Action {
@@ -41,6 +45,10 @@ impl FromStr for Action {
             "commit" => Ok(Action::Commit),
             "deltacommit" => Ok(Action::DeltaCommit),
             "replacecommit" => Ok(Action::ReplaceCommit),
+            "clean" => Ok(Action::Clean),
+            "rollback" => Ok(Action::Rollback),
+            "savepoint" => Ok(Action::Savepoint),
+            "restore" => Ok(Action::Restore),
             _ => Err(CoreError::Timeline(format!("Invalid action: {s}"))),
         }
     }
@@ -52,6 +60,10 @@ impl AsRef<str> for Action {
             Action::Commit => "commit",
             Action::DeltaCommit => "deltacommit",
             Action::ReplaceCommit => "replacecommit",
+            Action::Clean => "clean",
+            Action::Rollback => "rollback",
+            Action::Savepoint => "savepoint",
+            Action::Restore => "restore",
         }
     }
 }
@@ -139,6 +151,21 @@ impl FromStr for Instant {
 }
 
 impl Instant {
+    /// Create a new [Instant] without completion timestamp.
+    ///
+    /// The `timestamp` is interpreted in the given `timezone` to derive the epoch millis.
+    pub fn try_new(timestamp: &str, action: Action, state: State, timezone: &str) -> Result<Self> {
+        Self::validate_timestamp(timestamp)?;
+        let dt = Self::parse_datetime(timestamp, timezone)?;
+        Ok(Self {
+            timestamp: timestamp.to_string(),
+            completion_timestamp: None,
+            action,
+            state,
+            epoch_millis: dt.timestamp_millis(),
+        })
+    }
+
     pub fn try_from_file_name_and_timezone(file_name: &str, timezone: &str) -> Result<Self> {
         let (timestamp_part, action_suffix) = file_name
             .split_once('.')
@@ -321,6 +348,10 @@ mod tests {
     fn test_action_methods() {
         assert_eq!(Action::Commit.as_ref(), "commit");
         assert_eq!(Action::ReplaceCommit.as_ref(), "replacecommit");
+        assert_eq!(Action::Clean.as_ref(), "clean");
+        assert_eq!(Action::Rollback.as_ref(), "rollback");
+        assert_eq!(Action::Savepoint.as_ref(), "savepoint");
+        assert_eq!(Action::Restore.as_ref(), "restore");
 
         assert!(!Action::Commit.is_replacecommit());
         assert!(Action::ReplaceCommit.is_replacecommit());
@@ -333,6 +364,10 @@ mod tests {
             Action::from_str("replacecommit").unwrap(),
             Action::ReplaceCommit
         );
+        assert_eq!(Action::from_str("clean").unwrap(), Action::Clean);
+        assert_eq!(Action::from_str("rollback").unwrap(), Action::Rollback);
+        assert_eq!(Action::from_str("savepoint").unwrap(), Action::Savepoint);
+        assert_eq!(Action::from_str("restore").unwrap(), Action::Restore);
         assert!(Action::from_str("invalid").is_err());
     }
 
@@ -21,11 +21,16 @@ use crate::Result;
 use crate::config::HudiConfigs;
 use crate::config::table::HudiTableConfig::{ArchiveLogFolder, TimelineHistoryPath, TimelinePath};
 use crate::error::CoreError;
+use crate::file_group::log_file::reader::LogFileReader;
 use crate::metadata::HUDI_METADATA_DIR;
+use crate::metadata::archived::HoodieArchivedMetaEntry;
 use crate::metadata::commit::HoodieCommitMetadata;
 use crate::storage::Storage;
-use crate::timeline::instant::Instant;
+use crate::timeline::archiver::ARCHIVE_LOG_FILE_PREFIX;
+use crate::timeline::instant::{Action, Instant, State};
+use crate::timeline::lsm_tree::LSMTree;
 use crate::timeline::selector::TimelineSelector;
+use bytes::Bytes;
 use log::debug;
 use serde_json::{Map, Value};
 use std::sync::Arc;
@@ -88,6 +93,14 @@ impl TimelineLoader {
         &self.storage
     }
 
+    /// Whether completed instants in this layout carry completion timestamps in their file names.
+    pub(crate) fn tracks_completion_time(&self) -> bool {
+        matches!(
+            self.layout,
+            TimelineLayout::V2Active | TimelineLayout::V2Archived
+        )
+    }
+
     /// Check if this is a Layout Two Active loader (for testing/assertions)
     #[cfg(test)]
     pub(crate) fn is_layout_two_active(&self) -> bool {
@@ -116,13 +129,19 @@ impl TimelineLoader {
 
     /// Returns the directory for archived timeline instants.
     ///
-    /// - Layout One (v6-v8): configurable via `hoodie.archivelog.folder` (default: `.hoodie/archived`)
+    /// - Layout One (v6-v8): configurable via `hoodie.archivelog.folder` (default: `.hoodie/archived`),
+    ///   resolved against `.hoodie/` if not already under it, as written by Hudi Java in `hoodie.properties`
     /// - Layout Two (v8+): `.hoodie/{timeline_path}/{history_path}` (LSM history)
-    fn get_archived_timeline_dir(&self) -> String {
+    pub(crate) fn get_archived_timeline_dir(&self) -> String {
         match self.layout {
             TimelineLayout::V1Active | TimelineLayout::V1Archived => {
                 // Layout 1 uses hoodie.archivelog.folder for archived timeline
-                self.hudi_configs.get_or_default(ArchiveLogFolder).into()
+                let archive_dir: String = self.hudi_configs.get_or_default(ArchiveLogFolder).into();
+                if archive_dir.starts_with(&format!("{HUDI_METADATA_DIR}/")) {
+                    archive_dir
+                } else {
+                    format!("{HUDI_METADATA_DIR}/{archive_dir}")
+                }
             }
             TimelineLayout::V2Active | TimelineLayout::V2Archived => {
                 // Layout 2 uses LSM history directory
@@ -208,6 +227,21 @@ impl TimelineLoader {
         }
     }
 
+    /// Write the content of the given instant to its file in the active timeline directory.
+    pub(crate) async fn save_instant_file(&self, instant: &Instant, data: Bytes) -> Result<()> {
+        match self.layout {
+            TimelineLayout::V1Active | TimelineLayout::V2Active => {
+                let timeline_dir = self.get_timeline_dir();
+                let path = instant.relative_path_with_base(&timeline_dir)?;
+                self.storage.put_file_data(&path, data).await?;
+                Ok(())
+            }
+            _ => Err(CoreError::Unsupported(
+                "Writing instants to the archived timeline is not supported.".to_string(),
+            )),
+        }
+    }
+
     /// Load archived timeline instants based on selector criteria.
     ///
     /// # Behavior
@@ -235,32 +269,116 @@ impl TimelineLoader {
 
         match self.layout {
             TimelineLayout::V1Archived => {
-                // Resolve archive folder from configs or fallback
-                let archive_dir: String = self.hudi_configs.get_or_default(ArchiveLogFolder).into();
+                let archive_dir = self.get_archived_timeline_dir();
 
                 // List files and try creating instants through selector
                 let files = self.storage.list_files(Some(&archive_dir)).await?;
                 let mut instants = Vec::new();
                 for file_info in files {
-                    if let Ok(instant) = selector.try_create_instant(file_info.name.as_str()) {
+                    if file_info.name.starts_with(ARCHIVE_LOG_FILE_PREFIX) {
+                        // Archive log files hold the archived instants as Avro records
+                        let path = format!("{archive_dir}/{}", file_info.name);
+                        let mut reader = LogFileReader::new(
+                            self.hudi_configs.clone(),
+                            self.storage.clone(),
+                            &path,
+                        )
+                        .await?;
+                        for record in reader.read_all_avro_records()? {
+                            let entry = HoodieArchivedMetaEntry::from_avro_value(&record)?;
+                            let (Some(commit_time), Some(action_type)) =
+                                (entry.commit_time, entry.action_type)
+                            else {
+                                continue;
+                            };
+                            let file_name = format!("{commit_time}.{action_type}");
+                            if let Ok(instant) = selector.try_create_instant(&file_name) {
+                                instants.push(instant);
+                            }
+                        }
+                    } else if let Ok(instant) = selector.try_create_instant(file_info.name.as_str())
+                    {
                         instants.push(instant);
                     }
                 }
                 instants.sort_unstable();
+                instants.dedup();
                 if desc {
                     instants.reverse();
                 }
                 Ok(instants)
             }
             TimelineLayout::V2Archived => {
-                // TODO: Implement v2 LSM history reader. For now, return empty.
-                let _ = (selector, desc);
-                Ok(Vec::new())
+                let lsm_tree = LSMTree::new(self.storage.clone());
+                let mut instants = Vec::new();
+                for archived in lsm_tree.read_instants().await? {
+                    let file_name = format!(
+                        "{}_{}.{}",
+                        archived.instant_time, archived.completion_time, archived.action
+                    );
+                    if let Ok(instant) = selector.try_create_instant(&file_name) {
+                        instants.push(instant);
+                    }
+                }
+                instants.sort_unstable();
+                if desc {
+                    instants.reverse();
+                }
+                Ok(instants)
             }
             _ => Ok(Vec::new()),
         }
     }
 
+    /// Load the raw content of the given instant's file.
+    pub(crate) async fn load_instant_file_data(&self, instant: &Instant) -> Result<Bytes> {
+        let timeline_dir = self.get_timeline_dir();
+        let path = instant.relative_path_with_base(&timeline_dir)?;
+        Ok(self.storage.get_file_data(path.as_str()).await?)
+    }
+
+    /// Load the data of the requested instant of a clustering, which is
+    /// `<timestamp>.clustering.requested` in Layout Two and
+    /// `<timestamp>.replacecommit.requested` in Layout One.
+    pub(crate) async fn load_clustering_plan_data(&self, timestamp: &str) -> Result<Bytes> {
+        let action = if self.tracks_completion_time() {
+            "clustering"
+        } else {
+            Action::ReplaceCommit.as_ref()
+        };
+        let path = format!(
+            "{}/{timestamp}.{action}.{}",
+            self.get_active_timeline_dir(),
+            State::Requested.as_ref()
+        );
+        Ok(self.storage.get_file_data(path.as_str()).await?)
+    }
+
+    /// Load the data of the requested instants of the `action`, e.g.,
+    /// `<timestamp>.compaction.requested`, from the active timeline, as
+    /// `(timestamp, data)`.
+    ///
+    /// Unlike [TimelineLoader::load_instants], this covers actions not
+    /// modeled by [Action], e.g., `compaction` and `clustering`.
+    pub(crate) async fn load_requested_plan_data(
+        &self,
+        action: &str,
+    ) -> Result<Vec<(String, Bytes)>> {
+        let timeline_dir = self.get_active_timeline_dir();
+        let suffix = format!(".{action}.{}", State::Requested.as_ref());
+        let mut plans = Vec::new();
+        for file_info in self.storage.list_files(Some(&timeline_dir)).await? {
+            let Some(timestamp) = file_info.name.strip_suffix(&suffix) else {
+                continue;
+            };
+            let path = format!("{timeline_dir}/{}", file_info.name);
+            let data = self.storage.get_file_data(path.as_str()).await?;
+            plans.push((timestamp.to_string(), data));
+        }
+        plans.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(plans)
+    }
+
     /// Load instant metadata from storage and parse based on the layout version.
     ///
     /// Layout Version 1 (v6-v8): JSON format
@@ -401,6 +519,25 @@ mod tests {
         assert_eq!(loader.get_archived_timeline_dir(), ".hoodie/custom_archive");
     }
 
+    #[test]
+    fn test_archive_folder_relative_to_metadata_dir() {
+        let options = HashMap::from([
+            (
+                HudiTableConfig::BasePath.as_ref().to_string(),
+                "/tmp/test".to_string(),
+            ),
+            (
+                HudiTableConfig::ArchiveLogFolder.as_ref().to_string(),
+                "archived".to_string(),
+            ),
+        ]);
+        let configs = Arc::new(HudiConfigs::new(options));
+        let storage = create_test_storage(configs.clone());
+        let loader = TimelineLoader::new_layout_one_archived(configs, storage);
+
+        assert_eq!(loader.get_archived_timeline_dir(), ".hoodie/archived");
+    }
+
     #[test]
     fn test_custom_timeline_paths() {
         let mut options = HashMap::new();
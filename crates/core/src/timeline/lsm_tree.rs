@@ -22,8 +22,27 @@ use crate::config::table::HudiTableConfig::{TimelineHistoryPath, TimelinePath};
 use crate::error::CoreError;
 use crate::metadata::HUDI_METADATA_DIR;
 use crate::storage::Storage;
+use arrow::array::{Array, BinaryArray, Int32Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use bytes::Bytes;
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
+
+/// Version of the [LSMTimelineInstant] records written to the history files.
+const LSM_TIMELINE_INSTANT_VERSION: i32 = 1;
+
+static LSM_TIMELINE_INSTANT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("instantTime", DataType::Utf8, false),
+        Field::new("completionTime", DataType::Utf8, false),
+        Field::new("action", DataType::Utf8, false),
+        Field::new("metadata", DataType::Binary, true),
+        Field::new("plan", DataType::Binary, true),
+        Field::new("version", DataType::Int32, true),
+    ]))
+});
 
 #[cfg(not(tarpaulin_include))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +68,19 @@ pub struct ManifestEntry {
     pub file_size: i64,
 }
 
+/// A completed instant archived in the LSM timeline history.
+///
+/// - `metadata`: the content of the completed instant file
+/// - `plan`: the content of the requested instant file, if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct LSMTimelineInstant {
+    pub instant_time: String,
+    pub completion_time: String,
+    pub action: String,
+    pub metadata: Option<Bytes>,
+    pub plan: Option<Bytes>,
+}
+
 /// LSM tree for v8+ timeline history management.
 /// The paths are resolved from configs on-the-fly via `hoodie.timeline.path` and `hoodie.timeline.history.path`.
 #[cfg(not(tarpaulin_include))]
@@ -106,4 +138,263 @@ impl LSMTree {
             Ok(None)
         }
     }
+
+    /// Write a new manifest version, then point the `_version_` file to it.
+    async fn write_manifest(&self, manifest: &TimelineManifest) -> Result<()> {
+        let history_dir = self.history_dir();
+        let data = serde_json::to_vec(manifest).map_err(|e| CoreError::Timeline(e.to_string()))?;
+        let manifest_path = format!("{history_dir}/manifest_{}", manifest.version);
+        self.storage
+            .put_file_data(&manifest_path, Bytes::from(data))
+            .await?;
+        let version_path = format!("{history_dir}/_version_");
+        self.storage
+            .put_file_data(&version_path, Bytes::from(manifest.version.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    /// Write the instants into a new history file at the given level.
+    async fn write_file(
+        &self,
+        instants: &[LSMTimelineInstant],
+        level: i32,
+    ) -> Result<ManifestEntry> {
+        let (Some(first), Some(last)) = (instants.first(), instants.last()) else {
+            return Err(CoreError::Timeline(
+                "Cannot write empty timeline history file".to_string(),
+            ));
+        };
+        let file_name = format!(
+            "{}_{}_{level}.parquet",
+            first.instant_time, last.instant_time
+        );
+
+        let batch = RecordBatch::try_new(
+            LSM_TIMELINE_INSTANT_SCHEMA.clone(),
+            vec![
+                Arc::new(StringArray::from_iter_values(
+                    instants.iter().map(|i| i.instant_time.as_str()),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    instants.iter().map(|i| i.completion_time.as_str()),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    instants.iter().map(|i| i.action.as_str()),
+                )),
+                Arc::new(BinaryArray::from_iter(
+                    instants.iter().map(|i| i.metadata.as_deref()),
+                )),
+                Arc::new(BinaryArray::from_iter(
+                    instants.iter().map(|i| i.plan.as_deref()),
+                )),
+                Arc::new(Int32Array::from(vec![
+                    LSM_TIMELINE_INSTANT_VERSION;
+                    instants.len()
+                ])),
+            ],
+        )?;
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, LSM_TIMELINE_INSTANT_SCHEMA.clone(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        let file_size = buf.len() as i64;
+        let path = format!("{}/{file_name}", self.history_dir());
+        self.storage.put_file_data(&path, Bytes::from(buf)).await?;
+        Ok(ManifestEntry {
+            file_name,
+            min_instant: first.instant_time.clone(),
+            max_instant: last.instant_time.clone(),
+            level,
+            file_size,
+        })
+    }
+
+    /// Read the instants from a history file.
+    async fn read_file(&self, entry: &ManifestEntry) -> Result<Vec<LSMTimelineInstant>> {
+        let path = format!("{}/{}", self.history_dir(), entry.file_name);
+        let data = self.storage.get_file_data(&path).await?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(data)?.build()?;
+        let mut instants = Vec::new();
+        for batch in reader {
+            let batch = batch?;
+            let string_column = |name: &str| -> Result<&StringArray> {
+                batch
+                    .column_by_name(name)
+                    .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+                    .ok_or_else(|| {
+                        CoreError::Timeline(format!("Invalid column '{name}' in {path}"))
+                    })
+            };
+            let binary_column = |name: &str| -> Result<&BinaryArray> {
+                batch
+                    .column_by_name(name)
+                    .and_then(|c| c.as_any().downcast_ref::<BinaryArray>())
+                    .ok_or_else(|| {
+                        CoreError::Timeline(format!("Invalid column '{name}' in {path}"))
+                    })
+            };
+            let instant_times = string_column("instantTime")?;
+            let completion_times = string_column("completionTime")?;
+            let actions = string_column("action")?;
+            let metadata = binary_column("metadata")?;
+            let plans = binary_column("plan")?;
+            for i in 0..batch.num_rows() {
+                instants.push(LSMTimelineInstant {
+                    instant_time: instant_times.value(i).to_string(),
+                    completion_time: completion_times.value(i).to_string(),
+                    action: actions.value(i).to_string(),
+                    metadata: (!metadata.is_null(i))
+                        .then(|| Bytes::copy_from_slice(metadata.value(i))),
+                    plan: (!plans.is_null(i)).then(|| Bytes::copy_from_slice(plans.value(i))),
+                });
+            }
+        }
+        Ok(instants)
+    }
+
+    /// Read all instants in the timeline history, sorted by instant time.
+    pub async fn read_instants(&self) -> Result<Vec<LSMTimelineInstant>> {
+        let Some(manifest) = self.read_manifest().await? else {
+            return Ok(Vec::new());
+        };
+        let mut instants = Vec::new();
+        for entry in &manifest.entries {
+            instants.extend(self.read_file(entry).await?);
+        }
+        instants.sort_by(|a, b| a.instant_time.cmp(&b.instant_time));
+        Ok(instants)
+    }
+
+    /// Append the instants to the timeline history as a new level-0 file.
+    pub async fn append_instants(&self, instants: &[LSMTimelineInstant]) -> Result<()> {
+        if instants.is_empty() {
+            return Ok(());
+        }
+        let mut sorted = instants.to_vec();
+        sorted.sort_by(|a, b| a.instant_time.cmp(&b.instant_time));
+
+        let mut manifest = self.read_manifest().await?.unwrap_or(TimelineManifest {
+            version: 0,
+            entries: Vec::new(),
+        });
+        let entry = self.write_file(&sorted, 0).await?;
+        manifest.version += 1;
+        manifest.entries.push(entry);
+        self.write_manifest(&manifest).await
+    }
+
+    /// Compact the timeline history.
+    ///
+    /// Whenever a level has at least `batch_size` files, the oldest `batch_size` files
+    /// of the level are merged into a single file of the next level. This repeats
+    /// until no level has enough files to compact.
+    pub async fn compact(&self, batch_size: usize) -> Result<()> {
+        if batch_size < 2 {
+            return Ok(());
+        }
+        let Some(mut manifest) = self.read_manifest().await? else {
+            return Ok(());
+        };
+        let mut level = 0;
+        while manifest.entries.iter().any(|e| e.level >= level) {
+            let mut candidates: Vec<ManifestEntry> = manifest
+                .entries
+                .iter()
+                .filter(|e| e.level == level)
+                .cloned()
+                .collect();
+            if candidates.len() < batch_size {
+                level += 1;
+                continue;
+            }
+            candidates.sort_by(|a, b| a.min_instant.cmp(&b.min_instant));
+            let batch = &candidates[..batch_size];
+
+            let mut instants = Vec::new();
+            for entry in batch {
+                instants.extend(self.read_file(entry).await?);
+            }
+            instants.sort_by(|a, b| a.instant_time.cmp(&b.instant_time));
+            let merged = self.write_file(&instants, level + 1).await?;
+
+            manifest
+                .entries
+                .retain(|e| !batch.iter().any(|b| b.file_name == e.file_name));
+            manifest.entries.push(merged);
+            manifest.version += 1;
+            self.write_manifest(&manifest).await?;
+
+            let history_dir = self.history_dir();
+            for entry in batch {
+                let path = format!("{history_dir}/{}", entry.file_name);
+                self.storage.delete_file(&path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::util::parse_uri;
+
+    fn archived_instant(instant_time: &str) -> LSMTimelineInstant {
+        LSMTimelineInstant {
+            instant_time: instant_time.to_string(),
+            completion_time: format!("{}9", &instant_time[..16]),
+            action: "commit".to_string(),
+            metadata: Some(Bytes::from(format!("metadata of {instant_time}"))),
+            plan: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_compact_and_read_instants() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new_with_base_url(parse_uri(dir.path().to_str().unwrap())?)?;
+        let lsm_tree = LSMTree::new(storage);
+        assert!(lsm_tree.read_manifest().await?.is_none());
+        assert!(lsm_tree.read_instants().await?.is_empty());
+
+        let timestamps: Vec<String> = (1..=5).map(|i| format!("2024010100000000{i}")).collect();
+        for ts in &timestamps {
+            lsm_tree.append_instants(&[archived_instant(ts)]).await?;
+        }
+        let manifest = lsm_tree.read_manifest().await?.unwrap();
+        assert_eq!(manifest.version, 5);
+        assert_eq!(manifest.entries.len(), 5);
+        assert!(manifest.entries.iter().all(|e| e.level == 0));
+
+        lsm_tree.compact(2).await?;
+        let manifest = lsm_tree.read_manifest().await?.unwrap();
+        let mut levels: Vec<i32> = manifest.entries.iter().map(|e| e.level).collect();
+        levels.sort_unstable();
+        // 5 files at L0 -> 2 files at L1 and 1 at L0 -> 1 file at L2 and 1 at L0
+        assert_eq!(levels, vec![0, 2]);
+        let l2 = manifest.entries.iter().find(|e| e.level == 2).unwrap();
+        assert_eq!(
+            l2.file_name,
+            "20240101000000001_20240101000000004_2.parquet"
+        );
+        let files = lsm_tree
+            .storage
+            .list_files(Some(&lsm_tree.history_dir()))
+            .await?;
+        assert_eq!(
+            files
+                .iter()
+                .filter(|f| f.name.ends_with(".parquet"))
+                .count(),
+            2
+        );
+
+        let instants = lsm_tree.read_instants().await?;
+        let expected: Vec<LSMTimelineInstant> =
+            timestamps.iter().map(|ts| archived_instant(ts)).collect();
+        assert_eq!(instants, expected);
+        Ok(())
+    }
 }
@@ -16,6 +16,7 @@
  * specific language governing permissions and limitations
  * under the License.
  */
+pub(crate) mod archiver;
 pub mod builder;
 pub mod completion_time;
 pub mod instant;
@@ -27,20 +28,31 @@ pub mod view;
 
 use crate::Result;
 use crate::config::HudiConfigs;
+use crate::config::table::HudiTableConfig::TimelineTimezone;
 use crate::error::CoreError;
 use crate::file_group::FileGroup;
 use crate::file_group::builder::replaced_file_groups_from_replace_commit;
+use crate::metadata::clean::HoodieCleanMetadata;
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::metadata::compaction::HoodieCompactionPlan;
+use crate::metadata::replace_commit::{
+    HoodieReplaceCommitMetadata, HoodieRequestedReplaceMetadata,
+};
+use crate::metadata::savepoint::HoodieSavepointMetadata;
 use crate::schema::resolver::{
     resolve_avro_schema_from_commit_metadata, resolve_data_schema_from_commit_metadata,
 };
 use crate::statistics::estimator::FileStatsEstimator;
 use crate::storage::Storage;
 use crate::timeline::builder::TimelineBuilder;
-use crate::timeline::instant::Action;
+use crate::timeline::instant::{Action, State};
 use crate::timeline::loader::TimelineLoader;
 use crate::timeline::selector::TimelineSelector;
+use crate::timeline::util::format_datetime_in_timezone;
 use crate::timeline::view::TimelineView;
 use arrow_schema::Schema;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use instant::Instant;
 
 use serde_json::{Map, Value};
@@ -57,6 +69,10 @@ pub struct Timeline {
     active_loader: TimelineLoader,
     archived_loader: Option<TimelineLoader>,
     pub completed_commits: Vec<Instant>,
+    /// The timestamp of the earliest instant of any action and state on the active timeline.
+    ///
+    /// Instants before it are considered archived.
+    pub(crate) earliest_active_timestamp: Option<String>,
 }
 
 pub const EARLIEST_START_TIMESTAMP: &str = "19700101000000000";
@@ -76,6 +92,7 @@ impl Timeline {
             active_loader,
             archived_loader,
             completed_commits: Vec::new(),
+            earliest_active_timestamp: None,
         }
     }
 
@@ -85,13 +102,16 @@ impl Timeline {
     ) -> Result<Self> {
         let storage = Storage::new(storage_options.clone(), hudi_configs.clone())?;
         let mut timeline = TimelineBuilder::new(hudi_configs, storage).build().await?;
-        let selector = TimelineSelector::completed_actions_in_range(
-            DEFAULT_LOADING_ACTIONS,
-            timeline.hudi_configs.clone(),
-            None,
-            None,
-        )?;
-        timeline.completed_commits = timeline.load_instants(&selector, false).await?;
+        // Load instants of all actions and states in one listing to also find
+        // where the active timeline starts.
+        let selector =
+            TimelineSelector::actions_in_states(&[], &[], timeline.hudi_configs.clone())?;
+        let instants = timeline.load_instants(&selector, false).await?;
+        timeline.earliest_active_timestamp = instants.first().map(|i| i.timestamp.clone());
+        timeline.completed_commits = instants
+            .into_iter()
+            .filter(|i| i.state == State::Completed && DEFAULT_LOADING_ACTIONS.contains(&i.action))
+            .collect();
         Ok(timeline)
     }
 
@@ -209,13 +229,156 @@ impl Timeline {
                 .ok_or_else(|| {
                     CoreError::CommitMetadata("Failed to get operation type".to_string())
                 })?;
-            if op_type == "cluster" {
+            // Writers store the name of the operation type enum, `CLUSTER`.
+            if op_type.eq_ignore_ascii_case("cluster") {
                 clustering_instants.push(instant);
             }
         }
         Ok(clustering_instants)
     }
 
+    /// Get the sort columns of the completed clustering commits, keyed by the
+    /// timestamps of the clustering [Instant]s.
+    ///
+    /// The sort columns are read from the clustering plans; clusterings that
+    /// do not sort the records are left out.
+    pub async fn get_clustering_sort_columns(&self) -> Result<HashMap<String, Vec<String>>> {
+        let mut sort_columns = HashMap::new();
+        for instant in self.get_completed_clustering_commits(false).await? {
+            let bytes = self
+                .active_loader
+                .load_clustering_plan_data(&instant.timestamp)
+                .await?;
+            let columns = HoodieRequestedReplaceMetadata::from_avro_bytes(&bytes)?
+                .clustering_plan
+                .and_then(|plan| plan.sort_columns());
+            if let Some(columns) = columns {
+                sort_columns.insert(instant.timestamp, columns);
+            }
+        }
+        Ok(sort_columns)
+    }
+
+    /// Get the file groups in the plans of the pending compactions and
+    /// clusterings as `(partition path, file id)`.
+    ///
+    /// A compaction completes as a `commit` and a clustering as a
+    /// `replacecommit` at the timestamp of its plan, which is pending until
+    /// then. Clustering plans are in `<timestamp>.clustering.requested` in
+    /// Layout Two and `<timestamp>.replacecommit.requested` in Layout One.
+    pub(crate) async fn get_file_groups_in_pending_compaction_and_clustering(
+        &self,
+    ) -> Result<HashSet<(String, String)>> {
+        let completed: HashSet<&str> = self
+            .completed_commits
+            .iter()
+            .map(|i| i.timestamp.as_str())
+            .collect();
+        let loader = &self.active_loader;
+        let mut file_groups = HashSet::new();
+        for (timestamp, bytes) in loader.load_requested_plan_data("compaction").await? {
+            if completed.contains(timestamp.as_str()) {
+                continue;
+            }
+            let plan = HoodieCompactionPlan::from_avro_bytes(&bytes)?;
+            file_groups.extend(
+                plan.iter_file_group_ids()
+                    .map(|(p, f)| (p.to_string(), f.to_string())),
+            );
+        }
+        let clustering_action = if loader.tracks_completion_time() {
+            "clustering"
+        } else {
+            Action::ReplaceCommit.as_ref()
+        };
+        for (timestamp, bytes) in loader.load_requested_plan_data(clustering_action).await? {
+            // The requested instants of other replace commits may be empty.
+            if completed.contains(timestamp.as_str()) || bytes.is_empty() {
+                continue;
+            }
+            let metadata = HoodieRequestedReplaceMetadata::from_avro_bytes(&bytes)?;
+            if let Some(plan) = metadata.clustering_plan {
+                file_groups.extend(
+                    plan.iter_file_group_ids()
+                        .map(|(p, f)| (p.to_string(), f.to_string())),
+                );
+            }
+        }
+        Ok(file_groups)
+    }
+
+    /// Get the timestamps of the requested clusterings, including the
+    /// completed ones.
+    ///
+    /// In Layout One, a clustering is a `replacecommit` whose requested
+    /// instant holds a clustering plan, unlike other replace commits.
+    pub(crate) async fn get_requested_clustering_timestamps(&self) -> Result<HashSet<String>> {
+        let loader = &self.active_loader;
+        if loader.tracks_completion_time() {
+            let plans = loader.load_requested_plan_data("clustering").await?;
+            return Ok(plans.into_iter().map(|(timestamp, _)| timestamp).collect());
+        }
+        let mut timestamps = HashSet::new();
+        for (timestamp, bytes) in loader
+            .load_requested_plan_data(Action::ReplaceCommit.as_ref())
+            .await?
+        {
+            if bytes.is_empty() {
+                continue;
+            }
+            let metadata = HoodieRequestedReplaceMetadata::from_avro_bytes(&bytes)?;
+            if metadata.clustering_plan.is_some() {
+                timestamps.insert(timestamp);
+            }
+        }
+        Ok(timestamps)
+    }
+
+    /// Get the completed clean [Instant]s in the timeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - If true, the [Instant]s are sorted in descending order.
+    pub async fn get_completed_cleans(&self, desc: bool) -> Result<Vec<Instant>> {
+        let selector = TimelineSelector::completed_actions_in_range(
+            &[Action::Clean],
+            self.hudi_configs.clone(),
+            None,
+            None,
+        )?;
+        self.load_instants_inner(&selector, desc).await
+    }
+
+    /// Get the [HoodieCleanMetadata] of a completed clean [Instant].
+    pub async fn get_clean_metadata(&self, instant: &Instant) -> Result<HoodieCleanMetadata> {
+        let bytes = self.active_loader.load_instant_file_data(instant).await?;
+        HoodieCleanMetadata::from_avro_bytes(&bytes)
+    }
+
+    /// Get the completed savepoint [Instant]s in the timeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - If true, the [Instant]s are sorted in descending order.
+    pub async fn get_completed_savepoints(&self, desc: bool) -> Result<Vec<Instant>> {
+        let selector = TimelineSelector::completed_actions_in_range(
+            &[Action::Savepoint],
+            self.hudi_configs.clone(),
+            None,
+            None,
+        )?;
+        self.load_instants_inner(&selector, desc).await
+    }
+
+    /// Get the [HoodieSavepointMetadata] of a completed savepoint [Instant].
+    pub async fn get_savepoint_metadata(
+        &self,
+        instant: &Instant,
+    ) -> Result<HoodieSavepointMetadata> {
+        let bytes = self.active_loader.load_instant_file_data(instant).await?;
+        HoodieSavepointMetadata::from_avro_bytes(&bytes)
+    }
+
     pub(crate) async fn get_instant_metadata(
         &self,
         instant: &Instant,
@@ -230,6 +393,172 @@ impl Timeline {
             .await
     }
 
+    /// Get the [HoodieCommitMetadata] of a completed commit, deltacommit or replacecommit [Instant].
+    pub async fn get_commit_metadata(&self, instant: &Instant) -> Result<HoodieCommitMetadata> {
+        let metadata = self.get_instant_metadata(instant).await?;
+        HoodieCommitMetadata::from_json_map(&metadata)
+    }
+
+    /// Get the [Instant]s of all actions and states on the active timeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - If true, the [Instant]s are sorted in descending order.
+    pub async fn get_active_instants(&self, desc: bool) -> Result<Vec<Instant>> {
+        let selector = TimelineSelector::actions_in_states(&[], &[], self.hudi_configs.clone())?;
+        self.load_instants_inner(&selector, desc).await
+    }
+
+    fn timezone(&self) -> String {
+        self.hudi_configs.get_or_default(TimelineTimezone).into()
+    }
+
+    /// Generate a new instant timestamp from the current time.
+    ///
+    /// If `after` is given, the generated timestamp is guaranteed to be later than it,
+    /// which keeps the timestamps monotonically increasing on the [Timeline].
+    pub(crate) fn new_instant_timestamp(&self, after: Option<&str>) -> Result<String> {
+        let timezone = self.timezone();
+        let mut millis = Utc::now().timestamp_millis();
+        if let Some(after) = after {
+            let after_millis = Instant::parse_datetime(after, &timezone)?.timestamp_millis();
+            millis = millis.max(after_millis + 1);
+        }
+        let dt = DateTime::from_timestamp_millis(millis)
+            .ok_or_else(|| CoreError::Timeline(format!("Invalid epoch millis: {millis}")))?;
+        format_datetime_in_timezone(&dt, &timezone)
+    }
+
+    /// Create a requested [Instant] of the given action and save its content to the active timeline.
+    pub(crate) async fn create_requested_instant(
+        &self,
+        action: Action,
+        timestamp: &str,
+        data: Bytes,
+    ) -> Result<Instant> {
+        let requested = Instant::try_new(timestamp, action, State::Requested, &self.timezone())?;
+        self.active_loader
+            .save_instant_file(&requested, data)
+            .await?;
+        Ok(requested)
+    }
+
+    /// Transition a requested [Instant] to inflight and save the inflight content to the active timeline.
+    pub(crate) async fn transition_requested_to_inflight(
+        &self,
+        requested: &Instant,
+        data: Bytes,
+    ) -> Result<Instant> {
+        if requested.state != State::Requested {
+            return Err(CoreError::Timeline(format!(
+                "Expected a requested instant to transition to inflight, but got: {requested:?}"
+            )));
+        }
+        let inflight = Instant {
+            state: State::Inflight,
+            ..requested.clone()
+        };
+        self.active_loader
+            .save_instant_file(&inflight, data)
+            .await?;
+        Ok(inflight)
+    }
+
+    /// Transition an inflight [Instant] to completed and save the completed content to the active timeline.
+    ///
+    /// For timeline layout 2, the completion timestamp is generated and set to the returned [Instant].
+    pub(crate) async fn transition_inflight_to_completed(
+        &self,
+        inflight: &Instant,
+        data: Bytes,
+    ) -> Result<Instant> {
+        if inflight.state != State::Inflight {
+            return Err(CoreError::Timeline(format!(
+                "Expected an inflight instant to transition to completed, but got: {inflight:?}"
+            )));
+        }
+        let completion_timestamp = if self.active_loader.tracks_completion_time() {
+            Some(self.new_instant_timestamp(Some(&inflight.timestamp))?)
+        } else {
+            None
+        };
+        let completed = Instant {
+            state: State::Completed,
+            completion_timestamp,
+            ..inflight.clone()
+        };
+        self.active_loader
+            .save_instant_file(&completed, data)
+            .await?;
+        Ok(completed)
+    }
+
+    /// Delete the inflight and requested files of a pending [Instant] from the active timeline.
+    ///
+    /// The inflight file is deleted first, so that an interrupted deletion leaves the instant
+    /// requested, which is still recognized as pending.
+    pub(crate) async fn delete_pending_instant(&self, instant: &Instant) -> Result<()> {
+        if instant.state == State::Completed {
+            return Err(CoreError::Timeline(format!(
+                "Expected a pending instant to delete, but got: {instant:?}"
+            )));
+        }
+        let timeline_dir = self.active_loader.get_timeline_dir();
+        for state in [State::Inflight, State::Requested] {
+            let pending = Instant {
+                state,
+                ..instant.clone()
+            };
+            self.storage
+                .delete_file(&pending.relative_path_with_base(&timeline_dir)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Delete all files of an [Instant] from the active timeline.
+    ///
+    /// The completed file is deleted first, so that an interrupted deletion leaves the instant
+    /// pending, which is still recognized as a failed write.
+    pub(crate) async fn delete_instant(&self, instant: &Instant) -> Result<()> {
+        if instant.state == State::Completed {
+            let timeline_dir = self.active_loader.get_timeline_dir();
+            self.storage
+                .delete_file(&instant.relative_path_with_base(&timeline_dir)?)
+                .await?;
+        }
+        let pending = Instant {
+            state: State::Inflight,
+            completion_timestamp: None,
+            ..instant.clone()
+        };
+        self.delete_pending_instant(&pending).await
+    }
+
+    /// Serialize the commit metadata in the format of completed instants of the action on the
+    /// active timeline: Avro for timeline layout 2, and JSON for timeline layout 1.
+    ///
+    /// For timeline layout 2, `replacecommit` instants are serialized as
+    /// [HoodieReplaceCommitMetadata].
+    pub(crate) fn serialize_commit_metadata(
+        &self,
+        action: &Action,
+        metadata: &HoodieCommitMetadata,
+    ) -> Result<Bytes> {
+        if self.active_loader.tracks_completion_time() {
+            if action.is_replacecommit() {
+                let metadata = HoodieReplaceCommitMetadata::from(metadata);
+                return Ok(Bytes::from(metadata.to_avro_bytes()?));
+            }
+            Ok(Bytes::from(metadata.to_avro_bytes()?))
+        } else {
+            let json = serde_json::to_vec(&metadata.to_json_map()?).map_err(|e| {
+                CoreError::CommitMetadata(format!("Failed to serialize to JSON: {e}"))
+            })?;
+            Ok(Bytes::from(json))
+        }
+    }
+
     pub(crate) async fn get_latest_commit_metadata(&self) -> Result<Map<String, Value>> {
         match self.completed_commits.iter().next_back() {
             Some(instant) => self.get_instant_metadata(instant).await,
@@ -261,7 +590,8 @@ impl Timeline {
             &self.completed_commits,
             excludes,
             &self.hudi_configs,
-        ))
+        )
+        .with_archived_before(self.earliest_active_timestamp.clone()))
     }
 
     /// Get the latest [apache_avro::schema::Schema] as [String] from the [Timeline].
@@ -792,6 +1122,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_commit_metadata_and_active_instants() {
+        let base_url = SampleTable::V8Nonpartitioned.url_to_cow();
+        let timeline = create_test_timeline(base_url).await;
+
+        let instant = timeline.completed_commits.first().unwrap();
+        let metadata = timeline.get_commit_metadata(instant).await.unwrap();
+        let write_stats = metadata.partition_to_write_stats.unwrap();
+        assert!(write_stats.values().flatten().all(|s| s.file_id.is_some()));
+
+        let instants = timeline.get_active_instants(false).await.unwrap();
+        assert!(instants.len() > timeline.completed_commits.len());
+        assert!(instants.iter().any(|i| i.state == State::Requested));
+        assert!(
+            timeline
+                .completed_commits
+                .iter()
+                .all(|commit| instants.contains(commit))
+        );
+        let desc = timeline.get_active_instants(true).await.unwrap();
+        assert_eq!(desc.first(), instants.last());
+    }
+
     #[tokio::test]
     async fn test_get_latest_commit_timestamp() {
         let base_url = SampleTable::V8Nonpartitioned.url_to_cow();
@@ -812,4 +1165,63 @@ mod tests {
         assert!(timestamp.is_some());
         assert!(!timestamp.unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_get_clustering_sort_columns() {
+        let base_path = SampleTable::V9TxnsSimpleMeta.path_to_cow_fresh();
+        let base_url = Url::from_file_path(&base_path).unwrap();
+        let timeline = create_test_timeline(base_url).await;
+        let clustering = timeline
+            .get_completed_clustering_commits(false)
+            .await
+            .unwrap();
+        assert_eq!(clustering.len(), 1);
+        assert!(
+            timeline
+                .get_clustering_sort_columns()
+                .await
+                .unwrap()
+                .is_empty(),
+            "The clustering of the sample table does not sort the records"
+        );
+
+        let plan_path = Path::new(&base_path)
+            .join(".hoodie/timeline")
+            .join(format!("{}.clustering.requested", clustering[0].timestamp));
+        let mut metadata =
+            HoodieRequestedReplaceMetadata::from_avro_bytes(&std::fs::read(&plan_path).unwrap())
+                .unwrap();
+        assert_eq!(metadata.operation_type.as_deref(), Some("CLUSTER"));
+        let clustering_plan = metadata.clustering_plan.as_ref().unwrap();
+        assert!(clustering_plan.iter_file_group_ids().next().is_some());
+        // The clustering is completed, so its file groups are not pending.
+        assert!(
+            timeline
+                .get_file_groups_in_pending_compaction_and_clustering()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        metadata
+            .clustering_plan
+            .get_or_insert_default()
+            .strategy
+            .get_or_insert_default()
+            .strategy_params
+            .get_or_insert_default()
+            .insert(
+                crate::metadata::replace_commit::CLUSTERING_SORT_COLUMNS_KEY.to_string(),
+                "txn_ts,txn_id".to_string(),
+            );
+        std::fs::write(&plan_path, metadata.to_avro_bytes().unwrap()).unwrap();
+
+        let sort_columns = timeline.get_clustering_sort_columns().await.unwrap();
+        assert_eq!(
+            sort_columns,
+            HashMap::from([(
+                clustering[0].timestamp.clone(),
+                vec!["txn_ts".to_string(), "txn_id".to_string()]
+            )])
+        );
+    }
 }
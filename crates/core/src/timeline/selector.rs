@@ -198,6 +198,28 @@ impl TimelineSelector {
         })
     }
 
+    /// Select instants of the given actions in any of the given states, without time filter.
+    ///
+    /// An empty `states` selects instants in all states.
+    pub fn actions_in_states(
+        actions: &[Action],
+        states: &[State],
+        hudi_configs: Arc<HudiConfigs>,
+    ) -> Result<Self> {
+        let timezone = Self::get_timezone_from_configs(&hudi_configs);
+        let timeline_layout_version =
+            Self::get_timeline_layout_version_from_configs(&hudi_configs)?;
+        Ok(Self {
+            timezone,
+            start_datetime: None,
+            end_datetime: None,
+            states: states.to_vec(),
+            actions: actions.to_vec(),
+            include_archived: false,
+            timeline_layout_version,
+        })
+    }
+
     pub fn completed_commits_in_range(
         hudi_configs: Arc<HudiConfigs>,
         start: Option<&str>,
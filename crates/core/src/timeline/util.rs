@@ -127,6 +127,14 @@ fn parse_rfc3339_format(ts_str: &str, timezone: &TimelineTimezoneValue) -> Resul
     ))
 }
 
+/// Format a UTC datetime as a Hudi timeline timestamp `yyyyMMddHHmmssSSS` in the given timezone.
+pub fn format_datetime_in_timezone(dt: &DateTime<Utc>, timezone: &str) -> Result<String> {
+    match TimelineTimezoneValue::from_str(timezone)? {
+        TimelineTimezoneValue::UTC => Ok(datetime_to_timeline_format(dt)),
+        TimelineTimezoneValue::Local => Ok(datetime_to_timeline_format(&dt.with_timezone(&Local))),
+    }
+}
+
 fn datetime_to_timeline_format(dt: &DateTime<impl TimeZone>) -> String {
     let year = dt.year();
     let month = dt.month();
@@ -259,6 +267,29 @@ mod tests {
         assert_eq!(result, "20240105090807000");
     }
 
+    #[test]
+    #[serial(env_vars)]
+    fn test_format_datetime_in_timezone() {
+        let dt = Utc
+            .with_ymd_and_hms(2024, 3, 15, 14, 25, 30)
+            .unwrap()
+            .with_nanosecond(123_000_000)
+            .unwrap();
+        assert_eq!(
+            format_datetime_in_timezone(&dt, "UTC").unwrap(),
+            "20240315142530123"
+        );
+
+        set_singapore_timezone();
+        assert_eq!(
+            format_datetime_in_timezone(&dt, "Local").unwrap(),
+            "20240315222530123"
+        );
+        reset_timezone();
+
+        assert!(format_datetime_in_timezone(&dt, "InvalidTimezone").is_err());
+    }
+
     #[test]
     fn test_format_timestamp_timeline_format() {
         // Already in timeline format should be returned as-is
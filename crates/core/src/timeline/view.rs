@@ -64,6 +64,12 @@ pub struct TimelineView {
 
     /// Whether this table uses timeline layout v2 (completion time tracking).
     is_timeline_layout_v2: bool,
+
+    /// Request timestamps before this are considered archived and completed.
+    ///
+    /// Archived instants are not in the completion time map, so their completion
+    /// timestamps fall back to the request timestamps.
+    archived_before: Option<String>,
 }
 
 impl TimelineView {
@@ -104,9 +110,18 @@ impl TimelineView {
             excluding_file_groups,
             request_to_completion,
             is_timeline_layout_v2,
+            archived_before: None,
         }
     }
 
+    /// Consider the instants requested before the given timestamp as archived and completed.
+    ///
+    /// This is usually the start of the active timeline, see [crate::timeline::Timeline].
+    pub fn with_archived_before(mut self, timestamp: Option<String>) -> Self {
+        self.archived_before = timestamp;
+        self
+    }
+
     /// Build the completion time map from instants.
     fn build_completion_time_map<'a, I>(instants: I) -> HashMap<String, String>
     where
@@ -137,10 +152,16 @@ impl TimelineView {
 }
 
 impl CompletionTimeView for TimelineView {
-    fn get_completion_time(&self, request_timestamp: &str) -> Option<&str> {
+    fn get_completion_time<'a>(&'a self, request_timestamp: &'a str) -> Option<&'a str> {
         self.request_to_completion
             .get(request_timestamp)
             .map(|s| s.as_str())
+            .or_else(|| {
+                self.archived_before
+                    .as_deref()
+                    .filter(|start| self.is_timeline_layout_v2 && request_timestamp < *start)
+                    .map(|_| request_timestamp)
+            })
     }
 
     fn should_filter_uncommitted(&self) -> bool {
@@ -249,6 +270,41 @@ mod tests {
         assert!(view.get_completion_time("unknown").is_none());
     }
 
+    #[test]
+    fn test_completion_time_lookup_for_archived_instants() {
+        let instants = vec![create_instant(
+            "20240101130000000",
+            Some("20240101130010000"),
+        )];
+
+        let view = TimelineView::new(
+            "20240101130000000".to_string(),
+            None,
+            &instants,
+            HashSet::new(),
+            &create_layout_v2_configs(),
+        )
+        .with_archived_before(Some("20240101125000000".to_string()));
+
+        // Archived instants fall back to the request timestamps
+        assert_eq!(
+            view.get_completion_time("20240101120000000"),
+            Some("20240101120000000")
+        );
+        // Pending instants on the active timeline have no completion time
+        assert!(view.get_completion_time("20240101125500000").is_none());
+
+        let view = TimelineView::new(
+            "20240101130000000".to_string(),
+            None,
+            &instants,
+            HashSet::new(),
+            &create_layout_v1_configs(),
+        )
+        .with_archived_before(Some("20240101125000000".to_string()));
+        assert!(view.get_completion_time("20240101120000000").is_none());
+    }
+
     #[test]
     fn test_should_filter_uncommitted_layout_v2() {
         let instants = vec![create_instant(
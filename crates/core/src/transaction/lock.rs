@@ -0,0 +1,383 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Lock providers guarding the commits of concurrent writers.
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use lazy_static::lazy_static;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::Result;
+use crate::error::CoreError;
+use crate::storage::Storage;
+
+/// Interval between the checks of a lock held by others.
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Format of the lock creation time in [FileSystemLockProvider]'s lock file, as Hudi Java writes.
+const LOCK_CREATE_TIME_FORMAT: &str = "%Y%m%d%H%M%S%3f";
+
+lazy_static! {
+    static ref IN_PROCESS_LOCKS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// A lock shared by the writers of a table.
+///
+/// A lock provider instance is used by a single writer; it is not reentrant.
+#[async_trait]
+pub trait LockProvider: Debug + Send + Sync {
+    /// Try to acquire the lock, waiting for at most the given timeout.
+    ///
+    /// Returns `false` if the lock is still held by another writer after the timeout.
+    async fn try_lock(&self, timeout: Duration) -> Result<bool>;
+
+    /// Release the lock if it is held by this provider.
+    async fn unlock(&self) -> Result<()>;
+}
+
+/// Poll `try_once` until it succeeds or the timeout elapses.
+async fn poll_until_locked<F, Fut>(timeout: Duration, mut try_once: F) -> Result<bool>
+where
+    F: FnMut() -> Fut + Send,
+    Fut: Future<Output = Result<bool>> + Send,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if try_once().await? {
+            return Ok(true);
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Ok(false);
+        }
+        tokio::time::sleep(LOCK_POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
+
+/// A lock held by creating a lock file under `.hoodie/.aux` of the table, compatible with
+/// `FileSystemBasedLockProvider` of Hudi Java.
+///
+/// The lock file is created only if it does not exist, which relies on the storage supporting
+/// conditional writes. Each provider writes a unique owner token into the lock file, and only
+/// deletes a lock file with its own token on unlock, or with the token it found expired.
+#[derive(Debug)]
+pub struct FileSystemLockProvider {
+    storage: Arc<Storage>,
+    expire: Option<TimeDelta>,
+    owner: String,
+    held: AtomicBool,
+}
+
+/// The content of a lock file of [FileSystemLockProvider].
+struct LockFileInfo {
+    create_time: Option<NaiveDateTime>,
+    owner: Option<String>,
+}
+
+impl LockFileInfo {
+    fn parse(data: &[u8]) -> Self {
+        let info = serde_json::from_slice::<Value>(data).unwrap_or_default();
+        Self {
+            create_time: info["lockCreateTime"]
+                .as_str()
+                .and_then(|t| NaiveDateTime::parse_from_str(t, LOCK_CREATE_TIME_FORMAT).ok()),
+            owner: info["lockOwner"].as_str().map(str::to_string),
+        }
+    }
+}
+
+impl FileSystemLockProvider {
+    /// Relative path of the lock file to the table base path.
+    pub const LOCK_FILE_PATH: &'static str = ".hoodie/.aux/lock";
+
+    /// Create a lock provider for the table of the storage.
+    ///
+    /// A lock file older than `expire_minutes` is deleted when trying to acquire the lock;
+    /// `0` means the lock never expires.
+    pub fn new(storage: Arc<Storage>, expire_minutes: usize) -> Self {
+        let expire = (expire_minutes > 0).then(|| TimeDelta::minutes(expire_minutes as i64));
+        Self {
+            storage,
+            expire,
+            owner: Uuid::new_v4().to_string(),
+            held: AtomicBool::new(false),
+        }
+    }
+
+    /// Read the lock file, or `None` if there is no lock file.
+    async fn read_lock_file(&self) -> Option<LockFileInfo> {
+        let data = self
+            .storage
+            .get_file_data(Self::LOCK_FILE_PATH)
+            .await
+            .ok()?;
+        Some(LockFileInfo::parse(&data))
+    }
+
+    async fn try_create_lock_file(&self) -> Result<bool> {
+        let lock_info = json!({
+            "lockCreateTime": Utc::now().format(LOCK_CREATE_TIME_FORMAT).to_string(),
+            "lockThreadName": std::thread::current().name().unwrap_or_default(),
+            "lockStacksInfo": [],
+            "lockOwner": self.owner,
+        });
+        let created = self
+            .storage
+            .put_file_data_if_absent(Self::LOCK_FILE_PATH, Bytes::from(lock_info.to_string()))
+            .await?;
+        if !created {
+            self.delete_lock_file_if_expired().await?;
+        }
+        Ok(created)
+    }
+
+    async fn delete_lock_file_if_expired(&self) -> Result<()> {
+        let Some(expire) = self.expire else {
+            return Ok(());
+        };
+        let Some(lock_file) = self.read_lock_file().await else {
+            // The lock was released in the meantime.
+            return Ok(());
+        };
+        let expired = match lock_file.create_time {
+            Some(t) => Utc::now().naive_utc() - t > expire,
+            // A lock file that cannot be parsed is left by a crashed writer.
+            None => true,
+        };
+        if !expired {
+            return Ok(());
+        }
+        // Another writer may have replaced the expired lock file since it was read.
+        match self.read_lock_file().await {
+            Some(current) if current.owner == lock_file.owner => {
+                log::warn!("Deleting expired lock file {}", Self::LOCK_FILE_PATH);
+                Ok(self.storage.delete_file(Self::LOCK_FILE_PATH).await?)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl LockProvider for FileSystemLockProvider {
+    async fn try_lock(&self, timeout: Duration) -> Result<bool> {
+        if self.held.load(Ordering::SeqCst) {
+            return Err(CoreError::Lock(format!(
+                "Lock {} is already held by this writer",
+                Self::LOCK_FILE_PATH
+            )));
+        }
+        let locked = poll_until_locked(timeout, || self.try_create_lock_file()).await?;
+        self.held.store(locked, Ordering::SeqCst);
+        Ok(locked)
+    }
+
+    async fn unlock(&self) -> Result<()> {
+        if !self.held.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        match self.read_lock_file().await {
+            Some(lock_file) if lock_file.owner.as_deref() == Some(self.owner.as_str()) => {
+                Ok(self.storage.delete_file(Self::LOCK_FILE_PATH).await?)
+            }
+            _ => {
+                log::warn!(
+                    "Lock {} expired and was taken over by another writer",
+                    Self::LOCK_FILE_PATH
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An in-memory lock shared by the writers of the same table within the process,
+/// like `InProcessLockProvider` of Hudi Java.
+#[derive(Debug)]
+pub struct InProcessLockProvider {
+    lock_key: String,
+    held: AtomicBool,
+}
+
+impl InProcessLockProvider {
+    /// Create a lock provider for the table at the base path.
+    pub fn new(base_path: &str) -> Self {
+        Self {
+            lock_key: base_path.trim_end_matches('/').to_string(),
+            held: AtomicBool::new(false),
+        }
+    }
+
+    fn try_insert_lock_key(&self) -> Result<bool> {
+        let mut locks = IN_PROCESS_LOCKS
+            .lock()
+            .map_err(|e| CoreError::Lock(format!("In-process locks are poisoned: {e}")))?;
+        Ok(locks.insert(self.lock_key.clone()))
+    }
+}
+
+#[async_trait]
+impl LockProvider for InProcessLockProvider {
+    async fn try_lock(&self, timeout: Duration) -> Result<bool> {
+        if self.held.load(Ordering::SeqCst) {
+            return Err(CoreError::Lock(format!(
+                "In-process lock of {} is already held by this writer",
+                self.lock_key
+            )));
+        }
+        let locked = poll_until_locked(timeout, || async { self.try_insert_lock_key() }).await?;
+        self.held.store(locked, Ordering::SeqCst);
+        Ok(locked)
+    }
+
+    async fn unlock(&self) -> Result<()> {
+        if self.held.swap(false, Ordering::SeqCst) {
+            let mut locks = IN_PROCESS_LOCKS
+                .lock()
+                .map_err(|e| CoreError::Lock(format!("In-process locks are poisoned: {e}")))?;
+            locks.remove(&self.lock_key);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use url::Url;
+
+    const NO_WAIT: Duration = Duration::ZERO;
+
+    fn storage_for(path: &std::path::Path) -> Arc<Storage> {
+        Storage::new_with_base_url(Url::from_directory_path(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_file_system_lock_is_exclusive() {
+        let temp_dir = tempdir().unwrap();
+        let storage = storage_for(temp_dir.path());
+        let lock1 = FileSystemLockProvider::new(storage.clone(), 0);
+        let lock2 = FileSystemLockProvider::new(storage.clone(), 0);
+
+        assert!(lock1.try_lock(NO_WAIT).await.unwrap());
+        assert!(temp_dir.path().join(".hoodie/.aux/lock").is_file());
+        assert!(!lock2.try_lock(Duration::from_millis(120)).await.unwrap());
+        assert!(lock1.try_lock(NO_WAIT).await.is_err());
+
+        // unlocking without holding the lock keeps the lock file
+        lock2.unlock().await.unwrap();
+        assert!(temp_dir.path().join(".hoodie/.aux/lock").is_file());
+
+        lock1.unlock().await.unwrap();
+        assert!(!temp_dir.path().join(".hoodie/.aux/lock").exists());
+        assert!(lock2.try_lock(NO_WAIT).await.unwrap());
+        lock2.unlock().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_system_lock_waits_for_release() {
+        let temp_dir = tempdir().unwrap();
+        let storage = storage_for(temp_dir.path());
+        let lock1 = Arc::new(FileSystemLockProvider::new(storage.clone(), 0));
+        let lock2 = FileSystemLockProvider::new(storage.clone(), 0);
+
+        assert!(lock1.try_lock(NO_WAIT).await.unwrap());
+        let releasing = lock1.clone();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            releasing.unlock().await.unwrap();
+        });
+        assert!(lock2.try_lock(Duration::from_secs(5)).await.unwrap());
+        handle.await.unwrap();
+        lock2.unlock().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_system_lock_takes_over_expired_lock() {
+        let temp_dir = tempdir().unwrap();
+        let storage = storage_for(temp_dir.path());
+        let expired_time = (Utc::now() - TimeDelta::minutes(10)).format(LOCK_CREATE_TIME_FORMAT);
+        storage
+            .put_file_data(
+                FileSystemLockProvider::LOCK_FILE_PATH,
+                Bytes::from(json!({ "lockCreateTime": expired_time.to_string() }).to_string()),
+            )
+            .await
+            .unwrap();
+
+        let never_expires = FileSystemLockProvider::new(storage.clone(), 0);
+        assert!(!never_expires.try_lock(NO_WAIT).await.unwrap());
+        let lock = FileSystemLockProvider::new(storage.clone(), 5);
+        assert!(lock.try_lock(Duration::from_secs(1)).await.unwrap());
+        let lock_file = lock.read_lock_file().await.unwrap();
+        assert_eq!(lock_file.owner.as_deref(), Some(lock.owner.as_str()));
+
+        // The lock just taken over is not expired for others
+        let other = FileSystemLockProvider::new(storage.clone(), 5);
+        assert!(!other.try_lock(NO_WAIT).await.unwrap());
+        lock.unlock().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_system_unlock_keeps_lock_of_other_owner() {
+        let temp_dir = tempdir().unwrap();
+        let storage = storage_for(temp_dir.path());
+        let lock = FileSystemLockProvider::new(storage.clone(), 5);
+        assert!(lock.try_lock(NO_WAIT).await.unwrap());
+
+        // Another writer took over the lock after it expired
+        let now = Utc::now().format(LOCK_CREATE_TIME_FORMAT).to_string();
+        storage
+            .put_file_data(
+                FileSystemLockProvider::LOCK_FILE_PATH,
+                Bytes::from(json!({ "lockCreateTime": now, "lockOwner": "other" }).to_string()),
+            )
+            .await
+            .unwrap();
+        lock.unlock().await.unwrap();
+        let lock_file = lock.read_lock_file().await.unwrap();
+        assert_eq!(lock_file.owner.as_deref(), Some("other"));
+    }
+
+    #[tokio::test]
+    async fn test_in_process_lock_is_exclusive_per_table() {
+        let lock1 = InProcessLockProvider::new("file:///tmp/lock_test_table");
+        let lock2 = InProcessLockProvider::new("file:///tmp/lock_test_table/");
+        let other_table = InProcessLockProvider::new("file:///tmp/lock_test_other_table");
+
+        assert!(lock1.try_lock(NO_WAIT).await.unwrap());
+        assert!(!lock2.try_lock(Duration::from_millis(60)).await.unwrap());
+        assert!(other_table.try_lock(NO_WAIT).await.unwrap());
+
+        lock1.unlock().await.unwrap();
+        assert!(lock2.try_lock(NO_WAIT).await.unwrap());
+        lock2.unlock().await.unwrap();
+        other_table.unlock().await.unwrap();
+    }
+}
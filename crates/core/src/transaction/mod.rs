@@ -0,0 +1,608 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Committing writes with optimistic concurrency control.
+//!
+//! A writer begins a transaction from the [Timeline] it reads, i.e., its latest completed commit
+//! and the commits pending at that time, writes its files, and then commits its inflight instant
+//! through the [TransactionManager]. With [WriteConcurrencyModeValue::OptimisticConcurrencyControl],
+//! the commit is guarded by a [LockProvider], and aborted with [CoreError::WriteConflict] if a
+//! commit completed after the writer started wrote to any of the same file groups.
+
+pub mod lock;
+
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Result;
+use crate::config::HudiConfigs;
+use crate::config::lock::HudiLockConfig::{
+    FileSystemLockExpireMinutes, LockAcquireNumRetries, LockAcquireRetryWaitTimeMs,
+    LockAcquireWaitTimeoutMs, LockProvider as LockProviderConfig, WriteConcurrencyMode,
+};
+use crate::config::lock::{LockProviderValue, WriteConcurrencyModeValue};
+use crate::config::table::HudiTableConfig::BasePath;
+use crate::error::CoreError;
+use crate::metadata::commit::HoodieCommitMetadata;
+use crate::storage::Storage;
+use crate::table::Table;
+use crate::timeline::instant::{Instant, State};
+use crate::timeline::{DEFAULT_LOADING_ACTIONS, Timeline};
+use crate::transaction::lock::{FileSystemLockProvider, InProcessLockProvider, LockProvider};
+
+/// The state of the [Timeline] a writer started from, against which its commit is checked for
+/// conflicts.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionStart {
+    /// The latest completed commit, or `None` for a table without commits.
+    pub instant: Option<Instant>,
+    /// The timestamps of the commits that were requested or inflight, and may complete after the
+    /// writer started.
+    pub pending_timestamps: BTreeSet<String>,
+}
+
+impl TransactionStart {
+    /// The timestamp of the latest completed commit, if any.
+    pub fn timestamp(&self) -> Option<&str> {
+        self.instant.as_ref().map(|i| i.timestamp.as_str())
+    }
+}
+
+/// Commits the inflight instants of a writer, resolving conflicts with concurrent writers
+/// according to [WriteConcurrencyMode].
+#[derive(Clone, Debug)]
+pub struct TransactionManager {
+    hudi_configs: Arc<HudiConfigs>,
+    storage_options: Arc<HashMap<String, String>>,
+    concurrency_mode: WriteConcurrencyModeValue,
+    lock_provider: Arc<dyn LockProvider>,
+}
+
+impl TransactionManager {
+    /// Create a transaction manager with the lock provider configured by [LockProviderConfig].
+    pub fn new(
+        hudi_configs: Arc<HudiConfigs>,
+        storage_options: Arc<HashMap<String, String>>,
+    ) -> Result<Self> {
+        let concurrency_mode: String = hudi_configs.get_or_default(WriteConcurrencyMode).into();
+        let concurrency_mode = WriteConcurrencyModeValue::from_str(&concurrency_mode)?;
+        let lock_provider: String = hudi_configs.get_or_default(LockProviderConfig).into();
+        let lock_provider: Arc<dyn LockProvider> =
+            match LockProviderValue::from_str(&lock_provider)? {
+                LockProviderValue::FileSystem => {
+                    let storage = Storage::new(storage_options.clone(), hudi_configs.clone())?;
+                    let expire_minutes: usize = hudi_configs
+                        .get_or_default(FileSystemLockExpireMinutes)
+                        .into();
+                    Arc::new(FileSystemLockProvider::new(storage, expire_minutes))
+                }
+                LockProviderValue::InProcess => {
+                    let base_path: String = hudi_configs.get(BasePath)?.into();
+                    Arc::new(InProcessLockProvider::new(&base_path))
+                }
+            };
+        Ok(Self {
+            hudi_configs,
+            storage_options,
+            concurrency_mode,
+            lock_provider,
+        })
+    }
+
+    /// Create a transaction manager for the writers of the table.
+    pub fn from_table(table: &Table) -> Result<Self> {
+        Self::new(table.hudi_configs.clone(), table.storage_options.clone())
+    }
+
+    /// Replace the lock provider configured by [LockProviderConfig], e.g., with an implementation
+    /// backed by an external lock service.
+    ///
+    /// The lock provider is only used with [WriteConcurrencyModeValue::OptimisticConcurrencyControl].
+    pub fn with_lock_provider(mut self, lock_provider: Arc<dyn LockProvider>) -> Self {
+        self.lock_provider = lock_provider;
+        self
+    }
+
+    /// Begin a transaction from the timeline, before the writer requests its instant.
+    ///
+    /// The pending commits are only listed with
+    /// [WriteConcurrencyModeValue::OptimisticConcurrencyControl], as they are not checked otherwise.
+    pub async fn begin(&self, timeline: &Timeline) -> Result<TransactionStart> {
+        let instant = timeline.completed_commits.last().cloned();
+        if self.concurrency_mode == WriteConcurrencyModeValue::SingleWriter {
+            return Ok(TransactionStart {
+                instant,
+                pending_timestamps: BTreeSet::new(),
+            });
+        }
+        // The active timeline lists the instants of all the states an instant went through.
+        let (completed, pending): (Vec<_>, Vec<_>) = timeline
+            .get_active_instants(false)
+            .await?
+            .into_iter()
+            .filter(|i| DEFAULT_LOADING_ACTIONS.contains(&i.action))
+            .partition(|i| i.state == State::Completed);
+        let completed: BTreeSet<_> = completed.into_iter().map(|i| i.timestamp).collect();
+        let pending_timestamps = pending
+            .into_iter()
+            .map(|i| i.timestamp)
+            .filter(|t| !completed.contains(t))
+            .collect();
+        Ok(TransactionStart {
+            instant,
+            pending_timestamps,
+        })
+    }
+
+    /// Complete the inflight instant with the commit metadata.
+    ///
+    /// `start` is the [TransactionStart] from [Self::begin] before the inflight instant was
+    /// requested.
+    ///
+    /// # Errors
+    ///
+    /// With [WriteConcurrencyModeValue::OptimisticConcurrencyControl], returns
+    /// [CoreError::Lock] if the lock cannot be acquired after the configured retries, or
+    /// [CoreError::WriteConflict] if a commit completed after the writer started wrote to the same
+    /// file groups.
+    /// The inflight instant is left as is in both cases.
+    pub async fn commit(
+        &self,
+        start: &TransactionStart,
+        inflight: &Instant,
+        metadata: &HoodieCommitMetadata,
+    ) -> Result<Instant> {
+        self.commit_with(start, inflight, metadata, async { Ok(()) })
+            .await
+    }
+
+    /// Same as [Self::commit], and runs `before_complete`, e.g., the update of the metadata
+    /// table, after the conflicts are resolved and before the inflight instant is completed,
+    /// while holding the lock.
+    ///
+    /// `before_complete` is not run if the commit is aborted.
+    pub async fn commit_with<F>(
+        &self,
+        start: &TransactionStart,
+        inflight: &Instant,
+        metadata: &HoodieCommitMetadata,
+        before_complete: F,
+    ) -> Result<Instant>
+    where
+        F: Future<Output = Result<()>>,
+    {
+        if self.concurrency_mode == WriteConcurrencyModeValue::SingleWriter {
+            before_complete.await?;
+            let timeline = self.load_timeline().await?;
+            return complete(&timeline, inflight, metadata).await;
+        }
+
+        self.acquire_lock().await?;
+        let result = async {
+            let timeline = self.load_timeline().await?;
+            resolve_conflicts(&timeline, start, inflight, metadata).await?;
+            before_complete.await?;
+            complete(&timeline, inflight, metadata).await
+        }
+        .await;
+        let unlock_result = self.lock_provider.unlock().await;
+        let completed = result?;
+        unlock_result?;
+        Ok(completed)
+    }
+
+    async fn load_timeline(&self) -> Result<Timeline> {
+        Timeline::new_from_storage(self.hudi_configs.clone(), self.storage_options.clone()).await
+    }
+
+    async fn acquire_lock(&self) -> Result<()> {
+        let wait_timeout_ms: usize = self
+            .hudi_configs
+            .get_or_default(LockAcquireWaitTimeoutMs)
+            .into();
+        let num_retries: usize = self
+            .hudi_configs
+            .get_or_default(LockAcquireNumRetries)
+            .into();
+        let retry_wait_ms: usize = self
+            .hudi_configs
+            .get_or_default(LockAcquireRetryWaitTimeMs)
+            .into();
+        let wait_timeout = Duration::from_millis(wait_timeout_ms as u64);
+        for attempt in 0..=num_retries {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_millis(retry_wait_ms as u64)).await;
+            }
+            if self.lock_provider.try_lock(wait_timeout).await? {
+                return Ok(());
+            }
+            log::debug!(
+                "Lock not acquired at attempt {} of {}",
+                attempt + 1,
+                num_retries + 1
+            );
+        }
+        Err(CoreError::Lock(format!(
+            "Failed to acquire the lock after {} attempts",
+            num_retries + 1
+        )))
+    }
+}
+
+async fn complete(
+    timeline: &Timeline,
+    inflight: &Instant,
+    metadata: &HoodieCommitMetadata,
+) -> Result<Instant> {
+    let data = timeline.serialize_commit_metadata(&inflight.action, metadata)?;
+    timeline
+        .transition_inflight_to_completed(inflight, data)
+        .await
+}
+
+/// The completed commits that the writer of the inflight instant did not see when it started
+/// from `start`, i.e., the ones requested after its latest completed commit, pending when it
+/// started, or completed after the inflight instant was requested.
+///
+/// The completion timestamps are only available from table version 8; for older versions, the
+/// commits completed after the writer started are known from the pending ones.
+fn candidate_instants<'a>(
+    timeline: &'a Timeline,
+    start: &'a TransactionStart,
+    inflight: &'a Instant,
+) -> impl Iterator<Item = &'a Instant> {
+    timeline.completed_commits.iter().filter(move |instant| {
+        if instant.timestamp == inflight.timestamp {
+            return false;
+        }
+        let requested_after_start = start
+            .timestamp()
+            .is_none_or(|t| instant.timestamp.as_str() > t);
+        let pending_at_start = start.pending_timestamps.contains(&instant.timestamp);
+        let completed_after_requested = instant
+            .completion_timestamp
+            .as_ref()
+            .is_some_and(|t| *t > inflight.timestamp);
+        requested_after_start || pending_at_start || completed_after_requested
+    })
+}
+
+/// Check the file groups written by the commit against those written by the candidate instants.
+async fn resolve_conflicts(
+    timeline: &Timeline,
+    start: &TransactionStart,
+    inflight: &Instant,
+    metadata: &HoodieCommitMetadata,
+) -> Result<()> {
+    let file_groups = written_file_groups(metadata);
+    if file_groups.is_empty() {
+        return Ok(());
+    }
+    for candidate in candidate_instants(timeline, start, inflight) {
+        let candidate_metadata = timeline.get_instant_metadata(candidate).await?;
+        let candidate_metadata = HoodieCommitMetadata::from_json_map(&candidate_metadata)?;
+        let conflicts: Vec<_> = written_file_groups(&candidate_metadata)
+            .intersection(&file_groups)
+            .map(|(partition, file_id)| format!("{partition}/{file_id}"))
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(CoreError::WriteConflict(format!(
+                "Instant {} conflicts with the completed instant {} on file groups [{}]",
+                inflight.timestamp,
+                candidate.timestamp,
+                conflicts.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The file groups, as partition paths and file ids, written or replaced by the commit.
+fn written_file_groups(metadata: &HoodieCommitMetadata) -> BTreeSet<(String, String)> {
+    let written = metadata.iter_write_stats().filter_map(|(partition, stat)| {
+        stat.file_id
+            .as_ref()
+            .map(|file_id| (partition.clone(), file_id.clone()))
+    });
+    let replaced = metadata
+        .iter_replace_file_ids()
+        .map(|(partition, file_id)| (partition.clone(), file_id.clone()));
+    written.chain(replaced).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::lock::HudiLockConfig;
+    use crate::metadata::commit::HoodieWriteStat;
+    use crate::table::creator::TableCreator;
+    use crate::timeline::instant::Action;
+    use arrow_schema::{DataType, Field, Schema};
+    use bytes::Bytes;
+    use tempfile::{TempDir, tempdir};
+
+    async fn create_table(
+        table_version: isize,
+        options: &[(HudiLockConfig, &str)],
+    ) -> (TempDir, Table) {
+        let temp_dir = tempdir().unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("city", DataType::Utf8, true),
+        ]));
+        let table = TableCreator::new(temp_dir.path().to_str().unwrap(), "trips", schema)
+            .with_record_key_fields(["id"])
+            .with_partition_fields(["city"])
+            .with_table_version(table_version)
+            .with_hudi_options(options.iter().map(|(k, v)| (k.as_ref(), *v)))
+            .create()
+            .await
+            .unwrap();
+        (temp_dir, table)
+    }
+
+    fn occ_options() -> Vec<(HudiLockConfig, &'static str)> {
+        vec![
+            (WriteConcurrencyMode, "OPTIMISTIC_CONCURRENCY_CONTROL"),
+            (LockAcquireWaitTimeoutMs, "100"),
+            (LockAcquireNumRetries, "1"),
+            (LockAcquireRetryWaitTimeMs, "10"),
+        ]
+    }
+
+    /// Start a writer on the table's timeline with an inflight commit.
+    async fn start_writer(table: &Table, after: Option<&str>) -> Instant {
+        let timeline = &table.timeline;
+        let timestamp = timeline.new_instant_timestamp(after).unwrap();
+        let requested = timeline
+            .create_requested_instant(Action::Commit, &timestamp, Bytes::new())
+            .await
+            .unwrap();
+        timeline
+            .transition_requested_to_inflight(&requested, Bytes::new())
+            .await
+            .unwrap()
+    }
+
+    fn commit_metadata(partition: &str, file_ids: &[&str]) -> HoodieCommitMetadata {
+        let stats = file_ids
+            .iter()
+            .map(|file_id| HoodieWriteStat {
+                file_id: Some(file_id.to_string()),
+                path: Some(format!("{partition}/{file_id}_0-0-0_0.parquet")),
+                partition_path: Some(partition.to_string()),
+                ..Default::default()
+            })
+            .collect();
+        HoodieCommitMetadata {
+            version: Some(1),
+            operation_type: Some("UPSERT".to_string()),
+            partition_to_write_stats: Some(HashMap::from([(partition.to_string(), stats)])),
+            ..Default::default()
+        }
+    }
+
+    async fn assert_concurrent_commits(table_version: isize) {
+        let (_temp_dir, table) = create_table(table_version, &occ_options()).await;
+        let manager = TransactionManager::from_table(&table).unwrap();
+
+        let inflight1 = start_writer(&table, None).await;
+        let inflight2 = start_writer(&table, Some(&inflight1.timestamp)).await;
+        let inflight3 = start_writer(&table, Some(&inflight2.timestamp)).await;
+
+        let completed1 = manager
+            .commit(
+                &TransactionStart::default(),
+                &inflight1,
+                &commit_metadata("sf", &["fg1", "fg2"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(completed1.state, State::Completed);
+
+        let err = manager
+            .commit(
+                &TransactionStart::default(),
+                &inflight2,
+                &commit_metadata("sf", &["fg2"]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::WriteConflict(_)), "{err}");
+        assert!(err.to_string().contains("sf/fg2"), "{err}");
+
+        // Same file id in another partition is another file group
+        manager
+            .commit(
+                &TransactionStart::default(),
+                &inflight3,
+                &commit_metadata("la", &["fg2"]),
+            )
+            .await
+            .unwrap();
+
+        // A writer started after the completed commits does not conflict with them
+        let table = Table::new(table.base_url().as_str()).await.unwrap();
+        let start = manager.begin(&table.timeline).await.unwrap();
+        let inflight4 = start_writer(&table, Some(&inflight3.timestamp)).await;
+        manager
+            .commit(&start, &inflight4, &commit_metadata("sf", &["fg2"]))
+            .await
+            .unwrap();
+
+        let table = Table::new(table.base_url().as_str()).await.unwrap();
+        let completed: Vec<_> = table
+            .timeline
+            .completed_commits
+            .iter()
+            .map(|i| i.timestamp.as_str())
+            .collect();
+        assert_eq!(
+            completed,
+            [
+                inflight1.timestamp.as_str(),
+                inflight3.timestamp.as_str(),
+                inflight4.timestamp.as_str()
+            ]
+        );
+        assert!(
+            !table
+                .base_url()
+                .to_file_path()
+                .unwrap()
+                .join(FileSystemLockProvider::LOCK_FILE_PATH)
+                .exists()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commits_v9() {
+        assert_concurrent_commits(9).await;
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commits_v6() {
+        assert_concurrent_commits(6).await;
+    }
+
+    /// A commit requested before the latest completed commit that a writer started from, and
+    /// completed after the writer started, conflicts with the writer.
+    async fn assert_commit_pending_at_start(table_version: isize) {
+        let (_temp_dir, table) = create_table(table_version, &occ_options()).await;
+        let manager = TransactionManager::from_table(&table).unwrap();
+
+        let inflight1 = start_writer(&table, None).await;
+        let inflight2 = start_writer(&table, Some(&inflight1.timestamp)).await;
+        let default_start = TransactionStart::default();
+        manager
+            .commit(&default_start, &inflight2, &commit_metadata("sf", &["fg2"]))
+            .await
+            .unwrap();
+
+        let table = Table::new(table.base_url().as_str()).await.unwrap();
+        let start = manager.begin(&table.timeline).await.unwrap();
+        assert_eq!(start.timestamp(), Some(inflight2.timestamp.as_str()));
+        assert_eq!(
+            start.pending_timestamps,
+            BTreeSet::from([inflight1.timestamp.clone()])
+        );
+        let inflight3 = start_writer(&table, Some(&inflight2.timestamp)).await;
+
+        manager
+            .commit(&default_start, &inflight1, &commit_metadata("sf", &["fg1"]))
+            .await
+            .unwrap();
+        let err = manager
+            .commit(&start, &inflight3, &commit_metadata("sf", &["fg1"]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::WriteConflict(_)), "{err}");
+        assert!(err.to_string().contains(&inflight1.timestamp), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_commit_pending_at_start_conflicts_v9() {
+        assert_commit_pending_at_start(9).await;
+    }
+
+    #[tokio::test]
+    async fn test_commit_pending_at_start_conflicts_v6() {
+        assert_commit_pending_at_start(6).await;
+    }
+
+    #[tokio::test]
+    async fn test_single_writer_commits_without_conflict_resolution() {
+        let (_temp_dir, table) = create_table(9, &[]).await;
+        let manager = TransactionManager::from_table(&table).unwrap();
+        let inflight1 = start_writer(&table, None).await;
+        let inflight2 = start_writer(&table, Some(&inflight1.timestamp)).await;
+
+        // The lock held by others is not needed in single writer mode
+        let storage =
+            Storage::new(table.storage_options.clone(), table.hudi_configs.clone()).unwrap();
+        let other = FileSystemLockProvider::new(storage, 0);
+        assert!(other.try_lock(Duration::ZERO).await.unwrap());
+
+        for inflight in [&inflight1, &inflight2] {
+            manager
+                .commit(
+                    &TransactionStart::default(),
+                    inflight,
+                    &commit_metadata("sf", &["fg1"]),
+                )
+                .await
+                .unwrap();
+        }
+        other.unlock().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_commit_fails_when_lock_is_held() {
+        let (_temp_dir, table) = create_table(9, &occ_options()).await;
+        let manager = TransactionManager::from_table(&table).unwrap();
+        let inflight = start_writer(&table, None).await;
+
+        let storage =
+            Storage::new(table.storage_options.clone(), table.hudi_configs.clone()).unwrap();
+        let other = FileSystemLockProvider::new(storage, 0);
+        assert!(other.try_lock(Duration::ZERO).await.unwrap());
+
+        let metadata = commit_metadata("sf", &["fg1"]);
+        let err = manager
+            .commit(&TransactionStart::default(), &inflight, &metadata)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::Lock(_)), "{err}");
+
+        other.unlock().await.unwrap();
+        manager
+            .commit(&TransactionStart::default(), &inflight, &metadata)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_commit_with_in_process_lock_provider() {
+        let mut options = occ_options();
+        options.push((
+            HudiLockConfig::LockProvider,
+            "org.apache.hudi.client.transaction.lock.InProcessLockProvider",
+        ));
+        let (_temp_dir, table) = create_table(8, &options).await;
+        let manager = TransactionManager::from_table(&table).unwrap();
+        let base_path: String = table.hudi_configs.get(BasePath).unwrap().into();
+
+        let other = InProcessLockProvider::new(&base_path);
+        assert!(other.try_lock(Duration::ZERO).await.unwrap());
+        let inflight = start_writer(&table, None).await;
+        let metadata = commit_metadata("sf", &["fg1"]);
+        let err = manager
+            .commit(&TransactionStart::default(), &inflight, &metadata)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::Lock(_)), "{err}");
+
+        other.unlock().await.unwrap();
+        manager
+            .commit(&TransactionStart::default(), &inflight, &metadata)
+            .await
+            .unwrap();
+    }
+}
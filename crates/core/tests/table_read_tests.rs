@@ -653,6 +653,87 @@ mod v8_tables {
             Ok(())
         }
 
+        #[tokio::test]
+        async fn test_simple_keygen_hivestyle_no_metafields_virtual_meta_fields() -> Result<()> {
+            let base_url = SampleTable::V8SimplekeygenHivestyleNoMetafields.url_to_cow();
+            let hudi_table = Table::new(base_url.path()).await?;
+            let schema = hudi_table.get_schema_with_meta_fields().await?;
+
+            let options = ReadOptions::new().with_projection([
+                "_hoodie_commit_time",
+                "_hoodie_record_key",
+                "_hoodie_partition_path",
+                "_hoodie_file_name",
+            ]);
+            let records = hudi_table.read(&options).await?;
+            let records = concat_batches(&records[0].schema(), &records)?;
+            assert_eq!(
+                records.schema().fields()[..],
+                schema.project(&[0, 2, 3, 4])?.fields()[..]
+            );
+            assert_eq!(records.column(0).null_count(), records.num_rows());
+            let mut meta_values: Vec<(String, String, String)> = (0..records.num_rows())
+                .map(|i| {
+                    let value = |col: usize| {
+                        records
+                            .column(col)
+                            .as_any()
+                            .downcast_ref::<StringArray>()
+                            .unwrap()
+                            .value(i)
+                            .to_string()
+                    };
+                    (value(1), value(2), value(3))
+                })
+                .collect();
+            meta_values.sort_unstable();
+            let keys_and_partitions: Vec<(&str, &str)> = meta_values
+                .iter()
+                .map(|(key, partition, _)| (key.as_str(), partition.as_str()))
+                .collect();
+            assert_eq!(
+                keys_and_partitions,
+                vec![
+                    ("1", "byteField=10"),
+                    ("2", "byteField=20"),
+                    ("3", "byteField=10"),
+                    ("4", "byteField=30"),
+                ]
+            );
+            let file_slices = hudi_table.get_file_slices(&ReadOptions::new()).await?;
+            for (_, partition, file_name) in &meta_values {
+                assert!(file_slices.iter().any(|slice| {
+                    &slice.partition_path == partition
+                        && slice
+                            .base_file_relative_path()
+                            .unwrap()
+                            .ends_with(file_name.as_str())
+                }));
+            }
+
+            // Filters on the virtual meta fields, for both the eager and streaming reads
+            let options = ReadOptions::new()
+                .with_filters([("_hoodie_record_key", "=", "2")])?
+                .with_projection(["id", "_hoodie_partition_path"]);
+            let records = hudi_table.read(&options).await?;
+            let stream = hudi_table.read_stream(&options).await?;
+            let streamed = collect_stream_batches(stream).await?;
+            for records in [records, streamed] {
+                let records = concat_batches(&records[0].schema(), &records)?;
+                assert_eq!(records.num_rows(), 1);
+                assert_eq!(
+                    records
+                        .column(1)
+                        .as_any()
+                        .downcast_ref::<StringArray>()
+                        .unwrap()
+                        .value(0),
+                    "byteField=20"
+                );
+            }
+            Ok(())
+        }
+
         #[tokio::test]
         async fn test_simple_keygen_hivestyle_no_metafields() -> Result<()> {
             let base_url = SampleTable::V8SimplekeygenHivestyleNoMetafields.url_to_cow();
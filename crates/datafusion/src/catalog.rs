@@ -0,0 +1,144 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! A catalog of the Hudi tables discovered under a root URI, so that they
+//! can be queried as `catalog.database.table` without registering each one.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::catalog::{CatalogProvider, SchemaProvider};
+use datafusion::datasource::TableProvider;
+use datafusion_common::Result;
+use datafusion_expr::TableType;
+use log::warn;
+
+use crate::{HudiDataSource, external_error};
+use hudi_core::table::discovery::discover_tables;
+
+/// A [CatalogProvider] of the Hudi tables under a root URI, with a
+/// [HudiSchemaProvider] for each `hoodie.database.name` of the tables.
+///
+/// The tables are discovered once by [HudiCatalogProvider::try_new]; create
+/// the provider again to pick up the tables created afterwards.
+#[derive(Debug)]
+pub struct HudiCatalogProvider {
+    schemas: BTreeMap<String, Arc<HudiSchemaProvider>>,
+}
+
+impl HudiCatalogProvider {
+    /// Discover the Hudi tables under `root_uri`, where the `options` are
+    /// used both for the discovery and for reading each of the tables.
+    pub async fn try_new<I, K, V>(root_uri: &str, options: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let options: HashMap<String, String> = options
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.into()))
+            .collect();
+        let tables = discover_tables(root_uri, options.clone())
+            .await
+            .map_err(|e| external_error("Failed to discover Hudi tables", e))?;
+
+        let mut base_uris: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        for table in tables {
+            let schema = base_uris.entry(table.database_name.clone()).or_default();
+            if let Some(base_uri) = schema.get(&table.table_name) {
+                warn!(
+                    "Skipping Hudi table {} at {}: already discovered at {base_uri}",
+                    table.table_name, table.base_uri
+                );
+                continue;
+            }
+            schema.insert(table.table_name, table.base_uri);
+        }
+        let options = Arc::new(options);
+        let schemas = base_uris
+            .into_iter()
+            .map(|(database_name, base_uris)| {
+                let schema = HudiSchemaProvider {
+                    base_uris,
+                    options: options.clone(),
+                };
+                (database_name, Arc::new(schema))
+            })
+            .collect();
+        Ok(Self { schemas })
+    }
+}
+
+impl CatalogProvider for HudiCatalogProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema_names(&self) -> Vec<String> {
+        self.schemas.keys().cloned().collect()
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        self.schemas
+            .get(name)
+            .map(|schema| schema.clone() as Arc<dyn SchemaProvider>)
+    }
+}
+
+/// A [SchemaProvider] of the Hudi tables of a database in a
+/// [HudiCatalogProvider], named by their `hoodie.table.name`.
+///
+/// A [HudiDataSource] is created on each lookup of a table, so that a query
+/// reads the latest snapshot of the table.
+#[derive(Debug)]
+pub struct HudiSchemaProvider {
+    /// Base URIs of the tables keyed by the table names.
+    base_uris: BTreeMap<String, String>,
+    options: Arc<HashMap<String, String>>,
+}
+
+#[async_trait]
+impl SchemaProvider for HudiSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        self.base_uris.keys().cloned().collect()
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        let Some(base_uri) = self.base_uris.get(name) else {
+            return Ok(None);
+        };
+        let data_source =
+            HudiDataSource::new_with_options(base_uri, self.options.as_ref().clone()).await?;
+        Ok(Some(Arc::new(data_source)))
+    }
+
+    async fn table_type(&self, name: &str) -> Result<Option<TableType>> {
+        Ok(self.base_uris.contains_key(name).then_some(TableType::Base))
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.base_uris.contains_key(name)
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Planning support for `DELETE` and `UPDATE` statements on Hudi tables.
+
+use std::sync::Arc;
+
+use datafusion::catalog::Session;
+use datafusion::datasource::source_as_provider;
+use datafusion::execution::SessionState;
+use datafusion::execution::context::SessionContext;
+use datafusion::optimizer::optimizer::ApplyOrder;
+use datafusion::optimizer::{OptimizerConfig, OptimizerRule};
+use datafusion_common::DataFusionError::Plan;
+use datafusion_common::Result;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_expr::dml::WriteOp;
+use datafusion_expr::logical_plan::{DmlStatement, Filter, LogicalPlan, TableScan};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+
+use crate::HudiDataSource;
+
+/// Name of the [`HudiDmlFilterRule`] optimizer rule.
+pub const DML_FILTER_RULE: &str = "hudi_dml_filter";
+
+/// Optimizer rule keeping the predicates of `DELETE` and `UPDATE` statements
+/// on Hudi tables visible to [`HudiDataSource::delete_from`] and
+/// [`HudiDataSource::update`].
+///
+/// [`HudiDataSource`] reports partition filters as `Exact`, so DataFusion
+/// moves them from the `Filter` into the `TableScan` of the target table.
+/// DataFusion only passes the predicates of `Filter` nodes to the DML
+/// methods, so this rule restores the filters of the target `TableScan` in
+/// a `Filter` above it; otherwise the statement would match rows of every
+/// partition.
+///
+/// The rule is registered by [`HudiDataSource::register`] and
+/// [`with_hudi`](crate::with_hudi).
+#[derive(Debug, Default)]
+pub struct HudiDmlFilterRule;
+
+impl HudiDmlFilterRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn is_hudi_delete_or_update(dml: &DmlStatement) -> bool {
+        matches!(dml.op, WriteOp::Delete | WriteOp::Update)
+            && source_as_provider(&dml.target)
+                .is_ok_and(|provider| provider.as_any().is::<HudiDataSource>())
+    }
+
+    /// Adds the filters of the target `TableScan` missing from the `Filter`
+    /// right above it, or a new `Filter` if there is none.
+    fn restore_scan_filters(dml: &DmlStatement) -> Result<Transformed<LogicalPlan>> {
+        let is_target_scan = |plan: &LogicalPlan| match plan {
+            LogicalPlan::TableScan(scan) => scan.table_name == dml.table_name,
+            _ => false,
+        };
+        dml.input.as_ref().clone().transform_down(|plan| {
+            let (predicates, scan) = match plan {
+                LogicalPlan::Filter(filter) if is_target_scan(&filter.input) => {
+                    let predicates: Vec<_> = split_conjunction(&filter.predicate)
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                    (predicates, filter.input)
+                }
+                LogicalPlan::TableScan(_) if is_target_scan(&plan) => (vec![], Arc::new(plan)),
+                _ => return Ok(Transformed::no(plan)),
+            };
+            let LogicalPlan::TableScan(TableScan { filters, .. }) = scan.as_ref() else {
+                unreachable!("the input of the filter is the target table scan");
+            };
+            let missing: Vec<_> = filters
+                .iter()
+                .filter(|f| !predicates.contains(f))
+                .cloned()
+                .collect();
+            let transformed = !missing.is_empty();
+            let plan = match conjunction(predicates.into_iter().chain(missing)) {
+                Some(predicate) => LogicalPlan::Filter(Filter::try_new(predicate, scan)?),
+                None => Arc::unwrap_or_clone(scan),
+            };
+            Ok(Transformed::new(plan, transformed, TreeNodeRecursion::Jump))
+        })
+    }
+}
+
+impl OptimizerRule for HudiDmlFilterRule {
+    fn name(&self) -> &str {
+        DML_FILTER_RULE
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Dml(dml) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+        if !Self::is_hudi_delete_or_update(&dml) {
+            return Ok(Transformed::no(LogicalPlan::Dml(dml)));
+        }
+        let input = Self::restore_scan_filters(&dml)?;
+        if !input.transformed {
+            return Ok(Transformed::no(LogicalPlan::Dml(dml)));
+        }
+        Ok(Transformed::yes(LogicalPlan::Dml(DmlStatement {
+            input: Arc::new(input.data),
+            ..dml
+        })))
+    }
+}
+
+/// Registers the [`HudiDmlFilterRule`] with the session if it is not yet.
+pub(crate) fn register_dml_filter_rule(ctx: &SessionContext) {
+    if !has_dml_filter_rule(&ctx.state()) {
+        ctx.add_optimizer_rule(Arc::new(HudiDmlFilterRule::new()));
+    }
+}
+
+/// Returns an error if the session would plan a `DELETE` or `UPDATE`
+/// statement without the [`HudiDmlFilterRule`], which may drop its
+/// partition filters.
+pub(crate) fn ensure_dml_filter_rule(state: &dyn Session, statement: &str) -> Result<()> {
+    match state.as_any().downcast_ref::<SessionState>() {
+        Some(state) if has_dml_filter_rule(state) => Ok(()),
+        _ => Err(Plan(format!(
+            "{statement} on a partitioned Hudi table requires the `{DML_FILTER_RULE}` \
+             optimizer rule; register the table with `HudiDataSource::register()` or \
+             build the session with `with_hudi()`"
+        ))),
+    }
+}
+
+fn has_dml_filter_rule(state: &SessionState) -> bool {
+    state
+        .optimizers()
+        .iter()
+        .any(|rule| rule.name() == DML_FILTER_RULE)
+}
@@ -20,30 +20,49 @@
 //! [`FileGroupReader`], supporting all base file formats and MOR log merging.
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::fmt;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use arrow_array::RecordBatch;
-use arrow_schema::SchemaRef;
+use arrow_schema::{Schema, SchemaRef};
+use datafusion::physical_expr::expressions::Column;
+use datafusion::physical_expr::{
+    EquivalenceProperties, PhysicalExpr, PhysicalSortExpr, conjunction,
+};
+use datafusion::physical_expr_common::physical_expr::snapshot_physical_expr;
 use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
-use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::filter_pushdown::{
+    ChildPushdownResult, FilterPushdownPhase, FilterPushdownPropagation, PushedDown,
+};
+use datafusion::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, PlanProperties,
-    SendableRecordBatchStream,
+    SendableRecordBatchStream, SortOrderPushdownResult,
 };
 use datafusion_common::DataFusionError::Execution;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::pruning::PruningStatistics;
 use datafusion_common::stats::Precision;
-use datafusion_common::{ColumnStatistics, DataFusionError, Result, Statistics};
+use datafusion_common::{ColumnStatistics, DataFusionError, Result, ScalarValue, Statistics};
 use futures::stream::{self, BoxStream, TryStreamExt};
 use futures::{Stream, StreamExt};
 
+use crate::util::expr::physical_expr_to_filter_expr;
+use crate::util::layout::sort_columns_to_lex_ordering;
+use crate::util::pruning::{FileSlicePruningStatistics, can_prune_file_slice};
+use crate::util::stats::to_column_statistics;
 use crate::{external_error, inexact_usize_from_u64};
+use hudi_core::expr::filter::FilterExpr;
 use hudi_core::file_group::file_slice::FileSlice;
 use hudi_core::file_group::reader::FileGroupReader;
 use hudi_core::table::ReadOptions;
+use hudi_core::table::partition::PartitionPruner;
 
 /// DataFusion execution plan that reads Hudi file slices through
 /// [`FileGroupReader`].
@@ -52,7 +71,7 @@ use hudi_core::table::ReadOptions;
 /// queries where base + log file merging is required. Parquet-only
 /// COW and MOR read-optimized queries continue to use DataFusion's
 /// native `ParquetSource` path for row-group/page-level pruning.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HudiScanExec {
     file_slice_partitions: Vec<Arc<Vec<FileSlice>>>,
     file_group_reader: Arc<FileGroupReader>,
@@ -62,6 +81,12 @@ pub struct HudiScanExec {
     projected_schema: SchemaRef,
     projection: Option<Vec<usize>>,
     limit: Option<usize>,
+    sort_columns: Vec<String>,
+    partition_columns: Vec<String>,
+    partition_pruner: Option<Arc<PartitionPruner>>,
+    /// Filters pushed down at execution time, e.g., the dynamic filters of
+    /// joins, evaluated on each file slice when it is opened.
+    runtime_filters: Vec<Arc<dyn PhysicalExpr>>,
     properties: PlanProperties,
     metrics: ExecutionPlanMetricsSet,
 }
@@ -95,14 +120,7 @@ impl HudiScanExec {
         } else {
             file_slice_partitions.into_iter().map(Arc::new).collect()
         };
-        let n_partitions = partitions.len();
-
-        let properties = PlanProperties::new(
-            datafusion::physical_expr::EquivalenceProperties::new(projected_schema.clone()),
-            Partitioning::UnknownPartitioning(n_partitions),
-            EmissionType::Incremental,
-            Boundedness::Bounded,
-        );
+        let properties = Self::compute_properties(&partitions, &projected_schema, &[], &[]);
 
         Self {
             file_slice_partitions: partitions,
@@ -113,11 +131,88 @@ impl HudiScanExec {
             projected_schema,
             projection,
             limit,
+            sort_columns: Vec::new(),
+            partition_columns: Vec::new(),
+            partition_pruner: None,
+            runtime_filters: Vec::new(),
             properties,
             metrics: ExecutionPlanMetricsSet::new(),
         }
     }
 
+    /// Declares that the records of every file slice are sorted by
+    /// `sort_columns`, e.g., by clustering.
+    ///
+    /// The output ordering holds only for the partitions that read at most
+    /// one file slice, as the file slices of a partition are read
+    /// concurrently; no ordering is declared otherwise.
+    pub fn with_sort_columns(mut self, sort_columns: Vec<String>) -> Self {
+        self.sort_columns = sort_columns;
+        self.properties = self.recompute_properties();
+        self
+    }
+
+    /// Declares that the file slices are grouped by partition into the
+    /// partitions of the scan, so the output is hash-partitioned by the
+    /// `partition_columns` when they are all projected.
+    pub fn with_partition_columns(mut self, partition_columns: Vec<String>) -> Self {
+        self.partition_columns = partition_columns;
+        self.properties = self.recompute_properties();
+        self
+    }
+
+    /// Sets the pruner of the table partitions, which parses the partition
+    /// values of the file slices to prune them by the runtime filters.
+    pub fn with_partition_pruner(mut self, partition_pruner: PartitionPruner) -> Self {
+        self.partition_pruner = Some(Arc::new(partition_pruner));
+        self
+    }
+
+    fn recompute_properties(&self) -> PlanProperties {
+        Self::compute_properties(
+            &self.file_slice_partitions,
+            &self.projected_schema,
+            &self.sort_columns,
+            &self.partition_columns,
+        )
+    }
+
+    fn compute_properties(
+        partitions: &[Arc<Vec<FileSlice>>],
+        projected_schema: &SchemaRef,
+        sort_columns: &[String],
+        partition_columns: &[String],
+    ) -> PlanProperties {
+        let orderings = sort_columns_to_lex_ordering(sort_columns, projected_schema)
+            .filter(|_| partitions.iter().all(|slices| slices.len() <= 1));
+        let eq_properties = EquivalenceProperties::new_with_orderings(
+            projected_schema.clone(),
+            orderings.into_iter().map(|ordering| ordering.to_vec()),
+        );
+
+        let n_partitions = partitions.len();
+        let partition_exprs = partition_columns
+            .iter()
+            .map(|name| {
+                Column::new_with_schema(name, projected_schema)
+                    .map(|column| Arc::new(column) as Arc<dyn PhysicalExpr>)
+            })
+            .collect::<Result<Vec<_>>>()
+            .ok()
+            .filter(|exprs| !exprs.is_empty());
+        let partitioning = match partition_exprs {
+            Some(exprs) => Partitioning::Hash(exprs, n_partitions),
+            None => Partitioning::UnknownPartitioning(n_partitions),
+        };
+
+        PlanProperties::new(
+            eq_properties,
+            partitioning,
+            EmissionType::Incremental,
+            Boundedness::Bounded,
+        )
+    }
+
     #[cfg(test)]
     pub(crate) fn read_options(&self) -> &ReadOptions {
         &self.read_options
@@ -209,7 +304,19 @@ impl DisplayAs for HudiScanExec {
                     self.file_slice_read_concurrency,
                     self.projection,
                     self.limit,
-                )
+                )?;
+                if let Some(ordering) = self.properties.output_ordering() {
+                    write!(f, ", output_ordering=[{ordering}]")?;
+                }
+                if let Partitioning::Hash(..) = self.properties.partitioning {
+                    write!(f, ", partitioning={}", self.properties.partitioning)?;
+                }
+                if !self.runtime_filters.is_empty() {
+                    let filters: Vec<String> =
+                        self.runtime_filters.iter().map(|f| f.to_string()).collect();
+                    write!(f, ", runtime_filters=[{}]", filters.join(", "))?;
+                }
+                Ok(())
             }
             _ => {
                 write!(f, "HudiScanExec")
@@ -281,6 +388,7 @@ impl ExecutionPlan for HudiScanExec {
             .clone();
 
         let projected_schema = self.projected_schema.clone();
+        let output_schema = projected_schema.clone();
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
 
         if file_slices.is_empty() || self.limit == Some(0) {
@@ -297,28 +405,58 @@ impl ExecutionPlan for HudiScanExec {
             .file_slice_read_concurrency
             .min(file_slices.len())
             .max(1);
+        let runtime_filter = (!self.runtime_filters.is_empty())
+            .then(|| conjunction(self.runtime_filters.iter().cloned()));
+        let partition_pruner = self.partition_pruner.clone();
+        let pruned_file_slices = MetricBuilder::new(&self.metrics)
+            .counter("file_slices_pruned_by_runtime_filters", partition);
+        let num_file_slices = self.num_file_slices_to_read(&file_slices);
+        MetricBuilder::new(&self.metrics)
+            .counter("file_slices_skipped_by_limit", partition)
+            .add(file_slices.len() - num_file_slices);
 
-        let stream = stream::iter(0..file_slices.len())
+        let stream = stream::iter(0..num_file_slices)
             .map(move |idx| {
                 let file_slice = file_slices[idx].clone();
                 let reader = reader.clone();
-                let options = options.clone();
+                let mut options = options.clone();
+                let runtime_filter = runtime_filter.clone();
+                let partition_pruner = partition_pruner.clone();
+                let projected_schema = projected_schema.clone();
+                let pruned_file_slices = pruned_file_slices.clone();
                 async move {
+                    // The runtime filters are evaluated once the file slice is
+                    // polled, e.g., after the build side of a join completes.
+                    if let Some(runtime_filter) = runtime_filter
+                        && Self::prune_by_runtime_filter(
+                            runtime_filter,
+                            &file_slice,
+                            partition_pruner.as_deref(),
+                            &projected_schema,
+                            &mut options,
+                        )?
+                    {
+                        pruned_file_slices.add(1);
+                        return Ok(stream::empty().boxed());
+                    }
                     let inner_stream =
                         reader
                             .read_file_slice_stream(&file_slice, &options)
                             .await
                             .map_err(|e| external_error("Failed to read file slice", e))?;
                     Ok::<_, DataFusionError>(
-                        inner_stream.map_err(|e| external_error("Failed to read batch", e)),
+                        inner_stream
+                            .map_err(|e| external_error("Failed to read batch", e))
+                            .boxed(),
                     )
                 }
             })
-            // Scan output is unordered; SQL ordering is provided by explicit
-            // SortExec nodes. Keep both stages on the same small knob: one cap
-            // for async stream construction and one cap for active slice
-            // streams. Raising this can multiply memory pressure across input
-            // partitions on wide MOR scans.
+            // Scan output is unordered across file slices; an output ordering
+            // is only declared for partitions of at most one file slice. Keep
+            // both stages on the same small knob: one cap for async stream
+            // construction and one cap for active slice streams. Raising this
+            // can multiply memory pressure across input partitions on wide
+            // MOR scans.
             .buffer_unordered(concurrency)
             .try_flatten_unordered(concurrency)
             .boxed();
@@ -330,7 +468,7 @@ impl ExecutionPlan for HudiScanExec {
         let stream = BaselineMetricStream::new(stream, baseline_metrics);
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
-            projected_schema,
+            output_schema,
             stream,
         )))
     }
@@ -340,21 +478,20 @@ impl ExecutionPlan for HudiScanExec {
     }
 
     fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
-        let column_statistics =
-            vec![ColumnStatistics::new_unknown(); self.projected_schema.fields().len()];
-
-        let partitions: Box<dyn Iterator<Item = &[FileSlice]> + '_> = match partition {
-            None => Box::new(
-                self.file_slice_partitions
-                    .iter()
-                    .map(|slices| slices.as_slice()),
-            ),
+        let partitions: Vec<&[FileSlice]> = match partition {
+            None => self
+                .file_slice_partitions
+                .iter()
+                .map(|slices| slices.as_slice())
+                .collect(),
             Some(idx) => match self.file_slice_partitions.get(idx) {
-                Some(slices) => Box::new(std::iter::once(slices.as_slice())),
+                Some(slices) => vec![slices.as_slice()],
                 None => return Ok(Statistics::new_unknown(&self.projected_schema)),
             },
         };
 
+        let column_statistics =
+            Self::aggregate_column_statistics(partitions.iter().copied(), &self.projected_schema);
         Ok(Self::aggregate_partitions(partitions, column_statistics))
     }
 
@@ -368,6 +505,10 @@ impl ExecutionPlan for HudiScanExec {
             projected_schema: self.projected_schema.clone(),
             projection: self.projection.clone(),
             limit,
+            sort_columns: self.sort_columns.clone(),
+            partition_columns: self.partition_columns.clone(),
+            partition_pruner: self.partition_pruner.clone(),
+            runtime_filters: self.runtime_filters.clone(),
             properties: self.properties.clone(),
             // `with_fetch` is a planner-time clone used before execution. Metrics
             // belong to the cloned plan instance and must start empty.
@@ -378,18 +519,228 @@ impl ExecutionPlan for HudiScanExec {
     fn fetch(&self) -> Option<usize> {
         self.limit
     }
+
+    /// Keeps the filters pushed down after the physical optimization, i.e., the
+    /// dynamic filters of joins and TopK, to prune the file slices when they
+    /// are opened. The filters are reported as not pushed down, as the records
+    /// of the remaining file slices are not guaranteed to match them.
+    fn handle_child_pushdown_result(
+        &self,
+        phase: FilterPushdownPhase,
+        child_pushdown_result: ChildPushdownResult,
+        _config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn ExecutionPlan>>> {
+        let filters: Vec<Arc<dyn PhysicalExpr>> = child_pushdown_result
+            .parent_filters
+            .into_iter()
+            .map(|f| f.filter)
+            .collect();
+        let propagation = FilterPushdownPropagation::with_parent_pushdown_result(vec![
+            PushedDown::No;
+            filters.len()
+        ]);
+        if !matches!(phase, FilterPushdownPhase::Post) || filters.is_empty() {
+            return Ok(propagation);
+        }
+
+        let mut exec = self.clone();
+        exec.runtime_filters.extend(filters);
+        exec.metrics = ExecutionPlanMetricsSet::new();
+        Ok(propagation.with_updated_node(Arc::new(exec)))
+    }
+
+    /// Reorders the file slices of each partition to read first the ones whose
+    /// column stats of the leading sort column hold the top values, so that the
+    /// dynamic filter of TopK tightens early to prune the rest.
+    ///
+    /// The records are not sorted, so the sort is kept. Not supported when no
+    /// file slice has the column stats to order by.
+    fn try_pushdown_sort(
+        &self,
+        order: &[PhysicalSortExpr],
+    ) -> Result<SortOrderPushdownResult<Arc<dyn ExecutionPlan>>> {
+        let Some((column, descending)) = order.first().and_then(|sort_expr| {
+            let column = sort_expr.expr.as_any().downcast_ref::<Column>()?;
+            Some((column.name(), sort_expr.options.descending))
+        }) else {
+            return Ok(SortOrderPushdownResult::Unsupported);
+        };
+
+        let mut has_stats = false;
+        let file_slice_partitions = self
+            .file_slice_partitions
+            .iter()
+            .map(|slices| {
+                let mut keyed: Vec<(Option<ScalarValue>, &FileSlice)> = slices
+                    .iter()
+                    .map(|file_slice| (self.sort_key(file_slice, column, descending), file_slice))
+                    .collect();
+                has_stats |= keyed.iter().any(|(key, _)| key.is_some());
+                keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+                    (Some(a), Some(b)) => {
+                        let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                        if descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    }
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                });
+                Arc::new(keyed.into_iter().map(|(_, f)| f.clone()).collect())
+            })
+            .collect();
+        if !has_stats {
+            return Ok(SortOrderPushdownResult::Unsupported);
+        }
+
+        let mut exec = self.clone();
+        exec.file_slice_partitions = file_slice_partitions;
+        exec.metrics = ExecutionPlanMetricsSet::new();
+        Ok(SortOrderPushdownResult::Inexact {
+            inner: Arc::new(exec),
+        })
+    }
 }
 
 impl HudiScanExec {
+    /// Returns the number of the leading file slices to read for the limit,
+    /// i.e., all of them unless the record counts of the base files guarantee
+    /// enough records to be read from fewer file slices.
+    ///
+    /// The record counts only hold for the file slices read without log files,
+    /// and when no records are filtered out on read.
+    fn num_file_slices_to_read(&self, file_slices: &[FileSlice]) -> usize {
+        let Some(limit) = self.limit else {
+            return file_slices.len();
+        };
+        let filters_records = !self.runtime_filters.is_empty()
+            || !self.read_options.filters.is_empty()
+            || self.read_options.start_timestamp().is_some();
+        if filters_records {
+            return file_slices.len();
+        }
+        let reads_log_files = !self.read_options.is_read_optimized().unwrap_or(false);
+
+        let mut num_records: u64 = 0;
+        for (idx, file_slice) in file_slices.iter().enumerate() {
+            if num_records >= limit as u64 {
+                return idx;
+            }
+            if reads_log_files && file_slice.has_log_file() {
+                continue;
+            }
+            let base_file_records = file_slice
+                .base_file_column_stats
+                .as_ref()
+                .and_then(|stats| stats.num_rows)
+                .and_then(|num_rows| u64::try_from(num_rows).ok());
+            num_records = num_records.saturating_add(base_file_records.unwrap_or(0));
+        }
+        file_slices.len()
+    }
+
+    /// Returns the most promising value of the column in the file slice for a
+    /// sort, i.e., the max value for descending sorts and the min value for
+    /// ascending ones, from its column stats or partition values.
+    fn sort_key(
+        &self,
+        file_slice: &FileSlice,
+        column: &str,
+        descending: bool,
+    ) -> Option<ScalarValue> {
+        let partition_values = self
+            .partition_pruner
+            .as_ref()
+            .and_then(|pruner| pruner.partition_values(&file_slice.partition_path))
+            .unwrap_or_default();
+        let statistics =
+            FileSlicePruningStatistics::new(file_slice, partition_values, &self.projected_schema);
+        let column = datafusion_common::Column::new_unqualified(column);
+        let values = if descending {
+            statistics.max_values(&column)
+        } else {
+            statistics.min_values(&column)
+        }?;
+        ScalarValue::try_from_array(&values, 0)
+            .ok()
+            .filter(|value| !value.is_null())
+    }
+
+    /// Evaluates the runtime filter, as of now, on the statistics and partition
+    /// values of the file slice, returning `true` if the file slice is pruned.
+    ///
+    /// Otherwise, the parts of the filter convertible to [`FilterExpr`] are
+    /// added to the filters of the read options to skip records. Those on the
+    /// partition fields are left out, as the fields may not be stored in the
+    /// files.
+    fn prune_by_runtime_filter(
+        runtime_filter: Arc<dyn PhysicalExpr>,
+        file_slice: &FileSlice,
+        partition_pruner: Option<&PartitionPruner>,
+        schema: &SchemaRef,
+        options: &mut ReadOptions,
+    ) -> Result<bool> {
+        let predicate = snapshot_physical_expr(runtime_filter)?;
+        let partition_values = partition_pruner
+            .and_then(|pruner| pruner.partition_values(&file_slice.partition_path))
+            .unwrap_or_default();
+        let statistics = FileSlicePruningStatistics::new(file_slice, partition_values, schema);
+        if can_prune_file_slice(&predicate, schema, &statistics) {
+            return Ok(true);
+        }
+
+        let is_stored_field = |name: &str| {
+            partition_pruner
+                .is_none_or(|pruner| pruner.partition_schema().field_with_name(name).is_err())
+        };
+        let filters = match physical_expr_to_filter_expr(&predicate, schema) {
+            Some(FilterExpr::And(conjuncts)) => conjuncts,
+            Some(filter) => vec![filter],
+            None => Vec::new(),
+        };
+        options.filters.extend(
+            filters
+                .into_iter()
+                .filter(|filter| filter.filters().iter().all(|f| is_stored_field(&f.field))),
+        );
+        Ok(false)
+    }
+
     fn aggregate_file_slice_statistics(&self) -> Statistics {
-        let column_statistics =
-            vec![ColumnStatistics::new_unknown(); self.projected_schema.fields().len()];
-        Self::aggregate_partitions(
+        let partitions = || {
             self.file_slice_partitions
                 .iter()
-                .map(|slices| slices.as_slice()),
-            column_statistics,
-        )
+                .map(|slices| slices.as_slice())
+        };
+        let column_statistics =
+            Self::aggregate_column_statistics(partitions(), &self.projected_schema);
+        Self::aggregate_partitions(partitions(), column_statistics)
+    }
+
+    /// Aggregates the column stats of the base files of the file slices.
+    ///
+    /// The statistics are inexact, as the log files are not covered and the
+    /// read options may filter out records. All columns are unknown if any
+    /// file slice has no column stats loaded.
+    fn aggregate_column_statistics<'a, I>(partitions: I, schema: &Schema) -> Vec<ColumnStatistics>
+    where
+        I: IntoIterator<Item = &'a [FileSlice]>,
+    {
+        let mut slices = partitions.into_iter().flatten();
+        let merged = slices.next().and_then(|first| {
+            let mut merged = first.base_file_column_stats.clone()?;
+            for file_slice in slices {
+                merged.merge(file_slice.base_file_column_stats.as_ref()?);
+            }
+            Some(merged)
+        });
+        match merged {
+            Some(stats) => to_column_statistics(&stats, schema, false),
+            None => vec![ColumnStatistics::new_unknown(); schema.fields().len()],
+        }
     }
 
     fn aggregate_partitions<'a, I>(
@@ -449,11 +800,17 @@ impl HudiScanExec {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow_array::ArrayRef;
     use arrow_array::Int32Array;
     use arrow_schema::Schema;
     use arrow_schema::{DataType, Field};
+    use datafusion_common::ScalarValue;
     use hudi_core::config::util::empty_options;
     use hudi_core::file_group::base_file::BaseFile;
+    use hudi_core::file_group::log_file::LogFile;
+    use hudi_core::statistics::{
+        ColumnStatistics as HudiColumnStatistics, StatisticsContainer, StatsGranularity,
+    };
     use hudi_core::storage::file_metadata::FileMetadata;
     use std::collections::BTreeSet;
     use std::fs::canonicalize;
@@ -528,6 +885,56 @@ mod tests {
         assert_eq!(stats.column_statistics.len(), 2);
     }
 
+    #[test]
+    fn test_aggregate_column_statistics() {
+        let schema = Schema::new(vec![Field::new("value", DataType::Int32, false)]);
+        let with_stats = |file_name: &str, min: i32, max: i32| {
+            let mut stats = StatisticsContainer::new(StatsGranularity::File);
+            stats.num_rows = Some(10);
+            let column = HudiColumnStatistics {
+                column_name: "value".to_string(),
+                data_type: DataType::Int32,
+                min_value: Some(Arc::new(Int32Array::from(vec![min])) as ArrayRef),
+                max_value: Some(Arc::new(Int32Array::from(vec![max])) as ArrayRef),
+                null_count: Some(0),
+            };
+            stats.columns.insert("value".to_string(), column);
+            let mut file_slice = file_slice_with_meta(file_name, 100, 10, 200);
+            file_slice.base_file_column_stats = Some(stats);
+            file_slice
+        };
+        let mut partitions = [
+            vec![with_stats("fileA-0_0-1-1_20250101000000000.parquet", 5, 20)],
+            vec![with_stats("fileB-0_0-1-1_20250101000000000.parquet", 1, 9)],
+        ];
+
+        let columns = HudiScanExec::aggregate_column_statistics(
+            partitions.iter().map(Vec::as_slice),
+            &schema,
+        );
+        assert_eq!(
+            columns[0].min_value,
+            Precision::Inexact(ScalarValue::Int32(Some(1)))
+        );
+        assert_eq!(
+            columns[0].max_value,
+            Precision::Inexact(ScalarValue::Int32(Some(20)))
+        );
+        assert_eq!(columns[0].null_count, Precision::Inexact(0));
+
+        partitions[1].push(file_slice_with_meta(
+            "fileC-0_0-1-1_20250101000000000.parquet",
+            100,
+            10,
+            200,
+        ));
+        let columns = HudiScanExec::aggregate_column_statistics(
+            partitions.iter().map(Vec::as_slice),
+            &schema,
+        );
+        assert_eq!(columns, vec![ColumnStatistics::new_unknown()]);
+    }
+
     #[test]
     fn test_aggregate_partitions_returns_absent_when_metadata_missing() {
         let mut bf = BaseFile::from_str("fileA-0_0-1-1_20250101000000000.parquet").unwrap();
@@ -609,4 +1016,322 @@ mod tests {
 
         assert!(exec.metrics().is_some());
     }
+
+    #[tokio::test]
+    async fn test_declared_ordering_and_partitioning() {
+        let base_url =
+            Url::from_file_path(canonicalize(Path::new("tests/data/table_props_valid")).unwrap())
+                .unwrap();
+        let reader = Arc::new(
+            FileGroupReader::new_with_options(base_url.as_str(), empty_options())
+                .await
+                .unwrap(),
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("region", DataType::Utf8, true),
+        ]));
+        let new_exec = |partitions: Vec<Vec<FileSlice>>, projection: Option<Vec<usize>>| {
+            HudiScanExec::new(
+                partitions,
+                reader.clone(),
+                ReadOptions::new(),
+                2,
+                1,
+                schema.clone(),
+                projection,
+                None,
+            )
+        };
+        let slice = |name: &str| file_slice_with_meta(name, 1, 1, 1);
+        let sort_columns = vec!["id".to_string()];
+        let partition_columns = vec!["region".to_string()];
+
+        let exec = new_exec(
+            vec![
+                vec![slice("fileA-0_0-1-1_20250101000000000.parquet")],
+                vec![slice("fileB-0_0-1-1_20250101000000000.parquet")],
+            ],
+            None,
+        )
+        .with_sort_columns(sort_columns.clone())
+        .with_partition_columns(partition_columns.clone());
+        let properties = exec.properties();
+        assert_eq!(
+            properties.output_ordering().map(|o| o.to_string()),
+            Some("id@0 ASC".to_string())
+        );
+        assert_eq!(properties.partitioning.to_string(), "Hash([region@1], 2)");
+
+        // The file slices of a partition are read concurrently.
+        let exec = new_exec(
+            vec![vec![
+                slice("fileA-0_0-1-1_20250101000000000.parquet"),
+                slice("fileB-0_0-1-1_20250101000000000.parquet"),
+            ]],
+            Some(vec![0]),
+        )
+        .with_sort_columns(sort_columns)
+        .with_partition_columns(partition_columns);
+        let properties = exec.properties();
+        assert!(properties.output_ordering().is_none());
+        assert!(
+            matches!(
+                properties.partitioning,
+                Partitioning::UnknownPartitioning(1)
+            ),
+            "The partition column is not projected"
+        );
+    }
+
+    fn file_slice_with_stats(file_id: &str, num_rows: i64, min_id: i32, max_id: i32) -> FileSlice {
+        let base_file =
+            BaseFile::from_str(&format!("{file_id}-0_0-1-1_20250101000000000.parquet")).unwrap();
+        let mut file_slice = FileSlice::new(base_file, String::new());
+        let mut stats = StatisticsContainer::new(StatsGranularity::File);
+        stats.num_rows = Some(num_rows);
+        stats.columns.insert(
+            "id".to_string(),
+            HudiColumnStatistics {
+                column_name: "id".to_string(),
+                data_type: DataType::Int32,
+                min_value: Some(Arc::new(Int32Array::from(vec![min_id])) as ArrayRef),
+                max_value: Some(Arc::new(Int32Array::from(vec![max_id])) as ArrayRef),
+                null_count: Some(0),
+            },
+        );
+        file_slice.base_file_column_stats = Some(stats);
+        file_slice
+    }
+
+    async fn scan_exec(file_slices: Vec<FileSlice>, limit: Option<usize>) -> HudiScanExec {
+        let base_url =
+            Url::from_file_path(canonicalize(Path::new("tests/data/table_props_valid")).unwrap())
+                .unwrap();
+        let reader = Arc::new(
+            FileGroupReader::new_with_options(base_url.as_str(), empty_options())
+                .await
+                .unwrap(),
+        );
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        HudiScanExec::new(
+            vec![file_slices],
+            reader,
+            ReadOptions::new(),
+            1,
+            1,
+            schema,
+            None,
+            limit,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_num_file_slices_to_read_for_limit() {
+        let mut with_log_file = file_slice_with_stats("fileB", 10, 0, 0);
+        with_log_file
+            .log_files
+            .insert(LogFile::from_str(".fileB-0_20250101000000000.log.1_0-1-1").unwrap());
+        let mut without_stats = file_slice_with_stats("fileC", 10, 0, 0);
+        without_stats.base_file_column_stats = None;
+        let file_slices = vec![
+            file_slice_with_stats("fileA", 10, 0, 0),
+            with_log_file,
+            without_stats,
+            file_slice_with_stats("fileD", 10, 0, 0),
+            file_slice_with_stats("fileE", 10, 0, 0),
+        ];
+
+        for (limit, expected) in [
+            (None, 5),
+            (Some(1), 1),
+            (Some(10), 1),
+            (Some(11), 4),
+            (Some(21), 5),
+        ] {
+            let exec = scan_exec(file_slices.clone(), limit).await;
+            assert_eq!(
+                exec.num_file_slices_to_read(&file_slices),
+                expected,
+                "{limit:?}"
+            );
+        }
+
+        // The base files of the file slices with log files count for read-optimized reads.
+        let mut exec = scan_exec(file_slices.clone(), Some(11)).await;
+        exec.read_options = ReadOptions::new().with_hudi_option(
+            hudi_core::config::read::HudiReadConfig::UseReadOptimizedMode.as_ref(),
+            "true",
+        );
+        assert_eq!(exec.num_file_slices_to_read(&file_slices), 2);
+
+        // All file slices are read when records may be filtered out.
+        let mut exec = scan_exec(file_slices.clone(), Some(1)).await;
+        exec.read_options = ReadOptions::new().with_filters([("id", ">", "5")]).unwrap();
+        assert_eq!(exec.num_file_slices_to_read(&file_slices), 5);
+        let mut exec = scan_exec(file_slices.clone(), Some(1)).await;
+        exec.read_options = ReadOptions::new().with_start_timestamp("20250101000000000");
+        assert_eq!(exec.num_file_slices_to_read(&file_slices), 5);
+    }
+
+    #[tokio::test]
+    async fn test_try_pushdown_sort_reorders_file_slices_by_stats() {
+        use datafusion::physical_expr::expressions::col;
+
+        let mut without_stats = file_slice_with_stats("fileD", 10, 0, 0);
+        without_stats.base_file_column_stats = None;
+        let exec = scan_exec(
+            vec![
+                without_stats.clone(),
+                file_slice_with_stats("fileA", 10, 5, 20),
+                file_slice_with_stats("fileB", 10, 1, 9),
+                file_slice_with_stats("fileC", 10, 10, 30),
+            ],
+            None,
+        )
+        .await;
+        let file_ids = |descending: bool| {
+            let sort_expr = PhysicalSortExpr::new(
+                col("id", &exec.projected_schema).unwrap(),
+                arrow_schema::SortOptions {
+                    descending,
+                    nulls_first: false,
+                },
+            );
+            let result = exec.try_pushdown_sort(&[sort_expr]).unwrap();
+            let SortOrderPushdownResult::Inexact { inner } = result else {
+                panic!("Sort pushdown should be inexact");
+            };
+            let inner = inner.as_any().downcast_ref::<HudiScanExec>().unwrap();
+            inner.file_slice_partitions[0]
+                .iter()
+                .map(|f| f.file_id().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(file_ids(true), ["fileC-0", "fileA-0", "fileB-0", "fileD-0"]);
+        assert_eq!(
+            file_ids(false),
+            ["fileB-0", "fileA-0", "fileC-0", "fileD-0"]
+        );
+
+        let exec = scan_exec(vec![without_stats], None).await;
+        let sort_expr = PhysicalSortExpr::new_default(col("id", &exec.projected_schema).unwrap());
+        assert!(matches!(
+            exec.try_pushdown_sort(&[sort_expr]).unwrap(),
+            SortOrderPushdownResult::Unsupported
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_runtime_filters_from_post_phase_pushdown() {
+        use datafusion::logical_expr::Operator;
+        use datafusion::physical_expr::expressions::{BinaryExpr, col, lit};
+        use datafusion::physical_plan::filter_pushdown::ChildFilterPushdownResult;
+        use hudi_core::config::HudiConfigs;
+
+        let base_url =
+            Url::from_file_path(canonicalize(Path::new("tests/data/table_props_valid")).unwrap())
+                .unwrap();
+        let reader = Arc::new(
+            FileGroupReader::new_with_options(base_url.as_str(), empty_options())
+                .await
+                .unwrap(),
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("region", DataType::Utf8, true),
+        ]));
+        let exec = HudiScanExec::new(
+            vec![],
+            reader,
+            ReadOptions::new(),
+            1,
+            1,
+            schema.clone(),
+            None,
+            None,
+        );
+        let compare = |column: &str, op: Operator, value: ScalarValue| {
+            Arc::new(BinaryExpr::new(
+                col(column, &schema).unwrap(),
+                op,
+                lit(value),
+            )) as Arc<dyn PhysicalExpr>
+        };
+        let id_gt_5 = compare("id", Operator::Gt, ScalarValue::Int32(Some(5)));
+        let pushdown = |phase: FilterPushdownPhase| {
+            let result = ChildPushdownResult {
+                parent_filters: vec![ChildFilterPushdownResult {
+                    filter: id_gt_5.clone(),
+                    child_results: vec![],
+                }],
+                self_filters: vec![],
+            };
+            exec.handle_child_pushdown_result(phase, result, &ConfigOptions::default())
+                .unwrap()
+        };
+
+        let propagation = pushdown(FilterPushdownPhase::Pre);
+        assert!(matches!(propagation.filters[..], [PushedDown::No]));
+        assert!(propagation.updated_node.is_none());
+
+        let propagation = pushdown(FilterPushdownPhase::Post);
+        assert!(matches!(propagation.filters[..], [PushedDown::No]));
+        let updated = propagation.updated_node.unwrap();
+        let updated = updated.as_any().downcast_ref::<HudiScanExec>().unwrap();
+        assert_eq!(updated.runtime_filters.len(), 1);
+        let display = datafusion::physical_plan::displayable(updated)
+            .one_line()
+            .to_string();
+        assert!(display.contains("runtime_filters=[id@0 > 5]"), "{display}");
+
+        // The file slice is pruned by its column stats or partition values, and
+        // otherwise read with the runtime filters on the stored fields.
+        let mut file_slice = FileSlice::new(
+            BaseFile::from_str("fileA-0_0-1-1_20250101000000000.parquet").unwrap(),
+            "region=eu".to_string(),
+        );
+        let mut stats = StatisticsContainer::new(StatsGranularity::File);
+        stats.columns.insert(
+            "id".to_string(),
+            HudiColumnStatistics {
+                column_name: "id".to_string(),
+                data_type: DataType::Int32,
+                min_value: Some(Arc::new(Int32Array::from(vec![1])) as ArrayRef),
+                max_value: Some(Arc::new(Int32Array::from(vec![9])) as ArrayRef),
+                null_count: Some(0),
+            },
+        );
+        file_slice.base_file_column_stats = Some(stats);
+        let partition_schema = Schema::new(vec![Field::new("region", DataType::Utf8, true)]);
+        let configs =
+            HudiConfigs::new([("hoodie.datasource.write.hive_style_partitioning", "true")]);
+        let pruner = PartitionPruner::new(&[], &partition_schema, &configs).unwrap();
+        let prune = |filter: Arc<dyn PhysicalExpr>, options: &mut ReadOptions| {
+            HudiScanExec::prune_by_runtime_filter(
+                filter,
+                &file_slice,
+                Some(&pruner),
+                &schema,
+                options,
+            )
+            .unwrap()
+        };
+
+        let mut options = ReadOptions::new();
+        let id_gt_9 = compare("id", Operator::Gt, ScalarValue::Int32(Some(9)));
+        assert!(prune(id_gt_9, &mut options));
+        let region_us = compare("region", Operator::Eq, ScalarValue::from("us"));
+        assert!(prune(region_us, &mut options));
+        assert!(options.filters.is_empty());
+
+        let region_eu = compare("region", Operator::Eq, ScalarValue::from("eu"));
+        let filter = Arc::new(BinaryExpr::new(id_gt_5, Operator::And, region_eu));
+        assert!(!prune(filter, &mut options));
+        assert_eq!(
+            options.filters,
+            vec![FilterExpr::from(hudi_core::expr::filter::col("id").gt("5"))]
+        );
+    }
 }
@@ -0,0 +1,217 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! DataFusion data sink for writing to Hudi tables, used by `INSERT INTO`,
+//! `INSERT OVERWRITE`, `DELETE` and `UPDATE` statements.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_schema::SchemaRef;
+use async_trait::async_trait;
+use datafusion::datasource::sink::DataSink;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+use datafusion_common::DataFusionError::Execution;
+use datafusion_common::Result;
+use datafusion_expr::dml::{InsertOp, WriteOp};
+use futures::TryStreamExt;
+
+use crate::external_error;
+use hudi_core::config::write::HudiWriteConfig::MaxBufferedBytes;
+use hudi_core::table::Table as HudiTable;
+
+/// DataFusion data sink that writes the incoming records to a Hudi table as
+/// a single commit.
+///
+/// The write operation follows the statement:
+/// - [`InsertOp::Append`] writes with [`HudiTable::write`], i.e., inserts or
+///   upserts as configured by `hoodie.datasource.write.operation`.
+/// - [`InsertOp::Overwrite`] overwrites the partitions written to with
+///   [`HudiTable::insert_overwrite`]. Without input records, the
+///   `replacecommit` is still made, replacing all file groups of a
+///   non-partitioned table with [`HudiTable::insert_overwrite_table`].
+/// - [`InsertOp::Replace`] upserts with [`HudiTable::upsert`].
+/// - [`WriteOp::Delete`] deletes the records with [`HudiTable::delete`].
+/// - [`WriteOp::Update`] updates the records with [`HudiTable::update`].
+///
+/// The table is reloaded before writing, so that consecutive statements
+/// through the same table provider see the commits of each other.
+///
+/// The input records are buffered in memory to be written as a single
+/// commit, up to [`MaxBufferedBytes`] of the table; larger inputs fail the
+/// write.
+#[derive(Debug)]
+pub struct HudiDataSink {
+    table: Arc<HudiTable>,
+    schema: SchemaRef,
+    write_op: WriteOp,
+}
+
+impl HudiDataSink {
+    pub fn new(table: Arc<HudiTable>, schema: SchemaRef, write_op: WriteOp) -> Self {
+        Self {
+            table,
+            schema,
+            write_op,
+        }
+    }
+
+    /// Reload the Hudi table with the same options, so that the write sees
+    /// the commits made since the table was loaded.
+    pub(crate) async fn load_table(table: &HudiTable) -> Result<HudiTable> {
+        let options = table
+            .storage_options()
+            .into_iter()
+            .chain(table.hudi_options());
+        HudiTable::new_with_options(table.base_url().as_str(), options)
+            .await
+            .map_err(|e| external_error("Failed to load Hudi table for writing", e))
+    }
+
+    /// Collect the input records to be written, failing once their size
+    /// exceeds [`MaxBufferedBytes`] of the table.
+    pub(crate) async fn collect_input(
+        table: &HudiTable,
+        mut data: SendableRecordBatchStream,
+    ) -> Result<Vec<RecordBatch>> {
+        let max_bytes: usize = table.hudi_configs.get_or_default(MaxBufferedBytes).into();
+        let mut batches = Vec::new();
+        let mut num_bytes = 0;
+        while let Some(batch) = data.try_next().await? {
+            num_bytes += batch.get_array_memory_size();
+            if num_bytes > max_bytes {
+                return Err(Execution(format!(
+                    "The records to write to the Hudi table exceed {} = {max_bytes} bytes; \
+                     they are buffered in memory to be written as a single commit",
+                    MaxBufferedBytes.as_ref()
+                )));
+            }
+            batches.push(batch);
+        }
+        Ok(batches)
+    }
+
+    /// Project the records to the data columns of the table, dropping the
+    /// meta fields.
+    pub(crate) fn to_data_records(
+        data_schema: &SchemaRef,
+        batches: Vec<RecordBatch>,
+    ) -> Result<Vec<RecordBatch>> {
+        batches
+            .into_iter()
+            .filter(|batch| batch.num_rows() > 0)
+            .map(|batch| {
+                let columns = data_schema
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        batch.column_by_name(field.name()).cloned().ok_or_else(|| {
+                            Execution(format!(
+                                "Column '{}' of the Hudi table is missing from the records",
+                                field.name()
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(RecordBatch::try_new(data_schema.clone(), columns)?)
+            })
+            .collect()
+    }
+}
+
+impl DisplayAs for HudiDataSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "HudiDataSink: base_url={}, write_op={}",
+                    self.table.base_url(),
+                    self.write_op
+                )
+            }
+            _ => {
+                write!(f, "HudiDataSink")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for HudiDataSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let batches = Self::collect_input(&self.table, data).await?;
+        let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        let overwrite = matches!(self.write_op, WriteOp::Insert(InsertOp::Overwrite));
+        if num_rows == 0 && !overwrite {
+            return Ok(0);
+        }
+
+        let table = Self::load_table(&self.table).await?;
+        let data_schema = SchemaRef::from(
+            table
+                .get_schema()
+                .await
+                .map_err(|e| external_error("Failed to get Hudi table schema", e))?,
+        );
+        let records = Self::to_data_records(&data_schema, batches)?;
+        let result = match self.write_op {
+            // Overwriting a non-partitioned table replaces its only partition even without
+            // records, while no partitions of a partitioned table are written to.
+            WriteOp::Insert(InsertOp::Overwrite) if num_rows == 0 => {
+                let partition_schema = table
+                    .get_partition_schema()
+                    .await
+                    .map_err(|e| external_error("Failed to get Hudi partition schema", e))?;
+                if partition_schema.fields().is_empty() {
+                    table.insert_overwrite_table(&records).await
+                } else {
+                    table.insert_overwrite(&records).await
+                }
+            }
+            WriteOp::Insert(InsertOp::Append) => table.write(&records).await,
+            WriteOp::Insert(InsertOp::Overwrite) => table.insert_overwrite(&records).await,
+            WriteOp::Insert(InsertOp::Replace) => table.upsert(&records).await,
+            WriteOp::Delete => table.delete(&records).await,
+            WriteOp::Update => table.update(&records).await,
+            WriteOp::Ctas => {
+                return Err(Execution(
+                    "CREATE TABLE AS SELECT is not supported by HudiDataSink".to_string(),
+                ));
+            }
+        };
+        result.map_err(|e| external_error("Failed to write to Hudi table", e))?;
+
+        Ok(num_rows as u64)
+    }
+}
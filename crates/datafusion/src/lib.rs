@@ -17,14 +17,20 @@
  * under the License.
  */
 
+pub(crate) mod catalog;
+pub(crate) mod dml;
 pub(crate) mod hudi_exec;
+pub(crate) mod hudi_sink;
+pub(crate) mod merge;
+pub(crate) mod table_function;
 pub(crate) mod util;
 
 use std::any::Any;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
 
 use arrow_schema::{Schema, SchemaRef};
 use async_trait::async_trait;
@@ -35,31 +41,63 @@ use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::FileGroup;
 use datafusion::datasource::physical_plan::FileScanConfigBuilder;
 use datafusion::datasource::physical_plan::parquet::source::ParquetSource;
+use datafusion::datasource::sink::DataSinkExec;
 use datafusion::datasource::source::DataSourceExec;
 use datafusion::error::Result;
+use datafusion::execution::SessionStateBuilder;
+use datafusion::execution::context::SessionContext;
 use datafusion::logical_expr::Operator;
 use datafusion::physical_plan::ExecutionPlan;
+use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion_common::DFSchema;
 use datafusion_common::DataFusionError::Execution;
 use datafusion_common::config::TableParquetOptions;
 use datafusion_common::stats::Precision;
-use datafusion_common::{DataFusionError, Statistics};
-use datafusion_expr::utils::split_conjunction;
-use datafusion_expr::{CreateExternalTable, Expr, TableProviderFilterPushDown, TableType};
+use datafusion_common::{DataFusionError, SchemaExt, Statistics, TableReference, project_schema};
+use datafusion_expr::dml::{InsertOp, WriteOp};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{
+    CreateExternalTable, Expr, ExprSchemable, TableProviderFilterPushDown, TableType, col,
+};
 use datafusion_physical_expr::create_physical_expr;
 use log::warn;
 
+pub use crate::catalog::{HudiCatalogProvider, HudiSchemaProvider};
+pub use crate::dml::{DML_FILTER_RULE, HudiDmlFilterRule};
 use crate::hudi_exec::HudiScanExec;
-use crate::util::expr::exprs_to_filters;
+use crate::hudi_sink::HudiDataSink;
+pub use crate::merge::plan_merge_into;
+pub use crate::table_function::{
+    COMMIT_STATS_FUNCTION, FILE_SLICES_FUNCTION, HudiCommitStatsFunction, HudiFileSlicesFunction,
+    HudiSnapshotAsOfFunction, HudiTableChangesFunction, HudiTimelineFunction,
+    SNAPSHOT_AS_OF_FUNCTION, TABLE_CHANGES_FUNCTION, TIMELINE_FUNCTION, register_table_functions,
+};
+use crate::util::expr::{expr_to_filter_expr, exprs_to_filters};
+use crate::util::layout::{
+    clustering_sort_columns, count_partition_paths, group_by_partition_path,
+    sort_columns_to_lex_ordering,
+};
+use crate::util::stats::{file_slice_statistics, to_column_statistics};
 use hudi_core::config::read::HudiReadConfig::{
-    FileSliceReadConcurrency, InputPartitions, UseReadOptimizedMode,
+    AsOfTimestamp, EndTimestamp, FileSliceReadConcurrency, InputPartitions, QueryType,
+    StartTimestamp, UseReadOptimizedMode,
 };
+use hudi_core::config::table::HudiTableConfig::RecordKeyFields;
 use hudi_core::config::table::{BaseFileFormatValue, HudiTableConfig};
 use hudi_core::config::util::empty_options;
 use hudi_core::config::{ConfigParser, HudiConfigs};
+use hudi_core::expr::filter::FilterExpr;
 use hudi_core::file_group::file_slice::FileSlice;
+use hudi_core::metadata::meta_field::MetaField;
 use hudi_core::storage::util::{get_scheme_authority, join_url_segments};
-use hudi_core::table::{ReadOptions, Table as HudiTable};
+use hudi_core::table::partition::PartitionPruner;
+use hudi_core::table::{QueryType as HudiQueryType, ReadOptions, Table as HudiTable};
+
+/// Default of the meta fields left out of `INSERT INTO` statements. The
+/// meta fields are non-nullable, and their values are populated on write.
+static META_FIELD_DEFAULT: LazyLock<Expr> = LazyLock::new(|| datafusion_expr::lit(""));
 
 fn default_file_slice_read_concurrency() -> usize {
     match FileSliceReadConcurrency.default_value() {
@@ -153,6 +191,9 @@ pub struct HudiDataSource {
     file_slice_read_concurrency: usize,
     /// Explicit base file format from table config, if present.
     base_file_format: Option<BaseFileFormatValue>,
+    /// Query type and timestamps requested when constructing the provider,
+    /// applied to every scan, e.g., for incremental or time-travel queries.
+    query_options: ReadOptions,
 }
 
 impl std::fmt::Debug for HudiDataSource {
@@ -232,6 +273,19 @@ impl HudiDataSource {
             }
             None => default_file_slice_read_concurrency(),
         };
+        let query_options = ReadOptions::new().with_hudi_options(
+            all_options
+                .iter()
+                .filter(|(k, _)| {
+                    [QueryType, AsOfTimestamp, StartTimestamp, EndTimestamp]
+                        .iter()
+                        .any(|config| k == config.as_ref())
+                })
+                .cloned(),
+        );
+        query_options
+            .query_type()
+            .map_err(|e| external_error(format!("Invalid value for {}", QueryType.as_ref()), e))?;
         let table = HudiTable::new_with_options(base_uri, all_options)
             .await
             .map_err(|e| external_error("Failed to create Hudi table", e))?;
@@ -275,20 +329,28 @@ impl HudiDataSource {
         // Compute table-level statistics for join ordering and broadcast decisions.
         // Uses MDT files partition for base-file sizes and, for Parquet tables, one
         // sampled footer to infer row counts and byte sizes without loading all file groups.
+        // Column statistics and row counts are aggregated from MDT column stats of the
+        // base files when the column_stats partition is available.
         // Falls back to None if statistics cannot be derived.
-        let cached_stats = match table.compute_table_stats(None).await {
-            Some((num_rows, total_byte_size)) => {
-                let num_fields = schema.fields().len();
-                Some(Statistics {
-                    num_rows: inexact_usize_from_u64(num_rows),
-                    total_byte_size: inexact_usize_from_u64(total_byte_size),
-                    column_statistics: vec![
-                        datafusion_common::ColumnStatistics::new_unknown();
-                        num_fields
-                    ],
-                })
-            }
-            None => None,
+        let table_column_stats = table.compute_table_column_stats(&query_options).await;
+        let column_statistics = match &table_column_stats {
+            Some(stats) => to_column_statistics(stats, &schema, false),
+            None => vec![datafusion_common::ColumnStatistics::new_unknown(); schema.fields().len()],
+        };
+        let num_rows_from_column_stats = table_column_stats
+            .and_then(|stats| stats.num_rows)
+            .and_then(|n| u64::try_from(n).ok());
+        let cached_stats = match table.compute_table_stats(Some(&query_options)).await {
+            Some((num_rows, total_byte_size)) => Some(Statistics {
+                num_rows: inexact_usize_from_u64(num_rows_from_column_stats.unwrap_or(num_rows)),
+                total_byte_size: inexact_usize_from_u64(total_byte_size),
+                column_statistics,
+            }),
+            None => num_rows_from_column_stats.map(|num_rows| Statistics {
+                num_rows: inexact_usize_from_u64(num_rows),
+                total_byte_size: Precision::Absent,
+                column_statistics,
+            }),
         };
 
         Ok(Self {
@@ -300,9 +362,43 @@ impl HudiDataSource {
             read_optimized_mode,
             file_slice_read_concurrency,
             base_file_format,
+            query_options,
         })
     }
 
+    /// Registers the table with the session under the given name, together
+    /// with the [`HudiDmlFilterRule`] that `DELETE` and `UPDATE` statements on
+    /// partitioned tables require.
+    ///
+    /// The table is registered as with [`SessionContext::register_table`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use datafusion::prelude::SessionContext;
+    /// use hudi_datafusion::HudiDataSource;
+    ///
+    /// # async fn example() -> datafusion::error::Result<()> {
+    /// let ctx = SessionContext::new();
+    /// HudiDataSource::new("/tmp/trips_table")
+    ///     .await?
+    ///     .register(&ctx, "trips")?;
+    /// ctx.sql("DELETE FROM trips WHERE city = 'sf'")
+    ///     .await?
+    ///     .collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register(
+        self,
+        ctx: &SessionContext,
+        table_ref: impl Into<TableReference>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        dml::register_dml_filter_rule(ctx);
+        ctx.register_table(table_ref, Arc::new(self))
+    }
+
     fn get_input_partitions(&self) -> usize {
         self.input_partitions
     }
@@ -321,16 +417,18 @@ impl HudiDataSource {
 
     fn scan_read_options(
         &self,
-        pushdown_filters: Vec<(String, String, String)>,
+        pushdown_filters: Vec<FilterExpr>,
         read_optimized: bool,
-    ) -> Result<ReadOptions> {
-        let mut read_options = ReadOptions::new()
-            .with_filters(pushdown_filters)
-            .map_err(|e| external_error("Invalid pushdown filter", e))?;
+    ) -> ReadOptions {
+        let read_options = self
+            .query_options
+            .clone()
+            .with_filter_exprs(pushdown_filters);
         if read_optimized {
-            read_options = read_options.with_hudi_option(UseReadOptimizedMode.as_ref(), "true");
+            read_options.with_hudi_option(UseReadOptimizedMode.as_ref(), "true")
+        } else {
+            read_options
         }
-        Ok(read_options)
     }
 
     /// Build the [`ReadOptions`] passed to `HudiScanExec` for per-slice reads.
@@ -356,10 +454,12 @@ impl HudiDataSource {
         applicable.filters = options
             .filters
             .iter()
-            .filter(|filter| {
-                !partition_columns
-                    .iter()
-                    .any(|p| filter_field_matches_partition_column(&filter.field, p))
+            .filter(|expr| {
+                expr.filters().iter().all(|filter| {
+                    !partition_columns
+                        .iter()
+                        .any(|p| filter_field_matches_partition_column(&filter.field, p))
+                })
             })
             .cloned()
             .collect();
@@ -418,76 +518,15 @@ impl HudiDataSource {
 
     /// Check if the given expression can be pushed down to the Hudi table.
     ///
-    /// The expression can be pushed down if it is:
-    /// - A binary expression with a supported operator and operands
-    /// - A NOT expression wrapping a pushable expression
-    /// - An AND compound expression where at least one side can be pushed down
-    /// - A BETWEEN expression with column and literals
+    /// The expression can be pushed down if [`expr_to_filter_expr`] extracts
+    /// filters from it, and all the filters are on columns of the schema.
     fn can_push_down_expr(schema: &Schema, expr: &Expr) -> bool {
-        match expr {
-            Expr::BinaryExpr(binary_expr) => {
-                let left = &binary_expr.left;
-                let op = &binary_expr.op;
-                let right = &binary_expr.right;
-
-                match op {
-                    Operator::And => {
-                        // AND is pushable if at least one side is pushable
-                        Self::can_push_down_expr(schema, left)
-                            || Self::can_push_down_expr(schema, right)
-                    }
-                    Operator::Or => {
-                        // OR cannot be pushed down with current filter model
-                        false
-                    }
-                    _ => {
-                        Self::is_supported_operator(op)
-                            && Self::is_supported_operand(schema, left)
-                            && Self::is_supported_operand(schema, right)
-                    }
-                }
-            }
-            Expr::Not(inner_expr) => {
-                // Recursively check if the inner expression can be pushed down
-                Self::can_push_down_expr(schema, inner_expr)
-            }
-            Expr::Between(between) => {
-                // BETWEEN can be pushed if expr is a column and bounds are literals
-                !between.negated
-                    && matches!(&*between.expr, Expr::Column(col) if schema.column_with_name(&col.name).is_some())
-                    && matches!(&*between.low, Expr::Literal(..))
-                    && matches!(&*between.high, Expr::Literal(..))
-            }
-            Expr::InList(in_list) => {
-                !in_list.list.is_empty()
-                    && matches!(in_list.expr.as_ref(), Expr::Column(col) if schema.column_with_name(&col.name).is_some())
-                    && in_list
-                        .list
-                        .iter()
-                        .all(|expr| matches!(expr, Expr::Literal(..)))
-            }
-            _ => false,
-        }
-    }
-
-    fn is_supported_operator(op: &Operator) -> bool {
-        matches!(
-            op,
-            Operator::Eq
-                | Operator::NotEq
-                | Operator::Gt
-                | Operator::Lt
-                | Operator::GtEq
-                | Operator::LtEq
-        )
-    }
-
-    fn is_supported_operand(schema: &Schema, expr: &Expr) -> bool {
-        match expr {
-            Expr::Column(col) => schema.column_with_name(&col.name).is_some(),
-            Expr::Literal(..) => true,
-            _ => false,
-        }
+        expr_to_filter_expr(expr).is_some_and(|filter_expr| {
+            filter_expr
+                .filters()
+                .iter()
+                .all(|filter| schema.column_with_name(&filter.field).is_some())
+        })
     }
 
     /// Returns partition column names from partition schema.
@@ -506,36 +545,12 @@ impl HudiDataSource {
     /// DataFusion's `ParquetSource` so Hudi doesn't read Parquet footers for
     /// stats pruning before DataFusion reads the same files.
     fn is_partition_column_filter(expr: &Expr, partition_cols: &[String]) -> bool {
-        match expr {
-            Expr::BinaryExpr(binary_expr) => match binary_expr.op {
-                Operator::And => {
-                    Self::is_partition_column_filter(&binary_expr.left, partition_cols)
-                        && Self::is_partition_column_filter(&binary_expr.right, partition_cols)
-                }
-                Operator::Or => false,
-                _ => match (&*binary_expr.left, &*binary_expr.right) {
-                    (Expr::Column(col), Expr::Literal(..))
-                    | (Expr::Literal(..), Expr::Column(col)) => partition_cols.contains(&col.name),
-                    _ => false,
-                },
-            },
-            Expr::Not(inner) => Self::is_partition_column_filter(inner, partition_cols),
-            Expr::Between(between) => {
-                !between.negated
-                    && matches!(&*between.expr, Expr::Column(col) if partition_cols.contains(&col.name))
-                    && matches!(&*between.low, Expr::Literal(..))
-                    && matches!(&*between.high, Expr::Literal(..))
-            }
-            Expr::InList(in_list) => {
-                !in_list.list.is_empty()
-                    && matches!(in_list.expr.as_ref(), Expr::Column(col) if partition_cols.contains(&col.name))
-                    && in_list
-                        .list
-                        .iter()
-                        .all(|expr| matches!(expr, Expr::Literal(..)))
-            }
-            _ => false,
-        }
+        expr_to_filter_expr(expr).is_some_and(|filter_expr| {
+            filter_expr
+                .filters()
+                .iter()
+                .all(|filter| partition_cols.contains(&filter.field))
+        })
     }
 
     fn is_exact_partition_equality_filter(expr: &Expr, partition_cols: &[String]) -> bool {
@@ -603,10 +618,47 @@ impl HudiDataSource {
         (partition_pushdown_exprs, all_pushdown_exprs)
     }
 
+    /// Checks if the scan reads the meta fields of a table that does not
+    /// populate them, by the projection or the filters.
+    fn reads_virtual_meta_fields(&self, projection: Option<&Vec<usize>>, filters: &[Expr]) -> bool {
+        let populates_meta_fields: bool = self
+            .table
+            .hudi_configs
+            .get_or_default(HudiTableConfig::PopulatesMetaFields)
+            .into();
+        if populates_meta_fields {
+            return false;
+        }
+        let is_meta_field = |name: &str| MetaField::from_str(name).is_ok();
+        let projects_meta_fields = match projection {
+            Some(indices) => indices
+                .iter()
+                .any(|&i| is_meta_field(self.schema.field(i).name())),
+            None => true,
+        };
+        projects_meta_fields
+            || filters.iter().any(|expr| {
+                expr.column_refs()
+                    .iter()
+                    .any(|column| is_meta_field(&column.name))
+            })
+    }
+
     fn use_parquet_source_without_file_slices(
         &self,
         read_options: &ReadOptions,
+        reads_virtual_meta_fields: bool,
     ) -> Result<Option<bool>> {
+        // Incremental queries read only the records of the commits in range,
+        // which needs the commit time filtering of `HudiScanExec`.
+        if self.is_incremental_query() {
+            return Ok(Some(false));
+        }
+        // The meta fields of the tables that do not populate them are
+        // synthesized by `HudiScanExec`.
+        if reads_virtual_meta_fields {
+            return Ok(Some(false));
+        }
         if self.table.is_mor()
             && !read_options
                 .is_read_optimized()
@@ -629,8 +681,17 @@ impl HudiDataSource {
         projection: Option<&Vec<usize>>,
         filters: &[Expr],
         limit: Option<usize>,
-        flat_slices: Vec<FileSlice>,
+        mut flat_slices: Vec<FileSlice>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        // Like DataFusion's `ListingTable`, an empty scan has no file groups,
+        // which would leave it without output partitions.
+        if flat_slices.is_empty() {
+            let projected_schema = project_schema(&self.schema(), projection)?;
+            return Ok(Arc::new(EmptyExec::new(projected_schema)));
+        }
+        self.load_column_stats(state, &mut flat_slices).await;
+        let sort_columns = self.clustering_sort_columns(&flat_slices).await;
+        let table_schema = self.schema();
         let input_partitions = self.get_input_partitions_for_scan(state);
         let file_slices =
             hudi_core::util::collection::split_into_chunks(flat_slices, input_partitions);
@@ -648,7 +709,16 @@ impl HudiDataSource {
                 let url = join_url_segments(&base_url, &[relative_path.as_str()])
                     .map_err(|e| external_error("Failed to join URL segments", e))?;
                 let size = f.base_file.file_metadata.as_ref().map_or(0, |m| m.size);
-                let partitioned_file = PartitionedFile::new(url.path(), size);
+                let mut partitioned_file = PartitionedFile::new(url.path(), size);
+                if let Some(stats) = file_slice_statistics(&f, &table_schema) {
+                    // The rows are filtered by the predicate of the Parquet source.
+                    let stats = if filters.is_empty() {
+                        stats
+                    } else {
+                        stats.to_inexact()
+                    };
+                    partitioned_file = partitioned_file.with_statistics(Arc::new(stats));
+                }
                 parquet_file_group_vec.push(partitioned_file);
             }
             parquet_file_groups.push(parquet_file_group_vec)
@@ -661,7 +731,6 @@ impl HudiDataSource {
             key_value_metadata: Default::default(),
             crypto: Default::default(),
         };
-        let table_schema = self.schema();
         let mut parquet_source = ParquetSource::new(table_schema.clone())
             .with_table_parquet_options(parquet_opts)
             .with_pushdown_filters(true)
@@ -674,36 +743,112 @@ impl HudiDataSource {
             parquet_source = parquet_source.with_predicate(predicate)
         }
 
+        // Per-partition statistics are merged from the file statistics, when
+        // the column stats of all files are loaded.
         let file_groups: Vec<FileGroup> = parquet_file_groups
             .into_iter()
-            .map(FileGroup::from)
+            .map(|files| {
+                let stats = files
+                    .iter()
+                    .map(|f| f.statistics.as_deref())
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|stats| Statistics::try_merge_iter(stats, &table_schema).ok());
+                let file_group = FileGroup::from(files);
+                match stats {
+                    Some(stats) => file_group.with_statistics(Arc::new(stats)),
+                    None => file_group,
+                }
+            })
             .collect();
+        let file_groups_stats = file_groups
+            .iter()
+            .map(|g| g.file_statistics(None))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|stats| Statistics::try_merge_iter(stats, &table_schema).ok());
 
         let mut fsc_builder = FileScanConfigBuilder::new(url, Arc::new(parquet_source))
             .with_file_groups(file_groups)
             .with_projection_indices(projection.cloned())?
             .with_limit(limit);
 
-        if let Some(stats) = &self.cached_stats {
+        // DataFusion keeps the ordering only for the file groups whose files
+        // are ordered by their statistics.
+        if let Some(ordering) = sort_columns_to_lex_ordering(&sort_columns, &table_schema) {
+            fsc_builder = fsc_builder.with_output_ordering(vec![ordering]);
+        }
+
+        if let Some(stats) = file_groups_stats.or_else(|| self.cached_stats.clone()) {
             // DataFusion's FileScanConfig stores unprojected table statistics
             // and applies the source projection inside partition_statistics().
-            fsc_builder = fsc_builder.with_statistics(stats.clone());
+            fsc_builder = fsc_builder.with_statistics(stats);
         }
 
         let fsc = fsc_builder.build();
         Ok(Arc::new(DataSourceExec::new(Arc::new(fsc))))
     }
 
+    /// Loads the column stats of the base files of the file slices from the
+    /// metadata table for the scan statistics, when statistics collection is
+    /// enabled for the session. The stats are left unknown on failures.
+    async fn load_column_stats(&self, state: &dyn Session, file_slices: &mut [FileSlice]) {
+        if !state.config_options().execution.collect_statistics {
+            return;
+        }
+        if let Err(e) = self.table.load_column_stats(file_slices).await {
+            warn!("Failed to load column stats of file slices: {e}");
+        }
+    }
+
+    /// Returns the columns that all the file slices are sorted by, from the
+    /// plans of the clusterings that wrote their base files. Empty if any file
+    /// slice is not sorted, or the plans fail to load.
+    async fn clustering_sort_columns(&self, file_slices: &[FileSlice]) -> Vec<String> {
+        // Clusterings complete as replacecommits; skip loading the plans
+        // when there is none or a file slice cannot be sorted.
+        if file_slices.is_empty()
+            || file_slices.iter().any(|f| f.has_log_file())
+            || !self
+                .table
+                .timeline
+                .completed_commits
+                .iter()
+                .any(|i| i.is_replacecommit())
+        {
+            return Vec::new();
+        }
+        match self.table.timeline.get_clustering_sort_columns().await {
+            Ok(sort_columns) => clustering_sort_columns(file_slices, &sort_columns),
+            Err(e) => {
+                warn!("Failed to load the sort columns of clusterings: {e}");
+                Vec::new()
+            }
+        }
+    }
+
     async fn scan_hudi(
         &self,
+        state: &dyn Session,
         projection: Option<&Vec<usize>>,
         limit: Option<usize>,
         input_partitions: usize,
         flat_slices: Vec<FileSlice>,
         read_options: ReadOptions,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let file_slices =
-            hudi_core::util::collection::split_into_chunks(flat_slices, input_partitions);
+        let sort_columns = self.clustering_sort_columns(&flat_slices).await;
+
+        // Like DataFusion's `ListingTable`, file slices are grouped by partition
+        // to declare hash partitioning by the partition columns when there are
+        // at least `preserve_file_partitions` partitions to read.
+        let partition_columns = self.get_partition_columns();
+        let threshold = state.config_options().optimizer.preserve_file_partitions;
+        let group_by_partition = threshold > 0
+            && !partition_columns.is_empty()
+            && count_partition_paths(&flat_slices) >= threshold;
+        let file_slices = if group_by_partition {
+            group_by_partition_path(flat_slices, input_partitions)
+        } else {
+            hudi_core::util::collection::split_into_chunks(flat_slices, input_partitions)
+        };
 
         // The reader is built with the caller's full options so table-level
         // setup sees the same query context. Dropped partition filters are only
@@ -725,7 +870,7 @@ impl HudiDataSource {
             hudi_read_options = hudi_read_options.with_projection(col_names);
         }
 
-        Ok(Arc::new(HudiScanExec::new(
+        let mut exec = HudiScanExec::new(
             file_slices,
             file_group_reader,
             hudi_read_options,
@@ -734,7 +879,113 @@ impl HudiDataSource {
             self.schema.clone(),
             projection.cloned(),
             limit,
-        )))
+        )
+        .with_sort_columns(sort_columns);
+        if group_by_partition {
+            exec = exec.with_partition_columns(partition_columns);
+        }
+        if !self.partition_schema.fields().is_empty() {
+            match PartitionPruner::new(&[], &self.partition_schema, &self.table.hudi_configs) {
+                Ok(pruner) => exec = exec.with_partition_pruner(pruner),
+                Err(e) => warn!("Failed to create partition pruner for runtime filters: {e}"),
+            }
+        }
+        Ok(Arc::new(exec))
+    }
+
+    fn is_incremental_query(&self) -> bool {
+        matches!(
+            self.query_options.query_type(),
+            Ok(HudiQueryType::Incremental)
+        )
+    }
+
+    fn drops_partition_columns(&self) -> bool {
+        self.table
+            .hudi_configs
+            .get_or_default(HudiTableConfig::DropsPartitionFields)
+            .into()
+    }
+
+    /// Reloads the table for the DML statement, so that the rows are matched
+    /// against its latest snapshot, which is always read with the log files
+    /// merged.
+    async fn reload_for_dml(&self, statement: &str) -> Result<Self> {
+        // The rows cannot be matched nor written back without the partition fields.
+        if self.drops_partition_columns() {
+            return Err(Execution(format!(
+                "{statement} is not supported for Hudi tables with {} enabled",
+                HudiTableConfig::DropsPartitionFields.as_ref()
+            )));
+        }
+        if self.is_incremental_query() || self.query_options.as_of_timestamp().is_some() {
+            return Err(Execution(format!(
+                "{statement} is not supported for incremental or time travel queries of Hudi tables"
+            )));
+        }
+        let options = self
+            .table
+            .storage_options()
+            .into_iter()
+            .chain(self.table.hudi_options())
+            .chain([(
+                UseReadOptimizedMode.as_ref().to_string(),
+                "false".to_string(),
+            )]);
+        Self::new_with_options(self.table.base_url().as_str(), options).await
+    }
+
+    /// Plans the scan of the rows matching the filters for `DELETE` and
+    /// `UPDATE` statements, returning the table reloaded for the scan.
+    ///
+    /// The filters are pushed down to prune the file slices, and then
+    /// evaluated exactly on the scanned rows. The partition filters of
+    /// partitioned tables are only passed here with the [`HudiDmlFilterRule`].
+    async fn scan_matching_rows(
+        &self,
+        state: &dyn Session,
+        statement: &str,
+        filters: &[Expr],
+    ) -> Result<(Self, Arc<dyn ExecutionPlan>)> {
+        if !self.get_partition_columns().is_empty() {
+            dml::ensure_dml_filter_rule(state, statement)?;
+        }
+        let source = self.reload_for_dml(statement).await?;
+        let scan = source.scan(state, None, filters, None).await?;
+        let Some(predicate) = conjunction(filters.iter().cloned()) else {
+            return Ok((source, scan));
+        };
+        let df_schema = DFSchema::try_from(source.schema.clone())?;
+        let predicate = create_physical_expr(&predicate, &df_schema, state.execution_props())?;
+        Ok((source, Arc::new(FilterExec::try_new(predicate, scan)?)))
+    }
+
+    /// Checks that the columns assigned by an `UPDATE` statement can be
+    /// updated; the meta fields, record key fields and partition fields identify
+    /// the records and cannot change.
+    fn validate_update_assignments(&self, assignments: &[(String, Expr)]) -> Result<()> {
+        let record_key_fields: Vec<String> = self
+            .table
+            .hudi_configs
+            .get_or_default(RecordKeyFields)
+            .into();
+        let partition_cols = self.get_partition_columns();
+        for (column, _) in assignments {
+            if self.schema.field_with_name(column).is_err() {
+                return Err(Execution(format!(
+                    "Column '{column}' does not exist in the Hudi table"
+                )));
+            }
+            if MetaField::from_str(column).is_ok()
+                || record_key_fields.contains(column)
+                || partition_cols.contains(column)
+            {
+                return Err(Execution(format!(
+                    "Column '{column}' identifies the records of the Hudi table and cannot be updated"
+                )));
+            }
+        }
+        Ok(())
     }
 
     fn get_input_partitions_for_scan(&self, state: &dyn Session) -> usize {
@@ -812,14 +1063,18 @@ impl TableProvider for HudiDataSource {
 
         let read_optimized = self.effective_read_optimized();
         let partition_read_options =
-            self.scan_read_options(partition_pushdown_filters.clone(), read_optimized)?;
+            self.scan_read_options(partition_pushdown_filters.clone(), read_optimized);
         let all_read_options = if all_filters_are_partition_filters {
             partition_read_options.clone()
         } else {
-            self.scan_read_options(all_pushdown_filters, read_optimized)?
+            self.scan_read_options(all_pushdown_filters, read_optimized)
         };
 
-        match self.use_parquet_source_without_file_slices(&partition_read_options)? {
+        let reads_virtual_meta_fields = self.reads_virtual_meta_fields(projection, filters);
+        match self.use_parquet_source_without_file_slices(
+            &partition_read_options,
+            reads_virtual_meta_fields,
+        )? {
             Some(true) => {
                 let flat_slices = self
                     .table
@@ -830,12 +1085,14 @@ impl TableProvider for HudiDataSource {
                     .await
             }
             Some(false) => {
-                let flat_slices = self
+                let mut flat_slices = self
                     .table
                     .get_file_slices(&all_read_options)
                     .await
                     .map_err(|e| external_error("Failed to get file slices from Hudi table", e))?;
+                self.load_column_stats(state, &mut flat_slices).await;
                 self.scan_hudi(
+                    state,
                     projection,
                     limit,
                     input_partitions,
@@ -855,7 +1112,7 @@ impl TableProvider for HudiDataSource {
                     self.scan_parquet(state, projection, filters, limit, partition_flat_slices)
                         .await
                 } else {
-                    let flat_slices = if all_filters_are_partition_filters {
+                    let mut flat_slices = if all_filters_are_partition_filters {
                         partition_flat_slices
                     } else {
                         self.table
@@ -865,7 +1122,9 @@ impl TableProvider for HudiDataSource {
                                 external_error("Failed to get file slices from Hudi table", e)
                             })?
                     };
+                    self.load_column_stats(state, &mut flat_slices).await;
                     self.scan_hudi(
+                        state,
                         projection,
                         limit,
                         input_partitions,
@@ -878,6 +1137,86 @@ impl TableProvider for HudiDataSource {
         }
     }
 
+    /// Builds the plan writing the input records to this Hudi table as a
+    /// single commit; see [`HudiDataSink`] for the operations.
+    ///
+    /// The input may leave out the meta fields, e.g.,
+    /// `INSERT INTO t (id, name) VALUES ...`, as they are populated on write.
+    async fn insert_into(
+        &self,
+        _state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.schema
+            .logically_equivalent_names_and_types(&input.schema())?;
+        let sink = HudiDataSink::new(
+            self.table.clone(),
+            self.schema.clone(),
+            WriteOp::Insert(insert_op),
+        );
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+
+    /// Builds the plan deleting the rows matching the filters from this Hudi
+    /// table as a single commit; empty filters delete all rows.
+    ///
+    /// The matching rows are deleted by their record keys, which appends
+    /// delete blocks to merge-on-read tables and rewrites the base files of
+    /// copy-on-write tables.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (source, input) = self.scan_matching_rows(state, "DELETE", &filters).await?;
+        let sink = HudiDataSink::new(source.table, self.schema.clone(), WriteOp::Delete);
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+
+    /// Builds the plan updating the rows matching the filters of this Hudi
+    /// table as a single commit; empty filters update all rows.
+    ///
+    /// The assignments are evaluated on the matching rows, and the new rows
+    /// are written by their record keys, which appends data blocks to
+    /// merge-on-read tables and rewrites the base files of copy-on-write
+    /// tables. The record key and partition fields cannot be updated.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.validate_update_assignments(&assignments)?;
+        let (source, input) = self.scan_matching_rows(state, "UPDATE", &filters).await?;
+
+        let df_schema = DFSchema::try_from(self.schema.clone())?;
+        let mut assignments: HashMap<String, Expr> = assignments.into_iter().collect();
+        let exprs = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let expr = match assignments.remove(field.name()) {
+                    Some(expr) => expr.cast_to(field.data_type(), &df_schema)?,
+                    None => col(field.name()),
+                };
+                let expr = create_physical_expr(&expr, &df_schema, state.execution_props())?;
+                Ok((expr, field.name().clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let input = Arc::new(ProjectionExec::try_new(exprs, input)?);
+
+        let sink = HudiDataSink::new(source.table, self.schema.clone(), WriteOp::Update);
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        MetaField::from_str(column)
+            .is_ok()
+            .then_some(&*META_FIELD_DEFAULT)
+    }
+
     /// Reports partition equality predicates as `Exact`; all other pushed
     /// filters are `Inexact` so DataFusion retains a residual `FilterExec`.
     /// `scan()` splits conjunctions before converting them to Hudi filters, so
@@ -901,6 +1240,38 @@ impl TableProvider for HudiDataSource {
     }
 }
 
+/// Adds the Hudi extensions to the session being built: the
+/// [`HudiTableFactory`] for `CREATE EXTERNAL TABLE ... STORED AS HUDI`, and
+/// the [`HudiDmlFilterRule`] that `DELETE` and `UPDATE` statements on
+/// partitioned Hudi tables require.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use datafusion::execution::SessionStateBuilder;
+/// use datafusion::prelude::SessionContext;
+/// use hudi_datafusion::with_hudi;
+///
+/// # async fn example() -> datafusion::error::Result<()> {
+/// let state = with_hudi(SessionStateBuilder::new().with_default_features()).build();
+/// let ctx = SessionContext::new_with_state(state);
+/// ctx.sql("CREATE EXTERNAL TABLE trips STORED AS HUDI LOCATION '/tmp/trips_table'")
+///     .await?;
+/// ctx.sql("DELETE FROM trips WHERE city = 'sf'")
+///     .await?
+///     .collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn with_hudi(mut builder: SessionStateBuilder) -> SessionStateBuilder {
+    builder
+        .table_factories()
+        .get_or_insert_with(HashMap::new)
+        .insert("HUDI".to_string(), Arc::new(HudiTableFactory::new()));
+    builder.with_optimizer_rule(Arc::new(HudiDmlFilterRule::new()))
+}
+
 /// `HudiTableFactory` is responsible for creating and configuring Hudi tables.
 ///
 /// This factory handles the initialization of Hudi tables by creating configuration
@@ -988,6 +1359,7 @@ mod tests {
     use datafusion_common::{Column, ScalarValue};
     use hudi_core::config::internal::HudiInternalConfig;
     use hudi_core::config::table::{BaseFileFormatValue, HudiTableConfig};
+    use hudi_core::expr::filter::Filter;
     use std::fs::canonicalize;
     use std::path::Path;
     use url::Url;
@@ -1041,6 +1413,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_new_with_options_sets_query_options() {
+        let hudi = HudiDataSource::new_with_options(
+            V6Nonpartitioned.path_to_cow().as_str(),
+            [
+                (QueryType.as_ref(), "incremental"),
+                (StartTimestamp.as_ref(), "0"),
+                (InputPartitions.as_ref(), "2"),
+            ],
+        )
+        .await
+        .unwrap();
+        assert!(hudi.is_incremental_query());
+        assert_eq!(hudi.query_options.start_timestamp(), Some("0"));
+        assert_eq!(hudi.query_options.hudi_options.len(), 2);
+
+        let result = HudiDataSource::new_with_options(
+            V6Nonpartitioned.path_to_cow().as_str(),
+            [(QueryType.as_ref(), "invalid")],
+        )
+        .await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains(QueryType.as_ref()), "{error}");
+    }
+
     #[test]
     fn test_file_slices_are_parquet_empty_is_false() {
         assert!(!HudiDataSource::file_slices_are_parquet(&[]).unwrap());
@@ -1125,6 +1522,7 @@ mod tests {
         exec.read_options()
             .filters
             .iter()
+            .flat_map(FilterExpr::filters)
             .map(|filter| {
                 (
                     filter.field.clone(),
@@ -1306,7 +1704,7 @@ mod tests {
         );
         assert_eq!(
             pushdown_support(&schema, &partition_cols, &or_expr),
-            TableProviderFilterPushDown::Unsupported
+            TableProviderFilterPushDown::Inexact
         );
     }
 
@@ -1339,11 +1737,13 @@ mod tests {
 
         let partition_fields = partition_filters
             .iter()
-            .map(|(field, _, _)| field.as_str())
+            .flat_map(FilterExpr::filters)
+            .map(|filter| filter.field.as_str())
             .collect::<Vec<_>>();
         let all_fields = all_filters
             .iter()
-            .map(|(field, _, _)| field.as_str())
+            .flat_map(FilterExpr::filters)
+            .map(|filter| filter.field.as_str())
             .collect::<Vec<_>>();
 
         assert_eq!(partition_fields, ["byteField", "byteField"]);
@@ -1368,7 +1768,7 @@ mod tests {
         let actual = HudiDataSource::read_options_for_hudi_exec(&hudi_configs, &read_options);
 
         assert_eq!(actual.filters.len(), 1);
-        assert_eq!(actual.filters[0].field, "amount");
+        assert_eq!(actual.filters[0].as_filter().unwrap().field, "amount");
         assert_eq!(actual.projection, read_options.projection);
         assert_eq!(actual.hudi_options, read_options.hudi_options);
     }
@@ -1378,16 +1778,15 @@ mod tests {
         let hudi = HudiDataSource::new(V6SimplekeygenNonhivestyle.url_to_mor_parquet().as_str())
             .await
             .unwrap();
-        let read_options = hudi
-            .scan_read_options(
-                vec![("id".to_string(), ">".to_string(), "1".to_string())],
-                false,
-            )
-            .unwrap();
+        let read_options = hudi.scan_read_options(
+            vec![Filter::try_from(("id", ">", "1")).unwrap().into()],
+            false,
+        );
         let flat_slices = hudi.table.get_file_slices(&read_options).await.unwrap();
 
+        let ctx = SessionContext::new();
         let plan = hudi
-            .scan_hudi(None, None, 1, flat_slices, read_options)
+            .scan_hudi(&ctx.state(), None, None, 1, flat_slices, read_options)
             .await
             .unwrap();
         let exec = plan
@@ -1396,7 +1795,7 @@ mod tests {
             .expect("MOR snapshot scan should use HudiScanExec");
 
         assert_eq!(exec.read_options().filters.len(), 1);
-        let filter = &exec.read_options().filters[0];
+        let filter = exec.read_options().filters[0].as_filter().unwrap();
         assert_eq!(filter.field, "id");
         assert_eq!(filter.values, vec!["1".to_string()]);
     }
@@ -0,0 +1,591 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Planning of `MERGE INTO` statements on Hudi tables, which DataFusion's SQL
+//! planner does not support.
+//!
+//! The statement is planned as a join of the target table and the source on
+//! the `ON` condition, evaluating the `WHEN` clauses in order for each joined
+//! row. The resulting inserts, updates and deletes are written to the target
+//! table as a single commit with [`HudiTable::merge`].
+
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::compute::filter_record_batch;
+use arrow_array::BooleanArray;
+use arrow_schema::SchemaRef;
+use async_trait::async_trait;
+use datafusion::common::{Column, JoinType, TableReference};
+use datafusion::dataframe::DataFrame;
+use datafusion::datasource::sink::{DataSink, DataSinkExec};
+use datafusion::execution::TaskContext;
+use datafusion::execution::context::SessionContext;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
+};
+use datafusion::sql::parser::Statement;
+use datafusion::sql::sqlparser::ast::{
+    AssignmentTarget, BinaryOperator, Expr as SqlExpr, ExprWithAlias, MergeAction, MergeClause,
+    MergeClauseKind, MergeInsertKind, ObjectName, Statement as SqlStatement, TableFactor,
+};
+use datafusion_common::DataFusionError::{Execution, NotImplemented, Plan};
+use datafusion_common::{DFSchema, Result};
+use datafusion_expr::{Expr, cast, lit, when};
+
+use crate::HudiDataSource;
+use crate::hudi_sink::HudiDataSink;
+use hudi_core::config::table::HudiTableConfig::RecordKeyFields;
+use hudi_core::metadata::meta_field::MetaField;
+use hudi_core::table::Table as HudiTable;
+
+/// Column marking the joined rows with a target row.
+const TARGET_ROW_COLUMN: &str = "__hudi_merge_target_row";
+/// Column marking the joined rows with a source row.
+const SOURCE_ROW_COLUMN: &str = "__hudi_merge_source_row";
+/// Column of the merged rows telling whether to delete or upsert the row.
+const DELETE_COLUMN: &str = "__hudi_merge_delete";
+
+/// Plans a `MERGE INTO` statement on a Hudi table registered in the session.
+///
+/// The returned plan writes the merged records to the target table as a
+/// single commit when executed, and produces the number of inserted, updated
+/// and deleted rows as a single `count` row, the same as DataFusion's DML
+/// statements.
+///
+/// The `ON` condition must match the record key fields of the target table,
+/// as the records are upserted and deleted by their keys, and the record key
+/// and partition fields cannot be updated. The clauses are evaluated in
+/// order, and the first one matching a joined row applies; the target rows
+/// matched by more than one source row are merged by the ordering field.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use datafusion::physical_plan::collect;
+/// use datafusion::prelude::SessionContext;
+/// use hudi_datafusion::{HudiDataSource, plan_merge_into};
+///
+/// # async fn example() -> datafusion::error::Result<()> {
+/// let ctx = SessionContext::new();
+/// let hudi = HudiDataSource::new("/tmp/trips_table").await?;
+/// ctx.register_table("trips", Arc::new(hudi))?;
+/// ctx.sql("CREATE TABLE updates AS VALUES (1, 'a', 'sf', 2)").await?;
+///
+/// let plan = plan_merge_into(
+///     &ctx,
+///     "MERGE INTO trips t USING updates s ON t.id = s.column1 \
+///      WHEN MATCHED THEN UPDATE SET name = s.column2, ts = s.column4 \
+///      WHEN NOT MATCHED THEN INSERT (id, name, city, ts) \
+///      VALUES (s.column1, s.column2, s.column3, s.column4)",
+/// )
+/// .await?;
+/// collect(plan, ctx.task_ctx()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn plan_merge_into(ctx: &SessionContext, sql: &str) -> Result<Arc<dyn ExecutionPlan>> {
+    let state = ctx.state();
+    let dialect = state.config_options().sql_parser.dialect;
+    let statement = match state.sql_to_statement(sql, &dialect)? {
+        Statement::Statement(statement) => *statement,
+        _ => return Err(Plan("Expected a MERGE INTO statement".to_string())),
+    };
+    let SqlStatement::Merge {
+        table,
+        source,
+        on,
+        clauses,
+        ..
+    } = statement
+    else {
+        return Err(Plan("Expected a MERGE INTO statement".to_string()));
+    };
+
+    let (target_name, target_alias) = match &table {
+        TableFactor::Table { name, alias, .. } => (
+            name.to_string(),
+            alias
+                .as_ref()
+                .map(|a| a.name.value.clone())
+                .unwrap_or_else(|| last_name_part(name)),
+        ),
+        _ => return Err(Plan(format!("MERGE INTO target must be a table: {table}"))),
+    };
+    let provider = ctx.table_provider(target_name.as_str()).await?;
+    let Some(hudi) = provider.as_any().downcast_ref::<HudiDataSource>() else {
+        return Err(Plan(format!(
+            "MERGE INTO target {target_name} is not a Hudi table"
+        )));
+    };
+    let record_key_fields: Vec<String> = hudi
+        .table
+        .hudi_configs
+        .get_or_default(RecordKeyFields)
+        .into();
+    let target = Arc::new(hudi.reload_for_dml("MERGE INTO").await?);
+
+    let (source_df, source_alias) = match &source {
+        TableFactor::Table { name, alias, .. } => (
+            ctx.table(name.to_string().as_str()).await?,
+            alias
+                .as_ref()
+                .map(|a| a.name.value.clone())
+                .unwrap_or_else(|| last_name_part(name)),
+        ),
+        TableFactor::Derived {
+            subquery,
+            alias: Some(alias),
+            ..
+        } => (
+            ctx.sql(&subquery.to_string()).await?,
+            alias.name.value.clone(),
+        ),
+        _ => {
+            return Err(Plan(format!(
+                "MERGE INTO source must be a table or an aliased subquery: {source}"
+            )));
+        }
+    };
+    validate_merge_condition(&on, &target_alias, &source_alias, &record_key_fields)?;
+    let target_df = ctx
+        .read_table(target.clone())?
+        .with_column(TARGET_ROW_COLUMN, lit(true))?
+        .alias(&target_alias)?;
+    let source_df = source_df
+        .with_column(SOURCE_ROW_COLUMN, lit(true))?
+        .alias(&source_alias)?;
+    let joined_schema = target_df.schema().join(source_df.schema())?;
+
+    let join_type = if clauses
+        .iter()
+        .any(|c| c.clause_kind == MergeClauseKind::NotMatchedBySource)
+    {
+        JoinType::Full
+    } else {
+        JoinType::Right
+    };
+    let on = state.create_logical_expr_from_sql_expr(
+        ExprWithAlias {
+            expr: *on,
+            alias: None,
+        },
+        &joined_schema,
+    )?;
+    let joined = target_df.join_on(source_df, join_type, [on])?;
+
+    let planner = MergePlanner {
+        ctx,
+        hudi: target.as_ref(),
+        schema: &joined_schema,
+        target_alias: &target_alias,
+        source_alias: &source_alias,
+    };
+    let merged = planner.merge_rows(joined, clauses)?;
+    let input = merged.create_physical_plan().await?;
+    let sink = HudiMergeSink::new(target.table.clone(), input.schema());
+    Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+}
+
+/// The last part of a possibly qualified table name, which qualifies its
+/// columns without an alias.
+fn last_name_part(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .and_then(|part| part.as_ident())
+        .map(|ident| ident.value.clone())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Checks that the `ON` condition is exactly a conjunction of equalities of
+/// the record key fields of the target table to columns of the source, e.g.,
+/// `t.id = s.id`, one for each record key field. The records are matched by
+/// their keys, so the joined rows must be too.
+fn validate_merge_condition(
+    on: &SqlExpr,
+    target_alias: &str,
+    source_alias: &str,
+    record_key_fields: &[String],
+) -> Result<()> {
+    let column_of = |expr: &SqlExpr, alias: &str| match expr {
+        SqlExpr::CompoundIdentifier(idents) => match idents.as_slice() {
+            [table, column] if table.value == alias => Some(column.value.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let mut conjuncts = Vec::new();
+    split_sql_conjunction(on, &mut conjuncts);
+
+    let mut matched_keys = Vec::with_capacity(conjuncts.len());
+    for conjunct in conjuncts {
+        let target_key = match conjunct {
+            SqlExpr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            } => [(left, right), (right, left)]
+                .into_iter()
+                .find_map(|(target, source)| {
+                    column_of(source, source_alias)?;
+                    column_of(target, target_alias)
+                }),
+            _ => None,
+        };
+        match target_key {
+            Some(key) if record_key_fields.contains(&key) && !matched_keys.contains(&key) => {
+                matched_keys.push(key)
+            }
+            _ => {
+                return Err(Plan(format!(
+                    "MERGE INTO condition must only equate the record key fields of the Hudi \
+                     table to source columns, e.g., {target_alias}.key = {source_alias}.key, \
+                     but found: {conjunct}"
+                )));
+            }
+        }
+    }
+    if matched_keys.len() != record_key_fields.len() {
+        return Err(Plan(format!(
+            "MERGE INTO condition must match the record key fields of the Hudi table: {}",
+            record_key_fields.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Collects the conjuncts of the `AND`-ed SQL expression.
+fn split_sql_conjunction<'a>(expr: &'a SqlExpr, conjuncts: &mut Vec<&'a SqlExpr>) {
+    match expr {
+        SqlExpr::Nested(expr) => split_sql_conjunction(expr, conjuncts),
+        SqlExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            split_sql_conjunction(left, conjuncts);
+            split_sql_conjunction(right, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
+
+/// Evaluates the `WHEN` clauses of a `MERGE INTO` statement on the joined
+/// rows of the target table and the source.
+struct MergePlanner<'a> {
+    ctx: &'a SessionContext,
+    hudi: &'a HudiDataSource,
+    schema: &'a DFSchema,
+    target_alias: &'a str,
+    source_alias: &'a str,
+}
+
+impl MergePlanner<'_> {
+    /// Project the joined rows to the merged rows of the data columns and
+    /// [`DELETE_COLUMN`], leaving out the rows matching no clause.
+    fn merge_rows(&self, joined: DataFrame, clauses: Vec<MergeClause>) -> Result<DataFrame> {
+        let data_fields: Vec<_> = self
+            .hudi
+            .schema
+            .fields()
+            .iter()
+            .filter(|field| MetaField::from_str(field.name()).is_err())
+            .cloned()
+            .collect();
+
+        let mut conditions = Vec::with_capacity(clauses.len());
+        let mut column_values: Vec<Vec<Expr>> = vec![Vec::new(); data_fields.len()];
+        let mut deletes = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            let mut condition = self.clause_kind_condition(&clause.clause_kind);
+            if let Some(predicate) = clause.predicate {
+                condition = condition.and(self.to_expr(predicate)?);
+            }
+            conditions.push(condition);
+            let values = match clause.action {
+                MergeAction::Insert(insert) => {
+                    if !matches!(
+                        clause.clause_kind,
+                        MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget
+                    ) {
+                        return Err(Plan(
+                            "INSERT is only allowed in WHEN NOT MATCHED clauses".to_string(),
+                        ));
+                    }
+                    deletes.push(false);
+                    self.insert_values(&data_fields, insert.columns, insert.kind)?
+                }
+                MergeAction::Update { assignments } => {
+                    if !matches!(
+                        clause.clause_kind,
+                        MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource
+                    ) {
+                        return Err(Plan(
+                            "UPDATE is only allowed in WHEN MATCHED clauses".to_string(),
+                        ));
+                    }
+                    let mut updated = Vec::with_capacity(assignments.len());
+                    for assignment in assignments {
+                        let AssignmentTarget::ColumnName(name) = assignment.target else {
+                            return Err(NotImplemented(
+                                "Assigning to tuples in MERGE INTO is not supported".to_string(),
+                            ));
+                        };
+                        updated.push((last_name_part(&name), self.to_expr(assignment.value)?));
+                    }
+                    self.hudi.validate_update_assignments(&updated)?;
+                    deletes.push(false);
+                    data_fields
+                        .iter()
+                        .map(|field| {
+                            match updated.iter().find(|(column, _)| column == field.name()) {
+                                Some((_, value)) => value.clone(),
+                                None => self.target_column(field.name()),
+                            }
+                        })
+                        .collect()
+                }
+                MergeAction::Delete => {
+                    if clause.clause_kind != MergeClauseKind::Matched
+                        && clause.clause_kind != MergeClauseKind::NotMatchedBySource
+                    {
+                        return Err(Plan(
+                            "DELETE is only allowed in WHEN MATCHED clauses".to_string(),
+                        ));
+                    }
+                    deletes.push(true);
+                    // Deleting with the ordering values of the target rows.
+                    data_fields
+                        .iter()
+                        .map(|field| self.target_column(field.name()))
+                        .collect()
+                }
+            };
+            for (values, value) in column_values.iter_mut().zip(values) {
+                values.push(value);
+            }
+        }
+        if conditions.is_empty() {
+            return Err(Plan(
+                "MERGE INTO requires at least one WHEN clause".to_string(),
+            ));
+        }
+
+        let mut exprs = Vec::with_capacity(data_fields.len() + 1);
+        for (field, values) in data_fields.iter().zip(column_values) {
+            let mut case = when(conditions[0].clone(), values[0].clone());
+            for (condition, value) in conditions.iter().zip(&values).skip(1) {
+                case = case.when(condition.clone(), value.clone());
+            }
+            // Casting explicitly as the type of the values is only known after
+            // type coercion.
+            exprs.push(cast(case.end()?, field.data_type().clone()).alias(field.name()));
+        }
+        let mut delete = when(conditions[0].clone(), lit(deletes[0]));
+        for (condition, is_delete) in conditions.iter().zip(&deletes).skip(1) {
+            delete = delete.when(condition.clone(), lit(*is_delete));
+        }
+        exprs.push(delete.end()?.alias(DELETE_COLUMN));
+
+        joined
+            .select(exprs)?
+            .filter(Expr::Column(Column::from_name(DELETE_COLUMN)).is_not_null())
+    }
+
+    /// The values of the data columns inserted by an `INSERT` clause; the
+    /// columns not listed are inserted as nulls, and `INSERT ROW` inserts the
+    /// source columns of the same names.
+    fn insert_values(
+        &self,
+        data_fields: &[arrow_schema::FieldRef],
+        columns: Vec<datafusion::sql::sqlparser::ast::Ident>,
+        kind: MergeInsertKind,
+    ) -> Result<Vec<Expr>> {
+        let values = match kind {
+            MergeInsertKind::Values(values) => {
+                let [row] = <[Vec<SqlExpr>; 1]>::try_from(values.rows).map_err(|_| {
+                    Plan("INSERT in MERGE INTO must have one row of values".to_string())
+                })?;
+                row.into_iter()
+                    .map(|value| self.to_expr(value))
+                    .collect::<Result<Vec<_>>>()?
+            }
+            MergeInsertKind::Row => data_fields
+                .iter()
+                .map(|field| {
+                    let column =
+                        Column::new(Some(TableReference::bare(self.source_alias)), field.name());
+                    if !self.schema.has_column(&column) {
+                        return Err(Plan(format!(
+                            "INSERT ROW in MERGE INTO requires source column {column}"
+                        )));
+                    }
+                    Ok(Expr::Column(column))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+        let columns: Vec<String> = if columns.is_empty() {
+            data_fields.iter().map(|f| f.name().clone()).collect()
+        } else {
+            columns.into_iter().map(|c| c.value).collect()
+        };
+        if columns.len() != values.len() {
+            return Err(Plan(format!(
+                "INSERT in MERGE INTO has {} columns but {} values",
+                columns.len(),
+                values.len()
+            )));
+        }
+        if let Some(column) = columns
+            .iter()
+            .find(|c| !data_fields.iter().any(|f| f.name() == *c))
+        {
+            return Err(Execution(format!(
+                "Column '{column}' does not exist in the Hudi table"
+            )));
+        }
+        Ok(data_fields
+            .iter()
+            .map(
+                |field| match columns.iter().position(|column| column == field.name()) {
+                    Some(i) => values[i].clone(),
+                    None => lit(datafusion_common::ScalarValue::Null),
+                },
+            )
+            .collect())
+    }
+
+    /// Whether a joined row is of the kind of a `WHEN` clause.
+    fn clause_kind_condition(&self, kind: &MergeClauseKind) -> Expr {
+        let target_row = Expr::Column(Column::new(
+            Some(TableReference::bare(self.target_alias)),
+            TARGET_ROW_COLUMN,
+        ));
+        let source_row = Expr::Column(Column::new(
+            Some(TableReference::bare(self.source_alias)),
+            SOURCE_ROW_COLUMN,
+        ));
+        match kind {
+            MergeClauseKind::Matched => target_row.is_not_null().and(source_row.is_not_null()),
+            MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget => {
+                target_row.is_null()
+            }
+            MergeClauseKind::NotMatchedBySource => source_row.is_null(),
+        }
+    }
+
+    fn target_column(&self, name: &str) -> Expr {
+        Expr::Column(Column::new(
+            Some(TableReference::bare(self.target_alias)),
+            name,
+        ))
+    }
+
+    fn to_expr(&self, expr: SqlExpr) -> Result<Expr> {
+        self.ctx
+            .state()
+            .create_logical_expr_from_sql_expr(ExprWithAlias { expr, alias: None }, self.schema)
+    }
+}
+
+/// DataFusion data sink that writes the merged rows to a Hudi table as a
+/// single commit with [`HudiTable::merge`], upserting or deleting each row by
+/// [`DELETE_COLUMN`]. The merged rows are buffered in memory as by
+/// [`HudiDataSink`].
+#[derive(Debug)]
+pub struct HudiMergeSink {
+    table: Arc<HudiTable>,
+    schema: SchemaRef,
+}
+
+impl HudiMergeSink {
+    pub fn new(table: Arc<HudiTable>, schema: SchemaRef) -> Self {
+        Self { table, schema }
+    }
+}
+
+impl DisplayAs for HudiMergeSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "HudiMergeSink: base_url={}", self.table.base_url())
+            }
+            _ => {
+                write!(f, "HudiMergeSink")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for HudiMergeSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let batches = HudiDataSink::collect_input(&self.table, data).await?;
+        let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        if num_rows == 0 {
+            return Ok(0);
+        }
+
+        let mut upserts = Vec::with_capacity(batches.len());
+        let mut deletes = Vec::with_capacity(batches.len());
+        for batch in batches {
+            let is_delete = batch
+                .column_by_name(DELETE_COLUMN)
+                .and_then(|c| c.as_any().downcast_ref::<BooleanArray>())
+                .ok_or_else(|| Execution(format!("Merged rows have no {DELETE_COLUMN} column")))?
+                .clone();
+            deletes.push(filter_record_batch(&batch, &is_delete)?);
+            upserts.push(filter_record_batch(
+                &batch,
+                &arrow::compute::not(&is_delete)?,
+            )?);
+        }
+
+        let table = HudiDataSink::load_table(&self.table).await?;
+        let data_schema = SchemaRef::from(
+            table
+                .get_schema()
+                .await
+                .map_err(|e| crate::external_error("Failed to get Hudi table schema", e))?,
+        );
+        let upserts = HudiDataSink::to_data_records(&data_schema, upserts)?;
+        let deletes = HudiDataSink::to_data_records(&data_schema, deletes)?;
+        table
+            .merge(&upserts, &deletes)
+            .await
+            .map_err(|e| crate::external_error("Failed to merge into Hudi table", e))?;
+
+        Ok(num_rows as u64)
+    }
+}
@@ -0,0 +1,589 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! Table functions for querying Hudi tables ad hoc in SQL, without
+//! registering a table for each query, and for inspecting their timeline
+//! and file layout.
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, LazyLock};
+
+use arrow_array::builder::{Int64Builder, ListBuilder, StringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use datafusion::catalog::TableFunctionImpl;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::execution::context::SessionContext;
+use datafusion_common::DataFusionError;
+use datafusion_common::DataFusionError::Plan;
+use datafusion_common::Result;
+use datafusion_expr::Expr;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::{HudiDataSource, external_error};
+use hudi_core::table::{QueryType, ReadOptions, Table as HudiTable};
+use hudi_core::timeline::instant::State;
+
+/// Name of the table function of [`HudiTableChangesFunction`].
+pub const TABLE_CHANGES_FUNCTION: &str = "hudi_table_changes";
+/// Name of the table function of [`HudiSnapshotAsOfFunction`].
+pub const SNAPSHOT_AS_OF_FUNCTION: &str = "hudi_snapshot_as_of";
+/// Name of the table function of [`HudiTimelineFunction`].
+pub const TIMELINE_FUNCTION: &str = "hudi_timeline";
+/// Name of the table function of [`HudiFileSlicesFunction`].
+pub const FILE_SLICES_FUNCTION: &str = "hudi_file_slices";
+/// Name of the table function of [`HudiCommitStatsFunction`].
+pub const COMMIT_STATS_FUNCTION: &str = "hudi_commit_stats";
+
+static TIMELINE_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::Utf8, false),
+        Field::new("action", DataType::Utf8, false),
+        Field::new("state", DataType::Utf8, false),
+        Field::new("completion_timestamp", DataType::Utf8, true),
+        Field::new("epoch_millis", DataType::Int64, false),
+    ]))
+});
+
+static FILE_SLICES_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("partition_path", DataType::Utf8, false),
+        Field::new("file_id", DataType::Utf8, false),
+        Field::new("commit_timestamp", DataType::Utf8, false),
+        Field::new("base_file", DataType::Utf8, false),
+        Field::new("base_file_size", DataType::UInt64, true),
+        Field::new("base_file_num_records", DataType::Int64, true),
+        Field::new(
+            "log_files",
+            DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true))),
+            false,
+        ),
+        Field::new("log_files_size", DataType::UInt64, false),
+        Field::new("total_size", DataType::UInt64, false),
+    ]))
+});
+
+static COMMIT_STATS_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::Utf8, false),
+        Field::new("action", DataType::Utf8, false),
+        Field::new("operation_type", DataType::Utf8, true),
+        Field::new("partition_path", DataType::Utf8, false),
+        Field::new("num_files", DataType::Int64, false),
+        Field::new("num_writes", DataType::Int64, false),
+        Field::new("num_inserts", DataType::Int64, false),
+        Field::new("num_update_writes", DataType::Int64, false),
+        Field::new("num_deletes", DataType::Int64, false),
+        Field::new("total_write_bytes", DataType::Int64, false),
+        Field::new("total_write_errors", DataType::Int64, false),
+    ]))
+});
+
+/// Registers the Hudi table functions with the session.
+///
+/// The `options` are passed to every Hudi table loaded by the functions,
+/// e.g., the storage options for accessing the tables.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use datafusion::prelude::SessionContext;
+/// use hudi_core::config::util::empty_options;
+/// use hudi_datafusion::register_table_functions;
+///
+/// # async fn example() -> datafusion::error::Result<()> {
+/// let ctx = SessionContext::new();
+/// register_table_functions(&ctx, empty_options());
+/// ctx.sql(
+///     "SELECT * FROM hudi_table_changes('/tmp/trips_table', '20240101000000000', '20240201000000000')",
+/// )
+/// .await?
+/// .show()
+/// .await?;
+/// ctx.sql("SELECT * FROM hudi_snapshot_as_of('/tmp/trips_table', '2024-01-01T00:00:00Z')")
+///     .await?
+///     .show()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn register_table_functions<I, K, V>(ctx: &SessionContext, options: I)
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let options: HashMap<String, String> = options
+        .into_iter()
+        .map(|(k, v)| (k.as_ref().to_string(), v.into()))
+        .collect();
+    ctx.register_udtf(
+        TABLE_CHANGES_FUNCTION,
+        Arc::new(HudiTableChangesFunction::new_with_options(options.clone())),
+    );
+    ctx.register_udtf(
+        SNAPSHOT_AS_OF_FUNCTION,
+        Arc::new(HudiSnapshotAsOfFunction::new_with_options(options.clone())),
+    );
+    ctx.register_udtf(
+        TIMELINE_FUNCTION,
+        Arc::new(HudiTimelineFunction::new_with_options(options.clone())),
+    );
+    ctx.register_udtf(
+        FILE_SLICES_FUNCTION,
+        Arc::new(HudiFileSlicesFunction::new_with_options(options.clone())),
+    );
+    ctx.register_udtf(
+        COMMIT_STATS_FUNCTION,
+        Arc::new(HudiCommitStatsFunction::new_with_options(options)),
+    );
+}
+
+/// Table function `hudi_table_changes(base_uri, start_timestamp [, end_timestamp])`
+/// for incremental queries, reading the records changed by the commits after
+/// `start_timestamp` up to `end_timestamp`, or the latest commit if omitted.
+#[derive(Debug, Default)]
+pub struct HudiTableChangesFunction {
+    options: HashMap<String, String>,
+}
+
+impl HudiTableChangesFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_options(options: HashMap<String, String>) -> Self {
+        Self { options }
+    }
+}
+
+impl TableFunctionImpl for HudiTableChangesFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let (base_uri, start, end) = match args {
+            [base_uri, start] => (base_uri, start, None),
+            [base_uri, start, end] => (base_uri, start, Some(end)),
+            _ => {
+                return Err(Plan(format!(
+                    "{TABLE_CHANGES_FUNCTION} expects arguments (base_uri, start_timestamp [, end_timestamp])"
+                )));
+            }
+        };
+        let base_uri = literal_arg(TABLE_CHANGES_FUNCTION, "base_uri", base_uri)?;
+        let mut read_options = ReadOptions::new()
+            .with_query_type(QueryType::Incremental)
+            .with_start_timestamp(literal_arg(
+                TABLE_CHANGES_FUNCTION,
+                "start_timestamp",
+                start,
+            )?);
+        if let Some(end) = end {
+            read_options = read_options.with_end_timestamp(literal_arg(
+                TABLE_CHANGES_FUNCTION,
+                "end_timestamp",
+                end,
+            )?);
+        }
+        create_data_source(&base_uri, &self.options, read_options)
+    }
+}
+
+/// Table function `hudi_snapshot_as_of(base_uri, timestamp)` for time travel
+/// queries, reading the snapshot of the table as of the commit at or before
+/// `timestamp`.
+#[derive(Debug, Default)]
+pub struct HudiSnapshotAsOfFunction {
+    options: HashMap<String, String>,
+}
+
+impl HudiSnapshotAsOfFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_options(options: HashMap<String, String>) -> Self {
+        Self { options }
+    }
+}
+
+impl TableFunctionImpl for HudiSnapshotAsOfFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [base_uri, timestamp] = args else {
+            return Err(Plan(format!(
+                "{SNAPSHOT_AS_OF_FUNCTION} expects arguments (base_uri, timestamp)"
+            )));
+        };
+        let base_uri = literal_arg(SNAPSHOT_AS_OF_FUNCTION, "base_uri", base_uri)?;
+        let read_options = ReadOptions::new()
+            .with_query_type(QueryType::Snapshot)
+            .with_as_of_timestamp(literal_arg(
+                SNAPSHOT_AS_OF_FUNCTION,
+                "timestamp",
+                timestamp,
+            )?);
+        create_data_source(&base_uri, &self.options, read_options)
+    }
+}
+
+/// Table function `hudi_timeline(base_uri)` listing the instants of all
+/// actions and states on the active timeline, in the order of their
+/// timestamps.
+#[derive(Debug, Default)]
+pub struct HudiTimelineFunction {
+    options: HashMap<String, String>,
+}
+
+impl HudiTimelineFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_options(options: HashMap<String, String>) -> Self {
+        Self { options }
+    }
+}
+
+impl TableFunctionImpl for HudiTimelineFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [base_uri] = args else {
+            return Err(Plan(format!(
+                "{TIMELINE_FUNCTION} expects arguments (base_uri)"
+            )));
+        };
+        let base_uri = literal_arg(TIMELINE_FUNCTION, "base_uri", base_uri)?;
+        let instants = block_on(async {
+            let table = load_table(&base_uri, &self.options).await?;
+            table
+                .get_timeline()
+                .get_active_instants(false)
+                .await
+                .map_err(|e| external_error("Failed to load Hudi timeline", e))
+        })??;
+
+        let mut timestamps = StringBuilder::new();
+        let mut actions = StringBuilder::new();
+        let mut states = StringBuilder::new();
+        let mut completion_timestamps = StringBuilder::new();
+        let mut epoch_millis = Int64Builder::new();
+        for instant in &instants {
+            timestamps.append_value(&instant.timestamp);
+            actions.append_value(instant.action.as_ref());
+            states.append_value(match instant.state {
+                State::Requested => "requested",
+                State::Inflight => "inflight",
+                State::Completed => "completed",
+            });
+            completion_timestamps.append_option(instant.completion_timestamp.as_deref());
+            epoch_millis.append_value(instant.epoch_millis);
+        }
+        mem_table(
+            &TIMELINE_SCHEMA,
+            vec![
+                Arc::new(timestamps.finish()),
+                Arc::new(actions.finish()),
+                Arc::new(states.finish()),
+                Arc::new(completion_timestamps.finish()),
+                Arc::new(epoch_millis.finish()),
+            ],
+        )
+    }
+}
+
+/// Table function `hudi_file_slices(base_uri [, timestamp])` listing the
+/// latest file slices of the table, or of its snapshot as of `timestamp`.
+///
+/// The number of records of a base file is estimated from a sampled base
+/// file, and is null when it cannot be estimated. The log files of a file
+/// slice as of `timestamp` may include the ones written after it, whose log
+/// blocks are skipped by the reads as of `timestamp`.
+#[derive(Debug, Default)]
+pub struct HudiFileSlicesFunction {
+    options: HashMap<String, String>,
+}
+
+impl HudiFileSlicesFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_options(options: HashMap<String, String>) -> Self {
+        Self { options }
+    }
+}
+
+impl TableFunctionImpl for HudiFileSlicesFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let (base_uri, timestamp) = match args {
+            [base_uri] => (base_uri, None),
+            [base_uri, timestamp] => (base_uri, Some(timestamp)),
+            _ => {
+                return Err(Plan(format!(
+                    "{FILE_SLICES_FUNCTION} expects arguments (base_uri [, timestamp])"
+                )));
+            }
+        };
+        let base_uri = literal_arg(FILE_SLICES_FUNCTION, "base_uri", base_uri)?;
+        let mut read_options = ReadOptions::new();
+        if let Some(timestamp) = timestamp {
+            read_options = read_options.with_as_of_timestamp(literal_arg(
+                FILE_SLICES_FUNCTION,
+                "timestamp",
+                timestamp,
+            )?);
+        }
+        let mut file_slices = block_on(async {
+            let table = load_table(&base_uri, &self.options).await?;
+            table
+                .get_file_slices(&read_options)
+                .await
+                .map_err(|e| external_error("Failed to get file slices from Hudi table", e))
+        })??;
+        file_slices.sort_by(|a, b| {
+            (&a.partition_path, a.file_id()).cmp(&(&b.partition_path, b.file_id()))
+        });
+
+        let mut partition_paths = StringBuilder::new();
+        let mut file_ids = StringBuilder::new();
+        let mut commit_timestamps = StringBuilder::new();
+        let mut base_files = StringBuilder::new();
+        let mut base_file_sizes = UInt64Builder::new();
+        let mut base_file_num_records = Int64Builder::new();
+        let mut log_files = ListBuilder::new(StringBuilder::new())
+            .with_field(Field::new_list_field(DataType::Utf8, true));
+        let mut log_files_sizes = UInt64Builder::new();
+        let mut total_sizes = UInt64Builder::new();
+        for file_slice in &file_slices {
+            let base_file = &file_slice.base_file;
+            partition_paths.append_value(&file_slice.partition_path);
+            file_ids.append_value(file_slice.file_id());
+            commit_timestamps.append_value(&base_file.commit_timestamp);
+            base_files.append_value(base_file.file_name());
+            base_file_sizes.append_option(base_file.file_metadata.as_ref().map(|m| m.size));
+            base_file_num_records.append_option(
+                base_file
+                    .file_metadata
+                    .as_ref()
+                    .map(|m| m.num_records)
+                    .filter(|n| *n > 0),
+            );
+            let mut log_files_size = 0;
+            for log_file in &file_slice.log_files {
+                log_files.values().append_value(log_file.file_name());
+                log_files_size += log_file.file_metadata.as_ref().map_or(0, |m| m.size);
+            }
+            log_files.append(true);
+            log_files_sizes.append_value(log_files_size);
+            total_sizes.append_value(file_slice.total_size_bytes());
+        }
+        mem_table(
+            &FILE_SLICES_SCHEMA,
+            vec![
+                Arc::new(partition_paths.finish()),
+                Arc::new(file_ids.finish()),
+                Arc::new(commit_timestamps.finish()),
+                Arc::new(base_files.finish()),
+                Arc::new(base_file_sizes.finish()),
+                Arc::new(base_file_num_records.finish()),
+                Arc::new(log_files.finish()),
+                Arc::new(log_files_sizes.finish()),
+                Arc::new(total_sizes.finish()),
+            ],
+        )
+    }
+}
+
+/// Table function `hudi_commit_stats(base_uri)` listing the write stats of
+/// the completed commits on the active timeline, summed up per partition
+/// from the `HoodieCommitMetadata` of each commit.
+#[derive(Debug, Default)]
+pub struct HudiCommitStatsFunction {
+    options: HashMap<String, String>,
+}
+
+impl HudiCommitStatsFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_options(options: HashMap<String, String>) -> Self {
+        Self { options }
+    }
+}
+
+/// The write stats of a commit summed up for a partition.
+#[derive(Default)]
+struct PartitionWriteStats {
+    num_files: i64,
+    num_writes: i64,
+    num_inserts: i64,
+    num_update_writes: i64,
+    num_deletes: i64,
+    total_write_bytes: i64,
+    total_write_errors: i64,
+}
+
+impl TableFunctionImpl for HudiCommitStatsFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [base_uri] = args else {
+            return Err(Plan(format!(
+                "{COMMIT_STATS_FUNCTION} expects arguments (base_uri)"
+            )));
+        };
+        let base_uri = literal_arg(COMMIT_STATS_FUNCTION, "base_uri", base_uri)?;
+        let commits = block_on(async {
+            let table = load_table(&base_uri, &self.options).await?;
+            let timeline = table.get_timeline();
+            let mut commits = Vec::with_capacity(timeline.completed_commits.len());
+            for instant in &timeline.completed_commits {
+                let metadata = timeline.get_commit_metadata(instant).await.map_err(|e| {
+                    external_error(
+                        format!("Failed to get commit metadata of {}", instant.timestamp),
+                        e,
+                    )
+                })?;
+                commits.push((instant.clone(), metadata));
+            }
+            Ok::<_, DataFusionError>(commits)
+        })??;
+
+        let mut timestamps = StringBuilder::new();
+        let mut actions = StringBuilder::new();
+        let mut operation_types = StringBuilder::new();
+        let mut partition_paths = StringBuilder::new();
+        let mut stats_columns: [Int64Builder; 7] = Default::default();
+        for (instant, metadata) in &commits {
+            let mut partitions: BTreeMap<&str, PartitionWriteStats> = BTreeMap::new();
+            for (partition_path, write_stats) in metadata.partition_to_write_stats.iter().flatten()
+            {
+                let stats = partitions.entry(partition_path).or_default();
+                for write_stat in write_stats {
+                    stats.num_files += 1;
+                    stats.num_writes += write_stat.num_writes.unwrap_or_default();
+                    stats.num_inserts += write_stat.num_inserts.unwrap_or_default();
+                    stats.num_update_writes += write_stat.num_update_writes.unwrap_or_default();
+                    stats.num_deletes += write_stat.num_deletes.unwrap_or_default();
+                    stats.total_write_bytes += write_stat.total_write_bytes.unwrap_or_default();
+                    stats.total_write_errors += write_stat.total_write_errors.unwrap_or_default();
+                }
+            }
+            for (partition_path, stats) in partitions {
+                timestamps.append_value(&instant.timestamp);
+                actions.append_value(instant.action.as_ref());
+                operation_types.append_option(metadata.operation_type.as_deref());
+                partition_paths.append_value(partition_path);
+                for (column, value) in stats_columns.iter_mut().zip([
+                    stats.num_files,
+                    stats.num_writes,
+                    stats.num_inserts,
+                    stats.num_update_writes,
+                    stats.num_deletes,
+                    stats.total_write_bytes,
+                    stats.total_write_errors,
+                ]) {
+                    column.append_value(value);
+                }
+            }
+        }
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(timestamps.finish()),
+            Arc::new(actions.finish()),
+            Arc::new(operation_types.finish()),
+            Arc::new(partition_paths.finish()),
+        ];
+        columns.extend(
+            stats_columns
+                .iter_mut()
+                .map(|column| Arc::new(column.finish()) as ArrayRef),
+        );
+        mem_table(&COMMIT_STATS_SCHEMA, columns)
+    }
+}
+
+/// The value of a string or integer literal argument; timestamps can be given
+/// as either, e.g., `20240101000000000` or `'2024-01-01T00:00:00Z'`.
+fn literal_arg(function: &str, name: &str, arg: &Expr) -> Result<String> {
+    if let Expr::Literal(value, _) = arg {
+        if let Some(Some(value)) = value.try_as_str() {
+            return Ok(value.to_string());
+        }
+        if value.data_type().is_integer() && !value.is_null() {
+            return Ok(value.to_string());
+        }
+    }
+    Err(Plan(format!(
+        "{function} expects a string literal for {name}, got {arg}"
+    )))
+}
+
+async fn load_table(base_uri: &str, options: &HashMap<String, String>) -> Result<HudiTable> {
+    HudiTable::new_with_options(base_uri, options.clone())
+        .await
+        .map_err(|e| external_error("Failed to create Hudi table", e))
+}
+
+fn mem_table(schema: &SchemaRef, columns: Vec<ArrayRef>) -> Result<Arc<dyn TableProvider>> {
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+    Ok(Arc::new(MemTable::try_new(
+        schema.clone(),
+        vec![vec![batch]],
+    )?))
+}
+
+/// Creates the provider of the Hudi table with the read options of the query.
+fn create_data_source(
+    base_uri: &str,
+    options: &HashMap<String, String>,
+    read_options: ReadOptions,
+) -> Result<Arc<dyn TableProvider>> {
+    let options = options
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .chain(read_options.hudi_options);
+    let hudi = block_on(HudiDataSource::new_with_options(base_uri, options))??;
+    Ok(Arc::new(hudi))
+}
+
+/// Runs the future to completion from the synchronous [`TableFunctionImpl::call`],
+/// which is invoked while planning within the async runtime.
+///
+/// The multi-threaded runtime can block in place; otherwise, e.g., in the
+/// current-thread runtime, the future runs on a separate thread with its own
+/// runtime.
+fn block_on<F>(future: F) -> Result<F::Output>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            Ok(tokio::task::block_in_place(|| handle.block_on(future)))
+        }
+        _ => std::thread::scope(|scope| {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| {
+                    external_error("Failed to build runtime for Hudi table function", e)
+                })?;
+            match scope.spawn(move || runtime.block_on(future)).join() {
+                Ok(output) => Ok(output),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }),
+    }
+}
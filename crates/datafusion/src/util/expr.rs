@@ -17,41 +17,58 @@
  * under the License.
  */
 
+use std::sync::Arc;
+
+use arrow_schema::{DataType, Schema};
 use datafusion::logical_expr::Operator;
+use datafusion::physical_expr::PhysicalExpr;
+use datafusion::physical_expr::expressions::{
+    BinaryExpr as PhysicalBinaryExpr, Column as PhysicalColumn, InListExpr, Literal,
+};
 use datafusion_common::ScalarValue;
 use datafusion_expr::expr::InList;
-use datafusion_expr::{Between, BinaryExpr, Expr};
-use hudi_core::expr::filter::{Filter as HudiFilter, col};
+use datafusion_expr::{Between, BinaryExpr, Expr, Like};
+use hudi_core::expr::filter::{Filter as HudiFilter, FilterExpr, col};
 use log::{debug, warn};
 
-/// Extracts pushdown-safe filters from DataFusion expressions for partition pruning.
+/// Extracts pushdown-safe filters from DataFusion expressions for pruning.
 ///
 /// Takes a slice of DataFusion [`Expr`] and extracts filters that can be safely
-/// pushed down for partition pruning. The returned filters represent a **subset**
-/// of the original expression's constraints.
+/// pushed down for partition, file and bucket pruning. The returned filters
+/// represent a **subset** of the original expression's constraints.
 ///
 /// # Supported Expressions
 /// - Binary comparisons: `=`, `!=`, `<`, `>`, `<=`, `>=`
-/// - `NOT` expressions: negates inner binary expression
-/// - `AND` compound expressions: recursively flattens both sides
-/// - `BETWEEN` expressions: converts to `>= low AND <= high`
+/// - `AND` / `OR` compound expressions, converted to [`FilterExpr::And`] / [`FilterExpr::Or`]
+/// - `NOT` expressions whose inner expression is fully convertible
+/// - `BETWEEN` / `NOT BETWEEN` expressions: converts to `>= low AND <= high`
+///   or `< low OR > high`
 /// - `IN` / `NOT IN` expressions: converts to `IN` / `NOT IN` filters
+/// - `IS NULL` / `IS NOT NULL` on a column
+/// - `LIKE` / `NOT LIKE` on a column with a string literal pattern
+///
+/// Top-level conjunctions are flattened so that each returned entry is one
+/// conjunct, which keeps single-column comparisons usable for bucket pruning.
 ///
-/// # OR Expression Handling
+/// # Partial Extraction
 ///
-/// `OR` expressions cannot be represented in the current filter model and are
-/// **skipped**. This means expressions containing `OR` will be **partially extracted**:
+/// Conjuncts that cannot be converted are dropped, which makes the result a
+/// weaker constraint. An `OR` is only extracted if both of its sides can be
+/// converted, and a `NOT` only if its inner expression is converted exactly:
 ///
-/// | Input Expression      | Extracted Filters | Notes                          |
-/// |-----------------------|-------------------|--------------------------------|
-/// | `A AND B`             | `[A, B]`          | Full extraction                |
-/// | `A OR B`              | `[]`              | OR skipped entirely            |
-/// | `A AND (B OR C)`      | `[A]`             | Only A extracted, OR skipped   |
-/// | `(A OR B) AND C`      | `[C]`             | Only C extracted, OR skipped   |
+/// | Input Expression   | Extracted Filters | Notes                             |
+/// |--------------------|-------------------|-----------------------------------|
+/// | `A AND B`          | `[A, B]`          | Full extraction                   |
+/// | `A OR B`           | `[A OR B]`        | Full extraction                   |
+/// | `A AND (B OR C)`   | `[A, B OR C]`     | Full extraction                   |
+/// | `A AND X`          | `[A]`             | Unsupported `X` dropped           |
+/// | `A OR (B AND X)`   | `[A OR B]`        | Unsupported `X` dropped           |
+/// | `A OR X`           | `[]`              | OR with an unsupported side       |
+/// | `NOT (A AND X)`    | `[]`              | NOT of a partial extraction       |
 ///
 /// # Safety
 ///
-/// This function is **safe for partition pruning** because:
+/// This function is **safe for pruning** because:
 /// - Extracted filters are a weaker constraint (may match more rows than original)
 /// - Partitions that don't match extracted filters definitely don't match original
 /// - The original expression must still be applied to filter actual row data
@@ -63,39 +80,98 @@ use log::{debug, warn};
 /// * `exprs` - A slice of DataFusion expressions to convert
 ///
 /// # Returns
-/// A vector of filter tuples `(field, operator, value)`. All returned filters
-/// are implicitly AND-ed together.
-pub fn exprs_to_filters(exprs: &[Expr]) -> Vec<(String, String, String)> {
-    exprs
-        .iter()
-        .flat_map(expr_to_filters)
-        .map(|filter| filter.into())
-        .collect()
+/// A vector of [`FilterExpr`]. All returned filters are implicitly AND-ed together.
+pub fn exprs_to_filters(exprs: &[Expr]) -> Vec<FilterExpr> {
+    let mut filters = Vec::new();
+    for expr in exprs {
+        match expr_to_filter_expr(expr) {
+            Some(FilterExpr::And(conjuncts)) => filters.extend(conjuncts),
+            Some(filter) => filters.push(filter),
+            None => {}
+        }
+    }
+    filters
 }
 
-/// Recursively extracts pushdown-safe filters from a single expression.
-///
-/// OR expressions return empty (cannot be pushed down), which may result in
-/// partial extraction when OR is nested within AND expressions.
-fn expr_to_filters(expr: &Expr) -> Vec<HudiFilter> {
+/// Converts a single expression into a [`FilterExpr`] implied by it, or
+/// `None` if nothing of the expression can be pushed down.
+pub fn expr_to_filter_expr(expr: &Expr) -> Option<FilterExpr> {
+    convert_expr(expr).map(|(filter, _)| filter)
+}
+
+/// Converts a single expression into an equivalent [`FilterExpr`], or `None`
+/// if any part of the expression cannot be pushed down.
+pub fn expr_to_exact_filter_expr(expr: &Expr) -> Option<FilterExpr> {
+    match convert_expr(expr) {
+        Some((filter, true)) => Some(filter),
+        _ => None,
+    }
+}
+
+/// Recursively converts an expression, returning the converted filter and
+/// whether it is equivalent to the expression (`true`) or only implied by it.
+fn convert_expr(expr: &Expr) -> Option<(FilterExpr, bool)> {
     match expr {
         Expr::BinaryExpr(binary_expr) => match binary_expr.op {
             Operator::And => {
-                // Recursively flatten AND expressions
-                let mut filters = expr_to_filters(&binary_expr.left);
-                filters.extend(expr_to_filters(&binary_expr.right));
-                filters
+                let left = convert_expr(&binary_expr.left);
+                let right = convert_expr(&binary_expr.right);
+                match (left, right) {
+                    (Some((left, left_exact)), Some((right, right_exact))) => {
+                        Some((and_exprs(left, right), left_exact && right_exact))
+                    }
+                    // Dropping one side of an AND only weakens the constraint
+                    (Some((filter, _)), None) | (None, Some((filter, _))) => Some((filter, false)),
+                    (None, None) => None,
+                }
             }
             Operator::Or => {
-                // Cannot represent OR in current filter model - skip
-                vec![]
+                // Both sides are needed: dropping a side of an OR would
+                // strengthen the constraint and prune matching data
+                let (left, left_exact) = convert_expr(&binary_expr.left)?;
+                let (right, right_exact) = convert_expr(&binary_expr.right)?;
+                Some((or_exprs(left, right), left_exact && right_exact))
             }
-            _ => binary_expr_to_filter(binary_expr).into_iter().collect(),
+            _ => binary_expr_to_filter(binary_expr).map(|filter| (filter.into(), true)),
         },
-        Expr::Not(not_expr) => not_expr_to_filter(not_expr).into_iter().collect(),
-        Expr::Between(between) => between_to_filters(between),
-        Expr::InList(in_list) => inlist_expr_to_filter(in_list).into_iter().collect(),
-        _ => vec![],
+        Expr::Not(not_expr) => not_expr_to_filter(not_expr).map(|filter| (filter, true)),
+        Expr::Between(between) => between_to_filter(between).map(|filter| (filter, true)),
+        Expr::InList(in_list) => inlist_expr_to_filter(in_list).map(|filter| (filter.into(), true)),
+        Expr::IsNull(inner) => column_name(inner).map(|name| (col(name).is_null().into(), true)),
+        Expr::IsNotNull(inner) => {
+            column_name(inner).map(|name| (col(name).is_not_null().into(), true))
+        }
+        Expr::Like(like) => like_expr_to_filter(like).map(|filter| (filter.into(), true)),
+        _ => None,
+    }
+}
+
+fn and_exprs(left: FilterExpr, right: FilterExpr) -> FilterExpr {
+    let mut conjuncts = Vec::new();
+    for filter in [left, right] {
+        match filter {
+            FilterExpr::And(nested) => conjuncts.extend(nested),
+            filter => conjuncts.push(filter),
+        }
+    }
+    FilterExpr::And(conjuncts)
+}
+
+fn or_exprs(left: FilterExpr, right: FilterExpr) -> FilterExpr {
+    let mut disjuncts = Vec::new();
+    for filter in [left, right] {
+        match filter {
+            FilterExpr::Or(nested) => disjuncts.extend(nested),
+            filter => disjuncts.push(filter),
+        }
+    }
+    FilterExpr::Or(disjuncts)
+}
+
+fn column_name(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Column(column) => Some(column.name()),
+        _ => None,
     }
 }
 
@@ -124,31 +200,23 @@ fn binary_expr_to_filter(binary_expr: &BinaryExpr) -> Option<HudiFilter> {
     Some(filter)
 }
 
-/// Converts a NOT expression (`Expr::Not`) into a [`HudiFilter`].
-fn not_expr_to_filter(not_expr: &Expr) -> Option<HudiFilter> {
-    match not_expr {
-        Expr::BinaryExpr(binary_expr) => {
-            binary_expr_to_filter(binary_expr).map(|filter| filter.negate())?
-        }
-        _ => None,
-    }
-}
-
-/// Converts a BETWEEN expression into two filters: >= low AND <= high.
+/// Converts a NOT expression (`Expr::Not`) into a [`FilterExpr`].
 ///
-/// If `negated` is true, returns empty (NOT BETWEEN is complex to represent).
-fn between_to_filters(between: &Between) -> Vec<HudiFilter> {
-    if between.negated {
-        debug!("NOT BETWEEN expressions cannot be pushed down");
-        return vec![];
-    }
+/// Only an exactly converted inner expression can be negated, as negating a
+/// weaker constraint would yield a stronger one.
+fn not_expr_to_filter(not_expr: &Expr) -> Option<FilterExpr> {
+    expr_to_exact_filter_expr(not_expr).map(|filter| filter.negate())
+}
 
+/// Converts a BETWEEN expression into `>= low AND <= high`, or into
+/// `< low OR > high` if `negated` is true.
+fn between_to_filter(between: &Between) -> Option<FilterExpr> {
     // Extract column name from the expression
     let column_name = match &*between.expr {
         Expr::Column(col) => col.name.clone(),
         _ => {
             debug!("BETWEEN with non-column expression cannot be pushed down");
-            return vec![];
+            return None;
         }
     };
 
@@ -159,7 +227,7 @@ fn between_to_filters(between: &Between) -> Vec<HudiFilter> {
             warn!(
                 "BETWEEN low bound is not a literal for column '{column_name}', skipping pushdown"
             );
-            return vec![];
+            return None;
         }
     };
 
@@ -169,15 +237,22 @@ fn between_to_filters(between: &Between) -> Vec<HudiFilter> {
             warn!(
                 "BETWEEN high bound is not a literal for column '{column_name}', skipping pushdown"
             );
-            return vec![];
+            return None;
         }
     };
 
-    // Create two filters: >= low AND <= high
-    vec![
-        col(&column_name).gte(low_str),
-        col(&column_name).lte(high_str),
-    ]
+    let field = col(&column_name);
+    if between.negated {
+        Some(FilterExpr::Or(vec![
+            field.lt(low_str).into(),
+            field.gt(high_str).into(),
+        ]))
+    } else {
+        Some(FilterExpr::And(vec![
+            field.gte(low_str).into(),
+            field.lte(high_str).into(),
+        ]))
+    }
 }
 
 /// Converts an IN list expression into a HudiFilter with IN or NOT IN operator.
@@ -220,6 +295,169 @@ fn inlist_expr_to_filter(in_list: &InList) -> Option<HudiFilter> {
     }
 }
 
+/// Converts a LIKE expression into a HudiFilter with LIKE or NOT LIKE operator.
+///
+/// Only case-sensitive patterns given as string literals with the default
+/// `\` escape character are supported.
+fn like_expr_to_filter(like: &Like) -> Option<HudiFilter> {
+    if like.case_insensitive || !matches!(like.escape_char, None | Some('\\')) {
+        debug!("ILIKE or LIKE with a custom escape character cannot be pushed down");
+        return None;
+    }
+
+    let field = col(column_name(&like.expr)?);
+    let pattern = match like.pattern.as_ref() {
+        Expr::Literal(
+            ScalarValue::Utf8(Some(pattern))
+            | ScalarValue::LargeUtf8(Some(pattern))
+            | ScalarValue::Utf8View(Some(pattern)),
+            _,
+        ) => pattern.clone(),
+        _ => {
+            debug!("LIKE with a non-literal pattern cannot be pushed down");
+            return None;
+        }
+    };
+
+    if like.negated {
+        Some(field.not_like(pattern))
+    } else {
+        Some(field.like(pattern))
+    }
+}
+
+/// Converts a physical expression, e.g., a dynamic filter of a join taken at
+/// execution time, into a [`FilterExpr`] implied by it, or `None` if nothing of
+/// the expression can be converted.
+///
+/// Like [`expr_to_filter_expr`], unsupported conjuncts are dropped, and an `OR`
+/// is only converted if both of its sides are. Only comparisons and `IN` lists
+/// between a column of `schema` and non-null literals of the column's type are
+/// converted, so that the stringified values parse back to the literals.
+pub fn physical_expr_to_filter_expr(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+) -> Option<FilterExpr> {
+    if let Some(binary_expr) = expr.as_any().downcast_ref::<PhysicalBinaryExpr>() {
+        return match binary_expr.op() {
+            Operator::And => {
+                let left = physical_expr_to_filter_expr(binary_expr.left(), schema);
+                let right = physical_expr_to_filter_expr(binary_expr.right(), schema);
+                match (left, right) {
+                    (Some(left), Some(right)) => Some(and_exprs(left, right)),
+                    // Dropping one side of an AND only weakens the constraint
+                    (filter, None) | (None, filter) => filter,
+                }
+            }
+            Operator::Or => {
+                let left = physical_expr_to_filter_expr(binary_expr.left(), schema)?;
+                let right = physical_expr_to_filter_expr(binary_expr.right(), schema)?;
+                Some(or_exprs(left, right))
+            }
+            _ => physical_binary_expr_to_filter(binary_expr, schema).map(FilterExpr::from),
+        };
+    }
+    if let Some(in_list) = expr.as_any().downcast_ref::<InListExpr>() {
+        return physical_in_list_to_filter(in_list, schema).map(FilterExpr::from);
+    }
+    None
+}
+
+/// Converts a physical comparison between a column and a literal into a
+/// [`HudiFilter`], with the operator swapped if the literal is on the left.
+fn physical_binary_expr_to_filter(
+    binary_expr: &PhysicalBinaryExpr,
+    schema: &Schema,
+) -> Option<HudiFilter> {
+    let (column, literal, op) = match (
+        physical_column_field(binary_expr.left(), schema),
+        physical_column_field(binary_expr.right(), schema),
+    ) {
+        (Some((name, data_type)), None) => (
+            name,
+            physical_literal_value(binary_expr.right(), data_type)?,
+            *binary_expr.op(),
+        ),
+        (None, Some((name, data_type))) => (
+            name,
+            physical_literal_value(binary_expr.left(), data_type)?,
+            binary_expr.op().swap()?,
+        ),
+        _ => return None,
+    };
+
+    let field = col(column);
+    let filter = match op {
+        Operator::Eq => field.eq(literal),
+        Operator::NotEq => field.ne(literal),
+        Operator::Lt => field.lt(literal),
+        Operator::LtEq => field.lte(literal),
+        Operator::Gt => field.gt(literal),
+        Operator::GtEq => field.gte(literal),
+        _ => return None,
+    };
+    Some(filter)
+}
+
+fn physical_in_list_to_filter(in_list: &InListExpr, schema: &Schema) -> Option<HudiFilter> {
+    let (column, data_type) = physical_column_field(in_list.expr(), schema)?;
+    if in_list.list().is_empty() {
+        return None;
+    }
+    let values = in_list
+        .list()
+        .iter()
+        .map(|expr| physical_literal_value(expr, data_type))
+        .collect::<Option<Vec<_>>>()?;
+
+    let field = col(column);
+    if in_list.negated() {
+        Some(field.not_in_list(values))
+    } else {
+        Some(field.in_list(values))
+    }
+}
+
+/// Returns the name and type of the field of `schema` referenced by a physical
+/// column expression.
+fn physical_column_field<'a>(
+    expr: &'a Arc<dyn PhysicalExpr>,
+    schema: &'a Schema,
+) -> Option<(&'a str, &'a DataType)> {
+    let column = expr.as_any().downcast_ref::<PhysicalColumn>()?;
+    let field = schema.field_with_name(column.name()).ok()?;
+    Some((column.name(), field.data_type()))
+}
+
+/// Stringifies a non-null physical literal of `data_type`, for the types whose
+/// values parse back exactly from the string.
+fn physical_literal_value(expr: &Arc<dyn PhysicalExpr>, data_type: &DataType) -> Option<String> {
+    let value = expr.as_any().downcast_ref::<Literal>()?.value();
+    let parses_back = matches!(
+        data_type,
+        DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Utf8View
+            | DataType::Date32
+            | DataType::Decimal128(_, _)
+    );
+    if !parses_back || value.is_null() || value.data_type() != *data_type {
+        return None;
+    }
+    Some(scalar_to_filter_value(value))
+}
+
 /// Stringifies a DataFusion literal for Hudi's string-typed `Filter` API.
 ///
 /// `ScalarValue::Display` is lossy for decimals — it prints the unscaled
@@ -286,13 +524,29 @@ fn format_decimal_digits(mut digits: String, scale: i8) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow_schema::{DataType, Field, Schema};
+    use arrow_schema::{DataType, Field, Schema, TimeUnit};
     use datafusion::logical_expr::{col, lit};
     use datafusion_expr::{BinaryExpr, Expr};
     use hudi_core::expr::ExprOperator;
+    use hudi_core::expr::filter::col as hudi_col;
     use std::str::FromStr;
     use std::sync::Arc;
 
+    /// Converts the expressions and flattens the results, which must all be
+    /// single filters, into `(field, operator, value)` tuples.
+    fn exprs_to_filter_tuples(exprs: &[Expr]) -> Vec<(String, String, String)> {
+        exprs_to_filters(exprs)
+            .into_iter()
+            .map(|filter| {
+                filter
+                    .as_filter()
+                    .cloned()
+                    .expect("expected a single filter")
+                    .into()
+            })
+            .collect()
+    }
+
     #[test]
     fn test_convert_simple_binary_expr() {
         let schema = Arc::new(Schema::new(vec![Field::new("col", DataType::Int32, false)]));
@@ -305,7 +559,7 @@ mod tests {
 
         let filters = vec![expr];
 
-        let result = exprs_to_filters(&filters);
+        let result = exprs_to_filter_tuples(&filters);
 
         assert_eq!(result.len(), 1);
 
@@ -337,7 +591,7 @@ mod tests {
 
         let filters = vec![expr];
 
-        let result = exprs_to_filters(&filters);
+        let result = exprs_to_filter_tuples(&filters);
 
         assert_eq!(result.len(), 1);
 
@@ -395,7 +649,7 @@ mod tests {
         ];
 
         let filters: Vec<Expr> = test_cases.iter().map(|(expr, _)| expr.clone()).collect();
-        let result = exprs_to_filters(&filters);
+        let result = exprs_to_filter_tuples(&filters);
         let expected_filters: Vec<&HudiFilter> = test_cases
             .iter()
             .filter_map(|(_, opt_filter)| opt_filter.as_ref())
@@ -436,7 +690,7 @@ mod tests {
 
             let filters = vec![expr];
 
-            let result = exprs_to_filters(&filters);
+            let result = exprs_to_filter_tuples(&filters);
 
             assert_eq!(result.len(), 1);
 
@@ -466,7 +720,7 @@ mod tests {
         ));
 
         let filters = vec![expr];
-        let result = exprs_to_filters(&filters);
+        let result = exprs_to_filter_tuples(&filters);
         assert!(result.is_empty());
     }
 
@@ -489,7 +743,7 @@ mod tests {
             Box::new(right),
         ));
 
-        let result = exprs_to_filters(&[and_expr]);
+        let result = exprs_to_filter_tuples(&[and_expr]);
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].0, "col1");
@@ -501,8 +755,7 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_or_expr_returns_empty() {
-        // OR expressions cannot be pushed down
+    fn test_convert_or_expr() {
         let left = Expr::BinaryExpr(BinaryExpr::new(
             Box::new(col("col1")),
             Operator::Eq,
@@ -520,7 +773,13 @@ mod tests {
         ));
 
         let result = exprs_to_filters(&[or_expr]);
-        assert!(result.is_empty());
+        assert_eq!(
+            result,
+            vec![FilterExpr::Or(vec![
+                hudi_col("col1").eq("a").into(),
+                hudi_col("col2").eq("b").into(),
+            ])]
+        );
     }
 
     #[test]
@@ -533,7 +792,7 @@ mod tests {
             Box::new(lit(20i32)),
         ));
 
-        let result = exprs_to_filters(&[between]);
+        let result = exprs_to_filter_tuples(&[between]);
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].0, "count");
@@ -555,7 +814,7 @@ mod tests {
             )),
         ));
 
-        let result = exprs_to_filters(&[expr]);
+        let result = exprs_to_filter_tuples(&[expr]);
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].0, "amount");
@@ -564,8 +823,8 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_not_between_returns_empty() {
-        // NOT BETWEEN cannot be represented in current filter model
+    fn test_convert_not_between_expr() {
+        // Test: col NOT BETWEEN 10 AND 20 should produce < 10 OR > 20
         let not_between = Expr::Between(Between::new(
             Box::new(col("count")),
             true, // negated
@@ -574,7 +833,13 @@ mod tests {
         ));
 
         let result = exprs_to_filters(&[not_between]);
-        assert!(result.is_empty());
+        assert_eq!(
+            result,
+            vec![FilterExpr::Or(vec![
+                hudi_col("count").lt("10").into(),
+                hudi_col("count").gt("20").into(),
+            ])]
+        );
     }
 
     #[test]
@@ -591,195 +856,224 @@ mod tests {
     }
 
     // =========================================================================
-    // Partial extraction tests for OR expressions
+    // Extraction tests for OR and NOT expressions
     // =========================================================================
     //
-    // These tests verify the documented behavior: OR expressions cannot be
-    // pushed down, so expressions containing OR are partially extracted.
-    // This is safe for partition pruning (extracted filters are weaker
-    // constraints) but callers must apply original expressions for correctness.
+    // These tests verify the documented behavior: OR expressions are extracted
+    // when both sides can be converted, and unsupported conjuncts are dropped.
+    // This is safe for pruning (extracted filters are weaker constraints) but
+    // callers must apply original expressions for correctness.
+
+    fn eq_filter(field: &str, value: &str) -> FilterExpr {
+        hudi_col(field).eq(value).into()
+    }
+
+    fn unsupported_expr() -> Expr {
+        Expr::BinaryExpr(BinaryExpr::new(
+            Box::new(col("col_x")),
+            Operator::Modulo,
+            Box::new(lit(2i32)),
+        ))
+    }
 
     #[test]
-    fn test_partial_extraction_and_with_or_on_right() {
-        // Test: A AND (B OR C) should extract only [A]
-        // The OR subtree is skipped, leaving only the left AND operand
+    fn test_extraction_and_with_or_on_right() {
+        // Test: A AND (B OR C) should extract [A, B OR C]
         let a = col("col_a").eq(lit("a"));
         let b = col("col_b").eq(lit("b"));
         let c = col("col_c").eq(lit("c"));
 
-        // Build: (B OR C)
-        let b_or_c = Expr::BinaryExpr(BinaryExpr::new(Box::new(b), Operator::Or, Box::new(c)));
-
-        // Build: A AND (B OR C)
-        let expr = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(a),
-            Operator::And,
-            Box::new(b_or_c),
-        ));
-
-        let result = exprs_to_filters(&[expr]);
+        let result = exprs_to_filters(&[a.and(b.or(c))]);
 
-        // Only A should be extracted; (B OR C) is skipped
         assert_eq!(
-            result.len(),
-            1,
-            "Expected only 1 filter (A), OR subtree skipped"
+            result,
+            vec![
+                eq_filter("col_a", "a"),
+                FilterExpr::Or(vec![eq_filter("col_b", "b"), eq_filter("col_c", "c")]),
+            ]
         );
-        assert_eq!(result[0].0, "col_a");
-        assert_eq!(result[0].1, "=");
-        assert_eq!(result[0].2, "a");
     }
 
     #[test]
-    fn test_partial_extraction_and_with_or_on_left() {
-        // Test: (A OR B) AND C should extract only [C]
-        // The OR subtree is skipped, leaving only the right AND operand
+    fn test_extraction_and_with_or_on_left() {
+        // Test: (A OR B) AND C should extract [A OR B, C]
         let a = col("col_a").eq(lit("a"));
         let b = col("col_b").eq(lit("b"));
         let c = col("col_c").eq(lit("c"));
 
-        // Build: (A OR B)
-        let a_or_b = Expr::BinaryExpr(BinaryExpr::new(Box::new(a), Operator::Or, Box::new(b)));
-
-        // Build: (A OR B) AND C
-        let expr = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(a_or_b),
-            Operator::And,
-            Box::new(c),
-        ));
-
-        let result = exprs_to_filters(&[expr]);
+        let result = exprs_to_filters(&[a.or(b).and(c)]);
 
-        // Only C should be extracted; (A OR B) is skipped
         assert_eq!(
-            result.len(),
-            1,
-            "Expected only 1 filter (C), OR subtree skipped"
+            result,
+            vec![
+                FilterExpr::Or(vec![eq_filter("col_a", "a"), eq_filter("col_b", "b")]),
+                eq_filter("col_c", "c"),
+            ]
         );
-        assert_eq!(result[0].0, "col_c");
-        assert_eq!(result[0].1, "=");
-        assert_eq!(result[0].2, "c");
     }
 
     #[test]
-    fn test_partial_extraction_complex_and_or_mix() {
-        // Test: (A AND B) AND (C OR D) should extract [A, B]
-        // The left AND subtree is fully extracted, right OR subtree is skipped
+    fn test_extraction_nested_or_is_flattened() {
+        // Test: (A OR B) OR (C AND D) should extract [A OR B OR (C AND D)]
         let a = col("col_a").eq(lit("a"));
         let b = col("col_b").eq(lit("b"));
         let c = col("col_c").eq(lit("c"));
         let d = col("col_d").eq(lit("d"));
 
-        // Build: (A AND B)
-        let a_and_b = Expr::BinaryExpr(BinaryExpr::new(Box::new(a), Operator::And, Box::new(b)));
+        let result = exprs_to_filters(&[a.or(b).or(c.and(d))]);
 
-        // Build: (C OR D)
-        let c_or_d = Expr::BinaryExpr(BinaryExpr::new(Box::new(c), Operator::Or, Box::new(d)));
-
-        // Build: (A AND B) AND (C OR D)
-        let expr = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(a_and_b),
-            Operator::And,
-            Box::new(c_or_d),
-        ));
-
-        let result = exprs_to_filters(&[expr]);
-
-        // A and B should be extracted; (C OR D) is skipped
         assert_eq!(
-            result.len(),
-            2,
-            "Expected 2 filters (A, B), OR subtree skipped"
+            result,
+            vec![FilterExpr::Or(vec![
+                eq_filter("col_a", "a"),
+                eq_filter("col_b", "b"),
+                FilterExpr::And(vec![eq_filter("col_c", "c"), eq_filter("col_d", "d")]),
+            ])]
         );
-        assert_eq!(result[0].0, "col_a");
-        assert_eq!(result[1].0, "col_b");
     }
 
     #[test]
-    fn test_partial_extraction_or_both_sides_skipped() {
-        // Test: (A OR B) AND (C OR D) should extract []
-        // Both sides are OR, so nothing can be extracted
+    fn test_partial_extraction_drops_unsupported_conjuncts() {
+        // Test: A AND (B AND X) should extract [A, B]
         let a = col("col_a").eq(lit("a"));
         let b = col("col_b").eq(lit("b"));
-        let c = col("col_c").eq(lit("c"));
-        let d = col("col_d").eq(lit("d"));
-
-        let a_or_b = Expr::BinaryExpr(BinaryExpr::new(Box::new(a), Operator::Or, Box::new(b)));
 
-        let c_or_d = Expr::BinaryExpr(BinaryExpr::new(Box::new(c), Operator::Or, Box::new(d)));
-
-        let expr = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(a_or_b),
-            Operator::And,
-            Box::new(c_or_d),
-        ));
-
-        let result = exprs_to_filters(&[expr]);
+        let result = exprs_to_filters(&[a.clone().and(b.clone().and(unsupported_expr()))]);
+        assert_eq!(
+            result,
+            vec![eq_filter("col_a", "a"), eq_filter("col_b", "b")]
+        );
 
-        // Both sides are OR, nothing extracted
-        assert!(
-            result.is_empty(),
-            "Expected empty result when both AND operands are OR"
+        // Test: A OR (B AND X) should extract [A OR B]
+        let result = exprs_to_filters(&[a.or(b.and(unsupported_expr()))]);
+        assert_eq!(
+            result,
+            vec![FilterExpr::Or(vec![
+                eq_filter("col_a", "a"),
+                eq_filter("col_b", "b")
+            ])]
         );
     }
 
     #[test]
-    fn test_partial_extraction_deeply_nested() {
-        // Test: A AND (B AND (C OR D)) should extract [A, B]
-        // Nested AND is flattened, nested OR is skipped
+    fn test_partial_extraction_skips_or_with_unsupported_side() {
+        // Test: A OR X should extract [], as dropping X would prune rows matching X
         let a = col("col_a").eq(lit("a"));
+        assert!(exprs_to_filters(&[a.clone().or(unsupported_expr())]).is_empty());
+        assert!(exprs_to_filters(&[unsupported_expr().or(a.clone())]).is_empty());
+
+        // Test: Multiple input exprs [A, B OR X] should extract [A]
         let b = col("col_b").eq(lit("b"));
-        let c = col("col_c").eq(lit("c"));
-        let d = col("col_d").eq(lit("d"));
+        let result = exprs_to_filters(&[a, b.or(unsupported_expr())]);
+        assert_eq!(result, vec![eq_filter("col_a", "a")]);
+    }
 
-        // Build: (C OR D)
-        let c_or_d = Expr::BinaryExpr(BinaryExpr::new(Box::new(c), Operator::Or, Box::new(d)));
+    #[test]
+    fn test_convert_not_compound_expr() {
+        // Test: NOT (A OR B) should extract [A != 'a' AND B != 'b'] as two filters
+        let a = col("col_a").eq(lit("a"));
+        let b = col("col_b").eq(lit("b"));
+        let result = exprs_to_filter_tuples(&[Expr::Not(Box::new(a.clone().or(b.clone())))]);
+        assert_eq!(
+            result,
+            vec![
+                ("col_a".to_string(), "!=".to_string(), "a".to_string()),
+                ("col_b".to_string(), "!=".to_string(), "b".to_string()),
+            ]
+        );
 
-        // Build: B AND (C OR D)
-        let b_and_c_or_d = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(b),
-            Operator::And,
-            Box::new(c_or_d),
-        ));
+        // Test: NOT (A AND B) should extract [A != 'a' OR B != 'b']
+        let result = exprs_to_filters(&[Expr::Not(Box::new(a.clone().and(b)))]);
+        assert_eq!(
+            result,
+            vec![FilterExpr::Or(vec![
+                hudi_col("col_a").ne("a").into(),
+                hudi_col("col_b").ne("b").into(),
+            ])]
+        );
 
-        // Build: A AND (B AND (C OR D))
-        let expr = Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(a),
-            Operator::And,
-            Box::new(b_and_c_or_d),
-        ));
+        // Test: NOT (A AND X) should extract [], as only an exact conversion can be negated
+        let result = exprs_to_filters(&[Expr::Not(Box::new(a.and(unsupported_expr())))]);
+        assert!(result.is_empty());
+    }
 
-        let result = exprs_to_filters(&[expr]);
+    #[test]
+    fn test_exact_conversion() {
+        let a = col("col_a").eq(lit("a"));
+        let b = col("col_b").eq(lit("b"));
 
-        // A and B should be extracted from nested ANDs; (C OR D) is skipped
         assert_eq!(
-            result.len(),
-            2,
-            "Expected 2 filters (A, B) from nested ANDs"
+            expr_to_exact_filter_expr(&a.clone().or(b.clone())),
+            Some(FilterExpr::Or(vec![
+                eq_filter("col_a", "a"),
+                eq_filter("col_b", "b")
+            ]))
+        );
+        assert_eq!(
+            expr_to_exact_filter_expr(&a.clone().and(unsupported_expr())),
+            None
+        );
+        assert_eq!(
+            expr_to_filter_expr(&a.and(unsupported_expr())),
+            Some(eq_filter("col_a", "a"))
         );
-        assert_eq!(result[0].0, "col_a");
-        assert_eq!(result[1].0, "col_b");
     }
 
     #[test]
-    fn test_partial_extraction_multiple_input_exprs() {
-        // Test: Multiple expressions in input slice
-        // Input: [A, (B OR C)] should extract [A] (B OR C skipped)
-        let a = col("col_a").eq(lit("a"));
-        let b = col("col_b").eq(lit("b"));
-        let c = col("col_c").eq(lit("c"));
+    fn test_convert_null_checks() {
+        let result = exprs_to_filter_tuples(&[col("col_a").is_null(), col("col_b").is_not_null()]);
+        assert_eq!(
+            result,
+            vec![
+                ("col_a".to_string(), "IS NULL".to_string(), String::new()),
+                (
+                    "col_b".to_string(),
+                    "IS NOT NULL".to_string(),
+                    String::new()
+                ),
+            ]
+        );
 
-        let b_or_c = Expr::BinaryExpr(BinaryExpr::new(Box::new(b), Operator::Or, Box::new(c)));
+        // NOT IS NULL is negated into IS NOT NULL
+        let result = exprs_to_filter_tuples(&[Expr::Not(Box::new(col("col_a").is_null()))]);
+        assert_eq!(result[0].1, "IS NOT NULL");
 
-        let result = exprs_to_filters(&[a, b_or_c]);
+        // Null checks on non-column expressions cannot be pushed down
+        let non_col = Expr::IsNull(Box::new(col("col_a") + col("col_b")));
+        assert!(exprs_to_filters(&[non_col]).is_empty());
+    }
 
-        // Only A from first expr; second expr (B OR C) is skipped entirely
+    #[test]
+    fn test_convert_like() {
+        let result = exprs_to_filter_tuples(&[
+            col("name").like(lit("Al%")),
+            col("name").not_like(lit("B_b")),
+        ]);
         assert_eq!(
-            result.len(),
-            1,
-            "Expected 1 filter from first expr, OR expr skipped"
+            result,
+            vec![
+                ("name".to_string(), "LIKE".to_string(), "Al%".to_string()),
+                (
+                    "name".to_string(),
+                    "NOT LIKE".to_string(),
+                    "B_b".to_string()
+                ),
+            ]
         );
-        assert_eq!(result[0].0, "col_a");
+
+        // ILIKE, custom escape characters and non-literal patterns cannot be pushed down
+        let unsupported = [
+            col("name").ilike(lit("al%")),
+            Expr::Like(Like::new(
+                false,
+                Box::new(col("name")),
+                Box::new(lit("Al#%")),
+                Some('#'),
+                false,
+            )),
+            col("name").like(col("pattern")),
+        ];
+        assert!(exprs_to_filters(&unsupported).is_empty());
     }
 
     #[test]
@@ -790,7 +1084,7 @@ mod tests {
             vec![lit("a"), lit("b"), lit("c")],
             false,
         ));
-        let result = exprs_to_filters(&[in_list]);
+        let result = exprs_to_filter_tuples(&[in_list]);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].0, "part");
         assert_eq!(result[0].1, "IN");
@@ -802,7 +1096,7 @@ mod tests {
             vec![lit("x"), lit("y")],
             true,
         ));
-        let result = exprs_to_filters(&[not_in]);
+        let result = exprs_to_filter_tuples(&[not_in]);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].0, "part");
         assert_eq!(result[0].1, "NOT IN");
@@ -814,7 +1108,7 @@ mod tests {
             vec![lit(40i32), lit(60i32)],
             false,
         ));
-        let result = exprs_to_filters(&[in_int]);
+        let result = exprs_to_filter_tuples(&[in_int]);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].1, "IN");
     }
@@ -823,7 +1117,7 @@ mod tests {
     fn test_convert_in_list_unsupported_cases() {
         // Empty list
         let empty = Expr::InList(InList::new(Box::new(col("col1")), vec![], false));
-        assert!(exprs_to_filters(&[empty]).is_empty());
+        assert!(exprs_to_filter_tuples(&[empty]).is_empty());
 
         // Non-literal values
         let non_lit = Expr::InList(InList::new(
@@ -831,7 +1125,7 @@ mod tests {
             vec![col("col2"), col("col3")],
             false,
         ));
-        assert!(exprs_to_filters(&[non_lit]).is_empty());
+        assert!(exprs_to_filter_tuples(&[non_lit]).is_empty());
 
         // Non-column expression
         let non_col = Expr::InList(InList::new(
@@ -843,6 +1137,68 @@ mod tests {
             vec![lit(1i32)],
             false,
         ));
-        assert!(exprs_to_filters(&[non_col]).is_empty());
+        assert!(exprs_to_filter_tuples(&[non_col]).is_empty());
+    }
+
+    #[test]
+    fn test_convert_physical_expr() {
+        use datafusion::physical_expr::expressions::{
+            BinaryExpr as PhysicalBinaryExpr, col as physical_col, in_list, lit as physical_lit,
+        };
+
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+        ]);
+        let column = |name: &str| physical_col(name, &schema).unwrap();
+        let compare = |left: Arc<dyn PhysicalExpr>, op: Operator, right: Arc<dyn PhysicalExpr>| {
+            Arc::new(PhysicalBinaryExpr::new(left, op, right)) as Arc<dyn PhysicalExpr>
+        };
+
+        // The bounds and IN list of a join's dynamic filter, with a literal on
+        // the left swapped to the right.
+        let bounds = compare(
+            compare(column("id"), Operator::GtEq, physical_lit(1i64)),
+            Operator::And,
+            compare(physical_lit(9i64), Operator::GtEq, column("id")),
+        );
+        let names = in_list(
+            column("name"),
+            vec![physical_lit("a"), physical_lit("b")],
+            &false,
+            &schema,
+        )
+        .unwrap();
+        let expr = compare(bounds.clone(), Operator::And, names);
+        assert_eq!(
+            physical_expr_to_filter_expr(&expr, &schema),
+            Some(FilterExpr::And(vec![
+                hudi_col("id").gte("1").into(),
+                hudi_col("id").lte("9").into(),
+                hudi_col("name").in_list(["a", "b"]).into(),
+            ]))
+        );
+
+        // Unsupported types, mismatched literal types and unsupported conjuncts
+        // are dropped; an OR with an unsupported side is not converted.
+        let ts = compare(
+            column("ts"),
+            Operator::Gt,
+            physical_lit(ScalarValue::TimestampMicrosecond(Some(1), None)),
+        );
+        let mismatched = compare(column("id"), Operator::Eq, physical_lit(1i32));
+        let name_eq = compare(column("name"), Operator::Eq, physical_lit("a"));
+        let expr = compare(
+            compare(ts.clone(), Operator::And, mismatched),
+            Operator::And,
+            name_eq.clone(),
+        );
+        assert_eq!(
+            physical_expr_to_filter_expr(&expr, &schema),
+            Some(eq_filter("name", "a"))
+        );
+        let expr = compare(name_eq, Operator::Or, ts);
+        assert_eq!(physical_expr_to_filter_expr(&expr, &schema), None);
     }
 }
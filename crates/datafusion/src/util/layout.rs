@@ -0,0 +1,213 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+//! Data layout of Hudi scans declared to DataFusion: the output ordering of
+//! file slices sorted by clustering and the grouping of file slices by
+//! partition.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow_schema::{Schema, SortOptions};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{LexOrdering, PhysicalSortExpr};
+use hudi_core::file_group::file_slice::FileSlice;
+
+/// Returns the columns that the records of every file slice are sorted by.
+///
+/// A file slice is sorted if it has no log files and its base file is written
+/// by a clustering that sorts the records, given by `sort_columns` keyed by the
+/// clustering instant timestamps. The result is the longest common prefix of
+/// the sort columns of all file slices, which is empty if any file slice is
+/// not sorted.
+pub fn clustering_sort_columns(
+    file_slices: &[FileSlice],
+    sort_columns: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut common: Option<&[String]> = None;
+    for file_slice in file_slices {
+        if file_slice.has_log_file() {
+            return Vec::new();
+        }
+        let Some(columns) = sort_columns.get(&file_slice.base_file.commit_timestamp) else {
+            return Vec::new();
+        };
+        let prefix_len = match common {
+            Some(common) => common
+                .iter()
+                .zip(columns)
+                .take_while(|(a, b)| a == b)
+                .count(),
+            None => columns.len(),
+        };
+        common = Some(&columns[..prefix_len]);
+    }
+    common.map(|c| c.to_vec()).unwrap_or_default()
+}
+
+/// Builds the ascending, nulls-first ordering by the `columns` of `schema`, as
+/// written by clustering; the ordering stops at the first column not in
+/// `schema`. Returns `None` if no column is in `schema`.
+pub fn sort_columns_to_lex_ordering(columns: &[String], schema: &Schema) -> Option<LexOrdering> {
+    let sort_exprs = columns.iter().map_while(|name| {
+        let column = Column::new_with_schema(name, schema).ok()?;
+        Some(PhysicalSortExpr::new(
+            Arc::new(column),
+            SortOptions::new(false, true),
+        ))
+    });
+    LexOrdering::new(sort_exprs)
+}
+
+/// Groups the file slices by partition path into at most `max_partitions`
+/// groups, so that all the file slices of a partition are in the same group.
+///
+/// Partitions are assigned to the groups round-robin in the order of the
+/// partition paths, for a deterministic grouping across scans.
+pub fn group_by_partition_path(
+    file_slices: Vec<FileSlice>,
+    max_partitions: usize,
+) -> Vec<Vec<FileSlice>> {
+    let mut partitions: BTreeMap<String, Vec<FileSlice>> = BTreeMap::new();
+    for file_slice in file_slices {
+        partitions
+            .entry(file_slice.partition_path.clone())
+            .or_default()
+            .push(file_slice);
+    }
+
+    let num_groups = partitions.len().min(max_partitions.max(1));
+    let mut groups: Vec<Vec<FileSlice>> = vec![Vec::new(); num_groups];
+    for (idx, (_, slices)) in partitions.into_iter().enumerate() {
+        groups[idx % num_groups].extend(slices);
+    }
+    groups
+}
+
+/// Returns the number of distinct partition paths of the file slices.
+pub fn count_partition_paths(file_slices: &[FileSlice]) -> usize {
+    file_slices
+        .iter()
+        .map(|f| f.partition_path.as_str())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_schema::{DataType, Field};
+    use hudi_core::file_group::base_file::BaseFile;
+    use hudi_core::file_group::log_file::LogFile;
+    use std::str::FromStr;
+
+    fn file_slice(file_id: &str, commit_timestamp: &str, partition_path: &str) -> FileSlice {
+        let base_file =
+            BaseFile::from_str(&format!("{file_id}-0_0-1-1_{commit_timestamp}.parquet")).unwrap();
+        FileSlice::new(base_file, partition_path.to_string())
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_clustering_sort_columns() {
+        let sort_columns = HashMap::from([
+            ("20250101000000000".to_string(), strings(&["a", "b"])),
+            ("20250102000000000".to_string(), strings(&["a", "c"])),
+        ]);
+        let slices = vec![
+            file_slice("fileA", "20250101000000000", "p1"),
+            file_slice("fileB", "20250101000000000", "p2"),
+        ];
+        assert_eq!(
+            clustering_sort_columns(&slices, &sort_columns),
+            strings(&["a", "b"])
+        );
+
+        let mut slices = slices;
+        slices.push(file_slice("fileC", "20250102000000000", "p1"));
+        assert_eq!(
+            clustering_sort_columns(&slices, &sort_columns),
+            strings(&["a"])
+        );
+
+        slices.push(file_slice("fileD", "20250103000000000", "p1"));
+        assert!(clustering_sort_columns(&slices, &sort_columns).is_empty());
+
+        let mut sorted_with_log = file_slice("fileA", "20250101000000000", "p1");
+        sorted_with_log
+            .log_files
+            .insert(LogFile::from_str(".fileA-0_20250101000000000.log.1_0-1-1").unwrap());
+        assert!(clustering_sort_columns(&[sorted_with_log], &sort_columns).is_empty());
+        assert!(clustering_sort_columns(&[], &sort_columns).is_empty());
+    }
+
+    #[test]
+    fn test_sort_columns_to_lex_ordering() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let ordering = sort_columns_to_lex_ordering(&strings(&["b", "a"]), &schema).unwrap();
+        assert_eq!(ordering.to_string(), "b@1 ASC, a@0 ASC");
+
+        let ordering = sort_columns_to_lex_ordering(&strings(&["a", "x", "b"]), &schema).unwrap();
+        assert_eq!(ordering.to_string(), "a@0 ASC");
+        assert!(sort_columns_to_lex_ordering(&strings(&["x", "a"]), &schema).is_none());
+    }
+
+    #[test]
+    fn test_group_by_partition_path() {
+        let slices = vec![
+            file_slice("fileA", "20250101000000000", "p2"),
+            file_slice("fileB", "20250101000000000", "p1"),
+            file_slice("fileC", "20250101000000000", "p3"),
+            file_slice("fileD", "20250101000000000", "p2"),
+        ];
+        assert_eq!(count_partition_paths(&slices), 3);
+
+        let file_ids = |groups: &[Vec<FileSlice>]| -> Vec<Vec<String>> {
+            groups
+                .iter()
+                .map(|g| g.iter().map(|f| f.base_file.file_id.clone()).collect())
+                .collect()
+        };
+        let groups = group_by_partition_path(slices.clone(), 8);
+        assert_eq!(
+            file_ids(&groups),
+            vec![
+                strings(&["fileB-0"]),
+                strings(&["fileA-0", "fileD-0"]),
+                strings(&["fileC-0"])
+            ]
+        );
+
+        let groups = group_by_partition_path(slices, 2);
+        assert_eq!(
+            file_ids(&groups),
+            vec![
+                strings(&["fileB-0", "fileC-0"]),
+                strings(&["fileA-0", "fileD-0"])
+            ]
+        );
+        assert!(group_by_partition_path(Vec::new(), 2).is_empty());
+    }
+}
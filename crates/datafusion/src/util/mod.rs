@@ -18,3 +18,6 @@
  */
 
 pub mod expr;
+pub mod layout;
+pub mod pruning;
+pub mod stats;
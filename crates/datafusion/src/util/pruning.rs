@@ -0,0 +1,246 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+//! Pruning of file slices by predicates known only at execution time, e.g.,
+//! the dynamic filters of joins.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, UInt64Array};
+use arrow_schema::{Schema, SchemaRef};
+use datafusion::physical_expr::PhysicalExpr;
+use datafusion::physical_optimizer::pruning::PruningPredicate;
+use datafusion_common::pruning::PruningStatistics;
+use datafusion_common::{Column, ScalarValue};
+use hudi_core::file_group::file_slice::FileSlice;
+use hudi_core::statistics::{ColumnStatistics as HudiColumnStatistics, StatisticsContainer};
+use log::debug;
+
+/// The [`PruningStatistics`] of a single file slice, from the column stats of
+/// its base file and the values of its partition fields.
+///
+/// The column stats are only used for file slices without log files, as the
+/// records of the log files may fall out of their ranges. The partition values
+/// hold for all the records of the file slice; their null counts are left to
+/// the column stats, as a default partition may stand for null values.
+pub struct FileSlicePruningStatistics<'a> {
+    column_stats: Option<&'a StatisticsContainer>,
+    partition_values: HashMap<String, ArrayRef>,
+    schema: &'a Schema,
+}
+
+impl<'a> FileSlicePruningStatistics<'a> {
+    pub fn new(
+        file_slice: &'a FileSlice,
+        partition_values: HashMap<String, ArrayRef>,
+        schema: &'a Schema,
+    ) -> Self {
+        let column_stats = file_slice
+            .base_file_column_stats
+            .as_ref()
+            .filter(|_| !file_slice.has_log_file());
+        Self {
+            column_stats,
+            partition_values,
+            schema,
+        }
+    }
+
+    /// Casts a single-element value to the type of the column in the schema.
+    fn cast_to_column_type(&self, column: &Column, value: &ArrayRef) -> Option<ArrayRef> {
+        let field = self.schema.field_with_name(column.name()).ok()?;
+        if value.len() != 1 {
+            return None;
+        }
+        if value.data_type() == field.data_type() {
+            return Some(value.clone());
+        }
+        arrow_cast::cast(value, field.data_type()).ok()
+    }
+
+    fn partition_value(&self, column: &Column) -> Option<ArrayRef> {
+        let value = self.partition_values.get(column.name())?;
+        self.cast_to_column_type(column, value)
+            .filter(|value| value.null_count() == 0)
+    }
+
+    fn column_stats(&self, column: &Column) -> Option<&HudiColumnStatistics> {
+        self.column_stats?.columns.get(column.name())
+    }
+}
+
+impl PruningStatistics for FileSlicePruningStatistics<'_> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.partition_value(column).or_else(|| {
+            let min_value = self.column_stats(column)?.min_value.as_ref()?;
+            self.cast_to_column_type(column, min_value)
+        })
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.partition_value(column).or_else(|| {
+            let max_value = self.column_stats(column)?.max_value.as_ref()?;
+            self.cast_to_column_type(column, max_value)
+        })
+    }
+
+    fn num_containers(&self) -> usize {
+        1
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        let null_count = u64::try_from(self.column_stats(column)?.null_count?).ok()?;
+        Some(Arc::new(UInt64Array::from(vec![null_count])))
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        let num_rows = u64::try_from(self.column_stats?.num_rows?).ok()?;
+        Some(Arc::new(UInt64Array::from(vec![num_rows])))
+    }
+
+    fn contained(&self, column: &Column, values: &HashSet<ScalarValue>) -> Option<BooleanArray> {
+        let value = ScalarValue::try_from_array(&self.partition_value(column)?, 0).ok()?;
+        Some(BooleanArray::from(vec![values.contains(&value)]))
+    }
+}
+
+/// Returns `true` if the file slice has no records matching the predicate
+/// according to its statistics. Predicates that fail to be evaluated on the
+/// statistics do not prune the file slice.
+pub fn can_prune_file_slice(
+    predicate: &Arc<dyn PhysicalExpr>,
+    schema: &SchemaRef,
+    statistics: &FileSlicePruningStatistics,
+) -> bool {
+    let pruning_predicate = match PruningPredicate::try_new(predicate.clone(), schema.clone()) {
+        Ok(pruning_predicate) => pruning_predicate,
+        Err(e) => {
+            debug!("Failed to build pruning predicate from {predicate}: {e}");
+            return false;
+        }
+    };
+    if pruning_predicate.always_true() {
+        return false;
+    }
+    match pruning_predicate.prune(statistics) {
+        Ok(keep) => keep.iter().all(|keep| !keep),
+        Err(e) => {
+            debug!("Failed to prune file slice by {predicate}: {e}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use datafusion::logical_expr::Operator;
+    use datafusion::physical_expr::expressions::{BinaryExpr, col, is_null, lit};
+    use hudi_core::file_group::base_file::BaseFile;
+    use hudi_core::file_group::log_file::LogFile;
+    use hudi_core::statistics::StatsGranularity;
+    use std::str::FromStr;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("region", DataType::Utf8, true),
+        ]))
+    }
+
+    fn file_slice() -> FileSlice {
+        let base_file = BaseFile::from_str("fileA-0_0-1-1_20250101000000000.parquet").unwrap();
+        let mut file_slice = FileSlice::new(base_file, "region=eu".to_string());
+        let mut stats = StatisticsContainer::new(StatsGranularity::File);
+        stats.num_rows = Some(10);
+        stats.columns.insert(
+            "id".to_string(),
+            HudiColumnStatistics {
+                column_name: "id".to_string(),
+                data_type: DataType::Int32,
+                min_value: Some(Arc::new(Int32Array::from(vec![1])) as ArrayRef),
+                max_value: Some(Arc::new(Int32Array::from(vec![9])) as ArrayRef),
+                null_count: Some(0),
+            },
+        );
+        file_slice.base_file_column_stats = Some(stats);
+        file_slice
+    }
+
+    fn region_value(region: &str) -> HashMap<String, ArrayRef> {
+        let value: ArrayRef = Arc::new(StringArray::from(vec![region]));
+        HashMap::from([("region".to_string(), value)])
+    }
+
+    fn compare(column: &str, op: Operator, value: ScalarValue) -> Arc<dyn PhysicalExpr> {
+        let schema = schema();
+        Arc::new(BinaryExpr::new(
+            col(column, &schema).unwrap(),
+            op,
+            lit(value),
+        ))
+    }
+
+    #[test]
+    fn test_can_prune_file_slice_by_column_stats() {
+        let schema = schema();
+        let file_slice = file_slice();
+        let statistics = FileSlicePruningStatistics::new(&file_slice, HashMap::new(), &schema);
+
+        let id_gt_9 = compare("id", Operator::Gt, ScalarValue::Int64(Some(9)));
+        assert!(can_prune_file_slice(&id_gt_9, &schema, &statistics));
+        let id_gt_5 = compare("id", Operator::Gt, ScalarValue::Int64(Some(5)));
+        assert!(!can_prune_file_slice(&id_gt_5, &schema, &statistics));
+        let id_is_null = is_null(col("id", &schema).unwrap()).unwrap();
+        assert!(can_prune_file_slice(&id_is_null, &schema, &statistics));
+
+        // The records of the log files may be out of the ranges of the stats.
+        let mut file_slice = file_slice.clone();
+        let log_file = LogFile::from_str(".fileA-0_20250101000000000.log.1_0-1-1").unwrap();
+        file_slice.log_files.insert(log_file);
+        let statistics = FileSlicePruningStatistics::new(&file_slice, HashMap::new(), &schema);
+        assert!(!can_prune_file_slice(&id_gt_9, &schema, &statistics));
+    }
+
+    #[test]
+    fn test_can_prune_file_slice_by_partition_values() {
+        let schema = schema();
+        let mut file_slice = file_slice();
+        let log_file = LogFile::from_str(".fileA-0_20250101000000000.log.1_0-1-1").unwrap();
+        file_slice.log_files.insert(log_file);
+        let statistics = FileSlicePruningStatistics::new(&file_slice, region_value("eu"), &schema);
+
+        let region_us = compare("region", Operator::Eq, ScalarValue::from("us"));
+        assert!(can_prune_file_slice(&region_us, &schema, &statistics));
+        let region_eu = compare("region", Operator::Eq, ScalarValue::from("eu"));
+        assert!(!can_prune_file_slice(&region_eu, &schema, &statistics));
+        let values = HashSet::from([ScalarValue::from("us"), ScalarValue::from("apac")]);
+        assert_eq!(
+            statistics.contained(&Column::from_name("region"), &values),
+            Some(BooleanArray::from(vec![false]))
+        );
+
+        // A default partition may stand for null values.
+        let region_is_null = is_null(col("region", &schema).unwrap()).unwrap();
+        assert!(!can_prune_file_slice(&region_is_null, &schema, &statistics));
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+//! Conversion of Hudi column stats to DataFusion statistics.
+
+use arrow_schema::Schema;
+use datafusion_common::stats::Precision;
+use datafusion_common::{ColumnStatistics, ScalarValue, Statistics};
+use hudi_core::file_group::file_slice::FileSlice;
+use hudi_core::statistics::StatisticsContainer;
+
+use crate::inexact_usize_from_u64;
+
+/// Converts Hudi column stats to DataFusion column statistics of the fields
+/// of `schema`, in order.
+///
+/// Min/max values are always inexact, as writers may truncate them. Null
+/// counts are exact only if `exact` is set, i.e., the stats cover all the
+/// records to read. Fields without stats are unknown.
+pub fn to_column_statistics(
+    stats: &StatisticsContainer,
+    schema: &Schema,
+    exact: bool,
+) -> Vec<ColumnStatistics> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let Some(col_stats) = stats.columns.get(field.name()) else {
+                return ColumnStatistics::new_unknown();
+            };
+            let to_scalar = |value: &Option<arrow_array::ArrayRef>| {
+                value
+                    .as_ref()
+                    .and_then(|v| ScalarValue::try_from_array(v, 0).ok())
+                    .and_then(|v| v.cast_to(field.data_type()).ok())
+                    .filter(|v| !v.is_null())
+                    .map_or(Precision::Absent, Precision::Inexact)
+            };
+            let null_count = match col_stats.null_count.and_then(|n| usize::try_from(n).ok()) {
+                Some(n) if exact => Precision::Exact(n),
+                Some(n) => Precision::Inexact(n),
+                None => Precision::Absent,
+            };
+            ColumnStatistics {
+                null_count,
+                max_value: to_scalar(&col_stats.max_value),
+                min_value: to_scalar(&col_stats.min_value),
+                ..ColumnStatistics::new_unknown()
+            }
+        })
+        .collect()
+}
+
+/// Gets the statistics of a file slice from the column stats of its base
+/// file, or `None` if they are not loaded.
+///
+/// Row and null counts are exact for slices without log files, and inexact
+/// otherwise as the log files are not covered. The byte size is the on-disk
+/// size of the base file.
+pub fn file_slice_statistics(file_slice: &FileSlice, schema: &Schema) -> Option<Statistics> {
+    let stats = file_slice.base_file_column_stats.as_ref()?;
+    let exact = !file_slice.has_log_file();
+    let num_rows = match stats.num_rows.and_then(|n| usize::try_from(n).ok()) {
+        Some(n) if exact => Precision::Exact(n),
+        Some(n) => Precision::Inexact(n),
+        None => Precision::Absent,
+    };
+    let total_byte_size = match &file_slice.base_file.file_metadata {
+        Some(meta) if meta.size > 0 => inexact_usize_from_u64(meta.size),
+        _ => Precision::Absent,
+    };
+    Some(Statistics {
+        num_rows,
+        total_byte_size,
+        column_statistics: to_column_statistics(stats, schema, exact),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{ArrayRef, Int32Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use hudi_core::file_group::base_file::BaseFile;
+    use hudi_core::file_group::log_file::LogFile;
+    use hudi_core::statistics::{ColumnStatistics as HudiColumnStatistics, StatsGranularity};
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("other", DataType::Utf8, true),
+        ])
+    }
+
+    fn stats() -> StatisticsContainer {
+        let mut stats = StatisticsContainer::new(StatsGranularity::File);
+        stats.num_rows = Some(10);
+        stats.columns.insert(
+            "id".to_string(),
+            HudiColumnStatistics {
+                column_name: "id".to_string(),
+                data_type: DataType::Int32,
+                min_value: Some(Arc::new(Int32Array::from(vec![1])) as ArrayRef),
+                max_value: Some(Arc::new(Int32Array::from(vec![9])) as ArrayRef),
+                null_count: Some(0),
+            },
+        );
+        stats.columns.insert(
+            "name".to_string(),
+            HudiColumnStatistics {
+                column_name: "name".to_string(),
+                data_type: DataType::Utf8,
+                min_value: Some(Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+                max_value: None,
+                null_count: Some(2),
+            },
+        );
+        stats
+    }
+
+    #[test]
+    fn test_to_column_statistics() {
+        let columns = to_column_statistics(&stats(), &schema(), true);
+        assert_eq!(columns.len(), 3);
+        assert_eq!(
+            columns[0].min_value,
+            Precision::Inexact(ScalarValue::Int64(Some(1)))
+        );
+        assert_eq!(
+            columns[0].max_value,
+            Precision::Inexact(ScalarValue::Int64(Some(9)))
+        );
+        assert_eq!(columns[0].null_count, Precision::Exact(0));
+        assert_eq!(
+            columns[1].min_value,
+            Precision::Inexact(ScalarValue::Utf8(Some("a".to_string())))
+        );
+        assert_eq!(columns[1].max_value, Precision::Absent);
+        assert_eq!(columns[1].null_count, Precision::Exact(2));
+        assert_eq!(columns[2], ColumnStatistics::new_unknown());
+
+        let columns = to_column_statistics(&stats(), &schema(), false);
+        assert_eq!(columns[1].null_count, Precision::Inexact(2));
+    }
+
+    #[test]
+    fn test_file_slice_statistics() {
+        let base_file = BaseFile::from_str("fileA-0_0-1-1_20250101000000000.parquet").unwrap();
+        let mut file_slice = FileSlice::new(base_file, String::new());
+        assert!(file_slice_statistics(&file_slice, &schema()).is_none());
+
+        file_slice.base_file_column_stats = Some(stats());
+        let statistics = file_slice_statistics(&file_slice, &schema()).unwrap();
+        assert_eq!(statistics.num_rows, Precision::Exact(10));
+        assert_eq!(statistics.total_byte_size, Precision::Absent);
+        assert_eq!(statistics.column_statistics.len(), 3);
+
+        let log_file = LogFile::from_str(".fileA-0_20250101000000000.log.1_0-1-1").unwrap();
+        file_slice.log_files.insert(log_file);
+        let statistics = file_slice_statistics(&file_slice, &schema()).unwrap();
+        assert_eq!(statistics.num_rows, Precision::Inexact(10));
+        assert_eq!(
+            statistics.column_statistics[0].null_count,
+            Precision::Inexact(0)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use std::sync::Arc;
+
+use arrow::array::AsArray;
+use arrow::compute::cast;
+use arrow::datatypes::Int64Type;
+use arrow_schema::{DataType, Field, Schema};
+use datafusion::catalog::CatalogProvider;
+use datafusion::prelude::{SessionConfig, SessionContext};
+use hudi_core::config::table::HudiTableConfig::DatabaseName;
+use hudi_core::config::util::empty_options;
+use hudi_core::table::creator::TableCreator;
+use hudi_datafusion::HudiCatalogProvider;
+
+async fn create_table(base_path: &str, database_name: Option<&str>, table_name: &str) {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let mut creator = TableCreator::new(base_path, table_name, schema)
+        .with_record_key_fields(["id"])
+        .with_ordering_fields(["id"]);
+    if let Some(database_name) = database_name {
+        creator = creator.with_hudi_option(DatabaseName.as_ref(), database_name);
+    }
+    creator.create().await.unwrap();
+}
+
+async fn query_strings(ctx: &SessionContext, sql: &str) -> Vec<String> {
+    let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    let mut values = Vec::new();
+    for batch in &batches {
+        let column = cast(batch.column(0), &DataType::Utf8).unwrap();
+        let column = column.as_string::<i32>();
+        values.extend((0..batch.num_rows()).map(|i| column.value(i).to_string()));
+    }
+    values
+}
+
+#[tokio::test]
+async fn test_catalog_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_str().unwrap();
+    create_table(&format!("{root}/sales/orders"), Some("sales"), "orders").await;
+    create_table(
+        &format!("{root}/sales/customers"),
+        Some("sales"),
+        "customers",
+    )
+    .await;
+    create_table(&format!("{root}/trips"), None, "trips").await;
+
+    let catalog = HudiCatalogProvider::try_new(root, empty_options())
+        .await
+        .unwrap();
+    assert_eq!(catalog.schema_names(), vec!["default", "sales"]);
+    assert!(catalog.schema("missing").is_none());
+    let sales = catalog.schema("sales").unwrap();
+    assert_eq!(sales.table_names(), vec!["customers", "orders"]);
+    assert!(sales.table_exist("orders"));
+    assert!(!sales.table_exist("trips"));
+    assert!(sales.table("trips").await.unwrap().is_none());
+
+    let ctx = SessionContext::new_with_config(SessionConfig::new().with_information_schema(true));
+    ctx.register_catalog("lake", Arc::new(catalog));
+    assert_eq!(
+        query_strings(
+            &ctx,
+            "SELECT table_schema || '.' || table_name FROM information_schema.tables \
+             WHERE table_catalog = 'lake' AND table_type = 'BASE TABLE' ORDER BY 1"
+        )
+        .await,
+        vec!["default.trips", "sales.customers", "sales.orders"]
+    );
+
+    // Writes are visible to later lookups, which read the latest snapshot.
+    ctx.sql("INSERT INTO lake.sales.orders (id, name) VALUES (1, 'a'), (2, 'b')")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        query_strings(&ctx, "SELECT name FROM lake.sales.orders ORDER BY id").await,
+        vec!["a", "b"]
+    );
+    let batches = ctx
+        .sql("SELECT count(*) FROM lake.default.trips")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(batches[0].column(0).as_primitive::<Int64Type>().value(0), 0);
+
+    assert!(ctx.sql("SELECT * FROM lake.sales.trips").await.is_err());
+}
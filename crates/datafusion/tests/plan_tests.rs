@@ -27,6 +27,7 @@ use datafusion::catalog::TableProviderFactory;
 use datafusion::datasource::TableProvider;
 use datafusion::error::Result;
 use datafusion::execution::session_state::SessionStateBuilder;
+use datafusion::physical_plan::ExecutionPlan;
 use datafusion::prelude::{SessionConfig, SessionContext};
 use datafusion_common::{DataFusionError, ScalarValue};
 
@@ -450,8 +451,11 @@ mod v8_tests {
 
 mod dispatch_tests {
     use super::*;
+    use arrow::compute::concat_batches;
     use hudi_test::QuickstartTripsTable;
-    use hudi_test::SampleTable::{V6Nonpartitioned, V6SimplekeygenNonhivestyle};
+    use hudi_test::SampleTable::{
+        V6Nonpartitioned, V6SimplekeygenNonhivestyle, V8SimplekeygenHivestyleNoMetafields,
+    };
 
     #[tokio::test]
     async fn test_parquet_cow_uses_data_source_exec() {
@@ -575,6 +579,41 @@ mod dispatch_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_partitioned_parquet_mor_snapshot_prunes_file_slices_by_or_filter() {
+        let base_url = V6SimplekeygenNonhivestyle.url_to_mor_parquet();
+        let ctx = register_uri_as_table(
+            "partitioned_mor_snapshot",
+            base_url.as_str(),
+            empty_options(),
+        )
+        .await
+        .unwrap();
+
+        let sql = "SELECT id FROM partitioned_mor_snapshot WHERE byteField = 10 OR byteField >= 30";
+        let plan = explain_physical_plan(&ctx, sql).await;
+        assert!(
+            plan.contains("file_slices=2"),
+            "OR partition filter should reduce the scan to 2 file slices. Plan: {plan}"
+        );
+        assert!(
+            plan.contains("FilterExec"),
+            "Inexact OR filter should leave a residual FilterExec. Plan: {plan}"
+        );
+
+        let count_rows = |sql: &'static str| {
+            let ctx = ctx.clone();
+            async move {
+                let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+                batches.iter().map(|batch| batch.num_rows()).sum::<usize>()
+            }
+        };
+        assert_eq!(
+            count_rows(sql).await,
+            count_rows("SELECT id FROM partitioned_mor_snapshot WHERE byteField IN (10, 30)").await
+        );
+    }
+
     #[tokio::test]
     async fn test_lance_mor_snapshot_uses_hudi_scan_exec() {
         let base_url = QuickstartTripsTable::V9TripsLance.url_to_mor_avro();
@@ -637,6 +676,40 @@ mod dispatch_tests {
         let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
         assert_eq!(rows, 0);
     }
+
+    #[tokio::test]
+    async fn test_virtual_meta_fields_use_hudi_scan_exec() {
+        let table = V8SimplekeygenHivestyleNoMetafields;
+        let ctx = register_table_direct(&table, empty_options())
+            .await
+            .unwrap();
+
+        let data_only_sql = format!("SELECT id FROM {}", table.as_ref());
+        let plan = explain_physical_plan(&ctx, &data_only_sql).await;
+        assert!(
+            plan.contains("DataSourceExec"),
+            "Reading only data fields should use DataSourceExec. Plan: {plan}"
+        );
+
+        let sql = format!(
+            "SELECT _hoodie_record_key, _hoodie_partition_path FROM {} \
+             WHERE _hoodie_record_key IN ('1', '2') ORDER BY _hoodie_record_key",
+            table.as_ref()
+        );
+        let plan = explain_physical_plan(&ctx, &sql).await;
+        assert!(
+            plan.contains("HudiScanExec"),
+            "Reading virtual meta fields should use HudiScanExec. Plan: {plan}"
+        );
+
+        let batches = ctx.sql(&sql).await.unwrap().collect().await.unwrap();
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(get_str_column(&batch, "_hoodie_record_key"), ["1", "2"]);
+        assert_eq!(
+            get_str_column(&batch, "_hoodie_partition_path"),
+            ["byteField=10", "byteField=20"]
+        );
+    }
 }
 
 // ============================================================================
@@ -808,3 +881,454 @@ mod lance_tests {
         assert_eq!(txn_types, ["reversal", "transfer", "debit", "debit"]);
     }
 }
+
+// ============================================================================
+// Statistics Tests
+// ============================================================================
+
+mod statistics_tests {
+    use super::*;
+    use arrow_array::Int64Array;
+    use datafusion_common::stats::Precision;
+    use hudi_test::SampleTable::{V8Nonpartitioned, V9TxnsSimpleMeta};
+
+    async fn plan_statistics(ctx: &SessionContext, sql: &str) -> datafusion_common::Statistics {
+        let plan = ctx
+            .sql(sql)
+            .await
+            .unwrap()
+            .create_physical_plan()
+            .await
+            .unwrap();
+        plan.partition_statistics(None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_provider_statistics_from_mdt_column_stats() {
+        let hudi = HudiDataSource::new(V9TxnsSimpleMeta.url_to_cow().as_str())
+            .await
+            .unwrap();
+        let stats = hudi.statistics().unwrap();
+        assert_eq!(stats.num_rows, Precision::Inexact(12));
+
+        let schema = hudi.schema();
+        let region = &stats.column_statistics[schema.index_of("region").unwrap()];
+        assert_eq!(
+            region.min_value,
+            Precision::Inexact(ScalarValue::from("apac"))
+        );
+        assert_eq!(
+            region.max_value,
+            Precision::Inexact(ScalarValue::from("us"))
+        );
+        assert_eq!(region.null_count, Precision::Inexact(0));
+        let seqno =
+            &stats.column_statistics[schema.index_of(MetaField::CommitSeqno.as_ref()).unwrap()];
+        assert_eq!(seqno.min_value, Precision::Absent);
+    }
+
+    #[tokio::test]
+    async fn test_parquet_scan_statistics_from_mdt_column_stats() {
+        let test_table = V9TxnsSimpleMeta;
+        let ctx = register_table_direct(&test_table, empty_options())
+            .await
+            .unwrap();
+
+        // Row and null counts of base files are exact without filters
+        let sql = format!("SELECT txn_id, txn_ts FROM {}", test_table.as_ref());
+        let stats = plan_statistics(&ctx, &sql).await;
+        assert_eq!(stats.num_rows, Precision::Exact(12));
+        let txn_id = &stats.column_statistics[0];
+        assert_eq!(
+            txn_id.min_value,
+            Precision::Inexact(ScalarValue::from("TXN-001"))
+        );
+        assert_eq!(
+            txn_id.max_value,
+            Precision::Inexact(ScalarValue::from("TXN-018"))
+        );
+        assert_eq!(txn_id.null_count, Precision::Exact(0));
+        let txn_ts = &stats.column_statistics[1];
+        assert_eq!(
+            txn_ts.min_value,
+            Precision::Inexact(ScalarValue::Int64(Some(1700000000003)))
+        );
+        assert_eq!(
+            txn_ts.max_value,
+            Precision::Inexact(ScalarValue::Int64(Some(1700800000018)))
+        );
+
+        // Statistics cover the pruned file slices, and are inexact with filters
+        let sql = format!(
+            "SELECT txn_id, txn_ts FROM {} WHERE region = 'us'",
+            test_table.as_ref()
+        );
+        let stats = plan_statistics(&ctx, &sql).await;
+        assert_eq!(stats.num_rows, Precision::Inexact(5));
+        assert_eq!(
+            stats.column_statistics[1].max_value,
+            Precision::Inexact(ScalarValue::Int64(Some(1700800000017)))
+        );
+        assert_eq!(stats.column_statistics[0].null_count, Precision::Inexact(0));
+
+        let sql = format!(
+            "SELECT COUNT(*) AS cnt, MIN(txn_ts) AS min_ts FROM {}",
+            test_table.as_ref()
+        );
+        let batches = ctx.sql(&sql).await.unwrap().collect().await.unwrap();
+        let batch = batches.first().unwrap();
+        let column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .value(0)
+        };
+        assert_eq!(column("cnt"), 12);
+        assert_eq!(column("min_ts"), 1700000000003);
+    }
+
+    #[tokio::test]
+    async fn test_scan_statistics_without_column_stats() {
+        let test_table = V8Nonpartitioned;
+        let ctx = register_table_direct(&test_table, empty_options())
+            .await
+            .unwrap();
+        let sql = format!("SELECT id FROM {}", test_table.as_ref());
+        let stats = plan_statistics(&ctx, &sql).await;
+        assert_eq!(stats.num_rows, Precision::Absent);
+        assert_eq!(stats.column_statistics[0].min_value, Precision::Absent);
+    }
+}
+
+mod layout_tests {
+    use super::*;
+    use hudi_core::config::read::HudiReadConfig::AsOfTimestamp;
+    use hudi_core::metadata::replace_commit::{
+        CLUSTERING_SORT_COLUMNS_KEY, HoodieRequestedReplaceMetadata,
+    };
+    use hudi_test::SampleTable::V9TxnsSimpleMeta;
+    use std::path::Path;
+
+    const CLUSTERING_TIMESTAMP: &str = "20260307162454799";
+    const CLUSTERING_COMPLETION_TIMESTAMP: &str = "20260307162456102";
+
+    /// Extracts the sample table with its clustering plan rewritten to sort
+    /// the records by `sort_columns`.
+    fn table_clustered_by(sort_columns: &str) -> String {
+        let base_path = V9TxnsSimpleMeta.path_to_cow_fresh();
+        let plan_path = Path::new(&base_path)
+            .join(".hoodie/timeline")
+            .join(format!("{CLUSTERING_TIMESTAMP}.clustering.requested"));
+        let mut metadata =
+            HoodieRequestedReplaceMetadata::from_avro_bytes(&std::fs::read(&plan_path).unwrap())
+                .unwrap();
+        metadata
+            .clustering_plan
+            .get_or_insert_default()
+            .strategy
+            .get_or_insert_default()
+            .strategy_params
+            .get_or_insert_default()
+            .insert(
+                CLUSTERING_SORT_COLUMNS_KEY.to_string(),
+                sort_columns.to_string(),
+            );
+        std::fs::write(&plan_path, metadata.to_avro_bytes().unwrap()).unwrap();
+        base_path
+    }
+
+    #[tokio::test]
+    async fn test_parquet_scan_output_ordering_from_clustering() {
+        let base_path = table_clustered_by("txn_ts");
+
+        // All the file slices are written by the clustering as of its completion.
+        // Clustering sorts the records ascending with nulls first.
+        let ctx = register_uri_as_table(
+            "clustered",
+            &base_path,
+            [
+                (AsOfTimestamp.as_ref(), CLUSTERING_COMPLETION_TIMESTAMP),
+                (InputPartitions.as_ref(), "3"),
+            ],
+        )
+        .await
+        .unwrap();
+        let plan = explain_physical_plan(
+            &ctx,
+            "SELECT txn_id, txn_ts FROM clustered ORDER BY txn_ts NULLS FIRST",
+        )
+        .await;
+        assert!(
+            plan.contains("output_ordering=[txn_ts@1 ASC]"),
+            "Scan should declare the clustering sort order: {plan}"
+        );
+        assert!(
+            !plan.contains("SortExec"),
+            "Sorted partitions should only be merged: {plan}"
+        );
+
+        // The latest file slices include the base files of later commits.
+        let ctx = register_uri_as_table("clustered", &base_path, [(InputPartitions.as_ref(), "3")])
+            .await
+            .unwrap();
+        let plan = explain_physical_plan(
+            &ctx,
+            "SELECT txn_id, txn_ts FROM clustered ORDER BY txn_ts NULLS FIRST",
+        )
+        .await;
+        assert!(!plan.contains("output_ordering"), "{plan}");
+        assert!(plan.contains("SortExec"), "{plan}");
+    }
+
+    #[tokio::test]
+    async fn test_hudi_scan_hash_partitioning_by_partition_columns() {
+        let session = |preserve_file_partitions: usize| {
+            let config = SessionConfig::new().with_target_partitions(2).set_usize(
+                "datafusion.optimizer.preserve_file_partitions",
+                preserve_file_partitions,
+            );
+            SessionContext::new_with_config(config)
+        };
+        let sql = "SELECT region, COUNT(*) FROM t GROUP BY region";
+
+        let ctx = session(1);
+        let hudi = HudiDataSource::new(V9TxnsSimpleMeta.url_to_mor_avro().as_str())
+            .await
+            .unwrap();
+        ctx.register_table("t", Arc::new(hudi)).unwrap();
+        let plan = explain_physical_plan(&ctx, sql).await;
+        assert!(plan.contains("HudiScanExec"), "{plan}");
+        assert!(plan.contains("partitioning=Hash([region@0], 2)"), "{plan}");
+        assert!(
+            !plan.contains("RepartitionExec"),
+            "Partitions grouped by region need no repartitioning: {plan}"
+        );
+
+        // Grouping by partition is disabled when there are fewer partitions.
+        let ctx = session(4);
+        let hudi = HudiDataSource::new(V9TxnsSimpleMeta.url_to_mor_avro().as_str())
+            .await
+            .unwrap();
+        ctx.register_table("t", Arc::new(hudi)).unwrap();
+        let plan = explain_physical_plan(&ctx, sql).await;
+        let scan = plan.lines().find(|l| l.contains("HudiScanExec")).unwrap();
+        assert!(!scan.contains("partitioning=Hash"), "{plan}");
+        assert!(plan.contains("RepartitionExec"), "{plan}");
+
+        let batches = ctx
+            .sql("SELECT region, COUNT(*) AS n FROM t GROUP BY region ORDER BY region")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let grouped_ctx = session(1);
+        let hudi = HudiDataSource::new(V9TxnsSimpleMeta.url_to_mor_avro().as_str())
+            .await
+            .unwrap();
+        grouped_ctx.register_table("t", Arc::new(hudi)).unwrap();
+        let grouped_batches = grouped_ctx
+            .sql("SELECT region, COUNT(*) AS n FROM t GROUP BY region ORDER BY region")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            datafusion::arrow::util::pretty::pretty_format_batches(&batches)
+                .unwrap()
+                .to_string(),
+            datafusion::arrow::util::pretty::pretty_format_batches(&grouped_batches)
+                .unwrap()
+                .to_string()
+        );
+    }
+}
+
+/// Sums the metric of the `HudiScanExec` nodes in the executed plan.
+fn sum_scan_metric(plan: &Arc<dyn ExecutionPlan>, name: &str) -> usize {
+    let mut nodes = vec![plan.clone()];
+    let mut sum = 0;
+    while let Some(node) = nodes.pop() {
+        if node.name() == "HudiScanExec" {
+            sum += node
+                .metrics()
+                .and_then(|m| m.sum_by_name(name))
+                .map_or(0, |v| v.as_usize());
+        }
+        nodes.extend(node.children().into_iter().cloned());
+    }
+    sum
+}
+
+mod runtime_filter_tests {
+    use super::*;
+    use datafusion::arrow::util::pretty::pretty_format_batches;
+    use datafusion::physical_plan::{collect, displayable};
+    use hudi_test::SampleTable::V9TxnsSimpleMeta;
+
+    /// Runs the query, returning the formatted results, the plan and the number
+    /// of file slices pruned by the runtime filters of the scan.
+    async fn run_with_pruned_count(ctx: &SessionContext, sql: &str) -> (String, String, usize) {
+        let plan = ctx
+            .sql(sql)
+            .await
+            .unwrap()
+            .create_physical_plan()
+            .await
+            .unwrap();
+        let batches = collect(plan.clone(), ctx.task_ctx()).await.unwrap();
+        let pruned = sum_scan_metric(&plan, "file_slices_pruned_by_runtime_filters");
+        let results = pretty_format_batches(&batches).unwrap().to_string();
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        (results, plan, pruned)
+    }
+
+    async fn session(enable_dynamic_filter_pushdown: bool) -> SessionContext {
+        let config = SessionConfig::new().set_bool(
+            "datafusion.optimizer.enable_dynamic_filter_pushdown",
+            enable_dynamic_filter_pushdown,
+        );
+        let ctx = SessionContext::new_with_config(config);
+        let hudi = HudiDataSource::new(V9TxnsSimpleMeta.url_to_mor_avro().as_str())
+            .await
+            .unwrap();
+        ctx.register_table("t", Arc::new(hudi)).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_hudi_scan_prunes_file_slices_by_join_dynamic_filter() {
+        let ctx = session(true).await;
+        let disabled_ctx = session(false).await;
+
+        // The join on the partition column prunes the file slices of the other
+        // regions by their partition values, and the join on a data column
+        // prunes all the file slices by their column stats.
+        for (sql, expected_pruned) in [
+            (
+                "SELECT t.txn_id, t.region FROM (VALUES ('eu')) AS d(r) \
+                 JOIN t ON t.region = d.r ORDER BY t.txn_id",
+                3,
+            ),
+            (
+                "SELECT t.txn_id, t.region FROM (VALUES (1)) AS d(ts) \
+                 JOIN t ON t.txn_ts = d.ts ORDER BY t.txn_id",
+                5,
+            ),
+        ] {
+            let (results, plan, pruned) = run_with_pruned_count(&ctx, sql).await;
+            let scan = plan.lines().find(|l| l.contains("HudiScanExec")).unwrap();
+            assert!(scan.contains("runtime_filters=[DynamicFilter"), "{plan}");
+            assert_eq!(pruned, expected_pruned, "{plan}");
+
+            let (disabled_results, plan, pruned) = run_with_pruned_count(&disabled_ctx, sql).await;
+            assert!(!plan.contains("runtime_filters"), "{plan}");
+            assert_eq!(pruned, 0);
+            assert_eq!(results, disabled_results);
+        }
+
+        let (results, _, _) = run_with_pruned_count(
+            &ctx,
+            "SELECT t.txn_id FROM (VALUES ('eu')) AS d(r) JOIN t ON t.region = d.r ORDER BY t.txn_id",
+        )
+        .await;
+        assert!(results.contains("TXN-011"), "{results}");
+        assert!(results.contains("TXN-018"), "{results}");
+        assert!(!results.contains("TXN-001"), "{results}");
+    }
+}
+
+// ============================================================================
+// Limit and TopK Tests
+// ============================================================================
+
+mod limit_tests {
+    use super::*;
+    use datafusion::arrow::util::pretty::pretty_format_batches;
+    use datafusion::physical_plan::collect;
+    use hudi_core::config::read::HudiReadConfig::FileSliceReadConcurrency;
+    use hudi_test::SampleTable::V9TxnsSimpleMeta;
+
+    /// Runs the query, returning the formatted results and the sum of the
+    /// metric of the scan.
+    async fn run_with_scan_metric(ctx: &SessionContext, sql: &str, name: &str) -> (String, usize) {
+        let plan = ctx
+            .sql(sql)
+            .await
+            .unwrap()
+            .create_physical_plan()
+            .await
+            .unwrap();
+        let batches = collect(plan.clone(), ctx.task_ctx()).await.unwrap();
+        let results = pretty_format_batches(&batches).unwrap().to_string();
+        (results, sum_scan_metric(&plan, name))
+    }
+
+    /// Registers the table to be read by a single partition, one file slice
+    /// at a time.
+    async fn session(enable_sort_pushdown: bool) -> SessionContext {
+        let config = SessionConfig::new().with_target_partitions(1).set_bool(
+            "datafusion.optimizer.enable_sort_pushdown",
+            enable_sort_pushdown,
+        );
+        let ctx = SessionContext::new_with_config(config);
+        let hudi = HudiDataSource::new_with_options(
+            V9TxnsSimpleMeta.url_to_mor_avro().as_str(),
+            [(FileSliceReadConcurrency.as_ref(), "1")],
+        )
+        .await
+        .unwrap();
+        ctx.register_table("t", Arc::new(hudi)).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_hudi_scan_reads_enough_file_slices_for_limit() {
+        let ctx = session(true).await;
+
+        // The first file slice has enough records for the limit.
+        let (results, skipped) = run_with_scan_metric(
+            &ctx,
+            "SELECT txn_id FROM t LIMIT 1",
+            "file_slices_skipped_by_limit",
+        )
+        .await;
+        assert_eq!(skipped, 4);
+        assert_eq!(results.lines().filter(|l| l.contains("TXN-")).count(), 1);
+
+        let (results, skipped) = run_with_scan_metric(
+            &ctx,
+            "SELECT txn_id FROM t LIMIT 20",
+            "file_slices_skipped_by_limit",
+        )
+        .await;
+        assert_eq!(skipped, 0);
+        assert_eq!(results.lines().filter(|l| l.contains("TXN-")).count(), 12);
+    }
+
+    #[tokio::test]
+    async fn test_hudi_scan_reads_file_slices_by_stats_for_topk() {
+        let ctx = session(true).await;
+        let disabled_ctx = session(false).await;
+
+        // The file slice with the latest transactions is read first, so that
+        // the dynamic filter of TopK prunes the others by their column stats.
+        for sql in [
+            "SELECT txn_id, txn_ts FROM t ORDER BY txn_ts DESC LIMIT 1",
+            "SELECT txn_id, txn_ts FROM t ORDER BY txn_ts LIMIT 1",
+        ] {
+            let (results, pruned) =
+                run_with_scan_metric(&ctx, sql, "file_slices_pruned_by_runtime_filters").await;
+            assert_eq!(pruned, 4, "{sql}");
+
+            let (disabled_results, _) =
+                run_with_scan_metric(&disabled_ctx, sql, "file_slices_pruned_by_runtime_filters")
+                    .await;
+            assert_eq!(results, disabled_results);
+        }
+    }
+}
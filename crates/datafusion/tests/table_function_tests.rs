@@ -0,0 +1,462 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use std::sync::Arc;
+
+use arrow::array::AsArray;
+use arrow::compute::cast;
+use arrow::datatypes::Int64Type;
+use arrow_schema::{DataType, Field, Schema};
+use datafusion::physical_plan::collect;
+use datafusion::prelude::SessionContext;
+use hudi_core::config::table::TableTypeValue;
+use hudi_core::config::util::empty_options;
+use hudi_core::table::Table;
+use hudi_core::table::creator::TableCreator;
+use hudi_datafusion::{
+    HudiDataSource, HudiTableFactory, plan_merge_into, register_table_functions,
+};
+
+/// Creates a table with 3 commits, returning their timestamps:
+/// 1. inserts `a`, `b` and `c`;
+/// 2. updates `a` to `x` and inserts `d`;
+/// 3. updates `x` to `y`.
+async fn create_table_with_commits(base_path: &str, table_type: TableTypeValue) -> Vec<String> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("ts", DataType::Int64, true),
+    ]));
+    TableCreator::new(base_path, "trips", schema)
+        .with_table_type(table_type)
+        .with_record_key_fields(["id"])
+        .with_partition_fields(["city"])
+        .with_ordering_fields(["ts"])
+        .create()
+        .await
+        .unwrap();
+
+    let ctx = SessionContext::new();
+    HudiDataSource::new(base_path)
+        .await
+        .unwrap()
+        .register(&ctx, "trips")
+        .unwrap();
+    ctx.sql("INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1), (3, 'c', 'sf', 1)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx.sql("CREATE TABLE changes (id BIGINT, name VARCHAR, city VARCHAR) AS VALUES (1, 'x', 'sf'), (4, 'd', 'la')")
+        .await
+        .unwrap();
+    let merge = plan_merge_into(
+        &ctx,
+        "MERGE INTO trips t USING changes s ON t.id = s.id \
+         WHEN MATCHED THEN UPDATE SET name = s.name, ts = 2 \
+         WHEN NOT MATCHED THEN INSERT (id, name, city, ts) VALUES (s.id, s.name, s.city, 2)",
+    )
+    .await
+    .unwrap();
+    collect(merge, ctx.task_ctx()).await.unwrap();
+    ctx.sql("UPDATE trips SET name = 'y', ts = 3 WHERE id = 1")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let table = Table::new(base_path).await.unwrap();
+    table
+        .get_timeline()
+        .completed_commits
+        .iter()
+        .map(|instant| instant.timestamp.clone())
+        .collect()
+}
+
+/// Query the `(id, name)` rows ordered by `id`.
+async fn query_rows(ctx: &SessionContext, from: &str) -> Vec<(i64, String)> {
+    let batches = ctx
+        .sql(&format!("SELECT id, name FROM {from} ORDER BY id"))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let mut rows = Vec::new();
+    for batch in &batches {
+        let ids = batch.column(0).as_primitive::<Int64Type>();
+        let names = cast(batch.column(1), &DataType::Utf8).unwrap();
+        for i in 0..batch.num_rows() {
+            rows.push((ids.value(i), names.as_string::<i32>().value(i).to_string()));
+        }
+    }
+    rows
+}
+
+fn rows(expected: &[(i64, &str)]) -> Vec<(i64, String)> {
+    expected.iter().map(|(i, n)| (*i, n.to_string())).collect()
+}
+
+#[tokio::test]
+async fn test_table_changes() {
+    for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let commits = create_table_with_commits(base_path, table_type).await;
+        assert_eq!(commits.len(), 3);
+        let ctx = SessionContext::new();
+        register_table_functions(&ctx, empty_options());
+
+        assert_eq!(
+            query_rows(
+                &ctx,
+                &format!("hudi_table_changes('{base_path}', '{}')", commits[0])
+            )
+            .await,
+            rows(&[(1, "y"), (4, "d")])
+        );
+        assert_eq!(
+            query_rows(
+                &ctx,
+                &format!(
+                    "hudi_table_changes('{base_path}', '{}', '{}')",
+                    commits[0], commits[1]
+                )
+            )
+            .await,
+            rows(&[(1, "x"), (4, "d")])
+        );
+        assert_eq!(
+            query_rows(
+                &ctx,
+                &format!("hudi_table_changes('{base_path}', {})", commits[2])
+            )
+            .await,
+            rows(&[])
+        );
+        assert_eq!(
+            query_rows(&ctx, &format!("hudi_table_changes('{base_path}', 0)")).await,
+            rows(&[(1, "y"), (2, "b"), (3, "c"), (4, "d")])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_as_of() {
+    for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let commits = create_table_with_commits(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register_table_functions(&ctx, empty_options());
+
+        for (commit, expected) in [
+            (&commits[0], rows(&[(1, "a"), (2, "b"), (3, "c")])),
+            (&commits[1], rows(&[(1, "x"), (2, "b"), (3, "c"), (4, "d")])),
+            (&commits[2], rows(&[(1, "y"), (2, "b"), (3, "c"), (4, "d")])),
+        ] {
+            assert_eq!(
+                query_rows(
+                    &ctx,
+                    &format!("hudi_snapshot_as_of('{base_path}', '{commit}')")
+                )
+                .await,
+                expected
+            );
+        }
+
+        // The snapshot of each query can be joined with other snapshots.
+        let batches = ctx
+            .sql(&format!(
+                "SELECT cur.name FROM hudi_snapshot_as_of('{base_path}', '{}') prev \
+                 JOIN hudi_snapshot_as_of('{base_path}', '{}') cur ON prev.id = cur.id \
+                 WHERE prev.name <> cur.name",
+                commits[0], commits[2]
+            ))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(num_rows, 1);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_functions_in_multi_thread_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    let commits = create_table_with_commits(base_path, TableTypeValue::MergeOnRead).await;
+    let ctx = SessionContext::new();
+    register_table_functions(&ctx, empty_options());
+
+    assert_eq!(
+        query_rows(
+            &ctx,
+            &format!("hudi_table_changes('{base_path}', '{}')", commits[1])
+        )
+        .await,
+        rows(&[(1, "y")])
+    );
+    assert_eq!(
+        query_rows(
+            &ctx,
+            &format!("hudi_snapshot_as_of('{base_path}', '{}')", commits[0])
+        )
+        .await,
+        rows(&[(1, "a"), (2, "b"), (3, "c")])
+    );
+}
+
+#[tokio::test]
+async fn test_table_functions_reject_invalid_arguments() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table_with_commits(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register_table_functions(&ctx, empty_options());
+
+    for from in [
+        format!("hudi_table_changes('{base_path}')"),
+        format!("hudi_table_changes('{base_path}', 0, 1, 2)"),
+        format!("hudi_table_changes('{base_path}', NULL)"),
+        "hudi_table_changes(1, 0)".to_string(),
+        format!("hudi_snapshot_as_of('{base_path}')"),
+        format!("hudi_snapshot_as_of('{base_path}', 1.5)"),
+    ] {
+        assert!(
+            ctx.sql(&format!("SELECT * FROM {from}")).await.is_err(),
+            "{from} should fail"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_external_table_with_query_options() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    let commits = create_table_with_commits(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    ctx.state_ref()
+        .write()
+        .table_factories_mut()
+        .insert("HUDI".to_string(), Arc::new(HudiTableFactory::new()));
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE trips_changes STORED AS HUDI LOCATION '{base_path}' \
+         OPTIONS ('hoodie.read.query.type' 'incremental', 'hoodie.read.start.timestamp' '{}')",
+        commits[1]
+    ))
+    .await
+    .unwrap();
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE trips_as_of STORED AS HUDI LOCATION '{base_path}' \
+         OPTIONS ('hoodie.read.as.of.timestamp' '{}')",
+        commits[0]
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(query_rows(&ctx, "trips_changes").await, rows(&[(1, "y")]));
+    assert_eq!(
+        query_rows(&ctx, "trips_as_of").await,
+        rows(&[(1, "a"), (2, "b"), (3, "c")])
+    );
+    // The rows of past snapshots cannot be modified.
+    let result = match ctx.sql("DELETE FROM trips_as_of").await {
+        Ok(df) => df.collect().await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    assert!(result.is_err());
+}
+
+/// Query the rows of non-null values, cast to strings.
+async fn query_values(ctx: &SessionContext, sql: &str) -> Vec<Vec<String>> {
+    let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    let mut rows = Vec::new();
+    for batch in &batches {
+        let columns: Vec<_> = batch
+            .columns()
+            .iter()
+            .map(|c| cast(c, &DataType::Utf8).unwrap())
+            .collect();
+        for i in 0..batch.num_rows() {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|c| c.as_string::<i32>().value(i).to_string())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+fn values(expected: &[&[&str]]) -> Vec<Vec<String>> {
+    expected
+        .iter()
+        .map(|row| row.iter().map(|v| v.to_string()).collect())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_timeline() {
+    for (table_type, action) in [
+        (TableTypeValue::CopyOnWrite, "commit"),
+        (TableTypeValue::MergeOnRead, "deltacommit"),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let commits = create_table_with_commits(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register_table_functions(&ctx, empty_options());
+
+        let rows = query_values(
+            &ctx,
+            &format!(
+                "SELECT timestamp, action, state, completion_timestamp IS NOT NULL, \
+                 epoch_millis > 0 FROM hudi_timeline('{base_path}')"
+            ),
+        )
+        .await;
+        let mut expected = Vec::new();
+        for commit in &commits {
+            for (state, completed) in [
+                ("requested", "false"),
+                ("inflight", "false"),
+                ("completed", "true"),
+            ] {
+                expected.push(
+                    [commit.as_str(), action, state, completed, "true"]
+                        .map(String::from)
+                        .to_vec(),
+                );
+            }
+        }
+        assert_eq!(rows, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_file_slices() {
+    for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let commits = create_table_with_commits(base_path, table_type.clone()).await;
+        let ctx = SessionContext::new();
+        register_table_functions(&ctx, empty_options());
+
+        let sql = "SELECT partition_path, array_length(log_files), base_file LIKE file_id || '%', \
+                   base_file_size > 0, total_size = base_file_size + log_files_size \
+                   FROM hudi_file_slices";
+        let rows = query_values(&ctx, &format!("{sql}('{base_path}')")).await;
+        // The updates are merged into the base file of COW tables, and
+        // appended as log files of MOR tables.
+        let num_log_files = match table_type {
+            TableTypeValue::CopyOnWrite => "0",
+            TableTypeValue::MergeOnRead => "2",
+        };
+        assert_eq!(
+            rows,
+            values(&[
+                &["la", "0", "true", "true", "true"],
+                &["nyc", "0", "true", "true", "true"],
+                &["sf", num_log_files, "true", "true", "true"],
+            ])
+        );
+
+        let rows = query_values(
+            &ctx,
+            &format!(
+                "SELECT partition_path, commit_timestamp FROM hudi_file_slices('{base_path}', '{}')",
+                commits[0]
+            ),
+        )
+        .await;
+        assert_eq!(rows, values(&[&["nyc", &commits[0]], &["sf", &commits[0]]]));
+    }
+}
+
+#[tokio::test]
+async fn test_commit_stats() {
+    for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let commits = create_table_with_commits(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register_table_functions(&ctx, empty_options());
+
+        let rows = query_values(
+            &ctx,
+            &format!(
+                "SELECT timestamp, operation_type, partition_path, num_files, num_inserts, \
+                 num_update_writes, num_deletes, total_write_bytes > 0, total_write_errors \
+                 FROM hudi_commit_stats('{base_path}')"
+            ),
+        )
+        .await;
+        assert_eq!(
+            rows,
+            values(&[
+                &[
+                    &commits[0],
+                    "INSERT",
+                    "nyc",
+                    "1",
+                    "1",
+                    "0",
+                    "0",
+                    "true",
+                    "0"
+                ],
+                &[&commits[0], "INSERT", "sf", "1", "2", "0", "0", "true", "0"],
+                &[&commits[1], "UPSERT", "la", "1", "1", "0", "0", "true", "0"],
+                &[&commits[1], "UPSERT", "sf", "1", "0", "1", "0", "true", "0"],
+                &[&commits[2], "UPSERT", "sf", "1", "0", "1", "0", "true", "0"],
+            ])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_inspection_functions_reject_invalid_arguments() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table_with_commits(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register_table_functions(&ctx, empty_options());
+
+    for from in [
+        "hudi_timeline()".to_string(),
+        format!("hudi_timeline('{base_path}', 0)"),
+        format!("hudi_file_slices('{base_path}', 0, 1)"),
+        format!("hudi_file_slices('{base_path}', 'invalid')"),
+        format!("hudi_commit_stats('{base_path}', 0)"),
+        format!("hudi_commit_stats('{base_path}/nonexistent')"),
+    ] {
+        assert!(
+            ctx.sql(&format!("SELECT * FROM {from}")).await.is_err(),
+            "{from} should fail"
+        );
+    }
+}
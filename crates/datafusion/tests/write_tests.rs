@@ -0,0 +1,704 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+//! DataFusion write tests for Hudi tables.
+
+use std::sync::Arc;
+
+use arrow::array::AsArray;
+use arrow::compute::cast;
+use arrow::datatypes::{Int64Type, UInt64Type};
+use arrow_array::RecordBatch;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use datafusion::error::DataFusionError;
+use datafusion::execution::SessionStateBuilder;
+use datafusion::physical_plan::collect;
+use datafusion::prelude::SessionContext;
+use hudi_core::config::table::TableTypeValue;
+use hudi_core::config::write::HudiWriteConfig::{MaxBufferedBytes, Operation};
+use hudi_core::table::Table;
+use hudi_core::table::creator::TableCreator;
+use hudi_datafusion::{HudiDataSource, plan_merge_into, with_hudi};
+
+fn data_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("ts", DataType::Int64, true),
+    ]))
+}
+
+const BUCKET_OPTIONS: [(&str, &str); 2] = [
+    ("hoodie.index.type", "BUCKET"),
+    ("hoodie.bucket.index.num.buckets", "2"),
+];
+
+async fn create_table(base_path: &str, table_type: TableTypeValue) {
+    TableCreator::new(base_path, "trips", data_schema())
+        .with_table_type(table_type)
+        .with_record_key_fields(["id"])
+        .with_partition_fields(["city"])
+        .with_ordering_fields(["ts"])
+        .create()
+        .await
+        .unwrap();
+}
+
+async fn register(ctx: &SessionContext, base_path: &str, options: &[(&str, &str)]) {
+    HudiDataSource::new_with_options(base_path, options.iter().copied())
+        .await
+        .unwrap()
+        .register(ctx, "trips")
+        .unwrap();
+}
+
+async fn execute(ctx: &SessionContext, sql: &str) -> Vec<RecordBatch> {
+    ctx.sql(sql).await.unwrap().collect().await.unwrap()
+}
+
+fn count(batches: &[RecordBatch]) -> u64 {
+    batches[0].column(0).as_primitive::<UInt64Type>().value(0)
+}
+
+/// Read the `(city, id, name)` rows of the table with a new table provider.
+async fn read_rows(base_path: &str) -> Vec<(String, i64, String)> {
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+    let batches = execute(
+        &ctx,
+        "SELECT city, id, name FROM trips ORDER BY city, id, name",
+    )
+    .await;
+    let mut rows = Vec::new();
+    for batch in &batches {
+        let cities = cast(batch.column(0), &DataType::Utf8).unwrap();
+        let ids = batch.column(1).as_primitive::<Int64Type>();
+        let names = cast(batch.column(2), &DataType::Utf8).unwrap();
+        for i in 0..batch.num_rows() {
+            rows.push((
+                cities.as_string::<i32>().value(i).to_string(),
+                ids.value(i),
+                names.as_string::<i32>().value(i).to_string(),
+            ));
+        }
+    }
+    rows
+}
+
+fn rows(expected: &[(&str, i64, &str)]) -> Vec<(String, i64, String)> {
+    expected
+        .iter()
+        .map(|(c, i, n)| (c.to_string(), *i, n.to_string()))
+        .collect()
+}
+
+async fn merge_into(ctx: &SessionContext, sql: &str) -> Vec<RecordBatch> {
+    let plan = plan_merge_into(ctx, sql).await.unwrap();
+    collect(plan, ctx.task_ctx()).await.unwrap()
+}
+
+async fn latest_action(base_path: &str) -> String {
+    let table = Table::new(base_path).await.unwrap();
+    let instant = table.get_timeline().completed_commits.last().unwrap();
+    instant.action.as_ref().to_string()
+}
+
+#[tokio::test]
+async fn test_insert_into_appends_records() {
+    for (table_type, action) in [
+        (TableTypeValue::CopyOnWrite, "commit"),
+        (TableTypeValue::MergeOnRead, "deltacommit"),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        create_table(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register(&ctx, base_path, &[]).await;
+
+        let inserted = execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+        )
+        .await;
+        assert_eq!(count(&inserted), 2);
+        assert_eq!(latest_action(base_path).await, action);
+
+        // Records with existing keys are inserted as duplicates, through the same provider.
+        let inserted = execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) SELECT id + 2, name, city, ts FROM \
+             (VALUES (1, 'c', 'sf', 2), (-1, 'd', 'sf', 2)) AS t(id, name, city, ts)",
+        )
+        .await;
+        assert_eq!(count(&inserted), 2);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[
+                ("nyc", 2, "b"),
+                ("sf", 1, "a"),
+                ("sf", 1, "d"),
+                ("sf", 3, "c")
+            ])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_insert_into_upserts_records_with_bucket_index() {
+    for table_type in [TableTypeValue::CopyOnWrite, TableTypeValue::MergeOnRead] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        create_table(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register(&ctx, base_path, &BUCKET_OPTIONS).await;
+
+        execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+        )
+        .await;
+        execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES (1, 'c', 'sf', 2), (3, 'd', 'sf', 2)",
+        )
+        .await;
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[("nyc", 2, "b"), ("sf", 1, "c"), ("sf", 3, "d")])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_insert_into_with_configured_operation() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[(Operation.as_ref(), "upsert")]).await;
+
+    // Upserting requires the bucket index.
+    let result = ctx
+        .sql("INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(result.is_err());
+    let table = Table::new(base_path).await.unwrap();
+    assert!(table.get_timeline().completed_commits.is_empty());
+}
+
+#[tokio::test]
+async fn test_insert_into_fails_above_max_buffered_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[(MaxBufferedBytes.as_ref(), "65536")]).await;
+
+    let values = (1..=5000)
+        .map(|i| format!("({i}, 'name-{i}', 'sf', 1)"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let err = ctx
+        .sql(&format!(
+            "INSERT INTO trips (id, name, city, ts) VALUES {values}"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(MaxBufferedBytes.as_ref()), "{err}");
+    let table = Table::new(base_path).await.unwrap();
+    assert!(table.get_timeline().completed_commits.is_empty());
+
+    let inserted = execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)",
+    )
+    .await;
+    assert_eq!(count(&inserted), 1);
+}
+
+#[tokio::test]
+async fn test_insert_overwrite_replaces_written_partitions() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+    )
+    .await;
+    let inserted = execute(
+        &ctx,
+        "INSERT OVERWRITE trips (id, name, city, ts) VALUES (3, 'c', 'sf', 2), (4, 'd', 'la', 2)",
+    )
+    .await;
+    assert_eq!(count(&inserted), 2);
+    assert_eq!(latest_action(base_path).await, "replacecommit");
+    assert_eq!(
+        read_rows(base_path).await,
+        rows(&[("la", 4, "d"), ("nyc", 2, "b"), ("sf", 3, "c")])
+    );
+}
+
+#[tokio::test]
+async fn test_insert_overwrite_without_records() {
+    for partitioned in [true, false] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        let partition_fields: &[&str] = if partitioned { &["city"] } else { &[] };
+        TableCreator::new(base_path, "trips", data_schema())
+            .with_record_key_fields(["id"])
+            .with_partition_fields(partition_fields.iter().copied())
+            .with_ordering_fields(["ts"])
+            .create()
+            .await
+            .unwrap();
+        let ctx = SessionContext::new();
+        register(&ctx, base_path, &[]).await;
+        execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+        )
+        .await;
+
+        let inserted = execute(
+            &ctx,
+            "INSERT OVERWRITE trips (id, name, city, ts) \
+             SELECT * FROM (VALUES (3, 'c', 'sf', 2)) AS t (id, name, city, ts) WHERE id < 0",
+        )
+        .await;
+        assert_eq!(count(&inserted), 0);
+        assert_eq!(latest_action(base_path).await, "replacecommit");
+        let expected = if partitioned {
+            rows(&[("nyc", 2, "b"), ("sf", 1, "a")])
+        } else {
+            Vec::new()
+        };
+        assert_eq!(read_rows(base_path).await, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_delete_and_update() {
+    for (table_type, options, action) in [
+        (TableTypeValue::CopyOnWrite, &[][..], "commit"),
+        (TableTypeValue::MergeOnRead, &[][..], "deltacommit"),
+        (TableTypeValue::CopyOnWrite, &BUCKET_OPTIONS[..], "commit"),
+        (
+            TableTypeValue::MergeOnRead,
+            &BUCKET_OPTIONS[..],
+            "deltacommit",
+        ),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        create_table(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register(&ctx, base_path, options).await;
+        execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES \
+             (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1), (3, 'c', 'sf', 1), (4, 'd', 'la', 1)",
+        )
+        .await;
+
+        // The statements see the commits through the same provider.
+        let updated = execute(
+            &ctx,
+            "UPDATE trips SET name = upper(name), ts = ts + 1 WHERE city = 'sf' AND id > 1",
+        )
+        .await;
+        assert_eq!(count(&updated), 1);
+        assert_eq!(latest_action(base_path).await, action);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[
+                ("la", 4, "d"),
+                ("nyc", 2, "b"),
+                ("sf", 1, "a"),
+                ("sf", 3, "C")
+            ])
+        );
+
+        let deleted = execute(&ctx, "DELETE FROM trips WHERE name IN ('b', 'C')").await;
+        assert_eq!(count(&deleted), 2);
+        assert_eq!(latest_action(base_path).await, action);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[("la", 4, "d"), ("sf", 1, "a")])
+        );
+
+        let updated = execute(&ctx, "UPDATE trips SET name = 'x'").await;
+        assert_eq!(count(&updated), 2);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[("la", 4, "x"), ("sf", 1, "x")])
+        );
+
+        let deleted = execute(&ctx, "DELETE FROM trips WHERE city = 'nyc'").await;
+        assert_eq!(count(&deleted), 0);
+        let deleted = execute(&ctx, "DELETE FROM trips").await;
+        assert_eq!(count(&deleted), 2);
+        assert!(read_rows(base_path).await.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_update_rejects_record_identifying_columns() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)",
+    )
+    .await;
+
+    for sql in [
+        "UPDATE trips SET id = 2",
+        "UPDATE trips SET city = 'la'",
+        "UPDATE trips SET _hoodie_record_key = '2'",
+    ] {
+        let result = match ctx.sql(sql).await {
+            Ok(df) => df.collect().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        assert!(result.is_err(), "{sql} should fail");
+    }
+    assert_eq!(read_rows(base_path).await, rows(&[("sf", 1, "a")]));
+}
+
+#[tokio::test]
+async fn test_delete_and_update_without_dml_filter_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    let hudi = HudiDataSource::new(base_path).await.unwrap();
+    ctx.register_table("trips", Arc::new(hudi)).unwrap();
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+    )
+    .await;
+
+    // Without the rule, the partition filters would not reach the statements.
+    for sql in [
+        "DELETE FROM trips WHERE city = 'sf'",
+        "UPDATE trips SET name = 'x' WHERE city = 'sf'",
+    ] {
+        let result = match ctx.sql(sql).await {
+            Ok(df) => df.collect().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        let err = result.expect_err(sql).to_string();
+        assert!(err.contains("HudiDataSource::register()"), "{err}");
+    }
+    assert_eq!(
+        read_rows(base_path).await,
+        rows(&[("nyc", 2, "b"), ("sf", 1, "a")])
+    );
+
+    ctx.deregister_table("trips").unwrap();
+    register(&ctx, base_path, &[]).await;
+    let deleted = execute(&ctx, "DELETE FROM trips WHERE city = 'sf'").await;
+    assert_eq!(count(&deleted), 1);
+    assert_eq!(read_rows(base_path).await, rows(&[("nyc", 2, "b")]));
+}
+
+#[tokio::test]
+async fn test_delete_and_update_in_session_with_hudi() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let state = with_hudi(SessionStateBuilder::new().with_default_features()).build();
+    let ctx = SessionContext::new_with_state(state);
+    execute(
+        &ctx,
+        &format!("CREATE EXTERNAL TABLE trips STORED AS HUDI LOCATION '{base_path}'"),
+    )
+    .await;
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1)",
+    )
+    .await;
+
+    let updated = execute(&ctx, "UPDATE trips SET name = 'x' WHERE city = 'nyc'").await;
+    assert_eq!(count(&updated), 1);
+    let deleted = execute(&ctx, "DELETE FROM trips WHERE city = 'sf'").await;
+    assert_eq!(count(&deleted), 1);
+    assert_eq!(read_rows(base_path).await, rows(&[("nyc", 2, "x")]));
+}
+
+#[tokio::test]
+async fn test_explain_delete_and_update() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::MergeOnRead).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)",
+    )
+    .await;
+
+    for (sql, write_op) in [
+        ("DELETE FROM trips WHERE name = 'a'", "write_op=Delete"),
+        (
+            "UPDATE trips SET name = 'b' WHERE name = 'a'",
+            "write_op=Update",
+        ),
+    ] {
+        let plan = ctx
+            .sql(sql)
+            .await
+            .unwrap()
+            .create_physical_plan()
+            .await
+            .unwrap();
+        let plan = datafusion::physical_plan::displayable(plan.as_ref())
+            .indent(false)
+            .to_string();
+        assert!(plan.contains(write_op), "{plan}");
+        assert!(plan.contains("FilterExec"), "{plan}");
+    }
+    // Planning does not write.
+    assert_eq!(read_rows(base_path).await, rows(&[("sf", 1, "a")]));
+}
+
+#[tokio::test]
+async fn test_merge_into() {
+    for (table_type, options, action) in [
+        (TableTypeValue::CopyOnWrite, &[][..], "commit"),
+        (TableTypeValue::MergeOnRead, &[][..], "deltacommit"),
+        (TableTypeValue::CopyOnWrite, &BUCKET_OPTIONS[..], "commit"),
+        (
+            TableTypeValue::MergeOnRead,
+            &BUCKET_OPTIONS[..],
+            "deltacommit",
+        ),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        create_table(base_path, table_type).await;
+        let ctx = SessionContext::new();
+        register(&ctx, base_path, options).await;
+        execute(
+            &ctx,
+            "INSERT INTO trips (id, name, city, ts) VALUES \
+             (1, 'a', 'sf', 1), (2, 'b', 'nyc', 1), (3, 'c', 'sf', 1), (4, 'd', 'la', 1)",
+        )
+        .await;
+        execute(
+            &ctx,
+            "CREATE TABLE changes (id BIGINT, name VARCHAR, city VARCHAR, op VARCHAR) AS VALUES \
+             (1, 'x', 'sf', 'U'), (2, NULL, 'nyc', 'D'), (4, 'y', 'la', 'N'), \
+             (5, 'e', 'sf', 'I'), (6, 'f', 'nyc', 'N')",
+        )
+        .await;
+        let num_commits = Table::new(base_path)
+            .await
+            .unwrap()
+            .get_timeline()
+            .completed_commits
+            .len();
+
+        let merged = merge_into(
+            &ctx,
+            "MERGE INTO trips t USING changes s ON t.id = s.id \
+             WHEN MATCHED AND s.op = 'D' THEN DELETE \
+             WHEN MATCHED AND s.op = 'U' THEN UPDATE SET name = s.name, ts = t.ts + 1 \
+             WHEN NOT MATCHED AND s.op = 'I' THEN INSERT (id, name, city, ts) \
+             VALUES (s.id, s.name, s.city, 1)",
+        )
+        .await;
+        // Rows matching no clause are left out.
+        assert_eq!(count(&merged), 3);
+        let timeline = Table::new(base_path).await.unwrap().get_timeline().clone();
+        assert_eq!(timeline.completed_commits.len(), num_commits + 1);
+        assert_eq!(latest_action(base_path).await, action);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[
+                ("la", 4, "d"),
+                ("sf", 1, "x"),
+                ("sf", 3, "c"),
+                ("sf", 5, "e")
+            ])
+        );
+
+        // The statements see the commits through the same provider.
+        let merged = merge_into(
+            &ctx,
+            "MERGE INTO trips USING (SELECT id, name, city, 2 AS ts FROM changes WHERE op = 'N') AS s \
+             ON trips.id = s.id \
+             WHEN MATCHED THEN UPDATE SET name = upper(s.name), ts = trips.ts + 1 \
+             WHEN NOT MATCHED THEN INSERT ROW",
+        )
+        .await;
+        assert_eq!(count(&merged), 2);
+        assert_eq!(
+            read_rows(base_path).await,
+            rows(&[
+                ("la", 4, "Y"),
+                ("nyc", 6, "f"),
+                ("sf", 1, "x"),
+                ("sf", 3, "c"),
+                ("sf", 5, "e")
+            ])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_merge_into_rejects_invalid_statements() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)",
+    )
+    .await;
+    execute(
+        &ctx,
+        "CREATE TABLE changes (id BIGINT, name VARCHAR) AS VALUES (1, 'x')",
+    )
+    .await;
+
+    for sql in [
+        "SELECT * FROM trips",
+        "MERGE INTO changes c USING trips t ON c.id = t.id WHEN MATCHED THEN DELETE",
+        "MERGE INTO trips t USING changes s ON t.name = s.name WHEN MATCHED THEN DELETE",
+        "MERGE INTO trips t USING changes s ON t.id = s.id WHEN MATCHED THEN UPDATE SET city = 'la'",
+        "MERGE INTO trips t USING changes s ON t.id = s.id WHEN MATCHED THEN INSERT ROW",
+        "MERGE INTO trips t USING changes s ON t.id = s.id WHEN NOT MATCHED THEN INSERT ROW",
+        "MERGE INTO trips t USING changes s ON t.id = s.id WHEN NOT MATCHED THEN DELETE",
+        "MERGE INTO trips t USING changes s ON t.id = s.id \
+         WHEN NOT MATCHED THEN INSERT (id, unknown) VALUES (s.id, s.name)",
+    ] {
+        assert!(
+            plan_merge_into(&ctx, sql).await.is_err(),
+            "{sql} should fail"
+        );
+    }
+    assert_eq!(read_rows(base_path).await, rows(&[("sf", 1, "a")]));
+}
+
+#[tokio::test]
+async fn test_merge_into_requires_record_key_condition() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+    execute(
+        &ctx,
+        "INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)",
+    )
+    .await;
+    execute(
+        &ctx,
+        "CREATE TABLE changes (id BIGINT, name VARCHAR, flag BOOLEAN) AS VALUES (1, 'x', true)",
+    )
+    .await;
+
+    for on in [
+        "t.id = 5",
+        "t.id = s.id AND s.flag",
+        "t.id = s.id AND t.name = s.name",
+        "t.id = s.id OR t.name = s.name",
+        "t.id = s.id AND t.id = s.id",
+        "t.id = s.id + 1",
+        "t.id = t.ts",
+        "id = s.id",
+        "t.id > s.id",
+    ] {
+        let sql = format!("MERGE INTO trips t USING changes s ON {on} WHEN MATCHED THEN DELETE");
+        let err = plan_merge_into(&ctx, &sql).await.expect_err(&sql);
+        assert!(matches!(err, DataFusionError::Plan(_)), "{sql}: {err}");
+    }
+    for on in ["t.id = s.id", "s.id = t.id", "(t.id = s.id)"] {
+        let sql = format!("MERGE INTO trips t USING changes s ON {on} WHEN MATCHED THEN DELETE");
+        assert!(plan_merge_into(&ctx, &sql).await.is_ok(), "{sql}");
+    }
+}
+
+#[tokio::test]
+async fn test_explain_merge_into() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::MergeOnRead).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+
+    let plan = plan_merge_into(
+        &ctx,
+        "MERGE INTO trips t USING (SELECT 1 AS id, 'a' AS name) s ON t.id = s.id \
+         WHEN NOT MATCHED THEN INSERT (id, name, city, ts) VALUES (s.id, s.name, 'sf', 1)",
+    )
+    .await
+    .unwrap();
+    let plan = datafusion::physical_plan::displayable(plan.as_ref())
+        .indent(false)
+        .to_string();
+    assert!(
+        plan.contains("DataSinkExec: sink=HudiMergeSink: base_url=file://"),
+        "{plan}"
+    );
+    assert!(plan.contains("HashJoinExec"), "{plan}");
+    // Planning does not write.
+    assert!(read_rows(base_path).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_explain_insert_into() {
+    let dir = tempfile::tempdir().unwrap();
+    let base_path = dir.path().to_str().unwrap();
+    create_table(base_path, TableTypeValue::CopyOnWrite).await;
+    let ctx = SessionContext::new();
+    register(&ctx, base_path, &[]).await;
+
+    let plan = ctx
+        .sql("INSERT INTO trips (id, name, city, ts) VALUES (1, 'a', 'sf', 1)")
+        .await
+        .unwrap()
+        .create_physical_plan()
+        .await
+        .unwrap();
+    let plan = datafusion::physical_plan::displayable(plan.as_ref())
+        .indent(false)
+        .to_string();
+    assert!(plan.contains("DataSinkExec: sink=HudiDataSink: base_url=file://"));
+    assert!(plan.contains("write_op=Insert Into"));
+}
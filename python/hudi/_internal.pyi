@@ -47,7 +47,8 @@ class HudiReadOptions:
     from the bag.
 
     Attributes:
-        filters (List[Tuple[str, str, str]]): Column filters as ``(field, op, value)`` tuples.
+        filters (List[Tuple[str, str, str]]): Column filters as ``(field, op, value)`` tuples. Reading
+            raises ``HudiCoreError`` if a filter is a compound expression, e.g., ``OR`` or ``NOT``.
         projection (Optional[List[str]]): Column names to read. If None, all columns are read.
         hudi_options (Dict[str, str]): Per-read Hudi configs (``hoodie.*``).
     """
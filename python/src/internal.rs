@@ -36,6 +36,7 @@ use hudi::config::read::HudiReadConfig;
 use hudi::config::table::HudiTableConfig;
 use hudi::error::CoreError;
 use hudi::error::Result as HudiResult;
+use hudi::expr::filter::FilterExpr;
 use hudi::file_group::FileGroup;
 use hudi::file_group::file_slice::FileSlice;
 use hudi::file_group::reader::FileGroupReader;
@@ -160,22 +161,21 @@ impl HudiReadOptions {
     }
 
     fn __repr__(&self) -> String {
+        let filters = match filter_tuples(&self.inner.filters) {
+            Ok(tuples) => format!("{tuples:?}"),
+            Err(_) => format!("{:?}", self.inner.filters),
+        };
         format!(
-            "HudiReadOptions(filters={:?}, projection={:?}, hudi_options={:?})",
-            self.filters(),
-            self.inner.projection,
-            self.inner.hudi_options,
+            "HudiReadOptions(filters={filters}, projection={:?}, hudi_options={:?})",
+            self.inner.projection, self.inner.hudi_options,
         )
     }
 
+    /// Raises `HudiCoreError` if any filter is a compound expression, e.g.,
+    /// `OR` or `NOT`, which has no `(field, op, value)` tuple form.
     #[getter]
-    fn filters(&self) -> Vec<(String, String, String)> {
-        self.inner
-            .filters
-            .iter()
-            .cloned()
-            .map(|f| f.into())
-            .collect()
+    fn filters(&self) -> PyResult<Vec<(String, String, String)>> {
+        Ok(filter_tuples(&self.inner.filters).map_err(PythonError::from)?)
     }
 
     #[getter]
@@ -307,6 +307,21 @@ impl HudiReadOptions {
     }
 }
 
+/// Converts the filter expressions to `(field, op, value)` tuples, failing on
+/// compound expressions rather than dropping them.
+fn filter_tuples(filters: &[FilterExpr]) -> HudiResult<Vec<(String, String, String)>> {
+    filters
+        .iter()
+        .map(|expr| {
+            expr.as_filter().cloned().map(Into::into).ok_or_else(|| {
+                CoreError::Unsupported(format!(
+                    "Filter expression {expr:?} cannot be represented as a (field, op, value) tuple"
+                ))
+            })
+        })
+        .collect()
+}
+
 #[cfg(not(tarpaulin_include))]
 #[pyclass]
 pub struct HudiRecordBatchStream {
@@ -1017,7 +1032,8 @@ pub fn rt() -> &'static Runtime {
 
 #[cfg(test)]
 mod tests {
-    use super::pascal_to_screaming_snake;
+    use super::{filter_tuples, pascal_to_screaming_snake};
+    use hudi::expr::filter::{Filter, FilterExpr};
 
     #[test]
     fn pascal_to_screaming_snake_basic() {
@@ -1029,4 +1045,19 @@ mod tests {
         assert_eq!(pascal_to_screaming_snake("URLEncoded"), "URL_ENCODED");
         assert_eq!(pascal_to_screaming_snake("A"), "A");
     }
+
+    #[test]
+    fn filter_tuples_rejects_compound_expressions() {
+        let city = Filter::try_from(("city", "=", "sf")).unwrap();
+        let fare = Filter::try_from(("fare", ">", "10")).unwrap();
+        assert_eq!(
+            filter_tuples(&[city.clone().into()]).unwrap(),
+            [("city".to_string(), "=".to_string(), "sf".to_string())]
+        );
+
+        let or = FilterExpr::Or(vec![city.clone().into(), fare.into()]);
+        assert!(filter_tuples(&[city.clone().into(), or]).is_err());
+        let not = FilterExpr::Not(Box::new(city.into()));
+        assert!(filter_tuples(&[not]).is_err());
+    }
 }